@@ -5,10 +5,12 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::any::Any;
 use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Write;
+use std::sync::Arc;
 
 use lsp_types::DiagnosticSeverity;
 use lsp_types::DiagnosticTag;
@@ -52,7 +54,7 @@ pub fn diagnostics_result<T>(result: T, diagnostics: Diagnostics) -> Diagnostics
 /// - `location` is different from LSP in that it's a file + span instead of
 ///   just a span.
 /// - Unused fields are omitted.
-#[derive(fmt::Debug)]
+#[derive(fmt::Debug, Clone)]
 pub struct Diagnostic(Box<DiagnosticData>);
 
 impl Diagnostic {
@@ -63,13 +65,14 @@ impl Diagnostic {
         tags: Vec<DiagnosticTag>,
     ) -> Self {
         Self(Box::new(DiagnosticData {
-            message: Box::new(message),
+            message: Arc::new(message),
             location,
             related_information: Vec::new(),
             tags,
             severity,
             data: Vec::new(),
             machine_readable: BTreeMap::new(),
+            occurrences: 1,
         }))
     }
 
@@ -85,15 +88,16 @@ impl Diagnostic {
         message: T,
         location: Location,
     ) -> Self {
-        let data = message.get_data();
+        let data = message.get_data().into_iter().map(Arc::from).collect();
         Self(Box::new(DiagnosticData {
-            message: Box::new(message),
+            message: Arc::new(message),
             location,
             tags: Vec::new(),
             severity: DiagnosticSeverity::ERROR,
             related_information: Vec::new(),
             data,
             machine_readable: BTreeMap::new(),
+            occurrences: 1,
         }))
     }
 
@@ -136,7 +140,7 @@ impl Diagnostic {
         self.0
             .related_information
             .push(DiagnosticRelatedInformation {
-                message: Box::new(message),
+                message: Arc::new(message),
                 location,
             });
         self
@@ -170,6 +174,12 @@ impl Diagnostic {
         &self.0.message
     }
 
+    /// The message as `Any`, to downcast it to the type of the message, e.g.
+    /// to find the code of a validation message.
+    pub fn message_as_any(&self) -> &dyn Any {
+        (*self.0.message).as_any()
+    }
+
     pub fn location(&self) -> Location {
         self.0.location
     }
@@ -221,6 +231,17 @@ impl Diagnostic {
         &self.0.related_information
     }
 
+    /// The number of times this diagnostic was reported, e.g. once per
+    /// operation or project that includes the fragment it is in, when the
+    /// identical diagnostics are reported once.
+    pub fn occurrences(&self) -> usize {
+        self.0.occurrences
+    }
+
+    pub fn set_occurrences(&mut self, occurrences: usize) {
+        self.0.occurrences = occurrences;
+    }
+
     pub fn print_without_source(&self) -> String {
         let mut result = String::new();
         writeln!(
@@ -261,10 +282,10 @@ where
 {
 }
 
-#[derive(fmt::Debug)]
+#[derive(fmt::Debug, Clone)]
 struct DiagnosticData {
     /// Human readable error message.
-    message: Box<dyn DiagnosticDisplay>,
+    message: Arc<dyn DiagnosticDisplay>,
 
     /// The primary location of this diagnostic.
     location: Location,
@@ -280,17 +301,20 @@ struct DiagnosticData {
     /// A list with data that can be passed to the code actions
     /// `data` is used in the LSP protocol:
     /// @see https://microsoft.github.io/language-server-protocol/specifications/specification-current/#diagnostic
-    data: Vec<Box<dyn DiagnosticDisplay>>,
+    data: Vec<Arc<dyn DiagnosticDisplay>>,
 
     /// Metadata with (K,V) are strings that can read by machine
     machine_readable: BTreeMap<String, String>,
+
+    /// The number of identical diagnostics this one stands for.
+    occurrences: usize,
 }
 
 /// Secondary locations attached to a diagnostic.
-#[derive(fmt::Debug)]
+#[derive(fmt::Debug, Clone)]
 pub struct DiagnosticRelatedInformation {
     /// The message of this related diagnostic information.
-    pub message: Box<dyn DiagnosticDisplay>,
+    pub message: Arc<dyn DiagnosticDisplay>,
 
     /// The location of this related diagnostic information.
     pub location: Location,
@@ -302,11 +326,20 @@ pub trait WithDiagnosticData {
 
 /// Trait for diagnostic messages to allow structs that capture
 /// some data and can lazily convert it to a message.
-pub trait DiagnosticDisplay: fmt::Debug + fmt::Display + Send + Sync {}
+pub trait DiagnosticDisplay: fmt::Debug + fmt::Display + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+}
 
 /// Automatically implement the trait if constraints are met, so that
 /// implementors don't need to.
-impl<T> DiagnosticDisplay for T where T: fmt::Debug + fmt::Display + Send + Sync {}
+impl<T> DiagnosticDisplay for T
+where
+    T: fmt::Debug + fmt::Display + Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
 
 impl From<Diagnostic> for Diagnostics {
     fn from(diagnostic: Diagnostic) -> Self {
@@ -0,0 +1,62 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+/// A stable code of a kind of diagnostic message, like `RELAY-1007`, which
+/// doesn't change with the wording of the message, e.g. to search for it or
+/// to suppress it. Codes are never reused or renumbered: new messages get the
+/// next free code.
+#[derive(Debug)]
+pub struct DiagnosticCode {
+    pub code: &'static str,
+    /// The name of the variant of the message enum.
+    pub name: &'static str,
+    /// What the message means and how it is typically fixed.
+    pub explanation: &'static str,
+}
+
+/// The documentation of a code, on the page that lists all of them.
+pub fn diagnostic_code_url(code: &str) -> String {
+    format!(
+        "https://relay.dev/docs/error-reference/error-codes/#{}",
+        code.to_lowercase()
+    )
+}
+
+/// Assigns a code and an explanation to each variant of a message enum: it
+/// defines a static list of the codes, and a `code()` method that returns
+/// the code of a message. The match of `code()` is exhaustive, so that each
+/// new variant must be given a code.
+///
+/// ```ignore
+/// diagnostic_codes! {
+///     VALIDATION_MESSAGE_CODES for ValidationMessage {
+///         DuplicateDefinition => "RELAY-1001": "Two definitions have the same name.",
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! diagnostic_codes {
+    ($codes:ident for $message:ident {
+        $($variant:ident => $code:literal: $explanation:literal,)*
+    }) => {
+        pub static $codes: &[$crate::DiagnosticCode] = &[
+            $($crate::DiagnosticCode {
+                code: $code,
+                name: stringify!($variant),
+                explanation: $explanation,
+            },)*
+        ];
+
+        impl $message {
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $(Self::$variant { .. } => $code,)*
+                }
+            }
+        }
+    };
+}
@@ -60,6 +60,12 @@ pub struct FeatureFlags {
     /// Create normalization nodes for client edges to client objects
     #[serde(default)]
     pub emit_normalization_nodes_for_client_edges: bool,
+
+    /// Enable the proposed syntax for the arguments of fragments,
+    /// `fragment Foo($arg: Int) on Type`, as an alternative to
+    /// `@argumentDefinitions`.
+    #[serde(default)]
+    pub enable_fragment_variable_definitions: bool,
 }
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
@@ -12,12 +12,14 @@
 mod console_logger;
 mod diagnostic;
 mod diagnostic_check;
+mod diagnostic_code;
 mod feature_flags;
 mod location;
 mod named_item;
 mod perf_logger;
 mod pointer_address;
 mod rollout;
+mod schema_coordinate;
 mod span;
 pub mod sync;
 mod text_source;
@@ -38,6 +40,8 @@ pub use diagnostic_check::escalate_and_check;
 pub use diagnostic_check::CriticalDiagnostics;
 pub use diagnostic_check::DiagnosticCheck;
 pub use diagnostic_check::StableDiagnostics;
+pub use diagnostic_code::diagnostic_code_url;
+pub use diagnostic_code::DiagnosticCode;
 pub use feature_flags::FeatureFlag;
 pub use feature_flags::FeatureFlags;
 pub use location::Location;
@@ -60,5 +64,7 @@ pub use perf_logger::PerfLogEvent;
 pub use perf_logger::PerfLogger;
 pub use pointer_address::PointerAddress;
 pub use rollout::Rollout;
+pub use schema_coordinate::SchemaCoordinate;
+pub use schema_coordinate::SchemaCoordinateParseError;
 pub use span::Span;
 pub use text_source::TextSource;
@@ -12,13 +12,15 @@ use std::path::PathBuf;
 use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use intern::Lookup;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::span::Span;
 
 /// The location of a source. Could be a standalone file (e.g. test.graphql),
 /// an embedded source (GraphQL tag in a JS file) or generated code without a
 /// location.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub enum SourceLocationKey {
     /// A source embedded within a file. The 0-based index is an index into the
     /// embedded sources. E.g. the second graphql tag has index 1.
@@ -72,7 +74,7 @@ impl SourceLocationKey {
 
 /// An absolute source location describing both the file and position (span)
 /// with that file.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub struct Location {
     /// The source containing this location (e.g. embedded or standalone file).
     source_location: SourceLocationKey,
@@ -121,7 +123,7 @@ impl Location {
     }
 }
 
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WithLocation<T> {
     pub location: Location,
     pub item: T,
@@ -63,7 +63,18 @@ impl FromStr for DirectiveName {
 }
 
 impl_lookup!(DirectiveName);
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize
+)]
 pub struct ArgumentName(pub StringKey);
 
 impl fmt::Display for ArgumentName {
@@ -94,7 +105,18 @@ impl fmt::Display for ScalarName {
     }
 }
 impl_lookup!(ArgumentName);
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct ObjectName(pub StringKey);
 
 impl fmt::Display for ObjectName {
@@ -105,7 +127,18 @@ impl fmt::Display for ObjectName {
 
 impl_lookup!(ObjectName);
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct InputObjectName(pub StringKey);
 
 impl fmt::Display for InputObjectName {
@@ -116,7 +149,18 @@ impl fmt::Display for InputObjectName {
 
 impl_lookup!(InputObjectName);
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct EnumName(pub StringKey);
 
 impl fmt::Display for EnumName {
@@ -126,7 +170,18 @@ impl fmt::Display for EnumName {
 }
 impl_lookup!(EnumName);
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct InterfaceName(pub StringKey);
 
 impl fmt::Display for InterfaceName {
@@ -0,0 +1,265 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Schema coordinates, the textual references to the elements of a schema,
+//! such as `User.name` or `@include(if:)`, used in configs and by tooling.
+
+use std::fmt;
+use std::str::FromStr;
+
+use intern::string_key::Intern;
+use intern::string_key::StringKey;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::ArgumentName;
+use crate::DirectiveName;
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize
+)]
+#[serde(try_from = "String", into = "String")]
+pub enum SchemaCoordinate {
+    /// `Type`
+    Type(StringKey),
+    /// `Type.member`, a field of an object, interface or input object, or a
+    /// value of an enum
+    Member {
+        type_name: StringKey,
+        member_name: StringKey,
+    },
+    /// `Type.field(argument:)`
+    Argument {
+        type_name: StringKey,
+        field_name: StringKey,
+        argument_name: ArgumentName,
+    },
+    /// `@directive`
+    Directive(DirectiveName),
+    /// `@directive(argument:)`
+    DirectiveArgument {
+        directive_name: DirectiveName,
+        argument_name: ArgumentName,
+    },
+}
+
+impl SchemaCoordinate {
+    /// The name of the type the coordinate is in, if it isn't a directive.
+    pub fn type_name(&self) -> Option<StringKey> {
+        match self {
+            SchemaCoordinate::Type(type_name)
+            | SchemaCoordinate::Member { type_name, .. }
+            | SchemaCoordinate::Argument { type_name, .. } => Some(*type_name),
+            SchemaCoordinate::Directive(_) | SchemaCoordinate::DirectiveArgument { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for SchemaCoordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaCoordinate::Type(type_name) => write!(f, "{}", type_name),
+            SchemaCoordinate::Member {
+                type_name,
+                member_name,
+            } => write!(f, "{}.{}", type_name, member_name),
+            SchemaCoordinate::Argument {
+                type_name,
+                field_name,
+                argument_name,
+            } => write!(f, "{}.{}({}:)", type_name, field_name, argument_name),
+            SchemaCoordinate::Directive(directive_name) => write!(f, "@{}", directive_name),
+            SchemaCoordinate::DirectiveArgument {
+                directive_name,
+                argument_name,
+            } => write!(f, "@{}({}:)", directive_name, argument_name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaCoordinateParseError {
+    pub coordinate: String,
+}
+
+impl fmt::Display for SchemaCoordinateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expected a schema coordinate like `User`, `User.name`, `User.name(format:)`, `@include` or `@include(if:)`, got `{}`.",
+            self.coordinate
+        )
+    }
+}
+
+impl std::error::Error for SchemaCoordinateParseError {}
+
+impl FromStr for SchemaCoordinate {
+    type Err = SchemaCoordinateParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        parse_schema_coordinate(source.trim()).ok_or_else(|| SchemaCoordinateParseError {
+            coordinate: source.to_string(),
+        })
+    }
+}
+
+impl TryFrom<String> for SchemaCoordinate {
+    type Error = SchemaCoordinateParseError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        source.parse()
+    }
+}
+
+impl From<SchemaCoordinate> for String {
+    fn from(coordinate: SchemaCoordinate) -> Self {
+        coordinate.to_string()
+    }
+}
+
+fn parse_schema_coordinate(source: &str) -> Option<SchemaCoordinate> {
+    let (is_directive, source) = match source.strip_prefix('@') {
+        Some(source) => (true, source),
+        None => (false, source),
+    };
+    let (path, argument_name) = match source.split_once('(') {
+        Some((path, argument)) => (
+            path,
+            Some(ArgumentName(parse_name(argument.strip_suffix(":)")?)?)),
+        ),
+        None => (source, None),
+    };
+    if is_directive {
+        let directive_name = DirectiveName(parse_name(path)?);
+        return Some(match argument_name {
+            Some(argument_name) => SchemaCoordinate::DirectiveArgument {
+                directive_name,
+                argument_name,
+            },
+            None => SchemaCoordinate::Directive(directive_name),
+        });
+    }
+    match (path.split_once('.'), argument_name) {
+        (None, None) => Some(SchemaCoordinate::Type(parse_name(path)?)),
+        (Some((type_name, member_name)), None) => Some(SchemaCoordinate::Member {
+            type_name: parse_name(type_name)?,
+            member_name: parse_name(member_name)?,
+        }),
+        (Some((type_name, field_name)), Some(argument_name)) => Some(SchemaCoordinate::Argument {
+            type_name: parse_name(type_name)?,
+            field_name: parse_name(field_name)?,
+            argument_name,
+        }),
+        (None, Some(_)) => None,
+    }
+}
+
+/// A GraphQL name: `/[_A-Za-z][_0-9A-Za-z]*/`
+fn parse_name(name: &str) -> Option<StringKey> {
+    let mut chars = name.chars();
+    let is_name = chars
+        .next()
+        .map_or(false, |c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric());
+    if is_name { Some(name.intern()) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Option<SchemaCoordinate> {
+        source.parse().ok()
+    }
+
+    #[test]
+    fn parses_coordinates() {
+        assert_eq!(parse("User"), Some(SchemaCoordinate::Type("User".intern())));
+        assert_eq!(
+            parse(" User.name "),
+            Some(SchemaCoordinate::Member {
+                type_name: "User".intern(),
+                member_name: "name".intern(),
+            })
+        );
+        assert_eq!(
+            parse("User.profile_picture(scale:)"),
+            Some(SchemaCoordinate::Argument {
+                type_name: "User".intern(),
+                field_name: "profile_picture".intern(),
+                argument_name: ArgumentName("scale".intern()),
+            })
+        );
+        assert_eq!(
+            parse("@include"),
+            Some(SchemaCoordinate::Directive(DirectiveName(
+                "include".intern()
+            )))
+        );
+        assert_eq!(
+            parse("@include(if:)"),
+            Some(SchemaCoordinate::DirectiveArgument {
+                directive_name: DirectiveName("include".intern()),
+                argument_name: ArgumentName("if".intern()),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_coordinates() {
+        for source in [
+            "",
+            "1User",
+            "User.",
+            "User.name.first",
+            "User(name:)",
+            "User.name(format)",
+            "User.name(format:",
+            "User.name(:)",
+            "User.name (format:)",
+            "@",
+            "@include.if",
+            "@@include",
+        ] {
+            assert_eq!(parse(source), None, "{}", source);
+        }
+    }
+
+    #[test]
+    fn prints_coordinates() {
+        for source in [
+            "User",
+            "User.name",
+            "User.name(format:)",
+            "@include",
+            "@include(if:)",
+        ] {
+            assert_eq!(parse(source).unwrap().to_string(), source);
+        }
+    }
+
+    #[test]
+    fn deserializes_from_strings() {
+        let coordinates: Vec<SchemaCoordinate> =
+            serde_json::from_str(r#"["User.name", "@include(if:)"]"#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&coordinates).unwrap(),
+            r#"["User.name","@include(if:)"]"#
+        );
+        assert!(serde_json::from_str::<SchemaCoordinate>(r#""User.""#).is_err());
+    }
+}
@@ -7,7 +7,10 @@
 
 use std::fmt;
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub struct Span {
     pub start: u32,
     pub end: u32,
@@ -40,66 +40,49 @@ impl TextSource {
     // Generate an LSP Range for this GraphQL source string. This provides the absolute
     // range of the entire GraphQL string within the source file.
     pub fn to_range(&self) -> Range {
-        // Zero-indexed line offset in the document
-        let mut line = self.line_index;
-        // Zero-indexed character offset on the line
-        let mut character = self.column_index;
-        let mut chars = self.text.chars().peekable();
-        let start_position = Position::new(line as u32, character as u32);
-        while let Some(chr) = chars.next() {
-            let is_newline = match chr {
-                // Line terminators: https://www.ecma-international.org/ecma-262/#sec-line-terminators
-                '\u{000A}' | '\u{000D}' | '\u{2028}' | '\u{2029}' => {
-                    !matches!((chr, chars.peek()), ('\u{000D}', Some('\u{000D}')))
-                }
-                _ => false,
-            };
+        let [start, end] = self.positions_at([0, self.text.len()]);
+        Range::new(start, end)
+    }
 
-            if is_newline {
-                // New line, increment the line offset and reset the
-                // character offset.
-                line += 1;
-                character = 0;
-            }
-            // Make sure to only increment the character offset if this
-            // isn't a newline.
-            if !is_newline {
-                character += 1;
-            }
-        }
-        let end_position = Position::new(line as u32, character as u32);
-        Range::new(start_position, end_position)
+    /// The range in the file of a span of the text.
+    pub fn to_span_range(&self, span: Span) -> Range {
+        let [start, end] = self.positions_at([span.start as usize, span.end as usize]);
+        Range::new(start, end)
+    }
+
+    /// The position in the file of a byte offset into the text, like the
+    /// offsets of spans. Lines are zero-indexed, and columns count characters
+    /// like the extraction of the text from the file does.
+    pub fn position_at(&self, offset: usize) -> Position {
+        let [position] = self.positions_at([offset]);
+        position
     }
 
-    pub fn to_span_range(&self, span: Span) -> lsp_types::Range {
-        let start = span.start as usize;
-        let end = span.end as usize;
+    /// Maps ascending byte offsets to positions, in a single pass over the
+    /// text. Offsets past the end of the text map to its end.
+    fn positions_at<const N: usize>(&self, offsets: [usize; N]) -> [Position; N] {
+        let mut positions = [Position::default(); N];
+        let mut mapped = 0;
         // Zero-indexed line offset in the document
         let mut line = self.line_index;
         // Zero-indexed character offset on the line
         let mut character = self.column_index;
-        let mut start_position = lsp_types::Position::default();
-        let mut end_position = lsp_types::Position::default();
-        let mut chars = self.text.chars().enumerate().peekable();
-
+        let mut chars = self.text.char_indices().peekable();
         while let Some((index, chr)) = chars.next() {
-            if index == start {
-                start_position = lsp_types::Position::new(line as u32, character as u32);
+            while mapped < N && offsets[mapped] <= index {
+                positions[mapped] = Position::new(line as u32, character as u32);
+                mapped += 1;
             }
-            if index == end {
-                end_position = lsp_types::Position::new(line as u32, character as u32);
-                break;
+            if mapped == N {
+                return positions;
             }
-
             let is_newline = match chr {
                 // Line terminators: https://www.ecma-international.org/ecma-262/#sec-line-terminators
-                '\u{000A}' | '\u{000D}' | '\u{2028}' | '\u{2029}' => {
-                    // <CLRF>
-                    !matches!((chr, chars.peek()), ('\u{000D}', Some((_, '\u{000D}'))))
-                }
+                '\u{000A}' | '\u{2028}' | '\u{2029}' => true,
+                // <CRLF> is a single line terminator
+                '\u{000D}' => !matches!(chars.peek(), Some((_, '\u{000A}'))),
                 _ => false,
             };
-
             if is_newline {
                 // New line, increment the line offset and reset the
                 // character offset.
@@ -109,7 +92,10 @@ impl TextSource {
                 character += 1;
             }
         }
-        lsp_types::Range::new(start_position, end_position)
+        for position in &mut positions[mapped..] {
+            *position = Position::new(line as u32, character as u32);
+        }
+        positions
     }
 }
 
@@ -161,4 +147,37 @@ fn foo() {
         assert_eq!(range.start, lsp_types::Position::new(2, 4));
         assert_eq!(range.end, lsp_types::Position::new(2, 9));
     }
+
+    #[test]
+    fn to_range_embedded_test() {
+        let text_source = TextSource::new("\n  fragment Foo on User {\n    id\n  }\n", 3, 12);
+        assert_eq!(
+            text_source.to_range(),
+            lsp_types::Range::new(
+                lsp_types::Position::new(3, 12),
+                lsp_types::Position::new(7, 0)
+            )
+        );
+        let range = text_source.to_span_range(Span::new(30, 32));
+        assert_eq!(range.start, lsp_types::Position::new(5, 4));
+        assert_eq!(range.end, lsp_types::Position::new(5, 6));
+    }
+
+    #[test]
+    fn to_range_non_ascii_test() {
+        // Spans are byte offsets, while columns count characters
+        let text_source = TextSource::new("\"héllo 👋\" name", 0, 0);
+        let range = text_source.to_span_range(Span::new(14, 18));
+        assert_eq!(range.start, lsp_types::Position::new(0, 10));
+        assert_eq!(range.end, lsp_types::Position::new(0, 14));
+    }
+
+    #[test]
+    fn to_range_crlf_test() {
+        let text_source = TextSource::new("query {\r\n  me\r\n}", 0, 0);
+        let range = text_source.to_span_range(Span::new(11, 13));
+        assert_eq!(range.start, lsp_types::Position::new(1, 2));
+        assert_eq!(range.end, lsp_types::Position::new(1, 4));
+        assert_eq!(text_source.position_at(17), lsp_types::Position::new(2, 1));
+    }
 }
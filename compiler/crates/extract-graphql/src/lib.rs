@@ -10,11 +10,16 @@
 #![deny(clippy::all)]
 
 use std::iter::Peekable;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
 use std::str::CharIndices;
 
+use common::Span;
 use common::TextSource;
 use docblock_syntax::DocblockSource;
 use graphql_syntax::GraphQLSource;
+use memchr::memmem;
 
 #[derive(Clone)]
 pub enum JavaScriptSourceFeature {
@@ -40,6 +45,7 @@ impl JavaScriptSourceFeature {
 
 /// A wrapper around a peekable char iterator that tracks
 /// the column and line indicies.
+#[derive(Clone)]
 pub struct CharReader<'a> {
     chars: Peekable<CharIndices<'a>>,
     line_index: usize,
@@ -85,143 +91,508 @@ impl<'a> Iterator for CharReader<'a> {
     }
 }
 
+/// Which template literals contain GraphQL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtractOptions {
+    /// The tags of the template literals containing GraphQL, which may be
+    /// members of an import, e.g. `gql` or `graphql.experimental`.
+    pub tag_names: Vec<String>,
+    /// Also extract the untagged template literals following a
+    /// `/* GraphQL */` comment.
+    pub comment_annotated_templates: bool,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            tag_names: vec!["graphql".to_string()],
+            comment_annotated_templates: false,
+        }
+    }
+}
+
+impl ExtractOptions {
+    /// Whether `input` could contain anything to extract. This is a SIMD
+    /// accelerated substring search for the tags, which is much cheaper than
+    /// scanning the JavaScript and rules out most files of a project.
+    fn may_match(&self, input: &str) -> bool {
+        let contains = |needle: &str| memmem::find(input.as_bytes(), needle.as_bytes()).is_some();
+        contains("@RelayResolver")
+            || self
+                .tag_names
+                .iter()
+                .any(|tag_name| contains(tag_name.as_str()))
+            || (self.comment_annotated_templates && contains_graphql_annotation(input))
+    }
+
+    /// Whether `tag` is one of the tags, or a member named like one of them,
+    /// e.g. `Relay.graphql`.
+    fn is_tag(&self, tag: &str) -> bool {
+        self.tag_names.iter().any(|tag_name| {
+            tag.strip_suffix(tag_name.as_str())
+                .map_or(false, |object| object.is_empty() || object.ends_with('.'))
+        })
+    }
+}
+
 /// Extract graphql`text` literals and @RelayResolver comments from JS-like code.
 // This should work for Flow or TypeScript alike.
 pub fn extract(input: &str) -> Vec<JavaScriptSourceFeature> {
-    let mut res = Vec::new();
-    if !input.contains("graphql") && !input.contains("@RelayResolver") {
-        return res;
+    extract_with_options(input, &ExtractOptions::default())
+}
+
+/// Extract the template literals tagged with one of the configured tags, or
+/// annotated with a `/* GraphQL */` comment, and @RelayResolver comments.
+///
+/// The code is scanned token by token, so that the text of strings, comments,
+/// regular expressions and template literals is never mistaken for code,
+/// while the `${}` placeholders of template literals are scanned as code.
+pub fn extract_with_options(input: &str, options: &ExtractOptions) -> Vec<JavaScriptSourceFeature> {
+    if options.may_match(input) {
+        scan_javascript(input, options)
+    } else {
+        vec![]
     }
+}
+
+/// Whether `input` contains a `/* GraphQL */` comment, in any case, that
+/// could annotate a template literal.
+fn contains_graphql_annotation(input: &str) -> bool {
+    memmem::find_iter(input.as_bytes(), b"/*").any(|start| {
+        let text = &input[start + 2..];
+        matches!(
+            memmem::find(text.as_bytes(), b"*/"),
+            Some(end) if text[..end].trim().eq_ignore_ascii_case("graphql")
+        )
+    })
+}
+
+/// Scans all of `input`, without the pre-scan of `ExtractOptions::may_match`.
+fn scan_javascript(input: &str, options: &ExtractOptions) -> Vec<JavaScriptSourceFeature> {
+    let mut res = Vec::new();
     let mut it = CharReader::new(input);
-    'code: while let Some((i, c)) = it.next() {
+    // The brace depth of the code around each placeholder being scanned
+    let mut placeholders: Vec<usize> = Vec::new();
+    let mut brace_depth = 0;
+    // Whether a `/` would start a regular expression rather than a division
+    let mut regex_allowed = true;
+    while let Some((i, c)) = it.next() {
         match c {
-            'g' => {
-                for expected in ['r', 'a', 'p', 'h', 'q', 'l'] {
-                    if let Some((_, c)) = it.next() {
-                        if c != expected {
-                            consume_identifier(&mut it);
-                            continue 'code;
-                        }
+            'a'..='z' | 'A'..='Z' | '_' | '$' => {
+                let tag = &input[i..consume_tag(input, &mut it)];
+                if options.is_tag(tag) {
+                    if let Some(graphql_source) = extract_template_literal(input, &mut it) {
+                        res.push(JavaScriptSourceFeature::GraphQL(graphql_source));
                     }
                 }
-
-                let mut whitespace_num: usize = 0;
-
-                loop {
-                    if let Some((_, c)) = it.next() {
-                        match c {
-                            '`' => {
-                                break;
-                            }
-                            ' ' | '\n' | '\r' | '\t' => {
-                                whitespace_num += 1;
-                            }
-                            _ => {
-                                consume_identifier(&mut it);
-                                continue 'code;
-                            }
-                        }
+                regex_allowed = is_keyword_before_expression(tag);
+            }
+            '`' => {
+                if let TemplateTextEnd::Placeholder = consume_template_text(&mut it) {
+                    placeholders.push(brace_depth);
+                    brace_depth = 0;
+                    regex_allowed = true;
+                } else {
+                    regex_allowed = false;
+                }
+            }
+            '{' => {
+                brace_depth += 1;
+                regex_allowed = true;
+            }
+            '}' => {
+                if brace_depth > 0 {
+                    brace_depth -= 1;
+                    regex_allowed = false;
+                } else if let Some(outer_brace_depth) = placeholders.pop() {
+                    // The end of a placeholder, back in the text of its template
+                    if let TemplateTextEnd::Placeholder = consume_template_text(&mut it) {
+                        placeholders.push(outer_brace_depth);
+                        regex_allowed = true;
+                    } else {
+                        brace_depth = outer_brace_depth;
+                        regex_allowed = false;
                     }
                 }
-                let start = i;
-                let line_index = it.line_index;
-                let column_index = it.column_index;
-                let mut has_visited_first_char = false;
-                for (i, c) in &mut it {
-                    match c {
-                        '`' => {
-                            let end = i;
-                            let text = &input[start + (8 + whitespace_num)..end];
-                            res.push(JavaScriptSourceFeature::GraphQL(GraphQLSource::new(
+            }
+            '"' | '\'' => {
+                consume_string(&mut it, c);
+                regex_allowed = false;
+            }
+            '/' => match it.chars.peek() {
+                Some((_, '/')) => {
+                    consume_line_comment(&mut it);
+                }
+                Some((_, '*')) => {
+                    it.next();
+                    let line_index = it.line_index;
+                    let column_index = it.column_index;
+                    let mut is_graphql_annotation = false;
+                    if let Some(end) = consume_block_comment(&mut it) {
+                        let text = &input[i + 2..end - 1];
+                        if text.contains("@RelayResolver") {
+                            res.push(JavaScriptSourceFeature::Docblock(DocblockSource::new(
                                 text,
                                 line_index,
                                 column_index,
                             )));
-                            continue 'code;
-                        }
-                        ' ' | '\n' | '\r' | '\t' => {}
-                        'a'..='z' | 'A'..='Z' | '#' => {
-                            if !has_visited_first_char {
-                                has_visited_first_char = true;
-                            }
+                        } else {
+                            is_graphql_annotation = options.comment_annotated_templates
+                                && text.trim().eq_ignore_ascii_case("graphql");
                         }
-                        _ => {
-                            if !has_visited_first_char {
-                                continue 'code;
-                            }
+                    }
+                    if is_graphql_annotation {
+                        if let Some(graphql_source) = extract_template_literal(input, &mut it) {
+                            res.push(JavaScriptSourceFeature::GraphQL(graphql_source));
                         }
                     }
                 }
+                _ => {
+                    regex_allowed = !(regex_allowed && consume_regex(&mut it));
+                }
+            },
+            // A slash after `<` or `>` is more likely part of JSX, like `</a>`
+            ')' | ']' | '<' | '>' => regex_allowed = false,
+            c if c.is_whitespace() => {}
+            c if c.is_alphanumeric() => regex_allowed = false,
+            _ => regex_allowed = true,
+        };
+    }
+    res
+}
+
+/// Consumes the rest of a possible tag, an identifier or a member expression
+/// like `graphql.experimental`, and returns its end.
+fn consume_tag(input: &str, it: &mut CharReader<'_>) -> usize {
+    while let Some(&(i, c)) = it.chars.peek() {
+        match c {
+            'a'..='z' | 'A'..='Z' | '_' | '$' | '0'..='9' | '.' => {
+                it.next();
             }
-            'a'..='z' | 'A'..='Z' | '_' => {
-                consume_identifier(&mut it);
-            }
-            // Skip over template literals. Unfortunately, this isn't enough to
-            // deal with nested template literals and runs a risk of skipping
-            // over too much code -- so it is disabled.
-            //   '`' => {
-            //       while let Some((_, c)) = it.next() {
-            //           match c {
-            //               '`' => {
-            //                   continue 'code;
-            //               }
-            //               '\\' => {
-            //                   it.next();
-            //               }
-            //               _ => {}
-            //           }
-            //       }
-            //   }
-            '"' => consume_string(&mut it, '"'),
-            '\'' => consume_string(&mut it, '\''),
-            '/' => {
-                match it.next() {
-                    Some((_, '/')) => {
-                        consume_line_comment(&mut it);
-                    }
-                    Some((_, '*')) => {
-                        let start = i;
-                        let line_index = it.line_index;
-                        let column_index = it.column_index;
-                        let mut prev_c = ' '; // arbitrary character other than *
-                        let mut first = true;
-                        for (i, c) in &mut it {
-                            // Hack for template literals containing /*, see D21256605:
-                            if first && c == '`' {
-                                break;
-                            }
-                            first = false;
-                            if prev_c == '*' && c == '/' {
-                                let end = i;
-                                let text = &input[start + 2..end - 1];
-                                if text.contains("@RelayResolver") {
-                                    res.push(JavaScriptSourceFeature::Docblock(
-                                        DocblockSource::new(text, line_index, column_index),
-                                    ));
-                                }
-                                continue 'code;
-                            }
-                            prev_c = c;
-                        }
-                    }
-                    _ => {}
+            _ => return i,
+        }
+    }
+    input.len()
+}
+
+/// Whether an expression, and so a regular expression rather than a
+/// division, may follow the keyword.
+fn is_keyword_before_expression(identifier: &str) -> bool {
+    matches!(
+        identifier,
+        "await"
+            | "case"
+            | "delete"
+            | "do"
+            | "else"
+            | "in"
+            | "instanceof"
+            | "new"
+            | "of"
+            | "return"
+            | "throw"
+            | "typeof"
+            | "void"
+            | "yield"
+    )
+}
+
+/// Extracts the template literal following a tag or a `/* GraphQL */`
+/// comment, if any. Template literals that don't start like GraphQL, e.g.
+/// with a `${}` placeholder, are skipped.
+fn extract_template_literal(input: &str, it: &mut CharReader<'_>) -> Option<GraphQLSource> {
+    let start = loop {
+        match it.chars.peek().copied() {
+            Some((_, ' ' | '\n' | '\r' | '\t')) => {
+                it.next();
+            }
+            Some((i, '`')) => {
+                it.next();
+                break i + 1;
+            }
+            _ => return None,
+        }
+    };
+    let line_index = it.line_index;
+    let column_index = it.column_index;
+    let end = consume_template(it)?;
+    let text = &input[start..end];
+    let starts_like_graphql = text
+        .trim_start()
+        .chars()
+        .next()
+        .map_or(true, |c| c.is_ascii_alphabetic() || c == '#');
+    starts_like_graphql.then(|| GraphQLSource::new(text, line_index, column_index))
+}
+
+/// Where the text of a template literal ends.
+enum TemplateTextEnd {
+    /// The closing backtick, at this index
+    Backtick(usize),
+    /// The `${` of a placeholder
+    Placeholder,
+    /// An unterminated template literal
+    EndOfInput,
+}
+
+/// Consumes the text of a template literal, after its opening backtick or
+/// the closing brace of a placeholder.
+fn consume_template_text(it: &mut CharReader<'_>) -> TemplateTextEnd {
+    while let Some((i, c)) = it.next() {
+        match c {
+            '`' => return TemplateTextEnd::Backtick(i),
+            '\\' => {
+                it.next();
+            }
+            '$' if matches!(it.chars.peek(), Some((_, '{'))) => {
+                it.next();
+                return TemplateTextEnd::Placeholder;
+            }
+            _ => {}
+        }
+    }
+    TemplateTextEnd::EndOfInput
+}
+
+/// Consumes a whole template literal after its opening backtick, including
+/// the template literals nested in its placeholders, and returns the index of
+/// its closing backtick.
+fn consume_template(it: &mut CharReader<'_>) -> Option<usize> {
+    loop {
+        match consume_template_text(it) {
+            TemplateTextEnd::Backtick(i) => return Some(i),
+            TemplateTextEnd::Placeholder => consume_placeholder(it),
+            TemplateTextEnd::EndOfInput => return None,
+        }
+    }
+}
+
+/// Consumes the code of a placeholder, up to its closing brace.
+fn consume_placeholder(it: &mut CharReader<'_>) {
+    let mut brace_depth = 0;
+    while let Some((_, c)) = it.next() {
+        match c {
+            '{' => brace_depth += 1,
+            '}' => {
+                if brace_depth == 0 {
+                    return;
                 }
+                brace_depth -= 1;
+            }
+            '`' => {
+                consume_template(it);
             }
+            '"' | '\'' => consume_string(it, c),
+            '/' => match it.chars.peek() {
+                Some((_, '/')) => consume_line_comment(it),
+                Some((_, '*')) => {
+                    it.next();
+                    consume_block_comment(it);
+                }
+                _ => {}
+            },
             _ => {}
+        }
+    }
+}
+
+/// Extract graphql`text` literals and @RelayResolver comments from a file,
+/// depending on its extension: only the `<script>` blocks of Vue and Svelte
+/// single-file components contain code, and standalone GraphQL documents are
+/// GraphQL as a whole.
+pub fn extract_from_path(path: &Path, input: &str) -> Vec<JavaScriptSourceFeature> {
+    extract_from_path_with_options(path, input, &ExtractOptions::default())
+}
+
+/// Same as `extract_from_path`, for the template literals described by `options`.
+pub fn extract_from_path_with_options(
+    path: &Path,
+    input: &str,
+    options: &ExtractOptions,
+) -> Vec<JavaScriptSourceFeature> {
+    try_extract_from_path_with_options(path, input, options).unwrap_or_default()
+}
+
+/// Same as `extract_from_path_with_options`, but returns `None` for the
+/// files that were skipped without being scanned, because they can't contain
+/// any of the tags or annotations described by `options`.
+pub fn try_extract_from_path_with_options(
+    path: &Path,
+    input: &str,
+    options: &ExtractOptions,
+) -> Option<Vec<JavaScriptSourceFeature>> {
+    if is_standalone_document(path) {
+        Some(if input.trim().is_empty() {
+            vec![]
+        } else {
+            vec![JavaScriptSourceFeature::GraphQL(GraphQLSource::new(
+                input, 0, 0,
+            ))]
+        })
+    } else if !options.may_match(input) {
+        None
+    } else if is_single_file_component(path) {
+        Some(scan_script_blocks(input, options))
+    } else {
+        Some(scan_javascript(input, options))
+    }
+}
+
+/// Returns true for `.vue` and `.svelte` files, in which JavaScript is
+/// embedded in `<script>` blocks.
+pub fn is_single_file_component(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("vue" | "svelte")
+    )
+}
+
+/// Returns true for `.graphql` and `.gql` files. In the sources of a project
+/// these are documents of operations and fragments, rather than JavaScript.
+pub fn is_standalone_document(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("graphql" | "gql")
+    )
+}
+
+/// An `#import "./Fragment.graphql"` comment of a standalone document, naming
+/// a document defining fragments it spreads.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocumentImport {
+    /// The imported path, relative to the importing document
+    pub path: String,
+    /// The span of the path in the document, without the quotes
+    pub span: Span,
+}
+
+impl DocumentImport {
+    /// The path of the imported document, relative to the same directory as
+    /// `document_path`.
+    pub fn resolve(&self, document_path: &Path) -> PathBuf {
+        let mut resolved = PathBuf::new();
+        let joined = document_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(&self.path);
+        for component in joined.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !resolved.pop() {
+                        resolved.push(component);
+                    }
+                }
+                _ => resolved.push(component),
+            }
+        }
+        resolved
+    }
+}
+
+/// Extract the `#import "path"` comments of a standalone document, which
+/// start a line of the document.
+pub fn extract_imports(input: &str) -> Vec<DocumentImport> {
+    let mut imports = Vec::new();
+    let mut line_start = 0;
+    for line in input.split_inclusive('\n') {
+        let rest = line.trim_start().strip_prefix("#import");
+        line_start += line.len();
+        let rest = match rest {
+            Some(rest) if rest.starts_with([' ', '\t']) => rest.trim_start(),
+            _ => continue,
+        };
+        let quote = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => quote,
+            _ => continue,
         };
+        if let Some(length) = rest[1..].find(quote) {
+            let start = line_start - rest.len() + 1;
+            imports.push(DocumentImport {
+                path: rest[1..=length].to_string(),
+                span: Span::new(start as u32, (start + length) as u32),
+            });
+        }
+    }
+    imports
+}
+
+/// Extract graphql`text` literals and @RelayResolver comments from the
+/// `<script>` blocks of an HTML-like document. The line and column indices
+/// of the extracted sources are relative to the whole document.
+pub fn extract_from_script_blocks(
+    input: &str,
+    options: &ExtractOptions,
+) -> Vec<JavaScriptSourceFeature> {
+    if options.may_match(input) {
+        scan_script_blocks(input, options)
+    } else {
+        vec![]
+    }
+}
+
+/// Scans the `<script>` blocks of `input`, without the pre-scan of
+/// `ExtractOptions::may_match`.
+fn scan_script_blocks(input: &str, options: &ExtractOptions) -> Vec<JavaScriptSourceFeature> {
+    let mut res = Vec::new();
+    // Tracks the line and column indices while moving through the document
+    let mut it = CharReader::new(input);
+    let mut offset = 0;
+    while let Some((start, end)) = find_script_block(input, offset) {
+        offset = end;
+        while it.chars.peek().map_or(false, |(i, _)| *i < start) {
+            it.next();
+        }
+        let line_index = it.line_index;
+        let column_index = it.column_index;
+        for feature in scan_javascript(&input[start..end], options) {
+            let text_source = feature.text_source();
+            let feature_line_index = line_index + text_source.line_index;
+            // Only the first line of the block is preceded by the opening tag
+            let feature_column_index = if text_source.line_index == 0 {
+                column_index + text_source.column_index
+            } else {
+                text_source.column_index
+            };
+            res.push(match feature {
+                JavaScriptSourceFeature::GraphQL(graphql_source) => {
+                    JavaScriptSourceFeature::GraphQL(GraphQLSource::new(
+                        graphql_source.to_text_source().text,
+                        feature_line_index,
+                        feature_column_index,
+                    ))
+                }
+                JavaScriptSourceFeature::Docblock(docblock_source) => {
+                    JavaScriptSourceFeature::Docblock(DocblockSource::new(
+                        docblock_source.to_text_source().text,
+                        feature_line_index,
+                        feature_column_index,
+                    ))
+                }
+            });
+        }
     }
     res
 }
 
-fn consume_identifier(it: &mut CharReader<'_>) {
-    for (_, c) in it {
-        match c {
-            'a'..='z' | 'A'..='Z' | '_' | '0'..='9' => {}
-            _ => {
-                return;
-            }
+/// Returns the byte range of the content of the first `<script>` block
+/// starting at or after `offset`.
+fn find_script_block(input: &str, offset: usize) -> Option<(usize, usize)> {
+    let mut tag_start = offset;
+    loop {
+        tag_start += input[tag_start..].find("<script")?;
+        let after_tag_name = tag_start + "<script".len();
+        match input[after_tag_name..].chars().next() {
+            Some('>' | ' ' | '\t' | '\n' | '\r') => break,
+            // e.g. `<scripts>`
+            _ => tag_start = after_tag_name,
         }
     }
+    let start = tag_start + input[tag_start..].find('>')? + 1;
+    let end = input[start..]
+        .find("</script")
+        .map_or(input.len(), |index| start + index);
+    Some((start, end))
 }
 
 fn consume_line_comment(it: &mut CharReader<'_>) {
@@ -235,20 +606,61 @@ fn consume_line_comment(it: &mut CharReader<'_>) {
     }
 }
 
+/// Consumes a block comment after its opening `/*`, and returns the index of
+/// its closing slash.
+fn consume_block_comment(it: &mut CharReader<'_>) -> Option<usize> {
+    let mut prev_c = ' '; // arbitrary character other than *
+    for (i, c) in it {
+        if prev_c == '*' && c == '/' {
+            return Some(i);
+        }
+        prev_c = c;
+    }
+    None
+}
+
+/// Consumes a string after its opening quote, if it ends on the same line.
+/// Otherwise the quote isn't code, e.g. an apostrophe in the text of JSX.
 fn consume_string(it: &mut CharReader<'_>, quote: char) {
-    while let Some((_, c)) = it.next() {
+    let mut lookahead = it.clone();
+    while let Some((_, c)) = lookahead.next() {
         match c {
             '\\' => {
-                it.next();
+                lookahead.next();
             }
             '\'' | '"' if c == quote => {
+                *it = lookahead;
                 return;
             }
             '\n' | '\r' => {
-                // Unexpected newline, terminate the string parsing to recover
                 return;
             }
             _ => {}
         }
     }
 }
+
+/// Consumes a regular expression after its opening slash, if it ends on the
+/// same line, and returns whether it did.
+fn consume_regex(it: &mut CharReader<'_>) -> bool {
+    let mut lookahead = it.clone();
+    let mut in_class = false;
+    while let Some((_, c)) = lookahead.next() {
+        match c {
+            '\\' => {
+                lookahead.next();
+            }
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => {
+                *it = lookahead;
+                return true;
+            }
+            '\n' | '\r' => {
+                return false;
+            }
+            _ => {}
+        }
+    }
+    false
+}
@@ -0,0 +1,12 @@
+/**
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+// relay:comment_annotated_templates
+
+const query = /*GraphQL*/ `query Foo1Query { me { id } }`;
+
+/* Not annotated */ `fragment Ignored1 on User { id }`;
@@ -0,0 +1,23 @@
+/**
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+// relay:tag_names gql graphql.experimental Relay.graphql
+// relay:comment_annotated_templates
+
+gql`fragment Foo1 on User { id }`;
+
+graphql.experimental`fragment Foo2 on User { id }`;
+
+Relay.graphql `fragment Foo3 on User { id }`;
+
+graphql`fragment Ignored1 on User { id }`;
+
+gqlx`fragment Ignored2 on User { id }`;
+
+const query = /* GraphQL */ `query Foo4Query { me { id } }`;
+
+/* not GraphQL */ `fragment Ignored3 on User { id }`;
@@ -0,0 +1,31 @@
+/**
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+@Component({
+  fragment: graphql`fragment Decorated_user on User { id }`,
+})
+class Decorated {}
+
+const config = {
+  query: graphql`query SatisfiesQuery { me { id } }`,
+} satisfies Config;
+
+const help = `Use graphql\`fragment Ignored1 on User\` to read data, it's simple`;
+const pattern = /graphql`[^`]*`|'/g;
+const ratio = total / count / 2;
+
+function Hello() {
+  return (
+    <p title="it's">
+      Don't forget <a href="/docs">the docs</a> {graphql`fragment Hello_user on User { name }`}
+    </p>
+  );
+}
+
+const nested = `${flag ? `${graphql`fragment Nested_user on User { id }`}` : ''}`;
+
+graphql`fragment Last_user on User { id }`;
@@ -5,12 +5,31 @@
  * LICENSE file in the root directory of this source tree.
  */
 
-use extract_graphql::extract;
+use std::path::Path;
+
+use extract_graphql::extract_from_path_with_options;
+use extract_graphql::extract_imports;
+use extract_graphql::is_standalone_document;
+use extract_graphql::ExtractOptions;
 use extract_graphql::JavaScriptSourceFeature;
 use fixture_tests::Fixture;
 
 pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
-    let features = extract(fixture.content);
+    let mut options = ExtractOptions::default();
+    for line in fixture.content.lines() {
+        if let Some(tag_names) = line.strip_prefix("// relay:tag_names ") {
+            options.tag_names = tag_names.split_whitespace().map(String::from).collect();
+        } else if line == "// relay:comment_annotated_templates" {
+            options.comment_annotated_templates = true;
+        }
+    }
+    let path = Path::new(fixture.file_name);
+    let features = extract_from_path_with_options(path, fixture.content, &options);
+    let imports = if is_standalone_document(path) {
+        extract_imports(fixture.content)
+    } else {
+        vec![]
+    };
     Ok(features
         .into_iter()
         .map(|feature| match feature {
@@ -29,6 +48,14 @@ pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
                 )
             }
         })
+        .chain(imports.into_iter().map(|import| {
+            format!(
+                "import - span: {:?}, path: <{}>, resolved: <{}>",
+                import.span,
+                import.path,
+                import.resolve(path).display()
+            )
+        }))
         .collect::<Vec<_>>()
         .join("\n"))
 }
@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<7414a7d21db69b967b49c36af99ace12>>
+ * @generated SignedSource<<6a1abd8dc49a1715ea7af0e5aac34775>>
  */
 
 mod extract;
@@ -12,6 +12,13 @@ mod extract;
 use extract::transform_fixture;
 use fixture_tests::test_fixture;
 
+#[test]
+fn comment_annotation() {
+    let input = include_str!("extract/fixtures/comment_annotation.js");
+    let expected = include_str!("extract/fixtures/comment_annotation.expected");
+    test_fixture(transform_fixture, "comment_annotation.js", "extract/fixtures/comment_annotation.expected", input, expected);
+}
+
 #[test]
 fn comments() {
     let input = include_str!("extract/fixtures/comments.js");
@@ -19,6 +26,13 @@ fn comments() {
     test_fixture(transform_fixture, "comments.js", "extract/fixtures/comments.expected", input, expected);
 }
 
+#[test]
+fn custom_tags() {
+    let input = include_str!("extract/fixtures/custom_tags.js");
+    let expected = include_str!("extract/fixtures/custom_tags.expected");
+    test_fixture(transform_fixture, "custom_tags.js", "extract/fixtures/custom_tags.expected", input, expected);
+}
+
 #[test]
 fn inline() {
     let input = include_str!("extract/fixtures/inline.js");
@@ -26,6 +40,13 @@ fn inline() {
     test_fixture(transform_fixture, "inline.js", "extract/fixtures/inline.expected", input, expected);
 }
 
+#[test]
+fn modern_syntax() {
+    let input = include_str!("extract/fixtures/modern_syntax.tsx");
+    let expected = include_str!("extract/fixtures/modern_syntax.expected");
+    test_fixture(transform_fixture, "modern_syntax.tsx", "extract/fixtures/modern_syntax.expected", input, expected);
+}
+
 #[test]
 fn nested_template_literals() {
     let input = include_str!("extract/fixtures/nested_template_literals.js");
@@ -68,6 +89,20 @@ fn simple() {
     test_fixture(transform_fixture, "simple.flow", "extract/fixtures/simple.expected", input, expected);
 }
 
+#[test]
+fn standalone_document() {
+    let input = include_str!("extract/fixtures/standalone_document.graphql");
+    let expected = include_str!("extract/fixtures/standalone_document.expected");
+    test_fixture(transform_fixture, "standalone_document.graphql", "extract/fixtures/standalone_document.expected", input, expected);
+}
+
+#[test]
+fn svelte_component() {
+    let input = include_str!("extract/fixtures/svelte_component.svelte");
+    let expected = include_str!("extract/fixtures/svelte_component.expected");
+    test_fixture(transform_fixture, "svelte_component.svelte", "extract/fixtures/svelte_component.expected", input, expected);
+}
+
 #[test]
 fn tabbed() {
     let input = include_str!("extract/fixtures/tabbed.js");
@@ -82,6 +117,13 @@ fn template_literal() {
     test_fixture(transform_fixture, "template_literal.js", "extract/fixtures/template_literal.expected", input, expected);
 }
 
+#[test]
+fn vue_component() {
+    let input = include_str!("extract/fixtures/vue_component.vue");
+    let expected = include_str!("extract/fixtures/vue_component.expected");
+    test_fixture(transform_fixture, "vue_component.vue", "extract/fixtures/vue_component.expected", input, expected);
+}
+
 #[test]
 fn with_space() {
     let input = include_str!("extract/fixtures/with_space.js");
@@ -50,6 +50,7 @@
 //! *FB-internal: if you don't want to use cargo run. This is useful for development on a dev-server or
 //! *FB-internal: or machines w/o cargo installed.
 
+mod generate;
 mod print_diff;
 
 use std::env;
@@ -60,6 +61,14 @@ use std::sync::Arc;
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 
+pub use crate::generate::generate_fixture_tests;
+pub use crate::generate::EXPECTED_EXTENSION;
+
+/// Fixtures that contain this marker, usually in a comment like
+/// `# expected-to-throw`, are expected to fail: the test fails if the
+/// transform succeeds, and the error is the expected output.
+pub const EXPECTED_TO_THROW: &str = "expected-to-throw";
+
 lazy_static! {
     static ref LOCK: Arc<Mutex<usize>> = Arc::new(Mutex::new(0));
 }
@@ -89,7 +98,7 @@ pub fn test_fixture<T, U, V>(
         file_name: input_file_name,
         content: input,
     };
-    let expect_ok = !input.contains("expected-to-throw");
+    let expect_ok = !input.contains(EXPECTED_TO_THROW);
     let actual_result: Result<U, V>;
     {
         let _guard = LOCK.lock();
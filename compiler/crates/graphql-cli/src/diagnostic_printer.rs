@@ -17,13 +17,41 @@ use crate::SourcePrinter;
 use crate::Style;
 use crate::Styles;
 
+/// How much of the source is printed for the locations of a diagnostic.
+#[derive(Clone, Copy, Debug)]
+pub struct DiagnosticPrinterOptions {
+    /// The lines of source printed before and after each span.
+    pub context_lines: usize,
+    /// Whether to print the excerpts of the source, or only the file, line
+    /// and column of each location, e.g. for terse CI logs.
+    pub snippets: bool,
+}
+
+impl Default for DiagnosticPrinterOptions {
+    fn default() -> Self {
+        Self {
+            context_lines: 1,
+            snippets: true,
+        }
+    }
+}
+
 pub struct DiagnosticPrinter<T: Sources> {
     sources: T,
+    options: DiagnosticPrinterOptions,
 }
 
 impl<TSources: Sources> DiagnosticPrinter<TSources> {
     pub fn new(sources: TSources) -> Self {
-        Self { sources }
+        Self {
+            sources,
+            options: Default::default(),
+        }
+    }
+
+    pub fn with_options(mut self, options: DiagnosticPrinterOptions) -> Self {
+        self.options = options;
+        self
     }
 
     pub fn diagnostics_to_string(&self, diagnostics: &[Diagnostic]) -> String {
@@ -45,7 +73,7 @@ impl<TSources: Sources> DiagnosticPrinter<TSources> {
         writer: &mut W,
         diagnostic: &Diagnostic,
     ) -> std::fmt::Result {
-        let (message, text_color): (String, Style) = match diagnostic.severity() {
+        let (mut message, text_color): (String, Style) = match diagnostic.severity() {
             common::DiagnosticSeverity::ERROR => {
                 (format!("✖︎ {}", diagnostic.message()), Styles::red)
             }
@@ -57,6 +85,9 @@ impl<TSources: Sources> DiagnosticPrinter<TSources> {
             }
             _ => (format!("ℹ {}", diagnostic.message()), Styles::blue),
         };
+        if diagnostic.occurrences() > 1 {
+            write!(message, " (reported {} times)", diagnostic.occurrences())?;
+        }
 
         writeln!(writer, "{}\n", text_color(message))?;
         self.write_source(writer, diagnostic.location(), text_color)?;
@@ -67,19 +98,22 @@ impl<TSources: Sources> DiagnosticPrinter<TSources> {
                 "\n{}\n",
                 text_color(format!("  ℹ︎ {}", related_information.message)),
             )?;
-            self.write_source(writer, related_information.location, text_color)?;
+            // Annotations are secondary, their spans are highlighted
+            // differently from the span of the diagnostic.
+            self.write_source(writer, related_information.location, Styles::blue)?;
         }
         Ok(())
     }
 
-    /// Writes the file path and slice of the source code for the given location.
+    /// Writes the file path and slice of the source code for the given
+    /// location, or only the file path without snippets.
     fn write_source<W: Write>(
         &self,
         writer: &mut W,
         location: Location,
         highlight_color: Style,
     ) -> std::fmt::Result {
-        let source_printer = SourcePrinter::default();
+        let source_printer = SourcePrinter::with_context_lines(self.options.context_lines);
         if let Some(source) = self.sources.get(location.source_location()) {
             let range = source.to_span_range(location.span());
             writeln!(
@@ -88,6 +122,9 @@ impl<TSources: Sources> DiagnosticPrinter<TSources> {
                 location.source_location().path().underline(),
                 format!(":{}:{}", range.start.line + 1, range.start.character + 1).dimmed()
             )?;
+            if !self.options.snippets {
+                return Ok(());
+            }
             source_printer.write_span_with_highlight_style(
                 writer,
                 location.span(),
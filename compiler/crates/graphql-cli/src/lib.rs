@@ -10,7 +10,9 @@ mod source_printer;
 mod text_style;
 
 pub use diagnostic_printer::DiagnosticPrinter;
+pub use diagnostic_printer::DiagnosticPrinterOptions;
 pub use diagnostic_printer::Sources;
 pub use source_printer::SourcePrinter;
+pub use text_style::override_colors;
 pub use text_style::Style;
 pub use text_style::Styles;
@@ -12,12 +12,25 @@ use common::Span;
 use crate::Style;
 use crate::Styles;
 
-#[derive(Default)]
-pub struct SourcePrinter;
+pub struct SourcePrinter {
+    context_lines: usize,
+}
 
 const PRINT_WHITESPACE: bool = false;
 
+impl Default for SourcePrinter {
+    fn default() -> Self {
+        Self { context_lines: 1 }
+    }
+}
+
 impl SourcePrinter {
+    /// Prints this many lines of the source before and after the lines of
+    /// the span.
+    pub fn with_context_lines(context_lines: usize) -> Self {
+        Self { context_lines }
+    }
+
     pub fn write_span<W: Write>(
         &self,
         writer: &mut W,
@@ -36,23 +49,16 @@ impl SourcePrinter {
         line_offset: usize,
         highlight_style: Style,
     ) -> std::fmt::Result {
-        let start_char_index = span.start as usize;
-        let end_char_index = span.end as usize;
-
-        let start_byte_index =
-            if let Some((byte_index, _)) = source.char_indices().nth(start_char_index) {
-                byte_index
-            } else {
-                return write!(
-                    writer,
-                    "Internal error: Unable to print source, start index ({}) out of range.",
-                    start_char_index
-                );
-            };
-        let end_byte_index = source
-            .char_indices()
-            .nth(end_char_index)
-            .map_or_else(|| source.len(), |(byte_index, _)| byte_index);
+        // Spans are byte offsets into the source
+        let start_byte_index = span.start as usize;
+        if !source.is_char_boundary(start_byte_index) {
+            return write!(
+                writer,
+                "Internal error: Unable to print source, start index ({}) out of range.",
+                start_byte_index
+            );
+        }
+        let end_byte_index = (span.end as usize).min(source.len());
 
         let mut line_end_byte_indices = Vec::new();
         for (byte_index, chr) in source.char_indices() {
@@ -86,13 +92,12 @@ impl SourcePrinter {
             start..end
         };
 
-        const CONTEXT: usize = 1;
-
-        let first_line_index = byte_index_to_line_index(start_char_index);
-        let last_line_index = byte_index_to_line_index(end_char_index);
+        let first_line_index = byte_index_to_line_index(start_byte_index);
+        let last_line_index = byte_index_to_line_index(end_byte_index);
 
-        let first_printed_line_index = first_line_index.saturating_sub(CONTEXT);
-        let last_printed_line_index = (last_line_index + CONTEXT).min(line_end_byte_indices.len());
+        let first_printed_line_index = first_line_index.saturating_sub(self.context_lines);
+        let last_printed_line_index =
+            (last_line_index + self.context_lines).min(line_end_byte_indices.len());
 
         let mut currently_hightlighted = false;
         for line_index in first_printed_line_index..=last_printed_line_index {
@@ -117,7 +122,7 @@ impl SourcePrinter {
                             '␤'
                         } else {
                             // This prints a white-space if the \n is the first character in the Span.
-                            if chr_index == start_char_index {
+                            if chr_index == start_byte_index {
                                 ' '
                             } else {
                                 continue;
@@ -129,7 +134,7 @@ impl SourcePrinter {
                             '␍'
                         } else {
                             // This prints a white-space if the \r is the first character in the Span.
-                            if chr_index == start_char_index {
+                            if chr_index == start_byte_index {
                                 ' '
                             } else {
                                 continue;
@@ -28,3 +28,9 @@ impl Styles {
     create_style!(underline);
     create_style!(strikethrough);
 }
+
+/// Forces the styles on or off, instead of only styling the output of a
+/// terminal.
+pub fn override_colors(enabled: bool) {
+    colored::control::set_override(enabled);
+}
@@ -14,3 +14,5 @@
 mod validate_selection_conflict;
 
 pub use validate_selection_conflict::validate_selection_conflict;
+pub use validate_selection_conflict::validate_selection_conflict_with_cache;
+pub use validate_selection_conflict::SelectionConflictCache;
@@ -8,7 +8,10 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::hash::BuildHasher;
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use common::Diagnostic;
@@ -21,6 +24,7 @@ use errors::par_try_map;
 use errors::validate_map;
 use graphql_ir::node_identifier::LocationAgnosticBehavior;
 use graphql_ir::Argument;
+use graphql_ir::ExecutableDefinitionName;
 use graphql_ir::Field as IRField;
 use graphql_ir::FragmentDefinition;
 use graphql_ir::FragmentDefinitionName;
@@ -29,12 +33,17 @@ use graphql_ir::OperationDefinition;
 use graphql_ir::Program;
 use graphql_ir::ScalarField;
 use graphql_ir::Selection;
+use graphql_ir::StableNodeId;
+use graphql_ir::StableNodeIds;
 use intern::string_key::StringKey;
 use intern::Lookup;
+use once_cell::sync::OnceCell;
 use schema::SDLSchema;
 use schema::Schema;
 use schema::Type;
 use schema::TypeReference;
+use serde::Deserialize;
+use serde::Serialize;
 use thiserror::Error;
 
 use self::ignoring_type_and_location::arguments_equals;
@@ -44,7 +53,56 @@ pub fn validate_selection_conflict<B: LocationAgnosticBehavior + Sync>(
     program: &Program,
     further_optimization: bool,
 ) -> DiagnosticsResult<()> {
-    ValidateSelectionConflict::<B>::new(program, further_optimization).validate_program(program)
+    ValidateSelectionConflict::<B>::new(program, further_optimization, None)
+        .validate_program(program)
+}
+
+/// Like `validate_selection_conflict`, but skips the fragments and operations
+/// that were valid in a previous validation with the same cache, and that
+/// kept their `StableNodeId`. The cache is updated if the program is valid.
+pub fn validate_selection_conflict_with_cache<B: LocationAgnosticBehavior + Sync>(
+    program: &Program,
+    further_optimization: bool,
+    cache: &SelectionConflictCache,
+) -> DiagnosticsResult<()> {
+    let ids = StableNodeIds::new(program);
+    ValidateSelectionConflict::<B>::new(program, further_optimization, Some((cache, &ids)))
+        .validate_program(program)?;
+    for fragment in program.fragments() {
+        let name = ExecutableDefinitionName::FragmentDefinitionName(fragment.name.item);
+        if let Some(id) = ids.fragment(fragment.name.item) {
+            cache.valid_definitions.insert(name, id);
+        }
+    }
+    for operation in program.operations() {
+        let name = ExecutableDefinitionName::OperationDefinitionName(operation.name.item);
+        if let Some(id) = ids.operation(operation.name.item) {
+            cache.valid_definitions.insert(name, id);
+        }
+    }
+    Ok(())
+}
+
+/// The definitions without selection conflicts in previous validations, with
+/// the `StableNodeId` they had. It can be kept in the saved state, and should
+/// be cleared when the schema changes.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SelectionConflictCache {
+    valid_definitions: DashMap<ExecutableDefinitionName, StableNodeId>,
+}
+
+impl SelectionConflictCache {
+    pub fn clear(&self) {
+        self.valid_definitions.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.valid_definitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.valid_definitions.is_empty()
+    }
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -55,23 +113,35 @@ enum Field<'s> {
 
 type Fields<'s> = HashMap<StringKey, Vec<Field<'s>>, intern::BuildIdHasher<u32>>;
 
+/// The fields of a fragment or linked field, validated once by the first
+/// thread that needs them while the others wait for them, or the errors of
+/// the selections.
+type CachedFields<'s> = Arc<OnceCell<DiagnosticsResult<Arc<Fields<'s>>>>>;
+
 struct ValidateSelectionConflict<'s, TBehavior: LocationAgnosticBehavior> {
     program: &'s Program,
-    fragment_cache: DashMap<StringKey, Arc<Fields<'s>>, intern::BuildIdHasher<u32>>,
-    fields_cache: DashMap<PointerAddress, Arc<Fields<'s>>>,
+    fragment_cache: DashMap<StringKey, CachedFields<'s>, intern::BuildIdHasher<u32>>,
+    fields_cache: DashMap<PointerAddress, CachedFields<'s>>,
     further_optimization: bool,
     verified_fields_pair: DashSet<(PointerAddress, PointerAddress, bool)>,
+    previously_valid: Option<(&'s SelectionConflictCache, &'s StableNodeIds)>,
     _behavior: PhantomData<TBehavior>,
 }
 
 impl<'s, B: LocationAgnosticBehavior + Sync> ValidateSelectionConflict<'s, B> {
-    fn new(program: &'s Program, further_optimization: bool) -> Self {
+    fn new(
+        program: &'s Program,
+        further_optimization: bool,
+        previously_valid: Option<(&'s SelectionConflictCache, &'s StableNodeIds)>,
+    ) -> Self {
+        let shard_amount = cache_shard_amount();
         Self {
             program,
-            fragment_cache: Default::default(),
-            fields_cache: Default::default(),
+            fragment_cache: DashMap::with_hasher_and_shard_amount(Default::default(), shard_amount),
+            fields_cache: DashMap::with_shard_amount(shard_amount),
             further_optimization,
             verified_fields_pair: Default::default(),
+            previously_valid,
             _behavior: PhantomData::<B>,
         }
     }
@@ -81,12 +151,31 @@ impl<'s, B: LocationAgnosticBehavior + Sync> ValidateSelectionConflict<'s, B> {
             self.prewarm_fragments(program)?;
         }
 
-        par_try_map(&program.operations, |operation| {
+        let operations = program.operations().filter(|operation| {
+            !self.was_valid(ExecutableDefinitionName::OperationDefinitionName(
+                operation.name.item,
+            ))
+        });
+        par_try_map(operations.collect::<Vec<_>>(), |operation| {
             self.validate_operation(operation)
         })?;
         Ok(())
     }
 
+    /// Whether the definition was valid in a previous validation, and is the
+    /// same in this program.
+    fn was_valid(&self, name: ExecutableDefinitionName) -> bool {
+        let (cache, ids) = match self.previously_valid {
+            Some(previously_valid) => previously_valid,
+            None => return false,
+        };
+        let id = match name {
+            ExecutableDefinitionName::OperationDefinitionName(name) => ids.operation(name),
+            ExecutableDefinitionName::FragmentDefinitionName(name) => ids.fragment(name),
+        };
+        id.is_some() && cache.valid_definitions.get(&name).map(|cached| *cached) == id
+    }
+
     fn prewarm_fragments(&self, program: &'s Program) -> DiagnosticsResult<()> {
         // Validate the fragments in topology order.
         let mut unclaimed_fragment_queue: VecDeque<FragmentDefinitionName> = VecDeque::new();
@@ -119,12 +208,16 @@ impl<'s, B: LocationAgnosticBehavior + Sync> ValidateSelectionConflict<'s, B> {
 
         let dummy_hashset = HashSet::new();
         while let Some(visiting) = unclaimed_fragment_queue.pop_front() {
-            if let Err(e) = self.validate_and_collect_fragment(
-                program
-                    .fragment(visiting)
-                    .expect("fragment must have been registered"),
-            ) {
-                return Err(e);
+            // Fragments that were valid are only validated if an operation
+            // to validate spreads them.
+            if !self.was_valid(ExecutableDefinitionName::FragmentDefinitionName(visiting)) {
+                if let Err(e) = self.validate_and_collect_fragment(
+                    program
+                        .fragment(visiting)
+                        .expect("fragment must have been registered"),
+                ) {
+                    return Err(e);
+                }
             }
 
             for used_by in dag_used_by.get(&visiting).unwrap_or(&dummy_hashset) {
@@ -187,26 +280,18 @@ impl<'s, B: LocationAgnosticBehavior + Sync> ValidateSelectionConflict<'s, B> {
         &self,
         fragment: &'s FragmentDefinition,
     ) -> DiagnosticsResult<Arc<Fields<'s>>> {
-        if let Some(cached) = self.fragment_cache.get(&fragment.name.item.0) {
-            return Ok(Arc::clone(&cached));
-        }
-        let fields = Arc::new(self.validate_selections(&fragment.selections)?);
-        self.fragment_cache
-            .insert(fragment.name.item.0, Arc::clone(&fields));
-        Ok(fields)
+        get_or_validate(&self.fragment_cache, fragment.name.item.0, || {
+            self.validate_selections(&fragment.selections)
+        })
     }
 
     fn validate_linked_field_selections(
         &self,
         field: &'s LinkedField,
     ) -> DiagnosticsResult<Arc<Fields<'s>>> {
-        let key = PointerAddress::new(field);
-        if let Some(fields) = self.fields_cache.get(&key) {
-            return Ok(Arc::clone(&fields));
-        }
-        let fields = Arc::new(self.validate_selections(&field.selections)?);
-        self.fields_cache.insert(key, Arc::clone(&fields));
-        Ok(fields)
+        get_or_validate(&self.fields_cache, PointerAddress::new(field), || {
+            self.validate_selections(&field.selections)
+        })
     }
 
     fn validate_and_merge_fields(
@@ -483,6 +568,29 @@ impl<'s, B: LocationAgnosticBehavior + Sync> ValidateSelectionConflict<'s, B> {
     }
 }
 
+/// Validates the selections of the key once, and returns the cached fields
+/// or errors to the other callers, which wait while they are validated. The
+/// cache is only locked to find or add the cell of the key, so the
+/// validation can look up other keys.
+fn get_or_validate<'s, K: Eq + Hash, S: BuildHasher + Clone>(
+    cache: &DashMap<K, CachedFields<'s>, S>,
+    key: K,
+    validate: impl Fn() -> DiagnosticsResult<Fields<'s>>,
+) -> DiagnosticsResult<Arc<Fields<'s>>> {
+    let cell = match cache.get(&key) {
+        Some(cell) => Arc::clone(&cell),
+        None => Arc::clone(&cache.entry(key).or_default()),
+    };
+    cell.get_or_init(|| validate().map(Arc::new)).clone()
+}
+
+/// More shards than the default of `DashMap`, so that fewer threads wait on
+/// the same shard while many threads validate operations.
+fn cache_shard_amount() -> usize {
+    let threads = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    (threads * 16).next_power_of_two().max(4)
+}
+
 fn has_same_type_reference_wrapping(l: &TypeReference<Type>, r: &TypeReference<Type>) -> bool {
     match (l, r) {
         (TypeReference::Named(_), TypeReference::Named(_)) => true,
@@ -525,10 +633,10 @@ impl<'s> Field<'s> {
 }
 
 mod ignoring_type_and_location {
-    use graphql_ir::node_identifier::LocationAgnosticBehavior;
-    use graphql_ir::node_identifier::LocationAgnosticPartialEq;
     use graphql_ir::Argument;
     use graphql_ir::Value;
+    use graphql_ir::node_identifier::LocationAgnosticBehavior;
+    use graphql_ir::node_identifier::LocationAgnosticPartialEq;
 
     /// Verify that two sets of arguments are equivalent - same argument names
     /// and values. Notably, this ignores the types of arguments and values,
@@ -621,3 +729,59 @@ enum ValidationMessage {
     )]
     StreamConflictUsedInMultiplePlaces { response_key: StringKey },
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    fn validate_shared_fragment(
+        validate: impl Fn() -> DiagnosticsResult<Fields<'static>> + Sync,
+    ) -> Vec<DiagnosticsResult<Arc<Fields<'static>>>> {
+        let cache: DashMap<StringKey, CachedFields<'static>, intern::BuildIdHasher<u32>> =
+            Default::default();
+        let key = "SharedFragment".intern();
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| get_or_validate(&cache, key, &validate)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn validates_a_valid_fragment_once() {
+        let validations = AtomicUsize::new(0);
+        let results = validate_shared_fragment(|| {
+            validations.fetch_add(1, Ordering::SeqCst);
+            Ok(Default::default())
+        });
+        assert_eq!(validations.load(Ordering::SeqCst), 1);
+        assert!(results.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn validates_an_invalid_fragment_once() {
+        let validations = AtomicUsize::new(0);
+        let results = validate_shared_fragment(|| {
+            validations.fetch_add(1, Ordering::SeqCst);
+            Err(vec![Diagnostic::error(
+                "invalid fragment",
+                Location::generated(),
+            )])
+        });
+        assert_eq!(validations.load(Ordering::SeqCst), 1);
+        for result in results {
+            let errors = result.unwrap_err();
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].message().to_string(), "invalid fragment");
+        }
+    }
+}
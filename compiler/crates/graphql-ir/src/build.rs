@@ -687,7 +687,8 @@ impl<'schema, 'signatures, 'options> Builder<'schema, 'signatures, 'options> {
                             suggestions,
                         },
                         self.location.with_span(arg.span),
-                    )])
+                    )
+                    .metadata_for_machine("err", "UnknownFragmentArgument")])
                 }
             })
             .collect();
@@ -1541,6 +1542,7 @@ impl<'schema, 'signatures, 'options> Builder<'schema, 'signatures, 'options> {
                 )]);
             }
         };
+        self.validate_one_of_field_count(type_definition, object.items.len(), object.span)?;
         let mut seen_fields = StringKeyMap::default();
         let mut required_fields = type_definition
             .fields
@@ -1571,11 +1573,30 @@ impl<'schema, 'signatures, 'options> Builder<'schema, 'signatures, 'options> {
                         };
 
                         let value_span = x.value.span();
-                        let value = self.build_value(
-                            &x.value,
-                            &field_definition.type_,
-                            ValidationLevel::Strict,
-                        )?;
+                        let value = if type_definition.is_one_of() {
+                            if let graphql_syntax::Value::Constant(
+                                graphql_syntax::ConstantValue::Null(_),
+                            ) = x.value
+                            {
+                                return Err(self.null_one_of_field_error(
+                                    type_definition,
+                                    x.name.value,
+                                    value_span,
+                                ));
+                            }
+                            // Variables must be non-null too
+                            self.build_value(
+                                &x.value,
+                                &field_definition.type_.non_null(),
+                                ValidationLevel::Strict,
+                            )?
+                        } else {
+                            self.build_value(
+                                &x.value,
+                                &field_definition.type_,
+                                ValidationLevel::Strict,
+                            )?
+                        };
                         Ok(Argument {
                             name: x
                                 .name
@@ -1614,6 +1635,38 @@ impl<'schema, 'signatures, 'options> Builder<'schema, 'signatures, 'options> {
         }
     }
 
+    /// Exactly one field of a `@oneOf` input object must be set.
+    fn validate_one_of_field_count(
+        &self,
+        type_definition: &InputObject,
+        field_count: usize,
+        span: Span,
+    ) -> DiagnosticsResult<()> {
+        if type_definition.is_one_of() && field_count != 1 {
+            Err(vec![Diagnostic::error(
+                ValidationMessage::OneOfInputObjectFieldCount(
+                    type_definition.name.item.0,
+                    field_count,
+                ),
+                self.location.with_span(span),
+            )])
+        } else {
+            Ok(())
+        }
+    }
+
+    fn null_one_of_field_error(
+        &self,
+        type_definition: &InputObject,
+        field_name: StringKey,
+        span: Span,
+    ) -> Vec<Diagnostic> {
+        vec![Diagnostic::error(
+            ValidationMessage::NullOneOfInputField(type_definition.name.item.0, field_name),
+            self.location.with_span(span),
+        )]
+    }
+
     fn build_constant_value(
         &mut self,
         value: &graphql_syntax::ConstantValue,
@@ -1685,6 +1738,7 @@ impl<'schema, 'signatures, 'options> Builder<'schema, 'signatures, 'options> {
                 )]);
             }
         };
+        self.validate_one_of_field_count(type_definition, object.items.len(), object.span)?;
         let mut seen_fields = StringKeyMap::default();
         let mut required_fields = type_definition
             .fields
@@ -1715,6 +1769,15 @@ impl<'schema, 'signatures, 'options> Builder<'schema, 'signatures, 'options> {
                         };
 
                         let value_span = x.value.span();
+                        if type_definition.is_one_of() {
+                            if let graphql_syntax::ConstantValue::Null(_) = x.value {
+                                return Err(self.null_one_of_field_error(
+                                    type_definition,
+                                    x.name.value,
+                                    value_span,
+                                ));
+                            }
+                        }
                         let value = self.build_constant_value(
                             &x.value,
                             &field_definition.type_,
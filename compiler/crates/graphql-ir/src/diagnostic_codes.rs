@@ -0,0 +1,221 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The codes of the validation messages, see `common::DiagnosticCode`.
+
+use common::diagnostic_codes;
+use common::Diagnostic;
+use common::DiagnosticCode;
+
+use crate::errors::ValidationMessage;
+use crate::errors::ValidationMessageWithData;
+
+diagnostic_codes! {
+    VALIDATION_MESSAGE_CODES for ValidationMessage {
+        DuplicateDefinition => "RELAY-1001":
+            "Two operations or fragments have the same name. Names must be unique across all the documents of a project, because they name the generated files. Rename one of them.",
+        ExpectedCompositeType => "RELAY-1002":
+            "Selections or a type condition were used on a type that is not an object, interface or union. Remove the selections, or use a type condition on a composite type.",
+        ExpectedType => "RELAY-1003":
+            "A type used in a document is not the kind of type expected at that position. Check the type against the schema.",
+        InvalidSelectionsOnScalarField => "RELAY-1004":
+            "A scalar or enum field has a selection set. Only fields of object, interface or union types have selections, remove the braces.",
+        UnknownDirective => "RELAY-1005":
+            "The directive is not defined by the schema, the client schema extensions or Relay. Check the spelling, or define the directive in a schema extension.",
+        UnnecessaryUncheckedArgumentsDirective => "RELAY-1006":
+            "`@uncheckedArguments_DEPRECATED` is only needed when the arguments of a fragment spread can't be checked. All of them are defined with the right types here, use `@arguments` instead.",
+        ExpectedOperationName => "RELAY-1007":
+            "Relay names the generated files after their operation, so every query, mutation and subscription needs a name, like `query UserQuery { ... }`.",
+        UnsupportedOperation => "RELAY-1008":
+            "The schema has no root type for this kind of operation, e.g. a `subscription` without a `Subscription` type. Add the root type to the schema or use another operation kind.",
+        UnsupportedNestListType => "RELAY-1009":
+            "Relay doesn't support variables or arguments of nested list types like `[[Int]]`. Use a list of input objects instead.",
+        ExpectedValueMatchingType => "RELAY-1010":
+            "The value passed for an argument or variable doesn't match its type in the schema. Pass a value of the expected type.",
+        ExpectedEnumValueGotString => "RELAY-1011":
+            "Enum values are written without quotes in GraphQL. Write `ADMIN` instead of `\"ADMIN\"`.",
+        DuplicateInputField => "RELAY-1012":
+            "An input object value sets the same field twice. Remove one of them.",
+        MissingRequiredFields => "RELAY-1013":
+            "An input object value doesn't set all the non-null fields without a default value of its type. Set the missing fields.",
+        OneOfInputObjectFieldCount => "RELAY-1014":
+            "Input objects with `@oneOf` must set exactly one of their fields. Remove the extra fields, or set one if there are none.",
+        NullOneOfInputField => "RELAY-1015":
+            "The field set on a `@oneOf` input object must not be null. Pass a non-null value, or set another field.",
+        UnsupportedCustomScalarType => "RELAY-1016":
+            "An enum value was passed as a literal for a custom scalar, which Relay can't represent. Pass a string, number or object literal, or a variable.",
+        ExpectedOneArgumentsDirective => "RELAY-1017":
+            "A fragment spread has more than one `@arguments` directive. Merge them into one.",
+        ExpectedOneArgumentDefinitionsDirective => "RELAY-1018":
+            "A fragment has more than one `@argumentDefinitions` directive. Merge them into one.",
+        VariableDefinitionsAndArgumentDirective => "RELAY-1019":
+            "A fragment defines its variables both with the fragment variable syntax and with `@argumentDefinitions`. Use only one of them.",
+        FragmentArgumentsAndArgumentDirective => "RELAY-1020":
+            "A fragment spread passes arguments both with the fragment argument syntax and with `@arguments`. Use only one of them.",
+        OutsidePassedArgumentsMode => "RELAY-1021":
+            "Arguments were passed to a fragment spread with the fragment argument syntax, which isn't enabled. Use `@arguments` instead.",
+        ExpectedArgumentDefinitionLiteralType => "RELAY-1022":
+            "Each argument of `@argumentDefinitions` needs its type as a string literal, like `count: {type: \"Int!\"}`.",
+        ExpectedArgumentDefinitionToBeObject => "RELAY-1023":
+            "Each argument of `@argumentDefinitions` is an object with a `type` and an optional `defaultValue`, like `count: {type: \"Int\", defaultValue: 10}`.",
+        ExpectedArgumentDefinitionsDirectiveOnFragmentDefinition => "RELAY-1024":
+            "`@argumentDefinitions` defines the arguments of a fragment, so it can only be used on fragment definitions. Use `@arguments` on fragment spreads.",
+        ArgumentDefinitionsDirectivesNotStringListLiteral => "RELAY-1025":
+            "The `directives` of an argument in `@argumentDefinitions` are a list of string literals, like `directives: [\"@example\"]`.",
+        NonNullableVariableHasDefaultValue => "RELAY-1026":
+            "A variable with a default value can be omitted, so it can't be non-null. Make its type nullable or remove the default value.",
+        InvalidVariableUsage => "RELAY-1027":
+            "A variable is passed where a value of an incompatible type is expected, e.g. a nullable variable for a non-null argument. Change the type of the variable.",
+        IncompatibleVariableUsage => "RELAY-1028":
+            "A global variable of a fragment is used with types that are not compatible with each other. Use it with a single type, or pass a separate variable.",
+        ExpectedOperationVariableToBeDefined => "RELAY-1029":
+            "The variable is used, but not defined by the operation. Add it to the variable definitions of the operation.",
+        ExpectedFragmentArgumentToHaveInputType => "RELAY-1030":
+            "Fragment arguments can only have input types: scalars, enums and input objects. Change the type of the argument.",
+        ExpectedVariablesToHaveInputType => "RELAY-1031":
+            "Variables can only have input types: scalars, enums and input objects. Change the type of the variable.",
+        InvalidInlineFragmentTypeCondition => "RELAY-1032":
+            "The type condition of the inline fragment can never match the type of its parent, so its selections would never be read. Remove the fragment or fix the type condition.",
+        InvalidFragmentSpreadType => "RELAY-1033":
+            "The type of the fragment can never match the type where it is spread, so it would never be read. Spread it on a field of a compatible type.",
+        InvalidDirectiveUsageUnsupportedLocation => "RELAY-1034":
+            "The directive is not defined for this location, e.g. a field directive on a fragment spread. Check the locations of its definition.",
+        InvalidArgumentDefinitionsKey => "RELAY-1035":
+            "The arguments of `@argumentDefinitions` only support `type`, `defaultValue` and `directives`. Remove the other keys.",
+        InvalidArgumentsOnTypenameField => "RELAY-1036":
+            "`__typename` has no arguments. Remove them.",
+        InvalidArgumentsOnFetchTokenField => "RELAY-1037":
+            "`__token` has no arguments. Remove them.",
+        InvalidIdFieldType => "RELAY-1038":
+            "Relay uses the `id` field to identify records in the store, so it must have an `ID` or string-like type. Rename the field in the schema, or select it with an alias.",
+        DisallowNonNodeIdFieldType => "RELAY-1039":
+            "The `id` field of a type that doesn't implement `Node` has a type that Relay can't use to identify records. See https://relay.dev/docs/debugging/disallowed-id-types-error.",
+        DisallowReservedAliasError => "RELAY-1040":
+            "The alias is a name that Relay uses for its own fields, like `__typename` or `__id`. Pick another alias.",
+        DisallowTypenameOnRoot => "RELAY-1041":
+            "Relay doesn't allow selecting `__typename` on the root types of operations. Remove the selection.",
+        InvalidServerOnlyDirectiveInClientFields => "RELAY-1042":
+            "The directive affects how the server resolves a field, but it is used in a selection of client schema extension fields. Remove the directive.",
+        InvalidConnectionFieldType => "RELAY-1043":
+            "`@connection` must be used on a field that returns a single object or interface following the connection spec, not a list or scalar.",
+        ExpectedConnectionToHaveCountArgs => "RELAY-1044":
+            "A connection field needs a `first` or `last` argument to paginate. Add the argument to the field.",
+        ExpectedConnectionToHaveEdgesSelection => "RELAY-1045":
+            "A connection field must select its `edges`, which Relay updates when paginating. Add the selection.",
+        ExpectedConnectionToExposeValidEdgesField => "RELAY-1046":
+            "The type of a connection field must have an `edges` field that returns a list of objects, as in the connection spec. Fix the schema or use another field.",
+        ExpectedConnectionToExposeValidNodeField => "RELAY-1047":
+            "The edges of a connection must have a `node` field that returns an object, interface or union, as in the connection spec.",
+        ExpectedConnectionToExposeValidCursorField => "RELAY-1048":
+            "The edges of a connection must have a `cursor` field that returns a scalar, as in the connection spec.",
+        ExpectedConnectionToExposeValidPageInfoField => "RELAY-1049":
+            "The type of a connection field must have a `pageInfo` field that returns an object, as in the connection spec.",
+        ExpectedConnectionToExposeValidPageInfoSubField => "RELAY-1050":
+            "The `pageInfo` of a connection must have scalar fields like `hasNextPage` and `endCursor`, as in the connection spec.",
+        InvalidConnectionHandlerArg => "RELAY-1051":
+            "The `handler` argument of `@connection` must be a string literal.",
+        InvalidConnectionKeyArg => "RELAY-1052":
+            "The `key` argument of `@connection` must be a string literal, which identifies the connection in the store.",
+        InvalidConnectionDynamicKeyArg => "RELAY-1053":
+            "The `dynamicKey_UNSTABLE` argument of `@connection` must be a variable.",
+        InvalidConnectionKeyArgPostfix => "RELAY-1054":
+            "The `key` of a connection must end with an underscore and the name of the field, like `UserFriends_friends`.",
+        InvalidConnectionFiltersArg => "RELAY-1055":
+            "The `filters` argument of `@connection` must be a list of string literals.",
+        InvalidConnectionFiltersArgNotAnArgument => "RELAY-1056":
+            "The `filters` of `@connection` name the arguments of the field that identify the connection. Use an argument of the field, or omit `filters` to use all of them.",
+        UnsupportedAliasingInStreamConnection => "RELAY-1057":
+            "The fields of a `@stream_connection` can't be aliased, because Relay reads them by name. Remove the alias.",
+        InvalidRelayDirectiveArg => "RELAY-1058":
+            "The arguments of `@relay`, like `mask` and `plural`, must be boolean literals.",
+        InvalidUnmaskOnFragmentWithDirectives => "RELAY-1059":
+            "`@relay(mask: false)` inlines the selections of the fragment, which isn't possible if the fragment has directives. Remove them or spread the fragment without `@relay(mask: false)`.",
+        InvalidUnmaskOnFragmentWithArguments => "RELAY-1060":
+            "`@relay(mask: false)` inlines the selections of the fragment, which isn't possible if the fragment has `@argumentDefinitions`.",
+        InvalidUnmaskOnLocalAndGloablVariablesWithSameName => "RELAY-1061":
+            "An unmasked fragment uses a local and a global variable with the same name, which would conflict when its selections are inlined. Rename one of them.",
+        InvalidUnmaskOnVariablesOfIncompatibleTypesWithSameName => "RELAY-1062":
+            "Unmasked fragments use a variable with the same name but incompatible types, which would conflict when their selections are inlined. Rename one of them.",
+        LiteralStringArgumentExpectedForDirective => "RELAY-1063":
+            "The argument of the directive is read by the compiler, so it must be a string literal rather than a variable.",
+        UnusedVariable => "RELAY-1064":
+            "The operation defines a variable that none of its selections or fragments use. Remove the variable definition. `relay-compiler lint --fix` removes it.",
+        UnusedFragmentVariable => "RELAY-1065":
+            "The fragment defines a local variable with `@argumentDefinitions` that it doesn't use. Remove it. `relay-compiler lint --fix` removes it.",
+        UselessUnusedFragmentVariableAnnotation => "RELAY-1066":
+            "A fragment argument is marked with `unusedLocalVariable_DEPRECATED: true`, but it is used. Remove the annotation.",
+        InvalidUnusedFragmentVariableSuppressionArg => "RELAY-1067":
+            "`unusedLocalVariable_DEPRECATED` can only be the constant `true`.",
+        UnusedIgnoreUnusedVariablesDirective => "RELAY-1068":
+            "The operation has `@DEPRECATED__relay_ignore_unused_variables_error`, but no unused variables. Remove the directive.",
+        GlobalVariables => "RELAY-1069":
+            "The fragments of the operation use global variables that the operation doesn't define. Add them to the variable definitions of the operation; the language server has a code action for it.",
+        GenerateSubscriptionNameSingleSelectionItem => "RELAY-1070":
+            "A subscription must select exactly one root field.",
+        RepeatedNonRepeatableDirective => "RELAY-1071":
+            "The directive is not `repeatable`, so it can only be used once at a location. Remove the extra uses.",
+        ProvidedVariableIncompatibleWithDefaultValue => "RELAY-1072":
+            "A provided variable gets its value from its provider module, so it can't have a default value. Remove it.",
+        DeprecatedField => "RELAY-1073":
+            "The field is marked `@deprecated` in the schema. Use the replacement that the deprecation reason names. Reported with `--pedantic` and in the editor.",
+        DeprecatedFieldArgument => "RELAY-1074":
+            "The argument of the field is marked `@deprecated` in the schema. Stop passing it, or use its replacement.",
+        DeprecatedDirectiveArgument => "RELAY-1075":
+            "The argument of the directive is marked `@deprecated`. Stop passing it, or use its replacement.",
+        DeprecatedInputField => "RELAY-1076":
+            "The field of the input object is marked `@deprecated` in the schema. Stop setting it, or use its replacement.",
+        MissingRequiredArguments => "RELAY-1077":
+            "The field or directive has non-null arguments without a default value that are not passed. Pass them.",
+        DuplicateArgument => "RELAY-1078": "The same argument is passed twice. Remove one of them.",
+        MissingRequiredArgument => "RELAY-1079":
+            "A fragment spread in the operation needs a non-null argument without a default value, which is not passed. Pass it with `@arguments`, or give the argument a default value.",
+        MissingRequiredFragmentArgument => "RELAY-1080":
+            "The fragment defines a non-null argument without a default value, which this spread doesn't pass. Pass it with `@arguments`.",
+        DuplicateVariable => "RELAY-1081":
+            "The same variable is defined twice. Remove one of the definitions.",
+        RequiredRawResponseTypeOnNoInline => "RELAY-1082":
+            "A query with `@raw_response_type` includes a `@no_inline` fragment, whose raw response type is only generated with `@no_inline(raw_response_type: true)`. Add the argument.",
+        NoDoubleUnderscoreAlias => "RELAY-1083":
+            "Names that start with two underscores are reserved for introspection and Relay. Pick another alias.",
+    }
+}
+
+diagnostic_codes! {
+    VALIDATION_MESSAGE_WITH_DATA_CODES for ValidationMessageWithData {
+        UnknownType => "RELAY-1501":
+            "The type is not defined by the schema or the client schema extensions. Check the spelling; the message suggests similar names.",
+        UnknownField => "RELAY-1502":
+            "The type has no field of that name in the schema. Check the spelling or the type, or define the field in a client schema extension. See https://relay.dev/docs/error-reference/unknown-field/.",
+        ExpectedSelectionsOnObjectField => "RELAY-1503":
+            "Fields of object, interface or union types must select some of their fields, like `user { name }`.",
+        UndefinedFragment => "RELAY-1504":
+            "The spread fragment is not defined in the project. Check the spelling, or whether the file that defines it is in the sources of the project.",
+        UnknownArgument => "RELAY-1505":
+            "The field or directive has no argument of that name. Check the spelling; the message suggests similar names.",
+    }
+}
+
+/// The code of the message of the diagnostic, if it is a validation message
+/// of the IR.
+pub fn diagnostic_code(diagnostic: &Diagnostic) -> Option<&'static str> {
+    let message = diagnostic.message_as_any();
+    if let Some(message) = message.downcast_ref::<ValidationMessage>() {
+        Some(message.code())
+    } else {
+        message
+            .downcast_ref::<ValidationMessageWithData>()
+            .map(|message| message.code())
+    }
+}
+
+/// The codes of the validation messages of the IR, in the order of their
+/// numbers.
+pub fn diagnostic_codes() -> impl Iterator<Item = &'static DiagnosticCode> {
+    VALIDATION_MESSAGE_CODES
+        .iter()
+        .chain(VALIDATION_MESSAGE_WITH_DATA_CODES)
+}
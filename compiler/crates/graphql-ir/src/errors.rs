@@ -80,6 +80,12 @@ pub enum ValidationMessage {
     #[error("Missing required fields '{0:?}' of type '{1}'")] // TODO: print joined
     MissingRequiredFields(Vec<StringKey>, StringKey),
 
+    #[error("Exactly one field of the @oneOf input object '{0}' must be set, got {1}.")]
+    OneOfInputObjectFieldCount(StringKey, usize),
+
+    #[error("The field '{1}' of the @oneOf input object '{0}' must not be null.")]
+    NullOneOfInputField(StringKey, StringKey),
+
     #[error("Unsupported (user-defined) scalar type '{0}'")]
     UnsupportedCustomScalarType(StringKey),
 
@@ -459,6 +465,17 @@ pub enum ValidationMessage {
         deprecation_reason: Option<StringKey>,
     },
 
+    #[error("The input field `{input_object_name}.{field_name}` is deprecated.{}",
+    match deprecation_reason {
+        Some(reason) => format!(" Deprecation reason: \"{}\"", reason),
+        None => "".to_string()
+    })]
+    DeprecatedInputField {
+        field_name: ArgumentName,
+        input_object_name: StringKey,
+        deprecation_reason: Option<StringKey>,
+    },
+
     #[error("Missing required {}: `{}`",
         if missing_arg_names.len() > 1 { "arguments" } else { "argument" },
         missing_arg_names
@@ -37,7 +37,7 @@ use serde::Serialize;
 use crate::AssociatedData;
 // Definitions
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ExecutableDefinition {
     Operation(OperationDefinition),
     Fragment(FragmentDefinition),
@@ -85,7 +85,7 @@ impl Display for OperationDefinitionName {
 }
 impl_lookup!(OperationDefinitionName);
 /// A fully-typed mutation, query, or subscription definition
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct OperationDefinition {
     pub kind: OperationKind,
     pub name: WithLocation<OperationDefinitionName>,
@@ -141,7 +141,7 @@ pub type FragmentDefinitionNameMap<V> = HashMap<FragmentDefinitionName, V, Build
 pub type FragmentDefinitionNameSet = HashSet<FragmentDefinitionName, BuildIdHasher<u32>>;
 
 /// A fully-typed fragment definition
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct FragmentDefinition {
     pub name: WithLocation<FragmentDefinitionName>,
     pub variable_definitions: Vec<VariableDefinition>,
@@ -222,7 +222,7 @@ impl ExecutableDefinitionName {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub struct VariableName(pub StringKey);
 
 impl Display for VariableName {
@@ -241,7 +241,7 @@ impl FromStr for VariableName {
 impl_lookup!(VariableName);
 
 /// A variable definition of an operation or fragment
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct VariableDefinition {
     pub name: WithLocation<VariableName>,
     pub type_: TypeReference<Type>,
@@ -268,7 +268,7 @@ impl Named for VariableDefinition {
 // Selections
 
 /// A selection within an operation or fragment
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Selection {
     FragmentSpread(Arc<FragmentSpread>),
     InlineFragment(Arc<InlineFragment>),
@@ -373,7 +373,7 @@ impl fmt::Debug for Selection {
 }
 
 /// ... Name
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct FragmentSpread {
     pub fragment: WithLocation<FragmentDefinitionName>,
     pub arguments: Vec<Argument>,
@@ -382,7 +382,7 @@ pub struct FragmentSpread {
 
 /// ... SelectionSet
 /// ... on Type SelectionSet
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct InlineFragment {
     pub type_condition: Option<Type>,
     pub directives: Vec<Directive>,
@@ -412,7 +412,7 @@ pub trait Field {
 }
 
 /// Name Arguments? SelectionSet
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct LinkedField {
     pub alias: Option<WithLocation<StringKey>>,
     pub definition: WithLocation<FieldID>,
@@ -440,7 +440,7 @@ impl Field for LinkedField {
 }
 
 /// Name Arguments?
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ScalarField {
     pub alias: Option<WithLocation<StringKey>>,
     pub definition: WithLocation<FieldID>,
@@ -467,7 +467,7 @@ impl Field for ScalarField {
 }
 
 /// https://spec.graphql.org/June2018/#sec--skip
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Condition {
     pub selections: Vec<Selection>,
     pub value: ConditionValue,
@@ -488,13 +488,14 @@ impl Condition {
 // Associated Types
 
 /// @ Name Arguments?
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Directive {
     pub name: WithLocation<DirectiveName>,
     pub arguments: Vec<Argument>,
     /// Optional typed data that has no textual representation. This can be used
     /// to attach arbitrary data on compiler-internal directives, such as to
-    /// pass instructions to code generation.
+    /// pass instructions to code generation. It isn't serialized.
+    #[serde(skip)]
     pub data: Option<Box<dyn AssociatedData>>,
 }
 impl Named for Directive {
@@ -505,7 +506,7 @@ impl Named for Directive {
 }
 
 /// Name : Value
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Argument {
     pub name: WithLocation<ArgumentName>,
     pub value: WithLocation<Value>,
@@ -517,7 +518,7 @@ impl Named for Argument {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Value {
     Constant(ConstantValue),
     Variable(Variable),
@@ -560,14 +561,14 @@ impl Value {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Variable {
     pub name: WithLocation<VariableName>,
     pub type_: TypeReference<Type>,
 }
 
 /// Name : Value[Const]
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct ConstantArgument {
     pub name: WithLocation<ArgumentName>,
     pub value: WithLocation<ConstantValue>,
@@ -590,7 +591,7 @@ macro_rules! generate_unwrap_fn {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum ConstantValue {
     Int(i64),
     Float(FloatValue),
@@ -627,8 +628,46 @@ impl ConstantValue {
     generate_unwrap_fn!(unwrap_object, self, &Vec<ConstantArgument>, ConstantValue::Object(o) => o);
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ConditionValue {
     Constant(bool),
     Variable(Variable),
 }
+
+#[cfg(test)]
+mod tests {
+    use common::SourceLocationKey;
+    use graphql_syntax::parse_executable;
+    use relay_test_schema::TEST_SCHEMA;
+
+    use super::*;
+    use crate::build;
+
+    #[test]
+    fn definitions_roundtrip_through_json() {
+        let text = r#"
+            query Query($id: ID!, $cond: Boolean!) {
+              node(id: $id) {
+                ... on User @include(if: $cond) {
+                  name
+                  profilePicture(size: [32, 64], preset: LARGE) {
+                    uri
+                  }
+                }
+                ...Fragment
+              }
+            }
+
+            fragment Fragment on Node @argumentDefinitions(scale: {type: "Float", defaultValue: 1.5}) {
+              id
+            }
+        "#;
+        let ast = parse_executable(text, SourceLocationKey::standalone("test.graphql")).unwrap();
+        let definitions = build(&TEST_SCHEMA, &ast.definitions).unwrap();
+
+        let json = serde_json::to_string(&definitions).unwrap();
+        let deserialized: Vec<ExecutableDefinition> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, definitions);
+    }
+}
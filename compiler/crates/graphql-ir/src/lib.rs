@@ -13,11 +13,13 @@
 mod associated_data;
 mod build;
 mod constants;
+mod diagnostic_codes;
 mod errors;
 mod ir;
 pub mod node_identifier;
 mod program;
 mod signatures;
+mod stable_node_id;
 mod transform;
 mod validator;
 mod visitor;
@@ -33,10 +35,14 @@ pub use build::RelayMode;
 pub use build::DIRECTIVE_ARGUMENTS;
 pub use build::FIXME_FAT_INTERFACE;
 pub use constants::ARGUMENT_DEFINITION;
+pub use diagnostic_codes::diagnostic_code;
+pub use diagnostic_codes::diagnostic_codes;
 pub use ir::*;
 pub use program::Program;
 pub use signatures::ProvidedVariableMetadata;
 pub use signatures::UNUSED_LOCAL_VARIABLE_DEPRECATED;
+pub use stable_node_id::StableNodeId;
+pub use stable_node_id::StableNodeIds;
 pub use transform::transform_list;
 pub use transform::transform_list_multi;
 pub use transform::Transformed;
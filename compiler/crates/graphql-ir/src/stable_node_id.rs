@@ -0,0 +1,436 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Identifiers of IR nodes derived from their contents, unlike
+//! `PointerAddress`, which is only valid for the program in memory. The same
+//! node gets the same `StableNodeId` in another build or another process, so
+//! caches keyed by them can be kept in the saved state and reused by
+//! incremental builds.
+//!
+//! The identifier of a node covers what the node selects: the names and
+//! types of its fields from the schema, its arguments, directives and
+//! selections, and the identifiers of the fragments it spreads. It doesn't
+//! cover locations, nor the associated data of directives, which can't be
+//! hashed the same way in another process.
+
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use common::WithLocation;
+use fnv::FnvHashMap;
+use fnv::FnvHashSet;
+use fnv::FnvHasher;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use schema::FieldID;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+use schema::TypeReference;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct StableNodeId(u64);
+
+impl fmt::Display for StableNodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// The `StableNodeId`s of the definitions of a program. A fragment or an
+/// operation keeps its identifier as long as neither it, nor a fragment it
+/// spreads directly or transitively, nor the schema fields they select,
+/// change.
+#[derive(Debug, Default)]
+pub struct StableNodeIds {
+    fragments: FnvHashMap<FragmentDefinitionName, StableNodeId>,
+    operations: FnvHashMap<OperationDefinitionName, StableNodeId>,
+}
+
+impl StableNodeIds {
+    pub fn new(program: &Program) -> Self {
+        let mut ids = Self::default();
+        let mut visiting = FnvHashSet::default();
+        for fragment in program.fragments() {
+            ids.compute_fragment(program, fragment.name.item, &mut visiting);
+        }
+        for operation in program.operations() {
+            let mut hasher = NodeHasher::new(&program.schema, &ids.fragments);
+            hasher.operation(operation);
+            ids.operations.insert(operation.name.item, hasher.finish());
+        }
+        ids
+    }
+
+    pub fn fragment(&self, name: FragmentDefinitionName) -> Option<StableNodeId> {
+        self.fragments.get(&name).copied()
+    }
+
+    pub fn operation(&self, name: OperationDefinitionName) -> Option<StableNodeId> {
+        self.operations.get(&name).copied()
+    }
+
+    /// The identifier of a selection of the program, like a linked field.
+    pub fn selection(&self, schema: &SDLSchema, selection: &Selection) -> StableNodeId {
+        let mut hasher = NodeHasher::new(schema, &self.fragments);
+        hasher.selection(selection);
+        hasher.finish()
+    }
+
+    fn compute_fragment(
+        &mut self,
+        program: &Program,
+        name: FragmentDefinitionName,
+        visiting: &mut FnvHashSet<FragmentDefinitionName>,
+    ) {
+        if self.fragments.contains_key(&name) {
+            return;
+        }
+        let fragment = match program.fragment(name) {
+            Some(fragment) => fragment,
+            None => return,
+        };
+        // Fragments with cycles are reported by the validations, and only
+        // hash the names of the fragments of the cycle that they spread.
+        if !visiting.insert(name) {
+            return;
+        }
+        for spread in fragment
+            .selections
+            .iter()
+            .flat_map(|selection| selection.spreaded_fragments())
+        {
+            self.compute_fragment(program, spread.fragment.item, visiting);
+        }
+        visiting.remove(&name);
+
+        let mut hasher = NodeHasher::new(&program.schema, &self.fragments);
+        hasher.fragment(fragment);
+        let id = hasher.finish();
+        self.fragments.insert(name, id);
+    }
+}
+
+/// Hashes nodes with `FnvHasher`, which doesn't depend on the process, and
+/// the names of the interned strings and schema types, rather than their
+/// indices.
+struct NodeHasher<'a> {
+    schema: &'a SDLSchema,
+    fragments: &'a FnvHashMap<FragmentDefinitionName, StableNodeId>,
+    state: FnvHasher,
+}
+
+impl<'a> NodeHasher<'a> {
+    fn new(
+        schema: &'a SDLSchema,
+        fragments: &'a FnvHashMap<FragmentDefinitionName, StableNodeId>,
+    ) -> Self {
+        Self {
+            schema,
+            fragments,
+            state: FnvHasher::default(),
+        }
+    }
+
+    fn finish(&self) -> StableNodeId {
+        StableNodeId(self.state.finish())
+    }
+
+    fn str(&mut self, value: impl Lookup) {
+        value.lookup().hash(&mut self.state);
+    }
+
+    fn tag(&mut self, tag: u8) {
+        self.state.write_u8(tag);
+    }
+
+    fn len(&mut self, len: usize) {
+        self.state.write_usize(len);
+    }
+
+    fn operation(&mut self, operation: &OperationDefinition) {
+        self.tag(match operation.kind {
+            graphql_syntax::OperationKind::Query => 0,
+            graphql_syntax::OperationKind::Mutation => 1,
+            graphql_syntax::OperationKind::Subscription => 2,
+        });
+        self.str(operation.name.item.0);
+        self.type_(operation.type_);
+        self.variable_definitions(&operation.variable_definitions);
+        self.directives(&operation.directives);
+        self.selections(&operation.selections);
+    }
+
+    fn fragment(&mut self, fragment: &FragmentDefinition) {
+        self.str(fragment.name.item.0);
+        self.type_(fragment.type_condition);
+        self.variable_definitions(&fragment.variable_definitions);
+        self.variable_definitions(&fragment.used_global_variables);
+        self.directives(&fragment.directives);
+        self.selections(&fragment.selections);
+    }
+
+    fn variable_definitions(&mut self, definitions: &[VariableDefinition]) {
+        self.len(definitions.len());
+        for definition in definitions {
+            self.str(definition.name.item.0);
+            self.type_reference(&definition.type_);
+            match &definition.default_value {
+                Some(value) => {
+                    self.tag(1);
+                    self.constant_value(&value.item);
+                }
+                None => self.tag(0),
+            }
+            self.directives(&definition.directives);
+        }
+    }
+
+    fn selections(&mut self, selections: &[Selection]) {
+        self.len(selections.len());
+        for selection in selections {
+            self.selection(selection);
+        }
+    }
+
+    fn selection(&mut self, selection: &Selection) {
+        match selection {
+            Selection::FragmentSpread(spread) => {
+                self.tag(0);
+                self.str(spread.fragment.item.0);
+                if let Some(id) = self.fragments.get(&spread.fragment.item).copied() {
+                    id.0.hash(&mut self.state);
+                }
+                self.arguments(&spread.arguments);
+                self.directives(&spread.directives);
+            }
+            Selection::InlineFragment(fragment) => {
+                self.tag(1);
+                match fragment.type_condition {
+                    Some(type_) => {
+                        self.tag(1);
+                        self.type_(type_);
+                    }
+                    None => self.tag(0),
+                }
+                self.directives(&fragment.directives);
+                self.selections(&fragment.selections);
+            }
+            Selection::LinkedField(field) => {
+                self.tag(2);
+                self.field(&field.alias, field.definition.item);
+                self.arguments(&field.arguments);
+                self.directives(&field.directives);
+                self.selections(&field.selections);
+            }
+            Selection::ScalarField(field) => {
+                self.tag(3);
+                self.field(&field.alias, field.definition.item);
+                self.arguments(&field.arguments);
+                self.directives(&field.directives);
+            }
+            Selection::Condition(condition) => {
+                self.tag(4);
+                condition.passing_value.hash(&mut self.state);
+                match &condition.value {
+                    ConditionValue::Constant(value) => {
+                        self.tag(0);
+                        value.hash(&mut self.state);
+                    }
+                    ConditionValue::Variable(variable) => {
+                        self.tag(1);
+                        self.variable(variable);
+                    }
+                }
+                self.selections(&condition.selections);
+            }
+        }
+    }
+
+    fn field(&mut self, alias: &Option<WithLocation<StringKey>>, id: FieldID) {
+        match alias {
+            Some(alias) => {
+                self.tag(1);
+                self.str(alias.item);
+            }
+            None => self.tag(0),
+        }
+        let field = self.schema.field(id);
+        self.str(field.name.item);
+        match field.parent_type {
+            Some(parent_type) => {
+                self.tag(1);
+                self.type_(parent_type);
+            }
+            None => self.tag(0),
+        }
+        self.type_reference(&field.type_);
+    }
+
+    fn directives(&mut self, directives: &[Directive]) {
+        self.len(directives.len());
+        for directive in directives {
+            self.str(directive.name.item.0);
+            self.arguments(&directive.arguments);
+        }
+    }
+
+    fn arguments(&mut self, arguments: &[Argument]) {
+        self.len(arguments.len());
+        for argument in arguments {
+            self.str(argument.name.item.0);
+            self.value(&argument.value.item);
+        }
+    }
+
+    fn value(&mut self, value: &Value) {
+        match value {
+            Value::Constant(value) => {
+                self.tag(0);
+                self.constant_value(value);
+            }
+            Value::Variable(variable) => {
+                self.tag(1);
+                self.variable(variable);
+            }
+            Value::List(items) => {
+                self.tag(2);
+                self.len(items.len());
+                for item in items {
+                    self.value(item);
+                }
+            }
+            Value::Object(fields) => {
+                self.tag(3);
+                self.arguments(fields);
+            }
+        }
+    }
+
+    fn constant_value(&mut self, value: &ConstantValue) {
+        match value {
+            ConstantValue::Int(value) => {
+                self.tag(0);
+                value.hash(&mut self.state);
+            }
+            ConstantValue::Float(value) => {
+                self.tag(1);
+                value.as_float().to_bits().hash(&mut self.state);
+            }
+            ConstantValue::String(value) => {
+                self.tag(2);
+                self.str(*value);
+            }
+            ConstantValue::Boolean(value) => {
+                self.tag(3);
+                value.hash(&mut self.state);
+            }
+            ConstantValue::Null() => self.tag(4),
+            ConstantValue::Enum(value) => {
+                self.tag(5);
+                self.str(*value);
+            }
+            ConstantValue::List(items) => {
+                self.tag(6);
+                self.len(items.len());
+                for item in items {
+                    self.constant_value(item);
+                }
+            }
+            ConstantValue::Object(fields) => {
+                self.tag(7);
+                self.len(fields.len());
+                for field in fields {
+                    self.str(field.name.item.0);
+                    self.constant_value(&field.value.item);
+                }
+            }
+        }
+    }
+
+    fn variable(&mut self, variable: &Variable) {
+        self.str(variable.name.item.0);
+        self.type_reference(&variable.type_);
+    }
+
+    fn type_(&mut self, type_: Type) {
+        self.str(self.schema.get_type_name(type_));
+    }
+
+    fn type_reference(&mut self, type_: &TypeReference<Type>) {
+        match type_ {
+            TypeReference::Named(type_) => {
+                self.tag(0);
+                self.type_(*type_);
+            }
+            TypeReference::NonNull(of) => {
+                self.tag(1);
+                self.type_reference(of);
+            }
+            TypeReference::List(of) => {
+                self.tag(2);
+                self.type_reference(of);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common::SourceLocationKey;
+    use graphql_syntax::parse_executable;
+    use intern::string_key::Intern;
+    use relay_test_schema::TEST_SCHEMA;
+
+    use super::*;
+
+    fn ids(text: &str, file: &str) -> StableNodeIds {
+        let ast = parse_executable(text, SourceLocationKey::standalone(file)).unwrap();
+        let definitions = build(&TEST_SCHEMA, &ast.definitions).unwrap();
+        StableNodeIds::new(&Program::from_definitions(
+            Arc::clone(&TEST_SCHEMA),
+            definitions,
+        ))
+    }
+
+    #[test]
+    fn ids_change_with_the_contents_of_spread_fragments() {
+        let query = OperationDefinitionName("Query".intern());
+        let user = FragmentDefinitionName("User".intern());
+        let other = FragmentDefinitionName("Other".intern());
+        let original = ids(
+            "query Query { me { ...User } } fragment User on User { name } fragment Other on User { id }",
+            "a.graphql",
+        );
+        let moved = ids(
+            "fragment Other on User { id }\n\nquery Query {\n  me {\n    ...User\n  }\n}\n\nfragment User on User {\n  name\n}",
+            "b.graphql",
+        );
+        let changed_spread = ids(
+            "query Query { me { ...User } } fragment User on User { name id } fragment Other on User { id }",
+            "a.graphql",
+        );
+        let changed_other = ids(
+            "query Query { me { ...User } } fragment User on User { name } fragment Other on User { name }",
+            "a.graphql",
+        );
+
+        assert_eq!(original.operation(query), moved.operation(query));
+        assert_eq!(original.fragment(user), moved.fragment(user));
+        assert_ne!(original.fragment(user), changed_spread.fragment(user));
+        assert_ne!(original.operation(query), changed_spread.operation(query));
+        assert_eq!(original.operation(query), changed_other.operation(query));
+        assert_ne!(original.fragment(other), changed_other.fragment(other));
+    }
+}
@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<e573b168de2e50d455bd24bed56b02c6>>
+ * @generated SignedSource<<1a2980952c8b96cb8ad38ac964166887>>
  */
 
 mod parse_with_extensions;
@@ -25,3 +25,10 @@ fn client_fields_invalid() {
     let expected = include_str!("parse_with_extensions/fixtures/client-fields.invalid.expected");
     test_fixture(transform_fixture, "client-fields.invalid.graphql", "parse_with_extensions/fixtures/client-fields.invalid.expected", input, expected);
 }
+
+#[test]
+fn one_of_input_object_invalid() {
+    let input = include_str!("parse_with_extensions/fixtures/one-of-input-object.invalid.graphql");
+    let expected = include_str!("parse_with_extensions/fixtures/one-of-input-object.invalid.expected");
+    test_fixture(transform_fixture, "one-of-input-object.invalid.graphql", "parse_with_extensions/fixtures/one-of-input-object.invalid.expected", input, expected);
+}
@@ -0,0 +1,271 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A lossless view of GraphQL text: every token, with the whitespace, commas
+//! and comments the parser skips attached to it as trivia. The nodes of the
+//! AST parsed from the same text span their tokens, so together they form a
+//! concrete syntax tree that can be printed back exactly as it was written.
+
+use common::Span;
+use logos::Logos;
+
+use crate::lexer::TokenKind;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TriviaKind {
+    /// Spaces, tabs, line breaks and byte order marks
+    Whitespace,
+    /// Commas are insignificant in GraphQL
+    Comma,
+    /// A `#` comment, up to the end of its line
+    Comment,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SyntaxToken {
+    pub kind: TokenKind,
+    pub span: Span,
+    /// The trivia since the end of the line of the previous token
+    pub leading_trivia: Vec<Trivia>,
+    /// The trivia following the token on its line, like a trailing comment
+    pub trailing_trivia: Vec<Trivia>,
+}
+
+impl SyntaxToken {
+    pub fn trivia(&self) -> impl Iterator<Item = &Trivia> {
+        self.leading_trivia.iter().chain(self.trailing_trivia.iter())
+    }
+}
+
+/// The tokens of GraphQL text, with all of the text between them as trivia.
+/// The last token is an `EndOfFile` token, holding the trivia at the end.
+pub struct ConcreteSyntaxTree<'a> {
+    source: &'a str,
+    tokens: Vec<SyntaxToken>,
+}
+
+impl<'a> ConcreteSyntaxTree<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let mut lexer = TokenKind::lexer(source);
+        let mut tokens: Vec<SyntaxToken> = vec![];
+        let mut previous_end = 0;
+        loop {
+            let (kind, range) = match lexer.next() {
+                Some(TokenKind::Error) => (
+                    lexer.extras.error_token.take().unwrap_or(TokenKind::Error),
+                    lexer.span(),
+                ),
+                Some(kind) => (kind, lexer.span()),
+                None => (TokenKind::EndOfFile, source.len()..source.len()),
+            };
+            let mut leading_trivia = lex_trivia(source, previous_end, range.start);
+            if let Some(previous) = tokens.last_mut() {
+                let line_end = leading_trivia
+                    .iter()
+                    .position(|trivia| {
+                        trivia.kind == TriviaKind::Whitespace
+                            && span_text(source, trivia.span).contains(is_line_break)
+                    })
+                    .unwrap_or(leading_trivia.len());
+                previous.trailing_trivia = leading_trivia.drain(..line_end).collect();
+            }
+            tokens.push(SyntaxToken {
+                kind,
+                span: Span::new(range.start as u32, range.end as u32),
+                leading_trivia,
+                trailing_trivia: vec![],
+            });
+            if kind == TokenKind::EndOfFile {
+                break;
+            }
+            previous_end = range.end;
+        }
+        Self { source, tokens }
+    }
+
+    pub fn tokens(&self) -> &[SyntaxToken] {
+        &self.tokens
+    }
+
+    /// The tokens of a node of the AST, or any other span of the source.
+    pub fn tokens_in(&self, span: Span) -> &[SyntaxToken] {
+        let start = self
+            .tokens
+            .partition_point(|token| token.span.start < span.start);
+        let end = self
+            .tokens
+            .partition_point(|token| token.span.end <= span.end && token.span.start < span.end)
+            .max(start);
+        &self.tokens[start..end]
+    }
+
+    pub fn comments(&self) -> impl Iterator<Item = &Trivia> {
+        self.tokens
+            .iter()
+            .flat_map(|token| token.trivia())
+            .filter(|trivia| trivia.kind == TriviaKind::Comment)
+    }
+
+    /// The comments before a node of the AST, on the lines above it or before
+    /// it on its line.
+    pub fn leading_comments(&self, span: Span) -> Vec<Span> {
+        self.tokens_in(span)
+            .first()
+            .map_or_else(Vec::new, |token| comment_spans(&token.leading_trivia))
+    }
+
+    /// The comment after a node of the AST, on the line it ends on.
+    pub fn trailing_comment(&self, span: Span) -> Option<Span> {
+        self.tokens_in(span)
+            .last()
+            .and_then(|token| comment_spans(&token.trailing_trivia).pop())
+    }
+
+    pub fn text(&self, span: Span) -> &'a str {
+        span_text(self.source, span)
+    }
+
+    /// Prints the tokens and their trivia, which is the text the tree was
+    /// built from.
+    pub fn print(&self) -> String {
+        let mut output = String::with_capacity(self.source.len());
+        for token in &self.tokens {
+            for trivia in &token.leading_trivia {
+                output.push_str(self.text(trivia.span));
+            }
+            output.push_str(self.text(token.span));
+            for trivia in &token.trailing_trivia {
+                output.push_str(self.text(trivia.span));
+            }
+        }
+        output
+    }
+}
+
+/// Splits the text between two tokens into trivia. Line breaks start a new
+/// whitespace trivia, so that the trivia of a line can be told apart.
+fn lex_trivia(source: &str, start: usize, end: usize) -> Vec<Trivia> {
+    let mut trivia = vec![];
+    let mut index = start;
+    while index < end {
+        let rest = &source[index..end];
+        let (kind, length) = if rest.starts_with('#') {
+            (
+                TriviaKind::Comment,
+                rest.find(is_line_break).unwrap_or(rest.len()),
+            )
+        } else if rest.starts_with(',') {
+            (TriviaKind::Comma, 1)
+        } else {
+            let line_break = rest.find(is_line_break);
+            let length = match line_break {
+                // Keep the line break with the whitespace of the line it ends
+                Some(0) => rest.find(|c: char| !is_line_break(c)).unwrap_or(rest.len()),
+                Some(line_break) => line_break,
+                None => rest.len(),
+            };
+            let length = rest[..length]
+                .find(['#', ','])
+                .unwrap_or(length);
+            (TriviaKind::Whitespace, length)
+        };
+        trivia.push(Trivia {
+            kind,
+            span: Span::new(index as u32, (index + length) as u32),
+        });
+        index += length;
+    }
+    trivia
+}
+
+fn is_line_break(c: char) -> bool {
+    c == '\n' || c == '\r'
+}
+
+fn comment_spans(trivia: &[Trivia]) -> Vec<Span> {
+    trivia
+        .iter()
+        .filter(|trivia| trivia.kind == TriviaKind::Comment)
+        .map(|trivia| trivia.span)
+        .collect()
+}
+
+fn span_text(source: &str, span: Span) -> &str {
+    let (start, end) = span.as_usize();
+    &source[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use common::SourceLocationKey;
+
+    use super::*;
+    use crate::parse_executable;
+    use crate::ExecutableDefinition;
+
+    const SOURCE: &str = r##"# Leading comment
+fragment Foo on User { # After the brace
+  name(format: "# not a comment"),  # After the field
+
+  # Before the field
+  id
+}
+"##;
+
+    #[test]
+    fn prints_the_source() {
+        assert_eq!(ConcreteSyntaxTree::new(SOURCE).print(), SOURCE);
+        assert_eq!(ConcreteSyntaxTree::new("").print(), "");
+        assert_eq!(ConcreteSyntaxTree::new(" \r\n{a %}").print(), " \r\n{a %}");
+    }
+
+    #[test]
+    fn attaches_comments_to_tokens() {
+        let tree = ConcreteSyntaxTree::new(SOURCE);
+        let comments = tree
+            .comments()
+            .map(|comment| tree.text(comment.span))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            vec![
+                "# Leading comment",
+                "# After the brace",
+                "# After the field",
+                "# Before the field"
+            ]
+        );
+
+        let document = parse_executable(SOURCE, SourceLocationKey::generated()).unwrap();
+        let fragment = match &document.definitions[0] {
+            ExecutableDefinition::Fragment(fragment) => fragment,
+            ExecutableDefinition::Operation(_) => panic!("Expected a fragment"),
+        };
+        let leading_comments = tree.leading_comments(fragment.location.span());
+        assert_eq!(tree.text(leading_comments[0]), "# Leading comment");
+        let trailing_comment = tree.trailing_comment(fragment.selections.start.span);
+        assert_eq!(tree.text(trailing_comment.unwrap()), "# After the brace");
+
+        let name = fragment.selections.items[0].span();
+        assert_eq!(
+            tree.text(tree.trailing_comment(name).unwrap()),
+            "# After the field"
+        );
+        let id = fragment.selections.items[1].span();
+        assert_eq!(
+            tree.text(tree.leading_comments(id)[0]),
+            "# Before the field"
+        );
+        assert_eq!(tree.trailing_comment(id), None);
+    }
+}
@@ -9,6 +9,10 @@ use std::fmt;
 
 use logos::Lexer;
 use logos::Logos;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::string_literal::unescape;
 
 #[derive(Default, Eq, PartialEq)]
 pub struct TokenKindExtras {
@@ -19,7 +23,19 @@ pub struct TokenKindExtras {
 }
 
 /// Lexer for the GraphQL specification: http://spec.graphql.org/
-#[derive(Logos, Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Logos,
+    Copy,
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 #[logos(extras = TokenKindExtras)]
 pub enum TokenKind {
     #[regex(r"[ \t\r\n\f,\ufeff]+|#[^\n\r]*", logos::skip)]
@@ -27,7 +43,7 @@ pub enum TokenKind {
     Error,
 
     ErrorUnterminatedString,
-    ErrorUnsupportedStringCharacter,
+    ErrorInvalidEscapeSequence,
     ErrorUnterminatedBlockString,
     Empty,
 
@@ -110,7 +126,7 @@ pub enum TokenKind {
     BlockStringLiteral,
 }
 
-#[derive(Logos, Debug)]
+#[derive(Logos, Copy, Clone, Debug, Eq, PartialEq)]
 pub enum StringToken {
     #[error]
     Error,
@@ -121,23 +137,38 @@ pub enum StringToken {
     #[regex(r#"\\u[0-9A-Fa-f][0-9A-Fa-f][0-9A-Fa-f][0-9A-Fa-f]"#)]
     EscapedUnicode,
 
+    /// A leading and a trailing surrogate, which only encode a character
+    /// together
+    #[regex(r#"\\u[dD][89abAB][0-9A-Fa-f][0-9A-Fa-f]\\u[dD][c-fC-F][0-9A-Fa-f][0-9A-Fa-f]"#)]
+    EscapedSurrogatePair,
+
+    #[regex(r#"\\u\{[0-9A-Fa-f]+\}"#)]
+    EscapedVariableWidthUnicode,
+
     #[token("\"")]
     Quote,
 
     #[regex(r#"\n|\r|\r\n"#)]
     LineTerminator,
 
-    #[regex(r#"[\u0009\u0020\u0021\u0023-\u005B\u005D-\uFFFF]+"#)]
+    #[regex(r#"[^"\\\n\r]+"#)]
     StringCharacters,
 }
 
 fn lex_string(lexer: &mut Lexer<'_, TokenKind>) -> bool {
     let remainder = lexer.remainder();
     let mut string_lexer = StringToken::lexer(remainder);
+    let mut has_invalid_escape = false;
     while let Some(string_token) = string_lexer.next() {
         match string_token {
             StringToken::Quote => {
                 lexer.bump(string_lexer.span().end);
+                if has_invalid_escape {
+                    // The error token spans the whole string, the parser
+                    // points at the invalid escape sequence in it.
+                    lexer.extras.error_token = Some(TokenKind::ErrorInvalidEscapeSequence);
+                    return false;
+                }
                 return true;
             }
             StringToken::LineTerminator => {
@@ -145,12 +176,15 @@ fn lex_string(lexer: &mut Lexer<'_, TokenKind>) -> bool {
                 lexer.extras.error_token = Some(TokenKind::ErrorUnterminatedString);
                 return false;
             }
+            StringToken::StringCharacters => {}
+            StringToken::Error => has_invalid_escape = true,
             StringToken::EscapedCharacter
             | StringToken::EscapedUnicode
-            | StringToken::StringCharacters => {}
-            StringToken::Error => {
-                lexer.extras.error_token = Some(TokenKind::ErrorUnsupportedStringCharacter);
-                return false;
+            | StringToken::EscapedSurrogatePair
+            | StringToken::EscapedVariableWidthUnicode => {
+                if unescape(string_token, string_lexer.slice()).is_none() {
+                    has_invalid_escape = true;
+                }
             }
         }
     }
@@ -186,7 +220,7 @@ pub enum BlockStringToken {
     #[token("\"\"\"")]
     TripleQuote,
 
-    #[regex(r#"[\u0009\u000A\u000D\u0020-\uFFFF]"#)]
+    #[regex(r#"[^"\\]+|"|\\"#)]
     Other,
 }
 
@@ -222,7 +256,7 @@ impl fmt::Display for TokenKind {
             }
             TokenKind::StringLiteral => "string literal (e.g. '\"...\"')",
             TokenKind::ErrorUnterminatedString => "unterminated string",
-            TokenKind::ErrorUnsupportedStringCharacter => "unsupported character in string",
+            TokenKind::ErrorInvalidEscapeSequence => "invalid escape sequence in string",
             TokenKind::ErrorUnterminatedBlockString => "unterminated block string",
             TokenKind::Empty => "missing expected kind",
         };
@@ -431,6 +465,35 @@ mod tests {
         assert_eq!(lexer.slice(), "\"unterminated");
     }
 
+    #[test]
+    fn test_string_escape_lexing() {
+        let input = r#"
+            "\u00e9 \u{1F600} \uD83D\uDE00 😀"
+            "lone \uD83D surrogate" "invalid \x escape" "next"
+        "#;
+        let mut lexer = TokenKind::lexer(input);
+
+        assert_eq!(lexer.next(), Some(TokenKind::StringLiteral));
+        assert_eq!(lexer.slice(), r#""\u00e9 \u{1F600} \uD83D\uDE00 😀""#);
+
+        assert_eq!(lexer.next(), Some(TokenKind::Error));
+        assert_eq!(
+            lexer.extras.error_token.take(),
+            Some(TokenKind::ErrorInvalidEscapeSequence)
+        );
+        assert_eq!(lexer.slice(), r#""lone \uD83D surrogate""#);
+
+        assert_eq!(lexer.next(), Some(TokenKind::Error));
+        assert_eq!(
+            lexer.extras.error_token.take(),
+            Some(TokenKind::ErrorInvalidEscapeSequence)
+        );
+        assert_eq!(lexer.slice(), r#""invalid \x escape""#);
+
+        assert_eq!(lexer.next(), Some(TokenKind::StringLiteral));
+        assert_eq!(lexer.slice(), r#""next""#);
+    }
+
     #[test]
     fn test_invalid_character_lexing() {
         let input = r#"
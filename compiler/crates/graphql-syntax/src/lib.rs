@@ -10,21 +10,30 @@
 #![deny(clippy::all)]
 #![allow(clippy::large_enum_variant)]
 
+mod cst;
 mod lexer;
 mod node;
 mod parser;
 mod source;
+mod string_literal;
 mod syntax_error;
 mod utils;
 
 use common::DiagnosticsResult;
 use common::SourceLocationKey;
 use common::WithDiagnostics;
+pub use cst::ConcreteSyntaxTree;
+pub use cst::SyntaxToken;
+pub use cst::Trivia;
+pub use cst::TriviaKind;
 pub use lexer::TokenKind;
 pub use node::*;
 pub use parser::FragmentArgumentSyntaxKind;
 pub use parser::ParserFeatures;
 pub use source::GraphQLSource;
+pub use string_literal::is_block_string_value;
+pub use string_literal::write_block_string_literal;
+pub use string_literal::write_string_literal;
 pub use syntax_error::SyntaxError;
 pub use utils::*;
 
@@ -10,10 +10,26 @@ use core::fmt;
 use common::Named;
 use common::Span;
 use intern::string_key::StringKey;
+use intern::Lookup;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
 
 use super::primitive::*;
+use crate::write_string_literal;
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub enum ConstantValue {
     Int(IntNode),
     Float(FloatNode),
@@ -58,7 +74,7 @@ impl fmt::Display for ConstantValue {
         match self {
             ConstantValue::Int(value) => f.write_fmt(format_args!("{}", value)),
             ConstantValue::Float(value) => f.write_fmt(format_args!("{}", value)),
-            ConstantValue::String(value) => f.write_fmt(format_args!("\"{}\"", value)),
+            ConstantValue::String(value) => write_string_literal(f, value.value.lookup()),
             ConstantValue::Boolean(value) => f.write_fmt(format_args!("{}", value)),
             ConstantValue::Null(_) => f.write_str("null"),
             ConstantValue::Enum(value) => f.write_fmt(format_args!("{}", value)),
@@ -84,7 +100,17 @@ impl fmt::Display for ConstantValue {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct ConstantArgument {
     pub span: Span,
     pub name: Identifier,
@@ -105,7 +131,17 @@ impl Named for ConstantArgument {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct IntNode {
     pub token: Token,
     pub value: i64,
@@ -117,7 +153,17 @@ impl fmt::Display for IntNode {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct FloatNode {
     pub token: Token,
     pub value: FloatValue,
@@ -132,7 +178,17 @@ impl fmt::Display for FloatNode {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct StringNode {
     pub token: Token,
     pub value: StringKey,
@@ -144,7 +200,17 @@ impl fmt::Display for StringNode {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct EnumNode {
     pub token: Token,
     pub value: StringKey,
@@ -156,7 +222,17 @@ impl fmt::Display for EnumNode {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct BooleanNode {
     pub token: Token,
     pub value: bool,
@@ -184,6 +260,19 @@ impl FloatValue {
     }
 }
 
+/// Serialized as the number, rather than its bits.
+impl Serialize for FloatValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.as_float())
+    }
+}
+
+impl<'de> Deserialize<'de> for FloatValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        f64::deserialize(deserializer).map(FloatValue::new)
+    }
+}
+
 impl fmt::Debug for FloatValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_fmt(format_args!("{}", self.as_float()))
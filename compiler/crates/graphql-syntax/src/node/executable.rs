@@ -10,6 +10,8 @@ use std::fmt;
 use common::Location;
 use common::Span;
 use intern::string_key::StringKey;
+use serde::Deserialize;
+use serde::Serialize;
 
 use super::constant_value::*;
 use super::directive::Directive;
@@ -98,7 +100,7 @@ impl OperationDefinition {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub enum OperationKind {
     Query,
     Mutation,
@@ -12,16 +12,29 @@ use common::SourceLocationKey;
 use common::Span;
 use common::WithLocation;
 use intern::string_key::StringKey;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::lexer::TokenKind;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct Token {
     pub span: Span,
     pub kind: TokenKind,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Identifier {
     pub span: Span,
     pub token: Token,
@@ -52,7 +65,17 @@ impl Identifier {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct List<T> {
     pub span: Span,
     pub start: Token,
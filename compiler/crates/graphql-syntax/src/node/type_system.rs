@@ -9,6 +9,8 @@ use std::fmt;
 
 use common::Span;
 use intern::string_key::StringKey;
+use serde::Deserialize;
+use serde::Serialize;
 
 use super::constant_directive::ConstantDirective;
 use super::constant_value::ConstantValue;
@@ -74,6 +76,7 @@ impl fmt::Display for TypeSystemDefinition {
                 interfaces,
                 fields,
                 directives,
+                ..
             }) => write_object_helper(f, &name.value, interfaces, fields, directives, false),
             TypeSystemDefinition::ObjectTypeExtension(ObjectTypeExtension {
                 name,
@@ -97,6 +100,7 @@ impl fmt::Display for TypeSystemDefinition {
                 name,
                 directives,
                 members,
+                ..
             }) => write_union_type_definition_helper(f, &name.value, directives, members, false),
             TypeSystemDefinition::UnionTypeExtension(UnionTypeExtension {
                 name,
@@ -123,6 +127,7 @@ impl fmt::Display for TypeSystemDefinition {
                 name,
                 directives,
                 fields,
+                ..
             }) => {
                 write_input_object_type_definition_helper(f, &name.value, directives, fields, false)
             }
@@ -137,6 +142,7 @@ impl fmt::Display for TypeSystemDefinition {
                 name,
                 directives,
                 values,
+                ..
             }) => write_enum_type_definition_helper(f, &name.value, directives, values, false),
             TypeSystemDefinition::EnumTypeExtension(EnumTypeExtension {
                 name,
@@ -146,6 +152,7 @@ impl fmt::Display for TypeSystemDefinition {
             TypeSystemDefinition::ScalarTypeDefinition(ScalarTypeDefinition {
                 name,
                 directives,
+                ..
             }) => write_scalar_type_definition_helper(f, &name.value, directives, false),
             TypeSystemDefinition::ScalarTypeExtension(ScalarTypeExtension { name, directives }) => {
                 write_scalar_type_definition_helper(f, &name.value, directives, true)
@@ -201,6 +208,7 @@ pub struct ObjectTypeDefinition {
     pub interfaces: Vec<Identifier>,
     pub directives: Vec<ConstantDirective>,
     pub fields: Option<List<FieldDefinition>>,
+    pub description: Option<StringNode>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -217,6 +225,7 @@ pub struct InterfaceTypeDefinition {
     pub interfaces: Vec<Identifier>,
     pub directives: Vec<ConstantDirective>,
     pub fields: Option<List<FieldDefinition>>,
+    pub description: Option<StringNode>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -232,6 +241,7 @@ pub struct UnionTypeDefinition {
     pub name: Identifier,
     pub directives: Vec<ConstantDirective>,
     pub members: Vec<Identifier>,
+    pub description: Option<StringNode>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -245,6 +255,7 @@ pub struct UnionTypeExtension {
 pub struct ScalarTypeDefinition {
     pub name: Identifier,
     pub directives: Vec<ConstantDirective>,
+    pub description: Option<StringNode>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -258,6 +269,7 @@ pub struct EnumTypeDefinition {
     pub name: Identifier,
     pub directives: Vec<ConstantDirective>,
     pub values: Option<List<EnumValueDefinition>>,
+    pub description: Option<StringNode>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -272,6 +284,7 @@ pub struct InputObjectTypeDefinition {
     pub name: Identifier,
     pub directives: Vec<ConstantDirective>,
     pub fields: Option<List<InputValueDefinition>>,
+    pub description: Option<StringNode>,
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -285,6 +298,7 @@ pub struct InputObjectTypeExtension {
 pub struct EnumValueDefinition {
     pub name: Identifier,
     pub directives: Vec<ConstantDirective>,
+    pub description: Option<StringNode>,
 }
 
 impl fmt::Display for EnumValueDefinition {
@@ -304,7 +318,18 @@ pub struct DirectiveDefinition {
     pub hack_source: Option<StringNode>,
 }
 
-#[derive(PartialEq, Eq, Ord, PartialOrd, Hash, Debug, Clone, Copy)]
+#[derive(
+    PartialEq,
+    Eq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize
+)]
 pub enum DirectiveLocation {
     Query,
     Mutation,
@@ -369,6 +394,7 @@ pub struct InputValueDefinition {
     pub type_: TypeAnnotation,
     pub default_value: Option<ConstantValue>,
     pub directives: Vec<ConstantDirective>,
+    pub description: Option<StringNode>,
 }
 
 impl fmt::Display for InputValueDefinition {
@@ -549,7 +575,7 @@ fn write_directive_definition_helper(
     f: &mut fmt::Formatter<'_>,
     name: &StringKey,
     arguments: &Option<List<InputValueDefinition>>,
-    _repeatable: &bool,
+    repeatable: &bool,
     locations: &[DirectiveLocation],
     _description: &Option<StringNode>,
     _hack_source: &Option<StringNode>,
@@ -558,6 +584,9 @@ fn write_directive_definition_helper(
     if let Some(arguments) = arguments.as_ref() {
         write_arguments(f, &arguments.items)?;
     }
+    if *repeatable {
+        write!(f, " repeatable")?;
+    }
     write!(f, " on ")?;
     write_list(f, locations, " | ")?;
     writeln!(f)
@@ -18,11 +18,12 @@ use logos::Logos;
 
 use crate::lexer::TokenKind;
 use crate::node::*;
+use crate::string_literal::parse_string_literal;
 use crate::syntax_error::SyntaxError;
 
 type ParseResult<T> = Result<T, ()>;
 
-#[derive(Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum FragmentArgumentSyntaxKind {
     #[default]
     None,
@@ -30,7 +31,7 @@ pub enum FragmentArgumentSyntaxKind {
     SpreadArgumentsAndFragmentVariableDefinitions,
 }
 
-#[derive(Default)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct ParserFeatures {
     /// Whether and how to enable the experimental fragment variables definitions syntax
     pub fragment_argument_capability: FragmentArgumentSyntaxKind,
@@ -384,22 +385,22 @@ impl<'a> Parser<'a> {
                 self.parse_schema_definition()?,
             )),
             "scalar" => Ok(TypeSystemDefinition::ScalarTypeDefinition(
-                self.parse_scalar_type_definition()?,
+                self.parse_scalar_type_definition(description)?,
             )),
             "type" => Ok(TypeSystemDefinition::ObjectTypeDefinition(
-                self.parse_object_type_definition()?,
+                self.parse_object_type_definition(description)?,
             )),
             "interface" => Ok(TypeSystemDefinition::InterfaceTypeDefinition(
-                self.parse_interface_type_definition()?,
+                self.parse_interface_type_definition(description)?,
             )),
             "union" => Ok(TypeSystemDefinition::UnionTypeDefinition(
-                self.parse_union_type_definition()?,
+                self.parse_union_type_definition(description)?,
             )),
             "enum" => Ok(TypeSystemDefinition::EnumTypeDefinition(
-                self.parse_enum_type_definition()?,
+                self.parse_enum_type_definition(description)?,
             )),
             "input" => Ok(TypeSystemDefinition::InputObjectTypeDefinition(
-                self.parse_input_object_type_definition()?,
+                self.parse_input_object_type_definition(description)?,
             )),
             "directive" => Ok(TypeSystemDefinition::DirectiveDefinition(
                 self.parse_directive_definition(description, hack_source)?,
@@ -534,7 +535,10 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_object_type_definition(&mut self) -> ParseResult<ObjectTypeDefinition> {
+    fn parse_object_type_definition(
+        &mut self,
+        description: Option<StringNode>,
+    ) -> ParseResult<ObjectTypeDefinition> {
         self.parse_keyword("type")?;
         let name = self.parse_identifier()?;
         let interfaces = self.parse_implements_interfaces()?;
@@ -545,10 +549,14 @@ impl<'a> Parser<'a> {
             interfaces,
             directives,
             fields,
+            description,
         })
     }
 
-    fn parse_interface_type_definition(&mut self) -> ParseResult<InterfaceTypeDefinition> {
+    fn parse_interface_type_definition(
+        &mut self,
+        description: Option<StringNode>,
+    ) -> ParseResult<InterfaceTypeDefinition> {
         self.parse_keyword("interface")?;
         let name = self.parse_identifier()?;
         let interfaces = self.parse_implements_interfaces()?;
@@ -559,6 +567,7 @@ impl<'a> Parser<'a> {
             interfaces,
             directives,
             fields,
+            description,
         })
     }
 
@@ -566,7 +575,10 @@ impl<'a> Parser<'a> {
      * UnionTypeDefinition :
      *   - Description? union Name Directives? UnionMemberTypes?
      */
-    fn parse_union_type_definition(&mut self) -> ParseResult<UnionTypeDefinition> {
+    fn parse_union_type_definition(
+        &mut self,
+        description: Option<StringNode>,
+    ) -> ParseResult<UnionTypeDefinition> {
         self.parse_keyword("union")?;
         let name = self.parse_identifier()?;
         let directives = self.parse_constant_directives()?;
@@ -575,6 +587,7 @@ impl<'a> Parser<'a> {
             name,
             directives,
             members,
+            description,
         })
     }
 
@@ -616,7 +629,10 @@ impl<'a> Parser<'a> {
      * EnumTypeDefinition :
      *   - Description? enum Name Directives? EnumValuesDefinition?
      */
-    fn parse_enum_type_definition(&mut self) -> ParseResult<EnumTypeDefinition> {
+    fn parse_enum_type_definition(
+        &mut self,
+        description: Option<StringNode>,
+    ) -> ParseResult<EnumTypeDefinition> {
         self.parse_keyword("enum")?;
         let name = self.parse_identifier()?;
         let directives = self.parse_constant_directives()?;
@@ -625,6 +641,7 @@ impl<'a> Parser<'a> {
             name,
             directives,
             values,
+            description,
         })
     }
 
@@ -662,10 +679,14 @@ impl<'a> Parser<'a> {
      * EnumValue : Name
      */
     fn parse_enum_value_definition(&mut self) -> ParseResult<EnumValueDefinition> {
-        self.parse_optional_description();
+        let description = self.parse_optional_description();
         let name = self.parse_identifier()?;
         let directives = self.parse_constant_directives()?;
-        Ok(EnumValueDefinition { name, directives })
+        Ok(EnumValueDefinition {
+            name,
+            directives,
+            description,
+        })
     }
 
     /**
@@ -725,11 +746,18 @@ impl<'a> Parser<'a> {
     /**
      * ScalarTypeDefinition : Description? scalar Name Directives?
      */
-    fn parse_scalar_type_definition(&mut self) -> ParseResult<ScalarTypeDefinition> {
+    fn parse_scalar_type_definition(
+        &mut self,
+        description: Option<StringNode>,
+    ) -> ParseResult<ScalarTypeDefinition> {
         self.parse_keyword("scalar")?;
         let name = self.parse_identifier()?;
         let directives = self.parse_constant_directives()?;
-        Ok(ScalarTypeDefinition { name, directives })
+        Ok(ScalarTypeDefinition {
+            name,
+            directives,
+            description,
+        })
     }
 
     /**
@@ -747,7 +775,10 @@ impl<'a> Parser<'a> {
      * InputObjectTypeDefinition :
      *   - Description? input Name Directives? InputFieldsDefinition?
      */
-    fn parse_input_object_type_definition(&mut self) -> ParseResult<InputObjectTypeDefinition> {
+    fn parse_input_object_type_definition(
+        &mut self,
+        description: Option<StringNode>,
+    ) -> ParseResult<InputObjectTypeDefinition> {
         self.parse_keyword("input")?;
         let name = self.parse_identifier()?;
         let directives = self.parse_constant_directives()?;
@@ -756,6 +787,7 @@ impl<'a> Parser<'a> {
             name,
             directives,
             fields,
+            description,
         })
     }
 
@@ -900,7 +932,7 @@ impl<'a> Parser<'a> {
             TokenKind::StringLiteral => {
                 let token = self.parse_token();
                 let source = self.source(&token);
-                let value = source[1..source.len() - 1].to_string().intern();
+                let value = clean_string_literal(source).intern();
                 Some(StringNode { token, value })
             }
             TokenKind::BlockStringLiteral => {
@@ -921,7 +953,7 @@ impl<'a> Parser<'a> {
             TokenKind::StringLiteral => {
                 let token = self.parse_token();
                 let source = self.source(&token);
-                let value = source[1..source.len() - 1].to_string().intern();
+                let value = clean_string_literal(source).intern();
                 Some(StringNode { token, value })
             }
             TokenKind::BlockStringLiteral => {
@@ -989,7 +1021,7 @@ impl<'a> Parser<'a> {
      *   - Description? Name : Type DefaultValue? Directives?
      */
     fn parse_input_value_def(&mut self) -> ParseResult<InputValueDefinition> {
-        self.parse_optional_description();
+        let description = self.parse_optional_description();
         let name = self.parse_identifier()?;
         self.parse_kind(TokenKind::Colon)?;
         let type_ = self.parse_type_annotation()?;
@@ -1004,6 +1036,7 @@ impl<'a> Parser<'a> {
             type_,
             default_value,
             directives,
+            description,
         })
     }
 
@@ -1354,7 +1387,24 @@ impl<'a> Parser<'a> {
         let start = self.index();
         let spread = self.parse_kind(TokenKind::Spread)?;
         let is_on_keyword = self.peek_keyword("on");
-        if !is_on_keyword && self.peek_token_kind() == TokenKind::Identifier {
+        if matches!(
+            self.peek_token_kind(),
+            TokenKind::CloseBrace | TokenKind::EndOfFile
+        ) {
+            // A dangling spread, the name of the fragment is yet to be typed
+            let name = self.empty_identifier();
+            self.record_error(Diagnostic::error(
+                SyntaxError::ExpectedFragmentSpreadOrInlineFragment,
+                Location::new(self.source_location, spread.span),
+            ));
+            Ok(Selection::FragmentSpread(FragmentSpread {
+                span: Span::new(start, self.end_index),
+                spread,
+                name,
+                arguments: None,
+                directives: vec![],
+            }))
+        } else if !is_on_keyword && self.peek_token_kind() == TokenKind::Identifier {
             // fragment spread
             let name = self.parse_identifier()?;
             let arguments = if self.features.supports_spread_arguments_syntax() {
@@ -1401,7 +1451,10 @@ impl<'a> Parser<'a> {
             let peek_kind = self.peek_token_kind();
             if peek_kind == TokenKind::CloseParen {
                 break;
-            } else if peek_kind == TokenKind::OpenBrace || peek_kind == TokenKind::CloseBrace {
+            } else if matches!(
+                peek_kind,
+                TokenKind::OpenBrace | TokenKind::CloseBrace | TokenKind::EndOfFile
+            ) {
                 self.record_error(Diagnostic::error(
                     SyntaxError::Expected(TokenKind::CloseParen),
                     Location::new(self.source_location, self.peek().span),
@@ -1709,7 +1762,7 @@ impl<'a> Parser<'a> {
         let source = self.source(&token);
         match &token.kind {
             TokenKind::StringLiteral => {
-                let value = source[1..source.len() - 1].to_string();
+                let value = clean_string_literal(source);
                 Ok(ConstantValue::String(StringNode {
                     token,
                     value: value.intern(),
@@ -1783,10 +1836,14 @@ impl<'a> Parser<'a> {
                 self.record_error(error);
                 Err(())
             }
-            TokenKind::ErrorUnsupportedStringCharacter => {
+            TokenKind::ErrorInvalidEscapeSequence => {
+                // Point at the escape sequence rather than the whole string
+                let span = parse_string_literal(source)
+                    .err()
+                    .map_or(token.span, |span| span.with_offset(token.span.start));
                 let error = Diagnostic::error(
-                    SyntaxError::UnsupportedStringCharacter,
-                    Location::new(self.source_location, token.span),
+                    SyntaxError::InvalidEscapeSequence,
+                    Location::new(self.source_location, span),
                 );
                 self.record_error(error);
                 Err(())
@@ -1830,6 +1887,26 @@ impl<'a> Parser<'a> {
             return Err(());
         }
 
+        if matches!(
+            self.peek_token_kind(),
+            TokenKind::CloseParen
+                | TokenKind::CloseBrace
+                | TokenKind::CloseBracket
+                | TokenKind::EndOfFile
+        ) {
+            // The name of the variable is yet to be typed, e.g. `(size: $)`
+            let token = self.empty_token();
+            self.record_error(Diagnostic::error(
+                SyntaxError::ExpectedVariableIdentifier,
+                Location::new(self.source_location, dollar_token.span),
+            ));
+            return Ok(VariableIdentifier {
+                span: Span::new(start, token.span.end),
+                token,
+                name: "".intern(),
+            });
+        }
+
         let token = self.parse_token();
         if token.kind == TokenKind::Identifier {
             let name = self.source(&token).intern();
@@ -1966,6 +2043,21 @@ impl<'a> Parser<'a> {
         let start = self.parse_token();
         let mut items = vec![];
         while !self.peek_kind(end_kind) {
+            if self.peek_kind(TokenKind::EndOfFile) {
+                // The list is still being typed at the end of the document: keep
+                // the items parsed so far, and end the list after the last one.
+                self.record_error(Diagnostic::error(
+                    SyntaxError::Expected(end_kind),
+                    Location::new(self.source_location, start.span),
+                ));
+                let end = self.empty_token();
+                return Ok(List {
+                    span: Span::new(start.span.start, end.span.end),
+                    start,
+                    items,
+                    end,
+                });
+            }
             items.push(parse(self)?);
         }
         let end = self.parse_kind(end_kind)?;
@@ -2150,13 +2242,18 @@ impl<'a> Parser<'a> {
     }
 }
 
-// https://spec.graphql.org/June2018/#sec-String-Value
-fn clean_block_string_literal(source: &str) -> String {
-    let inner = &source[3..source.len() - 3];
-    let common_indent = get_common_indent(inner);
+// https://spec.graphql.org/October2021/#sec-String-Value
+fn clean_string_literal(source: &str) -> String {
+    // The lexer only accepts strings with valid escape sequences
+    parse_string_literal(source).unwrap_or_else(|_| source[1..source.len() - 1].to_string())
+}
+
+// https://spec.graphql.org/October2021/#BlockStringValue()
+pub(crate) fn clean_block_string_literal(source: &str) -> String {
+    let inner = source[3..source.len() - 3].replace("\\\"\"\"", "\"\"\"");
+    let common_indent = get_common_indent(&inner);
 
-    let mut formatted_lines = inner
-        .lines()
+    let mut formatted_lines = block_string_lines(&inner)
         .enumerate()
         .map(|(i, line)| {
             if i == 0 {
@@ -2185,7 +2282,7 @@ fn clean_block_string_literal(source: &str) -> String {
 }
 
 fn get_common_indent(source: &str) -> usize {
-    let lines = source.lines().skip(1);
+    let lines = block_string_lines(source).skip(1);
     let mut common_indent: Option<usize> = None;
     for line in lines {
         if let Some((first_index, _)) = line.match_indices(is_not_whitespace).next() {
@@ -2197,6 +2294,13 @@ fn get_common_indent(source: &str) -> usize {
     common_indent.unwrap_or(0)
 }
 
+/// Unlike `str::lines`, also splits on a carriage return on its own.
+fn block_string_lines(source: &str) -> impl Iterator<Item = &str> {
+    source
+        .split("\r\n")
+        .flat_map(|line| line.split(['\n', '\r']))
+}
+
 fn line_is_whitespace(line: &str) -> bool {
     !line.contains(is_not_whitespace)
 }
@@ -2280,6 +2384,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn clean_block_string_literal_splits_all_line_terminators() {
+        let actual = clean_block_string_literal(&triple_quote("\r\n  One\r    Two\n  Three\r\n"));
+        assert_eq!(actual, "One\n  Two\nThree");
+    }
+
+    #[test]
+    fn clean_block_string_literal_unescapes_triple_quotes() {
+        let actual = clean_block_string_literal(&triple_quote("Hello \\\"\"\" \\n world!"));
+        assert_eq!(actual, "Hello \"\"\" \\n world!");
+    }
+
     #[test]
     fn clean_block_string_literal_treats_tab_and_space_as_equal() {
         let actual = clean_block_string_literal(&triple_quote(
@@ -0,0 +1,243 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The values of string literals, and printing values back as string
+//! literals the way graphql-js does, so that printed documents hash the same.
+
+use std::fmt;
+use std::fmt::Write;
+
+use common::Span;
+use logos::Logos;
+
+use crate::lexer::StringToken;
+
+/// The value of a (quoted) string literal, with its escape sequences
+/// replaced. Returns the span of the first invalid escape sequence otherwise.
+pub(crate) fn parse_string_literal(source: &str) -> Result<String, Span> {
+    let inner = &source[1..source.len() - 1];
+    let mut value = String::with_capacity(inner.len());
+    let mut lexer = StringToken::lexer(inner);
+    while let Some(token) = lexer.next() {
+        if token == StringToken::StringCharacters {
+            value.push_str(lexer.slice());
+        } else if let Some(character) = unescape(token, lexer.slice()) {
+            value.push(character);
+        } else {
+            let start = lexer.span().start;
+            let end = if token == StringToken::Error {
+                // A backslash that doesn't start an escape sequence, and the
+                // character after it
+                start + 1 + inner[start + 1..].chars().next().map_or(0, char::len_utf8)
+            } else {
+                lexer.span().end
+            };
+            // Offset by the opening quote
+            return Err(Span::new(start as u32 + 1, end as u32 + 1));
+        }
+    }
+    Ok(value)
+}
+
+/// The character an escape sequence stands for, or None if it doesn't encode
+/// a Unicode scalar value, like a lone surrogate.
+pub(crate) fn unescape(token: StringToken, slice: &str) -> Option<char> {
+    let code_point = |hex: &str| u32::from_str_radix(hex, 16).ok();
+    match token {
+        StringToken::EscapedCharacter => match slice.as_bytes()[1] {
+            b'b' => Some('\u{0008}'),
+            b'f' => Some('\u{000C}'),
+            b'n' => Some('\n'),
+            b'r' => Some('\r'),
+            b't' => Some('\t'),
+            // '"', '\' and '/'
+            escaped => Some(escaped as char),
+        },
+        StringToken::EscapedUnicode => char::from_u32(code_point(&slice[2..])?),
+        StringToken::EscapedSurrogatePair => {
+            let leading = code_point(&slice[2..6])?;
+            let trailing = code_point(&slice[8..12])?;
+            char::from_u32(0x10000 + ((leading - 0xD800) << 10) + (trailing - 0xDC00))
+        }
+        StringToken::EscapedVariableWidthUnicode => {
+            let hex = &slice[3..slice.len() - 1];
+            // graphql-js reads at most 8 digits
+            if hex.len() > 8 {
+                None
+            } else {
+                char::from_u32(code_point(hex)?)
+            }
+        }
+        StringToken::Error
+        | StringToken::Quote
+        | StringToken::LineTerminator
+        | StringToken::StringCharacters => None,
+    }
+}
+
+/// Prints a value as a string literal, escaping the same characters as
+/// `printString` in graphql-js.
+pub fn write_string_literal(writer: &mut impl Write, value: &str) -> fmt::Result {
+    writer.write_char('"')?;
+    for character in value.chars() {
+        match character {
+            '"' => writer.write_str("\\\"")?,
+            '\\' => writer.write_str("\\\\")?,
+            '\u{0008}' => writer.write_str("\\b")?,
+            '\u{000C}' => writer.write_str("\\f")?,
+            '\n' => writer.write_str("\\n")?,
+            '\r' => writer.write_str("\\r")?,
+            '\t' => writer.write_str("\\t")?,
+            '\u{0000}'..='\u{001F}' | '\u{007F}'..='\u{009F}' => {
+                write!(writer, "\\u{:04X}", character as u32)?
+            }
+            _ => writer.write_char(character)?,
+        }
+    }
+    writer.write_char('"')
+}
+
+/// Whether a value reads back the same from the block string
+/// `write_block_string_literal` prints: reading a block string drops its
+/// blank first and last lines, the indentation its lines share, and turns
+/// carriage returns into line breaks.
+pub fn is_block_string_value(value: &str) -> bool {
+    let is_whitespace = |c: char| c == ' ' || c == '\t';
+    let is_blank = |line: &str| line.chars().all(is_whitespace);
+    let lines = value.split('\n').collect::<Vec<_>>();
+    !value.contains('\r')
+        && !is_blank(lines[0])
+        && !is_blank(lines[lines.len() - 1])
+        && lines
+            .iter()
+            .any(|&line| !line.starts_with(is_whitespace) && !is_blank(line))
+}
+
+/// Prints a value as a block string, with the lines of the value on their own
+/// lines indented by `indent`, and the closing quotes by `closing_indent`.
+/// The value should be one `is_block_string_value` accepts.
+pub fn write_block_string_literal(
+    writer: &mut impl Write,
+    value: &str,
+    indent: &str,
+    closing_indent: &str,
+) -> fmt::Result {
+    writer.write_str("\"\"\"")?;
+    for line in value.split('\n') {
+        writer.write_char('\n')?;
+        if !line.is_empty() {
+            writer.write_str(indent)?;
+            writer.write_str(&line.replace("\"\"\"", "\\\"\"\""))?;
+        }
+    }
+    writer.write_char('\n')?;
+    writer.write_str(closing_indent)?;
+    writer.write_str("\"\"\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::clean_block_string_literal;
+
+    fn print(value: &str) -> String {
+        let mut output = String::new();
+        write_string_literal(&mut output, value).unwrap();
+        output
+    }
+
+    #[test]
+    fn parses_escaped_characters() {
+        assert_eq!(
+            parse_string_literal(r#""\"quoted\" \\ \/ \b\f\n\r\t""#),
+            Ok("\"quoted\" \\ / \u{8}\u{c}\n\r\t".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_unicode_escapes() {
+        assert_eq!(
+            parse_string_literal(r#""é \u{1F600} 😀 \u{00000041}""#),
+            Ok("é 😀 😀 A".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_escapes() {
+        assert_eq!(parse_string_literal(r#""ab\x""#), Err(Span::new(3, 5)));
+        assert_eq!(parse_string_literal(r#""é\é""#), Err(Span::new(3, 6)));
+        // Lone surrogates
+        assert_eq!(parse_string_literal(r#""\uD83D""#), Err(Span::new(1, 7)));
+        assert_eq!(
+            parse_string_literal(r#""\uDE00\uD83D""#),
+            Err(Span::new(1, 7))
+        );
+        assert_eq!(
+            parse_string_literal(r#""a\u{D83D}""#),
+            Err(Span::new(2, 10))
+        );
+        // Above U+10FFFF, or more than 8 digits
+        assert_eq!(
+            parse_string_literal(r#""\u{110000}""#),
+            Err(Span::new(1, 11))
+        );
+        assert_eq!(
+            parse_string_literal(r#""\u{000000041}""#),
+            Err(Span::new(1, 14))
+        );
+    }
+
+    #[test]
+    fn prints_like_graphql_js() {
+        assert_eq!(print("plain é 😀"), r#""plain é 😀""#);
+        assert_eq!(print("\"\\/\u{8}\u{c}\n\r\t"), r#""\"\\/\b\f\n\r\t""#);
+        assert_eq!(
+            print("\u{0}\u{b}\u{1f}\u{7f}\u{9f}\u{a0}"),
+            "\"\\u0000\\u000B\\u001F\\u007F\\u009F\u{a0}\""
+        );
+    }
+
+    #[test]
+    fn prints_parsed_values_back() {
+        let source = r#""a \"b\" \\ \u{1F600}""#;
+        assert_eq!(
+            print(&parse_string_literal(source).unwrap()),
+            r#""a \"b\" \\ 😀""#
+        );
+    }
+
+    #[test]
+    fn prints_block_strings_that_read_back_the_same() {
+        for value in [
+            "one line",
+            "first\n  indented\n\nlast",
+            "  indented first\nsecond",
+            "quotes \"\"\" and \\\"\"\"",
+        ] {
+            assert!(is_block_string_value(value), "{:?}", value);
+            let mut output = String::new();
+            write_block_string_literal(&mut output, value, "    ", "  ").unwrap();
+            assert_eq!(clean_block_string_literal(&output), value);
+        }
+        let mut output = String::new();
+        write_block_string_literal(&mut output, "a\n\n  b", "  ", "").unwrap();
+        assert_eq!(output, "\"\"\"\n  a\n\n    b\n\"\"\"");
+    }
+
+    #[test]
+    fn rejects_block_strings_that_read_back_differently() {
+        for value in [
+            "",
+            "\nafter blank",
+            "before blank\n  ",
+            "  a\n  b",
+            "a\r\nb",
+        ] {
+            assert!(!is_block_string_value(value), "{:?}", value);
+        }
+    }
+}
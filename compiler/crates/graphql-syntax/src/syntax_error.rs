@@ -39,6 +39,8 @@ pub enum SyntaxError {
     ExpectedVariable,
     #[error("Expected a spread ('...')")]
     ExpectedSpread,
+    #[error("Expected a fragment name or an inline fragment after the spread ('...')")]
+    ExpectedFragmentSpreadOrInlineFragment,
     #[error("Expected an argument")]
     ExpectedArgument,
     #[error("Expected the list to be non-empty")]
@@ -57,8 +59,8 @@ pub enum SyntaxError {
     InvalidFloatMissingZeroLiteral,
     #[error("Unsupported character")]
     UnsupportedCharacter,
-    #[error("String containing an invalid character")]
-    UnsupportedStringCharacter,
+    #[error("Invalid escape sequence in string")]
+    InvalidEscapeSequence,
     #[error("Unterminated string literal (strings cannot contain unescaped line breaks)")]
     UnterminatedString,
     #[error("Unterminated block string literal")]
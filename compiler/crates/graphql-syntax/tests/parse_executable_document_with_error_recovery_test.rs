@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<74f4b149daa4ad7e9564404219116b8d>>
+ * @generated SignedSource<<eafc62f57d6420f2b9f83c465e7d1da3>>
  */
 
 mod parse_executable_document_with_error_recovery;
@@ -82,6 +82,13 @@ fn argument_without_closing_paren() {
     test_fixture(transform_fixture, "argument-without-closing-paren.graphql", "parse_executable_document_with_error_recovery/fixtures/argument-without-closing-paren.expected", input, expected);
 }
 
+#[test]
+fn dangling_spread() {
+    let input = include_str!("parse_executable_document_with_error_recovery/fixtures/dangling-spread.graphql");
+    let expected = include_str!("parse_executable_document_with_error_recovery/fixtures/dangling-spread.expected");
+    test_fixture(transform_fixture, "dangling-spread.graphql", "parse_executable_document_with_error_recovery/fixtures/dangling-spread.expected", input, expected);
+}
+
 #[test]
 fn directive_without_name() {
     let input = include_str!("parse_executable_document_with_error_recovery/fixtures/directive-without-name.graphql");
@@ -117,9 +124,23 @@ fn type_in_argument_value() {
     test_fixture(transform_fixture, "type-in-argument-value.graphql", "parse_executable_document_with_error_recovery/fixtures/type-in-argument-value.expected", input, expected);
 }
 
+#[test]
+fn unclosed_selection_set() {
+    let input = include_str!("parse_executable_document_with_error_recovery/fixtures/unclosed-selection-set.graphql");
+    let expected = include_str!("parse_executable_document_with_error_recovery/fixtures/unclosed-selection-set.expected");
+    test_fixture(transform_fixture, "unclosed-selection-set.graphql", "parse_executable_document_with_error_recovery/fixtures/unclosed-selection-set.expected", input, expected);
+}
+
 #[test]
 fn variable_definition_with_directive() {
     let input = include_str!("parse_executable_document_with_error_recovery/fixtures/variable-definition-with-directive.graphql");
     let expected = include_str!("parse_executable_document_with_error_recovery/fixtures/variable-definition-with-directive.expected");
     test_fixture(transform_fixture, "variable-definition-with-directive.graphql", "parse_executable_document_with_error_recovery/fixtures/variable-definition-with-directive.expected", input, expected);
 }
+
+#[test]
+fn variable_without_name() {
+    let input = include_str!("parse_executable_document_with_error_recovery/fixtures/variable-without-name.graphql");
+    let expected = include_str!("parse_executable_document_with_error_recovery/fixtures/variable-without-name.expected");
+    test_fixture(transform_fixture, "variable-without-name.graphql", "parse_executable_document_with_error_recovery/fixtures/variable-without-name.expected", input, expected);
+}
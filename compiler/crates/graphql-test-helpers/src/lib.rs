@@ -27,11 +27,15 @@ use graphql_text_printer::PrinterOptions;
 use relay_test_schema::get_test_schema;
 use relay_test_schema::get_test_schema_with_located_extensions;
 
+/// Separates the documents of a fixture from the schema extensions that are
+/// added to the test schema for it.
+pub const EXTENSIONS_SEPARATOR: &str = "%extensions%";
+
 pub fn apply_transform_for_test<T>(fixture: &Fixture<'_>, transform: T) -> Result<String, String>
 where
     T: Fn(&Program) -> DiagnosticsResult<Program>,
 {
-    let parts: Vec<_> = fixture.content.split("%extensions%").collect();
+    let parts: Vec<_> = fixture.content.split(EXTENSIONS_SEPARATOR).collect();
     let source_location = SourceLocationKey::embedded(fixture.file_name, 0);
     let source_text = parts[0];
 
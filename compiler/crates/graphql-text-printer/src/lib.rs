@@ -13,9 +13,11 @@ mod print_ast_to_text;
 mod print_full_operation;
 mod print_to_text;
 
+pub use print_ast_to_text::format_executable_document_ast;
 pub use print_ast_to_text::print_executable_definition_ast;
 pub use print_ast_to_text::print_fragment_ast;
 pub use print_ast_to_text::print_operation_ast;
+pub use print_ast_to_text::FormatOptions;
 pub use print_full_operation::print_full_operation;
 pub use print_full_operation::OperationPrinter;
 pub use print_to_text::print_arguments;
@@ -24,10 +26,13 @@ pub use print_to_text::print_directives;
 pub use print_to_text::print_fragment;
 pub use print_to_text::print_ir;
 pub use print_to_text::print_operation;
+pub use print_to_text::print_operation_with_inlined_fragments;
 pub use print_to_text::print_selection;
 pub use print_to_text::print_selections;
 pub use print_to_text::print_value;
 pub use print_to_text::write_arguments;
 pub use print_to_text::write_directives;
 pub use print_to_text::write_value;
+pub use print_to_text::FragmentArgumentSyntax;
 pub use print_to_text::PrinterOptions;
+pub use print_to_text::StringStyle;
@@ -9,8 +9,12 @@ use std::fmt::Result as FmtResult;
 use std::fmt::Write;
 
 use graphql_syntax::Argument;
+use graphql_syntax::ConcreteSyntaxTree;
+use graphql_syntax::ConstantArgument;
+use graphql_syntax::ConstantValue;
 use graphql_syntax::Directive;
 use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::ExecutableDocument;
 use graphql_syntax::FragmentDefinition;
 use graphql_syntax::FragmentSpread;
 use graphql_syntax::InlineFragment;
@@ -19,17 +23,22 @@ use graphql_syntax::List;
 use graphql_syntax::OperationDefinition;
 use graphql_syntax::ScalarField;
 use graphql_syntax::Selection;
+use graphql_syntax::Value;
 use graphql_syntax::VariableDefinition;
+use intern::Lookup;
+
+use crate::print_to_text::write_string_value;
+use crate::StringStyle;
 
 pub fn print_operation_ast(node: &OperationDefinition) -> String {
-    let mut printer: Printer = Default::default();
+    let mut printer: Printer<'_> = Default::default();
     printer.print_operation(node).unwrap();
 
     printer.output
 }
 
 pub fn print_fragment_ast(node: &FragmentDefinition) -> String {
-    let mut printer: Printer = Default::default();
+    let mut printer: Printer<'_> = Default::default();
     printer.print_fragment(node).unwrap();
 
     printer.output
@@ -42,12 +51,98 @@ pub fn print_executable_definition_ast(node: &ExecutableDefinition) -> String {
     }
 }
 
+/// Options for formatting an executable document.
+#[derive(Clone, Copy, Debug)]
+pub struct FormatOptions {
+    /// Argument and variable definition lists that would make their line longer
+    /// than this are printed one item per line.
+    pub max_line_width: usize,
+    /// Spaces per level of indentation
+    pub indent_width: usize,
+    /// Print variable definitions sorted by name
+    pub sort_variable_definitions: bool,
+    /// How to print string values
+    pub string_style: StringStyle,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            max_line_width: 80,
+            indent_width: 2,
+            sort_variable_definitions: false,
+            string_style: StringStyle::default(),
+        }
+    }
+}
+
+/// Prints `document`, parsed from `source`, as formatted GraphQL text. Comments
+/// around definitions and selections are kept in place, as are blank lines
+/// separating selections. Returns `None` if `source` has comments in places
+/// the printer can't keep them, so formatting never drops a comment.
+pub fn format_executable_document_ast(
+    document: &ExecutableDocument,
+    source: &str,
+    options: FormatOptions,
+) -> Option<String> {
+    let mut printer = Printer {
+        source: Some(source),
+        max_line_width: Some(options.max_line_width),
+        options,
+        ..Default::default()
+    };
+    printer.print_document(document).unwrap();
+
+    if printer.printed_comments != ConcreteSyntaxTree::new(source).comments().count() {
+        return None;
+    }
+    let trimmed_length = printer.output.trim_end().len();
+    printer.output.truncate(trimmed_length);
+    Some(printer.output)
+}
+
 #[derive(Default)]
-struct Printer {
+struct Printer<'source> {
     output: String,
+    /// Text the printed nodes were parsed from, to carry comments over from
+    source: Option<&'source str>,
+    /// Offset in `source` up to which comments have been printed
+    cursor: usize,
+    printed_comments: usize,
+    max_line_width: Option<usize>,
+    options: FormatOptions,
 }
 
-impl Printer {
+impl<'source> Printer<'source> {
+    fn print_document(&mut self, document: &ExecutableDocument) -> FmtResult {
+        let definitions = &document.definitions;
+        let source_end = self.source.map_or(0, |source| source.len() as u32);
+        for (index, definition) in definitions.iter().enumerate() {
+            let span = definition.location().span();
+            self.print_comments_before(span.start, "", false, false)?;
+            self.print_executable_definition(definition)?;
+            self.cursor = span.end as usize;
+            let next_start = definitions
+                .get(index + 1)
+                .map_or(source_end, |next| next.location().span().start);
+            self.print_trailing_comment(next_start)?;
+            writeln!(self.output)?;
+            if index + 1 < definitions.len() {
+                writeln!(self.output)?;
+            }
+        }
+        self.print_comments_before(source_end, "", false, true)?;
+
+        Ok(())
+    }
+
+    fn print_executable_definition(&mut self, definition: &ExecutableDefinition) -> FmtResult {
+        match definition {
+            ExecutableDefinition::Operation(operation) => self.print_operation(operation),
+            ExecutableDefinition::Fragment(fragment) => self.print_fragment(fragment),
+        }
+    }
+
     fn print_operation(&mut self, operation: &OperationDefinition) -> FmtResult {
         if let Some((_, operation_kind)) = operation.operation {
             write!(self.output, "{}", operation_kind)?;
@@ -59,8 +154,8 @@ impl Printer {
             self.print_variable_definitions(variable_definitions)?;
         }
         self.print_directives(&operation.directives)?;
-        writeln!(self.output, " {{")?;
-        self.print_selections(&operation.selections, "  ")?;
+        write!(self.output, " {{")?;
+        self.print_selections(&operation.selections, &self.indent_unit())?;
         write!(self.output, "}}")?;
 
         Ok(())
@@ -73,8 +168,8 @@ impl Printer {
         }
         write!(self.output, " {}", fragment.type_condition)?;
         self.print_directives(&fragment.directives)?;
-        writeln!(self.output, " {{")?;
-        self.print_selections(&fragment.selections, "  ")?;
+        write!(self.output, " {{")?;
+        self.print_selections(&fragment.selections, &self.indent_unit())?;
         write!(self.output, "}}")?;
 
         Ok(())
@@ -84,19 +179,13 @@ impl Printer {
         &mut self,
         variable_definitions: &List<VariableDefinition>,
     ) -> FmtResult {
-        write!(self.output, "(")?;
-        let last = variable_definitions.items.last();
-        for variable_definition in &variable_definitions.items {
-            self.print_variable_definition(variable_definition)?;
-            if let Some(last) = last {
-                if last != variable_definition {
-                    write!(self.output, ", ")?;
-                }
-            }
+        let mut variable_definitions = variable_definitions.items.iter().collect::<Vec<_>>();
+        if self.options.sort_variable_definitions {
+            variable_definitions.sort_by_key(|variable_definition| variable_definition.name.name);
         }
-        write!(self.output, ")")?;
-
-        Ok(())
+        self.print_list(&variable_definitions, |printer, variable_definition| {
+            printer.print_variable_definition(variable_definition)
+        })
     }
 
     fn print_variable_definition(&mut self, variable_definition: &VariableDefinition) -> FmtResult {
@@ -106,7 +195,8 @@ impl Printer {
             variable_definition.name, variable_definition.type_
         )?;
         if let Some(default_value) = &variable_definition.default_value {
-            write!(self.output, " = {}", default_value)?;
+            write!(self.output, " = ")?;
+            self.print_constant_value(&default_value.value)?;
         }
         self.print_directives(&variable_definition.directives)?;
 
@@ -132,25 +222,216 @@ impl Printer {
     }
 
     fn print_arguments(&mut self, arguments: &List<Argument>) -> FmtResult {
-        write!(self.output, "(")?;
-        let last_arg = arguments.items.last();
-        for argument in &arguments.items {
-            write!(self.output, "{}", argument)?;
-            if let Some(last_arg) = last_arg {
-                if last_arg != argument {
-                    write!(self.output, ", ")?;
-                }
+        self.print_list(&arguments.items, Self::print_argument)
+    }
+
+    fn print_argument(&mut self, argument: &Argument) -> FmtResult {
+        write!(self.output, "{}: ", argument.name)?;
+        self.print_value(&argument.value)
+    }
+
+    fn print_constant_argument(&mut self, argument: &ConstantArgument) -> FmtResult {
+        write!(self.output, "{}: ", argument.name)?;
+        self.print_constant_value(&argument.value)
+    }
+
+    fn print_value(&mut self, value: &Value) -> FmtResult {
+        match value {
+            Value::Constant(value) => self.print_constant_value(value),
+            Value::Variable(variable) => write!(self.output, "{}", variable),
+            Value::List(list) => self.print_items("[", &list.items, "]", Self::print_value),
+            Value::Object(object) => {
+                self.print_items("{", &object.items, "}", Self::print_argument)
+            }
+        }
+    }
+
+    fn print_constant_value(&mut self, value: &ConstantValue) -> FmtResult {
+        match value {
+            ConstantValue::String(string) => {
+                let indent = self.current_indent();
+                write_string_value(
+                    &mut self.output,
+                    string.value.lookup(),
+                    self.options.string_style,
+                    &indent,
+                    self.options.indent_width,
+                )
+            }
+            ConstantValue::List(list) => {
+                self.print_items("[", &list.items, "]", Self::print_constant_value)
+            }
+            ConstantValue::Object(object) => {
+                self.print_items("{", &object.items, "}", Self::print_constant_argument)
             }
+            ConstantValue::Int(_)
+            | ConstantValue::Float(_)
+            | ConstantValue::Boolean(_)
+            | ConstantValue::Null(_)
+            | ConstantValue::Enum(_) => write!(self.output, "{}", value),
         }
-        write!(self.output, ")")?;
+    }
+
+    /// Prints comma separated items between `open` and `close`.
+    fn print_items<T>(
+        &mut self,
+        open: &str,
+        items: &[T],
+        close: &str,
+        print_item: impl Fn(&mut Self, &T) -> FmtResult,
+    ) -> FmtResult {
+        write!(self.output, "{}", open)?;
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                write!(self.output, ", ")?;
+            }
+            print_item(self, item)?;
+        }
+        write!(self.output, "{}", close)
+    }
+
+    /// Prints a parenthesized, comma separated list on the current line, or one
+    /// item per line if that would exceed `max_line_width`.
+    fn print_list<T>(
+        &mut self,
+        items: &[T],
+        print_item: impl Fn(&mut Self, &T) -> FmtResult,
+    ) -> FmtResult {
+        let list_start = self.output.len();
+        self.print_items("(", items, ")", &print_item)?;
+
+        let max_line_width = match self.max_line_width {
+            Some(max_line_width) => max_line_width,
+            None => return Ok(()),
+        };
+        let line_start = self.output.rfind('\n').map_or(0, |index| index + 1);
+        if items.is_empty() || self.output[line_start..].chars().count() <= max_line_width {
+            return Ok(());
+        }
+        let indent = self.current_indent();
+        self.output.truncate(list_start);
+        writeln!(self.output, "(")?;
+        for item in items {
+            write!(self.output, "{}{}", indent, self.indent_unit())?;
+            print_item(self, item)?;
+            writeln!(self.output)?;
+        }
+        write!(self.output, "{})", indent)?;
+
         Ok(())
     }
 
+    /// Indentation of the line being printed
+    fn current_indent(&self) -> String {
+        let line_start = self.output.rfind('\n').map_or(0, |index| index + 1);
+        let line = &self.output[line_start..];
+        line[..line.len() - line.trim_start().len()].to_string()
+    }
+
+    fn indent_unit(&self) -> String {
+        " ".repeat(self.options.indent_width)
+    }
+
     fn print_selections(&mut self, selections: &List<Selection>, indent: &str) -> FmtResult {
-        for selection in &selections.items {
+        self.cursor = selections.start.span.end as usize;
+        self.print_trailing_comment(
+            selections
+                .items
+                .first()
+                .map_or(selections.end.span.start, |selection| {
+                    selection.span().start
+                }),
+        )?;
+        writeln!(self.output)?;
+        for (index, selection) in selections.items.iter().enumerate() {
+            let span = selection.span();
+            self.print_comments_before(span.start, indent, index > 0, false)?;
             self.print_selection(selection, indent)?;
+            self.cursor = span.end as usize;
+            self.print_trailing_comment(
+                selections
+                    .items
+                    .get(index + 1)
+                    .map_or(selections.end.span.start, |next| next.span().start),
+            )?;
             writeln!(self.output)?;
         }
+        self.print_comments_before(selections.end.span.start, indent, false, true)?;
+
+        Ok(())
+    }
+
+    /// Prints the comments in `source` between the last printed node and `end`
+    /// on their own lines. Blank lines in between are kept as a single blank
+    /// line, except at the start or end of a block.
+    fn print_comments_before(
+        &mut self,
+        end: u32,
+        indent: &str,
+        mut keep_blank_line: bool,
+        is_block_end: bool,
+    ) -> FmtResult {
+        let source = match self.source {
+            Some(source) => source,
+            None => return Ok(()),
+        };
+        let end = end as usize;
+        if end <= self.cursor {
+            return Ok(());
+        }
+        let gap = &source[self.cursor..end];
+        // The first line continues the last printed node, and the last one
+        // precedes the next node, so only the lines in between are whole.
+        let lines = gap.split('\n').collect::<Vec<_>>();
+        let first_whole_line = if self.cursor == 0 { 0 } else { 1 };
+        let whole_lines_end = if end == source.len() {
+            lines.len()
+        } else {
+            lines.len() - 1
+        };
+        self.cursor = end;
+
+        let mut has_blank_line = false;
+        for line in lines.iter().take(whole_lines_end).skip(first_whole_line) {
+            let line = line.trim_matches(|c: char| c.is_whitespace() || c == ',');
+            if line.is_empty() {
+                has_blank_line = true;
+                continue;
+            }
+            if has_blank_line && keep_blank_line {
+                writeln!(self.output)?;
+            }
+            has_blank_line = false;
+            keep_blank_line = true;
+            writeln!(self.output, "{}{}", indent, line)?;
+            self.printed_comments += 1;
+        }
+        if has_blank_line && keep_blank_line && !is_block_end {
+            writeln!(self.output)?;
+        }
+
+        Ok(())
+    }
+
+    /// Prints a comment following the last printed node on the same line.
+    fn print_trailing_comment(&mut self, end: u32) -> FmtResult {
+        let source = match self.source {
+            Some(source) => source,
+            None => return Ok(()),
+        };
+        let end = end as usize;
+        if end <= self.cursor {
+            return Ok(());
+        }
+        let line = source[self.cursor..end]
+            .split('\n')
+            .next()
+            .unwrap_or_default();
+        if let Some(index) = line.find('#') {
+            write!(self.output, " {}", line[index..].trim_end())?;
+            self.printed_comments += 1;
+            self.cursor += line.len();
+        }
 
         Ok(())
     }
@@ -181,8 +462,11 @@ impl Printer {
             write!(self.output, " {}", type_condition)?;
         }
         self.print_directives(&node.directives)?;
-        writeln!(self.output, " {{")?;
-        self.print_selections(&node.selections, &format!("  {}", indent))?;
+        write!(self.output, " {{")?;
+        self.print_selections(
+            &node.selections,
+            &format!("{}{}", indent, self.indent_unit()),
+        )?;
         write!(self.output, "{}}}", indent)?;
         Ok(())
     }
@@ -197,8 +481,11 @@ impl Printer {
             self.print_arguments(arguments)?;
         }
         self.print_directives(&node.directives)?;
-        writeln!(self.output, " {{")?;
-        self.print_selections(&node.selections, &format!("  {}", indent))?;
+        write!(self.output, " {{")?;
+        self.print_selections(
+            &node.selections,
+            &format!("{}{}", indent, self.indent_unit()),
+        )?;
         write!(self.output, "{}}}", indent)?;
         Ok(())
     }
@@ -8,6 +8,7 @@
 use std::fmt::Result as FmtResult;
 use std::fmt::Write;
 
+use common::ArgumentName;
 use common::DirectiveName;
 use common::Named;
 use common::NamedItem;
@@ -24,11 +25,15 @@ use graphql_ir::FragmentSpread;
 use graphql_ir::InlineFragment;
 use graphql_ir::LinkedField;
 use graphql_ir::OperationDefinition;
+use graphql_ir::Program;
 use graphql_ir::ScalarField;
 use graphql_ir::Selection;
 use graphql_ir::Value;
 use graphql_ir::VariableDefinition;
 use graphql_ir::VariableName;
+use graphql_syntax::is_block_string_value;
+use graphql_syntax::write_block_string_literal;
+use graphql_syntax::write_string_literal;
 use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use intern::Lookup;
@@ -60,6 +65,23 @@ pub fn print_operation(
     result
 }
 
+/// Prints an operation with the fragments it spreads inlined, recursively,
+/// as inline fragments on the type condition of the fragment. Each one is
+/// preceded by a `# from FragmentName` comment, except in compact mode. The
+/// arguments of the spreads are printed in the comment, they aren't
+/// substituted in the selections of the fragment.
+pub fn print_operation_with_inlined_fragments(
+    program: &Program,
+    operation: &OperationDefinition,
+    options: PrinterOptions,
+) -> String {
+    let mut result = String::new();
+    let mut printer = Printer::new(&program.schema, &mut result, options);
+    printer.inlined_fragments = Some(program);
+    printer.print_operation(operation).unwrap();
+    result
+}
+
 pub fn print_fragment(
     schema: &SDLSchema,
     fragment: &FragmentDefinition,
@@ -185,29 +207,150 @@ pub fn write_value(
     printer.print_value(value)
 }
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy)]
 pub struct PrinterOptions {
+    /// Print on a single line, without the whitespace that isn't needed to
+    /// separate tokens, and without comments
     pub compact: bool,
     pub sort_keys: bool,
     pub json_format: bool,
-    /// Print `data` from Directive nodes
+    /// Print `data` from Directive nodes. Ignored in compact mode.
     pub debug_directive_data: bool,
+    /// How to print the argument definitions of fragments
+    pub fragment_argument_syntax: FragmentArgumentSyntax,
+    /// Spaces per level of indentation
+    pub indent_width: usize,
+    /// Argument lists that would make their line longer than this are
+    /// printed one argument per line
+    pub max_line_width: Option<usize>,
+    /// Print variable definitions sorted by name
+    pub sort_variable_definitions: bool,
+    /// How to print string values
+    pub string_style: StringStyle,
+    /// Print directives sorted by name, `@include` and `@skip` first. The sort
+    /// is stable, so repeated directives keep their order.
+    pub sort_directives: bool,
+    /// Print the metadata directives transforms add, such as handle fields
+    /// and split operation markers, with their data, and the labels of
+    /// `@defer` and `@stream`, as comments on the lines before the node they
+    /// are on. Ignored in compact mode.
+    pub metadata_comments: bool,
+}
+
+impl Default for PrinterOptions {
+    fn default() -> Self {
+        Self {
+            compact: false,
+            sort_keys: false,
+            json_format: false,
+            debug_directive_data: false,
+            fragment_argument_syntax: FragmentArgumentSyntax::default(),
+            indent_width: 2,
+            max_line_width: None,
+            sort_variable_definitions: false,
+            string_style: StringStyle::default(),
+            sort_directives: false,
+            metadata_comments: false,
+        }
+    }
+}
+
+impl PrinterOptions {
+    /// Options that print semantically identical documents the same way:
+    /// compact, with the arguments, input object fields, variable
+    /// definitions and directives sorted by name. The text is meant for
+    /// hashing, so it should stay the same across compiler versions.
+    pub fn canonical() -> Self {
+        Self {
+            compact: true,
+            sort_keys: true,
+            sort_variable_definitions: true,
+            sort_directives: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// The syntaxes for the argument definitions of fragments, as not every
+/// server accepts the proposed fragment variable definitions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FragmentArgumentSyntax {
+    /// The syntax the fragment was written with
+    #[default]
+    AsWritten,
+    /// `fragment Foo on Type @argumentDefinitions(arg: {type: "Int"})`
+    Directive,
+    /// `fragment Foo($arg: Int) on Type`
+    VariableDefinitions,
+}
+
+/// How to print string values. Strings that wouldn't read back the same from
+/// a block string are always quoted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StringStyle {
+    /// `"..."`, escaping line breaks
+    #[default]
+    Quoted,
+    /// `"""..."""` for strings with line breaks, `"..."` for the others
+    BlockForMultiline,
+}
+
+/// Prints a string value in `style`. The lines of a block string are indented
+/// one level deeper than `indent`, the indentation of the line it starts on.
+pub(crate) fn write_string_value(
+    writer: &mut impl Write,
+    value: &str,
+    style: StringStyle,
+    indent: &str,
+    indent_width: usize,
+) -> FmtResult {
+    match style {
+        StringStyle::BlockForMultiline if value.contains('\n') && is_block_string_value(value) => {
+            let content_indent = format!("{}{}", indent, " ".repeat(indent_width));
+            write_block_string_literal(writer, value, &content_indent, indent)
+        }
+        StringStyle::Quoted | StringStyle::BlockForMultiline => write_string_literal(writer, value),
+    }
+}
+
+/// Writes to the underlying writer, keeping track of the width of the line
+/// being written, to wrap the argument lists that would make it too long.
+struct LineWriter<'writer, W: Write> {
+    writer: &'writer mut W,
+    line_width: usize,
+}
+
+impl<'writer, W: Write> Write for LineWriter<'writer, W> {
+    fn write_str(&mut self, s: &str) -> FmtResult {
+        match s.rfind('\n') {
+            Some(index) => self.line_width = s[index + 1..].chars().count(),
+            None => self.line_width += s.chars().count(),
+        }
+        self.writer.write_str(s)
+    }
 }
 
 struct Printer<'schema, 'writer, W: Write> {
     schema: &'schema SDLSchema,
-    writer: &'writer mut W,
+    writer: LineWriter<'writer, W>,
     options: PrinterOptions,
     indentation: usize,
+    /// The program to look up the fragments to inline in, if spreads should
+    /// be printed as inline fragments
+    inlined_fragments: Option<&'schema Program>,
 }
 
 impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
     fn new(schema: &'schema SDLSchema, writer: &'writer mut W, options: PrinterOptions) -> Self {
         Self {
             schema,
-            writer,
+            writer: LineWriter {
+                writer,
+                line_width: 0,
+            },
             options,
             indentation: 0,
+            inlined_fragments: None,
         }
     }
 
@@ -219,6 +362,7 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
     }
 
     fn print_operation(mut self, operation: &OperationDefinition) -> FmtResult {
+        self.print_metadata_comments(&operation.directives)?;
         write!(self.writer, "{} {}", operation.kind, operation.name.item)?;
         self.print_variable_definitions(&operation.variable_definitions)?;
         self.print_directives(&operation.directives, None, None)?;
@@ -228,21 +372,41 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
     fn print_fragment(mut self, fragment: &FragmentDefinition) -> FmtResult {
         let fragment_name = fragment.name.item;
         let type_condition_name = self.schema.get_type_name(fragment.type_condition);
-        write!(self.writer, "fragment {}", fragment_name)?;
-        if fragment
+        let argument_definitions_name = DirectiveName("argumentDefinitions".intern());
+        let has_argument_definitions_directive = fragment
             .directives
-            .named(DirectiveName("argumentDefinitions".intern()))
-            .is_none()
-        {
+            .named(argument_definitions_name)
+            .is_some();
+        let print_variable_definitions = match self.options.fragment_argument_syntax {
+            FragmentArgumentSyntax::AsWritten => !has_argument_definitions_directive,
+            FragmentArgumentSyntax::Directive => false,
+            FragmentArgumentSyntax::VariableDefinitions => true,
+        };
+        self.print_metadata_comments(&fragment.directives)?;
+        write!(self.writer, "fragment {}", fragment_name)?;
+        if print_variable_definitions {
             self.print_variable_definitions(&fragment.variable_definitions)?;
         }
         write!(self.writer, " on {}", type_condition_name)?;
 
-        self.print_directives(
-            &fragment.directives,
-            None,
-            Some(&fragment.variable_definitions),
-        )?;
+        if print_variable_definitions {
+            let directives = fragment
+                .directives
+                .iter()
+                .filter(|directive| directive.name.item != argument_definitions_name)
+                .cloned()
+                .collect::<Vec<_>>();
+            self.print_directives(&directives, None, None)?;
+        } else {
+            if !has_argument_definitions_directive {
+                self.print_argument_definitions(&fragment.variable_definitions)?;
+            }
+            self.print_directives(
+                &fragment.directives,
+                None,
+                Some(&fragment.variable_definitions),
+            )?;
+        }
         self.print_selections(&fragment.selections, fragment_name.0)
     }
 
@@ -292,6 +456,7 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         field: &ScalarField,
         conditions: Option<Vec<&Condition>>,
     ) -> FmtResult {
+        self.print_metadata_comments(&field.directives)?;
         let schema_field = self.schema.field(field.definition.item);
         self.print_alias_and_name(&field.alias, schema_field.name.item)?;
         self.print_arguments(&field.arguments)?;
@@ -303,6 +468,7 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         field: &LinkedField,
         conditions: Option<Vec<&Condition>>,
     ) -> FmtResult {
+        self.print_metadata_comments(&field.directives)?;
         let schema_field = self.schema.field(field.definition.item);
         self.print_alias_and_name(&field.alias, schema_field.name.item)?;
         self.print_arguments(&field.arguments)?;
@@ -316,7 +482,14 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         field: &FragmentSpread,
         conditions: Option<Vec<&Condition>>,
     ) -> FmtResult {
+        self.print_metadata_comments(&field.directives)?;
         let fragment_name = field.fragment.item;
+        if let Some(fragment) = self
+            .inlined_fragments
+            .and_then(|program| program.fragment(fragment_name))
+        {
+            return self.print_inlined_fragment(field, fragment, conditions);
+        }
         write!(self.writer, "...{}", fragment_name)?;
         self.print_directives(&field.directives, conditions, None)?;
         if !field.arguments.is_empty() {
@@ -328,11 +501,37 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         }
     }
 
+    fn print_inlined_fragment(
+        &mut self,
+        spread: &FragmentSpread,
+        fragment: &FragmentDefinition,
+        conditions: Option<Vec<&Condition>>,
+    ) -> FmtResult {
+        if !self.options.compact {
+            write!(self.writer, "# from {}", fragment.name.item)?;
+            if !spread.arguments.is_empty() {
+                write!(self.writer, " @arguments")?;
+                self.print_inline_arguments(&spread.arguments)?;
+            }
+            self.print_new_line(false)?;
+        }
+        write!(self.writer, "...")?;
+        self.print_optional_space()?;
+        write!(
+            self.writer,
+            "on {}",
+            self.schema.get_type_name(fragment.type_condition)
+        )?;
+        self.print_directives(&spread.directives, conditions, None)?;
+        self.print_selections(&fragment.selections, fragment.name.item.0)
+    }
+
     fn print_inline_fragment(
         &mut self,
         field: &InlineFragment,
         conditions: Option<Vec<&Condition>>,
     ) -> FmtResult {
+        self.print_metadata_comments(&field.directives)?;
         write!(self.writer, "...")?;
         if let Some(type_condition) = field.type_condition {
             self.print_optional_space()?;
@@ -374,9 +573,10 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         if has_repeated_condition {
             write!(self.writer, "...")?;
             self.print_directives(&[], Some(accum_conditions), None)?;
-            write!(self.writer, " {{")?;
+            self.print_optional_space()?;
+            write!(self.writer, "{{")?;
             self.indentation += 1;
-            self.print_new_line(false)?;
+            self.print_new_line(true)?;
             accum_conditions = vec![condition];
         } else {
             accum_conditions.push(condition);
@@ -388,7 +588,7 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
                 if is_first_selection {
                     is_first_selection = false;
                 } else {
-                    self.print_new_line(false)?;
+                    self.print_item_separator()?;
                 }
                 self.print_selection(
                     selection,
@@ -398,7 +598,7 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         }
         if has_repeated_condition {
             self.indentation -= 1;
-            self.print_new_line(false)?;
+            self.print_new_line(true)?;
             write!(self.writer, "}}")?;
         }
         Ok(())
@@ -413,6 +613,10 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         if let Some(conditions) = conditions {
             self.print_condition_directives(conditions)?;
         }
+        let mut directives = directives.iter().collect::<Vec<_>>();
+        if self.options.sort_directives {
+            directives.sort_by_key(|directive| directive.name.item);
+        }
         for directive in directives {
             if directive.name.item.0.lookup() == "argumentDefinitions" {
                 self.print_argument_definitions(fragment_argument_definitions.unwrap())?;
@@ -424,11 +628,28 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
     }
 
     fn print_directive(&mut self, directive: &Directive) -> FmtResult {
+        if self.prints_metadata_comments() {
+            if is_metadata_directive(directive) {
+                return Ok(());
+            }
+            if label_argument(directive).is_some() {
+                let label_name = ArgumentName("label".intern());
+                let arguments = directive
+                    .arguments
+                    .iter()
+                    .filter(|argument| argument.name.item != label_name)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                self.print_optional_space()?;
+                write!(self.writer, "@{}", directive.name.item)?;
+                return self.print_arguments(&arguments);
+            }
+        }
         self.print_optional_space()?;
         write!(self.writer, "@{}", directive.name.item)?;
         self.print_arguments(&directive.arguments)?;
 
-        if self.options.debug_directive_data {
+        if self.options.debug_directive_data && !self.options.compact {
             if let Some(data) = &directive.data {
                 for debug_line in format!("{:#?}", data).lines() {
                     self.print_new_line(false)?;
@@ -441,7 +662,44 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         Ok(())
     }
 
-    fn print_condition_directives(&mut self, conditions: Vec<&Condition>) -> FmtResult {
+    fn prints_metadata_comments(&self) -> bool {
+        self.options.metadata_comments && !self.options.compact
+    }
+
+    /// Prints the metadata directives of a node, and the labels of its
+    /// `@defer` and `@stream` directives, in comments, each followed by a
+    /// new line.
+    fn print_metadata_comments(&mut self, directives: &[Directive]) -> FmtResult {
+        if !self.prints_metadata_comments() {
+            return Ok(());
+        }
+        for directive in directives {
+            if is_metadata_directive(directive) {
+                write!(self.writer, "# @{}", directive.name.item)?;
+                if !directive.arguments.is_empty() {
+                    self.print_inline_arguments(&directive.arguments)?;
+                }
+                if let Some(data) = &directive.data {
+                    for debug_line in format!("{:#?}", data).lines() {
+                        self.print_new_line(false)?;
+                        write!(self.writer, "#   {}", debug_line)?;
+                    }
+                }
+                self.print_new_line(false)?;
+            } else if let Some(label) = label_argument(directive) {
+                write!(self.writer, "# @{} label: ", directive.name.item)?;
+                self.print_value(&label.value.item)?;
+                self.print_new_line(false)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn print_condition_directives(&mut self, mut conditions: Vec<&Condition>) -> FmtResult {
+        if self.options.sort_directives {
+            // `@include` before `@skip`
+            conditions.sort_by_key(|condition| !condition.passing_value);
+        }
         for condition in conditions {
             self.print_optional_space()?;
             write!(
@@ -471,7 +729,11 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         if !variable_definitions.is_empty() {
             write!(self.writer, "(")?;
             self.indentation += 1;
-            for (i, var_def) in variable_definitions.iter().enumerate() {
+            for (i, var_def) in self
+                .sorted_variable_definitions(variable_definitions)
+                .into_iter()
+                .enumerate()
+            {
                 if i == 0 {
                     self.print_new_line(true)?;
                 } else {
@@ -494,7 +756,11 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
             self.print_optional_space()?;
             write!(self.writer, "@argumentDefinitions(")?;
             self.indentation += 1;
-            for (i, arg_def) in argument_definitions.iter().enumerate() {
+            for (i, arg_def) in self
+                .sorted_variable_definitions(argument_definitions)
+                .into_iter()
+                .enumerate()
+            {
                 if i == 0 {
                     self.print_new_line(true)?;
                 } else {
@@ -522,6 +788,17 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         Ok(())
     }
 
+    fn sorted_variable_definitions<'a>(
+        &self,
+        variable_definitions: &'a [VariableDefinition],
+    ) -> Vec<&'a VariableDefinition> {
+        let mut variable_definitions = variable_definitions.iter().collect::<Vec<_>>();
+        if self.options.sort_variable_definitions {
+            variable_definitions.sort_by_key(|variable_definition| variable_definition.name.item);
+        }
+        variable_definitions
+    }
+
     fn print_arguments(&mut self, arguments: &[Argument]) -> FmtResult {
         if arguments.is_empty() {
             Ok(())
@@ -537,17 +814,53 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
                 .as_ref()
                 .map_or(arguments, |v| v.as_slice());
 
-            write!(self.writer, "(")?;
-            for (i, argument) in maybe_sorted_arguments.iter().enumerate() {
-                self.print_argument(argument)?;
-                if i != arguments.len() - 1 {
-                    self.print_comma_separator()?;
+            if self.exceeds_max_line_width(maybe_sorted_arguments)? {
+                write!(self.writer, "(")?;
+                self.indentation += 1;
+                for argument in maybe_sorted_arguments {
+                    self.print_new_line(false)?;
+                    self.print_argument(argument)?;
                 }
+                self.indentation -= 1;
+                self.print_new_line(false)?;
+                write!(self.writer, ")")
+            } else {
+                self.print_inline_arguments(maybe_sorted_arguments)
             }
-            write!(self.writer, ")")
         }
     }
 
+    fn print_inline_arguments(&mut self, arguments: &[Argument]) -> FmtResult {
+        write!(self.writer, "(")?;
+        for (i, argument) in arguments.iter().enumerate() {
+            self.print_argument(argument)?;
+            if i != arguments.len() - 1 {
+                self.print_comma_separator()?;
+            }
+        }
+        write!(self.writer, ")")
+    }
+
+    /// Whether printing `arguments` on the current line would make it longer
+    /// than `max_line_width`.
+    fn exceeds_max_line_width(&self, arguments: &[Argument]) -> Result<bool, std::fmt::Error> {
+        let max_line_width = match self.options.max_line_width {
+            Some(max_line_width) if !self.options.compact => max_line_width,
+            _ => return Ok(false),
+        };
+        let mut inline_arguments = String::new();
+        let mut printer = Printer::new(self.schema, &mut inline_arguments, self.options);
+        printer.indentation = self.indentation;
+        printer.print_inline_arguments(arguments)?;
+        let width = inline_arguments
+            .split('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count();
+        Ok(self.writer.line_width + width > max_line_width)
+    }
+
     fn print_value(&mut self, val: &Value) -> FmtResult {
         match val {
             Value::Constant(constant_val) => self.print_constant_value(constant_val),
@@ -605,7 +918,7 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
 
     fn print_constant_value(&mut self, constant_val: &ConstantValue) -> FmtResult {
         match &constant_val {
-            ConstantValue::String(val) => write!(self.writer, "\"{}\"", val),
+            ConstantValue::String(val) => self.print_string(val.lookup()),
             ConstantValue::Float(val) => write!(self.writer, "{}", val),
             ConstantValue::Int(val) => write!(self.writer, "{}", val),
             ConstantValue::Boolean(val) => write!(self.writer, "{}", val),
@@ -698,6 +1011,21 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         self.print_constant_value(&arg.value.item)
     }
 
+    fn print_string(&mut self, value: &str) -> FmtResult {
+        if self.options.compact || self.options.json_format {
+            write_string_literal(&mut self.writer, value)
+        } else {
+            let indent = " ".repeat(self.indentation * self.options.indent_width);
+            write_string_value(
+                &mut self.writer,
+                value,
+                self.options.string_style,
+                &indent,
+                self.options.indent_width,
+            )
+        }
+    }
+
     fn print_variable(&mut self, variable: VariableName) -> FmtResult {
         write!(self.writer, "${}", variable)
     }
@@ -718,10 +1046,11 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
             return Ok(());
         }
         writeln!(self.writer)?;
-        for _ in 0..self.indentation {
-            write!(self.writer, "  ")?;
-        }
-        Ok(())
+        write!(
+            self.writer,
+            "{}",
+            " ".repeat(self.indentation * self.options.indent_width)
+        )
     }
 
     fn print_optional_space(&mut self) -> FmtResult {
@@ -749,3 +1078,16 @@ impl<'schema, 'writer, W: Write> Printer<'schema, 'writer, W> {
         }
     }
 }
+
+/// Directives transforms add to nodes to pass data along, named `@__...`.
+fn is_metadata_directive(directive: &Directive) -> bool {
+    directive.data.is_some() || directive.name.item.0.lookup().starts_with("__")
+}
+
+/// The `label` argument of a `@defer` or `@stream` directive
+fn label_argument(directive: &Directive) -> Option<&Argument> {
+    match directive.name.item.0.lookup() {
+        "defer" | "stream" => directive.arguments.named(ArgumentName("label".intern())),
+        _ => None,
+    }
+}
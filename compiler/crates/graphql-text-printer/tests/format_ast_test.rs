@@ -0,0 +1,34 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<5b1e7c0d3a29f6e84c17d2a9b60f4e38>>
+ */
+
+mod format_ast;
+
+use format_ast::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn argument_wrapping() {
+    let input = include_str!("format_ast/fixtures/argument-wrapping.graphql");
+    let expected = include_str!("format_ast/fixtures/argument-wrapping.expected");
+    test_fixture(transform_fixture, "argument-wrapping.graphql", "format_ast/fixtures/argument-wrapping.expected", input, expected);
+}
+
+#[test]
+fn comment_in_arguments_invalid() {
+    let input = include_str!("format_ast/fixtures/comment-in-arguments.invalid.graphql");
+    let expected = include_str!("format_ast/fixtures/comment-in-arguments.invalid.expected");
+    test_fixture(transform_fixture, "comment-in-arguments.invalid.graphql", "format_ast/fixtures/comment-in-arguments.invalid.expected", input, expected);
+}
+
+#[test]
+fn comments() {
+    let input = include_str!("format_ast/fixtures/comments.graphql");
+    let expected = include_str!("format_ast/fixtures/comments.expected");
+    test_fixture(transform_fixture, "comments.graphql", "format_ast/fixtures/comments.expected", input, expected);
+}
@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_syntax::parse_executable;
+use graphql_text_printer::format_executable_document_ast;
+use graphql_text_printer::FormatOptions;
+use graphql_text_printer::StringStyle;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let ast = parse_executable(fixture.content, source_location).unwrap();
+
+    let options = FormatOptions {
+        max_line_width: 50,
+        indent_width: 4,
+        sort_variable_definitions: true,
+        string_style: StringStyle::BlockForMultiline,
+    };
+    let error = "Formatting would drop comments that aren't around definitions or selections";
+    format_executable_document_ast(&ast, fixture.content, options).ok_or_else(|| error.to_string())
+}
@@ -0,0 +1,20 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<bd57911385a49b7a8dd5e29375782c9a>>
+ */
+
+mod format_ast_with_options;
+
+use format_ast_with_options::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn style_options() {
+    let input = include_str!("format_ast_with_options/fixtures/style-options.graphql");
+    let expected = include_str!("format_ast_with_options/fixtures/style-options.expected");
+    test_fixture(transform_fixture, "style-options.graphql", "format_ast_with_options/fixtures/style-options.expected", input, expected);
+}
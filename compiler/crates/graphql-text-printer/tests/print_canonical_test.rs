@@ -0,0 +1,20 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<349a996440905f0f1781ab978199caff>>
+ */
+
+mod print_canonical;
+
+use print_canonical::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn reordered_operations() {
+    let input = include_str!("print_canonical/fixtures/reordered-operations.graphql");
+    let expected = include_str!("print_canonical/fixtures/reordered-operations.expected");
+    test_fixture(transform_fixture, "reordered-operations.graphql", "print_canonical/fixtures/reordered-operations.expected", input, expected);
+}
@@ -0,0 +1,61 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build;
+use graphql_ir::ExecutableDefinition;
+use graphql_syntax::parse_executable_with_features;
+use graphql_syntax::FragmentArgumentSyntaxKind;
+use graphql_syntax::ParserFeatures;
+use graphql_text_printer::print_fragment;
+use graphql_text_printer::FragmentArgumentSyntax;
+use graphql_text_printer::PrinterOptions;
+use relay_test_schema::TEST_SCHEMA;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let ast = parse_executable_with_features(
+        fixture.content,
+        source_location,
+        ParserFeatures {
+            fragment_argument_capability:
+                FragmentArgumentSyntaxKind::OnlyFragmentVariableDefinitions,
+        },
+    )
+    .unwrap();
+    let definitions = build(&TEST_SCHEMA, &ast.definitions).map_err(|errors| {
+        errors
+            .into_iter()
+            .map(|error| format!("{:?}", error))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    })?;
+    let mut printed = vec![];
+    for (title, fragment_argument_syntax) in [
+        ("directive", FragmentArgumentSyntax::Directive),
+        (
+            "variable definitions",
+            FragmentArgumentSyntax::VariableDefinitions,
+        ),
+    ] {
+        let options = PrinterOptions {
+            fragment_argument_syntax,
+            ..Default::default()
+        };
+        for definition in &definitions {
+            if let ExecutableDefinition::Fragment(fragment) = definition {
+                printed.push(format!(
+                    "# {}\n{}",
+                    title,
+                    print_fragment(&TEST_SCHEMA, fragment, options)
+                ));
+            }
+        }
+    }
+    Ok(printed.join("\n\n"))
+}
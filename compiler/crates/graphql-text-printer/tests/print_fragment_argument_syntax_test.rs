@@ -0,0 +1,20 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<add72d9a828916354f9699c980dfde70>>
+ */
+
+mod print_fragment_argument_syntax;
+
+use print_fragment_argument_syntax::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn fragment_arguments() {
+    let input = include_str!("print_fragment_argument_syntax/fixtures/fragment_arguments.graphql");
+    let expected = include_str!("print_fragment_argument_syntax/fixtures/fragment_arguments.expected");
+    test_fixture(transform_fixture, "fragment_arguments.graphql", "print_fragment_argument_syntax/fixtures/fragment_arguments.expected", input, expected);
+}
@@ -0,0 +1,35 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build;
+use graphql_ir::ExecutableDefinition;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable;
+use graphql_text_printer::print_operation_with_inlined_fragments;
+use relay_test_schema::TEST_SCHEMA;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let ast = parse_executable(fixture.content, source_location).unwrap();
+    let ir = build(&TEST_SCHEMA, &ast.definitions).unwrap();
+    let program = Program::from_definitions(Arc::clone(&TEST_SCHEMA), ir.clone());
+
+    Ok(ir
+        .iter()
+        .filter_map(|definition| match definition {
+            ExecutableDefinition::Operation(operation) => Some(
+                print_operation_with_inlined_fragments(&program, operation, Default::default()),
+            ),
+            ExecutableDefinition::Fragment(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
@@ -0,0 +1,20 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<b39474846e005ae192036dd3c221c476>>
+ */
+
+mod print_inlined_fragments;
+
+use print_inlined_fragments::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn nested_fragments() {
+    let input = include_str!("print_inlined_fragments/fixtures/nested-fragments.graphql");
+    let expected = include_str!("print_inlined_fragments/fixtures/nested-fragments.expected");
+    test_fixture(transform_fixture, "nested-fragments.graphql", "print_inlined_fragments/fixtures/nested-fragments.expected", input, expected);
+}
@@ -0,0 +1,20 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<08c06fb739d0d361fc61344c0b112792>>
+ */
+
+mod print_metadata_comments;
+
+use print_metadata_comments::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn metadata_directives() {
+    let input = include_str!("print_metadata_comments/fixtures/metadata-directives.graphql");
+    let expected = include_str!("print_metadata_comments/fixtures/metadata-directives.expected");
+    test_fixture(transform_fixture, "metadata-directives.graphql", "print_metadata_comments/fixtures/metadata-directives.expected", input, expected);
+}
@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<d33bba31770d0dda6327e188acc496a6>>
+ * @generated SignedSource<<333f1db499b30ec2a537f9150b92e8d4>>
  */
 
 mod print;
@@ -124,6 +124,13 @@ fn string_enum_fragment_arg_with_complex_input() {
     test_fixture(transform_fixture, "string-enum-fragment-arg-with-complex-input.graphql", "print/fixtures/string-enum-fragment-arg-with-complex-input.expected", input, expected);
 }
 
+#[test]
+fn string_escapes() {
+    let input = include_str!("print/fixtures/string-escapes.graphql");
+    let expected = include_str!("print/fixtures/string-escapes.expected");
+    test_fixture(transform_fixture, "string-escapes.graphql", "print/fixtures/string-escapes.expected", input, expected);
+}
+
 #[test]
 fn unknown_enum_arg_invalid() {
     let input = include_str!("print/fixtures/unknown-enum-arg.invalid.graphql");
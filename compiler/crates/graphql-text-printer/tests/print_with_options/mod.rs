@@ -0,0 +1,48 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build;
+use graphql_ir::ExecutableDefinition;
+use graphql_syntax::parse_executable;
+use graphql_text_printer::print_fragment;
+use graphql_text_printer::print_operation;
+use graphql_text_printer::PrinterOptions;
+use graphql_text_printer::StringStyle;
+use relay_test_schema::TEST_SCHEMA;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let ast = parse_executable(fixture.content, source_location).unwrap();
+    let definitions = build(&TEST_SCHEMA, &ast.definitions).map_err(|errors| {
+        errors
+            .into_iter()
+            .map(|error| format!("{:?}", error))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    })?;
+    let options = PrinterOptions {
+        indent_width: 4,
+        max_line_width: Some(50),
+        sort_variable_definitions: true,
+        string_style: StringStyle::BlockForMultiline,
+        ..Default::default()
+    };
+    Ok(definitions
+        .iter()
+        .map(|definition| match definition {
+            ExecutableDefinition::Operation(operation) => {
+                print_operation(&TEST_SCHEMA, operation, options)
+            }
+            ExecutableDefinition::Fragment(fragment) => {
+                print_fragment(&TEST_SCHEMA, fragment, options)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
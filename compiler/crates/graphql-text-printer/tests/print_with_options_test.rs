@@ -0,0 +1,20 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<980d95907f70fc62ef7b1f0cd9291678>>
+ */
+
+mod print_with_options;
+
+use print_with_options::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn style_options() {
+    let input = include_str!("print_with_options/fixtures/style-options.graphql");
+    let expected = include_str!("print_with_options/fixtures/style-options.expected");
+    test_fixture(transform_fixture, "style-options.graphql", "print_with_options/fixtures/style-options.expected", input, expected);
+}
@@ -0,0 +1,71 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Measures how interning scales with the number of threads, when the
+//! threads intern the same strings at the same time, like the parallel
+//! phases of the compiler do with field and type names:
+//!
+//! ```sh
+//! cargo run --release -p intern --example intern_contention -- [strings] [rounds]
+//! ```
+
+use std::thread;
+use std::time::Instant;
+
+use intern::string_key::Intern;
+use intern::string_key::StringKey;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let string_count: usize = args.next().map_or(10_000, |arg| arg.parse().unwrap());
+    let rounds: usize = args.next().map_or(100, |arg| arg.parse().unwrap());
+    let max_threads = thread::available_parallelism().map_or(8, |threads| threads.get());
+
+    // A few names are much more common than the others, like `id`.
+    let strings: Vec<String> = (0..string_count)
+        .map(|n| match n % 4 {
+            0 => "id".to_string(),
+            1 => format!("field{}", n % 64),
+            _ => format!("Type{}_field{}", n / 64, n),
+        })
+        .collect();
+    // Intern all the strings up front, since most strings are interned
+    // already in a build.
+    let expected: Vec<StringKey> = strings.iter().map(|s| s.as_str().intern()).collect();
+
+    println!("threads  interns/s (millions)  per thread");
+    let mut threads = 1;
+    while threads <= max_threads {
+        let start = Instant::now();
+        thread::scope(|scope| {
+            for thread_index in 0..threads {
+                let strings = &strings;
+                let expected = &expected;
+                scope.spawn(move || {
+                    for round in 0..rounds {
+                        // Each thread starts at a different string, and
+                        // all of them go through all the strings.
+                        let offset = (thread_index * 7919 + round) % strings.len();
+                        for i in 0..strings.len() {
+                            let index = (offset + i) % strings.len();
+                            assert_eq!(strings[index].as_str().intern(), expected[index]);
+                        }
+                    }
+                });
+            }
+        });
+        let seconds = start.elapsed().as_secs_f64();
+        let interns = (threads * rounds * strings.len()) as f64;
+        println!(
+            "{:>7}  {:>21.2}  {:>10.2}",
+            threads,
+            interns / seconds / 1e6,
+            interns / seconds / 1e6 / threads as f64
+        );
+        threads *= 2;
+    }
+}
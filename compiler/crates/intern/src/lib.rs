@@ -23,7 +23,9 @@
 //!   `get()` to turn an `Id` into a `&'static T`.)
 //! * Sharded locking when interning new objects.  We use
 //!   [rayon](https://docs.rs/rayon/1.5.1/rayon/) in our code and need to be
-//!   able to intern data in parallel.
+//!   able to intern data in parallel.  Looking up data that is interned
+//!   already only takes a read lock, and strings recently interned by a
+//!   thread are found in a per-thread cache without any lock.
 //! * Lock-free constant-time `deref()` of intern ids.  Again, we need to be
 //!   able read interned data from numerous threads.  Compared to using a
 //!   pointer, this requires a single extra load and no extra storage beyond
@@ -16,7 +16,7 @@ use hashbrown::raw::RawTable;
 use parking_lot::RwLock;
 use parking_lot::RwLockWriteGuard;
 
-const SHARD_SHIFT: usize = 6;
+const SHARD_SHIFT: usize = 8;
 const SHARDS: usize = 1 << SHARD_SHIFT;
 
 pub struct ShardedSet<T, S = RandomState> {
@@ -40,72 +40,7 @@ impl<T, S: BuildHasher> ShardedSet<T, S> {
     pub fn with_hasher(h: S) -> Self {
         Self {
             build_hasher: h,
-            shards: [
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-            ],
+            shards: std::array::from_fn(|_| Default::default()),
         }
     }
 }
@@ -153,23 +88,17 @@ impl<T: Eq + Hash, S: BuildHasher> ShardedSet<T, S> {
         Q: ?Sized + Hash + Eq,
     {
         let (hash, shard) = self.hash_and_shard(q);
-        // Assume load is low and try to take lock for writing.
-        // We don't faff around with upgradability right now.
-        let shard = if let Some(write_lock) = shard.try_write() {
-            write_lock
-        } else {
-            // Write contention.  Try reading first to see if the entry already exists.
-            if let Some(t) = shard.read().get(hash, |other| q == other.borrow()) {
-                // Already exists.
-                return Ok(t.clone());
-            }
-            // Unconditionally write lock.
-            shard.write()
-        };
-        // Now check for the data.  We need to do this even if we already
-        // checked in the write contention case above.  We don't use an
-        // upgradable read lock because those are exclusive from one another
-        // just like write locks.
+        // Most values are interned already, so look for the entry with a
+        // read lock first: lookups of existing entries don't exclude each
+        // other, unlike the write lock.
+        if let Some(t) = shard.read().get(hash, |other| q == other.borrow()) {
+            return Ok(t.clone());
+        }
+        let shard = shard.write();
+        // Check for the data again, since another thread may have inserted
+        // it between the locks.  We don't use an upgradable read lock
+        // because those are exclusive from one another just like write
+        // locks.
         if let Some(t) = shard.get(hash, |other| q == other.borrow()) {
             return Ok(t.clone());
         }
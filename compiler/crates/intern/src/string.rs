@@ -7,15 +7,18 @@
 
 use std::borrow::Borrow;
 use std::borrow::Cow;
+use std::cell::Cell;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Formatter;
 use std::hash::Hash;
+use std::hash::Hasher;
 use std::str::FromStr;
 use std::str::Utf8Error;
 
+use fnv::FnvHasher;
 #[doc(hidden)]
 pub use once_cell::sync::Lazy; // For macros
 use serde_derive::Deserialize;
@@ -265,13 +268,41 @@ pub fn intern<S: IntoUtf8Bytes>(s: S) -> StringId {
     StringId(intern_bytes(s.into_bytes()))
 }
 
-#[inline]
+/// The number of entries of the per-thread cache of `intern_bytes`.
+const THREAD_CACHE_SIZE: usize = 4096;
+
+thread_local! {
+    // The ids of bytes recently interned by this thread, by the hash of the
+    // bytes.  Ids never change once they are issued, so an entry is valid
+    // until it's replaced, and lookups of cached bytes don't touch the locks
+    // that all the threads share.
+    static THREAD_CACHE: Box<[Cell<Option<BytesId>>]> =
+        (0..THREAD_CACHE_SIZE).map(|_| Cell::new(None)).collect();
+}
+
 pub fn intern_bytes<S>(s: S) -> BytesId
 where
     S: Into<SmallBytes> + Borrow<[u8]>,
 {
-    let b: SmallBytes = s.into();
-    BytesId::intern(b)
+    let bytes: &[u8] = s.borrow();
+    let mut hasher = FnvHasher::default();
+    hasher.write(bytes);
+    let slot = hasher.finish() as usize % THREAD_CACHE_SIZE;
+    let cached = THREAD_CACHE
+        .try_with(|cache| cache[slot].get())
+        .ok()
+        .flatten();
+    if let Some(id) = cached {
+        if id.as_bytes() == bytes {
+            return id;
+        }
+    }
+    // Look up existing bytes without converting them to `SmallBytes`,
+    // which allocates for long bytes.
+    let id = BytesId::intern(s);
+    // The cache is gone while the thread is destroyed.
+    let _ = THREAD_CACHE.try_with(|cache| cache[slot].set(Some(id)));
+    id
 }
 
 /// Statically declare an interned string.
@@ -418,6 +449,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn thread_cache_agrees_with_other_threads() {
+        // More strings than entries of the cache, so that entries are
+        // replaced.
+        let strs: Vec<String> = (0..3 * THREAD_CACHE_SIZE)
+            .map(|n| format!("cached {}", n))
+            .collect();
+        let intern_all = || {
+            strs.iter()
+                .map(|s| intern(s.as_str()))
+                .collect::<Vec<StringId>>()
+        };
+        let ids = intern_all();
+        let other_thread_ids = std::thread::scope(|scope| scope.spawn(intern_all).join().unwrap());
+        assert_eq!(ids, other_thread_ids);
+        assert_eq!(ids, intern_all());
+        for (s, id) in strs.iter().zip(ids) {
+            assert_eq!(id.as_str(), s);
+        }
+    }
+
     #[test]
     fn all_kinds_of_bytes() {
         let d: &[u8] = b"Hello";
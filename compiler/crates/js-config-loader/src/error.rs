@@ -38,8 +38,16 @@ pub enum ErrorCode {
         error: serde_json::Error,
     },
 
-    #[error("YAML file parsing not supported")]
-    YamlFileUnsupported,
+    /// The file is valid JSON, YAML or JavaScript, but doesn't contain a valid
+    /// config.
+    #[error("{error}")]
+    InvalidConfig { error: serde_json::Error },
+
+    #[error("Error parsing YAML: {error}")]
+    YamlParseError { error: serde_yaml::Error },
+
+    #[error("Failed to run `node`, make sure that Node is installed: {error}")]
+    NodeNotFound { error: std::io::Error },
 
     #[error("Error running node: {}", String::from_utf8_lossy(&output.stderr))]
     NodeExecuteError { output: Output },
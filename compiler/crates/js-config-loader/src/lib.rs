@@ -114,6 +114,8 @@ where
             LoaderSource::Js(format!(".{}.rc.cjs", name)),
             LoaderSource::Js(format!("{}.config.js", name)),
             LoaderSource::Js(format!("{}.config.cjs", name)),
+            LoaderSource::Js(format!("{}.config.mjs", name)),
+            LoaderSource::Js(format!("{}.config.ts", name)),
         ],
     )
 }
@@ -43,9 +43,15 @@ impl<'a, T: for<'de> Deserialize<'de>> Loader<T> for PackageJsonLoader<'a> {
 }
 
 pub struct YamlLoader;
-impl<T> Loader<T> for YamlLoader {
-    fn load(&self, _path: &Path) -> Result<Option<T>, ErrorCode> {
-        Err(ErrorCode::YamlFileUnsupported)
+impl<T: for<'de> Deserialize<'de> + 'static> Loader<T> for YamlLoader {
+    fn load(&self, path: &Path) -> Result<Option<T>, ErrorCode> {
+        let file = File::open(&path).unwrap();
+        let reader = BufReader::new(file);
+        // YAML is a superset of JSON, so this also reads extensionless rc
+        // files in either format.
+        let value: Value =
+            serde_yaml::from_reader(reader).map_err(|error| ErrorCode::YamlParseError { error })?;
+        deserialize_config(value).map(Some)
     }
 }
 
@@ -54,26 +60,94 @@ impl<T: for<'de> Deserialize<'de> + 'static> Loader<T> for JsonLoader {
     fn load(&self, path: &Path) -> Result<Option<T>, ErrorCode> {
         let file = File::open(&path).unwrap();
         let reader = BufReader::new(file);
-        let config = serde_json::from_reader(reader)?;
-        Ok(Some(config))
+        let value: Value = serde_json::from_reader(reader)?;
+        deserialize_config(value).map(Some)
     }
 }
 
+/// Deserializing from a `Value` instead of the file contents keeps the line
+/// and column of the end of the file out of errors about the config values.
+fn deserialize_config<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T, ErrorCode> {
+    serde_json::from_value(value).map_err(|error| ErrorCode::InvalidConfig { error })
+}
+
+/// Evaluates `.js`, `.cjs`, `.mjs` and TypeScript config files by running
+/// [`JS_LOADER_SCRIPT`] with `node`. The config file can export the config
+/// object, a promise resolving to it, or a (possibly async) function that
+/// returns it. The script prints the config as JSON to stdout and exits with a
+/// non-zero status on errors.
 pub struct JsLoader;
 impl<T: for<'de> Deserialize<'de> + 'static> Loader<T> for JsLoader {
     fn load(&self, path: &Path) -> Result<Option<T>, ErrorCode> {
         let output = Command::new("node")
             .arg("-e")
-            .arg(r#"process.stdout.write(JSON.stringify(require(process.argv[1])))"#)
+            .arg(JS_LOADER_SCRIPT)
             .arg(&path)
             .output()
-            .expect("failed to execute process. Make sure you have Node installed.");
+            .map_err(|error| ErrorCode::NodeNotFound { error })?;
 
         if output.status.success() {
-            let value = serde_json::from_slice::<T>(&output.stdout);
-            Ok(Some(value.unwrap()))
+            let value: Value = serde_json::from_slice(&output.stdout)?;
+            deserialize_config(value).map(Some)
         } else {
             Err(ErrorCode::NodeExecuteError { output })
         }
     }
 }
+
+/// TypeScript configs are transpiled with `tsx` or `ts-node` if either is
+/// installed next to the config file, otherwise they rely on the type
+/// stripping that is built into recent versions of Node.
+const JS_LOADER_SCRIPT: &str = r#"
+const path = require('path');
+const url = require('url');
+const configPath = path.resolve(process.argv[1]);
+
+function registerTypeScript() {
+  for (const hook of ['tsx/cjs', 'ts-node/register/transpile-only']) {
+    let resolved;
+    try {
+      resolved = require.resolve(hook, {paths: [path.dirname(configPath)]});
+    } catch {
+      continue;
+    }
+    require(resolved);
+    return;
+  }
+}
+
+async function loadModule() {
+  if (/\.[cm]?ts$/.test(configPath)) {
+    registerTypeScript();
+  }
+  if (!/\.m[jt]s$/.test(configPath)) {
+    try {
+      const module = require(configPath);
+      return module != null && module.__esModule ? module.default : module;
+    } catch (error) {
+      if (error.code !== 'ERR_REQUIRE_ESM') {
+        throw error;
+      }
+    }
+  }
+  const module = await import(url.pathToFileURL(configPath).href);
+  return 'default' in module ? module.default : module;
+}
+
+async function main() {
+  let config = await loadModule();
+  if (typeof config === 'function') {
+    config = config();
+  }
+  config = await config;
+  if (config == null || typeof config !== 'object') {
+    throw new Error(`Expected ${configPath} to export a config object, got ${config}.`);
+  }
+  process.stdout.write(JSON.stringify(config));
+}
+
+main().catch(error => {
+  process.stderr.write(String(error && error.stack ? error.stack : error) + '\n');
+  process.exit(1);
+});
+"#;
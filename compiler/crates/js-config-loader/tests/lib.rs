@@ -147,6 +147,66 @@ fn config_js() {
     assert_eq!(config.value.name, "correct");
 }
 
+#[test]
+fn config_js_async_function() {
+    let dir = tempdir().unwrap();
+    let dir_d = dir.path().join("a/b/c/d");
+    let dir_f = dir.path().join("a/b/c/d/e/f");
+    create_dir_all(&dir_f).unwrap();
+
+    std::fs::write(
+        dir_d.join("foo.config.js"),
+        r#"
+        module.exports = async () => ({ name: ["cor", "rect"].join("") });
+        "#,
+    )
+    .unwrap();
+
+    let config = search::<TestConfig>("foo", &dir_f).unwrap().unwrap();
+    assert_eq!(config.value.name, "correct");
+}
+
+#[test]
+fn config_mjs() {
+    let dir = tempdir().unwrap();
+    let dir_d = dir.path().join("a/b/c/d");
+    let dir_f = dir.path().join("a/b/c/d/e/f");
+    create_dir_all(&dir_f).unwrap();
+
+    std::fs::write(
+        dir_d.join("foo.config.mjs"),
+        r#"
+        export const unused = 1;
+        export default { name: "correct" };
+        "#,
+    )
+    .unwrap();
+
+    let config = search::<TestConfig>("foo", &dir_f).unwrap().unwrap();
+    assert_eq!(config.value.name, "correct");
+}
+
+#[test]
+fn config_js_without_config() {
+    let dir = tempdir().unwrap();
+    let dir_d = dir.path().join("a/b/c/d");
+    let dir_f = dir.path().join("a/b/c/d/e/f");
+    create_dir_all(&dir_f).unwrap();
+
+    std::fs::write(dir_d.join("foo.config.js"), "module.exports = () => {};").unwrap();
+
+    match search::<TestConfig>("foo", &dir_f).unwrap_err() {
+        ConfigError {
+            code: ErrorCode::NodeExecuteError { output },
+            path,
+        } => {
+            assert_eq!(path.file_name().unwrap(), "foo.config.js");
+            assert!(String::from_utf8_lossy(&output.stderr).contains("to export a config object"));
+        }
+        other => panic!("incorrect error: {:?}", other),
+    }
+}
+
 #[test]
 fn config_js_invalid_js() {
     let dir = tempdir().unwrap();
@@ -174,7 +234,7 @@ fn config_js_invalid_js() {
 }
 
 #[test]
-fn unsupported_file() {
+fn rc_yaml() {
     let dir = tempdir().unwrap();
     let dir_d = dir.path().join("a/b/c/d");
     let dir_f = dir.path().join("a/b/c/d/e/f");
@@ -182,12 +242,26 @@ fn unsupported_file() {
 
     std::fs::write(dir_d.join(".foorc.yaml"), "name: correct").unwrap();
 
+    let config = search::<TestConfig>("foo", &dir_f).unwrap().unwrap();
+    assert_eq!(config.value.name, "correct");
+    assert_eq!(config.path.file_name().unwrap(), ".foorc.yaml");
+}
+
+#[test]
+fn rc_yaml_invalid_syntax() {
+    let dir = tempdir().unwrap();
+    let dir_d = dir.path().join("a/b/c/d");
+    let dir_f = dir.path().join("a/b/c/d/e/f");
+    create_dir_all(&dir_f).unwrap();
+
+    std::fs::write(dir_d.join(".foorc.yml"), "name: [correct").unwrap();
+
     match search::<TestConfig>("foo", &dir_f).unwrap_err() {
         ConfigError {
-            code: ErrorCode::YamlFileUnsupported,
+            code: ErrorCode::YamlParseError { .. },
             path,
         } => {
-            assert_eq!(path.file_name().unwrap(), ".foorc.yaml");
+            assert_eq!(path.file_name().unwrap(), ".foorc.yml");
         }
         other => panic!("incorrect error: {:?}", other),
     }
@@ -20,4 +20,25 @@ pub enum Error {
 
     #[error("Unable to run relay compiler. Error details: \n{details}")]
     CompilerError { details: String },
+
+    #[error("{details}")]
+    DefinitionNotFound { details: String },
+
+    #[error("Lint failed with {count} problem(s).")]
+    LintError { count: usize },
+
+    #[error("Doctor found {count} problem(s).")]
+    DoctorError { count: usize },
+
+    #[error("{count} breaking schema change(s) affect the documents.")]
+    SchemaDiffError { count: usize },
+
+    #[error("Schema lint found {count} problem(s).")]
+    SchemaLintError { count: usize },
+
+    #[error("{0}")]
+    MockError(relay_mocks::MockError),
+
+    #[error("Unknown diagnostic code `{code}`, codes look like `RELAY-1007`.")]
+    UnknownDiagnosticCode { code: String },
 }
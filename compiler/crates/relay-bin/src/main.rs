@@ -7,29 +7,94 @@
 
 use std::env;
 use std::env::current_dir;
+use std::io::Read;
+use std::num::NonZeroUsize;
+use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Arc;
 
 use clap::ArgEnum;
 use clap::Parser;
+use common::diagnostic_code_url;
 use common::ConsoleLogger;
+use common::PerfLogger;
+use common::SchemaCoordinate;
+use graphql_cli::override_colors;
+use graphql_cli::DiagnosticPrinterOptions;
+use graphql_ir::OperationDefinitionName;
 use intern::string_key::Intern;
 use intern::Lookup;
 use log::error;
 use log::info;
+use log::warn;
+use relay_compiler::allocations::set_counting;
+use relay_compiler::allocations::CountingAllocator;
+use relay_compiler::bench::bench_projects;
+use relay_compiler::bench::bench_to_json;
+use relay_compiler::bench::bench_to_text;
+use relay_compiler::bench::BenchOptions;
+use relay_compiler::bench::ProjectBench;
+use relay_compiler::build_project::artifact_writer::ArtifactOutputRootWriter;
 use relay_compiler::build_project::artifact_writer::ArtifactValidationWriter;
+use relay_compiler::build_report::BuildReport;
+use relay_compiler::build_stats::BuildStats;
+use relay_compiler::build_stats::BuildStatsPerfLogger;
+use relay_compiler::chrome_trace::ChromeTrace;
+use relay_compiler::chrome_trace::ChromeTracePerfLogger;
 use relay_compiler::compiler::Compiler;
+use relay_compiler::compiler_state::CompilerState;
 use relay_compiler::config::Config;
+use relay_compiler::dependency_graph::DependencyGraph;
+use relay_compiler::dependency_graph::GraphNodeKind;
+use relay_compiler::diagnostics_json::json_diagnostics;
+use relay_compiler::diagnostics_json::print_json_diagnostics;
+use relay_compiler::doctor::check_config;
+use relay_compiler::doctor::check_watchman;
+use relay_compiler::doctor::print_checks;
+use relay_compiler::doctor::CheckStatus;
+use relay_compiler::doctor::DoctorCheck;
 use relay_compiler::errors::Error as CompilerError;
+use relay_compiler::explain::explain_operation;
+use relay_compiler::explain::explain_operation_json;
+use relay_compiler::explain::ExplainPhase;
+use relay_compiler::fixes::apply_removal_fixes;
+use relay_compiler::generate_artifacts;
+use relay_compiler::git_changed_files_since;
+use relay_compiler::lint::line_and_column;
+use relay_compiler::lint::print_lint_json;
+use relay_compiler::lint::print_lint_text;
+use relay_compiler::log_groups::GroupedLogger;
+use relay_compiler::sarif::print_sarif;
+use relay_compiler::schema_impact::build_diff_schema;
+use relay_compiler::schema_impact::count_breaking_impacts;
+use relay_compiler::schema_impact::find_change_impact;
+use relay_compiler::schema_impact::print_impact_json;
+use relay_compiler::schema_impact::print_impact_text;
+use relay_compiler::schema_lint::lint_schema;
+use relay_compiler::status_reporter::BuildReportStatusReporter;
+use relay_compiler::status_reporter::ConsoleStatusReporter;
+use relay_compiler::status_reporter::JsonStatusReporter;
+use relay_compiler::status_reporter::SummaryStatusReporter;
 use relay_compiler::FileSourceKind;
+use relay_compiler::FsSourceReader;
 use relay_compiler::LocalPersister;
 use relay_compiler::OperationPersister;
+use relay_compiler::OtlpConfig;
 use relay_compiler::PersistConfig;
 use relay_compiler::RemotePersister;
+use relay_lsp::find_field_usages::get_usages;
+use relay_lsp::find_field_usages::parse_field_coordinate;
 use relay_lsp::start_language_server;
 use relay_lsp::DummyExtraDataProvider;
+use relay_mocks::mock_definition;
+use relay_mocks::MockError;
+use relay_mocks::MockOptions;
+use relay_mocks::ScalarGenerator;
+use relay_transforms::find_diagnostic_code;
+use schema::validate_schema_coordinate;
 use schema::SDLSchema;
+use schema_diff::breaking::find_schema_changes;
 use schema_documentation::SchemaDocumentationLoader;
 use simplelog::ColorChoice;
 use simplelog::ConfigBuilder as SimpleLogConfigBuilder;
@@ -41,6 +106,11 @@ mod errors;
 
 use errors::Error;
 
+// Counts the allocations of the phases for `relay bench` and
+// `--memoryReport`, and only allocates with the system allocator otherwise.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
 #[derive(Parser)]
 #[clap(
      name = "Relay Compiler",
@@ -84,14 +154,124 @@ struct CompileCommand {
     #[clap(long)]
     repersist: bool,
 
+    /// Fetch remote schemas from their endpoints even if a cached copy exists.
+    #[clap(long)]
+    refresh_schema: bool,
+
     /// Verbosity level
     #[clap(long, arg_enum, default_value = "verbose")]
     output: OutputKind,
 
+    /// Format of the diagnostics of each build. With `json` or `sarif`, they
+    /// are printed to stdout and the logs go to stderr. `summary` prints their
+    /// counts by file and by rule, and writes all of them to a report file
+    #[clap(long, arg_enum, default_value = "text")]
+    diagnostics_format: DiagnosticsFormat,
+
+    /// With `--diagnosticsFormat summary`, the file to write all the
+    /// diagnostics to. Defaults to `relay-diagnostics.txt` in the temp dir
+    #[clap(long)]
+    diagnostics_report: Option<PathBuf>,
+
+    /// The lines of source printed before and after the span of each
+    /// diagnostic and annotation
+    #[clap(long, default_value = "1")]
+    context_lines: usize,
+
+    /// Print only the file, line and column of the locations of diagnostics,
+    /// without the excerpts of the source, e.g. for terse CI logs
+    #[clap(long)]
+    no_snippets: bool,
+
+    /// Whether to color the output. `auto` colors it when it is a terminal
+    #[clap(long, arg_enum, default_value = "auto")]
+    color: ColorMode,
+
+    /// Print the explanation of a diagnostic code, like `RELAY-1007`, and
+    /// exit without compiling
+    #[clap(long, value_name = "CODE")]
+    explain: Option<String>,
+
     /// Looks for pending changes and exits with non-zero code instead of
     /// writing to disk
     #[clap(long)]
     validate: bool,
+
+    /// Print statistics of the build per project, e.g. the number of
+    /// artifacts, the largest operations and the durations of the phases
+    #[clap(long, conflicts_with = "watch")]
+    stats: bool,
+
+    /// Write the statistics of the build as JSON to this file
+    #[clap(long, conflicts_with = "watch")]
+    stats_json: Option<PathBuf>,
+
+    /// Write a JSON report of each build to this file, also in watch mode:
+    /// the artifacts written, updated and deleted, the diagnostics, the
+    /// schema cache hits, the durations of the phases and the persisted
+    /// operations
+    #[clap(long)]
+    build_report: Option<PathBuf>,
+
+    /// Only compile the documents in the files that changed since this git
+    /// revision, including uncommitted and untracked files, and the
+    /// operations and fragments related to them by spreads
+    #[clap(long, conflicts_with = "watch")]
+    affected_since: Option<String>,
+
+    /// Compile exactly the source and schema files listed in this file, one
+    /// path per line, instead of searching the source directories. Relative
+    /// paths are relative to the root dir. Pass `-` to read the list from stdin
+    #[clap(long, conflicts_with_all = &["watch", "affectedSince"])]
+    files_from: Option<PathBuf>,
+
+    /// Write the generated files to the same relative paths under this
+    /// directory instead of the root dir
+    #[clap(long, conflicts_with_all = &["watch", "validate"])]
+    output_root: Option<PathBuf>,
+
+    /// Skip the operations and fragments with errors, and the ones that
+    /// spread them, and still generate the artifacts of the others. The
+    /// build still fails with the errors
+    #[clap(long)]
+    keep_going: bool,
+
+    /// Stop building projects once this many errors were found, and report
+    /// only this many. `1` fails on the first error
+    #[clap(long)]
+    max_errors: Option<NonZeroUsize>,
+
+    /// The number of threads to compile with. Defaults to the `jobs` of the
+    /// config, or one per core
+    #[clap(long, short, env = "RELAY_JOBS")]
+    jobs: Option<NonZeroUsize>,
+
+    /// The maximum number of threads that write the artifacts of a project
+    /// at the same time
+    #[clap(long, env = "RELAY_ARTIFACT_WRITE_CONCURRENCY")]
+    artifact_write_concurrency: Option<NonZeroUsize>,
+
+    /// The maximum number of operations of a project that are persisted at
+    /// the same time
+    #[clap(long, env = "RELAY_PERSIST_CONCURRENCY")]
+    persist_concurrency: Option<NonZeroUsize>,
+
+    /// Write the timers of the build phases, transforms and artifact writes
+    /// of each project to this file as Chrome trace events, which can be
+    /// opened with `chrome://tracing` or Perfetto
+    #[clap(long, conflicts_with = "watch")]
+    profile: Option<PathBuf>,
+
+    /// Print an estimate of the memory the schema of each project holds
+    /// after the build
+    #[clap(long, conflicts_with_all = &["watch", "stats", "statsJson", "profile"])]
+    print_memory_stats: bool,
+
+    /// Print the allocations of each phase of each project, and the peak
+    /// heap and resident memory of the build. The projects are built one at
+    /// a time. Requires relay to be built with the `memory_profile` feature
+    #[clap(long, conflicts_with = "watch")]
+    memory_report: bool,
 }
 
 #[derive(Parser)]
@@ -110,10 +290,428 @@ struct LspCommand {
     output: OutputKind,
 }
 
+#[derive(Parser)]
+#[clap(
+    about = "Inspect the compiler configuration.",
+    rename_all = "camel_case"
+)]
+struct ConfigCommand {
+    #[clap(subcommand)]
+    command: ConfigSubcommand,
+}
+
+#[derive(clap::Subcommand)]
+enum ConfigSubcommand {
+    Check(ConfigCheckCommand),
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Validates the config file without compiling.",
+    rename_all = "camel_case"
+)]
+struct ConfigCheckCommand {
+    /// Validate this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Validates Relay files without writing generated files.",
+    rename_all = "camel_case"
+)]
+struct LintCommand {
+    /// Lint only this project. You can pass this argument multiple times.
+    /// If excluded, all projects will be linted.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+
+    /// Lint using this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Also run validations for likely mistakes, e.g. selections of
+    /// deprecated fields
+    #[clap(long)]
+    pedantic: bool,
+
+    /// Output format of the findings
+    #[clap(long, arg_enum, default_value = "text")]
+    format: LintFormat,
+
+    /// Remove the unused variables and arguments found, then report the
+    /// remaining findings
+    #[clap(long)]
+    fix: bool,
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum LintFormat {
+    Text,
+    Json,
+    Sarif,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Prints an operation after each transform phase, without writing generated files.",
+    rename_all = "camel_case"
+)]
+struct ExplainCommand {
+    /// Name of the operation to print
+    operation: String,
+
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Only search this project. You can pass this argument multiple times.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+
+    /// Only print the operation in this phase. You can pass this argument
+    /// multiple times. If excluded, all phases are printed.
+    #[clap(name = "phase", long, arg_enum)]
+    phases: Vec<ExplainPhaseArg>,
+
+    /// Print the fragments inlined in the operation, each preceded by a
+    /// `# from FragmentName` comment.
+    #[clap(long)]
+    inline: bool,
+
+    /// Output format. The JSON output is a list with the IR of the operation
+    /// and its fragments in each phase, for each project that has the
+    /// operation. `--inline` only applies to the text output.
+    #[clap(long, arg_enum, default_value = "text")]
+    format: ExplainFormat,
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum ExplainFormat {
+    Text,
+    Json,
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum ExplainPhaseArg {
+    Source,
+    Reader,
+    Normalization,
+    OperationText,
+    Typegen,
+}
+
+impl From<ExplainPhaseArg> for ExplainPhase {
+    fn from(phase: ExplainPhaseArg) -> Self {
+        match phase {
+            ExplainPhaseArg::Source => ExplainPhase::Source,
+            ExplainPhaseArg::Reader => ExplainPhase::Reader,
+            ExplainPhaseArg::Normalization => ExplainPhase::Normalization,
+            ExplainPhaseArg::OperationText => ExplainPhase::OperationText,
+            ExplainPhaseArg::Typegen => ExplainPhase::Typegen,
+        }
+    }
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Prints the fragment dependency graph of the operations and fragments.",
+    rename_all = "camel_case"
+)]
+struct GraphCommand {
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Only print the graph of this project. You can pass this argument
+    /// multiple times. If excluded, the graphs of all projects are printed.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+
+    /// Output format of the graph. The JSON output is a list with the graph
+    /// of each project.
+    #[clap(long, arg_enum, default_value = "dot")]
+    format: GraphFormat,
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum GraphFormat {
+    Dot,
+    Json,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Prints the chains of spreads from operations to a fragment, and the artifacts that include it.",
+    rename_all = "camel_case"
+)]
+struct WhyCommand {
+    /// Name of the fragment
+    fragment: String,
+
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Only search this project. You can pass this argument multiple times.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Checks the environment of the compiler and prints how to fix the problems found.",
+    rename_all = "camel_case"
+)]
+struct DoctorCommand {
+    /// Check this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Only check this project. You can pass this argument multiple times.
+    /// If excluded, all projects will be checked.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Prints the schema of a project, extended with its client schema extensions and Relay Resolvers, in SDL.",
+    rename_all = "camel_case"
+)]
+struct PrintSchemaCommand {
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Print the schema of this project. Required if the config has multiple
+    /// projects.
+    #[clap(long, short)]
+    project: Option<String>,
+
+    /// Sort the types, directives, fields and enum values by name
+    #[clap(long)]
+    sort: bool,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Classifies the changes between two schemas, and lists the operations and fragments that they affect.",
+    rename_all = "camel_case"
+)]
+struct SchemaDiffCommand {
+    /// The SDL file of the old schema
+    old: PathBuf,
+
+    /// The SDL file of the new schema
+    new: PathBuf,
+
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Only check the documents of this project. You can pass this argument
+    /// multiple times. If excluded, all projects are checked.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+
+    /// Output format of the report. The JSON output is a list with the
+    /// changes of each project.
+    #[clap(long, arg_enum, default_value = "text")]
+    format: SchemaDiffFormat,
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum SchemaDiffFormat {
+    Text,
+    Json,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Checks the schema of the projects, with their client schema extensions, with the rules of `schemaLintConfig`.",
+    rename_all = "camel_case"
+)]
+struct SchemaLintCommand {
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Only check the schema of this project. You can pass this argument
+    /// multiple times. If excluded, all projects are checked.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+
+    /// Output format of the findings
+    #[clap(long, arg_enum, default_value = "text")]
+    format: LintFormat,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Lists every selection of a schema field, given as a coordinate like `User.avatar`, in the operations and fragments.",
+    rename_all = "camel_case"
+)]
+struct UsagesCommand {
+    /// Schema coordinate of the field, e.g. `User.avatar`
+    coordinate: String,
+
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Only search this project. You can pass this argument multiple times.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+
+    /// Output format of the usages. The JSON output is a list with the
+    /// usages in each project.
+    #[clap(long, arg_enum, default_value = "text")]
+    format: UsagesFormat,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Prints a mock response of an operation, or mock data of a fragment, that is valid for the schema.",
+    rename_all = "camel_case"
+)]
+struct MockCommand {
+    /// Name of the operation or fragment
+    definition: String,
+
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Only search this project. You can pass this argument multiple times.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+
+    /// Mock options as JSON, like
+    /// `{"seed": 1, "listLength": 2, "connectionLength": 3, "scalars": {"DateTime": "2024-01-0{n}"}}`.
+    /// The other arguments override them.
+    #[clap(long)]
+    options: Option<String>,
+
+    /// The same seed prints the same data.
+    #[clap(long)]
+    seed: Option<u64>,
+
+    /// Number of items of list fields
+    #[clap(long)]
+    list_length: Option<usize>,
+
+    /// Number of edges of connections
+    #[clap(long)]
+    connection_length: Option<usize>,
+
+    /// Value of a custom scalar, like `DateTime=2024-01-01` or `Cost=12.5`.
+    /// The value is JSON, or else a string, where `{n}` is replaced with the
+    /// number of the value. You can pass this argument multiple times.
+    #[clap(name = "scalar", long)]
+    scalars: Vec<String>,
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum UsagesFormat {
+    Text,
+    Json,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Runs the phases of the compiler repeatedly over the projects, and prints the duration and allocations of each phase.",
+    rename_all = "camel_case"
+)]
+struct BenchCommand {
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `relay` key or `relay.config.json` files among other up
+    /// from the current working directory.
+    config: Option<PathBuf>,
+
+    /// Only run this project. You can pass this argument multiple times.
+    /// If excluded, all projects are run.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+
+    /// Measured runs of each project
+    #[clap(long, default_value = "5")]
+    iterations: usize,
+
+    /// Runs of each project before the measured runs
+    #[clap(long, default_value = "1")]
+    warmup: usize,
+
+    /// Run over the sources saved in this file with `--saveCorpus`, instead
+    /// of the files of the projects.
+    #[clap(long)]
+    corpus: Option<PathBuf>,
+
+    /// Save the sources of the projects to this file, to run other versions
+    /// of the compiler over the same sources with `--corpus`.
+    #[clap(long, conflicts_with = "corpus")]
+    save_corpus: Option<PathBuf>,
+
+    /// Print the change of each phase since the results in this file, which
+    /// were printed with `--format json`.
+    #[clap(long)]
+    compare: Option<PathBuf>,
+
+    /// Output format of the results. The JSON output is a list with the
+    /// results of each project.
+    #[clap(long, arg_enum, default_value = "text")]
+    format: BenchFormat,
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum BenchFormat {
+    Text,
+    Json,
+}
+
 #[derive(clap::Subcommand)]
 enum Commands {
     Compiler(CompileCommand),
     Lsp(LspCommand),
+    Config(ConfigCommand),
+    Lint(LintCommand),
+    Explain(ExplainCommand),
+    Graph(GraphCommand),
+    Why(WhyCommand),
+    Doctor(DoctorCommand),
+    PrintSchema(PrintSchemaCommand),
+    SchemaDiff(SchemaDiffCommand),
+    SchemaLint(SchemaLintCommand),
+    Usages(UsagesCommand),
+    Mock(MockCommand),
+    Bench(BenchCommand),
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum DiagnosticsFormat {
+    Text,
+    Summary,
+    Json,
+    Sarif,
 }
 
 #[derive(ArgEnum, Clone, Copy)]
@@ -170,6 +768,18 @@ async fn main() {
     let result = match command {
         Commands::Compiler(command) => handle_compiler_command(command).await,
         Commands::Lsp(command) => handle_lsp_command(command).await,
+        Commands::Config(command) => handle_config_command(command),
+        Commands::Lint(command) => handle_lint_command(command).await,
+        Commands::Explain(command) => handle_explain_command(command).await,
+        Commands::Graph(command) => handle_graph_command(command).await,
+        Commands::Why(command) => handle_why_command(command).await,
+        Commands::Doctor(command) => handle_doctor_command(command).await,
+        Commands::PrintSchema(command) => handle_print_schema_command(command).await,
+        Commands::SchemaDiff(command) => handle_schema_diff_command(command).await,
+        Commands::SchemaLint(command) => handle_schema_lint_command(command).await,
+        Commands::Usages(command) => handle_usages_command(command).await,
+        Commands::Mock(command) => handle_mock_command(command).await,
+        Commands::Bench(command) => handle_bench_command(command).await,
     };
 
     match result {
@@ -190,6 +800,14 @@ fn get_config(config_path: Option<PathBuf>) -> Result<Config, Error> {
 }
 
 fn configure_logger(output: OutputKind, terminal_mode: TerminalMode) {
+    configure_logger_with_colors(output, terminal_mode, ColorChoice::Auto)
+}
+
+fn configure_logger_with_colors(
+    output: OutputKind,
+    terminal_mode: TerminalMode,
+    color_choice: ColorChoice,
+) {
     let log_level = match output {
         OutputKind::Debug => LevelFilter::Debug,
         OutputKind::Quiet => LevelFilter::Off,
@@ -204,7 +822,15 @@ fn configure_logger(output: OutputKind, terminal_mode: TerminalMode) {
         .set_thread_level(LevelFilter::Off)
         .build();
 
-    TermLogger::init(log_level, log_config, terminal_mode, ColorChoice::Auto).unwrap();
+    // The log lines of projects that are built in parallel are grouped.
+    log::set_max_level(log_level);
+    log::set_boxed_logger(Box::new(GroupedLogger::new(TermLogger::new(
+        log_level,
+        log_config,
+        terminal_mode,
+        color_choice,
+    ))))
+    .unwrap();
 }
 
 /// Update Config if the `project` flag is set
@@ -240,11 +866,62 @@ fn set_project_flag(config: &mut Config, projects: Vec<String>) -> Result<(), Er
     return Ok(());
 }
 
-async fn handle_compiler_command(command: CompileCommand) -> Result<(), Error> {
-    configure_logger(command.output, TerminalMode::Mixed);
+fn explain_diagnostic_code(code: &str) -> Result<(), Error> {
+    let diagnostic_code =
+        find_diagnostic_code(code).ok_or_else(|| Error::UnknownDiagnosticCode {
+            code: code.to_string(),
+        })?;
+    println!(
+        "{} ({})\n\n{}\n\nSee {}",
+        diagnostic_code.code,
+        diagnostic_code.name,
+        diagnostic_code.explanation,
+        diagnostic_code_url(diagnostic_code.code)
+    );
+    Ok(())
+}
 
-    if command.cli_config.is_defined() {
-        return Err(Error::ConfigError(CompilerError::ConfigError {
+/// The number of files and rules with the most diagnostics printed by
+/// `--diagnosticsFormat summary`.
+const SUMMARY_TOP_COUNT: usize = 10;
+
+fn console_status_reporter(
+    config: &Config,
+    printer_options: DiagnosticPrinterOptions,
+) -> ConsoleStatusReporter {
+    ConsoleStatusReporter::new(
+        config.root_dir.clone(),
+        config.projects.len() > 1,
+        config.extract_options(),
+    )
+    .with_printer_options(printer_options)
+}
+
+async fn handle_compiler_command(command: CompileCommand) -> Result<(), Error> {
+    let color_choice = match command.color {
+        ColorMode::Auto => ColorChoice::Auto,
+        ColorMode::Always => {
+            override_colors(true);
+            ColorChoice::Always
+        }
+        ColorMode::Never => {
+            override_colors(false);
+            ColorChoice::Never
+        }
+    };
+    // Keep stdout for the diagnostics, so that the JSON output can be parsed.
+    let terminal_mode = match command.diagnostics_format {
+        DiagnosticsFormat::Text | DiagnosticsFormat::Summary => TerminalMode::Mixed,
+        DiagnosticsFormat::Json | DiagnosticsFormat::Sarif => TerminalMode::Stderr,
+    };
+    configure_logger_with_colors(command.output, terminal_mode, color_choice);
+
+    if let Some(code) = &command.explain {
+        return explain_diagnostic_code(code);
+    }
+
+    if command.cli_config.is_defined() {
+        return Err(Error::ConfigError(CompilerError::ConfigError {
             details: format!(
                 "\nPassing Relay compiler configuration is not supported. Please add `relay.config.json` file,\nor \"relay\" section to your `package.json` file.\n\nCompiler configuration JSON:{}",
                 command.cli_config.get_config_string(),
@@ -259,6 +936,48 @@ async fn handle_compiler_command(command: CompileCommand) -> Result<(), Error> {
     if command.validate {
         config.artifact_writer = Box::new(ArtifactValidationWriter::default());
     }
+    let printer_options = DiagnosticPrinterOptions {
+        context_lines: command.context_lines,
+        snippets: !command.no_snippets,
+    };
+    match command.diagnostics_format {
+        DiagnosticsFormat::Text => {
+            if command.context_lines != 1 || command.no_snippets {
+                config.status_reporter =
+                    Box::new(console_status_reporter(&config, printer_options));
+            }
+        }
+        DiagnosticsFormat::Summary => {
+            let report_path = command
+                .diagnostics_report
+                .unwrap_or_else(|| env::temp_dir().join("relay-diagnostics.txt"));
+            config.status_reporter = Box::new(SummaryStatusReporter::new(
+                console_status_reporter(&config, printer_options),
+                report_path,
+                SUMMARY_TOP_COUNT,
+            ));
+        }
+        DiagnosticsFormat::Json => {
+            config.status_reporter = Box::new(JsonStatusReporter::new(
+                config.root_dir.clone(),
+                config.extract_options(),
+                print_json_diagnostics,
+            ));
+        }
+        DiagnosticsFormat::Sarif => {
+            config.status_reporter = Box::new(JsonStatusReporter::new(
+                config.root_dir.clone(),
+                config.extract_options(),
+                print_sarif,
+            ));
+        }
+    }
+    if let Some(output_root) = command.output_root {
+        config.artifact_writer = Box::new(ArtifactOutputRootWriter::new(
+            config.root_dir.clone(),
+            current_dir().unwrap().join(output_root),
+        ));
+    }
 
     config.create_operation_persister = Some(Box::new(|project_config| {
         project_config.persist.as_ref().map(
@@ -275,12 +994,31 @@ async fn handle_compiler_command(command: CompileCommand) -> Result<(), Error> {
         )
     }));
 
-    config.file_source_config = if should_use_watchman() {
+    config.file_source_config = if let Some(files_from) = &command.files_from {
+        FileSourceKind::FileList(read_file_list(files_from, &config.root_dir)?)
+    } else if should_use_watchman() {
         FileSourceKind::Watchman
     } else {
         FileSourceKind::WalkDir
     };
+    if command.jobs.is_some() {
+        config.jobs = command.jobs;
+    }
+    if command.artifact_write_concurrency.is_some() {
+        config.artifact_write_concurrency = command.artifact_write_concurrency;
+    }
+    if command.persist_concurrency.is_some() {
+        config.persist_concurrency = command.persist_concurrency;
+    }
+    configure_thread_pool(config.jobs);
     config.repersist_operations = command.repersist;
+    config.refresh_schemas = command.refresh_schema;
+    config.keep_going = command.keep_going;
+    config.max_errors = command.max_errors;
+    if let Some(revision) = &command.affected_since {
+        config.affected_files =
+            Some(git_changed_files_since(&config.root_dir, revision).map_err(Error::ConfigError)?);
+    }
 
     if command.watch && !matches!(&config.file_source_config, FileSourceKind::Watchman) {
         panic!(
@@ -288,28 +1026,182 @@ async fn handle_compiler_command(command: CompileCommand) -> Result<(), Error> {
         );
     }
 
-    let compiler = Compiler::new(Arc::new(config), Arc::new(ConsoleLogger));
+    let memory_report = command.memory_report && cfg!(feature = "memory_profile");
+    if command.memory_report && !memory_report {
+        warn!("`--memoryReport` is ignored, relay was built without the `memory_profile` feature.");
+    }
+    if memory_report {
+        // The allocations of concurrent builds can't be told apart.
+        config.max_parallel_projects = NonZeroUsize::new(1);
+        set_counting(true);
+    }
+
+    let build_stats = if command.stats
+        || command.stats_json.is_some()
+        || command.build_report.is_some()
+        || memory_report
+    {
+        let build_stats = Arc::new(BuildStats::default());
+        config.build_stats = Some(Arc::clone(&build_stats));
+        Some(build_stats)
+    } else {
+        None
+    };
+    if let (Some(path), Some(build_stats)) = (command.build_report, &build_stats) {
+        let build_report = Arc::new(BuildReport::new(path, Arc::clone(build_stats)));
+        config.build_report = Some(Arc::clone(&build_report));
+        let console = Box::new(console_status_reporter(&config, printer_options));
+        let inner = std::mem::replace(&mut config.status_reporter, console);
+        config.status_reporter = Box::new(BuildReportStatusReporter::new(inner, build_report));
+    }
 
     if command.watch {
-        compiler.watch().await.map_err(|err| Error::CompilerError {
+        // The build report records the durations of the phases of each build.
+        let result = match &build_stats {
+            Some(build_stats) => {
+                let perf_logger = BuildStatsPerfLogger::new(ConsoleLogger, Arc::clone(build_stats));
+                Compiler::new(Arc::new(config), Arc::new(perf_logger))
+                    .watch()
+                    .await
+            }
+            None => {
+                Compiler::new(Arc::new(config), Arc::new(ConsoleLogger))
+                    .watch()
+                    .await
+            }
+        };
+        return result.map_err(|err| Error::CompilerError {
             details: format!("{:?}", err),
-        })?;
+        });
+    }
+
+    // The builds of watch mode are not exported.
+    let otlp_config = if cfg!(feature = "otlp") && !command.watch {
+        config.otlp.clone().or_else(OtlpConfig::from_env)
     } else {
-        compiler
-            .compile()
-            .await
-            .map_err(|err| Error::CompilerError {
-                details: format!("{}", err),
+        None
+    };
+    if !cfg!(feature = "otlp") && config.otlp.is_some() {
+        warn!("The `otlp` config is ignored, relay was built without the `otlp` feature.");
+    }
+    let trace = if command.profile.is_some() || otlp_config.is_some() {
+        Some(Arc::new(ChromeTrace::default()))
+    } else {
+        None
+    };
+    if build_stats.is_some() || trace.is_some() {
+        // The reports are also written if the build fails, e.g. for the
+        // durations of the phases before the failure.
+        let result = match (&build_stats, &trace) {
+            (Some(build_stats), Some(trace)) => {
+                compile_once(
+                    config,
+                    BuildStatsPerfLogger::new(
+                        ChromeTracePerfLogger::new(ConsoleLogger, Arc::clone(trace)),
+                        Arc::clone(build_stats),
+                    ),
+                )
+                .await
+            }
+            (Some(build_stats), None) => {
+                compile_once(
+                    config,
+                    BuildStatsPerfLogger::new(ConsoleLogger, Arc::clone(build_stats)),
+                )
+                .await
+            }
+            (None, Some(trace)) => {
+                compile_once(
+                    config,
+                    ChromeTracePerfLogger::new(ConsoleLogger, Arc::clone(trace)),
+                )
+                .await
+            }
+            (None, None) => unreachable!(),
+        };
+        if let Some(build_stats) = build_stats {
+            if command.stats {
+                info!("Build statistics:\n{}", build_stats.to_text().trim_end());
+            }
+            if memory_report {
+                info!(
+                    "Memory report:\n{}",
+                    build_stats.to_memory_text().trim_end()
+                );
+            }
+            if let Some(path) = command.stats_json {
+                std::fs::write(&path, build_stats.to_json()).map_err(|err| {
+                    Error::CompilerError {
+                        details: format!("Unable to write stats to {:?}: {}", path, err),
+                    }
+                })?;
+            }
+        }
+        #[cfg(feature = "otlp")]
+        if let (Some(trace), Some(otlp_config)) = (&trace, &otlp_config) {
+            // Failing to export doesn't fail the build.
+            if let Err(err) = relay_compiler::otlp::export(otlp_config, trace, result.is_ok()).await
+            {
+                warn!("{}", err);
+            }
+        }
+        if let (Some(trace), Some(path)) = (trace, command.profile) {
+            std::fs::write(&path, trace.to_json()).map_err(|err| Error::CompilerError {
+                details: format!("Unable to write the profile to {:?}: {}", path, err),
             })?;
+        }
+        return result;
+    }
+
+    let compiler_state = Compiler::new(Arc::new(config), Arc::new(ConsoleLogger))
+        .compile()
+        .await
+        .map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })?;
+    if command.print_memory_stats {
+        let mut schemas = compiler_state.schema_cache.iter().collect::<Vec<_>>();
+        schemas.sort_by_key(|(project_name, _)| project_name.lookup());
+        for (project_name, schema) in schemas {
+            info!(
+                "Schema memory of [{}]:\n{}",
+                project_name,
+                schema.memory_stats()
+            );
+        }
     }
 
     Ok(())
 }
 
+/// Limits the global thread pool that the compiler runs its parallel work
+/// on, e.g. to leave cores to the other processes of a shared CI machine.
+fn configure_thread_pool(jobs: Option<NonZeroUsize>) {
+    if let Some(jobs) = jobs {
+        if let Err(err) = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs.get())
+            .build_global()
+        {
+            warn!("Unable to limit the compiler to {} threads: {}", jobs, err);
+        }
+    }
+}
+
+async fn compile_once(config: Config, perf_logger: impl PerfLogger + 'static) -> Result<(), Error> {
+    Compiler::new(Arc::new(config), Arc::new(perf_logger))
+        .compile()
+        .await
+        .map(|_| ())
+        .map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })
+}
+
 async fn handle_lsp_command(command: LspCommand) -> Result<(), Error> {
     configure_logger(command.output, TerminalMode::Stderr);
 
     let config = get_config(command.config)?;
+    configure_thread_pool(config.jobs);
 
     let perf_logger = Arc::new(ConsoleLogger);
     let extra_data_provider = Box::new(DummyExtraDataProvider::new());
@@ -333,6 +1225,791 @@ async fn handle_lsp_command(command: LspCommand) -> Result<(), Error> {
     Ok(())
 }
 
+fn handle_config_command(command: ConfigCommand) -> Result<(), Error> {
+    configure_logger(OutputKind::Verbose, TerminalMode::Mixed);
+
+    match command.command {
+        ConfigSubcommand::Check(command) => {
+            // Loading the config also validates the paths of its projects.
+            let config = get_config(command.config)?;
+            info!(
+                "The config is valid. Projects: {}.",
+                config
+                    .projects
+                    .keys()
+                    .map(|name| name.lookup())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_lint_command(command: LintCommand) -> Result<(), Error> {
+    // Keep stdout for the findings, so that the JSON output can be parsed.
+    match command.format {
+        LintFormat::Text => configure_logger(OutputKind::Verbose, TerminalMode::Mixed),
+        LintFormat::Json | LintFormat::Sarif => {
+            configure_logger(OutputKind::QuietWithErrors, TerminalMode::Stderr)
+        }
+    }
+
+    let mut config = get_config(command.config)?;
+    set_project_flag(&mut config, command.projects)?;
+    config.pedantic_validations = command.pedantic;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+
+    let root_dir = config.root_dir.clone();
+    let source_reader = FsSourceReader::new(config.extract_options());
+    let compiler = Compiler::new(Arc::new(config), Arc::new(ConsoleLogger));
+    let mut diagnostics = compiler.lint().await.map_err(|err| Error::CompilerError {
+        details: format!("{}", err),
+    })?;
+
+    if command.fix {
+        let fixed_files = apply_removal_fixes(&root_dir, &diagnostics, &source_reader);
+        if !fixed_files.is_empty() {
+            for (path, contents) in &fixed_files {
+                std::fs::write(root_dir.join(path), contents).map_err(|err| {
+                    Error::CompilerError {
+                        details: format!("Unable to write {}: {}", path.display(), err),
+                    }
+                })?;
+            }
+            info!("Fixed {} file(s).", fixed_files.len());
+            diagnostics = compiler.lint().await.map_err(|err| Error::CompilerError {
+                details: format!("{}", err),
+            })?;
+        }
+    }
+
+    match command.format {
+        LintFormat::Text => {
+            if !diagnostics.is_empty() {
+                println!(
+                    "{}",
+                    print_lint_text(&root_dir, &diagnostics, &source_reader)
+                );
+            }
+        }
+        LintFormat::Json => {
+            println!(
+                "{}",
+                print_lint_json(&root_dir, &diagnostics, &source_reader)
+            );
+        }
+        LintFormat::Sarif => {
+            println!(
+                "{}",
+                print_sarif(&json_diagnostics(
+                    &root_dir,
+                    &diagnostics,
+                    None,
+                    &source_reader
+                ))
+            );
+        }
+    }
+
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::LintError {
+            count: diagnostics.len(),
+        })
+    }
+}
+
+async fn handle_explain_command(command: ExplainCommand) -> Result<(), Error> {
+    configure_logger(OutputKind::QuietWithErrors, TerminalMode::Stderr);
+
+    let mut config = get_config(command.config)?;
+    set_project_flag(&mut config, command.projects)?;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+    let is_multi_project = config.projects.len() > 1;
+
+    let compiler = Compiler::new(Arc::new(config), Arc::new(ConsoleLogger));
+    let programs = compiler
+        .programs()
+        .await
+        .map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })?;
+
+    let phases: Vec<ExplainPhase> = if command.phases.is_empty() {
+        ExplainPhase::ALL.to_vec()
+    } else {
+        command.phases.into_iter().map(ExplainPhase::from).collect()
+    };
+    let operation_name = OperationDefinitionName(command.operation.intern());
+    let mut found = false;
+    match command.format {
+        ExplainFormat::Text => {
+            for (project_name, programs, _) in programs {
+                if let Some(output) =
+                    explain_operation(&programs, operation_name, &phases, command.inline)
+                {
+                    if is_multi_project {
+                        println!("## project {}\n", project_name);
+                    }
+                    println!("{}", output);
+                    found = true;
+                }
+            }
+        }
+        ExplainFormat::Json => {
+            let outputs: Vec<serde_json::Value> = programs
+                .iter()
+                .filter_map(|(project_name, programs, _)| {
+                    let mut output = explain_operation_json(programs, operation_name, &phases)?;
+                    output["project"] = serde_json::json!(project_name);
+                    Some(output)
+                })
+                .collect();
+            found = !outputs.is_empty();
+            if found {
+                println!("{}", serde_json::to_string_pretty(&outputs).unwrap());
+            }
+        }
+    }
+
+    if found {
+        Ok(())
+    } else {
+        Err(Error::DefinitionNotFound {
+            details: format!("Operation `{}` not found.", operation_name),
+        })
+    }
+}
+
+async fn handle_graph_command(command: GraphCommand) -> Result<(), Error> {
+    configure_logger(OutputKind::QuietWithErrors, TerminalMode::Stderr);
+
+    let mut config = get_config(command.config)?;
+    set_project_flag(&mut config, command.projects)?;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+
+    let compiler = Compiler::new(Arc::new(config), Arc::new(ConsoleLogger));
+    let programs = compiler
+        .programs()
+        .await
+        .map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })?;
+    let graphs: Vec<DependencyGraph> = programs
+        .iter()
+        .map(|(project_name, programs, _)| DependencyGraph::new(*project_name, programs))
+        .collect();
+
+    match command.format {
+        GraphFormat::Dot => {
+            for graph in graphs {
+                print!("{}", graph.to_dot());
+            }
+        }
+        GraphFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&graphs).unwrap());
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_why_command(command: WhyCommand) -> Result<(), Error> {
+    configure_logger(OutputKind::QuietWithErrors, TerminalMode::Stderr);
+
+    let mut config = get_config(command.config)?;
+    set_project_flag(&mut config, command.projects)?;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+
+    let config = Arc::new(config);
+    let compiler = Compiler::new(Arc::clone(&config), Arc::new(ConsoleLogger));
+    let programs = compiler
+        .programs()
+        .await
+        .map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })?;
+
+    let fragment_name = command.fragment.intern();
+    let mut found = false;
+    for (project_name, programs, source_hashes) in programs {
+        let graph = DependencyGraph::new(project_name, &programs);
+        let node = match graph.node(fragment_name) {
+            Some(node) if node.kind == GraphNodeKind::Fragment => node,
+            _ => continue,
+        };
+        found = true;
+
+        println!(
+            "Fragment `{}` ({}) in the project `{}`:",
+            fragment_name, node.file, project_name
+        );
+        let paths = graph.paths_to(fragment_name);
+        if paths.is_empty() {
+            println!("  Not spread by any operation.");
+        } else {
+            for path in paths {
+                println!(
+                    "  {}",
+                    path.iter()
+                        .map(|name| name.lookup())
+                        .collect::<Vec<_>>()
+                        .join(" -> ")
+                );
+            }
+        }
+
+        let artifacts = generate_artifacts(
+            &config,
+            &config.projects[&project_name],
+            &programs,
+            source_hashes,
+        );
+        let artifacts = graph.artifacts_including(fragment_name, &artifacts);
+        if !artifacts.is_empty() {
+            println!("Included in the artifacts:");
+            for artifact in artifacts {
+                println!("  {}", artifact.path.display());
+            }
+        }
+        println!();
+    }
+
+    if found {
+        Ok(())
+    } else {
+        Err(Error::DefinitionNotFound {
+            details: format!("Fragment `{}` not found.", fragment_name),
+        })
+    }
+}
+
+async fn handle_doctor_command(command: DoctorCommand) -> Result<(), Error> {
+    configure_logger(OutputKind::Verbose, TerminalMode::Mixed);
+
+    let mut checks = vec![check_watchman()];
+    let config = get_config(command.config).and_then(|mut config| {
+        set_project_flag(&mut config, command.projects)?;
+        Ok(config)
+    });
+    match config {
+        Ok(config) => {
+            checks.push(DoctorCheck::ok("The config is valid."));
+            checks.extend(check_config(&config).await);
+        }
+        Err(err) => checks.push(DoctorCheck::error(
+            format!("The config is invalid: {}", err),
+            "Fix the config, see \
+            https://relay.dev/docs/getting-started/installation-and-setup/ for its options.",
+        )),
+    }
+    print!("{}", print_checks(&checks));
+
+    let count = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Error)
+        .count();
+    if count == 0 {
+        Ok(())
+    } else {
+        Err(Error::DoctorError { count })
+    }
+}
+
+async fn handle_print_schema_command(command: PrintSchemaCommand) -> Result<(), Error> {
+    configure_logger(OutputKind::QuietWithErrors, TerminalMode::Stderr);
+
+    let mut config = get_config(command.config)?;
+    let project = match command.project {
+        Some(project) => project,
+        None if config.projects.len() == 1 => config.projects.keys().next().unwrap().to_string(),
+        None => {
+            return Err(Error::ProjectFilterError {
+                details: format!(
+                    "Pass the project to print with `--project`, available projects: {}.",
+                    config
+                        .projects
+                        .keys()
+                        .map(|name| name.lookup())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            });
+        }
+    };
+    set_project_flag(&mut config, vec![project])?;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+
+    let compiler = Compiler::new(Arc::new(config), Arc::new(ConsoleLogger));
+    let schemas = compiler
+        .schemas()
+        .await
+        .map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })?;
+    for (_, schema) in schemas {
+        if command.sort {
+            print!("{}", schema_print::print_sorted(&schema));
+        } else {
+            print!("{}", schema_print::print(&schema));
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_schema_diff_command(command: SchemaDiffCommand) -> Result<(), Error> {
+    configure_logger(OutputKind::QuietWithErrors, TerminalMode::Stderr);
+
+    let read_schema = |path: &PathBuf| {
+        std::fs::read_to_string(path).map_err(|err| Error::CompilerError {
+            details: format!("Unable to read the schema {:?}: {}", path, err),
+        })
+    };
+    let old_text = read_schema(&command.old)?;
+    let new_text = read_schema(&command.new)?;
+
+    let mut config = get_config(command.config)?;
+    set_project_flag(&mut config, command.projects)?;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+    let root_dir = config.root_dir.clone();
+    let source_reader = FsSourceReader::new(config.extract_options());
+    let config = Arc::new(config);
+    let compiler = Compiler::new(Arc::clone(&config), Arc::new(ConsoleLogger));
+    let programs = compiler
+        .programs()
+        .await
+        .map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })?;
+
+    let mut project_impacts = Vec::new();
+    for (project_name, programs, _) in programs {
+        let project_config = &config.projects[&project_name];
+        let build = |path: &PathBuf, text: &str| {
+            build_diff_schema(project_config, &path.to_string_lossy(), text).map_err(|errors| {
+                Error::CompilerError {
+                    details: format!(
+                        "Invalid schema {:?}: {}",
+                        path,
+                        errors
+                            .iter()
+                            .map(|error| error.message().to_string())
+                            .collect::<Vec<_>>()
+                            .join("; ")
+                    ),
+                }
+            })
+        };
+        let old = build(&command.old, &old_text)?;
+        let new = build(&command.new, &new_text)?;
+        let changes = find_schema_changes(&old, &new);
+        project_impacts.push((project_name, find_change_impact(&programs.source, changes)));
+    }
+
+    match command.format {
+        SchemaDiffFormat::Text => {
+            let is_multi_project = project_impacts.len() > 1;
+            for (project_name, impacts) in &project_impacts {
+                if is_multi_project {
+                    println!("## project {}\n", project_name);
+                }
+                print!("{}", print_impact_text(&root_dir, impacts, &source_reader));
+            }
+        }
+        SchemaDiffFormat::Json => {
+            println!(
+                "{}",
+                print_impact_json(&root_dir, &project_impacts, &source_reader)
+            );
+        }
+    }
+
+    let count: usize = project_impacts
+        .iter()
+        .map(|(_, impacts)| count_breaking_impacts(impacts))
+        .sum();
+    if count == 0 {
+        Ok(())
+    } else {
+        Err(Error::SchemaDiffError { count })
+    }
+}
+
+async fn handle_schema_lint_command(command: SchemaLintCommand) -> Result<(), Error> {
+    // Keep stdout for the findings, so that the JSON output can be parsed.
+    match command.format {
+        LintFormat::Text => configure_logger(OutputKind::Verbose, TerminalMode::Mixed),
+        LintFormat::Json | LintFormat::Sarif => {
+            configure_logger(OutputKind::QuietWithErrors, TerminalMode::Stderr)
+        }
+    }
+
+    let mut config = get_config(command.config)?;
+    set_project_flag(&mut config, command.projects)?;
+    // Cached schemas have no locations or descriptions to check.
+    config.schema_cache_dir = None;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+    let root_dir = config.root_dir.clone();
+    let source_reader = FsSourceReader::new(config.extract_options());
+    let config = Arc::new(config);
+    let compiler = Compiler::new(Arc::clone(&config), Arc::new(ConsoleLogger));
+    let schemas = compiler
+        .schemas()
+        .await
+        .map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })?;
+
+    let mut diagnostics = Vec::new();
+    for (project_name, schema) in schemas {
+        diagnostics.extend(lint_schema(
+            &schema,
+            &config.projects[&project_name].schema_lint_config,
+        ));
+    }
+
+    match command.format {
+        LintFormat::Text => {
+            if !diagnostics.is_empty() {
+                println!(
+                    "{}",
+                    print_lint_text(&root_dir, &diagnostics, &source_reader)
+                );
+            }
+        }
+        LintFormat::Json => {
+            println!(
+                "{}",
+                print_lint_json(&root_dir, &diagnostics, &source_reader)
+            );
+        }
+        LintFormat::Sarif => {
+            println!(
+                "{}",
+                print_sarif(&json_diagnostics(
+                    &root_dir,
+                    &diagnostics,
+                    None,
+                    &source_reader
+                ))
+            );
+        }
+    }
+
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::SchemaLintError {
+            count: diagnostics.len(),
+        })
+    }
+}
+
+async fn handle_usages_command(command: UsagesCommand) -> Result<(), Error> {
+    configure_logger(OutputKind::QuietWithErrors, TerminalMode::Stderr);
+
+    let (type_name, field_name) = parse_field_coordinate(&command.coordinate)
+        .map_err(|details| Error::CompilerError { details })?;
+    let coordinate = SchemaCoordinate::Member {
+        type_name,
+        member_name: field_name,
+    };
+
+    let mut config = get_config(command.config)?;
+    set_project_flag(&mut config, command.projects)?;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+    let root_dir = config.root_dir.clone();
+    let source_reader = FsSourceReader::new(config.extract_options());
+    let compiler = Compiler::new(Arc::new(config), Arc::new(ConsoleLogger));
+    let programs = compiler
+        .programs()
+        .await
+        .map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })?;
+
+    let mut project_usages = Vec::new();
+    let mut unresolved_reason = None;
+    for (project_name, programs, _) in programs {
+        let schema = &programs.source.schema;
+        if let Err(error) = validate_schema_coordinate(schema.as_ref(), coordinate) {
+            unresolved_reason = Some(error.to_string());
+            continue;
+        }
+        let usages =
+            get_usages(&programs.source, schema, type_name, field_name).map_err(|err| {
+                Error::CompilerError {
+                    details: format!("{:?}", err),
+                }
+            })?;
+        project_usages.push((project_name, usages));
+    }
+    if project_usages.is_empty() {
+        return Err(Error::DefinitionNotFound {
+            details: unresolved_reason
+                .unwrap_or_else(|| format!("Field `{}` not found.", coordinate)),
+        });
+    }
+
+    let position = |location| line_and_column(&root_dir, location, &source_reader);
+    match command.format {
+        UsagesFormat::Text => {
+            let is_multi_project = project_usages.len() > 1;
+            for (project_name, usages) in &project_usages {
+                if is_multi_project {
+                    println!("## project {}\n", project_name);
+                }
+                if usages.is_empty() {
+                    println!("No selections of `{}.{}`.", type_name, field_name);
+                }
+                for (label, location) in usages {
+                    let file = location.source_location().path();
+                    match position(*location) {
+                        Some((line, column)) => {
+                            println!("{} ({}:{}:{})", label, file, line, column)
+                        }
+                        None => println!("{} ({})", label, file),
+                    }
+                }
+            }
+        }
+        UsagesFormat::Json => {
+            let projects: Vec<serde_json::Value> = project_usages
+                .iter()
+                .map(|(project_name, usages)| {
+                    serde_json::json!({
+                        "project": project_name.lookup(),
+                        "usages": usages
+                            .iter()
+                            .map(|(label, location)| {
+                                let start = position(*location);
+                                serde_json::json!({
+                                    "definition": label,
+                                    "file": location.source_location().path(),
+                                    "line": start.map(|(line, _)| line),
+                                    "column": start.map(|(_, column)| column),
+                                })
+                            })
+                            .collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&projects).unwrap());
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_mock_command(command: MockCommand) -> Result<(), Error> {
+    configure_logger(OutputKind::QuietWithErrors, TerminalMode::Stderr);
+
+    let mut options = match &command.options {
+        Some(json) => MockOptions::from_json(json).map_err(Error::MockError)?,
+        None => MockOptions::default(),
+    };
+    if let Some(seed) = command.seed {
+        options.seed = seed;
+    }
+    if let Some(list_length) = command.list_length {
+        options.list_length = list_length;
+    }
+    if let Some(connection_length) = command.connection_length {
+        options.connection_length = connection_length;
+    }
+    for scalar in &command.scalars {
+        let (name, value) = scalar.split_once('=').ok_or_else(|| {
+            Error::MockError(MockError::InvalidOptions(format!(
+                "expected `--scalar Name=value`, got `{}`",
+                scalar
+            )))
+        })?;
+        let value = serde_json::from_str(value)
+            .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+        options
+            .scalars
+            .insert(name.intern(), ScalarGenerator::Value(value));
+    }
+
+    let mut config = get_config(command.config)?;
+    set_project_flag(&mut config, command.projects)?;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+    let is_multi_project = config.projects.len() > 1;
+
+    let compiler = Compiler::new(Arc::new(config), Arc::new(ConsoleLogger));
+    let programs = compiler
+        .programs()
+        .await
+        .map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })?;
+
+    let definition_name = command.definition.intern();
+    let mut found = false;
+    for (project_name, programs, _) in programs {
+        match mock_definition(&programs.source, definition_name, &options) {
+            Ok(data) => {
+                if is_multi_project {
+                    println!("## project {}\n", project_name);
+                }
+                println!("{}", serde_json::to_string_pretty(&data).unwrap());
+                found = true;
+            }
+            // Only the definition itself may be in another project.
+            Err(MockError::UnknownDefinition(name)) if name == definition_name => {}
+            Err(err) => return Err(Error::MockError(err)),
+        }
+    }
+
+    if found {
+        Ok(())
+    } else {
+        Err(Error::DefinitionNotFound {
+            details: format!("Operation or fragment `{}` not found.", definition_name),
+        })
+    }
+}
+
+async fn handle_bench_command(command: BenchCommand) -> Result<(), Error> {
+    configure_logger(OutputKind::QuietWithErrors, TerminalMode::Stderr);
+
+    let mut config = get_config(command.config)?;
+    set_project_flag(&mut config, command.projects)?;
+    // The schemas are built in every run, instead of loaded from the cache.
+    config.schema_cache_dir = None;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+    let config = Arc::new(config);
+
+    let baseline: Option<Vec<ProjectBench>> = match &command.compare {
+        Some(path) => {
+            let json = std::fs::read_to_string(path).map_err(|err| Error::CompilerError {
+                details: format!("Unable to read {:?}: {}", path, err),
+            })?;
+            Some(
+                serde_json::from_str(&json).map_err(|err| Error::CompilerError {
+                    details: format!("Unable to read the results in {:?}: {}", path, err),
+                })?,
+            )
+        }
+        None => None,
+    };
+
+    let compiler_state = match &command.corpus {
+        Some(corpus) => CompilerState::deserialize_from_file(corpus),
+        None => {
+            Compiler::new(Arc::clone(&config), Arc::new(ConsoleLogger))
+                .load_sources()
+                .await
+        }
+    }
+    .map_err(|err| Error::CompilerError {
+        details: format!("{}", err),
+    })?;
+    if let Some(path) = &command.save_corpus {
+        compiler_state
+            .serialize_to_file(path)
+            .map_err(|err| Error::CompilerError {
+                details: format!("{}", err),
+            })?;
+    }
+
+    let options = BenchOptions {
+        iterations: command.iterations,
+        warmup_iterations: command.warmup,
+        count_allocations: true,
+    };
+    let projects =
+        bench_projects(&config, &compiler_state, &options).map_err(|err| Error::CompilerError {
+            details: format!("{}", err),
+        })?;
+    match command.format {
+        BenchFormat::Text => print!("{}", bench_to_text(&projects, baseline.as_deref())),
+        BenchFormat::Json => println!("{}", bench_to_json(&projects)),
+    }
+
+    Ok(())
+}
+
+/// Reads the paths of a `--filesFrom` list, relative to the root dir.
+fn read_file_list(list_path: &Path, root_dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let content = if list_path == Path::new("-") {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .map(|_| content)
+    } else {
+        std::fs::read_to_string(list_path)
+    }
+    .map_err(|err| Error::CompilerError {
+        details: format!("Unable to read the file list {:?}: {}", list_path, err),
+    })?;
+
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let path = Path::new(line);
+            if path.is_absolute() {
+                path.strip_prefix(root_dir)
+                    .map(Path::to_path_buf)
+                    .map_err(|_| Error::CompilerError {
+                        details: format!(
+                            "The file {:?} in the file list is not in the root dir {:?}.",
+                            path, root_dir
+                        ),
+                    })
+            } else {
+                Ok(path.to_path_buf())
+            }
+        })
+        .collect()
+}
+
 /// Check if `watchman` is available.
 /// Additionally, this method is checking for an existence of `FORCE_NO_WATCHMAN`
 /// environment variable. If this `FORCE_NO_WATCHMAN` is set, this method will return `false`
@@ -456,7 +456,12 @@ impl<'b> JSONPrinter<'b> {
                 f.push('\"');
                 Ok(())
             }
-            Primitive::String(key) => write!(f, "\"{}\"", key),
+            Primitive::String(key) => {
+                f.push('\"');
+                escape(key.lookup(), f);
+                f.push('\"');
+                Ok(())
+            }
             Primitive::Float(value) => write!(f, "{}", value.as_float()),
             Primitive::Int(value) => write!(f, "{}", value),
             Primitive::Variable(variable_name) => write!(f, "{}", variable_name),
@@ -776,7 +781,15 @@ fn write_argument_value(f: &mut String, builder: &AstBuilder, arg: &[ObjectEntry
 fn write_constant_value(f: &mut String, builder: &AstBuilder, value: &Primitive) -> FmtResult {
     match value {
         Primitive::Bool(b) => write!(f, "{}", if *b { "true" } else { "false" }),
-        Primitive::String(key) => write!(f, "\\\"{}\\\"", key),
+        Primitive::String(key) => {
+            // A JSON string in the storage key, which is a JavaScript string
+            let mut json = String::new();
+            escape(key.lookup(), &mut json);
+            f.push_str("\\\"");
+            escape(&json, f);
+            f.push_str("\\\"");
+            Ok(())
+        }
         Primitive::Float(value) => write!(f, "{}", value.as_float()),
         Primitive::Int(value) => write!(f, "{}", value),
         Primitive::Variable(variable_name) => write!(f, "{}", variable_name),
@@ -0,0 +1,170 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use intern::string_key::Intern;
+use intern::Lookup;
+use relay_compiler::config::Config;
+use relay_compiler::ArtifactFileWriter;
+use relay_compiler::ArtifactWriter;
+use relay_compiler::FileSourceKind;
+
+use crate::collectors::Collected;
+use crate::collectors::CollectingArtifactWriter;
+use crate::collectors::ForwardingStatusReporter;
+use crate::compiler::Compiler;
+use crate::output::CompileError;
+use crate::status_reporter::StatusReporter;
+
+/// How the compiler finds the source files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileSource {
+    /// Walks the source directories of the config.
+    WalkDir,
+    /// Queries watchman, which needs to be installed.
+    Watchman,
+    /// The exact list of source and schema files, relative to the root
+    /// directory of the config.
+    FileList(Vec<PathBuf>),
+}
+
+impl From<FileSource> for FileSourceKind {
+    fn from(file_source: FileSource) -> Self {
+        match file_source {
+            FileSource::WalkDir => FileSourceKind::WalkDir,
+            FileSource::Watchman => FileSourceKind::Watchman,
+            FileSource::FileList(files) => FileSourceKind::FileList(files),
+        }
+    }
+}
+
+/// Configures a `Compiler`.
+///
+/// By default, all projects of the config are compiled, files are discovered
+/// by walking the source directories (no watchman), artifacts are written to
+/// disk, and nothing is printed.
+pub struct CompilerBuilder {
+    config: Config,
+    projects: Vec<String>,
+    write_artifacts: bool,
+    status_reporter: Option<Box<dyn StatusReporter>>,
+}
+
+impl CompilerBuilder {
+    /// Searches for a config (`relay.config.json`, `package.json`, ...) in
+    /// `start_dir` and its ancestors.
+    pub fn search(start_dir: &Path) -> Result<Self, CompileError> {
+        Ok(Self::new(
+            Config::search(start_dir).map_err(CompileError::from_config_error)?,
+        ))
+    }
+
+    /// Loads the config from the `.json` or `.js` file at `config_path`.
+    pub fn load(config_path: impl Into<PathBuf>) -> Result<Self, CompileError> {
+        Ok(Self::new(
+            Config::load(config_path.into()).map_err(CompileError::from_config_error)?,
+        ))
+    }
+
+    fn new(mut config: Config) -> Self {
+        config.file_source_config = FileSourceKind::WalkDir;
+        Self {
+            config,
+            projects: vec![],
+            write_artifacts: true,
+            status_reporter: None,
+        }
+    }
+
+    /// Only compile the given project. Can be called multiple times, all
+    /// projects are compiled if this is never called.
+    pub fn project(mut self, name: impl Into<String>) -> Self {
+        self.projects.push(name.into());
+        self
+    }
+
+    /// If `false`, artifacts are only returned in the `CompileOutput` and
+    /// nothing is written to (or removed from) disk.
+    pub fn write_artifacts(mut self, write_artifacts: bool) -> Self {
+        self.write_artifacts = write_artifacts;
+        self
+    }
+
+    pub fn file_source(mut self, file_source: FileSource) -> Self {
+        self.config.file_source_config = file_source.into();
+        self
+    }
+
+    /// Generate artifacts for all documents, not only the changed ones.
+    pub fn compile_everything(mut self, compile_everything: bool) -> Self {
+        self.config.compile_everything = compile_everything;
+        self
+    }
+
+    /// Do not reuse persist ids from artifacts even if the text hash matches.
+    pub fn repersist_operations(mut self, repersist_operations: bool) -> Self {
+        self.config.repersist_operations = repersist_operations;
+        self
+    }
+
+    /// Report build progress and diagnostics, e.g. to print them.
+    pub fn status_reporter(mut self, status_reporter: Box<dyn StatusReporter>) -> Self {
+        self.status_reporter = Some(status_reporter);
+        self
+    }
+
+    pub fn build(self) -> Result<Compiler, CompileError> {
+        let Self {
+            mut config,
+            projects,
+            write_artifacts,
+            status_reporter,
+        } = self;
+
+        if !projects.is_empty() {
+            for project_config in config.projects.values_mut() {
+                project_config.enabled = false;
+            }
+            for name in projects {
+                match config.projects.get_mut(&name.as_str().intern()) {
+                    Some(project_config) => project_config.enabled = true,
+                    None => {
+                        return Err(CompileError::UnknownProject {
+                            name,
+                            available: config
+                                .projects
+                                .keys()
+                                .map(|name| name.lookup().to_string())
+                                .collect(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let collected: Arc<Collected> = Default::default();
+        let inner_writer: Option<Box<dyn ArtifactWriter + Send + Sync>> = if write_artifacts {
+            Some(Box::new(ArtifactFileWriter::new(
+                None,
+                config.root_dir.clone(),
+            )))
+        } else {
+            None
+        };
+        config.artifact_writer = Box::new(CollectingArtifactWriter::new(
+            inner_writer,
+            config.root_dir.clone(),
+            Arc::clone(&collected),
+        ));
+        config.status_reporter = Box::new(ForwardingStatusReporter::new(status_reporter));
+
+        Ok(Compiler::new(Arc::new(config), collected))
+    }
+}
@@ -0,0 +1,155 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::mem;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use relay_compiler::errors::BuildProjectError;
+use relay_compiler::errors::Error;
+use relay_compiler::ArtifactWriter;
+
+use crate::diagnostic::Diagnostic;
+use crate::output::CompileError;
+use crate::output::GeneratedArtifact;
+use crate::status_reporter::StatusReporter;
+
+/// Artifacts recorded while the compiler runs. Shared between the
+/// `ArtifactWriter` installed in the config and the `Compiler` that reads them
+/// back once the build is done.
+#[derive(Default)]
+pub(crate) struct Collected {
+    artifacts: Mutex<Vec<GeneratedArtifact>>,
+    removed_artifacts: Mutex<Vec<PathBuf>>,
+}
+
+impl Collected {
+    pub(crate) fn take(&self) -> (Vec<GeneratedArtifact>, Vec<PathBuf>) {
+        let mut artifacts = mem::take(&mut *self.artifacts.lock().unwrap());
+        artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+        let mut removed_artifacts = mem::take(&mut *self.removed_artifacts.lock().unwrap());
+        removed_artifacts.sort();
+        (artifacts, removed_artifacts)
+    }
+}
+
+/// Records every artifact, and forwards writes to the wrapped writer (if any)
+/// when the content is different from what's on disk.
+pub(crate) struct CollectingArtifactWriter {
+    inner: Option<Box<dyn ArtifactWriter + Send + Sync>>,
+    root_dir: PathBuf,
+    collected: Arc<Collected>,
+}
+
+impl CollectingArtifactWriter {
+    pub(crate) fn new(
+        inner: Option<Box<dyn ArtifactWriter + Send + Sync>>,
+        root_dir: PathBuf,
+        collected: Arc<Collected>,
+    ) -> Self {
+        Self {
+            inner,
+            root_dir,
+            collected,
+        }
+    }
+
+    fn relative_path(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.root_dir)
+            .unwrap_or(path)
+            .to_path_buf()
+    }
+}
+
+impl ArtifactWriter for CollectingArtifactWriter {
+    fn should_write(&self, _path: &PathBuf, _content: &[u8]) -> Result<bool, BuildProjectError> {
+        // We want to report all artifacts, not only the changed ones.
+        Ok(true)
+    }
+
+    fn write(&self, path: PathBuf, content: Vec<u8>) -> Result<(), BuildProjectError> {
+        let changed = match &self.inner {
+            Some(inner) => {
+                let changed = inner.should_write(&path, &content)?;
+                if changed {
+                    inner.write(path.clone(), content.clone())?;
+                }
+                changed
+            }
+            None => true,
+        };
+        self.collected
+            .artifacts
+            .lock()
+            .unwrap()
+            .push(GeneratedArtifact {
+                path: self.relative_path(&path),
+                content,
+                changed,
+            });
+        Ok(())
+    }
+
+    fn remove(&self, path: PathBuf) -> Result<(), BuildProjectError> {
+        self.collected
+            .removed_artifacts
+            .lock()
+            .unwrap()
+            .push(self.relative_path(&path));
+        match &self.inner {
+            Some(inner) => inner.remove(path),
+            None => Ok(()),
+        }
+    }
+
+    fn finalize(&self) -> relay_compiler::errors::Result<()> {
+        match &self.inner {
+            Some(inner) => inner.finalize(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Passes the status of the compiler to the `StatusReporter` of the
+/// embedder, if there is one. Otherwise diagnostics and errors are only
+/// returned from `Compiler::compile`.
+pub(crate) struct ForwardingStatusReporter {
+    inner: Option<Box<dyn StatusReporter>>,
+}
+
+impl ForwardingStatusReporter {
+    pub(crate) fn new(inner: Option<Box<dyn StatusReporter>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl relay_compiler::status_reporter::StatusReporter for ForwardingStatusReporter {
+    fn build_starts(&self) {
+        if let Some(inner) = &self.inner {
+            inner.build_starts();
+        }
+    }
+
+    fn build_completes(&self, diagnostics: &[common::Diagnostic]) {
+        if let Some(inner) = &self.inner {
+            inner.build_completes(
+                &diagnostics
+                    .iter()
+                    .map(Diagnostic::from_compiler_diagnostic)
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+
+    fn build_errors(&self, error: &Error) {
+        if let Some(inner) = &self.inner {
+            inner.build_errors(&CompileError::from_compiler_error(error));
+        }
+    }
+}
@@ -0,0 +1,52 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::NoopPerfLogger;
+use common::WithDiagnostics;
+use relay_compiler::config::Config;
+
+use crate::collectors::Collected;
+use crate::diagnostic::Diagnostic;
+use crate::output::CompileError;
+use crate::output::CompileOutput;
+
+/// A configured compiler, created with `CompilerBuilder`.
+pub struct Compiler {
+    config: Arc<Config>,
+    collected: Arc<Collected>,
+}
+
+impl Compiler {
+    pub(crate) fn new(config: Arc<Config>, collected: Arc<Collected>) -> Self {
+        Self { config, collected }
+    }
+
+    /// Runs a full build of the enabled projects.
+    ///
+    /// Documents with errors fail the build with `CompileError::Validation`,
+    /// carrying all the diagnostics of the failing projects.
+    pub async fn compile(&self) -> Result<CompileOutput, CompileError> {
+        let compiler = relay_compiler::compiler::Compiler::new(
+            Arc::clone(&self.config),
+            Arc::new(NoopPerfLogger),
+        );
+        let result = compiler.compile_with_diagnostics().await;
+        let (artifacts, removed_artifacts) = self.collected.take();
+        let WithDiagnostics { diagnostics, .. } =
+            result.map_err(|error| CompileError::from_compiler_error(&error))?;
+        Ok(CompileOutput {
+            artifacts,
+            removed_artifacts,
+            diagnostics: diagnostics
+                .iter()
+                .map(Diagnostic::from_compiler_diagnostic)
+                .collect(),
+        })
+    }
+}
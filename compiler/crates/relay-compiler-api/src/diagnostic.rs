@@ -0,0 +1,118 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fmt;
+use std::path::PathBuf;
+
+use common::DiagnosticSeverity;
+use common::SourceLocationKey;
+
+/// An error, warning or hint about a document or the schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// `None` for diagnostics that are not about a specific file, e.g. about
+    /// the config.
+    pub location: Option<DiagnosticLocation>,
+    /// Other locations of the problem, e.g. the other definitions of a
+    /// duplicate fragment.
+    pub related: Vec<RelatedInformation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Information,
+    Hint,
+}
+
+/// A range of a GraphQL document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticLocation {
+    /// Path of the file, relative to the root directory of the config.
+    pub path: PathBuf,
+    /// For GraphQL embedded in another file, e.g. in a `graphql` tag of a
+    /// JavaScript file, the 0-based index of the embedded document. The
+    /// offsets are relative to that document.
+    pub embedded_index: Option<u16>,
+    /// Byte offsets of the start and end of the range.
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelatedInformation {
+    pub message: String,
+    pub location: Option<DiagnosticLocation>,
+}
+
+impl Diagnostic {
+    pub(crate) fn from_compiler_diagnostic(diagnostic: &common::Diagnostic) -> Self {
+        Self {
+            severity: Severity::from_compiler_severity(diagnostic.severity()),
+            message: diagnostic.message().to_string(),
+            location: DiagnosticLocation::from_compiler_location(diagnostic.location()),
+            related: diagnostic
+                .related_information()
+                .iter()
+                .map(|related| RelatedInformation {
+                    message: related.message.to_string(),
+                    location: DiagnosticLocation::from_compiler_location(related.location),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{}: {}", location, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Severity {
+    fn from_compiler_severity(severity: DiagnosticSeverity) -> Self {
+        match severity {
+            DiagnosticSeverity::WARNING => Severity::Warning,
+            DiagnosticSeverity::INFORMATION => Severity::Information,
+            DiagnosticSeverity::HINT => Severity::Hint,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl DiagnosticLocation {
+    fn from_compiler_location(location: common::Location) -> Option<Self> {
+        let (path, embedded_index) = match location.source_location() {
+            SourceLocationKey::Embedded { path, index } => (path, Some(index)),
+            SourceLocationKey::Standalone { path } => (path, None),
+            SourceLocationKey::Generated => return None,
+        };
+        let span = location.span();
+        Some(Self {
+            path: PathBuf::from(path.to_string()),
+            embedded_index,
+            start: span.start,
+            end: span.end,
+        })
+    }
+}
+
+impl fmt::Display for DiagnosticLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(embedded_index) = self.embedded_index {
+            write!(f, "[{}]", embedded_index)?;
+        }
+        write!(f, ":{}:{}", self.start, self.end)
+    }
+}
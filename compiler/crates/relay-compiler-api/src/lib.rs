@@ -0,0 +1,46 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A stable API for embedding the Relay compiler in other Rust tools.
+//!
+//! The internal crates (`relay-compiler`, `relay-transforms`, ...) change
+//! their signatures frequently. This crate wraps the compile pipeline behind
+//! a small surface that we try hard to keep stable. None of the types of the
+//! internal crates are part of it:
+//!
+//! ```ignore
+//! let compiler = CompilerBuilder::load("relay.config.json")?
+//!     .write_artifacts(false)
+//!     .build()?;
+//! let output = compiler.compile().await?;
+//! for artifact in &output.artifacts {
+//!     println!("{}", artifact.path.display());
+//! }
+//! ```
+
+#![deny(warnings)]
+#![deny(rust_2018_idioms)]
+#![deny(clippy::all)]
+
+mod builder;
+mod collectors;
+mod compiler;
+mod diagnostic;
+mod output;
+mod status_reporter;
+
+pub use builder::CompilerBuilder;
+pub use builder::FileSource;
+pub use compiler::Compiler;
+pub use diagnostic::Diagnostic;
+pub use diagnostic::DiagnosticLocation;
+pub use diagnostic::RelatedInformation;
+pub use diagnostic::Severity;
+pub use output::CompileError;
+pub use output::CompileOutput;
+pub use output::GeneratedArtifact;
+pub use status_reporter::StatusReporter;
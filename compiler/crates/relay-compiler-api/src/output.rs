@@ -0,0 +1,120 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::path::PathBuf;
+
+use relay_compiler::errors::BuildProjectError;
+use relay_compiler::errors::Error;
+use thiserror::Error;
+
+use crate::diagnostic::Diagnostic;
+
+/// A single file produced by the compiler.
+#[derive(Debug, Clone)]
+pub struct GeneratedArtifact {
+    /// Path of the artifact, relative to the root directory of the config.
+    pub path: PathBuf,
+    pub content: Vec<u8>,
+    /// `false` if an identical file already existed on disk.
+    pub changed: bool,
+}
+
+impl GeneratedArtifact {
+    pub fn content_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.content).ok()
+    }
+}
+
+/// The result of a successful compilation.
+#[derive(Debug, Default)]
+pub struct CompileOutput {
+    /// Every artifact generated by this compilation, sorted by path.
+    pub artifacts: Vec<GeneratedArtifact>,
+    /// Artifacts that are no longer generated, relative to the root directory.
+    pub removed_artifacts: Vec<PathBuf>,
+    /// Non-fatal diagnostics (warnings, infos) reported during the build.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl CompileOutput {
+    pub fn changed_artifacts(&self) -> impl Iterator<Item = &GeneratedArtifact> {
+        self.artifacts.iter().filter(|artifact| artifact.changed)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CompileError {
+    #[error("{message}")]
+    Config { message: String },
+
+    #[error("Project `{name}` not found, available projects: {}.", available.join(", "))]
+    UnknownProject {
+        name: String,
+        available: Vec<String>,
+    },
+
+    #[error(
+        "Compilation failed with {} error(s):{}",
+        diagnostics.len(),
+        diagnostics
+            .iter()
+            .map(|diagnostic| format!("\n - {}", diagnostic))
+            .collect::<Vec<_>>()
+            .join("")
+    )]
+    Validation { diagnostics: Vec<Diagnostic> },
+
+    #[error("{message}")]
+    Compiler { message: String },
+}
+
+impl CompileError {
+    /// Diagnostics attached to this error, empty if the failure was not
+    /// caused by invalid documents or schema.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            CompileError::Validation { diagnostics } => diagnostics,
+            _ => &[],
+        }
+    }
+
+    pub(crate) fn from_config_error(error: Error) -> Self {
+        CompileError::Config {
+            message: error.to_string(),
+        }
+    }
+
+    pub(crate) fn from_compiler_error(error: &Error) -> Self {
+        match error {
+            Error::DiagnosticsError { errors } => CompileError::Validation {
+                diagnostics: errors
+                    .iter()
+                    .map(Diagnostic::from_compiler_diagnostic)
+                    .collect(),
+            },
+            Error::BuildProjectsErrors { errors }
+                if errors
+                    .iter()
+                    .all(|error| matches!(error, BuildProjectError::ValidationErrors { .. })) =>
+            {
+                CompileError::Validation {
+                    diagnostics: errors
+                        .iter()
+                        .flat_map(|error| match error {
+                            BuildProjectError::ValidationErrors { errors, .. } => errors,
+                            _ => unreachable!(),
+                        })
+                        .map(Diagnostic::from_compiler_diagnostic)
+                        .collect(),
+                }
+            }
+            error => CompileError::Compiler {
+                message: error.to_string(),
+            },
+        }
+    }
+}
@@ -0,0 +1,20 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use crate::diagnostic::Diagnostic;
+use crate::output::CompileError;
+
+/// Receives the progress of builds, e.g. to print it. The same diagnostics
+/// and errors are returned by `Compiler::compile`.
+pub trait StatusReporter: Send + Sync {
+    fn build_starts(&self) {}
+
+    /// `diagnostics` are the warnings and hints of a successful build.
+    fn build_completes(&self, _diagnostics: &[Diagnostic]) {}
+
+    fn build_errors(&self, _error: &CompileError) {}
+}
@@ -0,0 +1,160 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use relay_compiler_api::CompileError;
+use relay_compiler_api::CompilerBuilder;
+use relay_compiler_api::Severity;
+
+const SCHEMA: &str = r#"
+type Query {
+  me: User
+}
+
+type User {
+  id: ID!
+  name: String
+}
+"#;
+
+fn create_project(name: &str, source: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "relay-compiler-api-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("schema.graphql"), SCHEMA).unwrap();
+    fs::write(root.join("src/App.js"), source).unwrap();
+    fs::write(
+        root.join("relay.config.json"),
+        r#"{
+  "sources": { "src": "app" },
+  "projects": {
+    "app": { "schema": "schema.graphql", "language": "typescript" }
+  }
+}"#,
+    )
+    .unwrap();
+    root
+}
+
+#[tokio::test]
+async fn compiles_to_memory() {
+    let root = create_project("memory", "graphql`query AppQuery { me { name } }`;");
+    let compiler = CompilerBuilder::load(root.join("relay.config.json"))
+        .unwrap()
+        .write_artifacts(false)
+        .build()
+        .unwrap();
+    let output = compiler.compile().await.unwrap();
+
+    assert_eq!(
+        output
+            .artifacts
+            .iter()
+            .map(|artifact| artifact.path.as_path())
+            .collect::<Vec<_>>(),
+        vec![Path::new("src/__generated__/AppQuery.graphql.ts")]
+    );
+    assert!(
+        output.artifacts[0]
+            .content_str()
+            .unwrap()
+            .contains("\"name\": \"AppQuery\"")
+    );
+    assert!(output.diagnostics.is_empty());
+    assert!(!root.join("src/__generated__").exists());
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[tokio::test]
+async fn returns_validation_diagnostics() {
+    let root = create_project("invalid", "graphql`query AppQuery { me { unknown } }`;");
+    let compiler = CompilerBuilder::load(root.join("relay.config.json"))
+        .unwrap()
+        .write_artifacts(false)
+        .build()
+        .unwrap();
+    let error = compiler.compile().await.unwrap_err();
+
+    assert!(matches!(error, CompileError::Validation { .. }));
+    assert_eq!(error.diagnostics().len(), 1);
+    let diagnostic = &error.diagnostics()[0];
+    assert_eq!(diagnostic.severity, Severity::Error);
+    let location = diagnostic.location.as_ref().unwrap();
+    assert_eq!(location.path, Path::new("src/App.js"));
+    assert_eq!(location.embedded_index, Some(0));
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[tokio::test]
+async fn compiles_the_same_with_a_cached_schema() {
+    let root = create_project(
+        "schema-cache",
+        "graphql`query AppQuery { me { ...App_user } }`;\n\
+         graphql`fragment App_user on User { id name }`;",
+    );
+    fs::write(
+        root.join("relay.config.json"),
+        r#"{
+  "sources": { "src": "app" },
+  "schemaCacheDir": ".schema-cache",
+  "projects": {
+    "app": { "schema": "schema.graphql", "language": "typescript" }
+  }
+}"#,
+    )
+    .unwrap();
+    let compile = || async {
+        CompilerBuilder::load(root.join("relay.config.json"))
+            .unwrap()
+            .write_artifacts(false)
+            .build()
+            .unwrap()
+            .compile()
+            .await
+            .unwrap()
+    };
+
+    let built = compile().await;
+    assert!(root.join(".schema-cache/app.schema").exists());
+    let cached = compile().await;
+
+    let artifacts = |output: &relay_compiler_api::CompileOutput| {
+        output
+            .artifacts
+            .iter()
+            .map(|artifact| (artifact.path.clone(), artifact.content.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(artifacts(&built).len(), 2);
+    assert_eq!(artifacts(&built), artifacts(&cached));
+    assert_eq!(built.diagnostics, cached.diagnostics);
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn rejects_unknown_project() {
+    let root = create_project("unknown", "");
+    let error = CompilerBuilder::load(root.join("relay.config.json"))
+        .unwrap()
+        .project("web")
+        .build()
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error.to_string(),
+        "Project `web` not found, available projects: app."
+    );
+    fs::remove_dir_all(root).unwrap();
+}
@@ -124,6 +124,16 @@ describe('Ok', () => {
     );
     expect(actual.Ok).toMatchSnapshot();
   });
+
+  test('mock', () => {
+    const actual = JSON.parse(
+      playground.mock(SCHEMA, DOCUMENT, 'MyQuery', '{"seed": 1}'),
+    );
+    const data = JSON.parse(actual.Ok).data;
+    expect(data.me.name).toEqual('name 1');
+    expect(typeof data.me.age).toEqual('number');
+    expect(typeof data.me.best_friend.age).toEqual('number');
+  });
 });
 
 describe('Err', () => {
@@ -284,4 +294,13 @@ describe('Err', () => {
       ],
     });
   });
+
+  test('mock (unknown definition)', () => {
+    const actual = JSON.parse(
+      playground.mock(SCHEMA, DOCUMENT, 'NotMyQuery', '{}'),
+    );
+    expect(actual.Err).toEqual({
+      MockError: 'No operation or fragment named `NotMyQuery`.',
+    });
+  });
 });
@@ -19,6 +19,8 @@ use intern::string_key::Intern;
 use relay_codegen::print_fragment;
 use relay_codegen::print_operation;
 use relay_config::ProjectConfig;
+use relay_mocks::mock_definition;
+use relay_mocks::MockOptions;
 use relay_schema::build_schema_with_extensions;
 use relay_transforms::apply_transforms;
 use relay_transforms::Programs;
@@ -65,6 +67,7 @@ pub enum PlaygroundError {
     SchemaDiagnostics(Vec<WasmDiagnostic>),
     ConfigError(String),
     TypegenConfigError(String),
+    MockError(String),
 }
 
 pub type PlaygroundResult = Result<String, PlaygroundError>;
@@ -308,6 +311,51 @@ fn transform_impl(
     Ok(output)
 }
 
+#[wasm_bindgen]
+pub fn mock(
+    schema_text: &str,
+    document_text: &str,
+    definition_name: &str,
+    options_json: &str,
+) -> String {
+    serialize_result(mock_impl(
+        schema_text,
+        document_text,
+        definition_name,
+        options_json,
+    ))
+}
+
+/// Mock response of the operation, or mock data of the fragment, as JSON.
+/// The options are the JSON options of `relay_mocks::MockOptions`.
+pub fn mock_impl(
+    schema_text: &str,
+    document_text: &str,
+    definition_name: &str,
+    options_json: &str,
+) -> PlaygroundResult {
+    let options = MockOptions::from_json(options_json)
+        .map_err(|err| PlaygroundError::MockError(format!("{}", err)))?;
+    let document = graphql_syntax::parse_executable(document_text, Generated)
+        .map_err(|diagnostics| map_diagnostics(diagnostics, &InputType::Document(document_text)))?;
+
+    let schema = Arc::new(
+        build_schema_with_extensions(
+            &[(schema_text, SourceLocationKey::generated())],
+            &Vec::<(&str, SourceLocationKey)>::new(),
+        )
+        .map_err(|diagnostics| map_diagnostics(diagnostics, &InputType::Schema(schema_text)))?,
+    );
+
+    let ir = graphql_ir::build(&schema, &document.definitions)
+        .map_err(|diagnostics| map_diagnostics(diagnostics, &InputType::Document(document_text)))?;
+    let program = Program::from_definitions(schema, ir);
+
+    let data = mock_definition(&program, definition_name.intern(), &options)
+        .map_err(|err| PlaygroundError::MockError(format!("{}", err)))?;
+    Ok(serde_json::to_string_pretty(&data).unwrap())
+}
+
 fn get_project_config(
     feature_flags_json: &str,
     typegen_config_json: Option<&str>,
@@ -351,6 +399,7 @@ fn get_programs(
         Arc::new(NoopPerfLogger),
         None,
         None,
+        None,
     )
     .map_err(|diagnostics: Vec<Diagnostic>| {
         map_diagnostics(diagnostics, &InputType::Document(document_text))
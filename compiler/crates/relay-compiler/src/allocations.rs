@@ -0,0 +1,174 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Counts of the allocations of the compiler, for `relay bench` and the
+//! memory report of a build. They are only counted if `CountingAllocator`
+//! is the global allocator of the binary:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//! ```
+//!
+//! With the `memory_profile` feature, the allocator also tracks the bytes
+//! on the heap and their peak, from the start of the process.
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "memory_profile")]
+static HEAP_BYTES: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "memory_profile")]
+static PEAK_HEAP_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting the allocations while counting is on.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_allocation(size: usize) {
+        if COUNTING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        }
+    }
+
+    #[cfg(feature = "memory_profile")]
+    fn grow_heap(size: usize) {
+        let heap_bytes = HEAP_BYTES.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+        PEAK_HEAP_BYTES.fetch_max(heap_bytes, Ordering::Relaxed);
+    }
+
+    #[cfg(not(feature = "memory_profile"))]
+    fn grow_heap(_size: usize) {}
+
+    #[cfg(feature = "memory_profile")]
+    fn shrink_heap(size: usize) {
+        HEAP_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+
+    #[cfg(not(feature = "memory_profile"))]
+    fn shrink_heap(_size: usize) {}
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::record_allocation(layout.size());
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow_heap(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::record_allocation(layout.size());
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::grow_heap(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::record_allocation(new_size);
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::shrink_heap(layout.size());
+            Self::grow_heap(new_size);
+        }
+        new_ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrink_heap(layout.size());
+    }
+}
+
+/// Starts or stops counting the allocations.
+pub fn set_counting(counting: bool) {
+    COUNTING.store(counting, Ordering::Relaxed);
+}
+
+pub fn is_counting() -> bool {
+    COUNTING.load(Ordering::Relaxed)
+}
+
+/// The allocations counted so far, in all threads.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocationCounts {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    /// The bytes on the heap, with the `memory_profile` feature.
+    pub heap_bytes: Option<u64>,
+}
+
+impl AllocationCounts {
+    pub fn now() -> Self {
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+            heap_bytes: heap_bytes(),
+        }
+    }
+}
+
+#[cfg(feature = "memory_profile")]
+fn heap_bytes() -> Option<u64> {
+    Some(HEAP_BYTES.load(Ordering::Relaxed))
+}
+
+#[cfg(not(feature = "memory_profile"))]
+fn heap_bytes() -> Option<u64> {
+    None
+}
+
+/// The most bytes on the heap at the same time since the process started,
+/// with the `memory_profile` feature.
+#[cfg(feature = "memory_profile")]
+pub fn peak_heap_bytes() -> Option<u64> {
+    Some(PEAK_HEAP_BYTES.load(Ordering::Relaxed))
+}
+
+#[cfg(not(feature = "memory_profile"))]
+pub fn peak_heap_bytes() -> Option<u64> {
+    None
+}
+
+/// The peak resident set size of the process, as reported by Linux.
+pub fn peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes: u64 = line
+        .trim_start_matches("VmHWM:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Formats bytes as KiB, MiB or GiB.
+pub fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= KIB * KIB * KIB {
+        format!("{:.2}GiB", bytes / (KIB * KIB * KIB))
+    } else if bytes >= KIB * KIB {
+        format!("{:.1}MiB", bytes / (KIB * KIB))
+    } else {
+        format!("{:.1}KiB", bytes / KIB)
+    }
+}
@@ -0,0 +1,455 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Repeated runs of the phases of the compiler over the sources of each
+//! project, for `relay bench`. The sources are loaded once, from the files
+//! of the projects or from a corpus saved with
+//! `CompilerState::serialize_to_file`, and no artifact is written.
+
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Instant;
+
+use common::NoopPerfLogger;
+use common::PerfLogger;
+use fnv::FnvHashMap;
+use intern::Lookup;
+use relay_codegen::Printer;
+use relay_typegen::FragmentLocations;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::allocations::format_bytes;
+use crate::allocations::set_counting;
+use crate::allocations::AllocationCounts;
+use crate::build_project::build_raw_program;
+use crate::build_project::build_schema;
+use crate::build_project::generate_artifacts;
+use crate::build_project::get_project_asts;
+use crate::build_project::transform_program;
+use crate::build_project::validate_program;
+use crate::build_project::BuildProjectFailure;
+use crate::build_project::ProjectAstData;
+use crate::compiler_state::CompilerState;
+use crate::compiler_state::ProjectName;
+use crate::config::Config;
+use crate::config::ProjectConfig;
+use crate::errors::BuildProjectError;
+use crate::errors::Error;
+use crate::errors::Result;
+use crate::graphql_asts::parser_features;
+use crate::GraphQLAsts;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BenchPhase {
+    /// Parsing the GraphQL of the source files of the project.
+    Parse,
+    /// Building the schema with its extensions and Relay Resolvers.
+    Schema,
+    /// Building the IR of the documents against the schema.
+    Ir,
+    Validate,
+    /// The transforms of the reader, normalization, operation text and
+    /// typegen programs.
+    Transforms,
+    /// Generating and printing the artifacts, with their types.
+    Codegen,
+}
+
+impl BenchPhase {
+    pub const ALL: [BenchPhase; 6] = [
+        BenchPhase::Parse,
+        BenchPhase::Schema,
+        BenchPhase::Ir,
+        BenchPhase::Validate,
+        BenchPhase::Transforms,
+        BenchPhase::Codegen,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BenchPhase::Parse => "parse",
+            BenchPhase::Schema => "schema",
+            BenchPhase::Ir => "ir",
+            BenchPhase::Validate => "validate",
+            BenchPhase::Transforms => "transforms",
+            BenchPhase::Codegen => "codegen",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BenchOptions {
+    /// Measured runs of each project.
+    pub iterations: usize,
+    /// Runs of each project before the measured runs, which are not
+    /// reported.
+    pub warmup_iterations: usize,
+    /// Whether to report the allocations of each phase. This requires
+    /// `allocations::CountingAllocator` to be the global allocator.
+    pub count_allocations: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 5,
+            warmup_iterations: 1,
+            count_allocations: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseBench {
+    pub phase: BenchPhase,
+    /// The duration of each measured run, in microseconds.
+    pub durations_us: Vec<u64>,
+    /// The mean allocations of a run, if counted.
+    pub allocations: Option<u64>,
+    /// The mean bytes allocated by a run, if counted.
+    pub allocated_bytes: Option<u64>,
+}
+
+impl PhaseBench {
+    pub fn median_us(&self) -> u64 {
+        let mut durations = self.durations_us.clone();
+        durations.sort_unstable();
+        durations
+            .get(durations.len() / 2)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn min_us(&self) -> u64 {
+        self.durations_us.iter().copied().min().unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectBench {
+    pub name: ProjectName,
+    /// The operations and fragments of the project, without those of the
+    /// base project.
+    pub documents: usize,
+    pub artifacts: usize,
+    pub phases: Vec<PhaseBench>,
+}
+
+/// Runs the phases of each enabled project, sorted by name, the number of
+/// times of the options. The projects are run one after the other, so the
+/// phases can use all threads.
+pub fn bench_projects(
+    config: &Config,
+    compiler_state: &CompilerState,
+    options: &BenchOptions,
+) -> Result<Vec<ProjectBench>> {
+    let mut project_configs: Vec<_> = config.enabled_projects().collect();
+    project_configs.sort_by_key(|project_config| project_config.name.lookup());
+
+    set_counting(options.count_allocations);
+    let result = project_configs
+        .into_iter()
+        .map(|project_config| bench_project(config, project_config, compiler_state, options))
+        .collect();
+    set_counting(false);
+    result
+}
+
+/// Times the phases of one run, and counts their allocations.
+struct PhaseRecorder {
+    phases: Vec<PhaseBench>,
+    allocations: Vec<(u64, u64)>,
+    measured: bool,
+}
+
+impl PhaseRecorder {
+    fn new() -> Self {
+        Self {
+            phases: BenchPhase::ALL
+                .iter()
+                .map(|phase| PhaseBench {
+                    phase: *phase,
+                    durations_us: Vec::new(),
+                    allocations: None,
+                    allocated_bytes: None,
+                })
+                .collect(),
+            allocations: vec![(0, 0); BenchPhase::ALL.len()],
+            measured: false,
+        }
+    }
+
+    fn time<T>(&mut self, phase: BenchPhase, f: impl FnOnce() -> T) -> T {
+        let start_counts = AllocationCounts::now();
+        let start = Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+        let end_counts = AllocationCounts::now();
+        if self.measured {
+            let index = BenchPhase::ALL.iter().position(|p| *p == phase).unwrap();
+            self.phases[index]
+                .durations_us
+                .push(duration.as_micros() as u64);
+            let (allocations, bytes) = &mut self.allocations[index];
+            *allocations += end_counts.allocations - start_counts.allocations;
+            *bytes += end_counts.allocated_bytes - start_counts.allocated_bytes;
+        }
+        result
+    }
+
+    fn finish(self, options: &BenchOptions) -> Vec<PhaseBench> {
+        let iterations = options.iterations.max(1) as u64;
+        self.phases
+            .into_iter()
+            .zip(self.allocations)
+            .map(|(mut phase, (allocations, bytes))| {
+                if options.count_allocations {
+                    phase.allocations = Some(allocations / iterations);
+                    phase.allocated_bytes = Some(bytes / iterations);
+                }
+                phase
+            })
+            .collect()
+    }
+}
+
+fn bench_project(
+    config: &Config,
+    project_config: &ProjectConfig,
+    compiler_state: &CompilerState,
+    options: &BenchOptions,
+) -> Result<ProjectBench> {
+    let project_name = project_config.name;
+    if !compiler_state.schemas.contains_key(&project_name) {
+        return Err(Error::MissingProjectSources { project_name });
+    }
+    let validation_error = |errors| Error::BuildProjectsErrors {
+        errors: vec![BuildProjectError::ValidationErrors {
+            errors,
+            project_name,
+        }],
+    };
+    let project_error = |error| Error::BuildProjectsErrors {
+        errors: vec![error],
+    };
+
+    // The documents of the base project are parsed once, and not measured.
+    let mut graphql_asts_map = FnvHashMap::default();
+    if let Some(base_project_name) = project_config.base {
+        if let Some(sources) = compiler_state.graphql_sources.get(&base_project_name) {
+            let features = config
+                .projects
+                .get(&base_project_name)
+                .map_or_else(Default::default, |base_config| {
+                    parser_features(&base_config.feature_flags)
+                });
+            let asts = GraphQLAsts::from_graphql_sources(sources, None, features)?;
+            graphql_asts_map.insert(base_project_name, asts);
+        }
+    }
+
+    let log_event = NoopPerfLogger.create_event("bench_project");
+    let mut recorder = PhaseRecorder::new();
+    let mut documents = 0;
+    let mut artifacts = 0;
+    for iteration in 0..options.warmup_iterations + options.iterations {
+        recorder.measured = iteration >= options.warmup_iterations;
+
+        if let Some(sources) = compiler_state.graphql_sources.get(&project_name) {
+            let features = parser_features(&project_config.feature_flags);
+            let asts = recorder.time(BenchPhase::Parse, || {
+                GraphQLAsts::from_graphql_sources(sources, None, features)
+            })?;
+            graphql_asts_map.insert(project_name, asts);
+        }
+
+        let schema = recorder
+            .time(BenchPhase::Schema, || {
+                build_schema(config, compiler_state, project_config, &graphql_asts_map)
+            })
+            .map_err(validation_error)?;
+
+        let (program, source_hashes, base_fragment_names) = recorder
+            .time(BenchPhase::Ir, || {
+                let ProjectAstData {
+                    project_asts,
+                    base_fragment_names,
+                } = get_project_asts(&schema, &graphql_asts_map, project_config)?;
+                let (program, source_hashes) = build_raw_program(
+                    project_config,
+                    project_asts,
+                    Arc::clone(&schema),
+                    &log_event,
+                    None,
+                )?;
+                Ok::<_, BuildProjectError>((program, source_hashes, base_fragment_names))
+            })
+            .map_err(project_error)?;
+        documents = program
+            .document_count()
+            .saturating_sub(base_fragment_names.len());
+
+        recorder
+            .time(BenchPhase::Validate, || {
+                validate_program(config, project_config, &program, &log_event)
+            })
+            .map_err(project_error)?;
+
+        let programs = recorder
+            .time(BenchPhase::Transforms, || {
+                transform_program(
+                    project_config,
+                    Arc::new(program),
+                    Arc::new(base_fragment_names),
+                    Arc::new(NoopPerfLogger),
+                    &log_event,
+                    config.custom_transforms.as_ref(),
+                    None,
+                )
+            })
+            .map_err(|failure| match failure {
+                BuildProjectFailure::Error(error) => project_error(error),
+                BuildProjectFailure::Cancelled => Error::Cancelled,
+            })?;
+
+        artifacts = recorder.time(BenchPhase::Codegen, || {
+            let artifacts =
+                generate_artifacts(config, project_config, &programs, Arc::new(source_hashes));
+            let fragment_locations = FragmentLocations::new(programs.typegen.fragments());
+            let mut printer = Printer::with_dedupe(project_config);
+            for artifact in &artifacts {
+                artifact.content.as_bytes(
+                    config,
+                    project_config,
+                    &mut printer,
+                    &schema,
+                    artifact.source_file,
+                    &fragment_locations,
+                );
+            }
+            artifacts.len()
+        });
+    }
+
+    Ok(ProjectBench {
+        name: project_name,
+        documents,
+        artifacts,
+        phases: recorder.finish(options),
+    })
+}
+
+/// Prints a line per phase of each project, with the change of the median
+/// duration since the same phase of the `baseline` runs, if any.
+pub fn bench_to_text(projects: &[ProjectBench], baseline: Option<&[ProjectBench]>) -> String {
+    let mut output = String::new();
+    for project in projects {
+        writeln!(
+            output,
+            "[{}] {} documents, {} artifacts",
+            project.name, project.documents, project.artifacts
+        )
+        .unwrap();
+        let baseline_project =
+            baseline.and_then(|baseline| baseline.iter().find(|other| other.name == project.name));
+        for phase in &project.phases {
+            let median_us = phase.median_us();
+            write!(
+                output,
+                "  {:<10} {:>10} median {:>10} min",
+                phase.phase.as_str(),
+                format_duration(median_us),
+                format_duration(phase.min_us()),
+            )
+            .unwrap();
+            if median_us > 0 {
+                write!(
+                    output,
+                    " {:>10.0} documents/s",
+                    project.documents as f64 * 1_000_000.0 / median_us as f64
+                )
+                .unwrap();
+            }
+            if let (Some(allocations), Some(bytes)) = (phase.allocations, phase.allocated_bytes) {
+                write!(
+                    output,
+                    " {:>10} allocations {:>10}",
+                    allocations,
+                    format_bytes(bytes)
+                )
+                .unwrap();
+            }
+            let baseline_median_us = baseline_project
+                .and_then(|baseline| {
+                    baseline
+                        .phases
+                        .iter()
+                        .find(|other| other.phase == phase.phase)
+                })
+                .map(PhaseBench::median_us);
+            if let Some(baseline_median_us) = baseline_median_us.filter(|median| *median > 0) {
+                let change = (median_us as f64 - baseline_median_us as f64) * 100.0
+                    / baseline_median_us as f64;
+                write!(output, " ({:+.1}%)", change).unwrap();
+            }
+            writeln!(output).unwrap();
+        }
+    }
+    output
+}
+
+pub fn bench_to_json(projects: &[ProjectBench]) -> String {
+    serde_json::to_string_pretty(projects).unwrap()
+}
+
+fn format_duration(us: u64) -> String {
+    if us >= 1_000_000 {
+        format!("{:.2}s", us as f64 / 1_000_000.0)
+    } else {
+        format!("{:.1}ms", us as f64 / 1_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    fn phase(phase: BenchPhase, durations_us: Vec<u64>) -> PhaseBench {
+        PhaseBench {
+            phase,
+            durations_us,
+            allocations: None,
+            allocated_bytes: None,
+        }
+    }
+
+    #[test]
+    fn compares_medians_with_baseline() {
+        let project = |durations_us| ProjectBench {
+            name: "web".intern(),
+            documents: 500,
+            artifacts: 600,
+            phases: vec![phase(BenchPhase::Parse, durations_us)],
+        };
+        let baseline = vec![project(vec![2000, 1000, 9000])];
+        let current = vec![project(vec![2500, 2400, 9000])];
+
+        assert_eq!(current[0].phases[0].median_us(), 2500);
+        assert_eq!(current[0].phases[0].min_us(), 2400);
+        let text = bench_to_text(&current, Some(&baseline));
+        assert!(text.starts_with("[web] 500 documents, 600 artifacts\n"));
+        assert!(text.contains("2.5ms median"));
+        assert!(text.contains("200000 documents/s"));
+        assert!(text.trim_end().ends_with("(+25.0%)"));
+    }
+}
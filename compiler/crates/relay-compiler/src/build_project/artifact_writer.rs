@@ -10,11 +10,14 @@ use std::fs::create_dir_all;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
 use std::sync::Mutex;
 
 use common::sync::Ordering::Acquire;
+use dashmap::DashMap;
 use dashmap::DashSet;
 use log::info;
 use serde::Serialize;
@@ -112,6 +115,86 @@ impl ArtifactWriter for ArtifactFileWriter {
     }
 }
 
+/// Writes the artifacts to the same relative paths under `output_root`
+/// instead of `root_dir`, e.g. to the output tree of a build system.
+pub struct ArtifactOutputRootWriter {
+    root_dir: PathBuf,
+    output_root: PathBuf,
+    writer: ArtifactFileWriter,
+}
+
+impl ArtifactOutputRootWriter {
+    pub fn new(root_dir: PathBuf, output_root: PathBuf) -> Self {
+        Self {
+            writer: ArtifactFileWriter::new(None, output_root.clone()),
+            root_dir,
+            output_root,
+        }
+    }
+
+    fn output_path(&self, path: &Path) -> PathBuf {
+        match path.strip_prefix(&self.root_dir) {
+            Ok(relative_path) => self.output_root.join(relative_path),
+            Err(_) => path.to_path_buf(),
+        }
+    }
+}
+
+impl ArtifactWriter for ArtifactOutputRootWriter {
+    fn should_write(&self, path: &PathBuf, content: &[u8]) -> Result<bool, BuildProjectError> {
+        self.writer.should_write(&self.output_path(path), content)
+    }
+
+    fn write(&self, path: PathBuf, content: Vec<u8>) -> BuildProjectResult {
+        self.writer.write(self.output_path(&path), content)
+    }
+
+    fn remove(&self, path: PathBuf) -> BuildProjectResult {
+        self.writer.remove(self.output_path(&path))
+    }
+
+    fn finalize(&self) -> crate::errors::Result<()> {
+        self.writer.finalize()
+    }
+}
+
+/// Keeps the artifacts in memory instead of writing them, by absolute path.
+/// The artifacts are shared with the `files()` of the writer, so that they
+/// can be read after the writer is moved into the config.
+#[derive(Default)]
+pub struct ArtifactMemoryWriter {
+    files: Arc<DashMap<PathBuf, Vec<u8>>>,
+}
+
+impl ArtifactMemoryWriter {
+    pub fn files(&self) -> Arc<DashMap<PathBuf, Vec<u8>>> {
+        Arc::clone(&self.files)
+    }
+}
+
+impl ArtifactWriter for ArtifactMemoryWriter {
+    fn should_write(&self, path: &PathBuf, content: &[u8]) -> Result<bool, BuildProjectError> {
+        Ok(!matches!(
+            self.files.get(path),
+            Some(existing_content) if existing_content.as_slice() == content
+        ))
+    }
+
+    fn write(&self, path: PathBuf, content: Vec<u8>) -> BuildProjectResult {
+        self.files.insert(path, content);
+        Ok(())
+    }
+
+    fn remove(&self, path: PathBuf) -> BuildProjectResult {
+        self.files.remove(&path);
+        Ok(())
+    }
+
+    fn finalize(&self) -> crate::errors::Result<()> {
+        Ok(())
+    }
+}
+
 #[derive(Serialize)]
 struct CodegenRecords {
     pub removed: Vec<ArtifactDeletionRecord>,
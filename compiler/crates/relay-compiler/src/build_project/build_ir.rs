@@ -11,12 +11,17 @@ use fnv::FnvHashMap;
 use graphql_ir::ExecutableDefinitionName;
 use graphql_ir::FragmentDefinitionName;
 use graphql_ir::OperationDefinitionName;
+use graphql_ir::Program;
 use graphql_syntax::ExecutableDefinition;
 use graphql_text_printer::print_executable_definition_ast;
+use graphql_text_printer::print_fragment;
+use graphql_text_printer::print_operation;
 use md5::Digest;
 use md5::Md5;
 use schema::SDLSchema;
+use schema_diff::definitions::AffectedSchemaMembers;
 
+use super::type_usage_index::TypeUsageIndex;
 use super::ProjectAsts;
 use crate::config::ProjectConfig;
 
@@ -46,22 +51,56 @@ impl SourceHashes {
     pub fn get(&self, k: &ExecutableDefinitionName) -> Option<&String> {
         self.0.get(k)
     }
+
+    /// Adds the hashes of the definitions of the program that we don't have the
+    /// source of, e.g. fragments that were taken from another program. These
+    /// are hashes of the printed IR, so they differ from the source hashes.
+    pub(crate) fn add_missing_definitions(&mut self, program: &Program) {
+        for operation in program.operations() {
+            self.0.entry(operation.name.item.into()).or_insert_with(|| {
+                md5(&print_operation(
+                    &program.schema,
+                    operation,
+                    Default::default(),
+                ))
+            });
+        }
+        for fragment in program.fragments() {
+            self.0.entry(fragment.name.item.into()).or_insert_with(|| {
+                md5(&print_fragment(
+                    &program.schema,
+                    fragment,
+                    Default::default(),
+                ))
+            });
+        }
+    }
 }
 
+/// Builds the IR of all definitions. For incremental builds, i.e. when the
+/// schema members affected by the pending schema changes are known, only the
+/// IR of the changed definitions, of the definitions that use the affected
+/// schema members, and of their dependencies is returned.
 pub fn build_ir(
     _project_config: &ProjectConfig,
     project_asts: ProjectAsts,
     schema: &SDLSchema,
-    is_incremental_build: bool,
+    affected_schema_members: Option<&AffectedSchemaMembers>,
 ) -> Result<BuildIRResult, Vec<Diagnostic>> {
     let asts = project_asts.definitions;
     let source_hashes = SourceHashes::from_definitions(&asts);
     let ir = graphql_ir::build_ir_in_relay_mode(schema, &asts)?;
-    if is_incremental_build {
+    if let Some(affected_schema_members) = affected_schema_members {
+        let mut changed_names = project_asts.changed_names;
+        if !affected_schema_members.is_empty() {
+            changed_names.extend(
+                TypeUsageIndex::new(schema, &ir).find_affected_definitions(affected_schema_members),
+            );
+        }
         let affected_ir = get_reachable_ir(
             ir,
             project_asts.base_definition_names,
-            project_asts.changed_names,
+            changed_names,
             schema,
         );
         Ok(BuildIRResult {
@@ -5,19 +5,32 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::path::Path;
 use std::sync::Arc;
 
 use common::DiagnosticsResult;
+use common::SourceLocationKey;
 use fnv::FnvHashMap;
+use log::debug;
+use log::warn;
+use relay_config::SchemaConfig;
 use schema::SDLSchema;
+use schema::validate_semantic_non_null;
 
 use super::build_resolvers_schema::extend_schema_with_resolvers;
+use crate::build_report::SchemaSource;
 use crate::compiler_state::CompilerState;
 use crate::compiler_state::ProjectName;
+use crate::config::Config;
 use crate::config::ProjectConfig;
+use crate::schema_cache::load_cached_schema;
+use crate::schema_cache::schema_cache_path;
+use crate::schema_cache::store_cached_schema;
+use crate::schema_cache::SchemaCacheKey;
 use crate::GraphQLAsts;
 
 pub fn build_schema(
+    config: &Config,
     compiler_state: &CompilerState,
     project_config: &ProjectConfig,
     graphql_asts_map: &FnvHashMap<ProjectName, GraphQLAsts>,
@@ -25,6 +38,7 @@ pub fn build_schema(
     let schema = compiler_state.schema_cache.get(&project_config.name);
     match schema {
         Some(schema) if !compiler_state.project_has_pending_schema_changes(project_config.name) => {
+            record_schema_source(config, project_config, SchemaSource::Memory);
             Ok(schema.clone())
         }
         _ => {
@@ -46,8 +60,24 @@ pub fn build_schema(
                     .into_iter()
                     .map(|(schema, location_key)| (schema.as_str(), location_key)),
             );
-            let mut schema =
-                relay_schema::build_schema_with_extensions(&schema_sources, &extensions)?;
+            let mut schema = match &config.schema_cache_dir {
+                Some(schema_cache_dir) => build_cached_schema(
+                    config,
+                    &schema_cache_path(schema_cache_dir, project_config.name),
+                    project_config,
+                    &schema_sources,
+                    &extensions,
+                )?,
+                _ => {
+                    let schema = build_schema_from_sources(
+                        &project_config.schema_config,
+                        &schema_sources,
+                        &extensions,
+                    )?;
+                    record_schema_source(config, project_config, SchemaSource::Built);
+                    schema
+                }
+            };
 
             if project_config.feature_flags.enable_relay_resolver_transform {
                 extend_schema_with_resolvers(
@@ -56,9 +86,69 @@ pub fn build_schema(
                     project_config,
                     graphql_asts_map,
                 )?;
+                schema.shrink_to_fit();
             }
 
             Ok(Arc::new(schema))
         }
     }
 }
+
+/// Loads the schema from the cache file if it was cached from the same
+/// sources, or builds and caches it otherwise. Failing to write the cache
+/// doesn't fail the build.
+fn build_cached_schema<T: AsRef<str>, U: AsRef<str>>(
+    config: &Config,
+    cache_path: &Path,
+    project_config: &ProjectConfig,
+    schema_sources: &[(T, SourceLocationKey)],
+    extensions: &[(U, SourceLocationKey)],
+) -> DiagnosticsResult<SDLSchema> {
+    let key = SchemaCacheKey::new(
+        &config.saved_state_version,
+        &project_config.schema_config,
+        schema_sources,
+        extensions,
+    );
+    if let Some(schema) = load_cached_schema(cache_path, key) {
+        debug!("[{}] loaded schema from cache", project_config.name);
+        record_schema_source(config, project_config, SchemaSource::DiskCache);
+        return Ok(schema);
+    }
+    let schema =
+        build_schema_from_sources(&project_config.schema_config, schema_sources, extensions)?;
+    record_schema_source(config, project_config, SchemaSource::Built);
+    if let Err(err) = store_cached_schema(cache_path, key, &schema) {
+        warn!(
+            "[{}] failed to cache the schema in {:?}: {}",
+            project_config.name, cache_path, err
+        );
+    }
+    Ok(schema)
+}
+
+fn record_schema_source(config: &Config, project_config: &ProjectConfig, source: SchemaSource) {
+    if let Some(build_report) = &config.build_report {
+        build_report.record_schema(project_config.name, source);
+    }
+}
+
+/// Builds the schema from the project's schema files, which are composed as
+/// Apollo Federation subgraphs if `schemaConfig.federation` is set. The
+/// `@semanticNonNull` directives are validated if the schema declares
+/// semantic nullability support.
+pub(crate) fn build_schema_from_sources<T: AsRef<str>, U: AsRef<str>>(
+    schema_config: &SchemaConfig,
+    schema_sources: &[(T, SourceLocationKey)],
+    extensions: &[(U, SourceLocationKey)],
+) -> DiagnosticsResult<SDLSchema> {
+    let schema = if schema_config.federation {
+        relay_schema::build_schema_from_subgraphs_with_extensions(schema_sources, extensions)
+    } else {
+        relay_schema::build_schema_with_extensions(schema_sources, extensions)
+    }?;
+    if schema_config.semantic_non_null_support {
+        validate_semantic_non_null(&schema)?;
+    }
+    Ok(schema)
+}
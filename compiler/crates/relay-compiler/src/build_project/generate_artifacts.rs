@@ -46,14 +46,10 @@ pub fn generate_artifacts(
     programs: &Programs,
     source_hashes: Arc<SourceHashes>,
 ) -> Vec<Artifact> {
-    let printer_options = PrinterOptions {
-        compact: project_config
-            .feature_flags
-            .compact_query_text
-            .is_fully_enabled(),
-        ..Default::default()
-    };
-    let mut operation_printer = OperationPrinter::new(&programs.operation_text, printer_options);
+    let mut operation_printer = OperationPrinter::new(
+        &programs.operation_text,
+        operation_text_printer_options(project_config),
+    );
     return group_operations(programs).into_values().map(|operations| {
             if let Some(normalization) = operations.normalization {
                 // We have a normalization AST... so we'll move forward with that
@@ -174,6 +170,23 @@ pub fn generate_artifacts(
         .collect();
 }
 
+/// The options the operation text of the artifacts of a project is printed
+/// with.
+pub fn operation_text_printer_options(project_config: &ProjectConfig) -> PrinterOptions {
+    let operation_text_config = &project_config.operation_text_config;
+    PrinterOptions {
+        compact: operation_text_config.compact
+            || project_config
+                .feature_flags
+                .compact_query_text
+                .is_fully_enabled(),
+        sort_keys: operation_text_config.sort_arguments,
+        sort_directives: operation_text_config.sort_directives,
+        sort_variable_definitions: operation_text_config.sort_variable_definitions,
+        ..Default::default()
+    }
+}
+
 fn generate_normalization_artifact(
     operation_printer: &mut OperationPrinter<'_>,
     source_definition_name: ExecutableDefinitionName,
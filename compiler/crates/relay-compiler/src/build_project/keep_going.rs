@@ -0,0 +1,125 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::Diagnostic;
+use common::DiagnosticSeverity;
+use dependency_analyzer::get_definition_references;
+use dependency_analyzer::ExecutableDefinitionNameSet;
+use graphql_ir::ExecutableDefinitionName;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::OperationDefinitionName;
+use graphql_syntax::ExecutableDefinition;
+
+/// Removes the definitions that contain one of the errors, and the
+/// definitions that spread them, directly or through other fragments, so
+/// that `Config::keep_going` can build the others. Returns the names of the
+/// removed definitions, or `None` without removing any if one of the errors
+/// isn't in a named definition, e.g. an error of the schema.
+pub fn remove_broken_definitions(
+    definitions: &mut Vec<ExecutableDefinition>,
+    errors: &[Diagnostic],
+) -> Option<ExecutableDefinitionNameSet> {
+    let mut broken_names = ExecutableDefinitionNameSet::default();
+    for error in errors
+        .iter()
+        .filter(|error| error.severity() == DiagnosticSeverity::ERROR)
+    {
+        let location = error.location();
+        let definition = definitions.iter().find(|definition| {
+            definition.location().source_location() == location.source_location()
+                && definition.location().contains(location.span())
+        })?;
+        broken_names.insert(definition_name(definition)?);
+    }
+    if broken_names.is_empty() {
+        return None;
+    }
+
+    let references = get_definition_references(definitions.iter());
+    loop {
+        let dependents = references
+            .iter()
+            .filter(|(name, spreads)| {
+                !broken_names.contains(*name)
+                    && spreads.iter().any(|spread| broken_names.contains(spread))
+            })
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+        if dependents.is_empty() {
+            break;
+        }
+        broken_names.extend(dependents);
+    }
+
+    definitions.retain(|definition| {
+        definition_name(definition).map_or(true, |name| !broken_names.contains(&name))
+    });
+    Some(broken_names)
+}
+
+fn definition_name(definition: &ExecutableDefinition) -> Option<ExecutableDefinitionName> {
+    let name = definition.name()?;
+    Some(match definition {
+        ExecutableDefinition::Operation(_) => OperationDefinitionName(name).into(),
+        ExecutableDefinition::Fragment(_) => FragmentDefinitionName(name).into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Location;
+    use common::SourceLocationKey;
+    use common::Span;
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    const SOURCE: &str = r#"
+        fragment Broken on User { nam }
+        fragment UsesBroken on User { ...Broken }
+        query UsesUsesBroken { me { ...UsesBroken } }
+        query Healthy { me { name } }
+    "#;
+
+    fn names(definitions: &[ExecutableDefinition]) -> Vec<String> {
+        definitions
+            .iter()
+            .map(|definition| definition.name().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn removes_broken_definitions_and_their_dependents() {
+        let source_location = SourceLocationKey::standalone("test.graphql");
+        let mut definitions = graphql_syntax::parse_executable(SOURCE, source_location)
+            .unwrap()
+            .definitions;
+        let start = SOURCE.find("nam").unwrap() as u32;
+        let errors = vec![Diagnostic::error(
+            "Unknown field",
+            Location::new(source_location, Span::new(start, start + 3)),
+        )];
+
+        let removed = remove_broken_definitions(&mut definitions, &errors).unwrap();
+
+        assert_eq!(names(&definitions), vec!["Healthy"]);
+        assert_eq!(removed.len(), 3);
+        assert!(removed.contains(&FragmentDefinitionName("Broken".intern()).into()));
+    }
+
+    #[test]
+    fn keeps_all_definitions_if_an_error_is_outside_of_them() {
+        let source_location = SourceLocationKey::standalone("test.graphql");
+        let mut definitions = graphql_syntax::parse_executable(SOURCE, source_location)
+            .unwrap()
+            .definitions;
+        let errors = vec![Diagnostic::error("Schema error", Location::generated())];
+
+        assert_eq!(remove_broken_definitions(&mut definitions, &errors), None);
+        assert_eq!(definitions.len(), 4);
+    }
+}
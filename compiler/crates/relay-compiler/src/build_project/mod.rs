@@ -14,15 +14,19 @@ mod build_ir;
 mod build_resolvers_schema;
 pub mod build_schema;
 mod generate_artifacts;
+mod keep_going;
 pub mod generate_extra_artifacts;
 mod log_program_stats;
 mod persist_operations;
 mod project_asts;
 mod source_control;
+mod type_usage_index;
 mod validate;
+mod write_artifacts;
 
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::thread;
 
 pub use artifact_generated_types::ArtifactGeneratedTypes;
 use build_ir::BuildIRResult;
@@ -35,29 +39,39 @@ use common::PerfLogger;
 use common::WithDiagnostics;
 use dashmap::mapref::entry::Entry;
 use dashmap::DashSet;
+use errors::try2;
 use fnv::FnvBuildHasher;
 use fnv::FnvHashMap;
 use fnv::FnvHashSet;
 pub use generate_artifacts::generate_artifacts;
+pub use generate_artifacts::operation_text_printer_options;
 pub use generate_artifacts::Artifact;
 pub use generate_artifacts::ArtifactContent;
 use graphql_ir::ExecutableDefinitionName;
 use graphql_ir::FragmentDefinitionNameSet;
 use graphql_ir::Program;
+use graphql_ir_validations::SelectionConflictCache;
+use keep_going::remove_broken_definitions;
 use log::debug;
 use log::info;
 use log::warn;
 use rayon::iter::IntoParallelRefIterator;
-use rayon::slice::ParallelSlice;
-use relay_codegen::Printer;
 use relay_transforms::apply_transforms;
 use relay_transforms::CustomTransformsConfig;
 use relay_transforms::Programs;
 use relay_typegen::FragmentLocations;
 use schema::SDLSchema;
+use schema_diff::definitions::AffectedSchemaMembers;
 pub use source_control::add_to_mercurial;
+pub use source_control::git_changed_files_since;
 pub use validate::validate;
+pub use validate::validate_pedantic;
 pub use validate::AdditionalValidations;
+use tokio::runtime::Handle;
+use tokio::runtime::RuntimeFlavor;
+use tokio::task;
+use write_artifacts::render_artifacts;
+use write_artifacts::write_artifacts;
 
 use self::log_program_stats::print_stats;
 pub use self::project_asts::find_duplicates;
@@ -66,6 +80,7 @@ pub use self::project_asts::ProjectAstData;
 pub use self::project_asts::ProjectAsts;
 use super::artifact_content;
 use crate::artifact_map::ArtifactMap;
+use crate::build_report::ArtifactChange;
 use crate::compiler_state::ArtifactMapKind;
 use crate::compiler_state::CompilerState;
 use crate::compiler_state::ProjectName;
@@ -76,7 +91,7 @@ use crate::file_source::SourceControlUpdateStatus;
 use crate::graphql_asts::GraphQLAsts;
 
 type BuildProjectOutput = WithDiagnostics<(ProjectName, Arc<SDLSchema>, Programs, Vec<Artifact>)>;
-type BuildProgramsOutput = WithDiagnostics<(Programs, Arc<SourceHashes>)>;
+pub type BuildProgramsOutput = WithDiagnostics<(Programs, Arc<SourceHashes>)>;
 
 pub enum BuildProjectFailure {
     Error(BuildProjectError),
@@ -97,16 +112,20 @@ pub fn build_raw_program(
     project_asts: ProjectAsts,
     schema: Arc<SDLSchema>,
     log_event: &impl PerfLogEvent,
-    is_incremental_build: bool,
+    affected_schema_members: Option<&AffectedSchemaMembers>,
 ) -> Result<(Program, SourceHashes), BuildProjectError> {
     // Build a type aware IR.
     let BuildIRResult { ir, source_hashes } = log_event.time("build_ir_time", || {
-        build_ir::build_ir(project_config, project_asts, &schema, is_incremental_build).map_err(
-            |errors| BuildProjectError::ValidationErrors {
-                errors,
-                project_name: project_config.name,
-            },
+        build_ir::build_ir(
+            project_config,
+            project_asts,
+            &schema,
+            affected_schema_members,
         )
+        .map_err(|errors| BuildProjectError::ValidationErrors {
+            errors,
+            project_name: project_config.name,
+        })
     })?;
 
     // Turn the IR into a base Program.
@@ -125,7 +144,16 @@ pub fn validate_program(
 ) -> Result<Vec<Diagnostic>, BuildProjectError> {
     let timer = log_event.start("validate_time");
     log_event.number("validate_documents_count", program.document_count());
-    let result = validate(program, project_config, &config.additional_validations).map_or_else(
+    let result = validate(program, project_config, &config.additional_validations);
+    let result = if config.pedantic_validations {
+        try2(result, validate_pedantic(program)).map(|(mut result, pedantic_warnings)| {
+            result.diagnostics.extend(pedantic_warnings);
+            result
+        })
+    } else {
+        result
+    }
+    .map_or_else(
         |errors| {
             Err(BuildProjectError::ValidationErrors {
                 errors,
@@ -148,6 +176,7 @@ pub fn transform_program(
     perf_logger: Arc<impl PerfLogger + 'static>,
     log_event: &impl PerfLogEvent,
     custom_transforms_config: Option<&CustomTransformsConfig>,
+    selection_conflict_cache: Option<&SelectionConflictCache>,
 ) -> Result<Programs, BuildProjectFailure> {
     let timer = log_event.start("apply_transforms_time");
     let result = apply_transforms(
@@ -157,6 +186,7 @@ pub fn transform_program(
         perf_logger,
         Some(print_stats),
         custom_transforms_config,
+        selection_conflict_cache,
     )
     .map_err(|errors| {
         BuildProjectFailure::Error(BuildProjectError::ValidationErrors {
@@ -170,6 +200,10 @@ pub fn transform_program(
     result
 }
 
+/// Builds the programs of the project. With `Config::keep_going`, the
+/// definitions with errors and those that depend on them are skipped, and
+/// the programs of the others are built: their errors are returned as
+/// diagnostics with the programs.
 pub fn build_programs(
     config: &Config,
     project_config: &ProjectConfig,
@@ -180,21 +214,87 @@ pub fn build_programs(
     log_event: &impl PerfLogEvent,
     perf_logger: Arc<impl PerfLogger + 'static>,
 ) -> Result<BuildProgramsOutput, BuildProjectFailure> {
-    let project_name = project_config.name;
-    let is_incremental_build = compiler_state.has_processed_changes()
-        && !compiler_state.has_breaking_schema_change(project_name, &project_config.schema_config)
-        && if let Some(base) = project_config.base {
-            !compiler_state.has_breaking_schema_change(base, &project_config.schema_config)
-        } else {
-            true
-        };
+    if !config.keep_going {
+        return build_programs_once(
+            config,
+            project_config,
+            compiler_state,
+            project_asts,
+            base_fragment_names,
+            schema,
+            log_event,
+            perf_logger,
+        );
+    }
+
+    let mut project_asts = project_asts;
+    let mut skipped_errors = Vec::new();
+    loop {
+        match build_programs_once(
+            config,
+            project_config,
+            compiler_state,
+            project_asts.clone(),
+            base_fragment_names.clone(),
+            Arc::clone(&schema),
+            log_event,
+            Arc::clone(&perf_logger),
+        ) {
+            Ok(mut output) => {
+                skipped_errors.append(&mut output.diagnostics);
+                output.diagnostics = skipped_errors;
+                return Ok(output);
+            }
+            Err(BuildProjectFailure::Error(BuildProjectError::ValidationErrors {
+                errors,
+                project_name,
+            })) => match remove_broken_definitions(&mut project_asts.definitions, &errors) {
+                Some(broken_names) => {
+                    warn!(
+                        "[{}] skipping {} definition(s) with errors or depending on them",
+                        project_name,
+                        broken_names.len()
+                    );
+                    skipped_errors.extend(errors);
+                }
+                None => {
+                    skipped_errors.extend(errors);
+                    return Err(BuildProjectFailure::Error(
+                        BuildProjectError::ValidationErrors {
+                            errors: skipped_errors,
+                            project_name,
+                        },
+                    ));
+                }
+            },
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_programs_once(
+    config: &Config,
+    project_config: &ProjectConfig,
+    compiler_state: &CompilerState,
+    project_asts: ProjectAsts,
+    base_fragment_names: FragmentDefinitionNameSet,
+    schema: Arc<SDLSchema>,
+    log_event: &impl PerfLogEvent,
+    perf_logger: Arc<impl PerfLogger + 'static>,
+) -> Result<BuildProgramsOutput, BuildProjectFailure> {
+    let affected_schema_members = if compiler_state.has_processed_changes() {
+        compiler_state.get_affected_schema_members(project_config)
+    } else {
+        None
+    };
 
     let (program, source_hashes) = build_raw_program(
         project_config,
         project_asts,
         schema,
         log_event,
-        is_incremental_build,
+        affected_schema_members.as_ref(),
     )?;
 
     if compiler_state.should_cancel_current_build() {
@@ -206,6 +306,7 @@ pub fn build_programs(
     // FIXME: Return non-fatal diagnostics from transforms (only validations for now)
     let diagnostics = validate_program(config, project_config, &program, log_event)?;
 
+    let selection_conflict_cache = compiler_state.selection_conflict_cache(project_config);
     let programs = transform_program(
         project_config,
         Arc::new(program),
@@ -213,6 +314,7 @@ pub fn build_programs(
         Arc::clone(&perf_logger),
         log_event,
         config.custom_transforms.as_ref(),
+        Some(&selection_conflict_cache),
     )?;
 
     Ok(WithDiagnostics {
@@ -237,7 +339,7 @@ pub fn build_project(
     // Construct a schema instance including project specific extensions.
     let schema = log_event
         .time("build_schema_time", || {
-            build_schema(compiler_state, project_config, graphql_asts_map)
+            build_schema(config, compiler_state, project_config, graphql_asts_map)
         })
         .map_err(|errors| {
             BuildProjectFailure::Error(BuildProjectError::ValidationErrors {
@@ -318,28 +420,85 @@ pub async fn commit_project(
     log_event.string("project", project_config.name.to_string());
     let commit_time = log_event.start("commit_project_time");
 
-    let fragment_locations = FragmentLocations::new(programs.typegen.fragments());
+    let mut fragment_locations = FragmentLocations::new(programs.typegen.fragments());
+    if let Some(base_project_config) = project_config
+        .base
+        .and_then(|base| config.projects.get(&base))
+    {
+        fragment_locations.add_base_fragments(
+            base_project_config,
+            programs
+                .source
+                .fragments()
+                .filter(|fragment| programs.base_fragment_names.contains(&fragment.name.item)),
+        );
+    }
     if source_control_update_status.is_started() {
         debug!("commit_project cancelled before persisting due to source control updates");
         return Err(BuildProjectFailure::Cancelled);
     }
 
+    let should_stop_updating_artifacts = || {
+        if source_control_update_status.is_started() {
+            debug!("artifact_writer updates cancelled due source control updates");
+            true
+        } else {
+            false
+        }
+    };
+
+    // The content of the first artifacts, if they were rendered already.
+    let mut rendered = Vec::new();
     if let Some(operation_persister) = config
         .create_operation_persister
         .as_ref()
         .and_then(|create_fn| create_fn(project_config))
     {
         let persist_operations_timer = log_event.start("persist_operations_time");
-        persist_operations::persist_operations(
-            &mut artifacts,
+        // Only the operations depend on the persisted ids, so the other
+        // artifacts are rendered while the operations are persisted. This
+        // blocks a worker of the runtime, which the current thread runtime
+        // of tests doesn't have.
+        let (mut operations, others): (Vec<_>, Vec<_>) = artifacts
+            .into_iter()
+            .partition(|artifact| matches!(artifact.content, ArtifactContent::Operation { .. }));
+        let persist = persist_operations::persist_operations(
+            &mut operations,
             &config.root_dir,
             config,
             project_config,
             &(*operation_persister),
             &log_event,
             &programs,
-        )
-        .await?;
+        );
+        let (persisted, rendered_others) =
+            if Handle::current().runtime_flavor() == RuntimeFlavor::MultiThread {
+                task::block_in_place(|| {
+                    thread::scope(|scope| {
+                        let rendering = scope.spawn(|| {
+                            render_artifacts(
+                                config,
+                                project_config,
+                                schema,
+                                should_stop_updating_artifacts,
+                                &others,
+                                &fragment_locations,
+                            )
+                        });
+                        let persisted = Handle::current().block_on(persist);
+                        let rendered_others = rendering
+                            .join()
+                            .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+                        (persisted, rendered_others)
+                    })
+                })
+            } else {
+                (persist.await, Ok(Vec::new()))
+            };
+        persisted?;
+        rendered = rendered_others?;
+        artifacts = others;
+        artifacts.append(&mut operations);
         log_event.stop(persist_operations_timer);
     }
 
@@ -363,20 +522,23 @@ pub async fn commit_project(
         });
     }
 
+    if let Some(build_stats) = &config.build_stats {
+        build_stats.record_artifacts(
+            project_config.name,
+            &programs,
+            &artifacts,
+            operation_text_printer_options(project_config),
+        );
+    }
+    if let Some(build_report) = &config.build_report {
+        build_report.record_persisted_operations(project_config.name, &artifacts);
+    }
+
     if source_control_update_status.is_started() {
         debug!("commit_project cancelled before writing artifacts due to source control updates");
         return Err(BuildProjectFailure::Cancelled);
     }
 
-    let should_stop_updating_artifacts = || {
-        if source_control_update_status.is_started() {
-            debug!("artifact_writer updates cancelled due source control updates");
-            true
-        } else {
-            false
-        }
-    };
-
     // Write the generated artifacts to disk. This step is separate from
     // generating artifacts or persisting to avoid partial writes in case of
     // errors as much as possible.
@@ -390,7 +552,9 @@ pub async fn commit_project(
                 schema,
                 should_stop_updating_artifacts,
                 &artifacts,
+                rendered,
                 &fragment_locations,
+                &log_event,
             )?;
             for artifact in &artifacts {
                 if !existing_artifacts.remove(&artifact.path) {
@@ -408,6 +572,13 @@ pub async fn commit_project(
                 }
                 let path = config.root_dir.join(remaining_artifact);
                 config.artifact_writer.remove(path)?;
+                if let Some(build_report) = &config.build_report {
+                    build_report.record_artifact(
+                        project_config.name,
+                        ArtifactChange::Deleted,
+                        remaining_artifact.clone(),
+                    );
+                }
             }
             log_event.stop(delete_artifacts_time);
             ArtifactMap::from(artifacts)
@@ -425,7 +596,9 @@ pub async fn commit_project(
                 schema,
                 should_stop_updating_artifacts,
                 &artifacts,
+                rendered,
                 &fragment_locations,
+                &log_event,
             )?;
             artifacts.into_par_iter().for_each(|artifact| {
                 current_paths_map.insert(artifact);
@@ -478,7 +651,14 @@ pub async fn commit_project(
                 if should_stop_updating_artifacts() {
                     break;
                 }
-                config.artifact_writer.remove(config.root_dir.join(path))?;
+                config.artifact_writer.remove(config.root_dir.join(&path))?;
+                if let Some(build_report) = &config.build_report {
+                    build_report.record_artifact(
+                        project_config.name,
+                        ArtifactChange::Deleted,
+                        path,
+                    );
+                }
             }
             log_event.stop(delete_artifacts_incremental_time);
 
@@ -516,37 +696,3 @@ The compiler may produce outdated artifacts, but it will regenerate the correct
 
     Ok(next_artifact_map)
 }
-
-fn write_artifacts<F: Fn() -> bool + Sync + Send>(
-    config: &Config,
-    project_config: &ProjectConfig,
-    schema: &SDLSchema,
-    should_stop_updating_artifacts: F,
-    artifacts: &[Artifact],
-    fragment_locations: &FragmentLocations,
-) -> Result<(), BuildProjectFailure> {
-    artifacts.par_chunks(8192).try_for_each_init(
-        || Printer::with_dedupe(project_config),
-        |mut printer, artifacts| {
-            for artifact in artifacts {
-                if should_stop_updating_artifacts() {
-                    return Err(BuildProjectFailure::Cancelled);
-                }
-                let path = config.root_dir.join(&artifact.path);
-                let content = artifact.content.as_bytes(
-                    config,
-                    project_config,
-                    &mut printer,
-                    schema,
-                    artifact.source_file,
-                    fragment_locations,
-                );
-                if config.artifact_writer.should_write(&path, &content)? {
-                    config.artifact_writer.write(path, content)?;
-                }
-            }
-            Ok(())
-        },
-    )?;
-    Ok(())
-}
@@ -7,9 +7,15 @@
 
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
 use common::sync::ParallelIterator;
 use common::PerfLogEvent;
+use futures::StreamExt;
+use graphql_ir::OperationDefinition;
+use graphql_text_printer::print_full_operation;
+use graphql_text_printer::PrinterOptions;
 use lazy_static::lazy_static;
 use log::debug;
 use md5::Digest;
@@ -41,6 +47,8 @@ pub async fn persist_operations(
     log_event: &impl PerfLogEvent,
     programs: &Programs,
 ) -> Result<(), BuildProjectError> {
+    // Operations whose persisted id is still valid for their text.
+    let reused_count = AtomicUsize::new(0);
     let handles = artifacts
         .par_iter_mut()
         .flat_map(|artifact| {
@@ -48,6 +56,7 @@ pub async fn persist_operations(
                 ref text,
                 ref mut id_and_text_hash,
                 ref reader_operation,
+                ref normalization_operation,
                 ..
             } = artifact.content
             {
@@ -61,7 +70,16 @@ pub async fn persist_operations(
                     }
                     None
                 } else if let Some(text) = text {
-                    let text_hash = md5(text);
+                    let text_hash = if project_config
+                        .persist
+                        .as_ref()
+                        .map_or(false, |config| config.legacy_text_hash())
+                    {
+                        md5(text)
+                    } else {
+                        md5(&canonical_text(programs, normalization_operation)
+                            .unwrap_or_else(|| text.clone()))
+                    };
                     let artifact_path = root_dir.join(&artifact.path);
                     let relative_path = artifact.path.to_owned();
                     let extracted_persist_id = if config.repersist_operations {
@@ -70,6 +88,7 @@ pub async fn persist_operations(
                         extract_persist_id(&artifact_path, &text_hash)
                     };
                     if let Some(id) = extracted_persist_id {
+                        reused_count.fetch_add(1, Ordering::Relaxed);
                         *id_and_text_hash = Some(QueryID::Persisted { id, text_hash });
                         None
                     } else {
@@ -95,7 +114,19 @@ pub async fn persist_operations(
         })
         .collect::<Vec<_>>();
     log_event.number("persist_documents", handles.len());
-    let results = futures::future::join_all(handles).await;
+    log_event.number(
+        "persist_reused_documents",
+        reused_count.load(Ordering::Relaxed),
+    );
+    let results: Vec<_> = match config.persist_concurrency {
+        Some(concurrency) => {
+            futures::stream::iter(handles)
+                .buffer_unordered(concurrency.get())
+                .collect()
+                .await
+        }
+        None => futures::future::join_all(handles).await,
+    };
     operation_persister
         .finalize()
         .map_err(|error| BuildProjectError::PersistErrors {
@@ -118,6 +149,17 @@ pub async fn persist_operations(
     Ok(())
 }
 
+/// The text of the operation printed with canonical options, so that its
+/// hash doesn't change with the way the compiler prints the persisted text.
+fn canonical_text(programs: &Programs, operation: &OperationDefinition) -> Option<String> {
+    let operation = programs.operation_text.operation(operation.name.item)?;
+    Some(print_full_operation(
+        &programs.operation_text,
+        operation,
+        PrinterOptions::canonical(),
+    ))
+}
+
 fn extract_persist_id(path: &PathBuf, text_hash: &str) -> Option<String> {
     let content = fs::read_to_string(path).ok()?;
 
@@ -23,6 +23,7 @@ use crate::compiler_state::ProjectName;
 use crate::errors::BuildProjectError;
 use crate::GraphQLAsts;
 
+#[derive(Clone)]
 pub struct ProjectAsts {
     pub changed_names: ExecutableDefinitionNameSet,
     pub base_definition_names: ExecutableDefinitionNameSet,
@@ -5,13 +5,17 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
 use std::process::Stdio;
 use std::sync::Mutex;
 
+use fnv::FnvHashSet;
 use log::info;
 
+use crate::errors::Error;
+
 pub fn add_to_mercurial(
     root_dir: &PathBuf,
     added: &Mutex<Vec<PathBuf>>,
@@ -59,3 +63,44 @@ pub fn add_to_mercurial(
     }
     Ok(())
 }
+
+/// Returns the files under `root_dir` that differ between the git `revision`
+/// and the working tree, including untracked files, relative to `root_dir`.
+pub fn git_changed_files_since(
+    root_dir: &Path,
+    revision: &str,
+) -> crate::errors::Result<FnvHashSet<PathBuf>> {
+    let run_git = |args: &[&str]| {
+        let output = Command::new("git")
+            .current_dir(root_dir)
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|err| Error::SourceControlDiffError {
+                revision: revision.to_string(),
+                reason: format!("Failed to run `git`: {}", err),
+            })?;
+        if !output.status.success() {
+            return Err(Error::SourceControlDiffError {
+                revision: revision.to_string(),
+                reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect::<Vec<_>>())
+    };
+
+    let mut files: FnvHashSet<PathBuf> = Default::default();
+    files.extend(run_git(&[
+        "diff",
+        "--name-only",
+        "--relative",
+        revision,
+        "--",
+    ])?);
+    files.extend(run_git(&["ls-files", "--others", "--exclude-standard"])?);
+    Ok(files)
+}
@@ -0,0 +1,177 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use dependency_analyzer::ExecutableDefinitionNameSet;
+use fnv::FnvHashMap;
+use graphql_ir::Argument;
+use graphql_ir::Directive;
+use graphql_ir::ExecutableDefinition;
+use graphql_ir::ExecutableDefinitionName;
+use graphql_ir::FragmentDefinition;
+use graphql_ir::InlineFragment;
+use graphql_ir::LinkedField;
+use graphql_ir::OperationDefinition;
+use graphql_ir::ScalarField;
+use graphql_ir::VariableDefinition;
+use graphql_ir::Visitor;
+use intern::string_key::StringKey;
+use schema::ArgumentDefinitions;
+use schema::FieldID;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+use schema_diff::definitions::AffectedSchemaMembers;
+
+/// Maps the types and fields of the schema to the definitions that use them,
+/// to find the definitions a schema change affects.
+#[derive(Default)]
+pub struct TypeUsageIndex {
+    types: FnvHashMap<StringKey, ExecutableDefinitionNameSet>,
+    fields: FnvHashMap<(StringKey, StringKey), ExecutableDefinitionNameSet>,
+}
+
+impl TypeUsageIndex {
+    /// A definition uses the root type of an operation, the type conditions,
+    /// the parent and return types of its fields, and the types of its
+    /// variables and arguments, including the input objects and enums that
+    /// input objects use.
+    pub fn new(schema: &SDLSchema, definitions: &[ExecutableDefinition]) -> Self {
+        let mut collector = TypeUsageCollector {
+            schema,
+            index: Default::default(),
+            definition: None,
+        };
+        for definition in definitions {
+            match definition {
+                ExecutableDefinition::Operation(operation) => collector.visit_operation(operation),
+                ExecutableDefinition::Fragment(fragment) => collector.visit_fragment(fragment),
+            }
+        }
+        collector.index
+    }
+
+    pub fn find_affected_definitions(
+        &self,
+        members: &AffectedSchemaMembers,
+    ) -> ExecutableDefinitionNameSet {
+        let mut definitions = ExecutableDefinitionNameSet::default();
+        for type_name in &members.types {
+            if let Some(names) = self.types.get(type_name) {
+                definitions.extend(names.iter().copied());
+            }
+        }
+        for field in &members.fields {
+            if let Some(names) = self.fields.get(field) {
+                definitions.extend(names.iter().copied());
+            }
+        }
+        definitions
+    }
+}
+
+struct TypeUsageCollector<'s> {
+    schema: &'s SDLSchema,
+    index: TypeUsageIndex,
+    definition: Option<ExecutableDefinitionName>,
+}
+
+impl TypeUsageCollector<'_> {
+    fn definition(&self) -> ExecutableDefinitionName {
+        self.definition
+            .expect("Expected usages to be in a definition.")
+    }
+
+    fn record_type(&mut self, type_: Type) {
+        let definition = self.definition();
+        let is_new = self
+            .index
+            .types
+            .entry(self.schema.get_type_name(type_))
+            .or_default()
+            .insert(definition);
+        if is_new {
+            if let Type::InputObject(id) = type_ {
+                let schema = self.schema;
+                for field in schema.input_object(id).fields.iter() {
+                    self.record_type(field.type_.inner());
+                }
+            }
+        }
+    }
+
+    fn record_field(&mut self, field_id: FieldID, arguments: &[Argument]) {
+        let schema = self.schema;
+        let field = schema.field(field_id);
+        self.record_type(field.type_.inner());
+        // `__typename` and other special fields have no parent type.
+        if let Some(parent_type) = field.parent_type {
+            self.record_type(parent_type);
+            let definition = self.definition();
+            self.index
+                .fields
+                .entry((schema.get_type_name(parent_type), field.name.item))
+                .or_default()
+                .insert(definition);
+        }
+        self.record_argument_types(&field.arguments, arguments);
+    }
+
+    fn record_argument_types(&mut self, definitions: &ArgumentDefinitions, arguments: &[Argument]) {
+        for argument in arguments {
+            if let Some(definition) = definitions.named(argument.name.item) {
+                self.record_type(definition.type_.inner());
+            }
+        }
+    }
+}
+
+impl Visitor for TypeUsageCollector<'_> {
+    const NAME: &'static str = "TypeUsageCollector";
+    const VISIT_ARGUMENTS: bool = false;
+    const VISIT_DIRECTIVES: bool = true;
+
+    fn visit_operation(&mut self, operation: &OperationDefinition) {
+        self.definition = Some(operation.name.item.into());
+        self.record_type(operation.type_);
+        self.default_visit_operation(operation);
+    }
+
+    fn visit_fragment(&mut self, fragment: &FragmentDefinition) {
+        self.definition = Some(fragment.name.item.into());
+        self.record_type(fragment.type_condition);
+        self.default_visit_fragment(fragment);
+    }
+
+    fn visit_variable_definition(&mut self, variable_definition: &VariableDefinition) {
+        self.record_type(variable_definition.type_.inner());
+        self.default_visit_variable_definition(variable_definition);
+    }
+
+    fn visit_scalar_field(&mut self, field: &ScalarField) {
+        self.record_field(field.definition.item, &field.arguments);
+        self.default_visit_scalar_field(field);
+    }
+
+    fn visit_linked_field(&mut self, field: &LinkedField) {
+        self.record_field(field.definition.item, &field.arguments);
+        self.default_visit_linked_field(field);
+    }
+
+    fn visit_inline_fragment(&mut self, fragment: &InlineFragment) {
+        if let Some(type_condition) = fragment.type_condition {
+            self.record_type(type_condition);
+        }
+        self.default_visit_inline_fragment(fragment);
+    }
+
+    fn visit_directive(&mut self, directive: &Directive) {
+        let schema = self.schema;
+        if let Some(definition) = schema.get_directive(directive.name.item) {
+            self.record_argument_types(&definition.arguments, &directive.arguments);
+        }
+    }
+}
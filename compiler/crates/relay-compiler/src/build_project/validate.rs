@@ -7,14 +7,20 @@
 
 use common::escalate_and_check;
 use common::CriticalDiagnostics;
+use common::Diagnostic;
+use common::DiagnosticSeverity;
 use common::DiagnosticsResult;
 use common::FeatureFlags;
 use common::StableDiagnostics;
 use common::WithDiagnostics;
+use errors::try2;
 use errors::try_all;
 use graphql_ir::Program;
 use relay_config::ProjectConfig;
+use relay_transforms::deprecated_fields;
+use relay_transforms::disallow_banned_fields;
 use relay_transforms::disallow_circular_no_inline_fragments;
+use relay_transforms::disallow_client_enum_values;
 use relay_transforms::disallow_reserved_aliases;
 use relay_transforms::disallow_typename_on_root;
 use relay_transforms::validate_assignable_directive;
@@ -26,6 +32,7 @@ use relay_transforms::validate_no_inline_fragments_with_raw_response_type;
 use relay_transforms::validate_no_unselectable_selections;
 use relay_transforms::validate_relay_directives;
 use relay_transforms::validate_resolver_fragments;
+use relay_transforms::validate_server_only_directives;
 use relay_transforms::validate_static_args;
 use relay_transforms::validate_unused_fragment_variables;
 use relay_transforms::validate_unused_variables;
@@ -43,6 +50,8 @@ pub fn validate(
     let output = try_all(vec![
         disallow_reserved_aliases(program, &project_config.schema_config),
         validate_no_unselectable_selections(program, &project_config.schema_config),
+        disallow_banned_fields(program, &project_config.schema_config),
+        disallow_client_enum_values(program),
         validate_no_double_underscore_alias(program),
         validate_unused_variables(program),
         validate_unused_fragment_variables(program),
@@ -91,3 +100,20 @@ pub fn validate(
         }
     }
 }
+
+/// Validations that are only run if `Config::pedantic_validations` is set,
+/// e.g. by `relay lint --pedantic`. Usages of deprecated fields are reported
+/// as warnings.
+pub fn validate_pedantic(program: &Program) -> DiagnosticsResult<Vec<Diagnostic>> {
+    let (_, warnings) = try2(
+        validate_server_only_directives(program),
+        deprecated_fields(&program.schema, program),
+    )?;
+    Ok(warnings
+        .into_iter()
+        .map(|mut warning| {
+            warning.override_severity(DiagnosticSeverity::WARNING);
+            warning
+        })
+        .collect())
+}
@@ -0,0 +1,317 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Writing the artifacts of a project is a pipeline: the artifacts are
+//! rendered (printing and typegen) on the rayon thread pool, and a few
+//! writer threads compare them with the files on disk and write them. The
+//! stages are connected with a bounded channel, so the rendering of the
+//! remaining artifacts overlaps with the writing of the rendered ones,
+//! without holding all the rendered artifacts in memory.
+
+use std::panic::resume_unwind;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+
+use common::PerfLogEvent;
+use rayon::iter::IntoParallelRefIterator;
+use rayon::iter::ParallelIterator;
+use rayon::slice::ParallelSlice;
+use relay_codegen::Printer;
+use relay_typegen::FragmentLocations;
+use schema::SDLSchema;
+
+use super::Artifact;
+use super::BuildProjectFailure;
+use crate::build_report::ArtifactChange;
+use crate::config::Config;
+use crate::config::ProjectConfig;
+
+/// The most rendered artifacts that wait for a writer.
+const WRITE_QUEUE_CAPACITY: usize = 256;
+
+/// The number of writer threads without `artifact_write_concurrency`.
+const DEFAULT_WRITER_COUNT: usize = 4;
+
+/// Renders artifacts that don't depend on the persisted operations, while
+/// they are persisted. The content is passed to `write_artifacts` later.
+pub(super) fn render_artifacts<F: Fn() -> bool + Sync + Send>(
+    config: &Config,
+    project_config: &ProjectConfig,
+    schema: &SDLSchema,
+    should_stop_updating_artifacts: F,
+    artifacts: &[Artifact],
+    fragment_locations: &FragmentLocations,
+) -> Result<Vec<Vec<u8>>, BuildProjectFailure> {
+    artifacts
+        .par_iter()
+        .map_init(
+            || Printer::with_dedupe(project_config),
+            |printer, artifact| {
+                if should_stop_updating_artifacts() {
+                    return Err(BuildProjectFailure::Cancelled);
+                }
+                Ok(render_artifact(
+                    config,
+                    project_config,
+                    schema,
+                    printer,
+                    artifact,
+                    fragment_locations,
+                ))
+            },
+        )
+        .collect()
+}
+
+/// Writes the artifacts whose content changed. `rendered` is the content of
+/// the first artifacts, if they were rendered already.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn write_artifacts<F: Fn() -> bool + Sync + Send>(
+    config: &Config,
+    project_config: &ProjectConfig,
+    schema: &SDLSchema,
+    should_stop_updating_artifacts: F,
+    artifacts: &[Artifact],
+    rendered: Vec<Vec<u8>>,
+    fragment_locations: &FragmentLocations,
+    log_event: &impl PerfLogEvent,
+) -> Result<(), BuildProjectFailure> {
+    // Artifacts are not written if their content on disk is the same.
+    let written_count = AtomicUsize::new(0);
+    let (rendered_artifacts, artifacts_to_render) = artifacts.split_at(rendered.len());
+    let chunk_size = match config.artifact_write_concurrency {
+        Some(concurrency) => artifacts_to_render.len().div_ceil(concurrency.get()).max(1),
+        None => 8192,
+    };
+    let writer_count = config
+        .artifact_write_concurrency
+        .map_or(DEFAULT_WRITER_COUNT, |concurrency| concurrency.get());
+    pipeline(
+        WRITE_QUEUE_CAPACITY,
+        writer_count,
+        |sender| {
+            let (_, rendering) = rayon::join(
+                || {
+                    for (artifact, content) in rendered_artifacts.iter().zip(rendered) {
+                        if !sender.send((artifact, content)) {
+                            break;
+                        }
+                    }
+                },
+                || {
+                    artifacts_to_render
+                        .par_chunks(chunk_size)
+                        .try_for_each_init(
+                            || Printer::with_dedupe(project_config),
+                            |printer, artifacts| {
+                                for artifact in artifacts {
+                                    if should_stop_updating_artifacts() {
+                                        return Err(BuildProjectFailure::Cancelled);
+                                    }
+                                    let content = render_artifact(
+                                        config,
+                                        project_config,
+                                        schema,
+                                        printer,
+                                        artifact,
+                                        fragment_locations,
+                                    );
+                                    if !sender.send((artifact, content)) {
+                                        break;
+                                    }
+                                }
+                                Ok(())
+                            },
+                        )
+                },
+            );
+            rendering
+        },
+        |(artifact, content): (&Artifact, Vec<u8>)| {
+            if should_stop_updating_artifacts() {
+                return Err(BuildProjectFailure::Cancelled);
+            }
+            let path = config.root_dir.join(&artifact.path);
+            if config.artifact_writer.should_write(&path, &content)? {
+                if let Some(build_report) = &config.build_report {
+                    let change = if path.exists() {
+                        ArtifactChange::Updated
+                    } else {
+                        ArtifactChange::Written
+                    };
+                    build_report.record_artifact(
+                        project_config.name,
+                        change,
+                        artifact.path.clone(),
+                    );
+                }
+                config.artifact_writer.write(path, content)?;
+                written_count.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(())
+        },
+    )?;
+    let written_count = written_count.into_inner();
+    if let Some(build_report) = &config.build_report {
+        build_report
+            .record_unchanged_artifacts(project_config.name, artifacts.len() - written_count);
+    }
+    log_event.number("written_artifacts", written_count);
+    log_event.number("unchanged_artifacts", artifacts.len() - written_count);
+    Ok(())
+}
+
+fn render_artifact(
+    config: &Config,
+    project_config: &ProjectConfig,
+    schema: &SDLSchema,
+    printer: &mut Printer<'_>,
+    artifact: &Artifact,
+    fragment_locations: &FragmentLocations,
+) -> Vec<u8> {
+    let content = artifact.content.as_bytes(
+        config,
+        project_config,
+        printer,
+        schema,
+        artifact.source_file,
+        fragment_locations,
+    );
+    if let Some(build_stats) = &config.build_stats {
+        build_stats.record_artifact_bytes(project_config.name, content.len());
+    }
+    content
+}
+
+/// Sends items to the consumers of a `pipeline`.
+struct PipelineSender<'a, T> {
+    sender: mpsc::SyncSender<T>,
+    failed: &'a AtomicBool,
+}
+
+impl<T> PipelineSender<'_, T> {
+    /// Waits until there is room for the item in the channel. Returns false
+    /// if a consumer failed, and the producer should stop.
+    fn send(&self, item: T) -> bool {
+        !self.failed.load(Ordering::Relaxed) && self.sender.send(item).is_ok()
+    }
+}
+
+/// Runs `produce` on the current thread and `consume` on `consumer_count`
+/// threads, at the same time. At most `capacity` of the produced items wait
+/// for a consumer. After the first error of a consumer, the other consumers
+/// stop and that error is returned, otherwise the result of `produce`.
+fn pipeline<T, E, P, C>(
+    capacity: usize,
+    consumer_count: usize,
+    produce: P,
+    consume: C,
+) -> Result<(), E>
+where
+    T: Send,
+    E: Send,
+    P: FnOnce(&PipelineSender<'_, T>) -> Result<(), E>,
+    C: Fn(T) -> Result<(), E> + Sync,
+{
+    let (sender, receiver) = mpsc::sync_channel(capacity);
+    // The receiver is only owned by the consumers, so that sending fails
+    // instead of blocking once all of them stopped.
+    let receiver = Arc::new(Mutex::new(receiver));
+    let failed = AtomicBool::new(false);
+    thread::scope(|scope| {
+        let consumers = (0..consumer_count.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                let failed = &failed;
+                let consume = &consume;
+                scope.spawn(move || {
+                    loop {
+                        let item = receiver.lock().unwrap().recv();
+                        let Ok(item) = item else {
+                            return Ok(());
+                        };
+                        if failed.load(Ordering::Relaxed) {
+                            return Ok(());
+                        }
+                        if let Err(error) = consume(item) {
+                            failed.store(true, Ordering::Relaxed);
+                            return Err(error);
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        drop(receiver);
+
+        let produced = produce(&PipelineSender {
+            sender,
+            failed: &failed,
+        });
+        let mut consumed = Ok(());
+        for consumer in consumers {
+            let result = consumer.join().unwrap_or_else(|panic| resume_unwind(panic));
+            if consumed.is_ok() {
+                consumed = result;
+            }
+        }
+        consumed.and(produced)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipeline_consumes_all_items() {
+        let consumed = Mutex::new(Vec::new());
+        let result: Result<(), ()> = pipeline(
+            2,
+            3,
+            |sender| {
+                for item in 0..100 {
+                    assert!(sender.send(item));
+                }
+                Ok(())
+            },
+            |item| {
+                consumed.lock().unwrap().push(item);
+                Ok(())
+            },
+        );
+        assert_eq!(result, Ok(()));
+        let mut consumed = consumed.into_inner().unwrap();
+        consumed.sort_unstable();
+        assert_eq!(consumed, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pipeline_stops_producing_after_a_consumer_failed() {
+        let mut sent = 0;
+        let result = pipeline(
+            2,
+            3,
+            |sender| {
+                for item in 0..1000 {
+                    if !sender.send(item) {
+                        break;
+                    }
+                    sent += 1;
+                }
+                Ok(())
+            },
+            |item| if item == 10 { Err(item) } else { Ok(()) },
+        );
+        assert_eq!(result, Err(10));
+        assert!(sent < 1000);
+    }
+}
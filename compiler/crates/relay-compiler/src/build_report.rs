@@ -0,0 +1,328 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A machine-readable report of a build, written as JSON after each build,
+//! including those of watch mode, if `Config::build_report` is set, e.g. by
+//! `relay --buildReport`, for build observability pipelines.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use common::Diagnostic;
+use fnv::FnvHashMap;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use relay_codegen::QueryID;
+use serde::Serialize;
+
+use crate::build_project::Artifact;
+use crate::build_project::ArtifactContent;
+use crate::build_stats::BuildStats;
+use crate::build_stats::ProjectStats;
+use crate::compiler_state::ProjectName;
+use crate::diagnostics_summary::DiagnosticsSummary;
+
+/// Where the schema of a project came from in a build.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SchemaSource {
+    /// The schema of the previous build of watch mode, which didn't change.
+    Memory,
+    /// The schema cached in `Config::schema_cache_dir`.
+    DiskCache,
+    /// The schema was built from its files.
+    Built,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ArtifactChange {
+    Written,
+    Updated,
+    Deleted,
+}
+
+/// The artifacts of a project, relative to the root dir. `written` are new
+/// files, `updated` are files whose content changed.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactChanges {
+    pub written: Vec<PathBuf>,
+    pub updated: Vec<PathBuf>,
+    pub deleted: Vec<PathBuf>,
+    pub unchanged: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedOperation {
+    pub name: StringKey,
+    pub id: String,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ProjectRecord {
+    schema: Option<SchemaSource>,
+    artifacts: ArtifactChanges,
+    persisted_operations: Vec<PersistedOperation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectReport {
+    name: StringKey,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<SchemaSource>,
+    artifacts: ArtifactChanges,
+    persisted_operations: Vec<PersistedOperation>,
+    /// The statistics and the durations of the phases of the project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<ProjectStats>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheStats {
+    hits: usize,
+    misses: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Report {
+    succeeded: bool,
+    duration_ms: u128,
+    diagnostics: DiagnosticsSummary,
+    /// The errors of the build that have no diagnostics, like failing to
+    /// persist an operation.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<String>,
+    /// Schemas reused from the previous build or loaded from the schema
+    /// cache are hits.
+    schema_cache: CacheStats,
+    projects: Vec<ProjectReport>,
+}
+
+pub struct BuildReport {
+    path: PathBuf,
+    stats: Arc<BuildStats>,
+    started: Mutex<Option<Instant>>,
+    projects: Mutex<FnvHashMap<ProjectName, ProjectRecord>>,
+}
+
+impl BuildReport {
+    /// The report is written to `path`. The statistics and durations of the
+    /// projects are those collected in `stats`, which should be the
+    /// `Config::build_stats`.
+    pub fn new(path: PathBuf, stats: Arc<BuildStats>) -> Self {
+        Self {
+            path,
+            stats,
+            started: Default::default(),
+            projects: Default::default(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn update(&self, project_name: ProjectName, f: impl FnOnce(&mut ProjectRecord)) {
+        f(self
+            .projects
+            .lock()
+            .unwrap()
+            .entry(project_name)
+            .or_default())
+    }
+
+    /// Forgets the previous build.
+    pub(crate) fn start(&self) {
+        *self.started.lock().unwrap() = Some(Instant::now());
+        self.projects.lock().unwrap().clear();
+        self.stats.clear();
+    }
+
+    pub(crate) fn record_schema(&self, project_name: ProjectName, source: SchemaSource) {
+        self.update(project_name, |record| record.schema = Some(source));
+    }
+
+    pub(crate) fn record_artifact(
+        &self,
+        project_name: ProjectName,
+        change: ArtifactChange,
+        path: PathBuf,
+    ) {
+        self.update(project_name, |record| {
+            let artifacts = &mut record.artifacts;
+            match change {
+                ArtifactChange::Written => artifacts.written.push(path),
+                ArtifactChange::Updated => artifacts.updated.push(path),
+                ArtifactChange::Deleted => artifacts.deleted.push(path),
+            }
+        });
+    }
+
+    pub(crate) fn record_unchanged_artifacts(&self, project_name: ProjectName, count: usize) {
+        self.update(project_name, |record| record.artifacts.unchanged += count);
+    }
+
+    /// Records the ids of the operations of the artifacts that were
+    /// persisted in this build.
+    pub(crate) fn record_persisted_operations(
+        &self,
+        project_name: ProjectName,
+        artifacts: &[Artifact],
+    ) {
+        let persisted_operations = artifacts
+            .iter()
+            .filter_map(|artifact| match &artifact.content {
+                ArtifactContent::Operation {
+                    normalization_operation,
+                    id_and_text_hash: Some(QueryID::Persisted { id, .. }),
+                    ..
+                } => Some(PersistedOperation {
+                    name: normalization_operation.name.item.0,
+                    id: id.clone(),
+                }),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        self.update(project_name, |record| {
+            record.persisted_operations.extend(persisted_operations)
+        });
+    }
+
+    /// Prints the report of the build that ended with these diagnostics and
+    /// errors.
+    pub fn to_json<'a>(
+        &self,
+        succeeded: bool,
+        diagnostics: impl IntoIterator<Item = &'a Diagnostic>,
+        errors: Vec<String>,
+    ) -> String {
+        let duration_ms = self
+            .started
+            .lock()
+            .unwrap()
+            .map_or(0, |started| started.elapsed().as_millis());
+        let mut stats = self
+            .stats
+            .projects()
+            .into_iter()
+            .map(|stats| (stats.name, stats))
+            .collect::<FnvHashMap<_, _>>();
+        let mut records = self.projects.lock().unwrap().clone();
+        for project_name in stats.keys() {
+            records.entry(*project_name).or_default();
+        }
+
+        let mut schema_cache = CacheStats::default();
+        let mut projects = records
+            .into_iter()
+            .map(|(name, record)| {
+                match record.schema {
+                    Some(SchemaSource::Memory | SchemaSource::DiskCache) => schema_cache.hits += 1,
+                    Some(SchemaSource::Built) => schema_cache.misses += 1,
+                    None => {}
+                }
+                let mut artifacts = record.artifacts;
+                artifacts.written.sort();
+                artifacts.updated.sort();
+                artifacts.deleted.sort();
+                let mut persisted_operations = record.persisted_operations;
+                persisted_operations.sort_by(|a, b| a.name.lookup().cmp(b.name.lookup()));
+                ProjectReport {
+                    name,
+                    schema: record.schema,
+                    artifacts,
+                    persisted_operations,
+                    stats: stats.remove(&name),
+                }
+            })
+            .collect::<Vec<_>>();
+        projects.sort_by(|a, b| a.name.lookup().cmp(b.name.lookup()));
+
+        let report = Report {
+            succeeded,
+            duration_ms,
+            diagnostics: DiagnosticsSummary::new(diagnostics),
+            errors,
+            schema_cache,
+            projects,
+        };
+        serde_json::to_string_pretty(&report).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Location;
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    #[test]
+    fn reports_the_last_build() {
+        let report = BuildReport::new(
+            PathBuf::from("report.json"),
+            Arc::new(BuildStats::default()),
+        );
+        report.start();
+        report.record_artifact(
+            "web".intern(),
+            ArtifactChange::Written,
+            PathBuf::from("src/__generated__/Old.graphql.ts"),
+        );
+        report.start();
+        report.record_schema("web".intern(), SchemaSource::DiskCache);
+        report.record_schema("native".intern(), SchemaSource::Built);
+        report.record_artifact(
+            "web".intern(),
+            ArtifactChange::Updated,
+            PathBuf::from("src/__generated__/Foo.graphql.ts"),
+        );
+        report.record_artifact(
+            "web".intern(),
+            ArtifactChange::Deleted,
+            PathBuf::from("src/__generated__/Bar.graphql.ts"),
+        );
+        report.record_unchanged_artifacts("web".intern(), 3);
+
+        let diagnostics = vec![Diagnostic::warning(
+            "Deprecated",
+            Location::generated(),
+            vec![],
+        )];
+        let json: serde_json::Value = serde_json::from_str(&report.to_json(
+            true,
+            &diagnostics,
+            vec!["Failed to persist `FooQuery`".to_string()],
+        ))
+        .unwrap();
+
+        assert_eq!(json["succeeded"], true);
+        assert_eq!(json["diagnostics"]["warnings"], 1);
+        assert_eq!(json["errors"][0], "Failed to persist `FooQuery`");
+        assert_eq!(
+            json["schemaCache"],
+            serde_json::json!({"hits": 1, "misses": 1})
+        );
+        assert_eq!(json["projects"][0]["name"], "native");
+        assert_eq!(
+            json["projects"][1]["artifacts"],
+            serde_json::json!({
+                "written": [],
+                "updated": ["src/__generated__/Foo.graphql.ts"],
+                "deleted": ["src/__generated__/Bar.graphql.ts"],
+                "unchanged": 3,
+            })
+        );
+    }
+}
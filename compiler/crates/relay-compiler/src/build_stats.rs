@@ -0,0 +1,530 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Statistics of a build per project, collected if `Config::build_stats` is
+//! set, e.g. by `relay --stats`. The allocations of the phases are recorded
+//! while `allocations::is_counting`, e.g. for `relay --memoryReport`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use common::PerfLogEvent;
+use common::PerfLogger;
+use fnv::FnvHashMap;
+use graphql_text_printer::print_full_operation;
+use graphql_text_printer::PrinterOptions;
+use intern::string_key::Intern;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use relay_codegen::QueryID;
+use relay_transforms::Programs;
+use serde::Serialize;
+
+use crate::allocations::format_bytes;
+use crate::allocations::is_counting;
+use crate::allocations::peak_heap_bytes;
+use crate::allocations::peak_rss_bytes;
+use crate::allocations::AllocationCounts;
+use crate::build_project::Artifact;
+use crate::build_project::ArtifactContent;
+use crate::compiler_state::ProjectName;
+
+/// The number of operations listed in `ProjectStats::largest_operations`.
+const LARGEST_OPERATIONS_COUNT: usize = 10;
+
+/// The timers of the build that are reported as phase durations.
+const PHASES: [&str; 9] = [
+    "build_schema_time",
+    "build_ir_time",
+    "validate_time",
+    "apply_transforms_time",
+    "generate_artifacts_time",
+    "persist_operations_time",
+    "write_artifacts_time",
+    "build_project_time",
+    "commit_project_time",
+];
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStats {
+    pub name: StringKey,
+    pub operations: usize,
+    /// Fragments of the project, without those of the base project.
+    pub fragments: usize,
+    pub artifacts: usize,
+    pub artifact_bytes: usize,
+    pub persisted_operations: usize,
+    /// The source files that were read, and those of them skipped without
+    /// being scanned for GraphQL, because they contain none of the tags.
+    pub extracted_files: usize,
+    pub skipped_files: usize,
+    /// The operations with the largest text, largest first.
+    pub largest_operations: Vec<OperationSize>,
+    /// The total size of the text of the operations, printed pretty and
+    /// printed compact, to measure the bytes compact text saves.
+    pub operation_text_bytes: usize,
+    pub compact_operation_text_bytes: usize,
+    /// Durations of the phases in milliseconds.
+    pub phase_durations_ms: BTreeMap<&'static str, u128>,
+    /// Allocations of the phases, if they were counted.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub phase_allocations: BTreeMap<&'static str, PhaseAllocations>,
+}
+
+impl ProjectStats {
+    fn new(name: ProjectName) -> Self {
+        Self {
+            name,
+            operations: 0,
+            fragments: 0,
+            artifacts: 0,
+            artifact_bytes: 0,
+            persisted_operations: 0,
+            extracted_files: 0,
+            skipped_files: 0,
+            largest_operations: Vec::new(),
+            operation_text_bytes: 0,
+            compact_operation_text_bytes: 0,
+            phase_durations_ms: Default::default(),
+            phase_allocations: Default::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseAllocations {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    /// The bytes the phase left on the heap, which are negative if it freed
+    /// more than it allocated. Only tracked with the `memory_profile`
+    /// feature.
+    pub retained_bytes: Option<i64>,
+}
+
+impl PhaseAllocations {
+    fn between(start: AllocationCounts, end: AllocationCounts) -> Self {
+        Self {
+            allocations: end.allocations - start.allocations,
+            allocated_bytes: end.allocated_bytes - start.allocated_bytes,
+            retained_bytes: start
+                .heap_bytes
+                .zip(end.heap_bytes)
+                .map(|(start, end)| end as i64 - start as i64),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationSize {
+    pub name: StringKey,
+    pub text_bytes: usize,
+}
+
+#[derive(Default)]
+pub struct BuildStats {
+    projects: Mutex<FnvHashMap<ProjectName, ProjectStats>>,
+}
+
+impl BuildStats {
+    fn update(&self, project_name: ProjectName, f: impl FnOnce(&mut ProjectStats)) {
+        let mut projects = self.projects.lock().unwrap();
+        f(projects
+            .entry(project_name)
+            .or_insert_with(|| ProjectStats::new(project_name)))
+    }
+
+    /// Records the definitions and the artifacts of a project, after the
+    /// operations are persisted. The text of the operations is printed again,
+    /// pretty and compact, with the other `printer_options` of the project.
+    pub(crate) fn record_artifacts(
+        &self,
+        project_name: ProjectName,
+        programs: &Programs,
+        artifacts: &[Artifact],
+        printer_options: PrinterOptions,
+    ) {
+        let pretty_options = PrinterOptions {
+            compact: false,
+            ..printer_options
+        };
+        let compact_options = PrinterOptions {
+            compact: true,
+            ..printer_options
+        };
+        let mut operation_sizes = Vec::new();
+        let mut persisted_operations = 0;
+        let mut operation_text_bytes = 0;
+        let mut compact_operation_text_bytes = 0;
+        for artifact in artifacts {
+            if let ArtifactContent::Operation {
+                normalization_operation,
+                text,
+                id_and_text_hash,
+                ..
+            } = &artifact.content
+            {
+                if let Some(text) = text {
+                    operation_sizes.push(OperationSize {
+                        name: normalization_operation.name.item.0,
+                        text_bytes: text.len(),
+                    });
+                    if let Some(operation) = programs
+                        .operation_text
+                        .operation(normalization_operation.name.item)
+                    {
+                        let program = &programs.operation_text;
+                        operation_text_bytes +=
+                            print_full_operation(program, operation, pretty_options).len();
+                        compact_operation_text_bytes +=
+                            print_full_operation(program, operation, compact_options).len();
+                    }
+                }
+                if let Some(QueryID::Persisted { .. }) = id_and_text_hash {
+                    persisted_operations += 1;
+                }
+            }
+        }
+        operation_sizes.sort_by(|a, b| {
+            b.text_bytes
+                .cmp(&a.text_bytes)
+                .then_with(|| a.name.lookup().cmp(b.name.lookup()))
+        });
+        operation_sizes.truncate(LARGEST_OPERATIONS_COUNT);
+
+        self.update(project_name, |stats| {
+            stats.operations = programs.source.operations().count();
+            stats.fragments = programs
+                .source
+                .fragments()
+                .filter(|fragment| !programs.base_fragment_names.contains(&fragment.name.item))
+                .count();
+            stats.artifacts = artifacts.len();
+            stats.persisted_operations = persisted_operations;
+            stats.largest_operations = operation_sizes;
+            stats.operation_text_bytes = operation_text_bytes;
+            stats.compact_operation_text_bytes = compact_operation_text_bytes;
+        });
+    }
+
+    pub(crate) fn record_extracted_files(
+        &self,
+        project_name: ProjectName,
+        extracted_files: usize,
+        skipped_files: usize,
+    ) {
+        self.update(project_name, |stats| {
+            stats.extracted_files += extracted_files;
+            stats.skipped_files += skipped_files;
+        });
+    }
+
+    pub(crate) fn record_artifact_bytes(&self, project_name: ProjectName, bytes: usize) {
+        self.update(project_name, |stats| stats.artifact_bytes += bytes);
+    }
+
+    fn record_duration(&self, project_name: ProjectName, phase: &'static str, duration: Duration) {
+        self.update(project_name, |stats| {
+            *stats.phase_durations_ms.entry(phase).or_default() += duration.as_millis();
+        });
+    }
+
+    fn record_allocations(
+        &self,
+        project_name: ProjectName,
+        phase: &'static str,
+        allocations: PhaseAllocations,
+    ) {
+        self.update(project_name, |stats| {
+            let total = stats.phase_allocations.entry(phase).or_default();
+            total.allocations += allocations.allocations;
+            total.allocated_bytes += allocations.allocated_bytes;
+            total.retained_bytes = match (total.retained_bytes, allocations.retained_bytes) {
+                (Some(total), Some(retained)) => Some(total + retained),
+                (_, retained) => retained,
+            };
+        });
+    }
+
+    /// Forgets the statistics of the previous build, e.g. before each build
+    /// of watch mode.
+    pub(crate) fn clear(&self) {
+        self.projects.lock().unwrap().clear();
+    }
+
+    /// The statistics of each project, sorted by project name.
+    pub fn projects(&self) -> Vec<ProjectStats> {
+        let mut projects: Vec<ProjectStats> =
+            self.projects.lock().unwrap().values().cloned().collect();
+        projects.sort_by(|a, b| a.name.lookup().cmp(b.name.lookup()));
+        projects
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.projects()).unwrap()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut output = String::new();
+        for stats in self.projects() {
+            writeln!(output, "[{}]", stats.name).unwrap();
+            writeln!(
+                output,
+                "  {} operations, {} fragments",
+                stats.operations, stats.fragments
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "  {} artifacts, {} bytes",
+                stats.artifacts, stats.artifact_bytes
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "  {} persisted operations",
+                stats.persisted_operations
+            )
+            .unwrap();
+            if stats.extracted_files > 0 {
+                writeln!(
+                    output,
+                    "  {} of {} source files skipped without GraphQL",
+                    stats.skipped_files, stats.extracted_files
+                )
+                .unwrap();
+            }
+            if !stats.largest_operations.is_empty() {
+                writeln!(output, "  Largest operations:").unwrap();
+                for operation in &stats.largest_operations {
+                    writeln!(
+                        output,
+                        "    {}: {} bytes",
+                        operation.name, operation.text_bytes
+                    )
+                    .unwrap();
+                }
+            }
+            if stats.operation_text_bytes > 0 {
+                writeln!(
+                    output,
+                    "  Operation text: {} bytes pretty, {} bytes compact ({} bytes saved)",
+                    stats.operation_text_bytes,
+                    stats.compact_operation_text_bytes,
+                    stats
+                        .operation_text_bytes
+                        .saturating_sub(stats.compact_operation_text_bytes)
+                )
+                .unwrap();
+            }
+            if !stats.phase_durations_ms.is_empty() {
+                writeln!(output, "  Durations:").unwrap();
+                for (phase, duration) in &stats.phase_durations_ms {
+                    writeln!(output, "    {}: {}ms", phase, duration).unwrap();
+                }
+            }
+        }
+        output
+    }
+
+    /// The allocations of the phases of each project, and the peak memory
+    /// of the process.
+    pub fn to_memory_text(&self) -> String {
+        let mut output = String::new();
+        for stats in self.projects() {
+            writeln!(output, "[{}]", stats.name).unwrap();
+            for (phase, allocations) in &stats.phase_allocations {
+                write!(
+                    output,
+                    "  {}: {} allocations, {} allocated",
+                    phase,
+                    allocations.allocations,
+                    format_bytes(allocations.allocated_bytes)
+                )
+                .unwrap();
+                if let Some(retained_bytes) = allocations.retained_bytes {
+                    let sign = if retained_bytes < 0 { "-" } else { "" };
+                    write!(
+                        output,
+                        ", {}{} retained",
+                        sign,
+                        format_bytes(retained_bytes.unsigned_abs())
+                    )
+                    .unwrap();
+                }
+                writeln!(output).unwrap();
+            }
+        }
+        match peak_heap_bytes() {
+            Some(bytes) => writeln!(output, "Peak heap: {}", format_bytes(bytes)).unwrap(),
+            None => writeln!(
+                output,
+                "Peak heap: unknown, relay was built without the `memory_profile` feature"
+            )
+            .unwrap(),
+        }
+        match peak_rss_bytes() {
+            Some(bytes) => writeln!(output, "Peak RSS: {}", format_bytes(bytes)).unwrap(),
+            None => writeln!(output, "Peak RSS: unknown on this platform").unwrap(),
+        }
+        output
+    }
+}
+
+/// Records the durations of the build phases of each project in the build
+/// stats, and passes all events on to the wrapped logger.
+pub struct BuildStatsPerfLogger<T: PerfLogger> {
+    inner: T,
+    stats: Arc<BuildStats>,
+}
+
+impl<T: PerfLogger> BuildStatsPerfLogger<T> {
+    pub fn new(inner: T, stats: Arc<BuildStats>) -> Self {
+        Self { inner, stats }
+    }
+}
+
+impl<T: PerfLogger> PerfLogger for BuildStatsPerfLogger<T> {
+    type PerfLogEvent = BuildStatsPerfLogEvent<T::PerfLogEvent>;
+
+    fn create_event(&self, name: &'static str) -> Self::PerfLogEvent {
+        BuildStatsPerfLogEvent {
+            inner: self.inner.create_event(name),
+            stats: Arc::clone(&self.stats),
+            project_name: Mutex::new(None),
+            durations: Mutex::new(Vec::new()),
+            allocations: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+pub struct BuildStatsPerfLogEvent<T: PerfLogEvent> {
+    inner: T,
+    stats: Arc<BuildStats>,
+    /// Set by the `project` string of the event, which can be logged after
+    /// timers were started.
+    project_name: Mutex<Option<ProjectName>>,
+    durations: Mutex<Vec<(&'static str, Duration)>>,
+    allocations: Mutex<Vec<(&'static str, PhaseAllocations)>>,
+}
+
+impl<T: PerfLogEvent> PerfLogEvent for BuildStatsPerfLogEvent<T> {
+    type Timer = (T::Timer, &'static str, Instant, Option<AllocationCounts>);
+
+    fn number(&self, name: &'static str, number: usize) {
+        self.inner.number(name, number)
+    }
+
+    fn string(&self, name: &'static str, value: String) {
+        if name == "project" {
+            *self.project_name.lock().unwrap() = Some(value.as_str().intern());
+        }
+        self.inner.string(name, value)
+    }
+
+    fn start(&self, name: &'static str) -> Self::Timer {
+        let counts = is_counting().then(AllocationCounts::now);
+        (self.inner.start(name), name, Instant::now(), counts)
+    }
+
+    fn stop(&self, timer: Self::Timer) {
+        let (inner_timer, name, start, start_counts) = timer;
+        if PHASES.contains(&name) {
+            self.durations.lock().unwrap().push((name, start.elapsed()));
+            if let Some(start_counts) = start_counts {
+                self.allocations.lock().unwrap().push((
+                    name,
+                    PhaseAllocations::between(start_counts, AllocationCounts::now()),
+                ));
+            }
+        }
+        self.inner.stop(inner_timer)
+    }
+
+    fn complete(self) {
+        if let Some(project_name) = *self.project_name.lock().unwrap() {
+            for (phase, duration) in self.durations.lock().unwrap().drain(..) {
+                self.stats.record_duration(project_name, phase, duration);
+            }
+            for (phase, allocations) in self.allocations.lock().unwrap().drain(..) {
+                self.stats
+                    .record_allocations(project_name, phase, allocations);
+            }
+        }
+        self.inner.complete()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::NoopPerfLogger;
+
+    use super::*;
+
+    #[test]
+    fn records_phase_durations_per_project() {
+        let stats = Arc::new(BuildStats::default());
+        let perf_logger = BuildStatsPerfLogger::new(NoopPerfLogger, Arc::clone(&stats));
+        for project in ["web", "web", "native"] {
+            let event = perf_logger.create_event("build_project");
+            let timer = event.start("build_project_time");
+            event.string("project", project.to_string());
+            event.time("build_schema_time", || {});
+            event.time("some_transform", || {});
+            event.stop(timer);
+            event.complete();
+        }
+        perf_logger
+            .create_event("compiler_setup")
+            .time("build_schema_time", || {});
+
+        let projects = stats.projects();
+        let names: Vec<_> = projects.iter().map(|stats| stats.name.lookup()).collect();
+        assert_eq!(names, vec!["native", "web"]);
+        let phases: Vec<_> = projects[1].phase_durations_ms.keys().copied().collect();
+        assert_eq!(phases, vec!["build_project_time", "build_schema_time"]);
+    }
+
+    #[test]
+    fn reports_bytes_saved_by_compact_operation_text() {
+        let stats = BuildStats::default();
+        stats.update("web".intern(), |stats| {
+            stats.operation_text_bytes = 1200;
+            stats.compact_operation_text_bytes = 700;
+        });
+        stats.update("native".intern(), |_| {});
+
+        let text = stats.to_text();
+        let web_stats = text.split("[web]").nth(1).unwrap();
+        assert!(web_stats.contains("700 bytes compact (500 bytes saved)"));
+        assert_eq!(text.matches("Operation text: 1200 bytes pretty").count(), 1);
+    }
+
+    #[test]
+    fn prints_allocations_of_phases() {
+        let stats = BuildStats::default();
+        let allocations = PhaseAllocations {
+            allocations: 300,
+            allocated_bytes: 3 << 20,
+            retained_bytes: Some(-2048),
+        };
+        stats.record_allocations("web".intern(), "build_ir_time", allocations);
+        stats.record_allocations("web".intern(), "build_ir_time", allocations);
+
+        let text = stats.to_memory_text();
+        assert!(text.starts_with(
+            "[web]\n  build_ir_time: 600 allocations, 6.0MiB allocated, -4.0KiB retained\n"
+        ));
+        assert!(text.contains("Peak heap: "));
+    }
+}
@@ -0,0 +1,246 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Timers and numbers of a build in the Chrome trace event format, which can
+//! be opened with `chrome://tracing` or Perfetto, collected by
+//! `relay --profile`.
+
+use std::cmp::Reverse;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use common::PerfLogEvent;
+use common::PerfLogger;
+use serde::Serialize;
+
+/// A "complete" event of the trace format, i.e. a timer with its start and
+/// duration in microseconds, or a "counter" event for a logged number.
+#[derive(Debug, Serialize)]
+pub struct TraceEvent {
+    pub name: &'static str,
+    /// The name of the perf log event of the timer.
+    pub cat: &'static str,
+    pub ph: &'static str,
+    pub ts: u128,
+    pub dur: u128,
+    pub pid: u32,
+    pub tid: u64,
+    pub args: TraceEventArgs,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TraceEventArgs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// The number of a counter event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Trace<'a> {
+    trace_events: &'a [TraceEvent],
+    display_time_unit: &'static str,
+}
+
+pub struct ChromeTrace {
+    start: Instant,
+    start_time: SystemTime,
+    events: Mutex<Vec<TraceEvent>>,
+}
+
+impl Default for ChromeTrace {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            start_time: SystemTime::now(),
+            events: Default::default(),
+        }
+    }
+}
+
+impl ChromeTrace {
+    /// The wall clock time that the `ts` of the events are relative to.
+    pub fn start_time(&self) -> SystemTime {
+        self.start_time
+    }
+
+    /// The duration of the trace so far in microseconds.
+    pub fn elapsed_micros(&self) -> u128 {
+        self.start.elapsed().as_micros()
+    }
+
+    /// The events sorted by their start.
+    pub fn sorted_events(&self) -> MutexGuard<'_, Vec<TraceEvent>> {
+        let mut events = self.events.lock().unwrap();
+        // Timers that start at the same time are nested in the longer one.
+        events.sort_by_key(|event| (event.ts, Reverse(event.dur)));
+        events
+    }
+
+    pub fn to_json(&self) -> String {
+        let events = self.sorted_events();
+        serde_json::to_string(&Trace {
+            trace_events: &events,
+            display_time_unit: "ms",
+        })
+        .unwrap()
+    }
+}
+
+/// Each thread that starts a timer gets its own track in the trace, which
+/// shows how much of the build runs in parallel.
+fn current_thread_id() -> u64 {
+    static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+    }
+    THREAD_ID.with(|id| *id)
+}
+
+/// Records all timers in the trace, and passes all events on to the wrapped
+/// logger.
+pub struct ChromeTracePerfLogger<T: PerfLogger> {
+    inner: T,
+    trace: Arc<ChromeTrace>,
+}
+
+impl<T: PerfLogger> ChromeTracePerfLogger<T> {
+    pub fn new(inner: T, trace: Arc<ChromeTrace>) -> Self {
+        Self { inner, trace }
+    }
+}
+
+impl<T: PerfLogger> PerfLogger for ChromeTracePerfLogger<T> {
+    type PerfLogEvent = ChromeTracePerfLogEvent<T::PerfLogEvent>;
+
+    fn create_event(&self, name: &'static str) -> Self::PerfLogEvent {
+        ChromeTracePerfLogEvent {
+            inner: self.inner.create_event(name),
+            name,
+            trace: Arc::clone(&self.trace),
+            project: Mutex::new(None),
+            events: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+pub struct ChromeTracePerfLogEvent<T: PerfLogEvent> {
+    inner: T,
+    name: &'static str,
+    trace: Arc<ChromeTrace>,
+    /// Set by the `project` string of the event, which can be logged after
+    /// timers were started.
+    project: Mutex<Option<String>>,
+    events: Mutex<Vec<TraceEvent>>,
+}
+
+impl<T: PerfLogEvent> PerfLogEvent for ChromeTracePerfLogEvent<T> {
+    type Timer = (T::Timer, &'static str, Instant, u64);
+
+    fn number(&self, name: &'static str, number: usize) {
+        self.events.lock().unwrap().push(TraceEvent {
+            name,
+            cat: self.name,
+            ph: "C",
+            ts: self.trace.start.elapsed().as_micros(),
+            dur: 0,
+            pid: std::process::id(),
+            tid: current_thread_id(),
+            args: TraceEventArgs {
+                project: None,
+                value: Some(number),
+            },
+        });
+        self.inner.number(name, number)
+    }
+
+    fn string(&self, name: &'static str, value: String) {
+        if name == "project" {
+            *self.project.lock().unwrap() = Some(value.clone());
+        }
+        self.inner.string(name, value)
+    }
+
+    fn start(&self, name: &'static str) -> Self::Timer {
+        (
+            self.inner.start(name),
+            name,
+            Instant::now(),
+            current_thread_id(),
+        )
+    }
+
+    fn stop(&self, timer: Self::Timer) {
+        let (inner_timer, name, start, tid) = timer;
+        self.events.lock().unwrap().push(TraceEvent {
+            name,
+            cat: self.name,
+            ph: "X",
+            ts: start.duration_since(self.trace.start).as_micros(),
+            dur: start.elapsed().as_micros(),
+            pid: std::process::id(),
+            tid,
+            args: TraceEventArgs {
+                project: None,
+                value: None,
+            },
+        });
+        self.inner.stop(inner_timer)
+    }
+
+    fn complete(self) {
+        let project = self.project.lock().unwrap().take();
+        let mut events = self.events.lock().unwrap();
+        for event in events.iter_mut() {
+            event.args.project = project.clone();
+        }
+        self.trace.events.lock().unwrap().append(&mut events);
+        self.inner.complete()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::NoopPerfLogger;
+
+    use super::*;
+
+    #[test]
+    fn records_timers_with_project() {
+        let trace = Arc::new(ChromeTrace::default());
+        let perf_logger = ChromeTracePerfLogger::new(NoopPerfLogger, Arc::clone(&trace));
+        let event = perf_logger.create_event("build_project");
+        let timer = event.start("build_project_time");
+        event.time("build_schema_time", || {});
+        event.string("project", "web".to_string());
+        event.number("generated_artifacts", 3);
+        event.stop(timer);
+        event.complete();
+
+        let json: serde_json::Value = serde_json::from_str(&trace.to_json()).unwrap();
+        let events = json["traceEvents"].as_array().unwrap();
+        let names: Vec<_> = events.iter().map(|event| &event["name"]).collect();
+        assert_eq!(
+            names,
+            vec![
+                "build_project_time",
+                "build_schema_time",
+                "generated_artifacts"
+            ]
+        );
+        assert!(events.iter().all(|event| event["args"]["project"] == "web"));
+        assert_eq!(events[2]["ph"], "C");
+        assert_eq!(events[2]["args"]["value"], 3);
+    }
+}
@@ -5,33 +5,58 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::num::NonZeroUsize;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 
 use common::Diagnostic;
+use common::DiagnosticSeverity;
 use common::PerfLogEvent;
 use common::PerfLogger;
 use common::WithDiagnostics;
+use fnv::FnvHashMap;
 use futures::future::join_all;
 use graphql_watchman::WatchmanFileSourceSubscriptionNextChange;
 use log::debug;
 use log::info;
+use log::warn;
 use rayon::prelude::*;
+use relay_transforms::Programs;
+use schema::SDLSchema;
 use tokio::sync::Notify;
 use tokio::task;
 use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio::time::Instant;
 
+use crate::build_project::build_programs;
 use crate::build_project::build_project;
+use crate::build_project::build_schema;
 use crate::build_project::commit_project;
+use crate::build_project::get_project_asts;
+use crate::build_project::BuildProgramsOutput;
 use crate::build_project::BuildProjectFailure;
+use crate::build_project::ProjectAstData;
+use crate::build_project::SourceHashes;
 use crate::compiler_state::ArtifactMapKind;
 use crate::compiler_state::CompilerState;
+use crate::compiler_state::ProjectName;
 use crate::config::Config;
+use crate::deduplicate_diagnostics::deduplicate_diagnostics;
+use crate::deduplicate_diagnostics::deduplicate_project_errors;
+use crate::errors::BuildProjectError;
 use crate::errors::Error;
 use crate::errors::Result;
 use crate::file_source::FileSource;
 use crate::file_source::FileSourceSubscriptionNextChange;
 use crate::graphql_asts::GraphQLAsts;
+use crate::log_groups::with_log_group;
 use crate::red_to_green::RedToGreen;
+use crate::remote_schema::fetch_remote_schemas;
 use crate::FileSourceResult;
 
 pub struct Compiler<TPerfLogger>
@@ -51,18 +76,21 @@ impl<TPerfLogger: PerfLogger> Compiler<TPerfLogger> {
     }
 
     pub async fn compile(&self) -> Result<CompilerState> {
+        self.compile_with_diagnostics()
+            .await
+            .map(|result| result.item)
+    }
+
+    /// Same as `compile`, but also returns the non-fatal diagnostics of the
+    /// build after they have been passed to the status reporter.
+    pub async fn compile_with_diagnostics(&self) -> Result<WithDiagnostics<CompilerState>> {
         let setup_event = self.perf_logger.create_event("compiler_setup");
         self.config.status_reporter.build_starts();
         let result: Result<(CompilerState, Vec<Diagnostic>)> = async {
-            if let Some(initialize_resources) = &self.config.initialize_resources {
-                let timer = setup_event.start("load_resources");
-                initialize_resources();
-                setup_event.stop(timer);
+            let mut compiler_state = self.load_compiler_state(&setup_event).await?;
+            if let Some(affected_files) = &self.config.affected_files {
+                compiler_state.restrict_to_affected_files(affected_files);
             }
-            let file_source = FileSource::connect(&self.config, &setup_event).await?;
-            let mut compiler_state = file_source
-                .query(&setup_event, self.perf_logger.as_ref())
-                .await?;
 
             let diagnostics = self
                 .build_projects(&mut compiler_state, &setup_event)
@@ -71,6 +99,13 @@ impl<TPerfLogger: PerfLogger> Compiler<TPerfLogger> {
             Ok((compiler_state, diagnostics))
         }
         .await;
+        match &result {
+            Ok((_, non_fatal_diagnostics)) => {
+                setup_event.number("errors", 0);
+                setup_event.number("warnings", non_fatal_diagnostics.len());
+            }
+            Err(error) => setup_event.number("errors", error_count(error)),
+        }
         setup_event.complete();
 
         match result {
@@ -78,7 +113,10 @@ impl<TPerfLogger: PerfLogger> Compiler<TPerfLogger> {
                 self.config
                     .status_reporter
                     .build_completes(&non_fatal_diagnostics);
-                Ok(compiler_state)
+                Ok(WithDiagnostics {
+                    item: compiler_state,
+                    diagnostics: non_fatal_diagnostics,
+                })
             }
             Err(error) => {
                 self.config.status_reporter.build_errors(&error);
@@ -87,6 +125,141 @@ impl<TPerfLogger: PerfLogger> Compiler<TPerfLogger> {
         }
     }
 
+    /// Runs the parsing, IR building, validations and transforms of all
+    /// enabled projects without generating, persisting or writing artifacts.
+    /// Returns all diagnostics found in the documents, including the errors
+    /// that would fail a build. Other errors, e.g. for unreadable files, are
+    /// returned as `Err`.
+    pub async fn lint(&self) -> Result<Vec<Diagnostic>> {
+        let setup_event = self.perf_logger.create_event("compiler_lint");
+        let result = async {
+            let compiler_state = self.load_compiler_state(&setup_event).await?;
+            let graphql_asts = match parse_sources(&self.config, &compiler_state) {
+                Ok(graphql_asts) => graphql_asts,
+                Err(Error::DiagnosticsError { errors }) => return Ok(errors),
+                Err(error) => return Err(error),
+            };
+            let results = setup_event.time("build_programs_time", || {
+                build_enabled_programs(
+                    &self.config,
+                    Arc::clone(&self.perf_logger),
+                    &compiler_state,
+                    &graphql_asts,
+                )
+            });
+
+            let mut diagnostics = Vec::new();
+            let mut errors = Vec::new();
+            for (_, result) in results {
+                match result {
+                    Ok(result) => diagnostics.extend(result.diagnostics),
+                    Err(BuildProjectFailure::Error(BuildProjectError::ValidationErrors {
+                        errors,
+                        ..
+                    })) => diagnostics.extend(errors),
+                    Err(BuildProjectFailure::Error(error)) => errors.push(error),
+                    Err(BuildProjectFailure::Cancelled) => return Err(Error::Cancelled),
+                }
+            }
+            if !errors.is_empty() {
+                return Err(Error::BuildProjectsErrors { errors });
+            }
+            Ok(diagnostics)
+        }
+        .await;
+        setup_event.complete();
+        result
+    }
+
+    /// Builds the transformed `Programs` of all enabled projects without
+    /// generating, persisting or writing artifacts.
+    pub async fn programs(&self) -> Result<Vec<(ProjectName, Programs, Arc<SourceHashes>)>> {
+        let setup_event = self.perf_logger.create_event("compiler_programs");
+        let result = async {
+            let compiler_state = self.load_compiler_state(&setup_event).await?;
+            let graphql_asts = parse_sources(&self.config, &compiler_state)?;
+            let results = setup_event.time("build_programs_time", || {
+                build_enabled_programs(
+                    &self.config,
+                    Arc::clone(&self.perf_logger),
+                    &compiler_state,
+                    &graphql_asts,
+                )
+            });
+
+            let mut programs = Vec::new();
+            let mut errors = Vec::new();
+            for (project_name, result) in results {
+                match result {
+                    Ok(WithDiagnostics {
+                        item: (project_programs, source_hashes),
+                        ..
+                    }) => programs.push((project_name, project_programs, source_hashes)),
+                    Err(BuildProjectFailure::Error(error)) => errors.push(error),
+                    Err(BuildProjectFailure::Cancelled) => return Err(Error::Cancelled),
+                }
+            }
+            if !errors.is_empty() {
+                return Err(Error::BuildProjectsErrors { errors });
+            }
+            Ok(programs)
+        }
+        .await;
+        setup_event.complete();
+        result
+    }
+
+    /// Builds the schemas of all enabled projects, i.e. the server schema
+    /// extended with the client schema extensions and the types of Relay
+    /// Resolvers, without building the programs.
+    pub async fn schemas(&self) -> Result<Vec<(ProjectName, Arc<SDLSchema>)>> {
+        let setup_event = self.perf_logger.create_event("compiler_schemas");
+        let result = async {
+            let compiler_state = self.load_compiler_state(&setup_event).await?;
+            let graphql_asts = parse_sources(&self.config, &compiler_state)?;
+            let mut schemas = Vec::new();
+            let mut errors = Vec::new();
+            for project_config in self.config.enabled_projects() {
+                match build_schema(&self.config, &compiler_state, project_config, &graphql_asts) {
+                    Ok(schema) => schemas.push((project_config.name, schema)),
+                    Err(diagnostics) => errors.push(BuildProjectError::ValidationErrors {
+                        errors: diagnostics,
+                        project_name: project_config.name,
+                    }),
+                }
+            }
+            if !errors.is_empty() {
+                return Err(Error::BuildProjectsErrors { errors });
+            }
+            Ok(schemas)
+        }
+        .await;
+        setup_event.complete();
+        result
+    }
+
+    /// Loads the sources of all projects, without building them, e.g. to
+    /// benchmark the builds or to save the sources as a corpus.
+    pub async fn load_sources(&self) -> Result<CompilerState> {
+        let setup_event = self.perf_logger.create_event("compiler_load_sources");
+        let result = self.load_compiler_state(&setup_event).await;
+        setup_event.complete();
+        result
+    }
+
+    async fn load_compiler_state(&self, setup_event: &impl PerfLogEvent) -> Result<CompilerState> {
+        if let Some(initialize_resources) = &self.config.initialize_resources {
+            let timer = setup_event.start("load_resources");
+            initialize_resources();
+            setup_event.stop(timer);
+        }
+        fetch_remote_schemas(&self.config).await?;
+        let file_source = FileSource::connect(&self.config, setup_event).await?;
+        file_source
+            .query(setup_event, self.perf_logger.as_ref())
+            .await
+    }
+
     pub async fn watch(&self) -> Result<()> {
         'watch: loop {
             let setup_event = self.perf_logger.create_event("compiler_setup");
@@ -97,6 +270,7 @@ impl<TPerfLogger: PerfLogger> Compiler<TPerfLogger> {
                     initialize_resources();
                     setup_event.stop(timer);
                 }
+                fetch_remote_schemas(&self.config).await?;
                 let file_source = FileSource::connect(&self.config, &setup_event).await?;
 
                 let (compiler_state, mut subscription) = file_source
@@ -205,9 +379,19 @@ impl<TPerfLogger: PerfLogger> Compiler<TPerfLogger> {
                 return;
             }
 
-            // Single change to file sometimes produces 2 watchman change events for the same file
-            // wait for 50ms in case there is a subsequent request
-            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            // A single change to a file sometimes produces 2 watchman change
+            // events, and a save or checkout of many files produces many.
+            self.wait_for_changes_to_settle(&compiler_state, &notify_receiver)
+                .await;
+
+            if compiler_state.source_control_update_status.is_started() {
+                continue;
+            }
+
+            if compiler_state.source_control_update_status.is_completed() {
+                subscription_handle.abort();
+                return;
+            }
 
             if compiler_state.has_pending_file_source_changes() {
                 let incremental_build_event =
@@ -258,6 +442,43 @@ impl<TPerfLogger: PerfLogger> Compiler<TPerfLogger> {
         }
     }
 
+    /// Waits until no new changes were notified for the debounce window of
+    /// `Config::watch`, or until the changes were delayed for `max_wait_ms`.
+    async fn wait_for_changes_to_settle(
+        &self,
+        compiler_state: &CompilerState,
+        notify_receiver: &Notify,
+    ) {
+        let watch_config = &self.config.watch;
+        let max_wait = Duration::from_millis(watch_config.max_wait_ms);
+        let first_change = Instant::now();
+        let mut reported_count = None;
+        loop {
+            let count = compiler_state.pending_file_source_changes_count();
+            let debounce_ms = if count >= watch_config.large_change_file_count {
+                watch_config.large_change_debounce_ms
+            } else {
+                watch_config.debounce_ms
+            };
+            let window = Duration::from_millis(debounce_ms)
+                .min(max_wait.saturating_sub(first_change.elapsed()));
+            if timeout(window, notify_receiver.notified()).await.is_err()
+                || first_change.elapsed() >= max_wait
+                || compiler_state.source_control_update_status.is_completed()
+            {
+                break;
+            }
+            // The changes of a single save come in within the first window.
+            let count = compiler_state.pending_file_source_changes_count();
+            if first_change.elapsed() > Duration::from_millis(watch_config.debounce_ms)
+                && reported_count != Some(count)
+            {
+                self.config.status_reporter.changes_queued(count);
+                reported_count = Some(count);
+            }
+        }
+    }
+
     async fn build_projects(
         &self,
         compiler_state: &mut CompilerState,
@@ -294,10 +515,7 @@ async fn build_projects<TPerfLogger: PerfLogger + 'static>(
     compiler_state: &mut CompilerState,
 ) -> Result<Vec<Diagnostic>> {
     let mut graphql_asts = setup_event.time("parse_sources_time", || {
-        GraphQLAsts::from_graphql_sources_map(
-            &compiler_state.graphql_sources,
-            &compiler_state.get_dirty_definitions(&config),
-        )
+        parse_sources(&config, compiler_state)
     })?;
 
     if compiler_state.should_cancel_current_build() {
@@ -305,8 +523,8 @@ async fn build_projects<TPerfLogger: PerfLogger + 'static>(
         return Err(Error::Cancelled);
     }
 
-    let build_results: Vec<_> = config
-        .par_enabled_projects()
+    let projects_to_build: Vec<_> = config
+        .enabled_projects()
         .filter(|project_config| {
             if let Some(base) = project_config.base {
                 if compiler_state.project_has_pending_changes(base) {
@@ -315,19 +533,42 @@ async fn build_projects<TPerfLogger: PerfLogger + 'static>(
             }
             compiler_state.project_has_pending_changes(project_config.name)
         })
-        .map(|project_config| {
-            build_project(
+        .collect();
+    // With `max_errors`, the projects that didn't start building before
+    // that many errors were found are skipped.
+    let found_errors = AtomicUsize::new(0);
+    let build_results = par_map_bounded(
+        &projects_to_build,
+        config.max_parallel_projects,
+        |project_config| {
+            if let Some(max_errors) = config.max_errors {
+                if found_errors.load(Ordering::Relaxed) >= max_errors.get() {
+                    return None;
+                }
+            }
+            let result = build_project(
                 &config,
                 project_config,
                 compiler_state,
                 &graphql_asts,
                 Arc::clone(&perf_logger),
-            )
-        })
-        .collect();
+            );
+            let result_errors = match &result {
+                Ok(result) => result
+                    .diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.severity() == DiagnosticSeverity::ERROR)
+                    .count(),
+                Err(BuildProjectFailure::Error(error)) => project_error_count(error),
+                Err(BuildProjectFailure::Cancelled) => 0,
+            };
+            found_errors.fetch_add(result_errors, Ordering::Relaxed);
+            Some(result)
+        },
+    );
     let mut results = Vec::new();
     let mut errors = Vec::new();
-    for result in build_results {
+    for result in build_results.into_iter().flatten() {
         match result {
             Ok(result) => results.push(result),
             Err(error) => match error {
@@ -340,7 +581,7 @@ async fn build_projects<TPerfLogger: PerfLogger + 'static>(
     }
 
     if !errors.is_empty() {
-        return Err(Error::BuildProjectsErrors { errors });
+        return Err(build_projects_errors(&config, errors));
     }
 
     if compiler_state.should_cancel_current_build() {
@@ -412,6 +653,22 @@ async fn build_projects<TPerfLogger: PerfLogger + 'static>(
                     .insert(project_name, next_artifact_map);
                 compiler_state.schema_cache.insert(project_name, schema);
 
+                // With `keep_going`, the artifacts of the definitions without
+                // errors are written, but the build fails with the errors.
+                if config.keep_going {
+                    let (project_errors, warnings) = std::mem::take(diagnostics)
+                        .into_iter()
+                        .partition::<Vec<_>, _>(|diagnostic| {
+                            diagnostic.severity() == DiagnosticSeverity::ERROR
+                        });
+                    *diagnostics = warnings;
+                    if !project_errors.is_empty() {
+                        errors.push(BuildProjectError::ValidationErrors {
+                            errors: project_errors,
+                            project_name,
+                        });
+                    }
+                }
                 all_diagnostics.append(diagnostics);
             }
             Err(BuildProjectFailure::Error(error)) => {
@@ -424,12 +681,221 @@ async fn build_projects<TPerfLogger: PerfLogger + 'static>(
     }
 
     if !errors.is_empty() {
-        return Err(Error::BuildProjectsErrors { errors });
+        return Err(build_projects_errors(&config, errors));
     }
 
     if build_cancelled_during_commit {
         return Err(Error::Cancelled);
     }
 
+    deduplicate_diagnostics(&mut all_diagnostics);
     Ok(all_diagnostics)
 }
+
+fn parse_sources(
+    config: &Config,
+    compiler_state: &CompilerState,
+) -> Result<FnvHashMap<ProjectName, GraphQLAsts>> {
+    GraphQLAsts::from_graphql_sources_map(
+        config,
+        &compiler_state.graphql_sources,
+        &compiler_state.get_dirty_definitions(config),
+    )
+}
+
+/// The number of errors that failed a build, as reported by the status
+/// reporter.
+fn error_count(error: &Error) -> usize {
+    match error {
+        Error::DiagnosticsError { errors } => errors.len(),
+        Error::BuildProjectsErrors { errors } => errors.iter().map(project_error_count).sum(),
+        Error::Cancelled => 0,
+        _ => 1,
+    }
+}
+
+fn project_error_count(error: &BuildProjectError) -> usize {
+    match error {
+        BuildProjectError::ValidationErrors { errors, .. } => errors.len(),
+        BuildProjectError::PersistErrors { errors, .. } => errors.len(),
+        BuildProjectError::WriteFileError { .. } => 1,
+    }
+}
+
+/// The error of the failed projects, without duplicates and with at most
+/// `max_errors` errors.
+fn build_projects_errors(config: &Config, mut errors: Vec<BuildProjectError>) -> Error {
+    deduplicate_project_errors(&mut errors);
+    if let Some(max_errors) = config.max_errors {
+        let mut remaining = max_errors.get();
+        errors.retain_mut(|error| {
+            if remaining == 0 {
+                return false;
+            }
+            match error {
+                BuildProjectError::ValidationErrors { errors, .. } => errors.truncate(remaining),
+                BuildProjectError::PersistErrors { errors, .. } => errors.truncate(remaining),
+                BuildProjectError::WriteFileError { .. } => {}
+            }
+            remaining -= project_error_count(error);
+            true
+        });
+        if remaining == 0 {
+            warn!("Stopped after {} error(s).", max_errors);
+        }
+    }
+    Error::BuildProjectsErrors { errors }
+}
+
+/// Builds the programs of all enabled projects, regardless of pending changes.
+fn build_enabled_programs<TPerfLogger: PerfLogger + 'static>(
+    config: &Config,
+    perf_logger: Arc<TPerfLogger>,
+    compiler_state: &CompilerState,
+    graphql_asts: &FnvHashMap<ProjectName, GraphQLAsts>,
+) -> Vec<(
+    ProjectName,
+    std::result::Result<BuildProgramsOutput, BuildProjectFailure>,
+)> {
+    let projects: Vec<_> = config.enabled_projects().collect();
+    par_map_bounded(&projects, config.max_parallel_projects, |project_config| {
+        let log_event = perf_logger.create_event("build_programs");
+        log_event.string("project", project_config.name.to_string());
+        let result = build_schema(config, compiler_state, project_config, graphql_asts)
+            .map_err(|errors| BuildProjectError::ValidationErrors {
+                errors,
+                project_name: project_config.name,
+            })
+            .map_err(BuildProjectFailure::Error)
+            .and_then(|schema| {
+                let ProjectAstData {
+                    project_asts,
+                    base_fragment_names,
+                } = get_project_asts(&schema, graphql_asts, project_config)?;
+                build_programs(
+                    config,
+                    project_config,
+                    compiler_state,
+                    project_asts,
+                    base_fragment_names,
+                    schema,
+                    &log_event,
+                    Arc::clone(&perf_logger),
+                )
+            });
+        log_event.complete();
+        (project_config.name, result)
+    })
+}
+
+/// Maps all items in parallel, but with at most `max_parallelism` calls of
+/// `f` running at the same time. The calls can still use the global thread
+/// pool for their own parallel work. The log lines of each call are written
+/// together when it returns.
+fn par_map_bounded<T: Sync, R: Send>(
+    items: &[T],
+    max_parallelism: Option<NonZeroUsize>,
+    f: impl Fn(&T) -> R + Sync + Send,
+) -> Vec<R> {
+    if items.len() == 1 {
+        return vec![f(&items[0])];
+    }
+    let f = |item: &T| with_log_group(|| f(item));
+    let max_parallelism = match max_parallelism {
+        Some(max_parallelism) if max_parallelism.get() < items.len() => max_parallelism.get(),
+        _ => return items.par_iter().map(f).collect(),
+    };
+    let next_index = AtomicUsize::new(0);
+    let results = Mutex::new(items.iter().map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..max_parallelism {
+            scope.spawn(|| {
+                loop {
+                    let index = next_index.fetch_add(1, Ordering::Relaxed);
+                    match items.get(index) {
+                        Some(item) => {
+                            let result = f(item);
+                            results.lock().unwrap()[index] = Some(result);
+                        }
+                        None => break,
+                    }
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("Expected every item to be mapped."))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_source::FileListFileSourceResult;
+
+    #[test]
+    fn par_map_bounded_keeps_order_and_limit() {
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+        let items = (0..20).collect::<Vec<usize>>();
+        let results = par_map_bounded(&items, NonZeroUsize::new(3), |item| {
+            let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max_running.fetch_max(now_running, Ordering::SeqCst);
+            thread::sleep(std::time::Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+            item * 2
+        });
+        assert_eq!(
+            results,
+            items.iter().map(|item| item * 2).collect::<Vec<_>>()
+        );
+        assert!(max_running.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn batches_changes_until_they_settle() {
+        let config = Config::from_string_for_test(
+            r#"
+                {
+                    "sources": { "src": "app" },
+                    "projects": {
+                        "app": { "schema": "schema.graphql", "language": "typescript" }
+                    },
+                    "watch": { "debounceMs": 50, "maxWaitMs": 1000 }
+                }
+            "#,
+        )
+        .unwrap();
+        let compiler = Compiler::new(Arc::new(config), Arc::new(common::NoopPerfLogger));
+        let compiler_state = CompilerState::default();
+        let notify = Arc::new(Notify::new());
+
+        let pending_file_source_changes = Arc::clone(&compiler_state.pending_file_source_changes);
+        let notify_sender = Arc::clone(&notify);
+        task::spawn(async move {
+            // A change every 30ms for 300ms, e.g. of a checkout.
+            for _ in 0..10 {
+                pending_file_source_changes
+                    .write()
+                    .unwrap()
+                    .push(FileSourceResult::FileList(FileListFileSourceResult {
+                        files: vec![],
+                        resolved_root: Default::default(),
+                    }));
+                notify_sender.notify_one();
+                tokio::time::sleep(Duration::from_millis(30)).await;
+            }
+        });
+        notify.notified().await;
+        let start = Instant::now();
+        compiler
+            .wait_for_changes_to_settle(&compiler_state, &notify)
+            .await;
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(300), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(400), "{:?}", elapsed);
+    }
+}
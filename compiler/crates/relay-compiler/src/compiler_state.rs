@@ -22,24 +22,32 @@ use bincode::Options;
 use common::PerfLogEvent;
 use common::PerfLogger;
 use common::SourceLocationKey;
+use dashmap::DashMap;
 use dashmap::DashSet;
 use fnv::FnvBuildHasher;
 use fnv::FnvHashMap;
 use fnv::FnvHashSet;
 use graphql_ir::ExecutableDefinitionName;
+use graphql_ir_validations::SelectionConflictCache;
 use intern::string_key::StringKey;
+use log::debug;
 use rayon::prelude::*;
 use relay_config::SchemaConfig;
 use schema::SDLSchema;
+use schema_diff::definitions::AffectedSchemaMembers;
 use schema_diff::definitions::SchemaChange;
 use schema_diff::detect_changes;
+use schema_introspection::introspection_to_sdl;
+use schema_introspection::parse_introspection;
 use serde::Deserialize;
 use serde::Serialize;
 use zstd::stream::read::Decoder as ZstdDecoder;
 use zstd::stream::write::Encoder as ZstdEncoder;
 
 use crate::artifact_map::ArtifactMap;
+use crate::build_project::build_schema::build_schema_from_sources;
 use crate::config::Config;
+use crate::config::ProjectConfig;
 use crate::errors::Error;
 use crate::errors::Result;
 use crate::file_source::categorize_files;
@@ -53,6 +61,7 @@ use crate::file_source::LocatedDocblockSource;
 use crate::file_source::LocatedGraphQLSource;
 use crate::file_source::LocatedJavascriptSourceFeatures;
 use crate::file_source::SourceControlUpdateStatus;
+use crate::file_source::Vfs;
 
 /// Name of a compiler project.
 pub type ProjectName = StringKey;
@@ -273,6 +282,10 @@ pub struct CompilerState {
     #[serde(with = "clock_json_string")]
     pub clock: Option<Clock>,
     pub saved_state_version: String,
+    /// The definitions of each project without selection conflicts in
+    /// previous builds, which incremental builds don't validate again.
+    pub selection_conflict_caches:
+        DashMap<ProjectName, Arc<SelectionConflictCache>, FnvBuildHasher>,
     #[serde(skip)]
     pub dirty_artifact_paths: FnvHashMap<ProjectName, DashSet<PathBuf, FnvBuildHasher>>,
     #[serde(skip)]
@@ -304,6 +317,7 @@ impl CompilerState {
             match category {
                 FileGroup::Source { project_set } => {
                     let (graphql_sources, docblock_sources) = extract_sources(
+                        config,
                         &project_set,
                         files,
                         file_source_changes,
@@ -318,6 +332,7 @@ impl CompilerState {
                 }
                 FileGroup::Schema { project_set } => {
                     Self::process_schema_change(
+                        config.vfs.as_ref(),
                         file_source_changes,
                         files,
                         project_set,
@@ -326,6 +341,7 @@ impl CompilerState {
                 }
                 FileGroup::Extension { project_set } => {
                     Self::process_schema_change(
+                        config.vfs.as_ref(),
                         file_source_changes,
                         files,
                         project_set,
@@ -369,6 +385,30 @@ impl CompilerState {
                 .map_or(false, |sources| !sources.pending.is_empty())
     }
 
+    /// The cache of the selection conflict validation of the project. It's
+    /// cleared when the schema of the project or of its base project changes,
+    /// as the `StableNodeId`s of the definitions only cover the fields they
+    /// select.
+    pub fn selection_conflict_cache(
+        &self,
+        project_config: &ProjectConfig,
+    ) -> Arc<SelectionConflictCache> {
+        let cache = Arc::clone(
+            &self
+                .selection_conflict_caches
+                .entry(project_config.name)
+                .or_default(),
+        );
+        if self.project_has_pending_schema_changes(project_config.name)
+            || project_config
+                .base
+                .map_or(false, |base| self.project_has_pending_schema_changes(base))
+        {
+            cache.clear();
+        }
+        cache
+    }
+
     pub fn has_processed_changes(&self) -> bool {
         self.graphql_sources
             .values()
@@ -387,7 +427,81 @@ impl CompilerState {
                 .any(|sources| !sources.processed.is_empty())
     }
 
-    fn is_change_safe(&self, sources: &SchemaSources, schema_config: &SchemaConfig) -> bool {
+    /// Turns the initial build into an incremental build of the given files:
+    /// the sources of the other files are marked as processed, so that only
+    /// the definitions of affected files and the definitions related to them
+    /// are compiled. Projects with affected schema, extension or docblock
+    /// files keep all sources pending and are compiled in full.
+    pub fn restrict_to_affected_files(&mut self, affected_files: &FnvHashSet<PathBuf>) {
+        let project_names: Vec<ProjectName> = self
+            .graphql_sources
+            .keys()
+            .chain(self.schemas.keys())
+            .chain(self.extensions.keys())
+            .chain(self.docblocks.keys())
+            .copied()
+            .collect();
+        for project_name in project_names {
+            let has_affected_schema = [&self.schemas, &self.extensions]
+                .iter()
+                .filter_map(|sources| sources.get(&project_name))
+                .any(|sources| {
+                    sources
+                        .pending
+                        .keys()
+                        .any(|path| affected_files.contains(path))
+                })
+                || self
+                    .docblocks
+                    .get(&project_name)
+                    .into_iter()
+                    .any(|sources| {
+                        sources
+                            .pending
+                            .keys()
+                            .any(|path| affected_files.contains(path))
+                    });
+            if has_affected_schema {
+                continue;
+            }
+
+            for sources in [&mut self.schemas, &mut self.extensions] {
+                if let Some(sources) = sources.get_mut(&project_name) {
+                    sources.commit_pending_sources();
+                }
+            }
+            if let Some(sources) = self.docblocks.get_mut(&project_name) {
+                sources.commit_pending_sources();
+            }
+            if let Some(sources) = self.graphql_sources.get_mut(&project_name) {
+                let unaffected: Vec<PathBuf> = sources
+                    .pending
+                    .keys()
+                    .filter(|path| !affected_files.contains(*path))
+                    .cloned()
+                    .collect();
+                for path in unaffected {
+                    let value = sources.pending.remove(&path).unwrap();
+                    if !value.is_empty() {
+                        sources.processed.insert(path, value);
+                    }
+                }
+            }
+            // Without a mapping, committing the project would remove all
+            // artifacts that are not generated by this build.
+            if let Some(artifacts) = self.artifacts.get_mut(&project_name) {
+                if let ArtifactMapKind::Unconnected(_) = artifacts.as_ref() {
+                    *artifacts = Arc::new(ArtifactMapKind::Mapping(Default::default()));
+                }
+            }
+        }
+    }
+
+    fn get_schema_change_affected_members(
+        &self,
+        sources: &SchemaSources,
+        schema_config: &SchemaConfig,
+    ) -> Option<AffectedSchemaMembers> {
         let previous = sources
             .get_old_sources()
             .into_iter()
@@ -403,7 +517,7 @@ impl CompilerState {
         let schema_change = detect_changes(&current, &previous);
 
         if schema_change == SchemaChange::None {
-            true
+            Some(Default::default())
         } else {
             let current_sources_with_location = sources
                 .get_sources_with_location()
@@ -411,12 +525,19 @@ impl CompilerState {
                 .map(|(schema, location_key)| (schema.as_str(), location_key))
                 .collect::<Vec<_>>();
 
-            match relay_schema::build_schema_with_extensions(
+            match build_schema_from_sources(
+                schema_config,
                 &current_sources_with_location,
                 &Vec::<(&str, SourceLocationKey)>::new(),
             ) {
-                Ok(schema) => schema_change.is_safe(&schema, schema_config),
-                Err(_) => false,
+                Ok(schema) => {
+                    if schema_change.is_safe(&schema, schema_config) {
+                        Some(Default::default())
+                    } else {
+                        schema_change.get_affected_members(&schema, schema_config)
+                    }
+                }
+                Err(_) => None,
             }
         }
     }
@@ -442,22 +563,52 @@ impl CompilerState {
         project_name: StringKey,
         schema_config: &SchemaConfig,
     ) -> bool {
+        self.get_project_affected_schema_members(project_name, schema_config)
+            .map_or(true, |members| !members.is_empty())
+    }
+
+    /// The types and fields that the pending schema changes of the project
+    /// and its base project affect, so that only the documents that use them
+    /// have to be rebuilt in addition to the changed ones. Returns `None` if
+    /// every document has to be rebuilt, e.g. after changes to the schema
+    /// extensions.
+    pub fn get_affected_schema_members(
+        &self,
+        project_config: &ProjectConfig,
+    ) -> Option<AffectedSchemaMembers> {
+        let mut members = self.get_project_affected_schema_members(
+            project_config.name,
+            &project_config.schema_config,
+        )?;
+        if let Some(base) = project_config.base {
+            members.extend(
+                self.get_project_affected_schema_members(base, &project_config.schema_config)?,
+            );
+        }
+        Some(members)
+    }
+
+    fn get_project_affected_schema_members(
+        &self,
+        project_name: StringKey,
+        schema_config: &SchemaConfig,
+    ) -> Option<AffectedSchemaMembers> {
         if let Some(extension) = self.extensions.get(&project_name) {
             if !extension.pending.is_empty() {
-                return true;
+                return None;
             }
         }
         if let Some(docblocks) = self.docblocks.get(&project_name) {
             if !docblocks.pending.is_empty() {
-                return true;
+                return None;
             }
         }
-        if let Some(schema) = self.schemas.get(&project_name) {
-            if !(schema.pending.is_empty() || self.is_change_safe(schema, schema_config)) {
-                return true;
+        match self.schemas.get(&project_name) {
+            Some(schema) if !schema.pending.is_empty() => {
+                self.get_schema_change_affected_members(schema, schema_config)
             }
+            _ => Some(Default::default()),
         }
-        false
     }
 
     /// Merges pending changes from the file source into the compiler state.
@@ -485,6 +636,7 @@ impl CompilerState {
                         has_changed = true;
 
                         let (graphql_sources, docblock_sources) = extract_sources(
+                            config,
                             &project_set,
                             files,
                             &file_source_changes,
@@ -506,6 +658,7 @@ impl CompilerState {
                     FileGroup::Schema { project_set } => {
                         has_changed = true;
                         Self::process_schema_change(
+                            config.vfs.as_ref(),
                             &file_source_changes,
                             files,
                             project_set,
@@ -515,6 +668,7 @@ impl CompilerState {
                     FileGroup::Extension { project_set } => {
                         has_changed = true;
                         Self::process_schema_change(
+                            config.vfs.as_ref(),
                             &file_source_changes,
                             files,
                             project_set,
@@ -646,6 +800,17 @@ impl CompilerState {
         !self.pending_file_source_changes.read().unwrap().is_empty()
     }
 
+    /// The number of changed files that are not merged yet. A file can be
+    /// counted once per change.
+    pub fn pending_file_source_changes_count(&self) -> usize {
+        self.pending_file_source_changes
+            .read()
+            .unwrap()
+            .iter()
+            .map(|file_source_changes| file_source_changes.size())
+            .sum()
+    }
+
     fn set_pending_source_set(&mut self, project_name: ProjectName, source_set: &GraphQLSourceSet) {
         let entry = &mut self.graphql_sources.entry(project_name).or_default();
         entry.merge_pending_sources(source_set);
@@ -661,6 +826,7 @@ impl CompilerState {
     }
 
     fn process_schema_change(
+        vfs: &dyn Vfs,
         file_source_changes: &FileSourceResult,
         files: Vec<File>,
         project_set: ProjectSet,
@@ -671,7 +837,20 @@ impl CompilerState {
         for file in files {
             let file_name = file.name.clone();
             if file.exists {
-                added_sources.insert(file_name, read_file_to_string(file_source_changes, &file)?);
+                let mut content = read_file_to_string(vfs, file_source_changes, &file)?;
+                // Schemas can also be provided as the JSON result of an
+                // introspection query, which we convert to SDL here so that
+                // all later stages only ever deal with SDL.
+                if file_name.extension().map_or(false, |ext| ext == "json") {
+                    let introspection = parse_introspection(&content).map_err(|source| {
+                        Error::IntrospectionFileError {
+                            file: file_name.clone(),
+                            source,
+                        }
+                    })?;
+                    content = introspection_to_sdl(&introspection);
+                }
+                added_sources.insert(file_name, content);
             } else {
                 removed_sources.push(file_name);
             }
@@ -698,12 +877,15 @@ impl CompilerState {
 }
 
 fn extract_sources(
+    config: &Config,
     project_set: &ProjectSet,
     files: Vec<File>,
     file_source_changes: &FileSourceResult,
     preserve_empty: bool,
     perf_logger: &impl PerfLogger,
 ) -> Result<(GraphQLSourceSet, DocblockSourceSet)> {
+    let vfs = config.vfs.as_ref();
+    let extract_options = config.extract_options();
     let log_event = perf_logger.create_event("categorize");
     log_event.string("source_set_name", project_set.to_string());
     let extract_timer = log_event.start("extract_graphql_strings_from_file_time");
@@ -712,21 +894,43 @@ fn extract_sources(
         .par_iter()
         .map(|file| {
             if file.exists {
-                match extract_javascript_features_from_file(file_source_changes, file) {
+                match extract_javascript_features_from_file(
+                    vfs,
+                    &extract_options,
+                    file_source_changes,
+                    file,
+                ) {
                     Ok(features) => Ok((file, features)),
                     Err(err) => Err(err),
                 }
             } else {
-                Ok((file, LocatedJavascriptSourceFeatures::default()))
+                Ok((file, Some(LocatedJavascriptSourceFeatures::default())))
             }
         })
         .collect::<Result<Vec<_>>>()?;
 
     log_event.stop(extract_timer);
 
+    let extracted_files = files.iter().filter(|file| file.exists).count();
+    let skipped_files = source_features
+        .iter()
+        .filter(|(_, features)| features.is_none())
+        .count();
+    log_event.number("skipped_files_without_graphql", skipped_files);
+    debug!(
+        "Skipped {} of {} files without GraphQL in {}",
+        skipped_files, extracted_files, project_set
+    );
+    if let Some(build_stats) = &config.build_stats {
+        for project_name in project_set.iter() {
+            build_stats.record_extracted_files(*project_name, extracted_files, skipped_files);
+        }
+    }
+
     let mut graphql_sources: GraphQLSourceSet = FnvHashMap::default();
     let mut docblock_sources: DocblockSourceSet = FnvHashMap::default();
     for (file, features) in source_features {
+        let features = features.unwrap_or_default();
         if preserve_empty || !features.graphql_sources.is_empty() {
             graphql_sources.insert(file.name.clone(), features.graphql_sources);
         }
@@ -789,6 +993,10 @@ mod clock_json_string {
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
+    use intern::string_key::Intern;
+
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
 
@@ -855,4 +1063,60 @@ mod tests {
         // Pending for a should not be populated
         assert_eq!(incremental_source.pending.get(&a), None);
     }
+
+    #[test]
+    fn restrict_to_affected_files_processes_unaffected_sources() {
+        let project_name: ProjectName = "project".intern();
+        let source = |text: &str| {
+            vec![LocatedGraphQLSource {
+                index: 0,
+                graphql_source: graphql_syntax::GraphQLSource::new(text, 0, 0),
+            }]
+        };
+        let mut graphql_sources = GraphQLSources::default();
+        graphql_sources
+            .pending
+            .insert("a.js".into(), source("fragment a on User { id }"));
+        graphql_sources
+            .pending
+            .insert("b.js".into(), source("query b { me { id } }"));
+        let mut schemas = SchemaSources::default();
+        schemas
+            .pending
+            .insert("schema.graphql".into(), "type Query { id: ID }".to_string());
+
+        let mut compiler_state = CompilerState::default();
+        compiler_state
+            .graphql_sources
+            .insert(project_name, graphql_sources);
+        compiler_state.schemas.insert(project_name, schemas);
+        compiler_state.artifacts.insert(
+            project_name,
+            Arc::new(ArtifactMapKind::Unconnected(Default::default())),
+        );
+
+        let mut affected_files = FnvHashSet::default();
+        affected_files.insert(PathBuf::from("a.js"));
+        compiler_state.restrict_to_affected_files(&affected_files);
+
+        let graphql_sources = &compiler_state.graphql_sources[&project_name];
+        assert!(graphql_sources.pending.contains_key(Path::new("a.js")));
+        assert!(graphql_sources.processed.contains_key(Path::new("b.js")));
+        assert!(compiler_state.schemas[&project_name].pending.is_empty());
+        assert!(matches!(
+            compiler_state.artifacts[&project_name].as_ref(),
+            ArtifactMapKind::Mapping(_)
+        ));
+
+        // Projects with affected schema files are compiled in full.
+        affected_files.insert(PathBuf::from("schema.graphql"));
+        let mut compiler_state = CompilerState::default();
+        let mut schemas = SchemaSources::default();
+        schemas
+            .pending
+            .insert("schema.graphql".into(), "type Query { id: ID }".to_string());
+        compiler_state.schemas.insert(project_name, schemas);
+        compiler_state.restrict_to_affected_files(&affected_files);
+        assert!(!compiler_state.schemas[&project_name].pending.is_empty());
+    }
 }
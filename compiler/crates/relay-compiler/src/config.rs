@@ -8,6 +8,7 @@
 use std::env::current_dir;
 use std::ffi::OsStr;
 use std::fmt;
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -18,6 +19,7 @@ use common::FeatureFlags;
 use common::Rollout;
 use common::ScalarName;
 use dunce::canonicalize;
+use extract_graphql::ExtractOptions;
 use fnv::FnvBuildHasher;
 use fnv::FnvHashSet;
 use graphql_ir::OperationDefinition;
@@ -30,16 +32,23 @@ use log::warn;
 use persist_query::PersistError;
 use rayon::prelude::*;
 use regex::Regex;
+use relay_config::ConfigOverride;
 use relay_config::CustomScalarType;
 use relay_config::DiagnosticReportConfig;
+use relay_config::FeatureFlagOverrides;
+use relay_config::FilePattern;
 use relay_config::FlowTypegenConfig;
+use relay_config::GraphQLTagConfig;
 use relay_config::JsModuleFormat;
 pub use relay_config::LocalPersistConfig;
 use relay_config::ModuleImportConfig;
+use relay_config::OperationTextConfig;
 pub use relay_config::PersistConfig;
 pub use relay_config::ProjectConfig;
 pub use relay_config::RemotePersistConfig;
+pub use relay_config::RemoteSchemaConfig;
 use relay_config::SchemaConfig;
+use relay_config::SchemaLintConfig;
 pub use relay_config::SchemaLocation;
 use relay_config::TypegenConfig;
 pub use relay_config::TypegenLanguage;
@@ -57,12 +66,20 @@ use crate::build_project::artifact_writer::ArtifactFileWriter;
 use crate::build_project::artifact_writer::ArtifactWriter;
 use crate::build_project::generate_extra_artifacts::GenerateExtraArtifactsFn;
 use crate::build_project::AdditionalValidations;
+use crate::build_report::BuildReport;
+use crate::build_stats::BuildStats;
 use crate::compiler_state::CompilerState;
 use crate::compiler_state::ProjectName;
 use crate::compiler_state::ProjectSet;
+use crate::config_deserializer;
 use crate::errors::ConfigValidationError;
 use crate::errors::Error;
 use crate::errors::Result;
+use crate::file_source::FsVfs;
+use crate::file_source::Vfs;
+use crate::graphql_config::graphql_config_source;
+use crate::graphql_config::graphql_config_sources;
+use crate::graphql_config::relay_config_from_graphql_config;
 use crate::saved_state::SavedStateLoader;
 use crate::status_reporter::ConsoleStatusReporter;
 use crate::status_reporter::StatusReporter;
@@ -93,6 +110,8 @@ pub struct Config {
     /// Root directory of all projects to compile. Any other paths in the
     /// compiler should be relative to this root unless otherwise noted.
     pub root_dir: PathBuf,
+    /// The file this config was loaded from, if it was loaded from a file.
+    pub config_path: Option<PathBuf>,
     pub sources: FnvIndexMap<PathBuf, ProjectSet>,
     pub excludes: Vec<String>,
     pub projects: FnvIndexMap<ProjectName, ProjectConfig>,
@@ -113,6 +132,9 @@ pub struct Config {
     /// Path to which to write the output of the compilation
     pub artifact_writer: Box<dyn ArtifactWriter + Send + Sync>,
 
+    /// The file system that source and schema files are read from.
+    pub vfs: Arc<dyn Vfs>,
+
     /// Compile all files. Persist ids are still re-used unless
     /// `Config::repersist_operations` is also set.
     pub compile_everything: bool,
@@ -120,6 +142,65 @@ pub struct Config {
     /// Do not reuse persist ids from artifacts even if the text hash matches.
     pub repersist_operations: bool,
 
+    /// Fetch remote schemas again even if a cached copy already exists.
+    pub refresh_schemas: bool,
+
+    /// Also run the validations of `validate_pedantic`, which report likely
+    /// mistakes such as selections of deprecated fields.
+    pub pedantic_validations: bool,
+
+    /// Skip the operations and fragments with errors, and the ones that
+    /// spread them, and still generate the artifacts of the others. The
+    /// build fails with the errors of the skipped definitions.
+    pub keep_going: bool,
+
+    /// Stop building projects once this many errors were found, and report
+    /// only this many. `1` fails on the first error.
+    pub max_errors: Option<NonZeroUsize>,
+
+    /// If set, statistics of the build are collected in it.
+    pub build_stats: Option<Arc<BuildStats>>,
+
+    /// If set, the artifacts, persisted operations and caches of each build
+    /// are recorded in it, and written as a report with
+    /// `BuildReportStatusReporter`.
+    pub build_report: Option<Arc<BuildReport>>,
+
+    /// If set, only the documents in these files, relative to `root_dir`,
+    /// and the operations and fragments related to them by spreads are
+    /// validated and generated. Projects are compiled in full if one of
+    /// their schema files or Relay Resolvers changed.
+    pub affected_files: Option<FnvHashSet<PathBuf>>,
+
+    /// The maximum number of projects that are compiled at the same time.
+    /// If not set, all projects that need to be compiled run in parallel.
+    pub max_parallel_projects: Option<NonZeroUsize>,
+
+    /// The number of threads of the global thread pool, which is set up by
+    /// the `relay` binary before compiling. Defaults to one per core.
+    pub jobs: Option<NonZeroUsize>,
+
+    /// The maximum number of threads that write the artifacts of a project
+    /// at the same time.
+    pub artifact_write_concurrency: Option<NonZeroUsize>,
+
+    /// The maximum number of operations of a project that are persisted at
+    /// the same time, in addition to the `concurrency` of a remote persist
+    /// config.
+    pub persist_concurrency: Option<NonZeroUsize>,
+
+    /// How changes are batched into incremental builds in watch mode.
+    pub watch: WatchConfig,
+
+    /// If set, the spans and numbers of a build are exported to this
+    /// OpenTelemetry collector with `relay_compiler::otlp`.
+    pub otlp: Option<OtlpConfig>,
+
+    /// If set, the schemas of the projects are cached in this directory, to
+    /// be loaded instead of built on the next start while their schema
+    /// files are unchanged.
+    pub schema_cache_dir: Option<PathBuf>,
+
     pub saved_state_config: Option<ScmAwareClockData>,
     pub saved_state_loader: Option<Box<dyn SavedStateLoader + Send + Sync>>,
     pub saved_state_version: String,
@@ -164,6 +245,9 @@ pub enum FileSourceKind {
     /// This can be used to replace watchman queries
     External(PathBuf),
     WalkDir,
+    /// The exact list of source and schema files to compile, relative to
+    /// the root dir, instead of the files found in the source directories.
+    FileList(Vec<PathBuf>),
 }
 
 fn normalize_path_from_config(
@@ -191,7 +275,7 @@ impl From<SingleProjectConfigFile> for Config {
     fn from(config: SingleProjectConfigFile) -> Self {
         Self::from_struct(
             "/virtual/path".into(),
-            ConfigFile::SingleProject(config),
+            ConfigFile::SingleProject(Box::new(config)),
             false,
         )
         .unwrap()
@@ -206,32 +290,77 @@ impl Config {
                 LoaderSource::PackageJson("relay".to_string()),
                 LoaderSource::Json("relay.config.json".to_string()),
                 LoaderSource::Js("relay.config.js".to_string()),
+                LoaderSource::Js("relay.config.cjs".to_string()),
+                LoaderSource::Js("relay.config.mjs".to_string()),
+                LoaderSource::Js("relay.config.ts".to_string()),
             ],
+            &graphql_config_sources(),
         )
     }
 
     pub fn load(config_path: PathBuf) -> Result<Self> {
-        let loader = if config_path.extension() == Some(OsStr::new("js")) {
-            LoaderSource::Js(config_path.display().to_string())
-        } else if config_path.extension() == Some(OsStr::new("json")) {
-            LoaderSource::Json(config_path.display().to_string())
-        } else {
-            return Err(Error::ConfigError {
-                details: format!(
-                    "Invalid file extension. Expected `.js` or `.json`. Provided file \"{}\".",
-                    config_path.display()
-                ),
-            });
+        if let Some(loader) = graphql_config_source(&config_path) {
+            return Self::load_config(
+                &current_dir().expect("Unable to get current working directory."),
+                &[],
+                &[loader],
+            );
+        }
+        let loader = match config_path.extension().and_then(OsStr::to_str) {
+            Some("js" | "cjs" | "mjs" | "ts" | "cts" | "mts") => {
+                LoaderSource::Js(config_path.display().to_string())
+            }
+            Some("json") => LoaderSource::Json(config_path.display().to_string()),
+            _ => {
+                return Err(Error::ConfigError {
+                    details: format!(
+                        "Invalid file extension. Expected `.json`, `.js`, `.cjs`, `.mjs` or `.ts`. Provided file \"{}\".",
+                        config_path.display()
+                    ),
+                });
+            }
         };
         Self::load_config(
             &current_dir().expect("Unable to get current working directory."),
             &[loader],
+            &[],
         )
     }
 
-    fn load_config(start_dir: &Path, loaders_sources: &[LoaderSource]) -> Result<Self> {
-        match js_config_loader::load(start_dir, loaders_sources) {
-            Ok(Some(config)) => Self::from_struct(config.path, config.value, true),
+    /// Loads the first config of `loaders_sources`, or else the first
+    /// graphql-config file of `graphql_config_sources` with a Relay
+    /// extension.
+    fn load_config(
+        start_dir: &Path,
+        loaders_sources: &[LoaderSource],
+        graphql_config_sources: &[LoaderSource],
+    ) -> Result<Self> {
+        let loaded = js_config_loader::load(start_dir, loaders_sources).and_then(|config| {
+            if config.is_some() || graphql_config_sources.is_empty() {
+                return Ok(config);
+            }
+            js_config_loader::load::<GraphQLConfigFile>(start_dir, graphql_config_sources).map(
+                |config| {
+                    config.and_then(|config| {
+                        config.value.0.map(|value| js_config_loader::Config {
+                            path: config.path,
+                            value,
+                        })
+                    })
+                },
+            )
+        });
+        let loaders_sources = if loaders_sources.is_empty() {
+            graphql_config_sources
+        } else {
+            loaders_sources
+        };
+        match loaded {
+            Ok(Some(config)) => {
+                let mut loaded_config = Self::from_struct(config.path.clone(), config.value, true)?;
+                loaded_config.config_path = Some(config.path);
+                Ok(loaded_config)
+            }
             Ok(None) => Err(Error::ConfigError {
                 details: format!(
                     r#"
@@ -239,7 +368,8 @@ Configuration for Relay compiler not found.
 
 Please make sure that the configuration file is created in {}.
 
-You can also pass the path to the configuration file as `relay-compiler ./path-to-config/relay.json`.
+You can also pass the path to the configuration file as `relay-compiler ./path-to-config/relay.json`,
+or configure Relay in the `extensions.relay` section of a graphql-config file, like `.graphqlrc.yml`.
 
 Example file:
 {{
@@ -308,9 +438,18 @@ Example file:
         let projects = projects
             .into_iter()
             .map(|(project_name, config_file_project)| {
+                let mut remote_schema = None;
                 let schema_location =
                     match (config_file_project.schema, config_file_project.schema_dir) {
-                        (Some(schema_file), None) => Ok(SchemaLocation::File(schema_file)),
+                        (Some(ConfigFileSchema::Path(schema_file)), None) => {
+                            Ok(SchemaLocation::File(schema_file))
+                        }
+                        (Some(ConfigFileSchema::Remote(remote_schema_config)), None) => {
+                            let cache_file =
+                                remote_schema_config.cache_file_for_project(project_name);
+                            remote_schema = Some(*remote_schema_config);
+                            Ok(SchemaLocation::File(cache_file))
+                        }
                         (None, Some(schema_dir)) => Ok(SchemaLocation::Directory(schema_dir)),
                         _ => Err(Error::ConfigFileValidation {
                             config_path: config_path.clone(),
@@ -348,6 +487,18 @@ Example file:
                         }],
                     })?;
 
+                let overrides = config_file_project
+                    .overrides
+                    .into_iter()
+                    .map(|config_file_override| {
+                        config_file_override.into_config_override(project_name)
+                    })
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|error| Error::ConfigFileValidation {
+                        config_path: config_path.clone(),
+                        validation_errors: vec![error],
+                    })?;
+
                 let project_config = ProjectConfig {
                     name: project_name,
                     base: config_file_project.base,
@@ -358,6 +509,7 @@ Example file:
                     shard_output: config_file_project.shard_output,
                     shard_strip_regex,
                     schema_location,
+                    remote_schema,
                     schema_config: config_file_project.schema_config,
                     typegen_config: config_file_project.typegen_config,
                     persist: config_file_project.persist,
@@ -374,7 +526,12 @@ Example file:
                     rollout: config_file_project.rollout,
                     js_module_format: config_file_project.js_module_format,
                     module_import_config: config_file_project.module_import_config,
+                    graphql_tag_config: config_file_project.graphql_tag_config,
+                    operation_text_config: config_file_project.operation_text_config,
+                    schema_lint_config: config_file_project.schema_lint_config,
+                    standalone_documents: config_file_project.standalone_documents,
                     diagnostic_report_config: config_file_project.diagnostic_report_config,
+                    overrides,
                 };
                 Ok((project_name, project_config))
             })
@@ -387,15 +544,21 @@ Example file:
         } else {
             config_file_dir.to_owned()
         };
+        let schema_cache_dir = config_file
+            .schema_cache_dir
+            .map(|schema_cache_dir| root_dir.join(schema_cache_dir));
 
         let config = Self {
             name: config_file.name,
             artifact_writer: Box::new(ArtifactFileWriter::new(None, root_dir.clone())),
+            vfs: Arc::new(FsVfs),
             status_reporter: Box::new(ConsoleStatusReporter::new(
                 root_dir.clone(),
                 is_multi_project,
+                extract_options(projects.values()),
             )),
             root_dir,
+            config_path: None,
             sources: config_file.sources,
             excludes: config_file.excludes,
             projects,
@@ -410,6 +573,20 @@ Example file:
             create_operation_persister: None,
             compile_everything: false,
             repersist_operations: false,
+            refresh_schemas: false,
+            pedantic_validations: false,
+            keep_going: false,
+            max_errors: None,
+            build_stats: None,
+            build_report: None,
+            affected_files: None,
+            max_parallel_projects: config_file.max_parallel_projects,
+            jobs: config_file.jobs,
+            watch: config_file.watch,
+            artifact_write_concurrency: config_file.artifact_write_concurrency,
+            persist_concurrency: config_file.persist_concurrency,
+            otlp: config_file.otlp,
+            schema_cache_dir,
             post_artifacts_write: None,
             additional_validations: None,
             is_dev_variable_name: config_file.is_dev_variable_name,
@@ -450,6 +627,13 @@ Example file:
             .filter(|project_config| project_config.enabled)
     }
 
+    /// The template literals to extract GraphQL from. The tags of all of the
+    /// projects are recognized in every file, so that the embedded sources of
+    /// a file are numbered the same way wherever they are extracted.
+    pub fn extract_options(&self) -> ExtractOptions {
+        extract_options(self.projects.values())
+    }
+
     /// Validated internal consistency of the config.
     fn validate_consistency(&self, errors: &mut Vec<ConfigValidationError>) {
         let mut project_names = FnvHashSet::default();
@@ -481,6 +665,18 @@ Example file:
                     })
                 }
             }
+
+            if project_config
+                .typegen_config
+                .experimental_emit_semantic_nullability_types
+                && !project_config.schema_config.semantic_non_null_support
+            {
+                errors.push(
+                    ConfigValidationError::SemanticNullabilityTypesWithoutSchemaSupport {
+                        project_name,
+                    },
+                );
+            }
         }
     }
 
@@ -510,6 +706,9 @@ Example file:
 
         for (&project_name, project) in &self.projects {
             match &project.schema_location {
+                // Remote schemas are only written to the cache file when they
+                // are fetched.
+                SchemaLocation::File(_) if project.remote_schema.is_some() => {}
                 SchemaLocation::File(schema_file) => {
                     let abs_schema_file = self.root_dir.join(schema_file);
                     if !abs_schema_file.exists() {
@@ -549,10 +748,25 @@ impl fmt::Debug for Config {
             name,
             artifact_writer: _,
             root_dir,
+            config_path,
             sources,
             excludes,
             compile_everything,
             repersist_operations,
+            refresh_schemas,
+            pedantic_validations,
+            keep_going,
+            max_errors,
+            build_stats,
+            build_report,
+            affected_files,
+            max_parallel_projects,
+            jobs,
+            artifact_write_concurrency,
+            persist_concurrency,
+            watch,
+            otlp,
+            schema_cache_dir,
             projects,
             header,
             codegen_command,
@@ -573,10 +787,25 @@ impl fmt::Debug for Config {
         f.debug_struct("Config")
             .field("name", name)
             .field("root_dir", root_dir)
+            .field("config_path", config_path)
             .field("sources", sources)
             .field("excludes", excludes)
             .field("compile_everything", compile_everything)
             .field("repersist_operations", repersist_operations)
+            .field("refresh_schemas", refresh_schemas)
+            .field("pedantic_validations", pedantic_validations)
+            .field("keep_going", keep_going)
+            .field("max_errors", max_errors)
+            .field("build_stats", &build_stats.is_some())
+            .field("build_report", &build_report.is_some())
+            .field("affected_files", affected_files)
+            .field("max_parallel_projects", max_parallel_projects)
+            .field("jobs", jobs)
+            .field("artifact_write_concurrency", artifact_write_concurrency)
+            .field("persist_concurrency", persist_concurrency)
+            .field("watch", watch)
+            .field("otlp", otlp)
+            .field("schema_cache_dir", schema_cache_dir)
             .field("projects", projects)
             .field("header", header)
             .field("codegen_command", codegen_command)
@@ -603,6 +832,23 @@ impl fmt::Debug for Config {
     }
 }
 
+fn extract_options<'a>(projects: impl Iterator<Item = &'a ProjectConfig>) -> ExtractOptions {
+    let mut options = ExtractOptions {
+        tag_names: vec![],
+        comment_annotated_templates: false,
+    };
+    for project_config in projects {
+        let graphql_tag_config = &project_config.graphql_tag_config;
+        for tag_name in &graphql_tag_config.tag_names {
+            if !options.tag_names.contains(tag_name) {
+                options.tag_names.push(tag_name.clone());
+            }
+        }
+        options.comment_annotated_templates |= graphql_tag_config.comment_annotated_templates;
+    }
+    options
+}
+
 fn get_default_excludes() -> Vec<String> {
     vec![
         "**/node_modules/**".to_string(),
@@ -652,6 +898,114 @@ struct MultiProjectConfigFile {
 
     /// Then name of the global __DEV__ variable to use in generated artifacts
     is_dev_variable_name: Option<String>,
+
+    /// The maximum number of projects that are compiled at the same time.
+    /// Defaults to compiling all projects in parallel.
+    #[serde(default)]
+    max_parallel_projects: Option<NonZeroUsize>,
+
+    /// The number of threads to compile with. Defaults to one per core.
+    #[serde(default)]
+    jobs: Option<NonZeroUsize>,
+
+    /// The maximum number of threads that write the artifacts of a project
+    /// at the same time.
+    #[serde(default)]
+    artifact_write_concurrency: Option<NonZeroUsize>,
+
+    /// The maximum number of operations of a project that are persisted at
+    /// the same time.
+    #[serde(default)]
+    persist_concurrency: Option<NonZeroUsize>,
+
+    /// How changes are batched into incremental builds in watch mode.
+    #[serde(default)]
+    watch: WatchConfig,
+
+    /// OpenTelemetry collector to export the spans and numbers of builds to.
+    #[serde(default)]
+    otlp: Option<OtlpConfig>,
+
+    /// Directory, relative to the root, to cache the built schemas in.
+    #[serde(default)]
+    schema_cache_dir: Option<PathBuf>,
+}
+
+/// In watch mode, an incremental build starts once no files changed for the
+/// debounce window, so that the many changes of a rapid series of saves or
+/// of a `git checkout` are compiled together.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase", default)]
+pub struct WatchConfig {
+    /// The time without changes, in milliseconds, to wait for before a
+    /// build starts.
+    pub debounce_ms: u64,
+
+    /// The longest time, in milliseconds, that a build is delayed by
+    /// changes that keep coming in.
+    pub max_wait_ms: u64,
+
+    /// At this number of queued changed files, e.g. of a checkout, the
+    /// longer `large_change_debounce_ms` window is used.
+    pub large_change_file_count: usize,
+
+    /// The debounce window, in milliseconds, of large changes.
+    pub large_change_debounce_ms: u64,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 50,
+            max_wait_ms: 2000,
+            large_change_file_count: 100,
+            large_change_debounce_ms: 500,
+        }
+    }
+}
+
+/// An OpenTelemetry collector that accepts OTLP over HTTP with JSON.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct OtlpConfig {
+    /// Base URL of the collector, e.g. `http://localhost:4318`. The traces
+    /// and metrics are sent to `/v1/traces` and `/v1/metrics`.
+    pub endpoint: String,
+
+    /// The `service.name` of the exported resource.
+    #[serde(default = "get_default_otlp_service_name")]
+    pub service_name: String,
+
+    /// Additional headers to send, e.g. for authentication.
+    #[serde(default)]
+    pub headers: FnvIndexMap<String, String>,
+}
+
+fn get_default_otlp_service_name() -> String {
+    "relay-compiler".to_string()
+}
+
+impl OtlpConfig {
+    /// The collector configured by the standard `OTEL_EXPORTER_OTLP_ENDPOINT`,
+    /// `OTEL_EXPORTER_OTLP_HEADERS` and `OTEL_SERVICE_NAME` environment
+    /// variables, used if the config file has no `otlp` section.
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok()?;
+        let headers = std::env::var("OTEL_EXPORTER_OTLP_HEADERS")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|header| {
+                let (key, value) = header.split_once('=')?;
+                Some((key.trim().to_string(), value.trim().to_string()))
+            })
+            .collect();
+        Some(Self {
+            endpoint,
+            service_name: std::env::var("OTEL_SERVICE_NAME")
+                .unwrap_or_else(|_| get_default_otlp_service_name()),
+            headers,
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -660,8 +1014,10 @@ pub struct SingleProjectConfigFile {
     #[serde(skip)]
     pub project_name: StringKey,
 
-    /// Path to schema.graphql
-    pub schema: PathBuf,
+    /// Path to schema.graphql or to a JSON file with the result of an
+    /// introspection query, or the URL and headers of a GraphQL endpoint
+    /// to fetch the schema from.
+    pub schema: ConfigFileSchema,
 
     /// Root directory of application code
     pub src: PathBuf,
@@ -724,6 +1080,26 @@ pub struct SingleProjectConfigFile {
     #[serde(default)]
     pub module_import_config: ModuleImportConfig,
 
+    /// The tags of the template literals containing GraphQL, and whether
+    /// template literals annotated with `/* GraphQL */` contain GraphQL.
+    #[serde(default)]
+    pub graphql_tag_config: GraphQLTagConfig,
+
+    /// Whether the operation text in the generated artifacts has its
+    /// arguments, directives and variable definitions sorted by name.
+    #[serde(default)]
+    pub operation_text_config: OperationTextConfig,
+
+    /// The rules of `relay-compiler schema-lint`.
+    #[serde(default)]
+    pub schema_lint_config: SchemaLintConfig,
+
+    /// The `*.graphql` and `*.gql` files in the sources are documents of
+    /// operations and fragments, which may `#import` the files of the
+    /// fragments they spread.
+    #[serde(default)]
+    pub standalone_documents: bool,
+
     /// Added in 13.1.1 to customize Final/Compat mode in the single project config file
     /// Removed in 14.0.0
     #[serde(default)]
@@ -731,6 +1107,33 @@ pub struct SingleProjectConfigFile {
 
     #[serde(default)]
     pub feature_flags: Option<FeatureFlags>,
+
+    /// Settings that apply to the documents in some of the files, e.g. to
+    /// roll out a feature flag per directory. The `files` are relative to the
+    /// current directory, like `src`.
+    #[serde(default)]
+    pub overrides: Vec<ConfigFileOverride>,
+
+    /// The number of threads to compile with. Defaults to one per core.
+    pub jobs: Option<NonZeroUsize>,
+
+    /// The maximum number of threads that write the artifacts at the same
+    /// time.
+    pub artifact_write_concurrency: Option<NonZeroUsize>,
+
+    /// The maximum number of operations that are persisted at the same time.
+    pub persist_concurrency: Option<NonZeroUsize>,
+
+    /// How changes are batched into incremental builds in watch mode.
+    pub watch: WatchConfig,
+
+    /// OpenTelemetry collector to export the spans and numbers of builds to.
+    #[serde(default)]
+    pub otlp: Option<OtlpConfig>,
+
+    /// Directory to cache the built schema in.
+    #[serde(default)]
+    pub schema_cache_dir: Option<PathBuf>,
 }
 
 impl Default for SingleProjectConfigFile {
@@ -756,6 +1159,17 @@ impl Default for SingleProjectConfigFile {
             typegen_phase: None,
             feature_flags: None,
             module_import_config: Default::default(),
+            graphql_tag_config: Default::default(),
+            operation_text_config: Default::default(),
+            schema_lint_config: Default::default(),
+            standalone_documents: false,
+            overrides: vec![],
+            jobs: None,
+            artifact_write_concurrency: None,
+            persist_concurrency: None,
+            watch: Default::default(),
+            otlp: None,
+            schema_cache_dir: None,
         }
     }
 }
@@ -780,14 +1194,14 @@ impl SingleProjectConfigFile {
                 source_dir: self.src.clone(),
             }
         })?);
-        paths.push(
-            canonicalize(root_dir.join(self.schema.clone())).map_err(|_| {
+        if let ConfigFileSchema::Path(schema) = &self.schema {
+            paths.push(canonicalize(root_dir.join(schema)).map_err(|_| {
                 ConfigValidationError::SchemaFileNotExistent {
                     project_name: self.project_name,
-                    schema_file: self.schema.clone(),
+                    schema_file: schema.clone(),
                 }
-            })?,
-        );
+            })?);
+        }
         for extension_dir in self.schema_extensions.iter() {
             paths.push(
                 canonicalize(root_dir.join(extension_dir.clone())).map_err(|_| {
@@ -846,15 +1260,43 @@ impl SingleProjectConfigFile {
             }
         )?;
 
+        // Paths of the single project config are relative to the current
+        // directory, while the overrides are matched against paths relative
+        // to the root.
+        let current_dir_from_root = canonicalize(&current_dir)
+            .ok()
+            .and_then(|dir| {
+                dir.strip_prefix(&common_root_dir)
+                    .ok()
+                    .map(Path::to_path_buf)
+            })
+            .unwrap_or_default();
+
         let project_config = ConfigFileProject {
             output: self.artifact_directory.map(|dir| {
                 normalize_path_from_config(current_dir.clone(), common_root_dir.clone(), dir)
             }),
-            schema: Some(normalize_path_from_config(
-                current_dir.clone(),
-                common_root_dir.clone(),
-                self.schema,
-            )),
+            schema: Some(match self.schema {
+                ConfigFileSchema::Path(schema) => {
+                    ConfigFileSchema::Path(normalize_path_from_config(
+                        current_dir.clone(),
+                        common_root_dir.clone(),
+                        schema,
+                    ))
+                }
+                ConfigFileSchema::Remote(remote_schema) => {
+                    ConfigFileSchema::Remote(Box::new(RemoteSchemaConfig {
+                        cache_file: remote_schema.cache_file.map(|cache_file| {
+                            normalize_path_from_config(
+                                current_dir.clone(),
+                                common_root_dir.clone(),
+                                cache_file,
+                            )
+                        }),
+                        ..*remote_schema
+                    }))
+                }
+            }),
             schema_config: self.schema_config,
             schema_extensions: self
                 .schema_extensions
@@ -881,12 +1323,37 @@ impl SingleProjectConfigFile {
             js_module_format: self.js_module_format,
             feature_flags: self.feature_flags,
             module_import_config: self.module_import_config,
+            graphql_tag_config: self.graphql_tag_config,
+            operation_text_config: self.operation_text_config,
+            schema_lint_config: self.schema_lint_config,
+            standalone_documents: self.standalone_documents,
+            overrides: self
+                .overrides
+                .into_iter()
+                .map(|config_file_override| ConfigFileOverride {
+                    files: config_file_override
+                        .files
+                        .iter()
+                        .map(|pattern| {
+                            current_dir_from_root
+                                .join(pattern)
+                                .to_string_lossy()
+                                .into_owned()
+                        })
+                        .collect(),
+                    ..config_file_override
+                })
+                .collect(),
             ..Default::default()
         };
 
         let mut projects = FnvIndexMap::default();
         projects.insert(self.project_name, project_config);
 
+        let schema_cache_dir = self
+            .schema_cache_dir
+            .map(|schema_cache_dir| current_dir.join(schema_cache_dir));
+
         let mut sources = FnvIndexMap::default();
         let src = normalize_path_from_config(current_dir, common_root_dir.clone(), self.src);
 
@@ -899,6 +1366,12 @@ impl SingleProjectConfigFile {
             excludes: self.excludes,
             is_dev_variable_name: self.is_dev_variable_name,
             codegen_command: self.codegen_command,
+            jobs: self.jobs,
+            artifact_write_concurrency: self.artifact_write_concurrency,
+            persist_concurrency: self.persist_concurrency,
+            watch: self.watch,
+            otlp: self.otlp,
+            schema_cache_dir,
             ..Default::default()
         })
     }
@@ -909,7 +1382,7 @@ impl SingleProjectConfigFile {
 enum ConfigFile {
     /// Base case configuration (mostly of OSS) where the project
     /// have single schema, and single source directory
-    SingleProject(SingleProjectConfigFile),
+    SingleProject(Box<SingleProjectConfigFile>),
     /// Relay can support multiple projects with multiple schemas
     /// and different options (output, typegen, etc...).
     /// This MultiProjectConfigFile is responsible for configuring
@@ -920,32 +1393,71 @@ enum ConfigFile {
 impl<'de> Deserialize<'de> for ConfigFile {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
         let value = Value::deserialize(deserializer)?;
-        match MultiProjectConfigFile::deserialize(value.clone()) {
-            Ok(config) => Ok(ConfigFile::MultiProject(Box::new(config))),
-            Err(multi_project_error) => match SingleProjectConfigFile::deserialize(value) {
-                Ok(single_project_config) => Ok(ConfigFile::SingleProject(single_project_config)),
-                Err(single_project_error) => {
-                    let error_message = format!(
-                        r#"The config file cannot be parsed as a multi-project config file due to:
-- {:?}.
-
-It also cannot be a single project config file due to:
-- {:?}."#,
-                        multi_project_error, single_project_error
-                    );
-
-                    Err(DeError::custom(error_message))
-                }
-            },
+        // Only multi-project configs have `sources` and `projects`, picking
+        // the format upfront allows reporting the error of that format.
+        let is_multi_project = value.get("sources").is_some() || value.get("projects").is_some();
+        let result = if is_multi_project {
+            config_deserializer::from_value(value)
+                .map(|config| ConfigFile::MultiProject(Box::new(config)))
+                .map_err(|error| {
+                    error.with_unknown_key_options(|path| match path {
+                        ["projects", _] => Some(serialized_keys::<ConfigFileProject>()),
+                        _ => None,
+                    })
+                })
+        } else {
+            config_deserializer::from_value(value)
+                .map(|config| ConfigFile::SingleProject(Box::new(config)))
+        };
+        result.map_err(DeError::custom)
+    }
+}
+
+/// A graphql-config file, which configures Relay if it has a Relay
+/// extension.
+struct GraphQLConfigFile(Option<ConfigFile>);
+
+impl<'de> Deserialize<'de> for GraphQLConfigFile {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        match relay_config_from_graphql_config(value).map_err(DeError::custom)? {
+            Some(value) => ConfigFile::deserialize(value)
+                .map(|config| GraphQLConfigFile(Some(config)))
+                .map_err(DeError::custom),
+            None => Ok(GraphQLConfigFile(None)),
         }
     }
 }
 
+/// The keys of a struct with `#[serde(flatten)]` fields, which serde doesn't
+/// expose otherwise.
+fn serialized_keys<T: Serialize + Default>() -> Vec<String> {
+    match serde_json::to_value(T::default()) {
+        Ok(Value::Object(object)) => object.into_iter().map(|(key, _)| key).collect(),
+        _ => vec![],
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ConfigFileSchema {
+    Path(PathBuf),
+    Remote(Box<RemoteSchemaConfig>),
+}
+
+impl Default for ConfigFileSchema {
+    fn default() -> Self {
+        ConfigFileSchema::Path(Default::default())
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Default)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct ConfigFileProject {
     /// If a base project is set, the documents of that project can be
-    /// referenced, but won't produce output artifacts.
+    /// referenced, but won't produce output artifacts. Artifacts of this
+    /// project import the artifacts of the base project from wherever the
+    /// base project generates them.
     /// Extensions from the base project will be added as well and the schema
     /// of the base project should be a subset of the schema of this project.
     #[serde(default)]
@@ -977,9 +1489,12 @@ pub struct ConfigFileProject {
     schema_extensions: Vec<PathBuf>,
 
     /// Path to the schema.graphql or a directory containing a schema broken up
-    /// in multiple *.graphql files.
+    /// in multiple *.graphql files. The schema file can also be a *.json file
+    /// with the result of an introspection query.
     /// Exactly 1 of these options needs to be defined.
-    schema: Option<PathBuf>,
+    /// Instead of a path, `schema` can also be the URL and headers of a
+    /// GraphQL endpoint to fetch the schema from.
+    schema: Option<ConfigFileSchema>,
     schema_dir: Option<PathBuf>,
 
     /// If this option is set, the compiler will persist queries using this
@@ -1019,8 +1534,77 @@ pub struct ConfigFileProject {
     #[serde(default)]
     pub module_import_config: ModuleImportConfig,
 
+    /// The tags of the template literals containing GraphQL, and whether
+    /// template literals annotated with `/* GraphQL */` contain GraphQL.
+    #[serde(default)]
+    pub graphql_tag_config: GraphQLTagConfig,
+
+    /// Whether the operation text in the generated artifacts has its
+    /// arguments, directives and variable definitions sorted by name.
+    #[serde(default)]
+    pub operation_text_config: OperationTextConfig,
+
+    /// The rules of `relay-compiler schema-lint`.
+    #[serde(default)]
+    pub schema_lint_config: SchemaLintConfig,
+
+    /// The `*.graphql` and `*.gql` files in the sources are documents of
+    /// operations and fragments, which may `#import` the files of the
+    /// fragments they spread.
+    #[serde(default)]
+    pub standalone_documents: bool,
+
     #[serde(default)]
     pub diagnostic_report_config: DiagnosticReportConfig,
+
+    /// Settings that apply to the documents in some of the files of the
+    /// project, e.g. to roll out a feature flag per directory. Later overrides
+    /// take precedence.
+    #[serde(default)]
+    pub overrides: Vec<ConfigFileOverride>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ConfigFileOverride {
+    /// Directories, files or glob patterns, relative to the root directory.
+    files: Vec<String>,
+
+    /// Feature flags that are checked per fragment or operation, which apply
+    /// to the fragments and operations defined in the files.
+    #[serde(default)]
+    feature_flags: FeatureFlagOverrides,
+
+    /// Merged over the `customScalarTypes` of the project, for the artifacts
+    /// of the documents defined in the files.
+    #[serde(default)]
+    custom_scalar_types: FnvIndexMap<ScalarName, CustomScalarType>,
+}
+
+impl ConfigFileOverride {
+    fn into_config_override(
+        self,
+        project_name: ProjectName,
+    ) -> std::result::Result<ConfigOverride, ConfigValidationError> {
+        let files = self
+            .files
+            .into_iter()
+            .map(|pattern| {
+                FilePattern::new(&pattern).map_err(|error| {
+                    ConfigValidationError::InvalidOverridePattern {
+                        project_name,
+                        pattern,
+                        error,
+                    }
+                })
+            })
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(ConfigOverride {
+            files,
+            feature_flags: self.feature_flags,
+            custom_scalar_types: self.custom_scalar_types,
+        })
+    }
 }
 
 pub type PersistId = String;
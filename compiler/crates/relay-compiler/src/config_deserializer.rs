@@ -0,0 +1,819 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A deserializer for config files parsed as `serde_json::Value` that keeps
+//! track of the path to the value that failed to deserialize, so errors can
+//! point to e.g. `projects.web.language` and list the allowed keys or values
+//! with a did-you-mean suggestion, instead of a bare serde message.
+//!
+//! String values can reference environment variables:
+//! - `${NAME}` is replaced with the value of `NAME`, it's an error if `NAME`
+//!   is not set.
+//! - `${NAME:-default}` uses `default` if `NAME` is not set or empty.
+//! - `${NAME:?message}` reports `message` if `NAME` is not set or empty.
+//! - `$${` is a literal `${`.
+//!
+//! Interpolated strings can also be used for booleans and numbers, e.g.
+//! `"${ENABLE_FOO:-false}"`. This doesn't apply to values that serde buffers
+//! before deserializing them, i.e. in `#[serde(flatten)]` fields and untagged
+//! enums.
+
+use std::env;
+use std::fmt;
+use std::fmt::Display;
+
+use intern::string_key::Intern;
+use intern::string_key::StringKey;
+use schema::suggestion_list::did_you_mean;
+use schema::suggestion_list::suggestion_list;
+use serde::de;
+use serde::de::DeserializeOwned;
+use serde::de::DeserializeSeed;
+use serde::de::Expected;
+use serde::de::IntoDeserializer;
+use serde::de::Unexpected;
+use serde::de::Visitor;
+use serde::forward_to_deserialize_any;
+use serde_json::Map;
+use serde_json::Value;
+
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Deserializes `T` from `value`. Errors contain the path to the offending
+/// value.
+pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, ConfigValueError> {
+    let root = value.clone();
+    T::deserialize(ValueDeserializer(value)).map_err(|error| error.resolve_with_value(&root))
+}
+
+const INTERPOLATION_START: &str = "${";
+const UNKNOWN_FIELD_PREFIX: &str = "unknown field `";
+const UNTAGGED_ENUM_PREFIX: &str = "data did not match any variant of untagged enum";
+
+#[derive(Debug)]
+pub struct ConfigValueError {
+    /// Path segments in reverse order, errors are created at the leaf value
+    /// and the segments are added while they bubble up.
+    reversed_path: Vec<PathSegment>,
+    kind: ConfigValueErrorKind,
+}
+
+#[derive(Debug)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug)]
+enum ConfigValueErrorKind {
+    UnknownKey {
+        key: String,
+        expected: Vec<String>,
+    },
+    UnknownValue {
+        value: String,
+        expected: &'static [&'static str],
+    },
+    InvalidType {
+        found: String,
+        expected: String,
+    },
+    InvalidValue {
+        found: String,
+        expected: String,
+    },
+    MissingKey {
+        key: &'static str,
+    },
+    MissingEnvVar {
+        name: String,
+        message: Option<String>,
+    },
+    Custom(String),
+}
+
+impl ConfigValueError {
+    fn new(kind: ConfigValueErrorKind) -> Self {
+        Self {
+            reversed_path: vec![],
+            kind,
+        }
+    }
+
+    fn at(mut self, segment: PathSegment) -> Self {
+        self.reversed_path.push(segment);
+        self
+    }
+
+    /// The path to the offending value, e.g. `projects.web.language`.
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        for segment in self.reversed_path.iter().rev() {
+            match segment {
+                PathSegment::Key(key) => {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                }
+                PathSegment::Index(index) => path.push_str(&format!("[{}]", index)),
+            }
+        }
+        path
+    }
+
+    fn value_at<'a>(&self, root: &'a Value) -> Option<&'a Value> {
+        let mut current = root;
+        for segment in self.reversed_path.iter().rev() {
+            current = match segment {
+                PathSegment::Key(key) => current.get(key),
+                PathSegment::Index(index) => current.get(index),
+            }?;
+        }
+        Some(current)
+    }
+
+    /// Adds details that serde doesn't provide:
+    /// - Values of `#[serde(flatten)]` fields are buffered by serde and
+    ///   deserialized without going through this deserializer, so errors in
+    ///   them point at the enclosing object. For unknown enum values the key
+    ///   can be recovered by looking for the offending value.
+    /// - Errors of untagged enums don't mention the value that was found.
+    fn resolve_with_value(mut self, root: &Value) -> Self {
+        let value = match self.value_at(root) {
+            Some(value) => value,
+            None => return self,
+        };
+        match (&mut self.kind, value) {
+            (ConfigValueErrorKind::UnknownValue { value: variant, .. }, Value::Object(object)) => {
+                let key = object.iter().find_map(|(key, field_value)| {
+                    let field_value = interpolate_env(field_value.as_str()?).ok()?;
+                    (field_value == *variant).then(|| key.clone())
+                });
+                if let Some(key) = key {
+                    self.reversed_path.insert(0, PathSegment::Key(key));
+                }
+            }
+            (ConfigValueErrorKind::Custom(message), value)
+                if message.starts_with(UNTAGGED_ENUM_PREFIX) && !value.is_object() =>
+            {
+                *message = format!("{}, found {}.", message, unexpected(value));
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Structs with `#[serde(flatten)]` fields report unknown keys without
+    /// the allowed keys. `keys_for_path` can provide them for the path of the
+    /// struct, e.g. `["projects", "web"]`.
+    pub fn with_unknown_key_options(
+        mut self,
+        keys_for_path: impl FnOnce(&[&str]) -> Option<Vec<String>>,
+    ) -> Self {
+        if let ConfigValueErrorKind::UnknownKey { expected, .. } = &mut self.kind {
+            if expected.is_empty() {
+                let path: Vec<&str> = self
+                    .reversed_path
+                    .iter()
+                    .rev()
+                    .map(|segment| match segment {
+                        PathSegment::Key(key) => key.as_str(),
+                        PathSegment::Index(_) => "",
+                    })
+                    .collect();
+                if let Some(keys) = keys_for_path(&path) {
+                    *expected = keys;
+                }
+            }
+        }
+        self
+    }
+}
+
+impl Display for ConfigValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path();
+        if path.is_empty() {
+            write!(f, "Invalid config: ")?;
+        } else {
+            write!(f, "Invalid config at `{}`: ", path)?;
+        }
+        match &self.kind {
+            ConfigValueErrorKind::UnknownKey { key, expected } => {
+                write!(f, "unknown key `{}`.", key)?;
+                write_suggestions(f, key, expected, "keys")
+            }
+            ConfigValueErrorKind::UnknownValue { value, expected } => {
+                write!(f, "unknown value `{}`.", value)?;
+                write_suggestions(f, value, expected, "values")
+            }
+            ConfigValueErrorKind::InvalidType { found, expected } => {
+                write!(f, "expected {}, found {}.", expected, found)
+            }
+            ConfigValueErrorKind::InvalidValue { found, expected } => {
+                write!(f, "invalid value {}, expected {}.", found, expected)
+            }
+            ConfigValueErrorKind::MissingKey { key } => {
+                write!(f, "missing required key `{}`.", key)
+            }
+            ConfigValueErrorKind::MissingEnvVar { name, message } => {
+                write!(f, "environment variable `{}` is not set", name)?;
+                match message {
+                    Some(message) => write!(f, ": {}", message),
+                    None => write!(f, "."),
+                }
+            }
+            ConfigValueErrorKind::Custom(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+fn write_suggestions<T: AsRef<str>>(
+    f: &mut fmt::Formatter<'_>,
+    input: &str,
+    expected: &[T],
+    kind: &str,
+) -> fmt::Result {
+    if expected.is_empty() {
+        return Ok(());
+    }
+    let options: Vec<StringKey> = expected
+        .iter()
+        .map(|option| option.as_ref().intern())
+        .collect();
+    write!(
+        f,
+        "{}",
+        did_you_mean(&suggestion_list(input.intern(), &options, MAX_SUGGESTIONS))
+    )?;
+    write!(
+        f,
+        " Allowed {} are: {}.",
+        kind,
+        expected
+            .iter()
+            .map(|option| format!("`{}`", option.as_ref()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+impl std::error::Error for ConfigValueError {}
+
+impl de::Error for ConfigValueError {
+    fn custom<T: Display>(message: T) -> Self {
+        let message = message.to_string();
+        // Structs with `#[serde(flatten)]` fields report unknown keys as a
+        // custom error.
+        if let Some(key) = message
+            .strip_prefix(UNKNOWN_FIELD_PREFIX)
+            .and_then(|rest| rest.strip_suffix('`'))
+        {
+            return Self::new(ConfigValueErrorKind::UnknownKey {
+                key: key.to_string(),
+                expected: vec![],
+            });
+        }
+        Self::new(ConfigValueErrorKind::Custom(message))
+    }
+
+    fn invalid_type(unexpected: Unexpected<'_>, expected: &dyn Expected) -> Self {
+        Self::new(ConfigValueErrorKind::InvalidType {
+            found: unexpected.to_string(),
+            expected: expected.to_string(),
+        })
+    }
+
+    fn invalid_value(unexpected: Unexpected<'_>, expected: &dyn Expected) -> Self {
+        Self::new(ConfigValueErrorKind::InvalidValue {
+            found: unexpected.to_string(),
+            expected: expected.to_string(),
+        })
+    }
+
+    fn unknown_variant(variant: &str, expected: &'static [&'static str]) -> Self {
+        Self::new(ConfigValueErrorKind::UnknownValue {
+            value: variant.to_string(),
+            expected,
+        })
+    }
+
+    fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+        Self::new(ConfigValueErrorKind::UnknownKey {
+            key: field.to_string(),
+            expected: expected.iter().map(|key| key.to_string()).collect(),
+        })
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self::new(ConfigValueErrorKind::MissingKey { key: field })
+    }
+}
+
+/// Replaces the environment variable references in `input`, see the module
+/// documentation for the syntax.
+fn interpolate_env(input: &str) -> Result<String, ConfigValueError> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find(INTERPOLATION_START) {
+        let after_start = &rest[start + INTERPOLATION_START.len()..];
+        if rest[..start].ends_with('$') {
+            output.push_str(&rest[..start - 1]);
+            output.push_str(INTERPOLATION_START);
+            rest = after_start;
+            continue;
+        }
+        output.push_str(&rest[..start]);
+        let end = after_start.find('}').ok_or_else(|| {
+            de::Error::custom(format!(
+                "missing `}}` for the environment variable reference in `{}`.",
+                input
+            ))
+        })?;
+        output.push_str(&resolve_env_var(&after_start[..end])?);
+        rest = &after_start[end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+fn resolve_env_var(reference: &str) -> Result<String, ConfigValueError> {
+    let (name, modifier) = match reference.split_once(':') {
+        Some((name, modifier)) => (name, Some(modifier)),
+        None => (reference, None),
+    };
+    let is_valid_name = matches!(name.chars().next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_valid_name {
+        return Err(invalid_reference(reference));
+    }
+    let value = env::var(name).ok();
+    match modifier {
+        None => value.ok_or_else(|| {
+            ConfigValueError::new(ConfigValueErrorKind::MissingEnvVar {
+                name: name.to_string(),
+                message: None,
+            })
+        }),
+        Some(modifier) => {
+            let value = value.filter(|value| !value.is_empty());
+            if let Some(default) = modifier.strip_prefix('-') {
+                Ok(value.unwrap_or_else(|| default.to_string()))
+            } else if let Some(message) = modifier.strip_prefix('?') {
+                value.ok_or_else(|| {
+                    ConfigValueError::new(ConfigValueErrorKind::MissingEnvVar {
+                        name: name.to_string(),
+                        message: Some(message.to_string()),
+                    })
+                })
+            } else {
+                Err(invalid_reference(reference))
+            }
+        }
+    }
+}
+
+fn invalid_reference(reference: &str) -> ConfigValueError {
+    de::Error::custom(format!(
+        "invalid environment variable reference `${{{}}}`, expected `${{NAME}}`, `${{NAME:-default}}` or `${{NAME:?message}}`.",
+        reference
+    ))
+}
+
+fn unexpected(value: &Value) -> Unexpected<'_> {
+    match value {
+        Value::Null => Unexpected::Unit,
+        Value::Bool(b) => Unexpected::Bool(*b),
+        Value::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+            (Some(u), _, _) => Unexpected::Unsigned(u),
+            (_, Some(i), _) => Unexpected::Signed(i),
+            (_, _, Some(f)) => Unexpected::Float(f),
+            _ => Unexpected::Other("number"),
+        },
+        Value::String(s) => Unexpected::Str(s),
+        Value::Array(_) => Unexpected::Seq,
+        Value::Object(_) => Unexpected::Map,
+    }
+}
+
+/// Visits `value` without interpolating strings.
+fn visit_value<'de, V: Visitor<'de>>(
+    value: Value,
+    visitor: V,
+) -> Result<V::Value, ConfigValueError> {
+    match value {
+        Value::Null => visitor.visit_unit(),
+        Value::Bool(b) => visitor.visit_bool(b),
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                visitor.visit_u64(u)
+            } else if let Some(i) = n.as_i64() {
+                visitor.visit_i64(i)
+            } else {
+                visitor.visit_f64(n.as_f64().unwrap_or_default())
+            }
+        }
+        Value::String(s) => visitor.visit_string(s),
+        Value::Array(array) => visitor.visit_seq(SeqDeserializer {
+            iter: array.into_iter().enumerate(),
+        }),
+        Value::Object(object) => visitor.visit_map(MapDeserializer::new(object)),
+    }
+}
+
+struct ValueDeserializer(Value);
+
+impl ValueDeserializer {
+    /// Booleans and numbers can be given as interpolated strings.
+    fn deserialize_scalar<'de, V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, ConfigValueError> {
+        match self.0 {
+            Value::String(s) if s.contains(INTERPOLATION_START) => {
+                let s = interpolate_env(&s)?;
+                match serde_json::from_str::<Value>(&s) {
+                    Ok(value @ (Value::Bool(_) | Value::Number(_))) => visit_value(value, visitor),
+                    _ => visitor.visit_string(s),
+                }
+            }
+            value => de::Deserializer::deserialize_any(ValueDeserializer(value), visitor),
+        }
+    }
+}
+
+macro_rules! deserialize_scalar {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.deserialize_scalar(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = ConfigValueError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(s) => visitor.visit_string(interpolate_env(&s)?),
+            value => visit_value(value, visitor),
+        }
+    }
+
+    deserialize_scalar! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_f32
+        deserialize_f64
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(variant) => {
+                visitor.visit_enum(interpolate_env(&variant)?.into_deserializer())
+            }
+            Value::Object(object) if object.len() == 1 => {
+                let (variant, value) = object.into_iter().next().unwrap();
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            other => Err(de::Error::invalid_type(unexpected(&other), &visitor)),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Deserializes keys of objects, which are not interpolated.
+struct KeyDeserializer(String);
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer {
+    type Error = ConfigValueError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple tuple_struct map struct
+        enum identifier ignored_any
+    }
+}
+
+struct SeqDeserializer {
+    iter: std::iter::Enumerate<std::vec::IntoIter<Value>>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqDeserializer {
+    type Error = ConfigValueError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.iter.next() {
+            Some((index, value)) => seed
+                .deserialize(ValueDeserializer(value))
+                .map(Some)
+                .map_err(|error| error.at(PathSegment::Index(index))),
+            None => Ok(None),
+        }
+    }
+}
+
+struct MapDeserializer {
+    iter: serde_json::map::IntoIter,
+    value: Option<(String, Value)>,
+}
+
+impl MapDeserializer {
+    fn new(object: Map<String, Value>) -> Self {
+        Self {
+            iter: object.into_iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapDeserializer {
+    type Error = ConfigValueError;
+
+    /// Errors for keys are reported at the path of the map, since the key
+    /// itself is part of the message.
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some((key.clone(), value));
+                seed.deserialize(KeyDeserializer(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+        seed.deserialize(ValueDeserializer(value))
+            .map_err(|error| error.at(PathSegment::Key(key)))
+    }
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: Value,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = ConfigValueError;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(KeyDeserializer(self.variant.clone()))?;
+        Ok((
+            variant,
+            VariantDeserializer {
+                variant: self.variant,
+                value: self.value,
+            },
+        ))
+    }
+}
+
+struct VariantDeserializer {
+    variant: String,
+    value: Value,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = ConfigValueError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.value {
+            Value::Null => Ok(()),
+            other => Err(<ConfigValueError as de::Error>::invalid_type(
+                unexpected(&other),
+                &"unit variant",
+            )
+            .at(PathSegment::Key(self.variant))),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(ValueDeserializer(self.value))
+            .map_err(|error| error.at(PathSegment::Key(self.variant)))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_seq(ValueDeserializer(self.value), visitor)
+            .map_err(|error| error.at(PathSegment::Key(self.variant)))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_map(ValueDeserializer(self.value), visitor)
+            .map_err(|error| error.at(PathSegment::Key(self.variant)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields, rename_all = "camelCase")]
+    struct Project {
+        language: Language,
+        #[serde(default)]
+        output: Option<String>,
+        #[serde(default)]
+        excludes: Vec<String>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Language {
+        Flow,
+        TypeScript,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        projects: std::collections::BTreeMap<String, Project>,
+    }
+
+    fn error(value: Value) -> String {
+        from_value::<Config>(value).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn deserializes_valid_config() {
+        let config: Config = from_value(json!({
+            "projects": {"web": {"language": "typescript", "output": null, "excludes": ["a"]}}
+        }))
+        .unwrap();
+        let project = &config.projects["web"];
+        assert_eq!(project.language, Language::TypeScript);
+        assert_eq!(project.output, None);
+        assert_eq!(project.excludes, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn reports_unknown_value_with_path_and_suggestion() {
+        assert_eq!(
+            error(json!({"projects": {"web": {"language": "typscript"}}})),
+            "Invalid config at `projects.web.language`: unknown value `typscript`. Did you mean `typescript`? Allowed values are: `flow`, `typescript`."
+        );
+    }
+
+    #[test]
+    fn reports_unknown_key_with_suggestion() {
+        assert_eq!(
+            error(json!({"projects": {"web": {"language": "flow", "ouput": "out"}}})),
+            "Invalid config at `projects.web`: unknown key `ouput`. Did you mean `output`? Allowed keys are: `language`, `output`, `excludes`."
+        );
+    }
+
+    #[test]
+    fn reports_invalid_type_with_index() {
+        assert_eq!(
+            error(json!({"projects": {"web": {"language": "flow", "excludes": ["a", 1]}}})),
+            "Invalid config at `projects.web.excludes[1]`: expected a string, found integer `1`."
+        );
+    }
+
+    #[test]
+    fn reports_missing_key() {
+        assert_eq!(
+            error(json!({"projects": {"web": {}}})),
+            "Invalid config at `projects.web`: missing required key `language`."
+        );
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Persist {
+        url: String,
+        enabled: bool,
+        #[serde(default)]
+        limit: Option<u8>,
+        #[serde(default)]
+        headers: std::collections::BTreeMap<String, String>,
+    }
+
+    #[test]
+    fn interpolates_env_vars() {
+        std::env::set_var("RELAY_TEST_CONFIG_HOST", "https://example.com");
+        let persist: Persist = from_value(json!({
+            "url": "${RELAY_TEST_CONFIG_HOST}/persist",
+            "enabled": "${RELAY_TEST_CONFIG_UNSET:-true}",
+            "limit": "${RELAY_TEST_CONFIG_UNSET:-10}",
+            "headers": {"${RELAY_TEST_CONFIG_HOST}": "$${RELAY_TEST_CONFIG_HOST}"}
+        }))
+        .unwrap();
+        assert_eq!(persist.url, "https://example.com/persist");
+        assert!(persist.enabled);
+        assert_eq!(persist.limit, Some(10));
+        assert_eq!(
+            persist.headers["${RELAY_TEST_CONFIG_HOST}"],
+            "${RELAY_TEST_CONFIG_HOST}"
+        );
+
+        let config: Config = from_value(json!({
+            "projects": {"web": {"language": "${RELAY_TEST_CONFIG_UNSET:-typescript}"}}
+        }))
+        .unwrap();
+        assert_eq!(config.projects["web"].language, Language::TypeScript);
+    }
+
+    #[test]
+    fn reports_missing_env_var() {
+        assert_eq!(
+            from_value::<Persist>(json!({
+                "url": "${RELAY_TEST_CONFIG_UNSET:?set it to the persist endpoint}",
+                "enabled": true
+            }))
+            .unwrap_err()
+            .to_string(),
+            "Invalid config at `url`: environment variable `RELAY_TEST_CONFIG_UNSET` is not set: set it to the persist endpoint"
+        );
+        assert_eq!(
+            error(
+                json!({"projects": {"web": {"language": "flow", "excludes": ["${RELAY_TEST_CONFIG_UNSET}"]}}})
+            ),
+            "Invalid config at `projects.web.excludes[0]`: environment variable `RELAY_TEST_CONFIG_UNSET` is not set."
+        );
+    }
+
+    #[test]
+    fn reports_invalid_interpolated_type() {
+        assert_eq!(
+            from_value::<Persist>(json!({
+                "url": "",
+                "enabled": "${RELAY_TEST_CONFIG_UNSET:-yes}"
+            }))
+            .unwrap_err()
+            .to_string(),
+            "Invalid config at `enabled`: expected a boolean, found string \"yes\"."
+        );
+    }
+}
@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A fragment that is included in several projects, or reached by many
+//! operations, can get the same diagnostic once for each of them. Identical
+//! diagnostics are reported once, with the number of their occurrences.
+
+use common::Diagnostic;
+use common::Location;
+use fnv::FnvHashMap;
+use fnv::FnvHashSet;
+use relay_transforms::diagnostic_code;
+
+use crate::errors::BuildProjectError;
+
+/// Diagnostics are identical if they have the same code, or the same message
+/// if they have no code, at the same location. Diagnostics at generated
+/// locations are never identical.
+fn deduplication_key(diagnostic: &Diagnostic) -> Option<(String, Location)> {
+    let location = diagnostic.location();
+    if location.source_location().is_generated() {
+        return None;
+    }
+    let code = match diagnostic_code(diagnostic) {
+        Some(code) => code.to_string(),
+        None => diagnostic.message().to_string(),
+    };
+    Some((code, location))
+}
+
+/// Keeps the first of the identical diagnostics of all the lists, and sets
+/// its number of occurrences.
+fn deduplicate(lists: Vec<&mut Vec<Diagnostic>>) {
+    let mut counts: FnvHashMap<(String, Location), usize> = Default::default();
+    for diagnostics in &lists {
+        for key in diagnostics.iter().filter_map(deduplication_key) {
+            *counts.entry(key).or_default() += 1;
+        }
+    }
+    let mut reported: FnvHashSet<(String, Location)> = Default::default();
+    for diagnostics in lists {
+        diagnostics.retain_mut(|diagnostic| match deduplication_key(diagnostic) {
+            Some(key) => {
+                let occurrences = counts[&key];
+                if reported.insert(key) {
+                    diagnostic.set_occurrences(occurrences);
+                    true
+                } else {
+                    false
+                }
+            }
+            None => true,
+        });
+    }
+}
+
+pub fn deduplicate_diagnostics(diagnostics: &mut Vec<Diagnostic>) {
+    deduplicate(vec![diagnostics]);
+}
+
+/// Deduplicates the validation errors of all projects: the projects after
+/// the first one that reported an error don't report it again.
+pub fn deduplicate_project_errors(errors: &mut Vec<BuildProjectError>) {
+    deduplicate(
+        errors
+            .iter_mut()
+            .filter_map(|error| match error {
+                BuildProjectError::ValidationErrors { errors, .. } => Some(errors),
+                _ => None,
+            })
+            .collect(),
+    );
+    errors.retain(|error| match error {
+        BuildProjectError::ValidationErrors { errors, .. } => !errors.is_empty(),
+        _ => true,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use common::SourceLocationKey;
+    use common::Span;
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    fn error(message: &'static str, start: u32) -> Diagnostic {
+        Diagnostic::error(
+            message,
+            Location::new(
+                SourceLocationKey::standalone("src/Fragment.graphql"),
+                Span::new(start, start + 1),
+            ),
+        )
+    }
+
+    #[test]
+    fn deduplicates_errors_across_projects() {
+        let mut errors = vec![
+            BuildProjectError::ValidationErrors {
+                errors: vec![error("Unknown field", 1), error("Unknown field", 1)],
+                project_name: "web".intern(),
+            },
+            BuildProjectError::ValidationErrors {
+                errors: vec![error("Unknown field", 1)],
+                project_name: "mobile".intern(),
+            },
+            BuildProjectError::ValidationErrors {
+                errors: vec![error("Unknown field", 1), error("Unknown field", 5)],
+                project_name: "desktop".intern(),
+            },
+        ];
+        deduplicate_project_errors(&mut errors);
+
+        let occurrences = errors
+            .iter()
+            .map(|error| match error {
+                BuildProjectError::ValidationErrors { errors, .. } => errors
+                    .iter()
+                    .map(|error| error.occurrences())
+                    .collect::<Vec<_>>(),
+                _ => vec![],
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(occurrences, vec![vec![4], vec![1]]);
+    }
+}
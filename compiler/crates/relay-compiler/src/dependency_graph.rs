@@ -0,0 +1,292 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The graph of fragment spreads between the operations and fragments of a
+//! project, used by `relay graph`.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use common::DirectiveName;
+use common::NamedItem;
+use fnv::FnvHashMap;
+use fnv::FnvHashSet;
+use graphql_ir::Directive;
+use graphql_ir::FragmentDefinition;
+use graphql_ir::FragmentSpread;
+use graphql_ir::OperationDefinition;
+use graphql_ir::Visitor;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use relay_transforms::Programs;
+use relay_transforms::DEFER_STREAM_CONSTANTS;
+use relay_transforms::MATCH_CONSTANTS;
+use serde::Serialize;
+
+use crate::build_project::Artifact;
+use crate::build_project::ArtifactContent;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GraphNodeKind {
+    Operation,
+    Fragment,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphNode {
+    pub name: StringKey,
+    pub kind: GraphNodeKind,
+    pub file: &'static str,
+    /// The fragment is defined in the base project.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub base: bool,
+}
+
+/// A spread of fragment `to` in the operation or fragment `from`. Spreads of
+/// the same fragment with the same annotations are a single edge.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEdge {
+    pub from: StringKey,
+    pub to: StringKey,
+    /// The spread has `@module`, i.e. the fragment is loaded with its component.
+    pub module: bool,
+    /// The spread has `@defer`.
+    pub defer: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyGraph {
+    pub project: StringKey,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+    /// Builds the graph from the untransformed source program, so that the
+    /// spreads are as written in the documents.
+    pub fn new(project: StringKey, programs: &Programs) -> Self {
+        let program = &programs.source;
+        let mut nodes: Vec<GraphNode> = program
+            .operations()
+            .map(|operation| GraphNode {
+                name: operation.name.item.0,
+                kind: GraphNodeKind::Operation,
+                file: operation.name.location.source_location().path(),
+                base: false,
+            })
+            .chain(program.fragments().map(|fragment| GraphNode {
+                name: fragment.name.item.0,
+                kind: GraphNodeKind::Fragment,
+                file: fragment.name.location.source_location().path(),
+                base: programs.base_fragment_names.contains(&fragment.name.item),
+            }))
+            .collect();
+        nodes.sort_by_key(|node| (node.kind, node.name.lookup()));
+
+        let mut collector = EdgeCollector::default();
+        collector.visit_program(program);
+        let mut edges: Vec<GraphEdge> = collector.edges.into_iter().collect();
+        edges.sort_by_key(|edge| {
+            (
+                edge.from.lookup(),
+                edge.to.lookup(),
+                edge.module,
+                edge.defer,
+            )
+        });
+
+        Self {
+            project,
+            nodes,
+            edges,
+        }
+    }
+
+    pub fn node(&self, name: StringKey) -> Option<&GraphNode> {
+        self.nodes.iter().find(|node| node.name == name)
+    }
+
+    /// Returns every chain of spreads from an operation down to the given
+    /// definition, each starting with the operation name and ending with
+    /// `name`.
+    pub fn paths_to(&self, name: StringKey) -> Vec<Vec<StringKey>> {
+        let mut spread_by: FnvHashMap<StringKey, Vec<StringKey>> = Default::default();
+        for edge in &self.edges {
+            let parents = spread_by.entry(edge.to).or_default();
+            if !parents.contains(&edge.from) {
+                parents.push(edge.from);
+            }
+        }
+
+        let mut paths = Vec::new();
+        let mut path = vec![name];
+        self.collect_paths(&spread_by, &mut path, &mut paths);
+        paths.sort_by_cached_key(|path| path.iter().map(|name| name.lookup()).collect::<Vec<_>>());
+        paths
+    }
+
+    fn collect_paths(
+        &self,
+        spread_by: &FnvHashMap<StringKey, Vec<StringKey>>,
+        path: &mut Vec<StringKey>,
+        paths: &mut Vec<Vec<StringKey>>,
+    ) {
+        let current = *path.last().unwrap();
+        if let Some(GraphNode {
+            kind: GraphNodeKind::Operation,
+            ..
+        }) = self.node(current)
+        {
+            paths.push(path.iter().rev().copied().collect());
+            return;
+        }
+        for parent in spread_by.get(&current).into_iter().flatten() {
+            // Circular spreads are reported by validations; don't loop on them.
+            if !path.contains(parent) {
+                path.push(*parent);
+                self.collect_paths(spread_by, path, paths);
+                path.pop();
+            }
+        }
+    }
+
+    /// Returns the artifacts that contain the selections of the given
+    /// fragment: its own artifact, and the operation artifacts generated
+    /// from definitions that spread it directly or transitively, e.g. the
+    /// queries of `@refetchable` fragments.
+    pub fn artifacts_including<'a>(
+        &self,
+        fragment_name: StringKey,
+        artifacts: &'a [Artifact],
+    ) -> Vec<&'a Artifact> {
+        let mut using_definitions: FnvHashSet<StringKey> = Default::default();
+        let mut stack = vec![fragment_name];
+        while let Some(name) = stack.pop() {
+            if using_definitions.insert(name) {
+                stack.extend(
+                    self.edges
+                        .iter()
+                        .filter(|edge| edge.to == name)
+                        .map(|edge| edge.from),
+                );
+            }
+        }
+
+        let mut artifacts: Vec<&Artifact> = artifacts
+            .iter()
+            .filter(|artifact| match &artifact.content {
+                ArtifactContent::Fragment { .. } => artifact
+                    .source_definition_names
+                    .iter()
+                    .any(|name| StringKey::from(*name) == fragment_name),
+                ArtifactContent::Generic { .. } => false,
+                _ => artifact
+                    .source_definition_names
+                    .iter()
+                    .any(|name| using_definitions.contains(&StringKey::from(*name))),
+            })
+            .collect();
+        artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+        artifacts
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut output = String::new();
+        writeln!(output, "digraph {} {{", quote(self.project.lookup())).unwrap();
+        for node in &self.nodes {
+            let shape = match node.kind {
+                GraphNodeKind::Operation => "box",
+                GraphNodeKind::Fragment => "ellipse",
+            };
+            write!(
+                output,
+                "  {} [shape={}, label={}",
+                quote(node.name.lookup()),
+                shape,
+                quote(&format!("{}\n{}", node.name, node.file))
+            )
+            .unwrap();
+            if node.base {
+                write!(output, ", style=dashed").unwrap();
+            }
+            writeln!(output, "];").unwrap();
+        }
+        for edge in &self.edges {
+            write!(
+                output,
+                "  {} -> {}",
+                quote(edge.from.lookup()),
+                quote(edge.to.lookup())
+            )
+            .unwrap();
+            let annotations: Vec<&str> = [(edge.module, "@module"), (edge.defer, "@defer")]
+                .into_iter()
+                .filter_map(|(is_set, name)| if is_set { Some(name) } else { None })
+                .collect();
+            if !annotations.is_empty() {
+                write!(
+                    output,
+                    " [label={}, style=dashed]",
+                    quote(&annotations.join(" "))
+                )
+                .unwrap();
+            }
+            writeln!(output, ";").unwrap();
+        }
+        writeln!(output, "}}").unwrap();
+        output
+    }
+}
+
+fn has_directive(directives: &[Directive], name: DirectiveName) -> bool {
+    directives.named(name).is_some()
+}
+
+/// DOT identifiers use the escapes of JSON strings.
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+#[derive(Default)]
+struct EdgeCollector {
+    current: Option<StringKey>,
+    edges: BTreeSet<GraphEdge>,
+}
+
+impl Visitor for EdgeCollector {
+    const NAME: &'static str = "DependencyGraphEdgeCollector";
+    const VISIT_ARGUMENTS: bool = false;
+    const VISIT_DIRECTIVES: bool = false;
+
+    fn visit_operation(&mut self, operation: &OperationDefinition) {
+        self.current = Some(operation.name.item.0);
+        self.default_visit_operation(operation);
+    }
+
+    fn visit_fragment(&mut self, fragment: &FragmentDefinition) {
+        self.current = Some(fragment.name.item.0);
+        self.default_visit_fragment(fragment);
+    }
+
+    fn visit_fragment_spread(&mut self, spread: &FragmentSpread) {
+        self.edges.insert(GraphEdge {
+            from: self
+                .current
+                .expect("Expected spreads to be in a definition."),
+            to: spread.fragment.item.0,
+            module: has_directive(&spread.directives, MATCH_CONSTANTS.module_directive_name),
+            defer: has_directive(&spread.directives, DEFER_STREAM_CONSTANTS.defer_name),
+        });
+    }
+}
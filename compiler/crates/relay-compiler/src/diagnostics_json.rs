@@ -0,0 +1,289 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Machine-readable diagnostics of `relay compiler --diagnostics-format json`,
+//! e.g. for CI bots that comment on the lines of a pull request.
+
+use std::path::Path;
+
+use common::Diagnostic;
+use common::DiagnosticSeverity;
+use common::Location;
+use intern::Lookup;
+use relay_transforms::diagnostic_code;
+use serde::Serialize;
+
+use crate::compiler_state::ProjectName;
+use crate::errors::BuildProjectError;
+use crate::errors::Error;
+use crate::source_for_location;
+use crate::SourceReader;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDiagnostic {
+    pub message: String,
+    /// The stable code of the message, like `RELAY-1007`, see
+    /// `common::DiagnosticCode`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<&'static str>,
+    /// The name of the kind of the message, like `UndefinedField`, which
+    /// doesn't change with its wording. Messages without a kind have none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub severity: &'static str,
+    /// The number of identical diagnostics this one stands for, e.g. in a
+    /// fragment included in several projects. Omitted if it is one.
+    #[serde(skip_serializing_if = "is_single")]
+    pub occurrences: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<&'static str>,
+    #[serde(flatten)]
+    pub location: Option<JsonLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<JsonRelatedInformation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonRelatedInformation {
+    pub message: String,
+    #[serde(flatten)]
+    pub location: Option<JsonLocation>,
+}
+
+/// The file of a location relative to the root dir, and its span if the
+/// source can be read. Generated locations have none.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonLocation {
+    pub file: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<JsonSpan>,
+}
+
+/// Lines and columns are 1-based, and the end is the position after the
+/// last character of the span.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSpan {
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+pub fn json_diagnostics(
+    root_dir: &Path,
+    diagnostics: &[Diagnostic],
+    project: Option<ProjectName>,
+    source_reader: &dyn SourceReader,
+) -> Vec<JsonDiagnostic> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| JsonDiagnostic {
+            message: diagnostic.message().to_string(),
+            code: diagnostic_code(diagnostic),
+            kind: diagnostic_kind(diagnostic),
+            severity: severity_name(diagnostic.severity()),
+            occurrences: diagnostic.occurrences(),
+            project: project.map(|project| project.lookup()),
+            location: json_location(root_dir, diagnostic.location(), source_reader),
+            related: diagnostic
+                .related_information()
+                .iter()
+                .map(|related| JsonRelatedInformation {
+                    message: related.message.to_string(),
+                    location: json_location(root_dir, related.location, source_reader),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// The diagnostics of a failed build. Errors without diagnostics, like
+/// failing to persist an operation, are errors without a location.
+pub fn json_diagnostics_of_error(
+    root_dir: &Path,
+    error: &Error,
+    source_reader: &dyn SourceReader,
+) -> Vec<JsonDiagnostic> {
+    match error {
+        Error::DiagnosticsError { errors } => {
+            json_diagnostics(root_dir, errors, None, source_reader)
+        }
+        Error::BuildProjectsErrors { errors } => errors
+            .iter()
+            .flat_map(|error| match error {
+                BuildProjectError::ValidationErrors {
+                    errors,
+                    project_name,
+                } => json_diagnostics(root_dir, errors, Some(*project_name), source_reader),
+                BuildProjectError::PersistErrors {
+                    errors,
+                    project_name,
+                } => errors
+                    .iter()
+                    .map(|error| error_without_location(error, Some(*project_name)))
+                    .collect(),
+                error => vec![error_without_location(error, None)],
+            })
+            .collect(),
+        Error::Cancelled => vec![],
+        error => vec![error_without_location(error, None)],
+    }
+}
+
+pub fn print_json_diagnostics(diagnostics: &[JsonDiagnostic]) -> String {
+    serde_json::to_string_pretty(diagnostics).unwrap()
+}
+
+fn error_without_location(
+    error: &impl std::fmt::Display,
+    project: Option<ProjectName>,
+) -> JsonDiagnostic {
+    JsonDiagnostic {
+        message: error.to_string(),
+        code: None,
+        kind: None,
+        severity: severity_name(DiagnosticSeverity::ERROR),
+        occurrences: 1,
+        project: project.map(|project| project.lookup()),
+        location: None,
+        related: vec![],
+    }
+}
+
+fn json_location(
+    root_dir: &Path,
+    location: Location,
+    source_reader: &dyn SourceReader,
+) -> Option<JsonLocation> {
+    let source_location = location.source_location();
+    if source_location.is_generated() {
+        return None;
+    }
+    let span = source_for_location(root_dir, source_location, source_reader).map(|source| {
+        let range = source.to_text_source().to_span_range(location.span());
+        JsonSpan {
+            start_line: range.start.line + 1,
+            start_column: range.start.character + 1,
+            end_line: range.end.line + 1,
+            end_column: range.end.character + 1,
+        }
+    });
+    Some(JsonLocation {
+        file: source_location.path(),
+        span,
+    })
+}
+
+/// The messages of the compiler are variants of error enums, whose debug
+/// output starts with the name of the variant.
+pub(crate) fn diagnostic_kind(diagnostic: &Diagnostic) -> Option<String> {
+    let debug = format!("{:?}", diagnostic.message());
+    let kind = debug
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect::<String>();
+    if kind.is_empty() { None } else { Some(kind) }
+}
+
+fn is_single(occurrences: &usize) -> bool {
+    *occurrences == 1
+}
+
+fn severity_name(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::ERROR => "error",
+        DiagnosticSeverity::WARNING => "warning",
+        DiagnosticSeverity::INFORMATION => "info",
+        _ => "hint",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use common::SourceLocationKey;
+    use common::Span;
+    use intern::string_key::Intern;
+    use thiserror::Error;
+
+    use super::*;
+
+    struct TestSourceReader;
+
+    impl SourceReader for TestSourceReader {
+        fn read_file_to_string(&self, _path: &PathBuf) -> std::io::Result<String> {
+            Ok("query Foo {\n  me { nam }\n}\n".to_string())
+        }
+    }
+
+    #[derive(Debug, Error)]
+    enum TestMessage {
+        #[error("Unknown field `{0}`")]
+        UnknownField(&'static str),
+    }
+
+    #[test]
+    fn json_diagnostics_have_spans_and_related_locations() {
+        let location = |start, end| {
+            Location::new(
+                SourceLocationKey::standalone("src/Foo.graphql"),
+                Span::new(start, end),
+            )
+        };
+        let diagnostics = vec![
+            Diagnostic::error(TestMessage::UnknownField("nam"), location(19, 22))
+                .annotate("parent field", location(14, 16)),
+            Diagnostic::warning("Generated", Location::generated(), vec![]),
+        ];
+        let json = print_json_diagnostics(&json_diagnostics(
+            Path::new("/"),
+            &diagnostics,
+            Some("web".intern()),
+            &TestSourceReader,
+        ));
+        assert_eq!(
+            json,
+            r#"[
+  {
+    "message": "Unknown field `nam`",
+    "kind": "UnknownField",
+    "severity": "error",
+    "project": "web",
+    "file": "src/Foo.graphql",
+    "span": {
+      "startLine": 2,
+      "startColumn": 8,
+      "endLine": 2,
+      "endColumn": 11
+    },
+    "related": [
+      {
+        "message": "parent field",
+        "file": "src/Foo.graphql",
+        "span": {
+          "startLine": 2,
+          "startColumn": 3,
+          "endLine": 2,
+          "endColumn": 5
+        }
+      }
+    ]
+  },
+  {
+    "message": "Generated",
+    "severity": "warning",
+    "project": "web"
+  }
+]"#
+        );
+    }
+}
@@ -0,0 +1,192 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A summary of the diagnostics of a build, grouped by file and by rule, for
+//! `--diagnosticsFormat summary`: builds with hundreds of diagnostics are
+//! easier to triage by their top offenders than by reading all of them.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use common::Diagnostic;
+use common::DiagnosticSeverity;
+use fnv::FnvHashMap;
+use relay_transforms::diagnostic_code;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::diagnostics_json::diagnostic_kind;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsSummary {
+    errors: usize,
+    warnings: usize,
+    others: usize,
+    /// The files and rules by number of diagnostics, most first.
+    #[serde(serialize_with = "serialize_counts")]
+    by_file: Vec<(String, usize)>,
+    #[serde(serialize_with = "serialize_counts")]
+    by_rule: Vec<(String, usize)>,
+}
+
+impl DiagnosticsSummary {
+    pub fn new<'a>(diagnostics: impl IntoIterator<Item = &'a Diagnostic>) -> Self {
+        let mut errors = 0;
+        let mut warnings = 0;
+        let mut others = 0;
+        let mut by_file: FnvHashMap<String, usize> = Default::default();
+        let mut by_rule: FnvHashMap<String, usize> = Default::default();
+        for diagnostic in diagnostics {
+            match diagnostic.severity() {
+                DiagnosticSeverity::ERROR => errors += 1,
+                DiagnosticSeverity::WARNING => warnings += 1,
+                _ => others += 1,
+            }
+            let source_location = diagnostic.location().source_location();
+            let file = if source_location.is_generated() {
+                "<generated>".to_string()
+            } else {
+                source_location.path().to_string()
+            };
+            *by_file.entry(file).or_default() += 1;
+            *by_rule.entry(rule_name(diagnostic)).or_default() += 1;
+        }
+        Self {
+            errors,
+            warnings,
+            others,
+            by_file: sorted_by_count(by_file),
+            by_rule: sorted_by_count(by_rule),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors + self.warnings + self.others == 0
+    }
+
+    /// Prints the counts, and the `top` files and rules with the most
+    /// diagnostics. The report has all of them.
+    pub fn print(&self, top: usize, report_path: Option<&Path>) -> String {
+        let mut output = String::new();
+        writeln!(
+            output,
+            "{} error(s), {} warning(s) and {} other diagnostic(s) in {} file(s).",
+            self.errors,
+            self.warnings,
+            self.others,
+            self.by_file.len()
+        )
+        .unwrap();
+        write_group(&mut output, "By file", &self.by_file, top);
+        write_group(&mut output, "By rule", &self.by_rule, top);
+        if let Some(report_path) = report_path {
+            write!(output, "\nFull report: {}", report_path.display()).unwrap();
+        }
+        output.trim_end().to_string()
+    }
+}
+
+/// The stable code and the kind of the message, like
+/// `RELAY-1041 UndefinedFragment`, or the kind alone without a code.
+fn rule_name(diagnostic: &Diagnostic) -> String {
+    let kind = diagnostic_kind(diagnostic);
+    match (diagnostic_code(diagnostic), kind) {
+        (Some(code), Some(kind)) => format!("{} {}", code, kind),
+        (Some(code), None) => code.to_string(),
+        (None, Some(kind)) => kind,
+        (None, None) => "<other>".to_string(),
+    }
+}
+
+fn sorted_by_count(counts: FnvHashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut counts = counts.into_iter().collect::<Vec<_>>();
+    counts.sort_by(|(a_name, a_count), (b_name, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+    });
+    counts
+}
+
+/// Counts are serialized as an object, in their order.
+fn serialize_counts<S: Serializer>(
+    counts: &[(String, usize)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(counts.iter().map(|(name, count)| (name, count)))
+}
+
+fn write_group(output: &mut String, title: &str, counts: &[(String, usize)], top: usize) {
+    writeln!(output, "\n{}:", title).unwrap();
+    for (name, count) in counts.iter().take(top) {
+        writeln!(output, "  {:>5}  {}", count, name).unwrap();
+    }
+    if counts.len() > top {
+        writeln!(output, "  ... and {} more", counts.len() - top).unwrap();
+    }
+}
+
+/// Removes the terminal colors of the printed diagnostics, for the report
+/// file.
+pub fn strip_styles(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' && chars.peek() == Some(&'[') {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Location;
+    use common::SourceLocationKey;
+    use common::Span;
+
+    use super::*;
+
+    fn location(file: &'static str) -> Location {
+        Location::new(SourceLocationKey::standalone(file), Span::new(0, 1))
+    }
+
+    #[test]
+    fn summary_groups_by_file_and_rule() {
+        let diagnostics = vec![
+            Diagnostic::error("Unknown field", location("src/A.js")),
+            Diagnostic::error("Unknown field", location("src/B.js")),
+            Diagnostic::error("Unknown field", location("src/B.js")),
+            Diagnostic::warning("Deprecated", location("src/C.js"), vec![]),
+        ];
+        let summary = DiagnosticsSummary::new(&diagnostics);
+        assert_eq!(
+            summary.print(2, Some(Path::new("/tmp/report.txt"))),
+            r#"3 error(s), 1 warning(s) and 0 other diagnostic(s) in 3 file(s).
+
+By file:
+      2  src/B.js
+      1  src/A.js
+  ... and 1 more
+
+By rule:
+      4  <other>
+
+Full report: /tmp/report.txt"#
+        );
+    }
+
+    #[test]
+    fn strips_styles() {
+        assert_eq!(strip_styles("\u{1b}[31m✖︎ error\u{1b}[0m"), "✖︎ error");
+    }
+}
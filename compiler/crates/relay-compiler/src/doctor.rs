@@ -0,0 +1,453 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Checks of the environment of the compiler, run by `relay doctor`. Each
+//! check that fails comes with the steps to fix it.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use common::SourceLocationKey;
+use fnv::FnvBuildHasher;
+use indexmap::IndexMap;
+use relay_config::PersistConfig;
+use relay_config::ProjectConfig;
+use relay_config::SchemaLocation;
+use schema_introspection::introspection_to_sdl;
+use schema_introspection::parse_introspection;
+use url::Url;
+use walkdir::WalkDir;
+
+use crate::build_project::build_schema::build_schema_from_sources;
+use crate::compiler_state::ProjectSet;
+use crate::config::Config;
+
+const WATCHMAN_INSTALL_URL: &str = "https://facebook.github.io/watchman/docs/install";
+
+/// How long to wait for a TCP connection to an endpoint.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+#[derive(Debug)]
+pub struct DoctorCheck {
+    pub status: CheckStatus,
+    pub message: String,
+    /// What to do to fix a failed check.
+    pub remediation: Option<String>,
+}
+
+impl DoctorCheck {
+    pub fn ok(message: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Ok,
+            message: message.into(),
+            remediation: None,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>, remediation: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Warning,
+            message: message.into(),
+            remediation: Some(remediation.into()),
+        }
+    }
+
+    pub fn error(message: impl Into<String>, remediation: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Error,
+            message: message.into(),
+            remediation: Some(remediation.into()),
+        }
+    }
+}
+
+/// Checks that `watchman` is installed, and reports its version.
+pub fn check_watchman() -> DoctorCheck {
+    if env::var("FORCE_NO_WATCHMAN").is_ok() {
+        return DoctorCheck::ok(
+            "Watchman is disabled by FORCE_NO_WATCHMAN, the file system is crawled instead.",
+        );
+    }
+    match Command::new("watchman").arg("version").output() {
+        Ok(output) if output.status.success() => {
+            let version = serde_json::from_slice::<serde_json::Value>(&output.stdout)
+                .ok()
+                .and_then(|value| value["version"].as_str().map(str::to_string));
+            match version {
+                Some(version) => DoctorCheck::ok(format!("Watchman {} is available.", version)),
+                None => DoctorCheck::ok("Watchman is available."),
+            }
+        }
+        Ok(output) => DoctorCheck::warning(
+            format!(
+                "Watchman is installed but failed to run: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            "Restart the watchman server with `watchman shutdown-server`, and check its log \
+            if the problem persists. Without watchman, the file system is crawled and \
+            `--watch` is not available.",
+        ),
+        Err(_) => DoctorCheck::warning(
+            "Watchman is not installed. The file system is crawled instead, which is slower \
+            for large projects, and `--watch` is not available.",
+            format!("Install watchman, see {}.", WATCHMAN_INSTALL_URL),
+        ),
+    }
+}
+
+/// Runs the checks of a loaded config. The config itself is validated by
+/// loading it.
+pub async fn check_config(config: &Config) -> Vec<DoctorCheck> {
+    let mut checks = vec![];
+    checks.extend(check_overlapping_sources(&config.sources));
+    for project_config in config.enabled_projects() {
+        checks.push(check_schema(&config.root_dir, project_config));
+        checks.extend(check_artifact_directories(config, project_config));
+        if let Some(check) = check_persist(&config.root_dir, project_config).await {
+            checks.push(check);
+        }
+    }
+    checks
+}
+
+/// Files in a source that is nested in another source only belong to the
+/// projects of the innermost source, which is rarely intended.
+fn check_overlapping_sources(
+    sources: &IndexMap<PathBuf, ProjectSet, FnvBuildHasher>,
+) -> Vec<DoctorCheck> {
+    let mut checks = vec![];
+    for (path, project_set) in sources {
+        for (other_path, other_project_set) in sources {
+            if path != other_path && path.starts_with(other_path) {
+                checks.push(DoctorCheck::warning(
+                    format!(
+                        "The source `{}` ({}) is inside the source `{}` ({}). Its files only \
+                        belong to the projects of `{}`.",
+                        path.display(),
+                        project_names(project_set),
+                        other_path.display(),
+                        project_names(other_project_set),
+                        path.display(),
+                    ),
+                    format!(
+                        "If the files of `{}` should also belong to the projects of `{}`, list \
+                        all of these projects for `{}` in `sources`.",
+                        path.display(),
+                        other_path.display(),
+                        path.display(),
+                    ),
+                ));
+            }
+        }
+    }
+    if checks.is_empty() {
+        checks.push(DoctorCheck::ok("The source directories don't overlap."));
+    }
+    checks
+}
+
+fn project_names(project_set: &ProjectSet) -> String {
+    project_set
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parses the schema and the client schema extensions of the project.
+fn check_schema(root_dir: &Path, project_config: &ProjectConfig) -> DoctorCheck {
+    let project_name = project_config.name;
+    let schema_path = match &project_config.schema_location {
+        SchemaLocation::File(path) | SchemaLocation::Directory(path) => path,
+    };
+    if let Some(remote_schema) = &project_config.remote_schema {
+        if !root_dir.join(schema_path).exists() {
+            return DoctorCheck::ok(format!(
+                "[{}] The schema is fetched from {} on the next build.",
+                project_name, remote_schema.url
+            ));
+        }
+    }
+
+    let read_sources = |paths: &[&PathBuf]| -> Result<Vec<(String, SourceLocationKey)>, String> {
+        let mut sources = vec![];
+        for path in paths {
+            for file in schema_files(&root_dir.join(path)) {
+                let mut content = fs::read_to_string(&file)
+                    .map_err(|err| format!("Unable to read `{}`: {}", file.display(), err))?;
+                if matches!(file.extension().and_then(|ext| ext.to_str()), Some("json")) {
+                    let introspection = parse_introspection(&content).map_err(|err| {
+                        format!(
+                            "Unable to read the introspection result in `{}`: {}",
+                            file.display(),
+                            err
+                        )
+                    })?;
+                    content = introspection_to_sdl(&introspection);
+                }
+                let relative_path = file.strip_prefix(root_dir).unwrap_or(&file);
+                let location = SourceLocationKey::standalone(&relative_path.to_string_lossy());
+                sources.push((content, location));
+            }
+        }
+        Ok(sources)
+    };
+    let sources = read_sources(&[schema_path]).and_then(|schema_sources| {
+        let extension_paths: Vec<&PathBuf> = project_config.schema_extensions.iter().collect();
+        Ok((schema_sources, read_sources(&extension_paths)?))
+    });
+    let (schema_sources, extension_sources) = match sources {
+        Ok(sources) => sources,
+        Err(message) => {
+            return DoctorCheck::error(
+                format!("[{}] {}", project_name, message),
+                format!(
+                    "Check the `schema` and `schemaExtensions` of the project `{}` in the config.",
+                    project_name
+                ),
+            );
+        }
+    };
+    if schema_sources.is_empty() {
+        return DoctorCheck::error(
+            format!(
+                "[{}] No schema files were found at `{}`.",
+                project_name,
+                schema_path.display()
+            ),
+            "Point `schema` to a `.graphql` file, or `schemaDir` to a directory of `.graphql` \
+            files.",
+        );
+    }
+
+    match build_schema_from_sources(
+        &project_config.schema_config,
+        &schema_sources,
+        &extension_sources,
+    ) {
+        Ok(_) => DoctorCheck::ok(format!(
+            "[{}] The schema and {} schema extension file(s) are valid.",
+            project_name,
+            extension_sources.len()
+        )),
+        Err(diagnostics) => DoctorCheck::error(
+            format!(
+                "[{}] The schema is invalid: {}",
+                project_name,
+                diagnostics
+                    .iter()
+                    .map(|diagnostic| diagnostic.message().to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+            "Fix the schema, or run `relay lint` for the locations of the errors.",
+        ),
+    }
+}
+
+/// The schema files at a path, which is a file or a directory of `.graphql`
+/// files. A missing path has no files.
+fn schema_files(path: &Path) -> Vec<PathBuf> {
+    if path.is_file() {
+        return vec![path.to_path_buf()];
+    }
+    let mut files: Vec<PathBuf> = WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            path.is_file()
+                && matches!(
+                    path.extension().and_then(|ext| ext.to_str()),
+                    Some("graphql" | "gql")
+                )
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Checks that the directories that artifacts are written to can be written.
+/// Without an `output`, artifacts are written next to the sources.
+fn check_artifact_directories(config: &Config, project_config: &ProjectConfig) -> Vec<DoctorCheck> {
+    let directories: Vec<PathBuf> = match &project_config.output {
+        Some(output) => std::iter::once(output)
+            .chain(project_config.extra_artifacts_output.iter())
+            .cloned()
+            .collect(),
+        None => config
+            .sources
+            .iter()
+            .filter(|(_, project_set)| project_set.iter().any(|name| *name == project_config.name))
+            .map(|(path, _)| path.clone())
+            .collect(),
+    };
+    directories
+        .into_iter()
+        .map(|directory| {
+            let path = config.root_dir.join(&directory);
+            match check_writable(&path) {
+                Ok(()) => DoctorCheck::ok(format!(
+                    "[{}] Artifacts can be written to `{}`.",
+                    project_config.name,
+                    directory.display()
+                )),
+                Err(err) => DoctorCheck::error(
+                    format!(
+                        "[{}] Artifacts can't be written to `{}`: {}",
+                        project_config.name,
+                        directory.display(),
+                        err
+                    ),
+                    format!(
+                        "Make `{}` writable by the current user, e.g. with `chmod u+w`.",
+                        path.display()
+                    ),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Writes and removes a file in the directory, or in its closest existing
+/// ancestor, since the compiler creates missing directories.
+fn check_writable(path: &Path) -> std::io::Result<()> {
+    let mut directory = path;
+    while !directory.exists() {
+        match directory.parent() {
+            Some(parent) => directory = parent,
+            None => break,
+        }
+    }
+    let probe = directory.join(format!(".relay-doctor-{}", std::process::id()));
+    fs::write(&probe, "")?;
+    fs::remove_file(&probe)
+}
+
+/// Checks that the endpoint of a remote persister accepts connections, or
+/// that the file of a local persister can be written.
+async fn check_persist(root_dir: &Path, project_config: &ProjectConfig) -> Option<DoctorCheck> {
+    let project_name = project_config.name;
+    match project_config.persist.as_ref()? {
+        PersistConfig::Remote(remote_config) => {
+            Some(match check_reachable(&remote_config.url).await {
+                Ok(()) => DoctorCheck::ok(format!(
+                    "[{}] The persist endpoint {} is reachable.",
+                    project_name, remote_config.url
+                )),
+                Err(err) => DoctorCheck::error(
+                    format!(
+                        "[{}] The persist endpoint {} is not reachable: {}",
+                        project_name, remote_config.url, err
+                    ),
+                    "Check the persist `url` of the project, your network \
+                    connection, and any proxy or VPN that is required to reach it.",
+                ),
+            })
+        }
+        PersistConfig::Local(local_config) => {
+            let directory = root_dir.join(&local_config.file);
+            let directory = directory.parent().unwrap_or(root_dir);
+            Some(match check_writable(directory) {
+                Ok(()) => DoctorCheck::ok(format!(
+                    "[{}] Persisted queries can be written to `{}`.",
+                    project_name,
+                    local_config.file.display()
+                )),
+                Err(err) => DoctorCheck::error(
+                    format!(
+                        "[{}] Persisted queries can't be written to `{}`: {}",
+                        project_name,
+                        local_config.file.display(),
+                        err
+                    ),
+                    format!(
+                        "Make `{}` writable by the current user.",
+                        directory.display()
+                    ),
+                ),
+            })
+        }
+    }
+}
+
+/// Opens a TCP connection to the host of the URL. No request is sent, so that
+/// nothing is persisted.
+async fn check_reachable(url: &str) -> Result<(), String> {
+    let url = Url::parse(url).map_err(|err| format!("invalid URL: {}", err))?;
+    let host = url.host_str().ok_or("the URL has no host")?;
+    let port = url.port_or_known_default().ok_or("the URL has no port")?;
+    let addresses: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|err| format!("unable to resolve `{}`: {}", host, err))?
+        .collect();
+    let mut last_error = format!("`{}` has no addresses", host);
+    for address in addresses {
+        match tokio::time::timeout(CONNECT_TIMEOUT, tokio::net::TcpStream::connect(address)).await {
+            Ok(Ok(_)) => return Ok(()),
+            Ok(Err(err)) => last_error = format!("unable to connect to {}: {}", address, err),
+            Err(_) => last_error = format!("timed out connecting to {}", address),
+        }
+    }
+    Err(last_error)
+}
+
+pub fn print_checks(checks: &[DoctorCheck]) -> String {
+    let mut output = String::new();
+    for check in checks {
+        let label = match check.status {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warning",
+            CheckStatus::Error => "error",
+        };
+        writeln!(output, "[{}] {}", label, check.message).unwrap();
+        if let Some(remediation) = &check.remediation {
+            writeln!(output, "    Fix: {}", remediation).unwrap();
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    #[test]
+    fn nested_sources_are_reported() {
+        let mut sources = IndexMap::default();
+        sources.insert(PathBuf::from("src"), ProjectSet::of("web".intern()));
+        sources.insert(
+            PathBuf::from("src/shared"),
+            ProjectSet::of("native".intern()),
+        );
+        sources.insert(PathBuf::from("srcs"), ProjectSet::of("other".intern()));
+
+        let checks = check_overlapping_sources(&sources);
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].status, CheckStatus::Warning);
+        assert_eq!(
+            checks[0].message,
+            "The source `src/shared` (native) is inside the source `src` (web). Its files only \
+            belong to the projects of `src/shared`."
+        );
+    }
+}
@@ -11,6 +11,7 @@ use std::path::PathBuf;
 use common::Diagnostic;
 use glob::PatternError;
 use persist_query::PersistError;
+use schema_introspection::IntrospectionError;
 use thiserror::Error;
 
 use crate::compiler_state::ProjectName;
@@ -114,6 +115,12 @@ pub enum Error {
     #[error("Unable to parse changed files list. {reason}")]
     ExternalSourceParseError { reason: String },
 
+    #[error("Failed to list the files changed since `{revision}`: {reason}")]
+    SourceControlDiffError { revision: String, reason: String },
+
+    #[error("Failed to export the build telemetry to `{uri}`: {reason}")]
+    OtlpExportError { uri: String, reason: String },
+
     #[error("JSON parse error in `{file}`: {source}")]
     SerdeError {
         file: PathBuf,
@@ -123,6 +130,19 @@ pub enum Error {
     #[error("glob pattern error: {0}")]
     PatternError(PatternError),
 
+    #[error("Failed to read the introspection result in `{file}`: {source}")]
+    IntrospectionFileError {
+        file: PathBuf,
+        source: IntrospectionError,
+    },
+
+    #[error("Failed to fetch the schema of project `{project_name}` from `{url}`: {source}")]
+    RemoteSchemaError {
+        project_name: ProjectName,
+        url: String,
+        source: IntrospectionError,
+    },
+
     #[error(
         "Saved state versions mismatch. Saved state: {saved_state_version}, config: {config_version}."
     )]
@@ -130,6 +150,11 @@ pub enum Error {
         saved_state_version: String,
         config_version: String,
     },
+
+    #[error(
+        "The sources of the project `{project_name}` were not loaded, e.g. they are missing from the corpus."
+    )]
+    MissingProjectSources { project_name: ProjectName },
 }
 
 #[derive(Debug, Error)]
@@ -164,6 +189,11 @@ pub enum ConfigValidationError {
         base_project_name: ProjectName,
     },
 
+    #[error(
+        "The project `{project_name}` sets `experimentalEmitSemanticNullabilityTypes`, but its schema doesn't set `schemaConfig.semanticNonNullSupport`."
+    )]
+    SemanticNullabilityTypesWithoutSchemaSupport { project_name: ProjectName },
+
     #[error("Project `{project_name}` needs to define exactly one of `schema` or `schema_dir`.")]
     ProjectNeedsSchemaXorSchemaDir { project_name: ProjectName },
 
@@ -214,6 +244,15 @@ pub enum ConfigValidationError {
         error: regex::Error,
     },
 
+    #[error(
+        "The pattern `{pattern}` in the `overrides` for project `{project_name}` is invalid: {error}."
+    )]
+    InvalidOverridePattern {
+        project_name: ProjectName,
+        pattern: String,
+        error: PatternError,
+    },
+
     #[error("The `artifactDirectory` does not exist at `{path}`.")]
     ArtifactDirectoryNotExistent { path: PathBuf },
 
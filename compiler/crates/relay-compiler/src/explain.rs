@@ -0,0 +1,283 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Printing of an operation in the programs of each transform phase, used by
+//! `relay explain` to debug transforms.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use fnv::FnvHashSet;
+use graphql_ir::FragmentDefinition;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::FragmentSpread;
+use graphql_ir::InlineFragment;
+use graphql_ir::LinkedField;
+use graphql_ir::OperationDefinition;
+use graphql_ir::OperationDefinitionName;
+use graphql_ir::Program;
+use graphql_ir::ScalarField;
+use graphql_ir::Variable;
+use graphql_ir::VariableDefinition;
+use graphql_ir::Visitor;
+use graphql_text_printer::print_full_operation;
+use graphql_text_printer::print_operation_with_inlined_fragments;
+use graphql_text_printer::PrinterOptions;
+use relay_transforms::Programs;
+use schema::FieldID;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExplainPhase {
+    /// The program after validations, before any transform.
+    Source,
+    Reader,
+    Normalization,
+    OperationText,
+    Typegen,
+}
+
+impl ExplainPhase {
+    pub const ALL: [ExplainPhase; 5] = [
+        ExplainPhase::Source,
+        ExplainPhase::Reader,
+        ExplainPhase::Normalization,
+        ExplainPhase::OperationText,
+        ExplainPhase::Typegen,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ExplainPhase::Source => "source",
+            ExplainPhase::Reader => "reader",
+            ExplainPhase::Normalization => "normalization",
+            ExplainPhase::OperationText => "operation-text",
+            ExplainPhase::Typegen => "typegen",
+        }
+    }
+
+    fn program(self, programs: &Programs) -> &Arc<Program> {
+        match self {
+            ExplainPhase::Source => &programs.source,
+            ExplainPhase::Reader => &programs.reader,
+            ExplainPhase::Normalization => &programs.normalization,
+            ExplainPhase::OperationText => &programs.operation_text,
+            ExplainPhase::Typegen => &programs.typegen,
+        }
+    }
+}
+
+/// Prints the operation, with the fragments it references, as it is in the
+/// program of each of the given phases. The metadata directives transforms
+/// add, and the labels of `@defer` and `@stream`, are printed in comments.
+/// With `inline_fragments`, the fragments are inlined in the operation
+/// instead. Returns `None` if the operation isn't in the source program.
+pub fn explain_operation(
+    programs: &Programs,
+    operation_name: OperationDefinitionName,
+    phases: &[ExplainPhase],
+    inline_fragments: bool,
+) -> Option<String> {
+    programs.source.operation(operation_name)?;
+
+    let options = PrinterOptions {
+        metadata_comments: true,
+        ..Default::default()
+    };
+    let mut output = String::new();
+    for (index, phase) in phases.iter().enumerate() {
+        if index > 0 {
+            writeln!(output).unwrap();
+        }
+        writeln!(output, "# {}", phase.name()).unwrap();
+        let program = phase.program(programs);
+        match program.operation(operation_name) {
+            Some(operation) => {
+                let text = if inline_fragments {
+                    print_operation_with_inlined_fragments(program, operation, options)
+                } else {
+                    print_full_operation(program, operation, options)
+                };
+                writeln!(output, "{}", text.trim_end()).unwrap()
+            }
+            None => writeln!(output, "# The operation is not in this program.").unwrap(),
+        }
+    }
+    Some(output)
+}
+
+/// Like `explain_operation`, but returns the IR of the operation and of the
+/// fragments it references in each phase, as JSON for external tools. The IR
+/// refers to types and fields by their IDs in the schema, and the `schema` of
+/// each phase maps the IDs it uses to their names:
+///
+/// ```json
+/// {"types": {"Object": {"3": "User"}}, "fields": {"12": {"name": "id", "parentType": "User", "type": "ID!"}}}
+/// ```
+pub fn explain_operation_json(
+    programs: &Programs,
+    operation_name: OperationDefinitionName,
+    phases: &[ExplainPhase],
+) -> Option<Value> {
+    programs.source.operation(operation_name)?;
+
+    let phases: Vec<Value> = phases
+        .iter()
+        .map(|phase| {
+            let program = phase.program(programs);
+            let operation = match program.operation(operation_name) {
+                Some(operation) => operation,
+                None => {
+                    return json!({
+                        "phase": phase.name(),
+                        "operation": Value::Null,
+                        "fragments": [],
+                    });
+                }
+            };
+            let references = SchemaReferences::of_operation(program, operation);
+            let fragments: Vec<&Arc<FragmentDefinition>> = references
+                .fragments
+                .iter()
+                .filter_map(|name| program.fragment(*name))
+                .collect();
+            json!({
+                "phase": phase.name(),
+                "operation": operation.as_ref(),
+                "fragments": fragments,
+                "schema": references.to_json(&program.schema),
+            })
+        })
+        .collect();
+    Some(json!({
+        "operation": operation_name,
+        "phases": phases,
+    }))
+}
+
+/// The fragments, types and fields that an operation refers to, directly or
+/// through its fragments.
+#[derive(Default)]
+struct SchemaReferences {
+    fragments: BTreeSet<FragmentDefinitionName>,
+    types: BTreeSet<Type>,
+    fields: BTreeSet<FieldID>,
+}
+
+impl SchemaReferences {
+    fn of_operation(program: &Program, operation: &OperationDefinition) -> Self {
+        let mut references = Self::default();
+        references.visit_operation(operation);
+        let mut visited = FnvHashSet::default();
+        while let Some(name) = references
+            .fragments
+            .iter()
+            .find(|name| !visited.contains(*name))
+            .copied()
+        {
+            visited.insert(name);
+            if let Some(fragment) = program.fragment(name) {
+                references.visit_fragment(fragment);
+            }
+        }
+        references
+    }
+
+    fn to_json(&self, schema: &SDLSchema) -> Value {
+        let mut types: Map<String, Value> = Map::new();
+        for type_ in &self.types {
+            let (kind, id) = match type_ {
+                Type::Enum(id) => ("Enum", id.0),
+                Type::InputObject(id) => ("InputObject", id.0),
+                Type::Interface(id) => ("Interface", id.0),
+                Type::Object(id) => ("Object", id.0),
+                Type::Scalar(id) => ("Scalar", id.0),
+                Type::Union(id) => ("Union", id.0),
+            };
+            if let Value::Object(ids) = types
+                .entry(kind)
+                .or_insert_with(|| Value::Object(Map::new()))
+            {
+                ids.insert(id.to_string(), json!(schema.get_type_name(*type_)));
+            }
+        }
+        let fields: Map<String, Value> = self
+            .fields
+            .iter()
+            .map(|id| {
+                let field = schema.field(*id);
+                (
+                    id.0.to_string(),
+                    json!({
+                        "name": field.name.item,
+                        "parentType": field
+                            .parent_type
+                            .map(|parent_type| schema.get_type_name(parent_type)),
+                        "type": schema.get_type_string(&field.type_),
+                    }),
+                )
+            })
+            .collect();
+        json!({
+            "types": types,
+            "fields": fields,
+        })
+    }
+}
+
+impl Visitor for SchemaReferences {
+    const NAME: &'static str = "SchemaReferences";
+    const VISIT_ARGUMENTS: bool = true;
+    const VISIT_DIRECTIVES: bool = true;
+
+    fn visit_operation(&mut self, operation: &OperationDefinition) {
+        self.types.insert(operation.type_);
+        self.default_visit_operation(operation);
+    }
+
+    fn visit_fragment(&mut self, fragment: &FragmentDefinition) {
+        self.types.insert(fragment.type_condition);
+        self.default_visit_fragment(fragment);
+    }
+
+    fn visit_variable_definition(&mut self, variable_definition: &VariableDefinition) {
+        self.types.insert(variable_definition.type_.inner());
+        self.default_visit_variable_definition(variable_definition);
+    }
+
+    fn visit_variable(&mut self, variable: &Variable) {
+        self.types.insert(variable.type_.inner());
+    }
+
+    fn visit_scalar_field(&mut self, field: &ScalarField) {
+        self.fields.insert(field.definition.item);
+        self.default_visit_scalar_field(field);
+    }
+
+    fn visit_linked_field(&mut self, field: &LinkedField) {
+        self.fields.insert(field.definition.item);
+        self.default_visit_linked_field(field);
+    }
+
+    fn visit_inline_fragment(&mut self, fragment: &InlineFragment) {
+        if let Some(type_condition) = fragment.type_condition {
+            self.types.insert(type_condition);
+        }
+        self.default_visit_inline_fragment(fragment);
+    }
+
+    fn visit_fragment_spread(&mut self, spread: &FragmentSpread) {
+        self.fragments.insert(spread.fragment.item);
+        self.default_visit_fragment_spread(spread);
+    }
+}
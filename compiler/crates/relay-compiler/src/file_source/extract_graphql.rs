@@ -11,6 +11,7 @@ use std::path::PathBuf;
 
 use common::SourceLocationKey;
 use docblock_syntax::DocblockSource;
+use extract_graphql::ExtractOptions;
 use extract_graphql::JavaScriptSourceFeature;
 use graphql_syntax::GraphQLSource;
 use intern::Lookup;
@@ -20,6 +21,7 @@ use serde::Serialize;
 use super::read_file_to_string;
 use super::File;
 use super::FileSourceResult;
+use super::Vfs;
 use crate::errors::Result;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -42,26 +44,55 @@ pub struct LocatedJavascriptSourceFeatures {
 
 pub trait SourceReader {
     fn read_file_to_string(&self, path: &PathBuf) -> std::io::Result<String>;
+
+    /// Which template literals of the files contain GraphQL.
+    fn extract_options(&self) -> ExtractOptions {
+        ExtractOptions::default()
+    }
 }
 
 /// Default implementation of the file source reader
 /// that is directly using `fs` operations to read the content of the file
-pub struct FsSourceReader;
+#[derive(Default)]
+pub struct FsSourceReader {
+    extract_options: ExtractOptions,
+}
+
+impl FsSourceReader {
+    pub fn new(extract_options: ExtractOptions) -> Self {
+        Self { extract_options }
+    }
+}
 
 impl SourceReader for FsSourceReader {
     fn read_file_to_string(&self, path: &PathBuf) -> std::io::Result<String> {
         fs::read_to_string(path)
     }
+
+    fn extract_options(&self) -> ExtractOptions {
+        self.extract_options.clone()
+    }
 }
 
 /// Reads and extracts `graphql` tagged literals and Relay-specific docblocks
-/// from a JavaScript file.
+/// from a JavaScript file, or the `<script>` blocks of a Vue or Svelte component.
+/// Returns `None` for files skipped by the pre-scan, as they can't contain any
+/// of the configured tags.
 pub fn extract_javascript_features_from_file(
+    vfs: &dyn Vfs,
+    extract_options: &ExtractOptions,
     file_source_result: &FileSourceResult,
     file: &File,
-) -> Result<LocatedJavascriptSourceFeatures> {
-    let contents = read_file_to_string(file_source_result, file)?;
-    let features = extract_graphql::extract(&contents);
+) -> Result<Option<LocatedJavascriptSourceFeatures>> {
+    let contents = read_file_to_string(vfs, file_source_result, file)?;
+    let features = match extract_graphql::try_extract_from_path_with_options(
+        &file.name,
+        &contents,
+        extract_options,
+    ) {
+        Some(features) => features,
+        None => return Ok(None),
+    };
     let mut graphql_sources = Vec::new();
     let mut docblock_sources = Vec::new();
     for (index, feature) in features.into_iter().enumerate() {
@@ -81,10 +112,10 @@ pub fn extract_javascript_features_from_file(
         }
     }
 
-    Ok(LocatedJavascriptSourceFeatures {
+    Ok(Some(LocatedJavascriptSourceFeatures {
         graphql_sources,
         docblock_sources,
-    })
+    }))
 }
 
 pub fn source_for_location(
@@ -96,7 +127,11 @@ pub fn source_for_location(
         SourceLocationKey::Embedded { path, index } => {
             let absolute_path = root_dir.join(path.lookup());
             let contents = source_reader.read_file_to_string(&absolute_path).ok()?;
-            let file_sources = extract_graphql::extract(&contents);
+            let file_sources = extract_graphql::extract_from_path_with_options(
+                &absolute_path,
+                &contents,
+                &source_reader.extract_options(),
+            );
             file_sources.into_iter().nth(index.into())
         }
         SourceLocationKey::Standalone { path } => {
@@ -91,6 +91,9 @@ pub fn categorize_files(
         FileSourceResult::WalkDir(result) => {
             categorize_non_watchman_files(&categorizer, config, &result.files)
         }
+        FileSourceResult::FileList(result) => {
+            categorize_non_watchman_files(&categorizer, config, &result.files)
+        }
     };
     let mut categorized = HashMap::new();
     for (file_group, file) in result {
@@ -140,6 +143,7 @@ pub struct FileCategorizer {
     source_mapping: PathMapping<ProjectSet>,
     schema_file_mapping: HashMap<PathBuf, ProjectSet>,
     schema_dir_mapping: PathMapping<ProjectSet>,
+    standalone_document_projects: FnvHashSet<ProjectName>,
 }
 
 impl FileCategorizer {
@@ -205,6 +209,13 @@ impl FileCategorizer {
                 generated_dir_mapping.push((extra_artifacts_output.clone(), project_name));
             }
         }
+        let standalone_document_projects = config
+            .projects
+            .values()
+            .filter(|project_config| project_config.standalone_documents)
+            .map(|project_config| project_config.name)
+            .collect();
+
         let source_language: HashMap<ProjectName, TypegenLanguage> = config
             .projects
             .iter()
@@ -221,6 +232,7 @@ impl FileCategorizer {
             schema_file_mapping,
             schema_dir_mapping: PathMapping::new(schema_dir_mapping),
             source_mapping: PathMapping::new(source_mapping),
+            standalone_document_projects,
         }
     }
 
@@ -278,11 +290,18 @@ impl FileCategorizer {
                 Ok(FileGroup::Extension { project_set })
             } else if let Some(project_set) = self.schema_dir_mapping.find(path) {
                 Ok(FileGroup::Schema { project_set })
+            } else if let Some(project_set) = self.find_standalone_document_projects(path) {
+                Ok(FileGroup::Source { project_set })
             } else {
                 Err(Cow::Borrowed(
                     "Expected *.graphql/*.gql file to be either a schema or extension.",
                 ))
             }
+        } else if let Some(project_set) = self.schema_file_mapping.get(path) {
+            // Schema files may also be introspection results in JSON format.
+            Ok(FileGroup::Schema {
+                project_set: project_set.clone(),
+            })
         } else {
             Err(Cow::Borrowed(
                 "File categorizer encounter a file with unsupported extension.",
@@ -297,6 +316,25 @@ impl FileCategorizer {
         })
     }
 
+    /// The projects with standalone documents among the projects of the
+    /// sources containing `path`.
+    fn find_standalone_document_projects(&self, path: &Path) -> Option<ProjectSet> {
+        if self.in_relative_generated_dir(path) {
+            return None;
+        }
+        let project_names = self
+            .source_mapping
+            .find(path)?
+            .into_iter()
+            .filter(|project_name| self.standalone_document_projects.contains(project_name))
+            .collect::<Vec<_>>();
+        if project_names.is_empty() {
+            None
+        } else {
+            Some(ProjectSet::new(project_names))
+        }
+    }
+
     fn is_valid_extension_for_project_set(
         &self,
         project_set: &ProjectSet,
@@ -341,7 +379,16 @@ impl<T: Clone> PathMapping<T> {
 }
 
 fn is_source_code_extension(extension: &OsStr) -> bool {
-    extension == "js" || extension == "jsx" || extension == "ts" || extension == "tsx"
+    extension == "js"
+        || extension == "jsx"
+        || extension == "ts"
+        || extension == "tsx"
+        || is_single_file_component_extension(extension)
+}
+
+/// Vue and Svelte components, with the code in their `<script>` blocks
+fn is_single_file_component_extension(extension: &OsStr) -> bool {
+    extension == "vue" || extension == "svelte"
 }
 
 fn is_schema_extension(extension: &OsStr) -> bool {
@@ -356,7 +403,7 @@ fn is_valid_source_code_extension(typegen_language: &TypegenLanguage, extension:
     match typegen_language {
         TypegenLanguage::TypeScript => is_source_code_extension(extension),
         TypegenLanguage::Flow | TypegenLanguage::JavaScript => {
-            extension == "js" || extension == "jsx"
+            extension == "js" || extension == "jsx" || is_single_file_component_extension(extension)
         }
     }
 }
@@ -377,7 +424,8 @@ mod tests {
                         "src/vendor": "public",
                         "src/custom": "with_custom_generated_dir",
                         "src/typescript": "typescript",
-                        "src/custom_overlapping": ["with_custom_generated_dir", "overlapping_generated_dir"]
+                        "src/custom_overlapping": ["with_custom_generated_dir", "overlapping_generated_dir"],
+                        "src/introspection": "introspection"
                     },
                     "projects": {
                         "public": {
@@ -395,11 +443,16 @@ mod tests {
                         },
                         "typescript": {
                             "schema": "graphql/ts_schema.graphql",
-                            "language": "typescript"
+                            "language": "typescript",
+                            "standaloneDocuments": true
                         },
                         "overlapping_generated_dir": {
                             "schema": "graphql/__generated__/custom.graphql",
                             "language": "flow"
+                        },
+                        "introspection": {
+                            "schema": "graphql/introspection.json",
+                            "language": "flow"
                         }
                     }
                 }
@@ -481,6 +534,14 @@ mod tests {
                 project_set: ProjectSet::of("internal".intern())
             },
         );
+        assert_eq!(
+            categorizer
+                .categorize(&PathBuf::from("graphql/introspection.json"))
+                .unwrap(),
+            FileGroup::Schema {
+                project_set: ProjectSet::of("introspection".intern())
+            },
+        );
         assert_eq!(
             categorizer
                 .categorize(&PathBuf::from("src/typescript/a.ts"))
@@ -489,6 +550,100 @@ mod tests {
                 project_set: ProjectSet::of("typescript".intern()),
             },
         );
+        assert_eq!(
+            categorizer
+                .categorize(&PathBuf::from("src/typescript/Component.vue"))
+                .unwrap(),
+            FileGroup::Source {
+                project_set: ProjectSet::of("typescript".intern()),
+            },
+        );
+        assert_eq!(
+            categorizer
+                .categorize(&PathBuf::from("src/js/Component.svelte"))
+                .unwrap(),
+            FileGroup::Source {
+                project_set: ProjectSet::of("public".intern()),
+            },
+        );
+    }
+
+    #[test]
+    fn test_categorize_remote_schema() {
+        let config = Config::from_string_for_test(
+            r#"
+                {
+                    "sources": {
+                        "src/js": "remote",
+                        "src/custom": "remote_with_cache_file"
+                    },
+                    "projects": {
+                        "remote": {
+                            "schema": {
+                                "url": "https://example.com/graphql",
+                                "headers": { "Authorization": "Bearer token" }
+                            },
+                            "language": "flow"
+                        },
+                        "remote_with_cache_file": {
+                            "schema": {
+                                "url": "https://example.com/graphql",
+                                "cacheFile": "graphql/remote.graphql"
+                            },
+                            "language": "flow"
+                        }
+                    }
+                }
+            "#,
+        )
+        .unwrap();
+        let categorizer = FileCategorizer::from_config(&config);
+
+        assert_eq!(
+            categorizer
+                .categorize(&PathBuf::from(".relay/schemas/remote.graphql"))
+                .unwrap(),
+            FileGroup::Schema {
+                project_set: ProjectSet::of("remote".intern())
+            },
+        );
+        assert_eq!(
+            categorizer
+                .categorize(&PathBuf::from("graphql/remote.graphql"))
+                .unwrap(),
+            FileGroup::Schema {
+                project_set: ProjectSet::of("remote_with_cache_file".intern())
+            },
+        );
+    }
+
+    #[test]
+    fn test_categorize_standalone_documents() {
+        let config = create_test_config();
+        let categorizer = FileCategorizer::from_config(&config);
+
+        assert_eq!(
+            categorizer
+                .categorize(&PathBuf::from("src/typescript/a.graphql"))
+                .unwrap(),
+            FileGroup::Source {
+                project_set: ProjectSet::of("typescript".intern()),
+            },
+        );
+        assert_eq!(
+            categorizer
+                .categorize(&PathBuf::from("src/typescript/nested/a.gql"))
+                .unwrap(),
+            FileGroup::Source {
+                project_set: ProjectSet::of("typescript".intern()),
+            },
+        );
+        assert_eq!(
+            categorizer.categorize(&PathBuf::from("src/typescript/__generated__/a.graphql")),
+            Err(Cow::Borrowed(
+                "Expected *.graphql/*.gql file to be either a schema or extension."
+            )),
+        );
     }
 
     #[test]
@@ -0,0 +1,88 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::path::PathBuf;
+
+use common::PerfLogEvent;
+use common::PerfLogger;
+use log::debug;
+
+use super::File;
+use crate::compiler_state::CompilerState;
+use crate::config::Config;
+use crate::config::SchemaLocation;
+use crate::errors::Error;
+use crate::errors::Result;
+use crate::FileSourceResult;
+
+#[derive(Debug)]
+pub struct FileListFileSourceResult {
+    pub files: Vec<File>,
+    pub resolved_root: PathBuf,
+}
+
+/// Uses exactly the given source and schema files instead of discovering
+/// them, e.g. for the hermetic inputs of a build system.
+pub struct FileListFileSource<'config> {
+    pub config: &'config Config,
+    files: &'config [PathBuf],
+}
+
+impl<'config> FileListFileSource<'config> {
+    pub fn new(files: &'config [PathBuf], config: &'config Config) -> Self {
+        debug!(
+            "Using the list of {} files as the file source.",
+            files.len()
+        );
+        Self { config, files }
+    }
+
+    /// The schema is not discovered either, so it has to be in the list.
+    fn check_schema_files(&self) -> Result<()> {
+        for project_config in self.config.enabled_projects() {
+            let schema_path = match &project_config.schema_location {
+                SchemaLocation::File(path) | SchemaLocation::Directory(path) => path,
+            };
+            if !self.files.iter().any(|file| file.starts_with(schema_path)) {
+                return Err(Error::ConfigError {
+                    details: format!(
+                        "The file list has no schema files of project `{}` in `{}`.",
+                        project_config.name,
+                        schema_path.display()
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn create_compiler_state(&self, perf_logger: &impl PerfLogger) -> Result<CompilerState> {
+        self.check_schema_files()?;
+        let setup_event = perf_logger.create_event("file_list_file_source_create_compiler_state");
+        let file_source_changes = FileSourceResult::FileList(FileListFileSourceResult {
+            files: self
+                .files
+                .iter()
+                .map(|name| File {
+                    name: name.clone(),
+                    exists: true,
+                })
+                .collect(),
+            resolved_root: self.config.root_dir.clone(),
+        });
+        let compiler_state = CompilerState::from_file_source_changes(
+            self.config,
+            &file_source_changes,
+            &setup_event,
+            perf_logger,
+        )?;
+
+        setup_event.complete();
+
+        Ok(compiler_state)
+    }
+}
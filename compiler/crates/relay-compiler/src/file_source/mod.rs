@@ -10,8 +10,10 @@ mod extract_graphql;
 mod file_categorizer;
 mod file_filter;
 mod file_group;
+mod file_list_file_source;
 mod read_file_to_string;
 mod source_control_update_status;
+mod vfs;
 mod walk_dir_file_source;
 mod watchman_file_source;
 mod watchman_query_builder;
@@ -43,6 +45,11 @@ pub use self::extract_graphql::LocatedDocblockSource;
 pub use self::extract_graphql::LocatedGraphQLSource;
 pub use self::extract_graphql::LocatedJavascriptSourceFeatures;
 pub use self::extract_graphql::SourceReader;
+use self::file_list_file_source::FileListFileSource;
+pub use self::file_list_file_source::FileListFileSourceResult;
+pub use self::vfs::FsVfs;
+pub use self::vfs::MemoryVfs;
+pub use self::vfs::Vfs;
 use self::walk_dir_file_source::WalkDirFileSource;
 use self::walk_dir_file_source::WalkDirFileSourceResult;
 use crate::compiler_state::CompilerState;
@@ -55,6 +62,7 @@ pub enum FileSource<'config> {
     Watchman(WatchmanFileSource<'config>),
     External(ExternalFileSource<'config>),
     WalkDir(WalkDirFileSource<'config>),
+    FileList(FileListFileSource<'config>),
 }
 
 impl<'config> FileSource<'config> {
@@ -70,6 +78,9 @@ impl<'config> FileSource<'config> {
                 ExternalFileSource::new(changed_files_list.to_path_buf(), config),
             )),
             FileSourceKind::WalkDir => Ok(Self::WalkDir(WalkDirFileSource::new(config))),
+            FileSourceKind::FileList(files) => {
+                Ok(Self::FileList(FileListFileSource::new(files, config)))
+            }
         }
     }
 
@@ -101,6 +112,7 @@ impl<'config> FileSource<'config> {
                 }
             }
             Self::WalkDir(file_source) => file_source.create_compiler_state(perf_logger),
+            Self::FileList(file_source) => file_source.create_compiler_state(perf_logger),
         }
     }
 
@@ -119,7 +131,7 @@ impl<'config> FileSource<'config> {
                     FileSourceSubscription::Watchman(watchman_subscription),
                 ))
             }
-            Self::External(_) | Self::WalkDir(_) => {
+            Self::External(_) | Self::WalkDir(_) | Self::FileList(_) => {
                 unimplemented!(
                     "watch-mode (subscribe) is not available for non-watchman file sources."
                 )
@@ -147,6 +159,7 @@ pub enum FileSourceResult {
     Watchman(WatchmanFileSourceResult),
     External(ExternalFileSourceResult),
     WalkDir(WalkDirFileSourceResult),
+    FileList(FileListFileSourceResult),
 }
 
 impl FileSourceResult {
@@ -155,6 +168,7 @@ impl FileSourceResult {
             Self::Watchman(file_source) => Some(file_source.clock.clone()),
             Self::External(_) => None,
             Self::WalkDir(_) => None,
+            Self::FileList(_) => None,
         }
     }
 
@@ -163,6 +177,7 @@ impl FileSourceResult {
             Self::Watchman(file_source_result) => file_source_result.resolved_root.path(),
             Self::External(file_source_result) => file_source_result.resolved_root.clone(),
             Self::WalkDir(file_source_result) => file_source_result.resolved_root.clone(),
+            Self::FileList(file_source_result) => file_source_result.resolved_root.clone(),
         }
     }
 
@@ -171,6 +186,7 @@ impl FileSourceResult {
             Self::Watchman(file_source_result) => &file_source_result.saved_state_info,
             Self::External(_) => unimplemented!(),
             Self::WalkDir(_) => unimplemented!(),
+            Self::FileList(_) => unimplemented!(),
         }
     }
 
@@ -179,6 +195,7 @@ impl FileSourceResult {
             Self::Watchman(file_source_result) => file_source_result.files.len(),
             Self::External(file_source_result) => file_source_result.files.len(),
             Self::WalkDir(file_source_result) => file_source_result.files.len(),
+            Self::FileList(file_source_result) => file_source_result.files.len(),
         }
     }
 }
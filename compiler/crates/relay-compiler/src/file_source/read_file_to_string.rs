@@ -7,19 +7,25 @@
 
 use super::File;
 use super::FileSourceResult;
+use super::Vfs;
 use crate::errors::Error;
 use crate::errors::Result;
 
 /// Reads a file into a string.
-pub fn read_file_to_string(file_source_result: &FileSourceResult, file: &File) -> Result<String> {
+pub fn read_file_to_string(
+    vfs: &dyn Vfs,
+    file_source_result: &FileSourceResult,
+    file: &File,
+) -> Result<String> {
     assert!(
         file.exists,
         "Can't read from non-existent file: {:?}",
         &file.name
     );
     let absolute_path = file.absolute_path(file_source_result.resolved_root());
-    std::fs::read_to_string(&absolute_path).map_err(|err| Error::FileRead {
-        file: absolute_path.clone(),
-        source: err,
-    })
+    vfs.read_to_string(&absolute_path)
+        .map_err(|err| Error::FileRead {
+            file: absolute_path.clone(),
+            source: err,
+        })
 }
@@ -0,0 +1,114 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use dashmap::DashMap;
+
+/// The file system that source and schema files are read from. Embedders
+/// can replace it, e.g. to compile the inputs of a build action or the
+/// unsaved buffers of an editor, together with `FileSourceKind::FileList`
+/// to list the files.
+pub trait Vfs: Send + Sync {
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+}
+
+/// Reads the files from disk.
+pub struct FsVfs;
+
+impl Vfs for FsVfs {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+}
+
+/// Files held in memory, by absolute path.
+#[derive(Default)]
+pub struct MemoryVfs {
+    files: DashMap<PathBuf, String>,
+}
+
+impl MemoryVfs {
+    pub fn insert(&self, path: impl Into<PathBuf>, content: impl Into<String>) {
+        self.files.insert(path.into(), content.into());
+    }
+
+    pub fn remove(&self, path: &Path) {
+        self.files.remove(path);
+    }
+}
+
+impl Vfs for MemoryVfs {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.files
+            .get(path)
+            .map(|content| content.clone())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{:?} is not in the memory file system", path),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common::NoopPerfLogger;
+
+    use super::*;
+    use crate::compiler::Compiler;
+    use crate::config::Config;
+    use crate::config::FileSourceKind;
+    use crate::ArtifactMemoryWriter;
+
+    #[tokio::test]
+    async fn compiles_files_from_memory() {
+        let mut config = Config::from_string_for_test(
+            r#"
+                {
+                    "sources": { "src": "app" },
+                    "projects": {
+                        "app": { "schema": "schema.graphql", "language": "typescript" }
+                    }
+                }
+            "#,
+        )
+        .unwrap();
+        let vfs = MemoryVfs::default();
+        vfs.insert(
+            config.root_dir.join("schema.graphql"),
+            "type Query { name: String }",
+        );
+        vfs.insert(
+            config.root_dir.join("src/App.ts"),
+            "graphql`query AppQuery { name }`;",
+        );
+        config.vfs = Arc::new(vfs);
+        config.file_source_config =
+            FileSourceKind::FileList(vec!["schema.graphql".into(), "src/App.ts".into()]);
+        let artifact_writer = ArtifactMemoryWriter::default();
+        let artifacts = artifact_writer.files();
+        config.artifact_writer = Box::new(artifact_writer);
+        let root_dir = config.root_dir.clone();
+
+        Compiler::new(Arc::new(config), Arc::new(NoopPerfLogger))
+            .compile()
+            .await
+            .unwrap();
+
+        let artifact = artifacts
+            .get(&root_dir.join("src/__generated__/AppQuery.graphql.ts"))
+            .unwrap();
+        assert!(String::from_utf8_lossy(&artifact).contains("query AppQuery"));
+    }
+}
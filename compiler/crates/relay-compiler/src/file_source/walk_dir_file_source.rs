@@ -18,6 +18,7 @@ use walkdir::WalkDir;
 use super::File;
 use crate::compiler_state::CompilerState;
 use crate::config::Config;
+use crate::config::SchemaLocation;
 use crate::errors::Result;
 use crate::FileSourceResult;
 
@@ -30,12 +31,16 @@ pub struct WalkDirFileSourceResult {
 pub struct WalkDirFileSource<'config> {
     pub config: &'config Config,
     expected_file_extensions: HashSet<&'config str>,
+    schema_files: HashSet<&'config Path>,
 }
 
 fn get_expected_file_extensions(config: &Config) -> HashSet<&str> {
-    let mut file_extensions = HashSet::<&str>::with_capacity(6);
+    let mut file_extensions = HashSet::<&str>::with_capacity(8);
     file_extensions.insert("graphql");
     file_extensions.insert("gql");
+    // Vue and Svelte components, which may be used with any language
+    file_extensions.insert("vue");
+    file_extensions.insert("svelte");
 
     for project in config.enabled_projects() {
         match project.typegen_config.language {
@@ -54,6 +59,18 @@ fn get_expected_file_extensions(config: &Config) -> HashSet<&str> {
     file_extensions
 }
 
+/// Schema files are matched by their path, as they may have extensions that
+/// are otherwise ignored, e.g. `.json` for introspection results.
+fn get_schema_files(config: &Config) -> HashSet<&Path> {
+    config
+        .enabled_projects()
+        .filter_map(|project| match &project.schema_location {
+            SchemaLocation::File(schema_file) => Some(schema_file.as_path()),
+            SchemaLocation::Directory(_) => None,
+        })
+        .collect()
+}
+
 impl<'config> WalkDirFileSource<'config> {
     pub fn new(config: &'config Config) -> Self {
         debug!(
@@ -62,16 +79,18 @@ impl<'config> WalkDirFileSource<'config> {
         Self {
             config,
             expected_file_extensions: get_expected_file_extensions(config),
+            schema_files: get_schema_files(config),
         }
     }
 
     fn should_include_file(&self, name: &Path) -> bool {
-        matches!(
-            name.extension().map(|extension| self
-                .expected_file_extensions
-                .contains(extension.to_str().unwrap())),
-            Some(true)
-        )
+        self.schema_files.contains(name)
+            || matches!(
+                name.extension().map(|extension| self
+                    .expected_file_extensions
+                    .contains(extension.to_str().unwrap())),
+                Some(true)
+            )
     }
 
     fn find_files(&self) -> Vec<File> {
@@ -25,21 +25,34 @@ pub fn get_watchman_expr(config: &Config) -> Expr {
                     .collect::<Vec<_>>()
             })
             .map(|(path, project)| {
+                let mut suffixes = match &project.typegen_config.language {
+                    TypegenLanguage::Flow | TypegenLanguage::JavaScript => {
+                        vec![
+                            PathBuf::from("js"),
+                            PathBuf::from("jsx"),
+                            PathBuf::from("vue"),
+                            PathBuf::from("svelte"),
+                        ]
+                    }
+                    TypegenLanguage::TypeScript => {
+                        vec![
+                            PathBuf::from("js"),
+                            PathBuf::from("jsx"),
+                            PathBuf::from("ts"),
+                            PathBuf::from("tsx"),
+                            PathBuf::from("vue"),
+                            PathBuf::from("svelte"),
+                        ]
+                    }
+                };
+                if project.standalone_documents {
+                    suffixes.push(PathBuf::from("graphql"));
+                    suffixes.push(PathBuf::from("gql"));
+                }
                 Expr::All(vec![
-                    // Ending in *.js(x) or *.ts(x) depending on the project language.
-                    Expr::Suffix(match &project.typegen_config.language {
-                        TypegenLanguage::Flow | TypegenLanguage::JavaScript => {
-                            vec![PathBuf::from("js"), PathBuf::from("jsx")]
-                        }
-                        TypegenLanguage::TypeScript => {
-                            vec![
-                                PathBuf::from("js"),
-                                PathBuf::from("jsx"),
-                                PathBuf::from("ts"),
-                                PathBuf::from("tsx"),
-                            ]
-                        }
-                    }),
+                    // Ending in *.js(x) or *.ts(x) depending on the project language,
+                    // in *.vue or *.svelte, or in *.graphql for standalone documents.
+                    Expr::Suffix(suffixes),
                     // In the related source root.
                     Expr::DirName(DirNameTerm {
                         path: path.clone(),
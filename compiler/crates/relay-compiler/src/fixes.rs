@@ -0,0 +1,336 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Fixes for diagnostics about unused or unknown code, e.g. unused variables.
+//! The fixes remove the code the diagnostic points at, and clean up the lists
+//! and directives that end up empty. The LSP offers them as quick fixes, and
+//! `relay lint --fix` applies them in bulk.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use common::Diagnostic;
+use common::SourceLocationKey;
+use common::Span;
+use extract_graphql::JavaScriptSourceFeature;
+use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_syntax::Directive;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::List;
+use graphql_syntax::Selection;
+use intern::Lookup;
+
+use crate::SourceReader;
+
+const ARGUMENTS: &str = "arguments";
+const ARGUMENT_DEFINITIONS: &str = "argumentDefinitions";
+
+/// The span of a GraphQL source to remove to fix a diagnostic.
+#[derive(Debug, PartialEq)]
+pub struct RemovalFix {
+    pub title: &'static str,
+    pub span: Span,
+}
+
+/// The fix of a diagnostic, identified by its `err` machine readable metadata
+/// and located at `span` of the GraphQL source `text`.
+pub fn get_removal_fix(
+    text: &str,
+    source_location: SourceLocationKey,
+    err: &str,
+    span: Span,
+) -> Option<RemovalFix> {
+    let document = parse_executable_with_error_recovery(text, source_location).item;
+    let definitions = document.definitions.iter();
+    let (title, span) = match err {
+        "UnusedVariable" => (
+            "Remove unused variable",
+            definitions
+                .filter_map(|definition| match definition {
+                    ExecutableDefinition::Operation(operation) => {
+                        operation.variable_definitions.as_ref()
+                    }
+                    ExecutableDefinition::Fragment(_) => None,
+                })
+                .find_map(|list| remove_list_item(list, |item| item.span, span))?,
+        ),
+        "UnusedFragmentVariable" => (
+            "Remove unused fragment variable",
+            definitions
+                .filter_map(|definition| match definition {
+                    ExecutableDefinition::Fragment(fragment) => Some(fragment),
+                    ExecutableDefinition::Operation(_) => None,
+                })
+                .find_map(|fragment| {
+                    fragment
+                        .variable_definitions
+                        .as_ref()
+                        .and_then(|list| remove_list_item(list, |item| item.span, span))
+                        .or_else(|| {
+                            remove_directive_argument(
+                                text,
+                                &fragment.directives,
+                                ARGUMENT_DEFINITIONS,
+                                span,
+                            )
+                        })
+                })?,
+        ),
+        "UnknownFragmentArgument" => (
+            "Remove unknown argument",
+            definitions
+                .map(|definition| match definition {
+                    ExecutableDefinition::Operation(operation) => &operation.selections,
+                    ExecutableDefinition::Fragment(fragment) => &fragment.selections,
+                })
+                .find_map(|selections| remove_spread_argument(text, &selections.items, span))?,
+        ),
+        "UnusedIgnoreUnusedVariablesDirective" => (
+            "Remove unnecessary directive",
+            definitions
+                .filter_map(|definition| match definition {
+                    ExecutableDefinition::Operation(operation) => Some(&operation.directives),
+                    ExecutableDefinition::Fragment(_) => None,
+                })
+                .flatten()
+                .find(|directive| directive.span.contains(span))
+                .map(|directive| directive_removal_span(text, directive))?,
+        ),
+        _ => return None,
+    };
+    Some(RemovalFix { title, span })
+}
+
+/// Applies the fixes of the diagnostics to the files they are located in, and
+/// returns the new contents of the fixed files by path relative to the root dir.
+/// Fixes overlapping with another fix of the same file are left for the next run.
+pub fn apply_removal_fixes(
+    root_dir: &Path,
+    diagnostics: &[Diagnostic],
+    source_reader: &dyn SourceReader,
+) -> HashMap<PathBuf, String> {
+    let mut contents_by_path: HashMap<PathBuf, String> = HashMap::new();
+    let mut removals_by_path: HashMap<PathBuf, Vec<Span>> = HashMap::new();
+    for diagnostic in diagnostics {
+        if let Some((path, span)) =
+            get_file_removal(root_dir, diagnostic, source_reader, &mut contents_by_path)
+        {
+            removals_by_path.entry(path).or_default().push(span);
+        }
+    }
+
+    let mut fixed_contents = HashMap::new();
+    for (path, mut removals) in removals_by_path {
+        removals.sort_by_key(|span| (span.start, span.end));
+        let contents = &contents_by_path[&path];
+        let mut new_contents = String::with_capacity(contents.len());
+        let mut offset = 0;
+        for span in removals {
+            if (span.start as usize) < offset {
+                continue;
+            }
+            new_contents.push_str(&contents[offset..span.start as usize]);
+            offset = span.end as usize;
+        }
+        new_contents.push_str(&contents[offset..]);
+        fixed_contents.insert(path, new_contents);
+    }
+    fixed_contents
+}
+
+/// The span to remove from the whole file to fix the diagnostic.
+fn get_file_removal(
+    root_dir: &Path,
+    diagnostic: &Diagnostic,
+    source_reader: &dyn SourceReader,
+    contents_by_path: &mut HashMap<PathBuf, String>,
+) -> Option<(PathBuf, Span)> {
+    let err = diagnostic.machine_readable()?.remove("err")?;
+    let location = diagnostic.location();
+    let (path, index) = match location.source_location() {
+        SourceLocationKey::Embedded { path, index } => (path, Some(index as usize)),
+        SourceLocationKey::Standalone { path } => (path, None),
+        SourceLocationKey::Generated => return None,
+    };
+    let path = PathBuf::from(path.lookup());
+    let contents = match contents_by_path.entry(path.clone()) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => entry.insert(
+            source_reader
+                .read_file_to_string(&root_dir.join(&path))
+                .ok()?,
+        ),
+    };
+
+    let (text, source_offset) = match index {
+        Some(index) => match extract_graphql::extract_from_path_with_options(
+            &path,
+            contents,
+            &source_reader.extract_options(),
+        )
+        .into_iter()
+        .nth(index)?
+        {
+            JavaScriptSourceFeature::GraphQL(source) => {
+                let text_source = source.text_source();
+                let search_start =
+                    position_offset(contents, text_source.line_index, text_source.column_index)?;
+                let offset = search_start + contents[search_start..].find(&text_source.text)?;
+                (text_source.text.clone(), offset)
+            }
+            JavaScriptSourceFeature::Docblock(_) => return None,
+        },
+        None => (contents.clone(), 0),
+    };
+
+    let fix = get_removal_fix(&text, location.source_location(), &err, location.span())?;
+    Some((path, fix.span.with_offset(source_offset as u32)))
+}
+
+/// Byte offset of a zero-based line and character position.
+fn position_offset(text: &str, line_index: usize, column_index: usize) -> Option<usize> {
+    let line_start = if line_index == 0 {
+        0
+    } else {
+        text.match_indices('\n').nth(line_index - 1)?.0 + 1
+    };
+    text[line_start..]
+        .char_indices()
+        .nth(column_index)
+        .map(|(offset, _)| line_start + offset)
+}
+
+fn remove_spread_argument(text: &str, selections: &[Selection], span: Span) -> Option<Span> {
+    selections.iter().find_map(|selection| match selection {
+        Selection::FragmentSpread(spread) => {
+            remove_directive_argument(text, &spread.directives, ARGUMENTS, span)
+        }
+        Selection::InlineFragment(fragment) => {
+            remove_spread_argument(text, &fragment.selections.items, span)
+        }
+        Selection::LinkedField(field) => {
+            remove_spread_argument(text, &field.selections.items, span)
+        }
+        Selection::ScalarField(_) => None,
+    })
+}
+
+/// Removes the argument at `span` of the directive named `directive_name`,
+/// or the whole directive if it is the only argument.
+fn remove_directive_argument(
+    text: &str,
+    directives: &[Directive],
+    directive_name: &str,
+    span: Span,
+) -> Option<Span> {
+    let directive = directives.iter().find(|directive| {
+        directive.name.value.lookup() == directive_name && directive.span.contains(span)
+    })?;
+    let arguments = directive.arguments.as_ref()?;
+    if arguments.items.len() == 1 && arguments.items[0].span.contains(span) {
+        Some(directive_removal_span(text, directive))
+    } else {
+        remove_list_item(arguments, |argument| argument.span, span)
+    }
+}
+
+/// Removes the item at `span` with its separator, or the whole list (with
+/// its parentheses) if it is the only item.
+fn remove_list_item<T>(list: &List<T>, item_span: impl Fn(&T) -> Span, span: Span) -> Option<Span> {
+    let index = list
+        .items
+        .iter()
+        .position(|item| item_span(item).contains(span))?;
+    let removed = item_span(&list.items[index]);
+    Some(if list.items.len() == 1 {
+        list.span
+    } else if let Some(next) = list.items.get(index + 1) {
+        Span::new(removed.start, item_span(next).start)
+    } else {
+        Span::new(item_span(&list.items[index - 1]).end, removed.end)
+    })
+}
+
+/// The span of the directive, with the whitespace preceding it.
+fn directive_removal_span(text: &str, directive: &Directive) -> Span {
+    let start = text[..directive.span.start as usize].trim_end().len();
+    Span::new(start as u32, directive.span.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_fix(text: &str, err: &str, target: &str) -> String {
+        let start = text.find(target).unwrap();
+        let span = Span::new(start as u32, (start + target.len()) as u32);
+        let fix = get_removal_fix(
+            text,
+            SourceLocationKey::standalone("Foo.graphql"),
+            err,
+            span,
+        )
+        .unwrap();
+        let mut result = text.to_string();
+        result.replace_range(fix.span.start as usize..fix.span.end as usize, "");
+        result
+    }
+
+    #[test]
+    fn removes_unused_variables() {
+        assert_eq!(
+            apply_fix(
+                "query Foo($id: ID!, $size: Int) { node(id: $id) { id } }",
+                "UnusedVariable",
+                "$size"
+            ),
+            "query Foo($id: ID!) { node(id: $id) { id } }"
+        );
+        assert_eq!(
+            apply_fix(
+                "query Foo($size: Int) { me { id } }",
+                "UnusedVariable",
+                "$size"
+            ),
+            "query Foo { me { id } }"
+        );
+    }
+
+    #[test]
+    fn removes_unused_fragment_variables() {
+        assert_eq!(
+            apply_fix(
+                "fragment Foo on User @argumentDefinitions(size: {type: \"Int\"}, first: {type: \"Int\"}) { id }",
+                "UnusedFragmentVariable",
+                "size"
+            ),
+            "fragment Foo on User @argumentDefinitions(first: {type: \"Int\"}) { id }"
+        );
+        assert_eq!(
+            apply_fix(
+                "fragment Foo on User @argumentDefinitions(size: {type: \"Int\"}) { id }",
+                "UnusedFragmentVariable",
+                "size"
+            ),
+            "fragment Foo on User { id }"
+        );
+    }
+
+    #[test]
+    fn removes_empty_arguments_directives() {
+        assert_eq!(
+            apply_fix(
+                "fragment Foo on User { ...Bar @arguments(size: 1) @relay(mask: false) }",
+                "UnknownFragmentArgument",
+                "size: 1"
+            ),
+            "fragment Foo on User { ...Bar @relay(mask: false) }"
+        );
+    }
+}
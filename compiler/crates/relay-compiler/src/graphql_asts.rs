@@ -11,17 +11,24 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use common::Diagnostic;
+use common::FeatureFlags;
+use common::Location;
 use common::SourceLocationKey;
 use dependency_analyzer::ExecutableDefinitionNameSet;
 use dependency_analyzer::ExecutableDefinitionNameVec;
+use extract_graphql::extract_imports;
+use extract_graphql::is_standalone_document;
 use fnv::FnvHashMap;
 use graphql_ir::ExecutableDefinitionName;
 use graphql_ir::FragmentDefinitionName;
 use graphql_ir::OperationDefinitionName;
 use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::FragmentArgumentSyntaxKind;
+use graphql_syntax::ParserFeatures;
 
 use crate::compiler_state::GraphQLSources;
 use crate::compiler_state::ProjectName;
+use crate::config::Config;
 use crate::errors::Error;
 use crate::errors::Result;
 use crate::file_source::LocatedGraphQLSource;
@@ -48,15 +55,23 @@ impl GraphQLAsts {
     }
 
     pub fn from_graphql_sources_map(
+        config: &Config,
         graphql_sources_map: &FnvHashMap<ProjectName, GraphQLSources>,
         dirty_definitions_map: &FnvHashMap<ProjectName, Vec<ExecutableDefinitionName>>,
     ) -> Result<FnvHashMap<ProjectName, GraphQLAsts>> {
         graphql_sources_map
             .iter()
             .map(|(&project_name, sources)| {
+                let features = config
+                    .projects
+                    .get(&project_name)
+                    .map_or_else(ParserFeatures::default, |project_config| {
+                        parser_features(&project_config.feature_flags)
+                    });
                 let asts = GraphQLAsts::from_graphql_sources(
                     sources,
                     dirty_definitions_map.get(&project_name),
+                    features,
                 )?;
                 Ok((project_name, asts))
             })
@@ -69,6 +84,7 @@ impl GraphQLAsts {
     pub fn from_graphql_sources(
         graphql_sources: &GraphQLSources,
         dirty_definitions: Option<&Vec<ExecutableDefinitionName>>,
+        features: ParserFeatures,
     ) -> Result<Self> {
         let mut syntax_errors = Vec::new();
 
@@ -92,9 +108,10 @@ impl GraphQLAsts {
             {
                 let source_location =
                     SourceLocationKey::embedded(&file_name.to_string_lossy(), *index);
-                match graphql_syntax::parse_executable(
+                match graphql_syntax::parse_executable_with_features(
                     &graphql_source.text_source().text,
                     source_location,
+                    features,
                 ) {
                     Ok(document) => {
                         for def in &document.definitions {
@@ -117,6 +134,11 @@ impl GraphQLAsts {
                     Err(errors) => syntax_errors.extend(errors),
                 }
             }
+            syntax_errors.extend(validate_imports(
+                graphql_sources,
+                file_name,
+                pending_graphql_sources,
+            ));
             // Parse the processed source to get all processed definition names
             // and collect definition names that are removed from that file.
             // (A definition moved to another file is considered as a deletion and a new source)
@@ -129,9 +151,10 @@ impl GraphQLAsts {
                     // TODO: parse name instead of the whole graphql text
                     let source_location =
                         SourceLocationKey::embedded(&file_name.to_string_lossy(), *index);
-                    if let Ok(document) = graphql_syntax::parse_executable(
+                    if let Ok(document) = graphql_syntax::parse_executable_with_features(
                         &graphql_source.text_source().text,
                         source_location,
+                        features,
                     ) {
                         for def in document.definitions {
                             let name = def.name();
@@ -172,9 +195,10 @@ impl GraphQLAsts {
             {
                 let source_location =
                     SourceLocationKey::embedded(&file_name.to_string_lossy(), *index);
-                match graphql_syntax::parse_executable(
+                match graphql_syntax::parse_executable_with_features(
                     &graphql_source.text_source().text,
                     source_location,
+                    features,
                 ) {
                     Ok(document) => {
                         definitions_for_file.extend(document.definitions);
@@ -182,6 +206,11 @@ impl GraphQLAsts {
                     Err(errors) => syntax_errors.extend(errors),
                 }
             }
+            syntax_errors.extend(validate_imports(
+                graphql_sources,
+                file_name,
+                processed_graphql_sources,
+            ));
             match asts.entry(file_name.clone()) {
                 Entry::Vacant(entry) => {
                     entry.insert(definitions_for_file);
@@ -206,3 +235,137 @@ impl GraphQLAsts {
         }
     }
 }
+
+/// The syntax of the documents of a project, as enabled by its feature flags.
+pub fn parser_features(feature_flags: &FeatureFlags) -> ParserFeatures {
+    ParserFeatures {
+        fragment_argument_capability: if feature_flags.enable_fragment_variable_definitions {
+            FragmentArgumentSyntaxKind::OnlyFragmentVariableDefinitions
+        } else {
+            FragmentArgumentSyntaxKind::None
+        },
+    }
+}
+
+/// Reports the `#import`s of a standalone document that don't name another
+/// document of the project.
+fn validate_imports(
+    graphql_sources: &GraphQLSources,
+    file_name: &Path,
+    located_graphql_sources: &[LocatedGraphQLSource],
+) -> Vec<Diagnostic> {
+    if !is_standalone_document(file_name) {
+        return vec![];
+    }
+    let mut errors = vec![];
+    for LocatedGraphQLSource {
+        index,
+        graphql_source,
+    } in located_graphql_sources
+    {
+        let source_location = SourceLocationKey::embedded(&file_name.to_string_lossy(), *index);
+        for import in extract_imports(&graphql_source.text_source().text) {
+            let imported_file_name = import.resolve(file_name);
+            let is_document = graphql_sources
+                .pending
+                .get(&imported_file_name)
+                .or_else(|| graphql_sources.processed.get(&imported_file_name))
+                .map_or(false, |sources| !sources.is_empty());
+            if !is_document {
+                errors.push(Diagnostic::error(
+                    format!(
+                        "Cannot find the imported document '{}' in the sources of the project",
+                        import.path
+                    ),
+                    Location::new(source_location, import.span),
+                ));
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Span;
+    use graphql_syntax::GraphQLSource;
+
+    use super::*;
+    use crate::compiler_state::IncrementalSources;
+
+    fn standalone_document(text: &str) -> Vec<LocatedGraphQLSource> {
+        vec![LocatedGraphQLSource {
+            index: 0,
+            graphql_source: GraphQLSource::new(text, 0, 0),
+        }]
+    }
+
+    #[test]
+    fn validates_imports_of_standalone_documents() {
+        let mut pending = FnvHashMap::default();
+        pending.insert(
+            PathBuf::from("src/FooQuery.graphql"),
+            standalone_document(
+                "#import \"./Foo_user.graphql\"\n#import \"../Bar_user.graphql\"\nquery FooQuery { me { ...Foo_user ...Bar_user } }",
+            ),
+        );
+        pending.insert(
+            PathBuf::from("src/Foo_user.graphql"),
+            standalone_document("fragment Foo_user on User { id }"),
+        );
+        let graphql_sources = IncrementalSources {
+            pending,
+            processed: Default::default(),
+        };
+
+        let errors = match GraphQLAsts::from_graphql_sources(
+            &graphql_sources,
+            None,
+            ParserFeatures::default(),
+        ) {
+            Err(Error::DiagnosticsError { errors }) => errors,
+            _ => panic!("Expected the missing import to be reported"),
+        };
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message().to_string(),
+            "Cannot find the imported document '../Bar_user.graphql' in the sources of the project"
+        );
+        assert_eq!(errors[0].location().span(), Span::new(38, 57));
+    }
+
+    #[test]
+    fn parses_fragment_variable_definitions_if_enabled() {
+        let mut pending = FnvHashMap::default();
+        pending.insert(
+            PathBuf::from("src/Foo_user.graphql"),
+            standalone_document("fragment Foo_user($size: Int) on User { id }"),
+        );
+        let graphql_sources = IncrementalSources {
+            pending,
+            processed: Default::default(),
+        };
+
+        assert!(
+            GraphQLAsts::from_graphql_sources(&graphql_sources, None, ParserFeatures::default())
+                .is_err()
+        );
+        let feature_flags = FeatureFlags {
+            enable_fragment_variable_definitions: true,
+            ..Default::default()
+        };
+        let asts = GraphQLAsts::from_graphql_sources(
+            &graphql_sources,
+            None,
+            parser_features(&feature_flags),
+        )
+        .unwrap();
+        match &asts.get_all_executable_definitions()[0] {
+            ExecutableDefinition::Fragment(fragment) => {
+                let variable_definitions = fragment.variable_definitions.as_ref().unwrap();
+                assert_eq!(variable_definitions.items.len(), 1);
+            }
+            ExecutableDefinition::Operation(_) => panic!("Expected a fragment"),
+        }
+    }
+}
@@ -0,0 +1,356 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Reads the Relay config from a [graphql-config](https://the-guild.dev/graphql/config)
+//! file, like `.graphqlrc.yml`, so that projects that configure other GraphQL
+//! tools with it don't need a second config file. The Relay options are in
+//! `extensions.relay`, and the `schema` and `documents` of graphql-config are
+//! used if Relay's `schema` and `src` aren't set:
+//!
+//! ```yaml
+//! schema: ./schema.graphql
+//! documents: ./src/**/*.tsx
+//! extensions:
+//!   relay:
+//!     language: typescript
+//! ```
+//!
+//! With graphql-config `projects`, each project with a Relay extension is a
+//! Relay project, and the Relay extension at the top level has the options of
+//! all the projects, like `featureFlags`. Files without a Relay extension are
+//! not Relay configs.
+
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use js_config_loader::LoaderSource;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+const RC_FILE_NAME: &str = ".graphqlrc";
+const CONFIG_FILE_NAME: &str = "graphql.config";
+
+/// The files of graphql-config, in the order graphql-config looks for them.
+pub(crate) fn graphql_config_sources() -> Vec<LoaderSource> {
+    vec![
+        LoaderSource::PackageJson("graphql".to_string()),
+        // The rc file without an extension is either YAML or JSON.
+        LoaderSource::Yaml(RC_FILE_NAME.to_string()),
+        LoaderSource::Json(format!("{}.json", RC_FILE_NAME)),
+        LoaderSource::Yaml(format!("{}.yaml", RC_FILE_NAME)),
+        LoaderSource::Yaml(format!("{}.yml", RC_FILE_NAME)),
+        LoaderSource::Js(format!("{}.js", RC_FILE_NAME)),
+        LoaderSource::Js(format!("{}.ts", RC_FILE_NAME)),
+        LoaderSource::Json(format!("{}.json", CONFIG_FILE_NAME)),
+        LoaderSource::Yaml(format!("{}.yaml", CONFIG_FILE_NAME)),
+        LoaderSource::Yaml(format!("{}.yml", CONFIG_FILE_NAME)),
+        LoaderSource::Js(format!("{}.js", CONFIG_FILE_NAME)),
+        LoaderSource::Js(format!("{}.cjs", CONFIG_FILE_NAME)),
+        LoaderSource::Js(format!("{}.mjs", CONFIG_FILE_NAME)),
+        LoaderSource::Js(format!("{}.ts", CONFIG_FILE_NAME)),
+    ]
+}
+
+/// The loader of a config file passed on the command line, if it's a
+/// graphql-config file.
+pub(crate) fn graphql_config_source(path: &Path) -> Option<LoaderSource> {
+    let file_name = path.file_name()?.to_str()?;
+    if file_name != RC_FILE_NAME
+        && !file_name.starts_with(&format!("{}.", RC_FILE_NAME))
+        && !file_name.starts_with(&format!("{}.", CONFIG_FILE_NAME))
+    {
+        return None;
+    }
+    let path = path.display().to_string();
+    Some(
+        match Path::new(file_name).extension().and_then(|e| e.to_str()) {
+            None | Some("yaml" | "yml") => LoaderSource::Yaml(path),
+            Some("json") => LoaderSource::Json(path),
+            Some(_) => LoaderSource::Js(path),
+        },
+    )
+}
+
+/// Converts a graphql-config file to a Relay config file, or returns `None`
+/// if it doesn't configure Relay.
+pub(crate) fn relay_config_from_graphql_config(value: Value) -> Result<Option<Value>, String> {
+    let mut config = match value {
+        Value::Object(config) => config,
+        _ => return Err("Expected the graphql-config to be an object.".to_string()),
+    };
+    let relay = take_relay_extension(&mut config, "extensions.relay")?;
+    let projects = match config.remove("projects") {
+        None => {
+            return Ok(relay.map(|mut relay| {
+                // Unless the extension is a complete multi-project config.
+                if !relay.contains_key("sources") && !relay.contains_key("projects") {
+                    add_schema(&mut relay, &config);
+                    if !relay.contains_key("src") {
+                        if let Some(src) = config.get("documents").and_then(documents_directory) {
+                            relay.insert("src".to_string(), src.into());
+                        }
+                    }
+                }
+                Value::Object(relay)
+            }));
+        }
+        Some(Value::Object(projects)) => projects,
+        Some(_) => return Err("Expected `projects` to be an object.".to_string()),
+    };
+
+    let mut sources = Map::new();
+    let mut relay_projects = Map::new();
+    for (project_name, project) in projects {
+        let mut project = match project {
+            Value::Object(project) => project,
+            _ => {
+                return Err(format!(
+                    "Expected the project `{}` to be an object.",
+                    project_name
+                ));
+            }
+        };
+        let path = format!("projects.{}.extensions.relay", project_name);
+        let mut relay_project = match take_relay_extension(&mut project, &path)? {
+            Some(relay_project) => relay_project,
+            None => continue,
+        };
+        add_schema(&mut relay_project, &project);
+        let src = match relay_project.remove("src") {
+            Some(Value::String(src)) => src,
+            Some(_) => return Err(format!("Expected `{}.src` to be a string.", path)),
+            None => project
+                .get("documents")
+                .and_then(documents_directory)
+                .ok_or_else(|| {
+                    format!(
+                        "The project `{}` needs `documents` or `{}.src`.",
+                        project_name, path
+                    )
+                })?,
+        };
+        add_source(&mut sources, src, &project_name);
+        relay_projects.insert(project_name, Value::Object(relay_project));
+    }
+    if relay_projects.is_empty() {
+        return Ok(None);
+    }
+
+    let mut relay = relay.unwrap_or_default();
+    for (key, entries) in [("sources", sources), ("projects", relay_projects)] {
+        match relay
+            .entry(key)
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            Value::Object(existing) => existing.extend(entries),
+            _ => {
+                return Err(format!(
+                    "Expected `extensions.relay.{}` to be an object.",
+                    key
+                ));
+            }
+        }
+    }
+    Ok(Some(Value::Object(relay)))
+}
+
+fn take_relay_extension(
+    config: &mut Map<String, Value>,
+    path: &str,
+) -> Result<Option<Map<String, Value>>, String> {
+    match config
+        .get_mut("extensions")
+        .and_then(|extensions| extensions.as_object_mut())
+        .and_then(|extensions| extensions.remove("relay"))
+    {
+        None => Ok(None),
+        Some(Value::Object(relay)) => Ok(Some(relay)),
+        Some(_) => Err(format!("Expected `{}` to be an object.", path)),
+    }
+}
+
+/// Uses the graphql-config schema if it's a single file or URL, which is all
+/// that Relay supports.
+fn add_schema(relay: &mut Map<String, Value>, config: &Map<String, Value>) {
+    if relay.contains_key("schema") || relay.contains_key("schemaDir") {
+        return;
+    }
+    let schema = match config.get("schema") {
+        Some(Value::Array(schemas)) if schemas.len() == 1 => &schemas[0],
+        Some(schema) => schema,
+        None => return,
+    };
+    let schema = match schema {
+        Value::String(schema)
+            if schema.starts_with("http://") || schema.starts_with("https://") =>
+        {
+            json!({ "url": schema })
+        }
+        Value::String(schema) => schema.clone().into(),
+        // A URL with options, like `{"https://...": {"headers": {...}}}`.
+        Value::Object(urls) if urls.len() == 1 => {
+            let (url, options) = urls.iter().next().unwrap();
+            json!({
+                "url": url,
+                "headers": options.get("headers").cloned().unwrap_or_else(|| json!({})),
+            })
+        }
+        // Relay reports the missing schema.
+        _ => return,
+    };
+    relay.insert("schema".to_string(), schema);
+}
+
+fn add_source(sources: &mut Map<String, Value>, src: String, project_name: &str) {
+    match sources.get_mut(&src) {
+        Some(Value::Array(project_names)) => project_names.push(project_name.into()),
+        Some(existing) => *existing = Value::Array(vec![existing.take(), project_name.into()]),
+        None => {
+            sources.insert(src, project_name.into());
+        }
+    }
+}
+
+/// The directory with all the documents of the graphql-config `documents`
+/// globs, which Relay uses as `src`.
+fn documents_directory(documents: &Value) -> Option<String> {
+    let globs: Vec<&str> = match documents {
+        Value::String(glob) => vec![glob],
+        Value::Array(globs) => globs.iter().filter_map(|glob| glob.as_str()).collect(),
+        _ => vec![],
+    };
+    globs
+        .into_iter()
+        // Excludes
+        .filter(|glob| !glob.starts_with('!'))
+        .map(glob_directory)
+        .reduce(|a, b| common_directory(&a, &b))
+        .map(|directory| {
+            if directory.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                directory.display().to_string()
+            }
+        })
+}
+
+/// The part of a glob before the first component with a pattern, or the
+/// directory of a single file.
+fn glob_directory(glob: &str) -> PathBuf {
+    let mut directory = PathBuf::new();
+    let mut components = Path::new(glob).components().peekable();
+    while let Some(component) = components.next() {
+        let is_pattern = component
+            .as_os_str()
+            .to_str()
+            .map_or(false, |component| component.contains(['*', '?', '[', '{']));
+        // The last component is a file, if it's not a pattern.
+        if is_pattern || components.peek().is_none() {
+            break;
+        }
+        if component != Component::CurDir {
+            directory.push(component);
+        }
+    }
+    directory
+}
+
+fn common_directory(a: &Path, b: &Path) -> PathBuf {
+    a.components()
+        .zip(b.components())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_project() {
+        let config = json!({
+            "schema": "./schema.graphql",
+            "documents": "./src/**/*.tsx",
+            "extensions": { "relay": { "language": "typescript" } }
+        });
+        assert_eq!(
+            relay_config_from_graphql_config(config),
+            Ok(Some(json!({
+                "language": "typescript",
+                "schema": "./schema.graphql",
+                "src": "src"
+            })))
+        );
+    }
+
+    #[test]
+    fn relay_options_take_precedence() {
+        let config = json!({
+            "schema": ["https://example.com/graphql"],
+            "documents": ["app/**/*.graphql", "app/components/*.tsx", "!app/generated/**"],
+            "extensions": { "relay": { "src": "./app/components" } }
+        });
+        assert_eq!(
+            relay_config_from_graphql_config(config),
+            Ok(Some(json!({
+                "src": "./app/components",
+                "schema": { "url": "https://example.com/graphql" }
+            })))
+        );
+    }
+
+    #[test]
+    fn projects() {
+        let config = json!({
+            "projects": {
+                "web": {
+                    "schema": "web/schema.graphql",
+                    "documents": ["web/src/**/*.ts", "web/lib/*.ts"],
+                    "extensions": { "relay": { "language": "typescript" } }
+                },
+                "web_native": {
+                    "schema": "native/schema.graphql",
+                    "documents": "web/**/*.ts",
+                    "extensions": { "relay": { "language": "flow" } }
+                },
+                "codegen": { "schema": "codegen/schema.graphql" }
+            },
+            "extensions": { "relay": { "featureFlags": {} } }
+        });
+        assert_eq!(
+            relay_config_from_graphql_config(config),
+            Ok(Some(json!({
+                "featureFlags": {},
+                "sources": { "web": ["web", "web_native"] },
+                "projects": {
+                    "web": { "language": "typescript", "schema": "web/schema.graphql" },
+                    "web_native": { "language": "flow", "schema": "native/schema.graphql" }
+                }
+            })))
+        );
+    }
+
+    #[test]
+    fn not_a_relay_config() {
+        let config = json!({
+            "schema": "schema.graphql",
+            "documents": "src/**/*.graphql",
+            "extensions": { "codegen": {} }
+        });
+        assert_eq!(relay_config_from_graphql_config(config), Ok(None));
+    }
+
+    #[test]
+    fn config_file_sources() {
+        assert!(graphql_config_source(Path::new("/repo/.graphqlrc")).is_some());
+        assert!(graphql_config_source(Path::new("graphql.config.ts")).is_some());
+        assert!(graphql_config_source(Path::new("relay.config.json")).is_none());
+    }
+}
@@ -9,25 +9,50 @@
 #![deny(rust_2018_idioms)]
 #![deny(clippy::all)]
 
+pub mod allocations;
 pub mod artifact_content;
 mod artifact_map;
+pub mod bench;
 pub mod build_project;
+pub mod build_report;
+pub mod build_stats;
+pub mod chrome_trace;
 pub mod compiler;
 pub mod compiler_state;
 pub mod config;
+mod config_deserializer;
+mod deduplicate_diagnostics;
+pub mod dependency_graph;
+pub mod diagnostics_json;
+pub mod diagnostics_summary;
 mod docblocks;
+pub mod doctor;
 pub mod errors;
+pub mod explain;
 mod file_source;
+pub mod fixes;
 mod graphql_asts;
+mod graphql_config;
+pub mod lint;
+pub mod log_groups;
 mod operation_persister;
+pub mod otlp;
+pub mod preview;
 mod red_to_green;
+mod remote_schema;
+pub mod sarif;
 pub mod saved_state;
+mod schema_cache;
+pub mod schema_impact;
+pub mod schema_lint;
 pub mod status_reporter;
 
 pub use build_project::add_to_mercurial;
 pub use build_project::artifact_writer::ArtifactDifferenceShardedWriter;
 pub use build_project::artifact_writer::ArtifactDifferenceWriter;
 pub use build_project::artifact_writer::ArtifactFileWriter;
+pub use build_project::artifact_writer::ArtifactMemoryWriter;
+pub use build_project::artifact_writer::ArtifactOutputRootWriter;
 pub use build_project::artifact_writer::ArtifactValidationWriter;
 pub use build_project::artifact_writer::ArtifactWriter;
 pub use build_project::artifact_writer::NoopArtifactWriter;
@@ -37,6 +62,8 @@ pub use build_project::build_schema;
 pub use build_project::find_duplicates;
 pub use build_project::generate_artifacts;
 pub use build_project::generate_extra_artifacts::GenerateExtraArtifactsFn;
+pub use build_project::git_changed_files_since;
+pub use build_project::operation_text_printer_options;
 pub use build_project::transform_program;
 pub use build_project::validate;
 pub use build_project::validate_program;
@@ -50,22 +77,29 @@ pub use config::ConfigFileProject;
 pub use config::FileSourceKind;
 pub use config::LocalPersistConfig;
 pub use config::OperationPersister;
+pub use config::OtlpConfig;
 pub use config::PersistConfig;
 pub use config::ProjectConfig;
 pub use config::RemotePersistConfig;
+pub use config::RemoteSchemaConfig;
 pub use config::SchemaLocation;
 pub use file_source::source_for_location;
 pub use file_source::ExternalFileSourceResult;
 pub use file_source::File;
 pub use file_source::FileCategorizer;
 pub use file_source::FileGroup;
+pub use file_source::FileListFileSourceResult;
 pub use file_source::FileSource;
 pub use file_source::FileSourceResult;
 pub use file_source::FileSourceSubscription;
 pub use file_source::FileSourceSubscriptionNextChange;
 pub use file_source::FsSourceReader;
+pub use file_source::FsVfs;
+pub use file_source::MemoryVfs;
 pub use file_source::SourceControlUpdateStatus;
 pub use file_source::SourceReader;
+pub use file_source::Vfs;
+pub use graphql_asts::parser_features;
 pub use graphql_asts::GraphQLAsts;
 pub use operation_persister::LocalPersister;
 pub use operation_persister::RemotePersister;
@@ -0,0 +1,160 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Printing of the diagnostics returned by `Compiler::lint`.
+
+use std::path::Path;
+
+use common::Diagnostic;
+use common::DiagnosticSeverity;
+use common::Location;
+use common::TextSource;
+use graphql_cli::DiagnosticPrinter;
+use serde::Serialize;
+
+use crate::source_for_location;
+use crate::SourceReader;
+
+/// A diagnostic in the format of `relay lint --format json`. Lines and
+/// columns are 1-based and omitted for generated locations.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFinding {
+    pub severity: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+}
+
+pub fn lint_findings(
+    root_dir: &Path,
+    diagnostics: &[Diagnostic],
+    source_reader: &dyn SourceReader,
+) -> Vec<LintFinding> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let location = diagnostic.location();
+            let position = line_and_column(root_dir, location, source_reader);
+            LintFinding {
+                severity: severity_name(diagnostic.severity()),
+                message: diagnostic.message().to_string(),
+                file: if location.source_location().is_generated() {
+                    None
+                } else {
+                    Some(location.source_location().path())
+                },
+                line: position.map(|(line, _)| line),
+                column: position.map(|(_, column)| column),
+            }
+        })
+        .collect()
+}
+
+pub fn print_lint_json(
+    root_dir: &Path,
+    diagnostics: &[Diagnostic],
+    source_reader: &dyn SourceReader,
+) -> String {
+    serde_json::to_string_pretty(&lint_findings(root_dir, diagnostics, source_reader)).unwrap()
+}
+
+pub fn print_lint_text(
+    root_dir: &Path,
+    diagnostics: &[Diagnostic],
+    source_reader: &dyn SourceReader,
+) -> String {
+    let printer = DiagnosticPrinter::new(|source_location| {
+        source_for_location(root_dir, source_location, source_reader)
+            .map(|source| source.to_text_source())
+    });
+    printer.diagnostics_to_string(diagnostics)
+}
+
+/// The 1-based line and column of the start of the location, if its source
+/// can be read.
+pub fn line_and_column(
+    root_dir: &Path,
+    location: Location,
+    source_reader: &dyn SourceReader,
+) -> Option<(u32, u32)> {
+    text_source(root_dir, location, source_reader).map(|source| {
+        let range = source.to_span_range(location.span());
+        (range.start.line + 1, range.start.character + 1)
+    })
+}
+
+fn text_source(
+    root_dir: &Path,
+    location: Location,
+    source_reader: &dyn SourceReader,
+) -> Option<TextSource> {
+    source_for_location(root_dir, location.source_location(), source_reader)
+        .map(|source| source.to_text_source())
+}
+
+fn severity_name(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::ERROR => "error",
+        DiagnosticSeverity::WARNING => "warning",
+        DiagnosticSeverity::INFORMATION => "info",
+        _ => "hint",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use common::SourceLocationKey;
+    use common::Span;
+
+    use super::*;
+
+    struct TestSourceReader;
+
+    impl SourceReader for TestSourceReader {
+        fn read_file_to_string(&self, _path: &PathBuf) -> std::io::Result<String> {
+            Ok("query Foo {\n  me { nam }\n}\n".to_string())
+        }
+    }
+
+    #[test]
+    fn lint_findings_have_positions() {
+        let diagnostics = vec![
+            Diagnostic::error(
+                "Unknown field `nam`",
+                Location::new(
+                    SourceLocationKey::standalone("src/Foo.graphql"),
+                    Span::new(19, 22),
+                ),
+            ),
+            Diagnostic::warning("Generated", Location::generated(), vec![]),
+        ];
+        let json = print_lint_json(Path::new("/"), &diagnostics, &TestSourceReader);
+        assert_eq!(
+            json,
+            r#"[
+  {
+    "severity": "error",
+    "message": "Unknown field `nam`",
+    "file": "src/Foo.graphql",
+    "line": 2,
+    "column": 8
+  },
+  {
+    "severity": "warning",
+    "message": "Generated"
+  }
+]"#
+        );
+    }
+}
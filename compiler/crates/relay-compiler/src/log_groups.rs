@@ -0,0 +1,197 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! When projects are built in parallel, the log lines of each project are
+//! buffered and written together when the project is built, instead of being
+//! interleaved with the lines of the other projects. Only the lines logged on
+//! the thread that builds the project are grouped.
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+use log::Level;
+use log::Log;
+use log::Metadata;
+use log::Record;
+
+thread_local! {
+    /// The lines of the group of the current thread, if there is one.
+    static GROUP: RefCell<Option<Vec<LogLine>>> = const { RefCell::new(None) };
+
+    /// Whether the current thread writes a group, and holds `OUTPUT`.
+    static WRITING_GROUP: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Held while a group is written, so that it isn't interleaved with lines
+/// of other threads.
+static OUTPUT: Mutex<()> = Mutex::new(());
+
+struct LogLine {
+    level: Level,
+    target: String,
+    message: String,
+}
+
+/// A logger that buffers the lines logged in `with_log_group`, and otherwise
+/// logs with the `inner` logger.
+pub struct GroupedLogger {
+    inner: Box<dyn Log>,
+}
+
+impl GroupedLogger {
+    pub fn new(inner: Box<dyn Log>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Log for GroupedLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let buffered = GROUP.with(|group| match &mut *group.borrow_mut() {
+            Some(lines) => {
+                lines.push(LogLine {
+                    level: record.level(),
+                    target: record.target().to_string(),
+                    message: record.args().to_string(),
+                });
+                true
+            }
+            None => false,
+        });
+        if buffered {
+            return;
+        }
+        if WRITING_GROUP.with(Cell::get) {
+            self.inner.log(record);
+        } else {
+            let _output = OUTPUT.lock().unwrap();
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush()
+    }
+}
+
+/// Runs `f` and writes the lines it logged together afterwards, if the
+/// logger is a `GroupedLogger`. Groups can be nested, e.g. when a thread of
+/// the thread pool builds another project while it waits.
+pub(crate) fn with_log_group<R>(f: impl FnOnce() -> R) -> R {
+    with_log_group_to(log::logger(), f)
+}
+
+fn with_log_group_to<R>(logger: &dyn Log, f: impl FnOnce() -> R) -> R {
+    let outer_group = GROUP.with(|group| group.replace(Some(Vec::new())));
+    let result = f();
+    let lines = GROUP
+        .with(|group| group.replace(None))
+        .expect("Expected the log group to be set.");
+    if !lines.is_empty() {
+        let _output = OUTPUT.lock().unwrap();
+        WRITING_GROUP.with(|writing_group| writing_group.set(true));
+        for line in lines {
+            logger.log(
+                &Record::builder()
+                    .level(line.level)
+                    .target(&line.target)
+                    .args(format_args!("{}", line.message))
+                    .build(),
+            );
+        }
+        WRITING_GROUP.with(|writing_group| writing_group.set(false));
+    }
+    GROUP.with(|group| group.replace(outer_group));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::Barrier;
+    use std::thread;
+
+    use super::*;
+
+    struct CollectingLogger(Arc<Mutex<Vec<String>>>);
+
+    impl Log for CollectingLogger {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn log(logger: &dyn Log, message: &str) {
+        logger.log(
+            &Record::builder()
+                .level(Level::Info)
+                .args(format_args!("{}", message))
+                .build(),
+        );
+    }
+
+    #[test]
+    fn writes_the_lines_of_a_group_together() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let logger = GroupedLogger::new(Box::new(CollectingLogger(Arc::clone(&lines))));
+        let logger = &logger;
+        let barrier = Barrier::new(2);
+        thread::scope(|scope| {
+            for project in ["a", "b"] {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    with_log_group_to(logger, || {
+                        log(logger, &format!("[{}] compiling...", project));
+                        // Both projects log while the other one is compiling.
+                        barrier.wait();
+                        log(logger, &format!("[{}] compiled", project));
+                    });
+                });
+            }
+        });
+        log(logger, "done");
+
+        let lines = lines.lock().unwrap();
+        assert_eq!(lines.len(), 5);
+        for group in lines[..4].chunks(2) {
+            let project = &group[0][1..2];
+            assert_eq!(
+                group,
+                [
+                    format!("[{}] compiling...", project),
+                    format!("[{}] compiled", project)
+                ]
+            );
+        }
+        assert_eq!(lines[4], "done");
+    }
+
+    #[test]
+    fn writes_nested_groups_before_the_outer_group() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let logger = GroupedLogger::new(Box::new(CollectingLogger(Arc::clone(&lines))));
+        with_log_group_to(&logger, || {
+            log(&logger, "outer");
+            with_log_group_to(&logger, || log(&logger, "inner"));
+            assert_eq!(*lines.lock().unwrap(), ["inner"]);
+        });
+        assert_eq!(*lines.lock().unwrap(), ["inner", "outer"]);
+    }
+}
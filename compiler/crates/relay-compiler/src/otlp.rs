@@ -0,0 +1,333 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Export of a `ChromeTrace` to an OpenTelemetry collector with OTLP over
+//! HTTP and JSON: the timers as spans of one trace per build, and the logged
+//! numbers, e.g. diagnostics and artifact counts, as gauges. Sending requires
+//! the `otlp` feature.
+
+use std::collections::BTreeMap;
+use std::time::UNIX_EPOCH;
+
+use fnv::FnvHashMap;
+#[cfg(feature = "otlp")]
+use hyper::Body;
+#[cfg(feature = "otlp")]
+use hyper::Client;
+#[cfg(feature = "otlp")]
+use hyper::Method;
+#[cfg(feature = "otlp")]
+use hyper::Request;
+#[cfg(feature = "otlp")]
+use hyper_tls::HttpsConnector;
+use md5::Digest;
+use md5::Md5;
+use serde_json::json;
+use serde_json::Value;
+
+use crate::chrome_trace::ChromeTrace;
+use crate::config::OtlpConfig;
+#[cfg(feature = "otlp")]
+use crate::errors::Error;
+#[cfg(feature = "otlp")]
+use crate::errors::Result;
+
+const SCOPE_NAME: &str = "relay-compiler";
+
+/// Cache hit rates that are derived from the counts of hits and misses.
+const HIT_RATES: [(&str, &str, &str); 2] = [
+    (
+        "persist_cache_hit_rate",
+        "persist_reused_documents",
+        "persist_documents",
+    ),
+    (
+        "artifact_cache_hit_rate",
+        "unchanged_artifacts",
+        "written_artifacts",
+    ),
+];
+
+/// Sends the trace and metrics of a build to the collector.
+#[cfg(feature = "otlp")]
+pub async fn export(config: &OtlpConfig, trace: &ChromeTrace, success: bool) -> Result<()> {
+    post(config, "v1/traces", traces_json(config, trace, success)).await?;
+    post(config, "v1/metrics", metrics_json(config, trace)).await
+}
+
+#[cfg(feature = "otlp")]
+async fn post(config: &OtlpConfig, path: &str, body: Value) -> Result<()> {
+    let uri = format!("{}/{}", config.endpoint.trim_end_matches('/'), path);
+    let error = |reason: String| Error::OtlpExportError {
+        uri: uri.clone(),
+        reason,
+    };
+    let mut builder = Request::builder()
+        .method(Method::POST)
+        .uri(&uri)
+        .header("content-type", "application/json");
+    for (key, value) in &config.headers {
+        builder = builder.header(key, value);
+    }
+    let request = builder
+        .body(Body::from(body.to_string()))
+        .map_err(|err| error(err.to_string()))?;
+    let client = Client::builder().build(HttpsConnector::new());
+    let response = client
+        .request(request)
+        .await
+        .map_err(|err| error(err.to_string()))?;
+    if !response.status().is_success() {
+        return Err(error(format!(
+            "the collector responded with {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+/// The `ExportTraceServiceRequest` with a root span for the build, and the
+/// timers nested in it as they are nested in time on each thread.
+pub fn traces_json(config: &OtlpConfig, trace: &ChromeTrace, success: bool) -> Value {
+    let start_nanos = trace
+        .start_time()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let unix_nanos = |micros: u128| (start_nanos + micros * 1000).to_string();
+    let trace_id = {
+        let mut md5 = Md5::new();
+        md5.update(format!("{}:{}", std::process::id(), start_nanos));
+        hex::encode(md5.finalize())
+    };
+    let span_id = |index: usize| format!("{:016x}", index + 1);
+    let root_span_id = span_id(0);
+
+    let mut spans = vec![json!({
+        "traceId": trace_id,
+        "spanId": root_span_id,
+        "name": "relay_build",
+        "kind": 1,
+        "startTimeUnixNano": unix_nanos(0),
+        "endTimeUnixNano": unix_nanos(trace.elapsed_micros()),
+        "status": { "code": if success { 1 } else { 2 } },
+    })];
+    // The ends and ids of the timers that are running on each thread.
+    let mut running: FnvHashMap<u64, Vec<(u128, String)>> = Default::default();
+    for event in trace.sorted_events().iter().filter(|event| event.ph == "X") {
+        let id = span_id(spans.len());
+        let end = event.ts + event.dur;
+        let stack = running.entry(event.tid).or_default();
+        // Allows for the rounding of the start and duration to microseconds.
+        while matches!(stack.last(), Some((parent_end, _)) if *parent_end + 1 < end) {
+            stack.pop();
+        }
+        let parent_id = stack
+            .last()
+            .map_or_else(|| root_span_id.clone(), |(_, id)| id.clone());
+        stack.push((end, id.clone()));
+
+        let mut attributes = vec![string_attribute("relay.event", event.cat)];
+        if let Some(project) = &event.args.project {
+            attributes.push(string_attribute("relay.project", project));
+        }
+        spans.push(json!({
+            "traceId": trace_id,
+            "spanId": id,
+            "parentSpanId": parent_id,
+            "name": event.name,
+            "kind": 1,
+            "startTimeUnixNano": unix_nanos(event.ts),
+            "endTimeUnixNano": unix_nanos(end),
+            "attributes": attributes,
+        }));
+    }
+
+    json!({
+        "resourceSpans": [{
+            "resource": resource_json(config),
+            "scopeSpans": [{
+                "scope": { "name": SCOPE_NAME },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+/// The `ExportMetricsServiceRequest` with a gauge `relay.<name>` for each
+/// number that was logged, summed per project, a `relay.<name>` gauge for
+/// each cache hit rate and the `relay.build_duration_ms`.
+pub fn metrics_json(config: &OtlpConfig, trace: &ChromeTrace) -> Value {
+    let end_nanos = (trace
+        .start_time()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+        + trace.elapsed_micros() * 1000)
+        .to_string();
+
+    let mut counts: BTreeMap<&'static str, BTreeMap<Option<String>, usize>> = BTreeMap::new();
+    for event in trace.sorted_events().iter() {
+        if let Some(value) = event.args.value {
+            *counts
+                .entry(event.name)
+                .or_default()
+                .entry(event.args.project.clone())
+                .or_default() += value;
+        }
+    }
+
+    let data_point = |project: &Option<String>, value: Value| {
+        let mut data_point = json!({ "timeUnixNano": end_nanos });
+        let key = if value.is_f64() { "asDouble" } else { "asInt" };
+        data_point[key] = value;
+        if let Some(project) = project {
+            data_point["attributes"] = json!([string_attribute("relay.project", project)]);
+        }
+        data_point
+    };
+    let gauge = |name: &str, data_points: Vec<Value>| {
+        json!({
+            "name": format!("relay.{}", name),
+            "gauge": { "dataPoints": data_points },
+        })
+    };
+
+    let mut metrics = vec![gauge(
+        "build_duration_ms",
+        vec![data_point(
+            &None,
+            json!((trace.elapsed_micros() / 1000).to_string()),
+        )],
+    )];
+    for (name, values) in &counts {
+        metrics.push(gauge(
+            name,
+            values
+                .iter()
+                .map(|(project, value)| data_point(project, json!(value.to_string())))
+                .collect(),
+        ));
+    }
+    for (name, hits_name, misses_name) in HIT_RATES {
+        let (Some(hits), Some(misses)) = (counts.get(hits_name), counts.get(misses_name)) else {
+            continue;
+        };
+        let data_points: Vec<_> = hits
+            .iter()
+            .filter_map(|(project, hits)| {
+                let total = hits + misses.get(project).copied().unwrap_or(0);
+                (total > 0).then(|| data_point(project, json!(*hits as f64 / total as f64)))
+            })
+            .collect();
+        if !data_points.is_empty() {
+            metrics.push(gauge(name, data_points));
+        }
+    }
+
+    json!({
+        "resourceMetrics": [{
+            "resource": resource_json(config),
+            "scopeMetrics": [{
+                "scope": { "name": SCOPE_NAME },
+                "metrics": metrics,
+            }],
+        }],
+    })
+}
+
+fn resource_json(config: &OtlpConfig) -> Value {
+    json!({
+        "attributes": [string_attribute("service.name", &config.service_name)],
+    })
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common::NoopPerfLogger;
+    use common::PerfLogEvent;
+    use common::PerfLogger;
+
+    use super::*;
+    use crate::chrome_trace::ChromeTracePerfLogger;
+
+    fn otlp_config() -> OtlpConfig {
+        OtlpConfig {
+            endpoint: "http://localhost:4318".to_string(),
+            service_name: "relay-compiler".to_string(),
+            headers: Default::default(),
+        }
+    }
+
+    fn build_trace() -> Arc<ChromeTrace> {
+        let trace = Arc::new(ChromeTrace::default());
+        let perf_logger = ChromeTracePerfLogger::new(NoopPerfLogger, Arc::clone(&trace));
+        let event = perf_logger.create_event("commit_project");
+        event.string("project", "web".to_string());
+        let timer = event.start("commit_project_time");
+        event.time("write_artifacts_time", || {
+            std::thread::sleep(std::time::Duration::from_millis(2))
+        });
+        event.number("written_artifacts", 1);
+        event.number("unchanged_artifacts", 3);
+        event.stop(timer);
+        event.complete();
+        trace
+    }
+
+    #[test]
+    fn nests_timers_in_the_build_span() {
+        let json = traces_json(&otlp_config(), &build_trace(), false);
+        let spans = json["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+        let names: Vec<_> = spans.iter().map(|span| &span["name"]).collect();
+        assert_eq!(
+            names,
+            vec!["relay_build", "commit_project_time", "write_artifacts_time"]
+        );
+        assert_eq!(spans[0]["status"]["code"], 2);
+        assert_eq!(spans[1]["parentSpanId"], spans[0]["spanId"]);
+        assert_eq!(spans[2]["parentSpanId"], spans[1]["spanId"]);
+        assert!(
+            spans
+                .iter()
+                .all(|span| span["traceId"] == spans[0]["traceId"])
+        );
+    }
+
+    #[test]
+    fn reports_numbers_and_hit_rates() {
+        let json = metrics_json(&otlp_config(), &build_trace());
+        let metrics = json["resourceMetrics"][0]["scopeMetrics"][0]["metrics"]
+            .as_array()
+            .unwrap();
+        let metric = |name: &str| {
+            &metrics
+                .iter()
+                .find(|metric| metric["name"] == name)
+                .unwrap()["gauge"]["dataPoints"][0]
+        };
+        assert_eq!(metric("relay.written_artifacts")["asInt"], "1");
+        assert_eq!(
+            metric("relay.unchanged_artifacts")["attributes"][0]["value"]["stringValue"],
+            "web"
+        );
+        assert_eq!(metric("relay.artifact_cache_hit_rate")["asDouble"], 0.75);
+        assert!(
+            !metrics
+                .iter()
+                .any(|metric| metric["name"] == "relay.persist_cache_hit_rate")
+        );
+    }
+}
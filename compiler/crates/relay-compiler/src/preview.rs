@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! In-memory compilation of a single definition, used by the LSP to preview
+//! the artifacts of a definition without writing them to disk.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common::DiagnosticsResult;
+use common::PerfLogger;
+use graphql_ir::ExecutableDefinitionName;
+use graphql_ir::Program;
+use relay_codegen::Printer;
+use relay_transforms::apply_transforms;
+use relay_typegen::FragmentLocations;
+
+use crate::build_project::generate_artifacts;
+use crate::build_project::validate;
+use crate::build_project::SourceHashes;
+use crate::config::Config;
+use crate::config::ProjectConfig;
+
+/// An artifact as it would be written by the compiler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewArtifact {
+    /// The path of the artifact, relative to the root directory.
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// Validates and transforms the program, and prints the artifacts generated
+/// for `definition_name`: its reader and normalization artifacts, and the ones
+/// derived from it, like the refetch query of a `@refetchable` fragment.
+///
+/// The program should contain the definition, and the fragments it references.
+pub fn preview_artifacts(
+    config: &Config,
+    project_config: &ProjectConfig,
+    program: Program,
+    mut source_hashes: SourceHashes,
+    definition_name: ExecutableDefinitionName,
+    perf_logger: Arc<impl PerfLogger + 'static>,
+) -> DiagnosticsResult<Vec<PreviewArtifact>> {
+    validate(&program, project_config, &config.additional_validations)?;
+    source_hashes.add_missing_definitions(&program);
+
+    let programs = apply_transforms(
+        project_config,
+        Arc::new(program),
+        Default::default(),
+        perf_logger,
+        None,
+        config.custom_transforms.as_ref(),
+        None,
+    )?;
+
+    let fragment_locations = FragmentLocations::new(programs.typegen.fragments());
+    let mut printer = Printer::with_dedupe(project_config);
+    Ok(
+        generate_artifacts(config, project_config, &programs, Arc::new(source_hashes))
+            .into_iter()
+            .filter(|artifact| artifact.source_definition_names.contains(&definition_name))
+            .map(|artifact| {
+                let content = artifact.content.as_bytes(
+                    config,
+                    project_config,
+                    &mut printer,
+                    &programs.source.schema,
+                    artifact.source_file,
+                    &fragment_locations,
+                );
+                PreviewArtifact {
+                    path: artifact.path,
+                    content: String::from_utf8_lossy(&content).into_owned(),
+                }
+            })
+            .collect(),
+    )
+}
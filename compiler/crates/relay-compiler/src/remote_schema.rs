@@ -0,0 +1,207 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Projects can configure `schema: { url, headers }` to use the schema of a
+//! GraphQL endpoint. The schema is fetched with an introspection query and
+//! cached as SDL at the project's `schema_location`, so that the rest of the
+//! compiler can treat it like any other schema file.
+
+use std::fs;
+use std::path::Path;
+
+use log::info;
+use relay_config::ProjectConfig;
+use relay_config::RemoteSchemaConfig;
+use relay_config::SchemaLocation;
+use schema_introspection::fetch_introspection;
+use schema_introspection::introspection_to_sdl;
+use schema_introspection::parse_introspection;
+use schema_introspection::FetchResult;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::config::Config;
+use crate::errors::Error;
+use crate::errors::Result;
+
+const URL_HEADER: &str = "# url: ";
+const ETAG_HEADER: &str = "# etag: ";
+const SHA256_HEADER: &str = "# sha256: ";
+
+/// Makes sure that the cached schema of every enabled project with a remote
+/// schema exists. The schema is only fetched if there is no valid cache for
+/// the configured URL, or if `Config::refresh_schemas` is set.
+pub(crate) async fn fetch_remote_schemas(config: &Config) -> Result<()> {
+    for project_config in config.enabled_projects() {
+        if let (Some(remote_schema), SchemaLocation::File(cache_file)) =
+            (&project_config.remote_schema, &project_config.schema_location)
+        {
+            fetch_remote_schema(
+                project_config,
+                remote_schema,
+                &config.root_dir.join(cache_file),
+                config.refresh_schemas,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn fetch_remote_schema(
+    project_config: &ProjectConfig,
+    remote_schema: &RemoteSchemaConfig,
+    cache_file: &Path,
+    refresh: bool,
+) -> Result<()> {
+    let cached = fs::read_to_string(cache_file)
+        .ok()
+        .and_then(|content| CachedSchema::parse(&content))
+        .filter(|cached| cached.url == remote_schema.url);
+    if !refresh && cached.is_some() {
+        return Ok(());
+    }
+
+    info!(
+        "[{}] Fetching schema from {}",
+        project_config.name, remote_schema.url
+    );
+    let to_error = |source| Error::RemoteSchemaError {
+        project_name: project_config.name,
+        url: remote_schema.url.clone(),
+        source,
+    };
+    let fetch_result = fetch_introspection(
+        &remote_schema.url,
+        &remote_schema.headers,
+        cached.as_ref().and_then(|cached| cached.etag.as_deref()),
+    )
+    .await
+    .map_err(to_error)?;
+    let (response, etag) = match fetch_result {
+        FetchResult::NotModified => return Ok(()),
+        FetchResult::Fetched { response, etag } => (response, etag),
+    };
+    let sdl = introspection_to_sdl(&parse_introspection(&response).map_err(to_error)?);
+
+    let sha256 = hash(&sdl);
+    if let Some(cached) = &cached {
+        if cached.sha256 == sha256 && cached.etag == etag {
+            return Ok(());
+        }
+    }
+    let mut content = format!(
+        "# @generated by the Relay compiler from an introspection query. Do not edit.\n{}{}\n",
+        URL_HEADER, remote_schema.url
+    );
+    if let Some(etag) = &etag {
+        content.push_str(&format!("{}{}\n", ETAG_HEADER, etag));
+    }
+    content.push_str(&format!("{}{}\n\n{}", SHA256_HEADER, sha256, sdl));
+
+    if let Some(dir) = cache_file.parent() {
+        fs::create_dir_all(dir).map_err(|source| Error::WriteFileError {
+            file: dir.to_path_buf(),
+            source,
+        })?;
+    }
+    fs::write(cache_file, content).map_err(|source| Error::WriteFileError {
+        file: cache_file.to_path_buf(),
+        source,
+    })
+}
+
+/// The metadata stored in the header comments of a cached schema.
+struct CachedSchema {
+    url: String,
+    etag: Option<String>,
+    sha256: String,
+}
+
+impl CachedSchema {
+    /// Returns `None` if the header is missing or the SDL doesn't match the
+    /// stored hash, e.g. because the file was edited by hand. Line endings
+    /// may have been converted to CRLF, e.g. by git on Windows.
+    fn parse(content: &str) -> Option<Self> {
+        let content = content.replace("\r\n", "\n");
+        let mut url = None;
+        let mut etag = None;
+        let mut sha256 = None;
+        let mut sdl_start = 0;
+        for line in content.lines() {
+            sdl_start += line.len() + 1;
+            if let Some(value) = line.strip_prefix(URL_HEADER) {
+                url = Some(value.to_string());
+            } else if let Some(value) = line.strip_prefix(ETAG_HEADER) {
+                etag = Some(value.to_string());
+            } else if let Some(value) = line.strip_prefix(SHA256_HEADER) {
+                sha256 = Some(value.to_string());
+            } else if line.is_empty() {
+                break;
+            }
+        }
+        let sha256 = sha256?;
+        if hash(content.get(sdl_start..)?) != sha256 {
+            return None;
+        }
+        Some(Self {
+            url: url?,
+            etag,
+            sha256,
+        })
+    }
+}
+
+fn hash(sdl: &str) -> String {
+    let mut hash = Sha256::new();
+    hash.update(sdl);
+    hex::encode(hash.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cached_schema() {
+        let sdl = "type Query {\n  id: ID\n}\n";
+        let content = format!(
+            "# @generated\n# url: https://example.com/graphql\n# etag: \"abc\"\n# sha256: {}\n\n{}",
+            hash(sdl),
+            sdl
+        );
+        let cached = CachedSchema::parse(&content).unwrap();
+        assert_eq!(cached.url, "https://example.com/graphql");
+        assert_eq!(cached.etag.as_deref(), Some("\"abc\""));
+        assert_eq!(cached.sha256, hash(sdl));
+    }
+
+    #[test]
+    fn rejects_edited_cached_schema() {
+        let sdl = "type Query {\n  id: ID\n}\n";
+        let content = format!(
+            "# @generated\n# url: https://example.com/graphql\n# sha256: {}\n\n{}extend type Query {{ name: String }}\n",
+            hash(sdl),
+            sdl
+        );
+        assert!(CachedSchema::parse(&content).is_none());
+    }
+
+    #[test]
+    fn parses_cached_schema_with_crlf_line_endings() {
+        let sdl = "type Query {\n  id: ID\n}\n";
+        let content = format!(
+            "# @generated\n# url: https://example.com/graphql\n# sha256: {}\n\n{}",
+            hash(sdl),
+            sdl
+        )
+        .replace('\n', "\r\n");
+        let cached = CachedSchema::parse(&content).unwrap();
+        assert_eq!(cached.url, "https://example.com/graphql");
+        assert_eq!(cached.sha256, hash(sdl));
+    }
+}
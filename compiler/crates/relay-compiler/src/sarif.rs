@@ -0,0 +1,388 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Diagnostics in the SARIF 2.1.0 format of static analysis results, e.g.
+//! for GitHub code scanning:
+//! https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use std::collections::HashMap;
+
+use common::diagnostic_code_url;
+use serde::Serialize;
+
+use crate::diagnostics_json::JsonDiagnostic;
+use crate::diagnostics_json::JsonLocation;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    information_uri: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    name: String,
+    short_description: SarifMessage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+    default_configuration: SarifConfiguration,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifConfiguration {
+    level: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    level: &'static str,
+    message: SarifMessage,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    occurrence_count: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<usize>,
+    physical_location: SarifPhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<SarifMessage>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactLocation {
+    uri: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: u32,
+    start_column: u32,
+    end_line: u32,
+    end_column: u32,
+}
+
+/// Prints the diagnostics as a SARIF log with a single run. The codes of the
+/// diagnostics, or the kinds of those without a code, are the rules, whose
+/// default level is the severity of their first diagnostic. Files are
+/// relative to the root dir of the config.
+pub fn print_sarif(diagnostics: &[JsonDiagnostic]) -> String {
+    let mut rules = Vec::new();
+    let mut rule_indices = HashMap::new();
+    let results = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let rule_id = rule_id(diagnostic);
+            let rule_index = rule_id.as_ref().map(|id| {
+                *rule_indices.entry(id.clone()).or_insert_with(|| {
+                    let name = diagnostic.kind.clone().unwrap_or_else(|| id.clone());
+                    rules.push(SarifRule {
+                        id: id.clone(),
+                        short_description: SarifMessage {
+                            text: describe_kind(&name),
+                        },
+                        help_uri: diagnostic.code.map(diagnostic_code_url),
+                        name,
+                        default_configuration: SarifConfiguration {
+                            level: sarif_level(diagnostic.severity),
+                        },
+                    });
+                    rules.len() - 1
+                })
+            });
+            SarifResult {
+                rule_id,
+                rule_index,
+                level: sarif_level(diagnostic.severity),
+                message: SarifMessage {
+                    text: diagnostic.message.clone(),
+                },
+                locations: diagnostic
+                    .location
+                    .as_ref()
+                    .map(|location| sarif_location(location, None, None))
+                    .into_iter()
+                    .collect(),
+                related_locations: diagnostic
+                    .related
+                    .iter()
+                    .filter_map(|related| {
+                        related
+                            .location
+                            .as_ref()
+                            .map(|location| (location, related))
+                    })
+                    .enumerate()
+                    .map(|(index, (location, related))| {
+                        sarif_location(
+                            location,
+                            Some(index),
+                            Some(SarifMessage {
+                                text: related.message.clone(),
+                            }),
+                        )
+                    })
+                    .collect(),
+                occurrence_count: (diagnostic.occurrences > 1).then_some(diagnostic.occurrences),
+            }
+        })
+        .collect::<Vec<_>>();
+    let log = SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "relay-compiler",
+                    information_uri: "https://relay.dev",
+                    rules,
+                },
+            },
+            results,
+        }],
+    };
+    serde_json::to_string_pretty(&log).unwrap()
+}
+
+fn sarif_location(
+    location: &JsonLocation,
+    id: Option<usize>,
+    message: Option<SarifMessage>,
+) -> SarifLocation {
+    SarifLocation {
+        id,
+        physical_location: SarifPhysicalLocation {
+            artifact_location: SarifArtifactLocation { uri: location.file },
+            region: location.span.as_ref().map(|span| SarifRegion {
+                start_line: span.start_line,
+                start_column: span.start_column,
+                end_line: span.end_line,
+                end_column: span.end_column,
+            }),
+        },
+        message,
+    }
+}
+
+fn sarif_level(severity: &str) -> &'static str {
+    match severity {
+        "error" => "error",
+        "warning" => "warning",
+        _ => "note",
+    }
+}
+
+/// Rules are identified by the stable code of the diagnostics, or else by
+/// the kind of their message.
+fn rule_id(diagnostic: &JsonDiagnostic) -> Option<String> {
+    diagnostic
+        .code
+        .map(|code| code.to_string())
+        .or_else(|| diagnostic.kind.clone())
+}
+
+/// Splits the name of the kind into words, e.g. `Undefined field` for
+/// `UndefinedField`.
+fn describe_kind(kind: &str) -> String {
+    let mut description = String::new();
+    for (index, c) in kind.chars().enumerate() {
+        if index == 0 {
+            description.push(c);
+        } else if c.is_ascii_uppercase() {
+            description.push(' ');
+            description.push(c.to_ascii_lowercase());
+        } else {
+            description.push(c);
+        }
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics_json::JsonRelatedInformation;
+    use crate::diagnostics_json::JsonSpan;
+
+    fn location(line: u32) -> Option<JsonLocation> {
+        Some(JsonLocation {
+            file: "src/Foo.graphql",
+            span: Some(JsonSpan {
+                start_line: line,
+                start_column: 3,
+                end_line: line,
+                end_column: 6,
+            }),
+        })
+    }
+
+    #[test]
+    fn sarif_has_rules_and_related_locations() {
+        let diagnostics = vec![
+            JsonDiagnostic {
+                message: "Unknown field `nam`".to_string(),
+                code: Some("RELAY-2001"),
+                kind: Some("UndefinedField".to_string()),
+                severity: "error",
+                occurrences: 1,
+                project: None,
+                location: location(2),
+                related: vec![JsonRelatedInformation {
+                    message: "parent field".to_string(),
+                    location: location(1),
+                }],
+            },
+            JsonDiagnostic {
+                message: "Persisting failed".to_string(),
+                code: None,
+                kind: None,
+                severity: "hint",
+                occurrences: 1,
+                project: None,
+                location: None,
+                related: vec![],
+            },
+        ];
+        assert_eq!(
+            print_sarif(&diagnostics),
+            r#"{
+  "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+  "version": "2.1.0",
+  "runs": [
+    {
+      "tool": {
+        "driver": {
+          "name": "relay-compiler",
+          "informationUri": "https://relay.dev",
+          "rules": [
+            {
+              "id": "RELAY-2001",
+              "name": "UndefinedField",
+              "shortDescription": {
+                "text": "Undefined field"
+              },
+              "helpUri": "https://relay.dev/docs/error-reference/error-codes/#relay-2001",
+              "defaultConfiguration": {
+                "level": "error"
+              }
+            }
+          ]
+        }
+      },
+      "results": [
+        {
+          "ruleId": "RELAY-2001",
+          "ruleIndex": 0,
+          "level": "error",
+          "message": {
+            "text": "Unknown field `nam`"
+          },
+          "locations": [
+            {
+              "physicalLocation": {
+                "artifactLocation": {
+                  "uri": "src/Foo.graphql"
+                },
+                "region": {
+                  "startLine": 2,
+                  "startColumn": 3,
+                  "endLine": 2,
+                  "endColumn": 6
+                }
+              }
+            }
+          ],
+          "relatedLocations": [
+            {
+              "id": 0,
+              "physicalLocation": {
+                "artifactLocation": {
+                  "uri": "src/Foo.graphql"
+                },
+                "region": {
+                  "startLine": 1,
+                  "startColumn": 3,
+                  "endLine": 1,
+                  "endColumn": 6
+                }
+              },
+              "message": {
+                "text": "parent field"
+              }
+            }
+          ]
+        },
+        {
+          "level": "note",
+          "message": {
+            "text": "Persisting failed"
+          }
+        }
+      ]
+    }
+  ]
+}"#
+        );
+    }
+}
@@ -0,0 +1,197 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! With `schemaCacheDir`, the schema of a project is serialized to the cache
+//! directory after it is built. On the next start of the compiler or the
+//! LSP, while the schema files and extensions of the project are unchanged,
+//! the in-memory schema is deserialized from the memory mapped file instead
+//! of parsing and building the schema again. It keeps the locations and
+//! descriptions of the built schema.
+//!
+//! A cached file starts with a header of the format version and the hash of
+//! everything the schema is built from, and is stale if either differs.
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use common::SourceLocationKey;
+use memmap2::Mmap;
+use relay_config::SchemaConfig;
+use schema::InMemorySchema;
+use schema::SDLSchema;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::compiler_state::ProjectName;
+
+const MAGIC: &[u8; 8] = b"RELAYSDL";
+
+/// The version of the format of cached schemas, to be incremented when it,
+/// or the definitions of `InMemorySchema`, change.
+const VERSION: u32 = 6;
+
+const HEADER_LENGTH: usize = MAGIC.len() + 4 + 32;
+
+/// The hash of the sources a schema is built from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SchemaCacheKey([u8; 32]);
+
+impl SchemaCacheKey {
+    /// `config_version` should change with the compiler and its config, like
+    /// `Config::saved_state_version`.
+    pub(crate) fn new<T: AsRef<str>, U: AsRef<str>>(
+        config_version: &str,
+        schema_config: &SchemaConfig,
+        schema_sources: &[(T, SourceLocationKey)],
+        extensions: &[(U, SourceLocationKey)],
+    ) -> Self {
+        fn update_with_source(hash: &mut Sha256, source: &str, location: SourceLocationKey) {
+            hash.update(location.path());
+            hash.update([0]);
+            hash.update((source.len() as u64).to_le_bytes());
+            hash.update(source);
+        }
+
+        let mut hash = Sha256::new();
+        hash.update(config_version);
+        hash.update([
+            0,
+            schema_config.federation as u8,
+            schema_config.semantic_non_null_support as u8,
+        ]);
+        hash.update((schema_sources.len() as u64).to_le_bytes());
+        for (source, location) in schema_sources {
+            update_with_source(&mut hash, source.as_ref(), *location);
+        }
+        hash.update((extensions.len() as u64).to_le_bytes());
+        for (source, location) in extensions {
+            update_with_source(&mut hash, source.as_ref(), *location);
+        }
+        Self(hash.finalize().into())
+    }
+
+    fn header(&self) -> Vec<u8> {
+        let mut header = Vec::with_capacity(HEADER_LENGTH);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&VERSION.to_le_bytes());
+        header.extend_from_slice(&self.0);
+        header
+    }
+}
+
+/// The file the schema of a project is cached in.
+pub(crate) fn schema_cache_path(schema_cache_dir: &Path, project_name: ProjectName) -> PathBuf {
+    schema_cache_dir.join(format!("{}.schema", project_name))
+}
+
+/// Reads the cached schema, if it was cached with the same key.
+pub(crate) fn load_cached_schema(path: &Path, key: SchemaCacheKey) -> Option<SDLSchema> {
+    let file = File::open(path).ok()?;
+    // SAFETY: cache files are replaced by renaming a new file over them,
+    // never written in place, so the mapped file doesn't change.
+    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+    if mmap.len() <= HEADER_LENGTH || mmap[..HEADER_LENGTH] != key.header()[..] {
+        return None;
+    }
+    let mut schema: InMemorySchema = bincode::deserialize(&mmap[HEADER_LENGTH..]).ok()?;
+    // Shares the default values of arguments again
+    schema.shrink_to_fit();
+    Some(SDLSchema::InMemory(schema))
+}
+
+/// Writes the schema to the cache. The file is written next to the cache
+/// file and renamed over it, so that readers never see a partial file.
+/// Only in-memory schemas are written.
+pub(crate) fn store_cached_schema(
+    path: &Path,
+    key: SchemaCacheKey,
+    schema: &SDLSchema,
+) -> io::Result<()> {
+    let schema = match schema {
+        SDLSchema::InMemory(schema) => schema,
+        SDLSchema::FlatBuffer(_) => return Ok(()),
+    };
+    let mut bytes = key.header();
+    bincode::serialize_into(&mut bytes, schema).map_err(io::Error::other)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&temp_path, bytes)?;
+    fs::rename(&temp_path, path).inspect_err(|_| {
+        fs::remove_file(&temp_path).ok();
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use intern::string_key::Intern;
+    use schema::Schema;
+
+    use super::*;
+
+    const SDL: &str = "type Query { user: User } \"A user.\" type User { name: String }";
+
+    fn key(sdl: &str) -> SchemaCacheKey {
+        SchemaCacheKey::new::<_, &str>(
+            "version",
+            &SchemaConfig::default(),
+            &[(sdl, SourceLocationKey::standalone("schema.graphql"))],
+            &[],
+        )
+    }
+
+    #[test]
+    fn loads_stored_schema() {
+        let dir = std::env::temp_dir().join(format!("relay-schema-cache-{}", std::process::id()));
+        let path = schema_cache_path(&dir, "test".intern());
+        let schema = relay_schema::build_schema_with_extensions::<_, &str>(
+            &[(SDL, SourceLocationKey::standalone("schema.graphql"))],
+            &[],
+        )
+        .unwrap();
+        store_cached_schema(&path, key(SDL), &schema).unwrap();
+
+        {
+            let cached_schema = load_cached_schema(&path, key(SDL)).unwrap();
+            let user = cached_schema.get_type("User".intern()).unwrap();
+            assert!(cached_schema.named_field(user, "name".intern()).is_some());
+            let user = cached_schema.object(user.get_object_id().unwrap());
+            let built_user = schema.object(
+                schema
+                    .get_type("User".intern())
+                    .unwrap()
+                    .get_object_id()
+                    .unwrap(),
+            );
+            assert_eq!(user.name.location, built_user.name.location);
+            assert_eq!(user.description, Some("A user.".intern()));
+            assert!(cached_schema.get_objects().count() > 0);
+        }
+        assert!(load_cached_schema(&path, key("type Query { id: ID }")).is_none());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn keys_change_with_sources() {
+        assert_eq!(key(SDL), key(SDL));
+        assert_ne!(key(SDL), key("type Query { id: ID }"));
+        let extended = SchemaCacheKey::new(
+            "version",
+            &SchemaConfig::default(),
+            &[(SDL, SourceLocationKey::standalone("schema.graphql"))],
+            &[(
+                "extend type User { age: Int }",
+                SourceLocationKey::generated(),
+            )],
+        );
+        assert_ne!(key(SDL), extended);
+    }
+}
@@ -0,0 +1,679 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The operations and fragments of a project that are affected by the
+//! changes between two schemas, used by `relay schema-diff`.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use common::DiagnosticsResult;
+use common::Location;
+use common::SchemaCoordinate;
+use common::SourceLocationKey;
+use graphql_ir::ConstantValue;
+use graphql_ir::Directive;
+use graphql_ir::FragmentDefinition;
+use graphql_ir::FragmentSpread;
+use graphql_ir::InlineFragment;
+use graphql_ir::LinkedField;
+use graphql_ir::OperationDefinition;
+use graphql_ir::Program;
+use graphql_ir::ScalarField;
+use graphql_ir::Value;
+use graphql_ir::VariableDefinition;
+use graphql_ir::Visitor;
+use intern::string_key::StringKey;
+use relay_config::ProjectConfig;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+use schema::TypeReference;
+use schema_diff::breaking::ChangeKind;
+use schema_diff::breaking::ChangeSeverity;
+use serde::Serialize;
+
+use crate::build_project::build_schema::build_schema_from_sources;
+use crate::compiler_state::ProjectName;
+use crate::lint::line_and_column;
+use crate::SourceReader;
+
+/// Builds a schema to compare from SDL, with the schema config of the
+/// project. Client schema extensions are not included, since they are the
+/// same for both schemas.
+pub fn build_diff_schema(
+    project_config: &ProjectConfig,
+    path: &str,
+    text: &str,
+) -> DiagnosticsResult<SDLSchema> {
+    let no_extensions: &[(&str, SourceLocationKey)] = &[];
+    build_schema_from_sources(
+        &project_config.schema_config,
+        &[(text, SourceLocationKey::standalone(path))],
+        no_extensions,
+    )
+}
+
+/// A selection, type condition, variable, value or directive in a document
+/// that is affected by a schema change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Usage {
+    /// The operation or fragment with the usage.
+    pub definition: StringKey,
+    pub location: Location,
+}
+
+pub struct ChangeImpact {
+    pub change: ChangeKind,
+    pub usages: Vec<Usage>,
+}
+
+/// Pairs each change with the usages in the program of the changed type or
+/// member that the change affects. Changes that can't affect existing
+/// documents, e.g. added fields, have no usages.
+pub fn find_change_impact(program: &Program, changes: Vec<ChangeKind>) -> Vec<ChangeImpact> {
+    let mut collector = UsageCollector::new(program);
+    collector.visit_program(program);
+    let records = collector.records;
+
+    changes
+        .into_iter()
+        .map(|change| {
+            let mut usages: Vec<Usage> = vec![];
+            for record in &records {
+                if is_affected(&change, &record.kind) && !usages.contains(&record.usage) {
+                    usages.push(record.usage);
+                }
+            }
+            usages.sort_by(|a, b| {
+                a.location
+                    .source_location()
+                    .path()
+                    .cmp(b.location.source_location().path())
+                    .then_with(|| a.location.span().start.cmp(&b.location.span().start))
+            });
+            ChangeImpact { change, usages }
+        })
+        .collect()
+}
+
+/// The number of breaking changes with usages.
+pub fn count_breaking_impacts(impacts: &[ChangeImpact]) -> usize {
+    impacts
+        .iter()
+        .filter(|impact| {
+            impact.change.severity() == ChangeSeverity::Breaking && !impact.usages.is_empty()
+        })
+        .count()
+}
+
+fn is_affected(change: &ChangeKind, usage: &UsageKind) -> bool {
+    match (change, usage) {
+        (ChangeKind::TypeRemoved(name), usage)
+        | (
+            ChangeKind::TypeKindChanged {
+                type_name: name, ..
+            },
+            usage,
+        ) => match usage {
+            UsageKind::Field {
+                parent_type,
+                return_type,
+                ..
+            } => parent_type == name || return_type == name,
+            UsageKind::TypeCondition { type_name, .. }
+            | UsageKind::Variable { type_name }
+            | UsageKind::InputValue { type_name, .. }
+            | UsageKind::EnumValue { type_name, .. } => type_name == name,
+            UsageKind::Directive { .. } => false,
+        },
+        (
+            ChangeKind::FieldRemoved {
+                type_name,
+                field_name,
+            }
+            | ChangeKind::FieldTypeChanged {
+                type_name,
+                field_name,
+                ..
+            }
+            | ChangeKind::FieldDeprecated {
+                type_name,
+                field_name,
+            }
+            | ChangeKind::ArgumentAdded {
+                type_name,
+                field_name,
+                required: true,
+                ..
+            },
+            UsageKind::Field {
+                parent_type,
+                field_name: used_field_name,
+                ..
+            },
+        ) => parent_type == type_name && used_field_name == field_name,
+        (
+            ChangeKind::ArgumentRemoved {
+                type_name,
+                field_name,
+                argument_name,
+            }
+            | ChangeKind::ArgumentTypeChanged {
+                type_name,
+                field_name,
+                argument_name,
+                ..
+            }
+            | ChangeKind::ArgumentDefaultChanged {
+                type_name,
+                field_name,
+                argument_name,
+            },
+            UsageKind::Field {
+                parent_type,
+                field_name: used_field_name,
+                arguments,
+                ..
+            },
+        ) => {
+            parent_type == type_name
+                && used_field_name == field_name
+                && arguments.contains(argument_name)
+        }
+        (
+            ChangeKind::InputFieldRemoved {
+                type_name,
+                field_name,
+            }
+            | ChangeKind::InputFieldTypeChanged {
+                type_name,
+                field_name,
+                ..
+            },
+            usage,
+        ) => match usage {
+            UsageKind::InputValue {
+                type_name: used_type_name,
+                field_name: used_field_name,
+            } => used_type_name == type_name && used_field_name.as_ref() == Some(field_name),
+            UsageKind::Variable {
+                type_name: used_type_name,
+            } => used_type_name == type_name,
+            _ => false,
+        },
+        (
+            ChangeKind::InputFieldAdded {
+                type_name,
+                required: true,
+                ..
+            },
+            UsageKind::InputValue {
+                type_name: used_type_name,
+                field_name: None,
+            }
+            | UsageKind::Variable {
+                type_name: used_type_name,
+            },
+        ) => used_type_name == type_name,
+        (ChangeKind::EnumValueRemoved { type_name, value }, usage) => match usage {
+            UsageKind::EnumValue {
+                type_name: used_type_name,
+                value: used_value,
+            } => used_type_name == type_name && used_value == value,
+            UsageKind::Variable {
+                type_name: used_type_name,
+            } => used_type_name == type_name,
+            _ => false,
+        },
+        // Clients may receive values or types they don't know about.
+        (
+            ChangeKind::EnumValueAdded { type_name, .. }
+            | ChangeKind::UnionMemberAdded { type_name, .. }
+            | ChangeKind::InterfaceAdded {
+                interface: type_name,
+                ..
+            },
+            UsageKind::Field { return_type, .. },
+        ) => return_type == type_name,
+        (
+            ChangeKind::UnionMemberRemoved { type_name, member },
+            UsageKind::TypeCondition {
+                parent_type,
+                type_name: condition,
+            },
+        ) => parent_type.as_ref() == Some(type_name) && condition == member,
+        (
+            ChangeKind::InterfaceRemoved {
+                type_name,
+                interface,
+            },
+            UsageKind::TypeCondition {
+                parent_type,
+                type_name: condition,
+            },
+        ) => {
+            (parent_type.as_ref() == Some(interface) && condition == type_name)
+                || (parent_type.as_ref() == Some(type_name) && condition == interface)
+        }
+        (ChangeKind::DirectiveRemoved(name), UsageKind::Directive { name: used_name }) => {
+            used_name == name
+        }
+        _ => false,
+    }
+}
+
+enum UsageKind {
+    Field {
+        parent_type: StringKey,
+        field_name: StringKey,
+        return_type: StringKey,
+        arguments: Vec<StringKey>,
+    },
+    TypeCondition {
+        /// The type of the selection with the fragment, unknown for the type
+        /// condition of a fragment definition.
+        parent_type: Option<StringKey>,
+        type_name: StringKey,
+    },
+    Variable {
+        type_name: StringKey,
+    },
+    /// An input object literal, and each of its fields.
+    InputValue {
+        type_name: StringKey,
+        field_name: Option<StringKey>,
+    },
+    EnumValue {
+        type_name: StringKey,
+        value: StringKey,
+    },
+    Directive {
+        name: StringKey,
+    },
+}
+
+struct UsageRecord {
+    kind: UsageKind,
+    usage: Usage,
+}
+
+struct UsageCollector<'p> {
+    program: &'p Program,
+    schema: &'p SDLSchema,
+    records: Vec<UsageRecord>,
+    definition: Option<StringKey>,
+    parent_types: Vec<Type>,
+}
+
+impl<'p> UsageCollector<'p> {
+    fn new(program: &'p Program) -> Self {
+        Self {
+            program,
+            schema: &program.schema,
+            records: vec![],
+            definition: None,
+            parent_types: vec![],
+        }
+    }
+
+    fn record(&mut self, kind: UsageKind, location: Location) {
+        self.records.push(UsageRecord {
+            kind,
+            usage: Usage {
+                definition: self
+                    .definition
+                    .expect("Expected usages to be in a definition."),
+                location,
+            },
+        });
+    }
+
+    fn record_type_condition(&mut self, type_: Type, location: Location) {
+        let parent_type = self
+            .parent_types
+            .last()
+            .map(|parent_type| self.schema.get_type_name(*parent_type));
+        self.record(
+            UsageKind::TypeCondition {
+                parent_type,
+                type_name: self.schema.get_type_name(type_),
+            },
+            location,
+        );
+    }
+
+    fn record_field(
+        &mut self,
+        field_id: schema::FieldID,
+        arguments: &[graphql_ir::Argument],
+        location: Location,
+    ) {
+        let schema = self.schema;
+        let field = schema.field(field_id);
+        // `__typename` and other special fields have no parent type.
+        let parent_type = match field.parent_type {
+            Some(parent_type) => schema.get_type_name(parent_type),
+            None => return,
+        };
+        self.record(
+            UsageKind::Field {
+                parent_type,
+                field_name: field.name.item,
+                return_type: schema.get_type_name(field.type_.inner()),
+                arguments: arguments
+                    .iter()
+                    .map(|argument| argument.name.item.0)
+                    .collect(),
+            },
+            location,
+        );
+        for argument in arguments {
+            if let Some(definition) = field.arguments.named(argument.name.item) {
+                self.record_value(
+                    &argument.value.item,
+                    &definition.type_,
+                    argument.value.location,
+                );
+            }
+        }
+    }
+
+    fn record_value(&mut self, value: &Value, type_: &TypeReference<Type>, location: Location) {
+        match value {
+            Value::Constant(value) => self.record_constant_value(value, type_, location),
+            Value::Variable(_) => {}
+            Value::List(items) => {
+                let item_type = list_item_type(type_);
+                for item in items {
+                    self.record_value(item, item_type, location);
+                }
+            }
+            Value::Object(fields) => {
+                if let Type::InputObject(id) = type_.inner() {
+                    let schema = self.schema;
+                    let input_object = schema.input_object(id);
+                    let type_name = input_object.name.item.0;
+                    self.record(
+                        UsageKind::InputValue {
+                            type_name,
+                            field_name: None,
+                        },
+                        location,
+                    );
+                    for field in fields {
+                        self.record(
+                            UsageKind::InputValue {
+                                type_name,
+                                field_name: Some(field.name.item.0),
+                            },
+                            field.name.location,
+                        );
+                        if let Some(definition) = input_object.fields.named(field.name.item) {
+                            self.record_value(
+                                &field.value.item,
+                                &definition.type_,
+                                field.value.location,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn record_constant_value(
+        &mut self,
+        value: &ConstantValue,
+        type_: &TypeReference<Type>,
+        location: Location,
+    ) {
+        match value {
+            ConstantValue::Enum(value) => {
+                if let Type::Enum(id) = type_.inner() {
+                    self.record(
+                        UsageKind::EnumValue {
+                            type_name: self.schema.enum_(id).name.item.0,
+                            value: *value,
+                        },
+                        location,
+                    );
+                }
+            }
+            ConstantValue::List(items) => {
+                let item_type = list_item_type(type_);
+                for item in items {
+                    self.record_constant_value(item, item_type, location);
+                }
+            }
+            ConstantValue::Object(fields) => {
+                if let Type::InputObject(id) = type_.inner() {
+                    let schema = self.schema;
+                    let input_object = schema.input_object(id);
+                    let type_name = input_object.name.item.0;
+                    self.record(
+                        UsageKind::InputValue {
+                            type_name,
+                            field_name: None,
+                        },
+                        location,
+                    );
+                    for field in fields {
+                        self.record(
+                            UsageKind::InputValue {
+                                type_name,
+                                field_name: Some(field.name.item.0),
+                            },
+                            field.name.location,
+                        );
+                        if let Some(definition) = input_object.fields.named(field.name.item) {
+                            self.record_constant_value(
+                                &field.value.item,
+                                &definition.type_,
+                                field.value.location,
+                            );
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn list_item_type(type_: &TypeReference<Type>) -> &TypeReference<Type> {
+    match type_.nullable_type() {
+        TypeReference::List(of) => of,
+        _ => type_,
+    }
+}
+
+impl Visitor for UsageCollector<'_> {
+    const NAME: &'static str = "SchemaImpactUsageCollector";
+    const VISIT_ARGUMENTS: bool = false;
+    const VISIT_DIRECTIVES: bool = true;
+
+    fn visit_operation(&mut self, operation: &OperationDefinition) {
+        self.definition = Some(operation.name.item.0);
+        self.parent_types = vec![operation.type_];
+        self.default_visit_operation(operation);
+    }
+
+    fn visit_fragment(&mut self, fragment: &FragmentDefinition) {
+        self.definition = Some(fragment.name.item.0);
+        self.parent_types = vec![];
+        self.record_type_condition(fragment.type_condition, fragment.name.location);
+        self.parent_types = vec![fragment.type_condition];
+        self.default_visit_fragment(fragment);
+    }
+
+    fn visit_variable_definition(&mut self, variable_definition: &VariableDefinition) {
+        self.record(
+            UsageKind::Variable {
+                type_name: self.schema.get_type_name(variable_definition.type_.inner()),
+            },
+            variable_definition.name.location,
+        );
+    }
+
+    fn visit_scalar_field(&mut self, field: &ScalarField) {
+        self.record_field(
+            field.definition.item,
+            &field.arguments,
+            field.definition.location,
+        );
+        self.default_visit_scalar_field(field);
+    }
+
+    fn visit_linked_field(&mut self, field: &LinkedField) {
+        self.record_field(
+            field.definition.item,
+            &field.arguments,
+            field.definition.location,
+        );
+        let field_type = self.schema.field(field.definition.item).type_.inner();
+        self.parent_types.push(field_type);
+        self.default_visit_linked_field(field);
+        self.parent_types.pop();
+    }
+
+    fn visit_inline_fragment(&mut self, fragment: &InlineFragment) {
+        match fragment.type_condition {
+            Some(type_condition) => {
+                self.record_type_condition(type_condition, fragment.spread_location);
+                self.parent_types.push(type_condition);
+                self.default_visit_inline_fragment(fragment);
+                self.parent_types.pop();
+            }
+            None => self.default_visit_inline_fragment(fragment),
+        }
+    }
+
+    fn visit_fragment_spread(&mut self, spread: &FragmentSpread) {
+        if let Some(fragment) = self.program.fragment(spread.fragment.item) {
+            self.record_type_condition(fragment.type_condition, spread.fragment.location);
+        }
+        self.default_visit_fragment_spread(spread);
+    }
+
+    fn visit_directive(&mut self, directive: &Directive) {
+        self.record(
+            UsageKind::Directive {
+                name: directive.name.item.0,
+            },
+            directive.name.location,
+        );
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectImpactJson {
+    project: StringKey,
+    changes: Vec<ChangeImpactJson>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangeImpactJson {
+    severity: &'static str,
+    coordinate: SchemaCoordinate,
+    message: String,
+    usages: Vec<UsageJson>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageJson {
+    definition: StringKey,
+    file: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<u32>,
+}
+
+fn usage_json(root_dir: &Path, usage: &Usage, source_reader: &dyn SourceReader) -> UsageJson {
+    let position = line_and_column(root_dir, usage.location, source_reader);
+    UsageJson {
+        definition: usage.definition,
+        file: usage.location.source_location().path(),
+        line: position.map(|(line, _)| line),
+        column: position.map(|(_, column)| column),
+    }
+}
+
+/// Prints a list with the changes of each project, and their usages.
+pub fn print_impact_json(
+    root_dir: &Path,
+    project_impacts: &[(ProjectName, Vec<ChangeImpact>)],
+    source_reader: &dyn SourceReader,
+) -> String {
+    let projects: Vec<ProjectImpactJson> = project_impacts
+        .iter()
+        .map(|(project, impacts)| ProjectImpactJson {
+            project: *project,
+            changes: impacts
+                .iter()
+                .map(|impact| ChangeImpactJson {
+                    severity: impact.change.severity().name(),
+                    coordinate: impact.change.coordinate(),
+                    message: impact.change.to_string(),
+                    usages: impact
+                        .usages
+                        .iter()
+                        .map(|usage| usage_json(root_dir, usage, source_reader))
+                        .collect(),
+                })
+                .collect(),
+        })
+        .collect();
+    serde_json::to_string_pretty(&projects).unwrap()
+}
+
+/// Prints the changes grouped by severity, each with the definitions and
+/// locations it affects.
+pub fn print_impact_text(
+    root_dir: &Path,
+    impacts: &[ChangeImpact],
+    source_reader: &dyn SourceReader,
+) -> String {
+    let mut output = String::new();
+    for (severity, title) in [
+        (ChangeSeverity::Breaking, "Breaking changes"),
+        (ChangeSeverity::Dangerous, "Dangerous changes"),
+        (ChangeSeverity::Safe, "Safe changes"),
+    ] {
+        let impacts: Vec<&ChangeImpact> = impacts
+            .iter()
+            .filter(|impact| impact.change.severity() == severity)
+            .collect();
+        if impacts.is_empty() {
+            continue;
+        }
+        writeln!(output, "{}:", title).unwrap();
+        for impact in impacts {
+            writeln!(output, "  {}", impact.change).unwrap();
+            for usage in &impact.usages {
+                let usage = usage_json(root_dir, usage, source_reader);
+                match (usage.line, usage.column) {
+                    (Some(line), Some(column)) => writeln!(
+                        output,
+                        "    {} ({}:{}:{})",
+                        usage.definition, usage.file, line, column
+                    ),
+                    _ => writeln!(output, "    {} ({})", usage.definition, usage.file),
+                }
+                .unwrap();
+            }
+        }
+    }
+    if output.is_empty() {
+        writeln!(output, "The schemas are the same.").unwrap();
+    }
+    output
+}
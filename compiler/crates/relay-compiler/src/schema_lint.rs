@@ -0,0 +1,401 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The rules of `relay schema-lint`, which check the naming, documentation
+//! and deprecations of the schema of a project, and find unreachable types
+//! and paginated lists. Findings are warnings at the definitions in the
+//! schema files and client schema extensions; the definitions of the
+//! compiler itself are not checked.
+
+use common::Diagnostic;
+use common::Location;
+use fnv::FnvHashSet;
+use intern::Lookup;
+use intern::string_key::StringKey;
+use relay_config::SchemaLintConfig;
+use schema::Argument;
+use schema::EnumID;
+use schema::FieldID;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+use thiserror::Error;
+
+/// The arguments of a field that pages through a list.
+const PAGINATION_ARGUMENTS: [&str; 4] = ["first", "after", "last", "before"];
+
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+pub enum SchemaLintMessage {
+    #[error("Type `{type_name}` should be named in PascalCase.")]
+    TypeName { type_name: StringKey },
+
+    #[error("Field `{type_name}.{field_name}` should be named in camelCase.")]
+    FieldName {
+        type_name: StringKey,
+        field_name: StringKey,
+    },
+
+    #[error("Argument `{type_name}.{field_name}({argument_name}:)` should be named in camelCase.")]
+    ArgumentName {
+        type_name: StringKey,
+        field_name: StringKey,
+        argument_name: StringKey,
+    },
+
+    #[error("Enum value `{enum_name}.{value}` should be named in SCREAMING_SNAKE_CASE.")]
+    EnumValueName {
+        enum_name: StringKey,
+        value: StringKey,
+    },
+
+    #[error("Field `{type_name}.{field_name}` has no description.")]
+    MissingDescription {
+        type_name: StringKey,
+        field_name: StringKey,
+    },
+
+    #[error("`{coordinate}` is deprecated without a reason.")]
+    DeprecationWithoutReason { coordinate: String },
+
+    #[error("Type `{type_name}` can't be reached from the root operation types.")]
+    UnreachableType { type_name: StringKey },
+
+    #[error(
+        "Field `{type_name}.{field_name}` takes pagination arguments, but returns a list rather than a connection."
+    )]
+    PluralFieldWithoutConnection {
+        type_name: StringKey,
+        field_name: StringKey,
+    },
+}
+
+/// Checks the schema with the rules enabled in the config. The findings are
+/// ordered by the name of their type.
+pub fn lint_schema(schema: &SDLSchema, config: &SchemaLintConfig) -> Vec<Diagnostic> {
+    let mut linter = SchemaLinter {
+        schema,
+        config,
+        diagnostics: Vec::new(),
+    };
+    linter.lint();
+    linter.diagnostics
+}
+
+struct SchemaLinter<'a> {
+    schema: &'a SDLSchema,
+    config: &'a SchemaLintConfig,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl SchemaLinter<'_> {
+    fn lint(&mut self) {
+        let schema = self.schema;
+        let reachable_types = if self.config.unreachable_types {
+            Some(self.reachable_types())
+        } else {
+            None
+        };
+        let mut types = schema
+            .get_type_map()
+            .map(|(name, type_)| (*name, *type_))
+            .collect::<Vec<_>>();
+        types.sort_by_key(|(name, _)| name.lookup());
+
+        for (type_name, type_) in types {
+            let location = self.type_location(type_);
+            if !is_linted(location) {
+                continue;
+            }
+            if self.config.naming_conventions && !is_pascal_case(type_name.lookup()) {
+                self.warn(SchemaLintMessage::TypeName { type_name }, location);
+            }
+            if let Some(reachable_types) = &reachable_types {
+                if !reachable_types.contains(&type_) {
+                    self.warn(SchemaLintMessage::UnreachableType { type_name }, location);
+                }
+            }
+            match type_ {
+                Type::Object(id) => self.lint_fields(type_name, &schema.object(id).fields),
+                Type::Interface(id) => self.lint_fields(type_name, &schema.interface(id).fields),
+                Type::Enum(id) => self.lint_enum_values(type_name, id),
+                Type::InputObject(id) => {
+                    for field in schema.input_object(id).fields.iter() {
+                        self.lint_input_value(field, location, || {
+                            format!("{}.{}", type_name, field.name)
+                        });
+                        if self.config.naming_conventions && !is_camel_case(field.name.0.lookup()) {
+                            self.warn(
+                                SchemaLintMessage::FieldName {
+                                    type_name,
+                                    field_name: field.name.0,
+                                },
+                                location,
+                            );
+                        }
+                    }
+                }
+                Type::Scalar(_) | Type::Union(_) => {}
+            }
+        }
+    }
+
+    fn lint_fields(&mut self, type_name: StringKey, fields: &[FieldID]) {
+        for field_id in fields {
+            let field = self.schema.field(*field_id);
+            let field_name = field.name.item;
+            let location = field.name.location;
+            if field_name.lookup().starts_with("__") || !is_linted(location) {
+                continue;
+            }
+            if self.config.naming_conventions && !is_camel_case(field_name.lookup()) {
+                self.warn(
+                    SchemaLintMessage::FieldName {
+                        type_name,
+                        field_name,
+                    },
+                    location,
+                );
+            }
+            if self.config.missing_descriptions
+                && !field.is_extension
+                && field.description.is_none()
+            {
+                self.warn(
+                    SchemaLintMessage::MissingDescription {
+                        type_name,
+                        field_name,
+                    },
+                    location,
+                );
+            }
+            if self.config.deprecation_reasons
+                && field
+                    .deprecated()
+                    .map_or(false, |deprecation| deprecation.reason.is_none())
+            {
+                self.warn(
+                    SchemaLintMessage::DeprecationWithoutReason {
+                        coordinate: format!("{}.{}", type_name, field_name),
+                    },
+                    location,
+                );
+            }
+            if self.config.plural_fields_without_connections
+                && field.type_.is_list()
+                && field.type_.inner().is_composite_type()
+                && field
+                    .arguments
+                    .iter()
+                    .any(|argument| PAGINATION_ARGUMENTS.contains(&argument.name.0.lookup()))
+            {
+                self.warn(
+                    SchemaLintMessage::PluralFieldWithoutConnection {
+                        type_name,
+                        field_name,
+                    },
+                    location,
+                );
+            }
+            for argument in field.arguments.iter() {
+                self.lint_input_value(argument, location, || {
+                    format!("{}.{}({}:)", type_name, field_name, argument.name)
+                });
+                if self.config.naming_conventions && !is_camel_case(argument.name.0.lookup()) {
+                    self.warn(
+                        SchemaLintMessage::ArgumentName {
+                            type_name,
+                            field_name,
+                            argument_name: argument.name.0,
+                        },
+                        location,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Arguments and input fields have no location of their own, and are
+    /// reported at their field or input object.
+    fn lint_input_value(
+        &mut self,
+        input_value: &Argument,
+        location: Location,
+        coordinate: impl FnOnce() -> String,
+    ) {
+        if self.config.deprecation_reasons
+            && input_value
+                .deprecated()
+                .map_or(false, |deprecation| deprecation.reason.is_none())
+        {
+            self.warn(
+                SchemaLintMessage::DeprecationWithoutReason {
+                    coordinate: coordinate(),
+                },
+                location,
+            );
+        }
+    }
+
+    fn lint_enum_values(&mut self, enum_name: StringKey, id: EnumID) {
+        let schema = self.schema;
+        for value in &schema.enum_(id).values {
+            if !is_linted(value.location) {
+                continue;
+            }
+            if self.config.naming_conventions && !is_screaming_snake_case(value.value.lookup()) {
+                self.warn(
+                    SchemaLintMessage::EnumValueName {
+                        enum_name,
+                        value: value.value,
+                    },
+                    value.location,
+                );
+            }
+            if self.config.deprecation_reasons
+                && value
+                    .deprecated()
+                    .map_or(false, |deprecation| deprecation.reason.is_none())
+            {
+                self.warn(
+                    SchemaLintMessage::DeprecationWithoutReason {
+                        coordinate: format!("{}.{}", enum_name, value.value),
+                    },
+                    value.location,
+                );
+            }
+        }
+    }
+
+    /// The types used by the root operation types, directly or through
+    /// other types, and by the arguments of directives. The implementations
+    /// of a reachable interface are reachable too.
+    fn reachable_types(&self) -> FnvHashSet<Type> {
+        let mut stack = [
+            self.schema.query_type(),
+            self.schema.mutation_type(),
+            self.schema.subscription_type(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        for directive in self.schema.get_directives() {
+            stack.extend(
+                directive
+                    .arguments
+                    .iter()
+                    .map(|argument| argument.type_.inner()),
+            );
+        }
+
+        let mut reachable_types = FnvHashSet::default();
+        while let Some(type_) = stack.pop() {
+            if !reachable_types.insert(type_) {
+                continue;
+            }
+            match type_ {
+                Type::Object(id) => {
+                    let object = self.schema.object(id);
+                    self.push_field_types(&object.fields, &mut stack);
+                    stack.extend(object.interfaces.iter().copied().map(Type::Interface));
+                }
+                Type::Interface(id) => {
+                    let interface = self.schema.interface(id);
+                    self.push_field_types(&interface.fields, &mut stack);
+                    stack.extend(interface.interfaces.iter().copied().map(Type::Interface));
+                    stack.extend(
+                        interface
+                            .implementing_interfaces
+                            .iter()
+                            .copied()
+                            .map(Type::Interface),
+                    );
+                    stack.extend(
+                        interface
+                            .implementing_objects
+                            .iter()
+                            .copied()
+                            .map(Type::Object),
+                    );
+                }
+                Type::Union(id) => {
+                    stack.extend(
+                        self.schema
+                            .union(id)
+                            .members
+                            .iter()
+                            .copied()
+                            .map(Type::Object),
+                    );
+                }
+                Type::InputObject(id) => {
+                    stack.extend(
+                        self.schema
+                            .input_object(id)
+                            .fields
+                            .iter()
+                            .map(|field| field.type_.inner()),
+                    );
+                }
+                Type::Enum(_) | Type::Scalar(_) => {}
+            }
+        }
+        reachable_types
+    }
+
+    fn push_field_types(&self, fields: &[FieldID], stack: &mut Vec<Type>) {
+        for field_id in fields {
+            let field = self.schema.field(*field_id);
+            stack.push(field.type_.inner());
+            stack.extend(
+                field
+                    .arguments
+                    .iter()
+                    .map(|argument| argument.type_.inner()),
+            );
+        }
+    }
+
+    fn type_location(&self, type_: Type) -> Location {
+        match type_ {
+            Type::Enum(id) => self.schema.enum_(id).name.location,
+            Type::InputObject(id) => self.schema.input_object(id).name.location,
+            Type::Interface(id) => self.schema.interface(id).name.location,
+            Type::Object(id) => self.schema.object(id).name.location,
+            Type::Scalar(id) => self.schema.scalar(id).name.location,
+            Type::Union(id) => self.schema.union(id).name.location,
+        }
+    }
+
+    fn warn(&mut self, message: SchemaLintMessage, location: Location) {
+        self.diagnostics
+            .push(Diagnostic::warning(message, location, Vec::new()));
+    }
+}
+
+/// The builtin definitions, and those the compiler adds, have generated
+/// locations.
+fn is_linted(location: Location) -> bool {
+    !location.source_location().is_generated()
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    let mut chars = name.trim_start_matches('_').chars();
+    chars.next().map_or(false, |c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_camel_case(name: &str) -> bool {
+    let mut chars = name.trim_start_matches('_').chars();
+    chars.next().map_or(false, |c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_screaming_snake_case(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().map_or(false, |c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
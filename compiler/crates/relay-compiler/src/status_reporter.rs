@@ -5,15 +5,27 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use common::Diagnostic;
 use common::DiagnosticSeverity;
+use extract_graphql::ExtractOptions;
 use graphql_cli::DiagnosticPrinter;
+use graphql_cli::DiagnosticPrinterOptions;
 use log::error;
 use log::info;
 use log::warn;
+use relay_transforms::diagnostic_code;
 
+use crate::build_report::BuildReport;
+use crate::compiler_state::ProjectName;
+use crate::diagnostics_json::json_diagnostics;
+use crate::diagnostics_json::json_diagnostics_of_error;
+use crate::diagnostics_json::JsonDiagnostic;
+use crate::diagnostics_summary::strip_styles;
+use crate::diagnostics_summary::DiagnosticsSummary;
 use crate::errors::BuildProjectError;
 use crate::errors::Error;
 use crate::source_for_location;
@@ -24,22 +36,33 @@ pub trait StatusReporter {
     fn build_starts(&self);
     fn build_completes(&self, diagnostics: &[Diagnostic]);
     fn build_errors(&self, error: &Error);
+
+    /// Called in watch mode while changes are batched before a build.
+    fn changes_queued(&self, _file_count: usize) {}
 }
 
 pub struct ConsoleStatusReporter {
     source_reader: Box<dyn SourceReader + Send + Sync>,
     root_dir: PathBuf,
     is_multi_project: bool,
+    printer_options: DiagnosticPrinterOptions,
 }
 
 impl ConsoleStatusReporter {
-    pub fn new(root_dir: PathBuf, is_multi_project: bool) -> Self {
+    pub fn new(root_dir: PathBuf, is_multi_project: bool, extract_options: ExtractOptions) -> Self {
         Self {
             root_dir,
-            source_reader: Box::new(FsSourceReader),
+            source_reader: Box::new(FsSourceReader::new(extract_options)),
             is_multi_project,
+            printer_options: Default::default(),
         }
     }
+
+    /// The context lines and snippets of the printed diagnostics.
+    pub fn with_printer_options(mut self, printer_options: DiagnosticPrinterOptions) -> Self {
+        self.printer_options = printer_options;
+        self
+    }
 }
 
 impl ConsoleStatusReporter {
@@ -130,8 +153,13 @@ impl ConsoleStatusReporter {
         let printer = DiagnosticPrinter::new(|source_location| {
             source_for_location(&self.root_dir, source_location, self.source_reader.as_ref())
                 .map(|source| source.to_text_source())
-        });
-        printer.diagnostic_to_string(diagnostic)
+        })
+        .with_options(self.printer_options);
+        let output = printer.diagnostic_to_string(diagnostic);
+        match diagnostic_code(diagnostic) {
+            Some(code) => format!("[{}] {}", code, output),
+            None => output,
+        }
     }
 }
 
@@ -150,4 +178,228 @@ impl StatusReporter for ConsoleStatusReporter {
             error!("Compilation failed.");
         }
     }
+
+    fn changes_queued(&self, file_count: usize) {
+        info!(
+            "{} changed file(s) queued, waiting for more changes...",
+            file_count
+        );
+    }
+}
+
+/// Prints the diagnostics of each build to stdout, for the machine-readable
+/// formats of `--diagnosticsFormat`, e.g. `print_json_diagnostics` or
+/// `print_sarif`. The logs go to stderr.
+pub struct JsonStatusReporter {
+    source_reader: Box<dyn SourceReader + Send + Sync>,
+    root_dir: PathBuf,
+    print: fn(&[JsonDiagnostic]) -> String,
+}
+
+impl JsonStatusReporter {
+    pub fn new(
+        root_dir: PathBuf,
+        extract_options: ExtractOptions,
+        print: fn(&[JsonDiagnostic]) -> String,
+    ) -> Self {
+        Self {
+            root_dir,
+            source_reader: Box::new(FsSourceReader::new(extract_options)),
+            print,
+        }
+    }
+}
+
+impl StatusReporter for JsonStatusReporter {
+    fn build_starts(&self) {}
+
+    fn build_completes(&self, diagnostics: &[Diagnostic]) {
+        let diagnostics = json_diagnostics(
+            &self.root_dir,
+            diagnostics,
+            None,
+            self.source_reader.as_ref(),
+        );
+        println!("{}", (self.print)(&diagnostics));
+    }
+
+    fn build_errors(&self, error: &Error) {
+        if !matches!(error, Error::Cancelled) {
+            let diagnostics =
+                json_diagnostics_of_error(&self.root_dir, error, self.source_reader.as_ref());
+            println!("{}", (self.print)(&diagnostics));
+        }
+    }
+}
+
+/// Prints a summary of the diagnostics of each build, grouped by file and by
+/// rule, instead of all of them, which are written to a report file.
+pub struct SummaryStatusReporter {
+    console: ConsoleStatusReporter,
+    report_path: PathBuf,
+    top: usize,
+}
+
+impl SummaryStatusReporter {
+    pub fn new(console: ConsoleStatusReporter, report_path: PathBuf, top: usize) -> Self {
+        Self {
+            console,
+            report_path,
+            top,
+        }
+    }
+
+    fn print_summary(&self, diagnostics: &[(Option<ProjectName>, &Diagnostic)]) {
+        let summary =
+            DiagnosticsSummary::new(diagnostics.iter().map(|(_, diagnostic)| *diagnostic));
+        if summary.is_empty() {
+            return;
+        }
+        let report = diagnostics
+            .iter()
+            .map(|(project_name, diagnostic)| {
+                let output = strip_styles(&self.console.print_diagnostic(diagnostic));
+                match project_name {
+                    Some(project_name) => format!("In the project `{}`: {}", project_name, output),
+                    None => output,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let report_path = match fs::write(&self.report_path, report) {
+            Ok(()) => Some(self.report_path.as_path()),
+            Err(err) => {
+                warn!(
+                    "Unable to write the diagnostics report to {}: {}",
+                    self.report_path.display(),
+                    err
+                );
+                None
+            }
+        };
+        let output = summary.print(self.top, report_path);
+        if diagnostics
+            .iter()
+            .any(|(_, diagnostic)| diagnostic.severity() == DiagnosticSeverity::ERROR)
+        {
+            error!("{}", output);
+        } else {
+            warn!("{}", output);
+        }
+    }
+}
+
+impl StatusReporter for SummaryStatusReporter {
+    fn build_starts(&self) {}
+
+    fn build_completes(&self, diagnostics: &[Diagnostic]) {
+        let diagnostics = diagnostics
+            .iter()
+            .map(|diagnostic| (None, diagnostic))
+            .collect::<Vec<_>>();
+        self.print_summary(&diagnostics);
+        info!("Compilation completed.");
+    }
+
+    fn build_errors(&self, error: &Error) {
+        let mut diagnostics = vec![];
+        match error {
+            Error::DiagnosticsError { errors } => {
+                diagnostics.extend(errors.iter().map(|diagnostic| (None, diagnostic)));
+            }
+            Error::BuildProjectsErrors { errors } => {
+                for error in errors {
+                    match error {
+                        BuildProjectError::ValidationErrors {
+                            errors,
+                            project_name,
+                        } => diagnostics.extend(
+                            errors
+                                .iter()
+                                .map(|diagnostic| (Some(*project_name), diagnostic)),
+                        ),
+                        error => self.console.print_project_error(error),
+                    }
+                }
+            }
+            Error::Cancelled => {
+                info!("Compilation cancelled due to new changes.");
+                return;
+            }
+            error => {
+                error!("{}", error);
+            }
+        }
+        self.print_summary(&diagnostics);
+        error!("Compilation failed.");
+    }
+
+    fn changes_queued(&self, file_count: usize) {
+        self.console.changes_queued(file_count);
+    }
+}
+
+/// Writes the `BuildReport` of each build, including those of watch mode,
+/// and passes the builds on to the wrapped reporter. Failing to write the
+/// report doesn't fail the build.
+pub struct BuildReportStatusReporter {
+    inner: Box<dyn StatusReporter + Send + Sync>,
+    report: Arc<BuildReport>,
+}
+
+impl BuildReportStatusReporter {
+    pub fn new(inner: Box<dyn StatusReporter + Send + Sync>, report: Arc<BuildReport>) -> Self {
+        Self { inner, report }
+    }
+
+    fn write_report(&self, json: String) {
+        if let Err(err) = fs::write(self.report.path(), json) {
+            warn!(
+                "Unable to write the build report to {}: {}",
+                self.report.path().display(),
+                err
+            );
+        }
+    }
+}
+
+impl StatusReporter for BuildReportStatusReporter {
+    fn build_starts(&self) {
+        self.report.start();
+        self.inner.build_starts();
+    }
+
+    fn build_completes(&self, diagnostics: &[Diagnostic]) {
+        self.inner.build_completes(diagnostics);
+        self.write_report(self.report.to_json(true, diagnostics, vec![]));
+    }
+
+    fn build_errors(&self, error: &Error) {
+        self.inner.build_errors(error);
+        let mut diagnostics = vec![];
+        let mut errors = vec![];
+        match error {
+            // The next build reports the changes of the cancelled one.
+            Error::Cancelled => return,
+            Error::DiagnosticsError { errors } => diagnostics.extend(errors),
+            Error::BuildProjectsErrors {
+                errors: project_errors,
+            } => {
+                for error in project_errors {
+                    match error {
+                        BuildProjectError::ValidationErrors { errors, .. } => {
+                            diagnostics.extend(errors)
+                        }
+                        error => errors.push(error.to_string()),
+                    }
+                }
+            }
+            error => errors.push(error.to_string()),
+        }
+        self.write_report(self.report.to_json(false, diagnostics, errors));
+    }
+
+    fn changes_queued(&self, file_count: usize) {
+        self.inner.changes_queued(file_count);
+    }
 }
@@ -33,6 +33,7 @@ use relay_codegen::print_operation;
 use relay_codegen::print_request;
 use relay_codegen::JsModuleFormat;
 use relay_compiler::find_duplicates;
+use relay_compiler::operation_text_printer_options;
 use relay_compiler::validate;
 use relay_compiler::ConfigFileProject;
 use relay_compiler::ProjectConfig;
@@ -113,6 +114,7 @@ pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
         compact_query_text: FeatureFlag::Disabled,
         emit_normalization_nodes_for_client_edges: true,
         relay_resolver_enable_output_type: FeatureFlag::Disabled,
+        enable_fragment_variable_definitions: false,
     };
 
     let default_project_config = ProjectConfig {
@@ -146,6 +148,7 @@ pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
                     schema_config: config_file_project.schema_config,
                     typegen_config: config_file_project.typegen_config,
                     module_import_config: config_file_project.module_import_config,
+                    operation_text_config: config_file_project.operation_text_config,
                     feature_flags: config_file_project
                         .feature_flags
                         .map_or(default_project_config.feature_flags, |flags| {
@@ -201,6 +204,7 @@ pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
         Arc::new(ConsoleLogger),
         None,
         None,
+        None,
     )
     .map_err(|diagnostics| diagnostics_to_sorted_string(fixture.content, &diagnostics))?;
 
@@ -230,7 +234,7 @@ pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
                         print_full_operation(
                             &programs.operation_text,
                             print_operation_node,
-                            Default::default(),
+                            operation_text_printer_options(&project_config),
                         )
                     },
                 );
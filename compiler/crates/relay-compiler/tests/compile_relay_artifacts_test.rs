@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<2a17651ff486f00579270758059a000e>>
+ * @generated SignedSource<<54d88a55b6f7a6fdac86e2dcaf757a76>>
  */
 
 mod compile_relay_artifacts;
@@ -418,6 +418,13 @@ fn directive_with_conditions() {
     test_fixture(transform_fixture, "directive_with_conditions.graphql", "compile_relay_artifacts/fixtures/directive_with_conditions.expected", input, expected);
 }
 
+#[test]
+fn directive_with_conditions_sorted() {
+    let input = include_str!("compile_relay_artifacts/fixtures/directive_with_conditions_sorted.graphql");
+    let expected = include_str!("compile_relay_artifacts/fixtures/directive_with_conditions_sorted.expected");
+    test_fixture(transform_fixture, "directive_with_conditions_sorted.graphql", "compile_relay_artifacts/fixtures/directive_with_conditions_sorted.expected", input, expected);
+}
+
 #[test]
 fn duplicate_directive_invalid() {
     let input = include_str!("compile_relay_artifacts/fixtures/duplicate-directive.invalid.graphql");
@@ -880,6 +887,13 @@ fn query_with_and_without_module_directive() {
     test_fixture(transform_fixture, "query-with-and-without-module-directive.graphql", "compile_relay_artifacts/fixtures/query-with-and-without-module-directive.expected", input, expected);
 }
 
+#[test]
+fn query_with_compact_operation_text() {
+    let input = include_str!("compile_relay_artifacts/fixtures/query-with-compact-operation-text.graphql");
+    let expected = include_str!("compile_relay_artifacts/fixtures/query-with-compact-operation-text.expected");
+    test_fixture(transform_fixture, "query-with-compact-operation-text.graphql", "compile_relay_artifacts/fixtures/query-with-compact-operation-text.expected", input, expected);
+}
+
 #[test]
 fn query_with_conditional_module() {
     let input = include_str!("compile_relay_artifacts/fixtures/query-with-conditional-module.graphql");
@@ -0,0 +1,67 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::ConsoleLogger;
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build_ir_in_relay_mode;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use intern::string_key::Intern;
+use intern::Lookup;
+use relay_compiler::dependency_graph::DependencyGraph;
+use relay_compiler::dependency_graph::GraphNodeKind;
+use relay_compiler::ProjectConfig;
+use relay_test_schema::get_test_schema;
+use relay_transforms::apply_transforms;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let schema = get_test_schema();
+    let ast = parse_executable(fixture.content, source_location)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(fixture.content, &diagnostics))?;
+    let ir = build_ir_in_relay_mode(&schema, &ast.definitions)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(fixture.content, &diagnostics))?;
+    let program = Program::from_definitions(Arc::clone(&schema), ir);
+    let programs = apply_transforms(
+        &ProjectConfig::default(),
+        Arc::new(program),
+        Default::default(),
+        Arc::new(ConsoleLogger),
+        None,
+        None,
+        None,
+    )
+    .map_err(|diagnostics| diagnostics_to_sorted_string(fixture.content, &diagnostics))?;
+
+    let graph = DependencyGraph::new("test".intern(), &programs);
+    let paths: Vec<String> = graph
+        .nodes
+        .iter()
+        .filter(|node| node.kind == GraphNodeKind::Fragment)
+        .map(|node| {
+            let paths: Vec<String> = graph
+                .paths_to(node.name)
+                .iter()
+                .map(|path| {
+                    let names: Vec<&str> = path.iter().map(|name| name.lookup()).collect();
+                    format!("  {}", names.join(" -> "))
+                })
+                .collect();
+            format!("paths to {}:\n{}", node.name, paths.join("\n"))
+        })
+        .collect();
+    Ok(format!(
+        "{}\n{}\n\n{}",
+        graph.to_dot(),
+        graph.to_json(),
+        paths.join("\n")
+    ))
+}
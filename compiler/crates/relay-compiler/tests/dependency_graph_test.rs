@@ -0,0 +1,20 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<e7e24fa4ee31962c26f6e4df3509245b>>
+ */
+
+mod dependency_graph;
+
+use dependency_graph::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn spreads() {
+    let input = include_str!("dependency_graph/fixtures/spreads.graphql");
+    let expected = include_str!("dependency_graph/fixtures/spreads.expected");
+    test_fixture(transform_fixture, "spreads.graphql", "dependency_graph/fixtures/spreads.expected", input, expected);
+}
@@ -0,0 +1,52 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::ConsoleLogger;
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build_ir_in_relay_mode;
+use graphql_ir::OperationDefinition;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use relay_compiler::explain::explain_operation;
+use relay_compiler::explain::ExplainPhase;
+use relay_compiler::ProjectConfig;
+use relay_test_schema::get_test_schema;
+use relay_transforms::apply_transforms;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let schema = get_test_schema();
+    let ast = parse_executable(fixture.content, source_location)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(fixture.content, &diagnostics))?;
+    let ir = build_ir_in_relay_mode(&schema, &ast.definitions)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(fixture.content, &diagnostics))?;
+    let program = Program::from_definitions(Arc::clone(&schema), ir);
+    let programs = apply_transforms(
+        &ProjectConfig::default(),
+        Arc::new(program),
+        Default::default(),
+        Arc::new(ConsoleLogger),
+        None,
+        None,
+        None,
+    )
+    .map_err(|diagnostics| diagnostics_to_sorted_string(fixture.content, &diagnostics))?;
+
+    let mut operations: Vec<&Arc<OperationDefinition>> = programs.source.operations().collect();
+    operations.sort_by_key(|operation| operation.name.item.0);
+    Ok(operations
+        .into_iter()
+        .map(|operation| {
+            explain_operation(&programs, operation.name.item, &ExplainPhase::ALL, false).unwrap()
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<cf94e3863f64fd5e623a214f47bffe86>>
+ */
+
+mod explain;
+
+use explain::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn defer() {
+    let input = include_str!("explain/fixtures/defer.graphql");
+    let expected = include_str!("explain/fixtures/defer.expected");
+    test_fixture(transform_fixture, "defer.graphql", "explain/fixtures/defer.expected", input, expected);
+}
+
+#[test]
+fn directives() {
+    let input = include_str!("explain/fixtures/directives.graphql");
+    let expected = include_str!("explain/fixtures/directives.expected");
+    test_fixture(transform_fixture, "directives.graphql", "explain/fixtures/directives.expected", input, expected);
+}
@@ -0,0 +1,64 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build_ir_in_relay_mode;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use relay_compiler::schema_impact::build_diff_schema;
+use relay_compiler::schema_impact::find_change_impact;
+use relay_compiler::schema_impact::print_impact_text;
+use relay_compiler::ProjectConfig;
+use relay_compiler::SourceReader;
+use schema_diff::breaking::find_schema_changes;
+
+struct FixtureSourceReader(String);
+
+impl SourceReader for FixtureSourceReader {
+    fn read_file_to_string(&self, _path: &PathBuf) -> std::io::Result<String> {
+        Ok(self.0.clone())
+    }
+}
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let parts: Vec<_> = fixture.content.split("%old_schema%").collect();
+    let (documents, schemas) = match parts.as_slice() {
+        [documents, schemas] => (*documents, *schemas),
+        _ => panic!("Expected the documents to be followed by %old_schema%."),
+    };
+    let (old_text, new_text) = schemas
+        .split_once("%new_schema%")
+        .expect("Expected the old and the new schema to be separated by %new_schema%.");
+
+    let project_config = ProjectConfig::default();
+    let old = build_diff_schema(&project_config, "old.graphql", old_text)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(old_text, &diagnostics))?;
+    let new = build_diff_schema(&project_config, "new.graphql", new_text)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(new_text, &diagnostics))?;
+
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let ast = parse_executable(documents, source_location)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(documents, &diagnostics))?;
+    let changes = find_schema_changes(&old, &new);
+    let old = Arc::new(old);
+    let ir = build_ir_in_relay_mode(&old, &ast.definitions)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(documents, &diagnostics))?;
+    let program = Program::from_definitions(old, ir);
+
+    let impacts = find_change_impact(&program, changes);
+    Ok(print_impact_text(
+        Path::new("/"),
+        &impacts,
+        &FixtureSourceReader(fixture.content.to_string()),
+    ))
+}
@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<a0bbf8c8f04829d0ad8d51e29fd429d8>>
+ */
+
+mod schema_impact;
+
+use schema_impact::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn fields_and_arguments() {
+    let input = include_str!("schema_impact/fixtures/fields-and-arguments.graphql");
+    let expected = include_str!("schema_impact/fixtures/fields-and-arguments.expected");
+    test_fixture(transform_fixture, "fields-and-arguments.graphql", "schema_impact/fixtures/fields-and-arguments.expected", input, expected);
+}
+
+#[test]
+fn input_values() {
+    let input = include_str!("schema_impact/fixtures/input-values.graphql");
+    let expected = include_str!("schema_impact/fixtures/input-values.expected");
+    test_fixture(transform_fixture, "input-values.graphql", "schema_impact/fixtures/input-values.expected", input, expected);
+}
@@ -0,0 +1,34 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use relay_compiler::schema_lint::lint_schema;
+use relay_config::SchemaLintConfig;
+use relay_schema::build_schema_with_extensions;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let (sdl, config) = match fixture.content.split_once("%config%") {
+        Some((sdl, config)) => (
+            sdl,
+            serde_json::from_str::<SchemaLintConfig>(config).unwrap(),
+        ),
+        None => (fixture.content, SchemaLintConfig::default()),
+    };
+
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let schema = build_schema_with_extensions::<_, &str>(&[(sdl, source_location)], &[])
+        .map_err(|diagnostics| diagnostics_to_sorted_string(fixture.content, &diagnostics))?;
+
+    let diagnostics = lint_schema(&schema, &config);
+    if diagnostics.is_empty() {
+        Ok("OK".to_string())
+    } else {
+        Ok(diagnostics_to_sorted_string(fixture.content, &diagnostics))
+    }
+}
@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<e5da800de57dc62ac117b6b5dcaff812>>
+ */
+
+mod schema_lint;
+
+use schema_lint::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn rules_disabled() {
+    let input = include_str!("schema_lint/fixtures/rules-disabled.graphql");
+    let expected = include_str!("schema_lint/fixtures/rules-disabled.expected");
+    test_fixture(transform_fixture, "rules-disabled.graphql", "schema_lint/fixtures/rules-disabled.expected", input, expected);
+}
+
+#[test]
+fn schema_lint() {
+    let input = include_str!("schema_lint/fixtures/schema-lint.graphql");
+    let expected = include_str!("schema_lint/fixtures/schema-lint.expected");
+    test_fixture(transform_fixture, "schema-lint.graphql", "schema_lint/fixtures/schema-lint.expected", input, expected);
+}
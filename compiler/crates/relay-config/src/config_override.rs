@@ -0,0 +1,258 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use common::FeatureFlag;
+use common::FeatureFlags;
+use common::ScalarName;
+use common::SourceLocationKey;
+use fnv::FnvBuildHasher;
+use glob::Pattern;
+use glob::PatternError;
+use indexmap::IndexMap;
+use indexmap::IndexSet;
+use intern::string_key::StringKey;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::CustomScalarType;
+
+type FnvIndexMap<K, V> = IndexMap<K, V, FnvBuildHasher>;
+
+/// Settings of a project that are changed for the documents in a subset of
+/// its files, e.g. to gradually roll out a feature flag per directory.
+#[derive(Debug)]
+pub struct ConfigOverride {
+    pub files: Vec<FilePattern>,
+    pub feature_flags: FeatureFlagOverrides,
+    /// Merged over the `customScalarTypes` of the project.
+    pub custom_scalar_types: FnvIndexMap<ScalarName, CustomScalarType>,
+}
+
+impl ConfigOverride {
+    pub fn matches(&self, location: SourceLocationKey) -> bool {
+        if let SourceLocationKey::Generated = location {
+            return false;
+        }
+        let path = Path::new(location.path());
+        self.files.iter().any(|pattern| pattern.matches(path))
+    }
+}
+
+#[derive(Debug)]
+pub enum FilePattern {
+    /// Matches all files in the directory, or the file itself.
+    Path(PathBuf),
+    Glob(Pattern),
+}
+
+impl FilePattern {
+    /// Patterns with glob characters are globs, others are paths. Both are
+    /// relative to the root directory.
+    pub fn new(pattern: &str) -> Result<Self, PatternError> {
+        if pattern.contains(['*', '?', '[']) {
+            Pattern::new(pattern).map(FilePattern::Glob)
+        } else {
+            Ok(FilePattern::Path(PathBuf::from(pattern)))
+        }
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        match self {
+            FilePattern::Path(prefix) => path.starts_with(prefix),
+            FilePattern::Glob(pattern) => pattern.matches_path(path),
+        }
+    }
+}
+
+/// The feature flags that are checked per fragment or operation, and can
+/// therefore be enabled or disabled for the documents of some files.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeatureFlagOverrides {
+    #[serde(default)]
+    pub no_inline: Option<FeatureFlag>,
+
+    #[serde(default)]
+    pub actor_change_support: Option<FeatureFlag>,
+
+    #[serde(default)]
+    pub enable_fragment_aliases: Option<FeatureFlag>,
+}
+
+impl FeatureFlagOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.no_inline.is_none()
+            && self.actor_change_support.is_none()
+            && self.enable_fragment_aliases.is_none()
+    }
+}
+
+type FeatureFlagAccessors = (
+    fn(&FeatureFlagOverrides) -> &Option<FeatureFlag>,
+    fn(&mut FeatureFlags) -> &mut FeatureFlag,
+);
+
+const OVERRIDABLE_FEATURE_FLAGS: [FeatureFlagAccessors; 3] = [
+    (
+        |overrides| &overrides.no_inline,
+        |flags| &mut flags.no_inline,
+    ),
+    (
+        |overrides| &overrides.actor_change_support,
+        |flags| &mut flags.actor_change_support,
+    ),
+    (
+        |overrides| &overrides.enable_fragment_aliases,
+        |flags| &mut flags.enable_fragment_aliases,
+    ),
+];
+
+/// Returns the feature flags with the overrides applied to the given
+/// documents, as `(name, location)` of each fragment and operation.
+/// Flags with overrides are resolved to a `limited` allowlist of the enabled
+/// documents, unless they end up enabled for all documents.
+pub(crate) fn apply_feature_flag_overrides(
+    feature_flags: &FeatureFlags,
+    overrides: &[ConfigOverride],
+    documents: &[(StringKey, SourceLocationKey)],
+) -> FeatureFlags {
+    let mut feature_flags = feature_flags.clone();
+    for (get_override, get_flag) in OVERRIDABLE_FEATURE_FLAGS {
+        let flag_overrides: Vec<(&ConfigOverride, &FeatureFlag)> = overrides
+            .iter()
+            .filter_map(|config_override| {
+                get_override(&config_override.feature_flags)
+                    .as_ref()
+                    .map(|flag| (config_override, flag))
+            })
+            .collect();
+        if flag_overrides.is_empty() {
+            continue;
+        }
+
+        let flag = get_flag(&mut feature_flags);
+        let mut allowlist = match flag {
+            FeatureFlag::Limited { allowlist } => allowlist.clone(),
+            _ => IndexSet::new(),
+        };
+        let mut enabled_for_all = true;
+        for (name, location) in documents {
+            // Later overrides take precedence.
+            let document_flag = flag_overrides
+                .iter()
+                .rev()
+                .find(|(config_override, _)| config_override.matches(*location))
+                .map_or(&*flag, |(_, flag)| flag);
+            if document_flag.is_enabled_for(*name) {
+                allowlist.insert(*name);
+            } else {
+                allowlist.remove(name);
+                enabled_for_all = false;
+            }
+        }
+        if !(enabled_for_all && flag.is_fully_enabled()) {
+            *flag = FeatureFlag::Limited { allowlist };
+        }
+    }
+    feature_flags
+}
+
+#[cfg(test)]
+mod tests {
+    use intern::string_key::Intern;
+    use intern::Lookup;
+
+    use super::*;
+
+    fn config_override(files: &str, no_inline: FeatureFlag) -> ConfigOverride {
+        ConfigOverride {
+            files: vec![FilePattern::new(files).unwrap()],
+            feature_flags: FeatureFlagOverrides {
+                no_inline: Some(no_inline),
+                ..Default::default()
+            },
+            custom_scalar_types: Default::default(),
+        }
+    }
+
+    fn documents() -> Vec<(StringKey, SourceLocationKey)> {
+        vec![
+            (
+                "TeamA_fragment".intern(),
+                SourceLocationKey::embedded("src/team-a/Foo.js", 0),
+            ),
+            (
+                "TeamB_fragment".intern(),
+                SourceLocationKey::embedded("src/team-b/Bar.js", 0),
+            ),
+            (
+                "TeamANested_fragment".intern(),
+                SourceLocationKey::standalone("src/team-a/nested/Baz.graphql"),
+            ),
+        ]
+    }
+
+    fn allowlist(flag: &FeatureFlag) -> Vec<&str> {
+        match flag {
+            FeatureFlag::Limited { allowlist } => {
+                let mut names: Vec<_> = allowlist.iter().map(|name| name.lookup()).collect();
+                names.sort_unstable();
+                names
+            }
+            other => panic!("Expected a limited flag, got {}", other),
+        }
+    }
+
+    #[test]
+    fn enables_flag_for_directory() {
+        let flags = apply_feature_flag_overrides(
+            &FeatureFlags::default(),
+            &[config_override("src/team-a", FeatureFlag::Enabled)],
+            &documents(),
+        );
+        assert_eq!(
+            allowlist(&flags.no_inline),
+            vec!["TeamANested_fragment", "TeamA_fragment"]
+        );
+        assert!(matches!(flags.actor_change_support, FeatureFlag::Disabled));
+    }
+
+    #[test]
+    fn later_overrides_take_precedence() {
+        let flags = apply_feature_flag_overrides(
+            &FeatureFlags {
+                no_inline: FeatureFlag::Enabled,
+                ..Default::default()
+            },
+            &[
+                config_override("src/team-a", FeatureFlag::Disabled),
+                config_override("src/**/nested/*", FeatureFlag::Enabled),
+            ],
+            &documents(),
+        );
+        assert_eq!(
+            allowlist(&flags.no_inline),
+            vec!["TeamANested_fragment", "TeamB_fragment"]
+        );
+    }
+
+    #[test]
+    fn keeps_enabled_flag_without_matching_documents() {
+        let flags = apply_feature_flag_overrides(
+            &FeatureFlags {
+                no_inline: FeatureFlag::Enabled,
+                ..Default::default()
+            },
+            &[config_override("src/team-c", FeatureFlag::Disabled)],
+            &documents(),
+        );
+        assert!(flags.no_inline.is_fully_enabled());
+    }
+}
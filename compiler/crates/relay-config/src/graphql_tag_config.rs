@@ -0,0 +1,37 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Configuration of the template literals the compiler extracts GraphQL from.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct GraphQLTagConfig {
+    /// The tags of the template literals containing GraphQL. Tags may be
+    /// members of an import, e.g. `graphql.experimental` or `Relay.graphql`.
+    #[serde(default = "default_tag_names")]
+    pub tag_names: Vec<String>,
+
+    /// Also extract the untagged template literals annotated with a
+    /// `/* GraphQL */` comment.
+    #[serde(default)]
+    pub comment_annotated_templates: bool,
+}
+
+impl Default for GraphQLTagConfig {
+    fn default() -> Self {
+        Self {
+            tag_names: default_tag_names(),
+            comment_annotated_templates: false,
+        }
+    }
+}
+
+fn default_tag_names() -> Vec<String> {
+    vec!["graphql".to_string()]
+}
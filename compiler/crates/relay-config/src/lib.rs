@@ -9,29 +9,40 @@
 #![deny(rust_2018_idioms)]
 #![deny(clippy::all)]
 
+mod config_override;
 mod connection_interface;
 mod diagnostic_report_config;
+mod graphql_tag_config;
 mod js_module_format;
 mod module_import_config;
 mod non_node_id_fields_config;
+mod operation_text_config;
 mod project_config;
+mod schema_lint_config;
 mod typegen_config;
 
+pub use config_override::ConfigOverride;
+pub use config_override::FeatureFlagOverrides;
+pub use config_override::FilePattern;
 pub use connection_interface::ConnectionInterface;
 pub use diagnostic_report_config::DiagnosticLevel;
 pub use diagnostic_report_config::DiagnosticReportConfig;
+pub use graphql_tag_config::GraphQLTagConfig;
 pub use js_module_format::JsModuleFormat;
 pub use module_import_config::DynamicModuleProvider;
 pub use module_import_config::ModuleImportConfig;
 pub use non_node_id_fields_config::NonNodeIdFieldsConfig;
+pub use operation_text_config::OperationTextConfig;
 pub use project_config::LocalPersistAlgorithm;
 pub use project_config::LocalPersistConfig;
 pub use project_config::PersistConfig;
 pub use project_config::ProjectConfig;
 pub use project_config::ProjectName;
 pub use project_config::RemotePersistConfig;
+pub use project_config::RemoteSchemaConfig;
 pub use project_config::SchemaConfig;
 pub use project_config::SchemaLocation;
+pub use schema_lint_config::SchemaLintConfig;
 pub use typegen_config::CustomScalarType;
 pub use typegen_config::CustomScalarTypeImport;
 pub use typegen_config::FlowTypegenConfig;
@@ -0,0 +1,33 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Configuration of the layout of the operation text of the generated
+/// artifacts, and of the order of its directives, arguments and variable
+/// definitions. By default they are printed in the order they are written in.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct OperationTextConfig {
+    /// Print the text on a single line, without the whitespace that isn't
+    /// needed to separate tokens.
+    #[serde(default)]
+    pub compact: bool,
+
+    /// Sort the arguments, and the fields of input object values, by name.
+    #[serde(default)]
+    pub sort_arguments: bool,
+
+    /// Sort the directives by name, `@include` and `@skip` first.
+    #[serde(default)]
+    pub sort_directives: bool,
+
+    /// Sort the variable definitions of operations by name.
+    #[serde(default)]
+    pub sort_variable_definitions: bool,
+}
@@ -15,6 +15,8 @@ use std::usize;
 use common::DirectiveName;
 use common::FeatureFlags;
 use common::Rollout;
+use common::ScalarName;
+use common::SchemaCoordinate;
 use common::SourceLocationKey;
 use common::WithLocation;
 use fmt::Debug;
@@ -30,10 +32,16 @@ use serde::Deserializer;
 use serde::Serialize;
 use serde_json::Value;
 
+use crate::config_override::apply_feature_flag_overrides;
 use crate::connection_interface::ConnectionInterface;
 use crate::diagnostic_report_config::DiagnosticReportConfig;
+use crate::graphql_tag_config::GraphQLTagConfig;
 use crate::module_import_config::ModuleImportConfig;
 use crate::non_node_id_fields_config::NonNodeIdFieldsConfig;
+use crate::operation_text_config::OperationTextConfig;
+use crate::schema_lint_config::SchemaLintConfig;
+use crate::ConfigOverride;
+use crate::CustomScalarType;
 use crate::JsModuleFormat;
 use crate::TypegenConfig;
 use crate::TypegenLanguage;
@@ -65,6 +73,39 @@ pub struct RemotePersistConfig {
 
     #[serde(default)]
     pub include_query_text: bool,
+
+    /// Hash the printed text of operations, instead of their canonical text,
+    /// to keep the hashes of operations persisted by older versions.
+    #[serde(default)]
+    pub legacy_text_hash: bool,
+}
+
+/// A schema that is fetched from a GraphQL endpoint with an introspection
+/// query instead of being read from the file system.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct RemoteSchemaConfig {
+    /// URL of the GraphQL endpoint to send the introspection query to.
+    pub url: String,
+
+    /// Additional headers to send
+    #[serde(default)]
+    pub headers: FnvIndexMap<String, String>,
+
+    /// Path, relative to the root directory, where the fetched schema is
+    /// cached as SDL. Defaults to `.relay/schemas/<project>.graphql`.
+    #[serde(default)]
+    pub cache_file: Option<PathBuf>,
+}
+
+impl RemoteSchemaConfig {
+    pub fn cache_file_for_project(&self, project_name: ProjectName) -> PathBuf {
+        self.cache_file.clone().unwrap_or_else(|| {
+            PathBuf::from(".relay")
+                .join("schemas")
+                .join(format!("{}.graphql", project_name))
+        })
+    }
 }
 
 fn deserialize_semaphore_permits<'de, D>(d: D) -> Result<Option<usize>, D::Error>
@@ -104,6 +145,11 @@ pub struct LocalPersistConfig {
 
     #[serde(default)]
     pub include_query_text: bool,
+
+    /// Hash the printed text of operations, instead of their canonical text,
+    /// to keep the hashes of operations persisted by older versions.
+    #[serde(default, rename = "legacyTextHash")]
+    pub legacy_text_hash: bool,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -120,6 +166,13 @@ impl PersistConfig {
             PersistConfig::Local(local_config) => local_config.include_query_text,
         }
     }
+
+    pub fn legacy_text_hash(&self) -> bool {
+        match self {
+            PersistConfig::Remote(remote_config) => remote_config.legacy_text_hash,
+            PersistConfig::Local(local_config) => local_config.legacy_text_hash,
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for PersistConfig {
@@ -181,6 +234,24 @@ pub struct SchemaConfig {
     /// The name of the directive indicating fields that cannot be selected
     #[serde(default = "default_unselectable_directive_name")]
     pub unselectable_directive_name: DirectiveName,
+
+    /// Treat the schema files as Apollo Federation subgraphs: federation
+    /// directives and types are removed, and types defined by multiple
+    /// subgraph files are merged.
+    #[serde(default)]
+    pub federation: bool,
+
+    /// Schema coordinates of fields (`User.name`) that cannot be selected,
+    /// and of field arguments (`User.name(format:)`) that cannot be passed.
+    #[serde(default)]
+    pub banned_fields: Vec<SchemaCoordinate>,
+
+    /// The schema declares semantic nullability: fields marked with
+    /// `@semanticNonNull` are only null when they have an error. The levels
+    /// of the directives are validated, and typegen may use them. Schemas
+    /// without it treat the directive as any other server directive.
+    #[serde(default)]
+    pub semantic_non_null_support: bool,
 }
 
 fn default_node_interface_id_field() -> StringKey {
@@ -203,6 +274,9 @@ impl Default for SchemaConfig {
             node_interface_id_variable_name: default_node_interface_id_variable_name(),
             non_node_id_fields: None,
             unselectable_directive_name: default_unselectable_directive_name(),
+            federation: false,
+            banned_fields: vec![],
+            semantic_non_null_support: false,
         }
     }
 }
@@ -217,6 +291,9 @@ pub struct ProjectConfig {
     pub schema_extensions: Vec<PathBuf>,
     pub enabled: bool,
     pub schema_location: SchemaLocation,
+    /// If set, the schema at `schema_location` is a local cache of this
+    /// remote schema.
+    pub remote_schema: Option<RemoteSchemaConfig>,
     pub schema_config: SchemaConfig,
     pub typegen_config: TypegenConfig,
     pub persist: Option<PersistConfig>,
@@ -230,7 +307,16 @@ pub struct ProjectConfig {
     pub rollout: Rollout,
     pub js_module_format: JsModuleFormat,
     pub module_import_config: ModuleImportConfig,
+    pub graphql_tag_config: GraphQLTagConfig,
+    pub operation_text_config: OperationTextConfig,
+    pub schema_lint_config: SchemaLintConfig,
+    /// The `*.graphql` and `*.gql` files in the sources of the project are
+    /// documents of operations and fragments, rather than schema files.
+    pub standalone_documents: bool,
     pub diagnostic_report_config: DiagnosticReportConfig,
+    /// Settings for subsets of the files of the project. Later overrides take
+    /// precedence.
+    pub overrides: Vec<ConfigOverride>,
 }
 
 impl Default for ProjectConfig {
@@ -246,6 +332,7 @@ impl Default for ProjectConfig {
             schema_extensions: vec![],
             enabled: true,
             schema_location: SchemaLocation::File(PathBuf::default()),
+            remote_schema: None,
             schema_config: Default::default(),
             typegen_config: Default::default(),
             persist: None,
@@ -257,7 +344,12 @@ impl Default for ProjectConfig {
             rollout: Default::default(),
             js_module_format: Default::default(),
             module_import_config: Default::default(),
+            graphql_tag_config: Default::default(),
+            operation_text_config: Default::default(),
+            schema_lint_config: Default::default(),
+            standalone_documents: false,
             diagnostic_report_config: Default::default(),
+            overrides: vec![],
         }
     }
 }
@@ -274,6 +366,7 @@ impl Debug for ProjectConfig {
             schema_extensions,
             enabled,
             schema_location,
+            remote_schema,
             schema_config,
             typegen_config,
             persist,
@@ -286,7 +379,12 @@ impl Debug for ProjectConfig {
             rollout,
             js_module_format,
             module_import_config,
+            graphql_tag_config,
+            operation_text_config,
+            schema_lint_config,
+            standalone_documents,
             diagnostic_report_config,
+            overrides,
         } = self;
         f.debug_struct("ProjectConfig")
             .field("name", name)
@@ -298,6 +396,7 @@ impl Debug for ProjectConfig {
             .field("schema_extensions", schema_extensions)
             .field("enabled", enabled)
             .field("schema_location", schema_location)
+            .field("remote_schema", remote_schema)
             .field("schema_config", schema_config)
             .field("typegen_config", typegen_config)
             .field("persist", persist)
@@ -324,12 +423,52 @@ impl Debug for ProjectConfig {
             .field("rollout", rollout)
             .field("js_module_format", js_module_format)
             .field("module_import_config", module_import_config)
+            .field("graphql_tag_config", graphql_tag_config)
+            .field("operation_text_config", operation_text_config)
+            .field("schema_lint_config", schema_lint_config)
+            .field("standalone_documents", standalone_documents)
             .field("diagnostic_report_config", diagnostic_report_config)
+            .field("overrides", overrides)
             .finish()
     }
 }
 
 impl ProjectConfig {
+    /// The feature flags with the `overrides` applied to these documents, as
+    /// the `(name, location)` of each fragment and operation.
+    pub fn feature_flags_for_documents(
+        &self,
+        documents: &[(StringKey, SourceLocationKey)],
+    ) -> Arc<FeatureFlags> {
+        if self
+            .overrides
+            .iter()
+            .all(|config_override| config_override.feature_flags.is_empty())
+        {
+            return Arc::clone(&self.feature_flags);
+        }
+        Arc::new(apply_feature_flag_overrides(
+            &self.feature_flags,
+            &self.overrides,
+            documents,
+        ))
+    }
+
+    /// The JS type of a custom scalar in the documents at `location`, taking
+    /// the `overrides` into account.
+    pub fn custom_scalar_type(
+        &self,
+        scalar_name: ScalarName,
+        location: SourceLocationKey,
+    ) -> Option<&CustomScalarType> {
+        self.overrides
+            .iter()
+            .rev()
+            .filter(|config_override| config_override.matches(location))
+            .find_map(|config_override| config_override.custom_scalar_types.get(&scalar_name))
+            .or_else(|| self.typegen_config.custom_scalar_types.get(&scalar_name))
+    }
+
     /// This function will create a correct path for an artifact based on the project configuration
     pub fn create_path_for_artifact(
         &self,
@@ -0,0 +1,53 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Configuration of the rules `relay-compiler schema-lint` checks the schema
+/// of the project with. All rules are enabled by default.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct SchemaLintConfig {
+    /// Types are named in PascalCase, fields and arguments in camelCase and
+    /// enum values in SCREAMING_SNAKE_CASE.
+    #[serde(default = "default_enabled")]
+    pub naming_conventions: bool,
+
+    /// The fields of server types have a description.
+    #[serde(default = "default_enabled")]
+    pub missing_descriptions: bool,
+
+    /// `@deprecated` directives have a `reason`.
+    #[serde(default = "default_enabled")]
+    pub deprecation_reasons: bool,
+
+    /// Every type can be reached from the root operation types.
+    #[serde(default = "default_enabled")]
+    pub unreachable_types: bool,
+
+    /// Fields that take pagination arguments, like `first` and `after`,
+    /// return a connection rather than a list.
+    #[serde(default = "default_enabled")]
+    pub plural_fields_without_connections: bool,
+}
+
+impl Default for SchemaLintConfig {
+    fn default() -> Self {
+        Self {
+            naming_conventions: true,
+            missing_descriptions: true,
+            deprecation_reasons: true,
+            unreachable_types: true,
+            plural_fields_without_connections: true,
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
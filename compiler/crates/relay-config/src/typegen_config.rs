@@ -108,6 +108,11 @@ pub struct TypegenConfig {
     /// This option enables emitting es modules artifacts.
     #[serde(default)]
     pub eager_es_modules: bool,
+
+    /// Emit the fields marked with `@semanticNonNull` as non-null types.
+    /// Requires `semanticNonNullSupport` in the schema config of the project.
+    #[serde(default)]
+    pub experimental_emit_semantic_nullability_types: bool,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone, Copy)]
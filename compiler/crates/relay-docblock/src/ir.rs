@@ -602,6 +602,7 @@ trait ResolverTypeDefinitionIr: ResolverIr {
                         type_: arg.type_.clone(),
                         default_value: arg.default_value.clone(),
                         directives: vec![],
+                        description: None,
                     })
                     .collect::<Vec<_>>(),
             )
@@ -1080,6 +1081,7 @@ impl ResolverIr for StrongObjectIr {
                 arguments: None,
             }],
             fields: Some(List::generated(fields)),
+            description: None,
         });
 
         Ok(vec![type_])
@@ -1185,6 +1187,7 @@ impl WeakObjectIr {
                 vec![],
                 self.location(),
             )])),
+            description: None,
         })
     }
 
@@ -1226,6 +1229,7 @@ impl WeakObjectIr {
                     },
                 ])),
             }],
+            description: None,
         })
     }
 
@@ -0,0 +1,87 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+#![deny(warnings)]
+#![deny(rust_2018_idioms)]
+#![deny(clippy::all)]
+
+//! Golden file tests for transforms of the Relay compiler, in the style of
+//! the compiler's own tests, for transform plugins and forks.
+//!
+//! Each test is a directory of fixtures next to a module that exports the
+//! transform under test:
+//!
+//! ```ignore
+//! my-transform/
+//!   tests/
+//!     my_transform_test.rs # generated (see below)
+//!     my_transform/
+//!       mod.rs
+//!       fixtures/
+//!         selects-the-id.graphql
+//!         selects-the-id.expected
+//!         missing-argument.invalid.graphql
+//!         missing-argument.invalid.expected
+//! ```
+//!
+//! `tests/my_transform/mod.rs` exports a `transform_fixture`, which is
+//! usually the transform applied with [`apply_transform_for_test`]:
+//!
+//! ```ignore
+//! use relay_fixture_harness::apply_transform_for_test;
+//! use relay_fixture_harness::DiagnosticsResult;
+//! use relay_fixture_harness::Fixture;
+//! use relay_fixture_harness::Program;
+//!
+//! fn transform(program: &Program) -> DiagnosticsResult<Program> {
+//!     my_transform(program)
+//! }
+//!
+//! pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+//!     apply_transform_for_test(fixture, transform)
+//! }
+//! ```
+//!
+//! The test file with one test per fixture is generated, and generated again
+//! when fixtures are added, by the binary of this crate:
+//!
+//! ```ignore
+//! cargo run -p relay-fixture-harness -- my-transform/tests/my_transform
+//! ```
+//!
+//! The conventions of the fixtures are:
+//!
+//! - The documents of a fixture are built against the test schema, see
+//!   [`get_test_schema`]. Schema extensions for a single fixture follow the
+//!   documents after a line with [`EXTENSIONS_SEPARATOR`].
+//! - The `.expected` file has the input and the printed output, or the
+//!   printed diagnostics if the transform fails. Fixtures that are expected
+//!   to fail contain [`EXPECTED_TO_THROW`], usually in a comment on their
+//!   first line, and the test fails if they don't fail, and the other way
+//!   around.
+//! - Tests fail if the output differs from the `.expected` file, and print
+//!   the difference. Running them with `UPDATE_SNAPSHOTS=1` writes the
+//!   output to the `.expected` files instead.
+
+pub use common::Diagnostic;
+pub use common::DiagnosticsResult;
+pub use fixture_tests::generate_fixture_tests;
+pub use fixture_tests::test_fixture;
+pub use fixture_tests::Fixture;
+pub use fixture_tests::EXPECTED_EXTENSION;
+pub use fixture_tests::EXPECTED_TO_THROW;
+pub use graphql_ir::Program;
+pub use graphql_test_helpers::apply_transform_for_test;
+pub use graphql_test_helpers::diagnostics_to_sorted_string;
+pub use graphql_test_helpers::diagnostics_to_sorted_strings_with_sources_map;
+pub use graphql_test_helpers::EXTENSIONS_SEPARATOR;
+pub use relay_test_schema::get_test_schema;
+pub use relay_test_schema::get_test_schema_with_custom_id;
+pub use relay_test_schema::get_test_schema_with_custom_id_with_extensions;
+pub use relay_test_schema::get_test_schema_with_extensions;
+pub use relay_test_schema::get_test_schema_with_located_extensions;
+pub use schema::SDLSchema;
@@ -0,0 +1,33 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+#![deny(clippy::all)]
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use relay_fixture_harness::generate_fixture_tests;
+
+#[derive(Debug, Parser)]
+#[clap(
+    name = "relay-fixture-harness",
+    about = "Generates fixture tests that use relay-fixture-harness."
+)]
+struct Options {
+    /// List of directories, each should contain a `/fixtures` subdirectory
+    /// from which a test file will be generated
+    #[clap(name = "DIR")]
+    dirs: Vec<PathBuf>,
+}
+
+fn main() {
+    let opt = Options::parse();
+
+    for dir in opt.dirs {
+        generate_fixture_tests(&dir, "relay_fixture_harness");
+    }
+}
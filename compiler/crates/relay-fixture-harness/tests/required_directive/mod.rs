@@ -0,0 +1,22 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::FeatureFlag;
+use relay_fixture_harness::apply_transform_for_test;
+use relay_fixture_harness::DiagnosticsResult;
+use relay_fixture_harness::Fixture;
+use relay_fixture_harness::Program;
+use relay_transforms::fragment_alias_directive;
+use relay_transforms::required_directive;
+
+fn transform(program: &Program) -> DiagnosticsResult<Program> {
+    required_directive(&fragment_alias_directive(program, &FeatureFlag::Enabled)?)
+}
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    apply_transform_for_test(fixture, transform)
+}
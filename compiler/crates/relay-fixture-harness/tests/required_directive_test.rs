@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<82187af9d31edc97e3c83eaffcaafeef>>
+ */
+
+mod required_directive;
+
+use required_directive::transform_fixture;
+use relay_fixture_harness::test_fixture;
+
+#[test]
+fn action_argument_omitted_invalid() {
+    let input = include_str!("required_directive/fixtures/action-argument-omitted.invalid.graphql");
+    let expected = include_str!("required_directive/fixtures/action-argument-omitted.invalid.expected");
+    test_fixture(transform_fixture, "action-argument-omitted.invalid.graphql", "required_directive/fixtures/action-argument-omitted.invalid.expected", input, expected);
+}
+
+#[test]
+fn scalar_field_log() {
+    let input = include_str!("required_directive/fixtures/scalar-field-log.graphql");
+    let expected = include_str!("required_directive/fixtures/scalar-field-log.expected");
+    test_fixture(transform_fixture, "scalar-field-log.graphql", "required_directive/fixtures/scalar-field-log.expected", input, expected);
+}
@@ -0,0 +1,258 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Quick fix for the "undefined global variables" error: declares the variables
+//! used by a fragment in its `@argumentDefinitions`, with the types the compiler
+//! inferred from the argument positions they are used in.
+
+use std::collections::HashMap;
+
+use common::SourceLocationKey;
+use common::Span;
+use extract_graphql::ExtractOptions;
+use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_syntax::ExecutableDefinition;
+use intern::Lookup;
+use lsp_types::CodeAction;
+use lsp_types::CodeActionKind;
+use lsp_types::CodeActionOrCommand;
+use lsp_types::Diagnostic;
+use lsp_types::NumberOrString;
+use lsp_types::TextEdit;
+use lsp_types::Url;
+use lsp_types::WorkspaceEdit;
+use serde_json::Value;
+
+use super::extract_fragment::collect_selection_variables;
+use super::extract_fragment::ARGUMENT_DEFINITIONS;
+use super::find_graphql_source_for_range;
+
+/// The `err` metadata of the diagnostic reported by `validate_global_variables`.
+const GLOBAL_VARIABLES_ERROR: &str = "GlobalVariables";
+/// Prefix of the diagnostic data keys holding the inferred type of each variable.
+const VARIABLE_TYPE_PREFIX: &str = "variable.";
+
+pub(crate) fn create_declare_variables_code_action(
+    document_text: &str,
+    extract_options: &ExtractOptions,
+    url: &Url,
+    diagnostic: &Diagnostic,
+) -> Option<CodeActionOrCommand> {
+    if diagnostic.code != Some(NumberOrString::String(GLOBAL_VARIABLES_ERROR.to_string())) {
+        return None;
+    }
+    let variable_types = match &diagnostic.data {
+        Some(Value::Object(data)) => data
+            .iter()
+            .filter_map(|(key, value)| {
+                Some((key.strip_prefix(VARIABLE_TYPE_PREFIX)?, value.as_str()?))
+            })
+            .collect::<HashMap<_, _>>(),
+        _ => return None,
+    };
+
+    let range = diagnostic.range;
+    let (index, text_source, range_span) =
+        find_graphql_source_for_range(document_text, extract_options, url, range)?;
+    let text = &text_source.text;
+
+    let document = parse_executable_with_error_recovery(
+        text,
+        SourceLocationKey::embedded(url.as_ref(), index),
+    )
+    .item;
+    // Operations declare their variables with variable definitions instead.
+    let fragment = document
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            ExecutableDefinition::Fragment(fragment)
+                if fragment.location.span().contains(range_span) =>
+            {
+                Some(fragment)
+            }
+            _ => None,
+        })?;
+    let argument_definitions = fragment
+        .directives
+        .iter()
+        .find(|directive| directive.name.value.lookup() == ARGUMENT_DEFINITIONS);
+
+    let mut used_variables = vec![];
+    for selection in &fragment.selections.items {
+        collect_selection_variables(selection, &mut used_variables);
+    }
+    let new_argument_definitions = used_variables
+        .into_iter()
+        .filter(|variable| {
+            !argument_definitions
+                .and_then(|directive| directive.arguments.as_ref())
+                .map_or(false, |arguments| {
+                    arguments
+                        .items
+                        .iter()
+                        .any(|argument| argument.name.value == *variable)
+                })
+        })
+        .filter_map(|variable| {
+            let type_ = variable_types.get(variable.lookup())?;
+            Some(format!("{}: {{type: \"{}\"}}", variable, type_))
+        })
+        .collect::<Vec<_>>();
+    if new_argument_definitions.is_empty() {
+        return None;
+    }
+    let new_argument_definitions = new_argument_definitions.join(", ");
+
+    let (offset, new_text) = match argument_definitions {
+        Some(directive) => match &directive.arguments {
+            Some(arguments) if arguments.items.is_empty() => {
+                (arguments.end.span.start, new_argument_definitions)
+            }
+            Some(arguments) => (
+                arguments.end.span.start,
+                format!(", {}", new_argument_definitions),
+            ),
+            None => (
+                directive.span.end,
+                format!("({})", new_argument_definitions),
+            ),
+        },
+        None => (
+            fragment.type_condition.span.end,
+            format!(" @{}({})", ARGUMENT_DEFINITIONS, new_argument_definitions),
+        ),
+    };
+    let insert_range = text_source.to_span_range(Span::new(offset, offset));
+
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: format!(
+            "Declare in @{} of '{}'",
+            ARGUMENT_DEFINITIONS, fragment.name.value
+        ),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(
+                url.clone(),
+                vec![TextEdit {
+                    range: insert_range,
+                    new_text,
+                }],
+            )])),
+            document_changes: None,
+            ..Default::default()
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use extract_graphql::ExtractOptions;
+    use lsp_types::CodeActionOrCommand;
+    use lsp_types::Diagnostic;
+    use lsp_types::NumberOrString;
+    use lsp_types::Position;
+    use lsp_types::Range;
+    use lsp_types::Url;
+    use serde_json::json;
+
+    use super::create_declare_variables_code_action;
+
+    fn get_edit_text(text: &str, diagnostic: Diagnostic) -> Option<(Range, String)> {
+        let url = Url::parse("file:///src/Foo.react.js").unwrap();
+        match create_declare_variables_code_action(
+            text,
+            &ExtractOptions::default(),
+            &url,
+            &diagnostic,
+        )? {
+            CodeActionOrCommand::CodeAction(action) => {
+                let edit = action.edit?.changes?.remove(&url)?.pop()?;
+                Some((edit.range, edit.new_text))
+            }
+            CodeActionOrCommand::Command(_) => None,
+        }
+    }
+
+    fn global_variables_diagnostic(line: u32, character: u32) -> Diagnostic {
+        Diagnostic {
+            range: Range {
+                start: Position { line, character },
+                end: Position {
+                    line,
+                    character: character + 5,
+                },
+            },
+            code: Some(NumberOrString::String("GlobalVariables".to_string())),
+            data: Some(json!({
+                "err": "GlobalVariables",
+                "variable.size": "[Int]",
+                "variable.cond": "Boolean!",
+            })),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn adds_argument_definitions_directive() {
+        let text = r#"const fragment = graphql`
+  fragment Foo_user on User {
+    profilePicture(size: $size) {
+      uri
+    }
+  }
+`;
+"#;
+        assert_eq!(
+            get_edit_text(text, global_variables_diagnostic(2, 25)),
+            Some((
+                Range {
+                    start: Position {
+                        line: 1,
+                        character: 27,
+                    },
+                    end: Position {
+                        line: 1,
+                        character: 27,
+                    },
+                },
+                r#" @argumentDefinitions(size: {type: "[Int]"})"#.to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn extends_argument_definitions() {
+        let text = r#"const fragment = graphql`
+  fragment Foo_user on User @argumentDefinitions(size: {type: "[Int]"}) {
+    profilePicture(size: $size) @include(if: $cond) {
+      uri
+    }
+  }
+`;
+"#;
+        assert_eq!(
+            get_edit_text(text, global_variables_diagnostic(2, 45)),
+            Some((
+                Range {
+                    start: Position {
+                        line: 1,
+                        character: 70,
+                    },
+                    end: Position {
+                        line: 1,
+                        character: 70,
+                    },
+                },
+                r#", cond: {type: "Boolean!"}"#.to_string()
+            ))
+        );
+    }
+}
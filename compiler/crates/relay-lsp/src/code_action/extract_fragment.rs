@@ -0,0 +1,444 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The "Extract into fragment" refactor: moves the selected selections into a
+//! new fragment on their parent type, and replaces them with a spread.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use common::SourceLocationKey;
+use common::Span;
+use common::TextSource;
+use extract_graphql::ExtractOptions;
+use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_syntax::Argument;
+use graphql_syntax::Directive;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::OperationKind;
+use graphql_syntax::Selection;
+use graphql_syntax::Value;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use lsp_types::CodeAction;
+use lsp_types::CodeActionKind;
+use lsp_types::CodeActionOrCommand;
+use lsp_types::Range;
+use lsp_types::TextEdit;
+use lsp_types::Url;
+use lsp_types::WorkspaceEdit;
+use relay_transforms::extract_module_name;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+
+use super::find_graphql_source_for_range;
+
+pub(crate) const ARGUMENT_DEFINITIONS: &str = "argumentDefinitions";
+
+/// Selections of a single selection set, fully covered by the range of the
+/// code action, with the type they are selected on.
+struct SelectedSelections<'a> {
+    parent_type: Type,
+    selections: Vec<&'a Selection>,
+}
+
+pub(crate) fn create_extract_fragment_code_action(
+    document_text: &str,
+    extract_options: &ExtractOptions,
+    schema: &SDLSchema,
+    url: &Url,
+    range: Range,
+    used_fragment_names: &HashSet<String>,
+) -> Option<CodeActionOrCommand> {
+    if range.start == range.end {
+        return None;
+    }
+    let (index, text_source, range_span) =
+        find_graphql_source_for_range(document_text, extract_options, url, range)?;
+    let text = &text_source.text;
+
+    let document = parse_executable_with_error_recovery(
+        text,
+        SourceLocationKey::embedded(url.as_ref(), index),
+    )
+    .item;
+    let definition = document
+        .definitions
+        .iter()
+        .find(|definition| definition.location().span().contains(range_span))?;
+
+    let (root_type, root_selections, argument_definitions, operation_variables) = match definition {
+        ExecutableDefinition::Operation(operation) => {
+            let root_type = match operation.operation_kind() {
+                OperationKind::Query => schema.query_type(),
+                OperationKind::Mutation => schema.mutation_type(),
+                OperationKind::Subscription => schema.subscription_type(),
+            }?;
+            let variables = operation
+                .variable_definitions
+                .iter()
+                .flat_map(|list| list.items.iter())
+                .map(|variable| (variable.name.name, variable.type_.to_string()))
+                .collect::<HashMap<_, _>>();
+            (root_type, &operation.selections, None, variables)
+        }
+        ExecutableDefinition::Fragment(fragment) => (
+            schema.get_type(fragment.type_condition.type_.value)?,
+            &fragment.selections,
+            fragment
+                .directives
+                .iter()
+                .find(|directive| directive.name.value.lookup() == ARGUMENT_DEFINITIONS),
+            HashMap::new(),
+        ),
+    };
+
+    let selected = find_selected_selections(schema, root_type, &root_selections.items, range_span)?;
+    if !selected.parent_type.is_composite_type() {
+        return None;
+    }
+
+    let mut variables = vec![];
+    for selection in &selected.selections {
+        collect_selection_variables(selection, &mut variables);
+    }
+
+    // Variables defined by the enclosing fragment (or operation) become arguments of the
+    // new fragment. The others are global variables, and stay as they are.
+    let mut new_argument_definitions = vec![];
+    let mut spread_arguments = vec![];
+    for variable in variables {
+        let local_definition = argument_definitions
+            .and_then(|directive| directive.arguments.as_ref())
+            .and_then(|arguments| {
+                arguments
+                    .items
+                    .iter()
+                    .find(|argument| argument.name.value == variable)
+            });
+        if let Some(argument) = local_definition {
+            new_argument_definitions.push(span_text(text, argument.span).to_string());
+        } else if let Some(type_) = operation_variables.get(&variable) {
+            new_argument_definitions.push(format!("{}: {{type: \"{}\"}}", variable, type_));
+        } else {
+            continue;
+        }
+        spread_arguments.push(format!("{}: ${}", variable, variable));
+    }
+
+    let type_name = schema.get_type_name(selected.parent_type);
+    let fragment_name = create_fragment_name(url, type_name, used_fragment_names)?;
+
+    let first = selected.selections.first()?.span();
+    let last = selected.selections.last()?.span();
+    let selections_span = Span::new(first.start, last.end);
+
+    let mut fragment_text = format!("\ngraphql`\n  fragment {} on {}", fragment_name, type_name);
+    if !new_argument_definitions.is_empty() {
+        fragment_text.push_str(&format!(
+            " @argumentDefinitions({})",
+            new_argument_definitions.join(", ")
+        ));
+    }
+    fragment_text.push_str(" {\n");
+    for line in reindent_selections(text, selections_span) {
+        fragment_text.push_str(&format!("    {}\n", line));
+    }
+    fragment_text.push_str("  }\n`;\n");
+
+    let mut spread_text = format!("...{}", fragment_name);
+    if !spread_arguments.is_empty() {
+        spread_text.push_str(&format!(" @arguments({})", spread_arguments.join(", ")));
+    }
+
+    let end_of_document = TextSource::from_whole_document(document_text)
+        .to_range()
+        .end;
+    let edits = vec![
+        TextEdit {
+            range: text_source.to_span_range(selections_span),
+            new_text: spread_text,
+        },
+        TextEdit {
+            range: Range {
+                start: end_of_document,
+                end: end_of_document,
+            },
+            new_text: fragment_text,
+        },
+    ];
+
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: format!("Extract into fragment '{}'", fragment_name),
+        kind: Some(CodeActionKind::REFACTOR_EXTRACT),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(url.clone(), edits)])),
+            document_changes: None,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }))
+}
+
+/// Find the innermost selection set with selections in the range, and the
+/// type of that selection set. The range must cover whole selections.
+fn find_selected_selections<'a>(
+    schema: &SDLSchema,
+    parent_type: Type,
+    selections: &'a [Selection],
+    range: Span,
+) -> Option<SelectedSelections<'a>> {
+    for selection in selections {
+        match selection {
+            Selection::LinkedField(field) if field.selections.span.contains(range) => {
+                let field_id = schema.named_field(parent_type, field.name.value)?;
+                let field_type = schema.field(field_id).type_.inner();
+                return find_selected_selections(
+                    schema,
+                    field_type,
+                    &field.selections.items,
+                    range,
+                );
+            }
+            Selection::InlineFragment(fragment) if fragment.selections.span.contains(range) => {
+                let type_ = match &fragment.type_condition {
+                    Some(type_condition) => schema.get_type(type_condition.type_.value)?,
+                    None => parent_type,
+                };
+                return find_selected_selections(schema, type_, &fragment.selections.items, range);
+            }
+            _ => {}
+        }
+    }
+
+    let mut selected = vec![];
+    for selection in selections {
+        let span = selection.span();
+        if range.contains(span) {
+            selected.push(selection);
+        } else if span.start < range.end && range.start < span.end {
+            // Partially selected
+            return None;
+        }
+    }
+    if selected.is_empty() {
+        None
+    } else {
+        Some(SelectedSelections {
+            parent_type,
+            selections: selected,
+        })
+    }
+}
+
+pub(crate) fn collect_selection_variables(selection: &Selection, variables: &mut Vec<StringKey>) {
+    let arguments = match selection {
+        Selection::FragmentSpread(spread) => spread.arguments.as_ref(),
+        Selection::InlineFragment(_) => None,
+        Selection::LinkedField(field) => field.arguments.as_ref(),
+        Selection::ScalarField(field) => field.arguments.as_ref(),
+    };
+    collect_arguments_variables(
+        arguments.iter().flat_map(|list| list.items.iter()),
+        variables,
+    );
+    collect_directives_variables(selection.directives(), variables);
+    let selections = match selection {
+        Selection::InlineFragment(fragment) => &fragment.selections.items[..],
+        Selection::LinkedField(field) => &field.selections.items[..],
+        Selection::FragmentSpread(_) | Selection::ScalarField(_) => &[],
+    };
+    for selection in selections {
+        collect_selection_variables(selection, variables);
+    }
+}
+
+fn collect_directives_variables(directives: &[Directive], variables: &mut Vec<StringKey>) {
+    for directive in directives {
+        collect_arguments_variables(
+            directive
+                .arguments
+                .iter()
+                .flat_map(|list| list.items.iter()),
+            variables,
+        );
+    }
+}
+
+fn collect_arguments_variables<'a>(
+    arguments: impl Iterator<Item = &'a Argument>,
+    variables: &mut Vec<StringKey>,
+) {
+    for argument in arguments {
+        collect_value_variables(&argument.value, variables);
+    }
+}
+
+fn collect_value_variables(value: &Value, variables: &mut Vec<StringKey>) {
+    match value {
+        Value::Constant(_) => {}
+        Value::Variable(variable) => {
+            if !variables.contains(&variable.name) {
+                variables.push(variable.name);
+            }
+        }
+        Value::List(list) => {
+            for item in &list.items {
+                collect_value_variables(item, variables);
+            }
+        }
+        Value::Object(object) => collect_arguments_variables(object.items.iter(), variables),
+    }
+}
+
+/// Suggests `<ModuleName>_<typeName>`, with an index if the name is already used.
+fn create_fragment_name(
+    url: &Url,
+    type_name: StringKey,
+    used_fragment_names: &HashSet<String>,
+) -> Option<String> {
+    let module_name = extract_module_name(url.path())?;
+    let type_name = type_name.lookup();
+    let mut chars = type_name.chars();
+    let prop_name = match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect::<String>(),
+        None => return None,
+    };
+    let base_name = format!("{}_{}", module_name, prop_name);
+    if !used_fragment_names.contains(&base_name) {
+        return Some(base_name);
+    }
+    (1..)
+        .map(|index| format!("{}{}", base_name, index))
+        .find(|name| !used_fragment_names.contains(name))
+}
+
+/// The lines of the selections, without the indentation of the selection set.
+fn reindent_selections(text: &str, span: Span) -> Vec<&str> {
+    let line_start = text[..span.start as usize]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+    let indentation = span.start as usize - line_start;
+    span_text(text, span)
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 {
+                line
+            } else {
+                let whitespace = line.len() - line.trim_start().len();
+                &line[whitespace.min(indentation)..]
+            }
+        })
+        .collect()
+}
+
+fn span_text(text: &str, span: Span) -> &str {
+    &text[span.start as usize..span.end as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use extract_graphql::ExtractOptions;
+    use lsp_types::CodeActionOrCommand;
+    use lsp_types::Position;
+    use lsp_types::Range;
+    use lsp_types::Url;
+    use relay_test_schema::get_test_schema;
+
+    use super::create_extract_fragment_code_action;
+
+    #[test]
+    fn extracts_selections_with_their_arguments() {
+        let text = r#"const fragment = graphql`
+  fragment Foo_user on User @argumentDefinitions(size: {type: "Int"}) {
+    id
+    name
+    profilePicture(size: $size) {
+      uri
+    }
+  }
+`;
+"#;
+        let url = Url::parse("file:///src/Foo.react.js").unwrap();
+        let range = Range {
+            start: Position {
+                line: 3,
+                character: 4,
+            },
+            end: Position {
+                line: 6,
+                character: 5,
+            },
+        };
+        let used_fragment_names = HashSet::from(["Foo_user".to_string()]);
+        let action = create_extract_fragment_code_action(
+            text,
+            &ExtractOptions::default(),
+            &get_test_schema(),
+            &url,
+            range,
+            &used_fragment_names,
+        );
+
+        let action = match action {
+            Some(CodeActionOrCommand::CodeAction(action)) => action,
+            _ => panic!("Expected a code action"),
+        };
+        assert_eq!(action.title, "Extract into fragment 'Foo_user1'");
+        let edits = &action.edit.unwrap().changes.unwrap()[&url];
+        assert_eq!(edits[0].range, range);
+        assert_eq!(edits[0].new_text, "...Foo_user1 @arguments(size: $size)");
+        assert_eq!(
+            edits[1].new_text,
+            r#"
+graphql`
+  fragment Foo_user1 on User @argumentDefinitions(size: {type: "Int"}) {
+    name
+    profilePicture(size: $size) {
+      uri
+    }
+  }
+`;
+"#
+        );
+    }
+
+    #[test]
+    fn ignores_partially_selected_fields() {
+        let text = r#"const fragment = graphql`
+  fragment Foo_user on User {
+    id
+    name
+  }
+`;
+"#;
+        let url = Url::parse("file:///src/Foo.react.js").unwrap();
+        let range = Range {
+            start: Position {
+                line: 2,
+                character: 5,
+            },
+            end: Position {
+                line: 3,
+                character: 8,
+            },
+        };
+        assert!(create_extract_fragment_code_action(
+            text,
+            &ExtractOptions::default(),
+            &get_test_schema(),
+            &url,
+            range,
+            &HashSet::new(),
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,238 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The "Make fragment refetchable" refactor: adds `@refetchable` to the
+//! fragment under the cursor, with a query name derived from the fragment.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use common::SourceLocationKey;
+use common::Span;
+use extract_graphql::ExtractOptions;
+use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_syntax::ExecutableDefinition;
+use intern::Lookup;
+use lsp_types::CodeAction;
+use lsp_types::CodeActionDisabled;
+use lsp_types::CodeActionKind;
+use lsp_types::CodeActionOrCommand;
+use lsp_types::Range;
+use lsp_types::TextEdit;
+use lsp_types::Url;
+use lsp_types::WorkspaceEdit;
+use relay_transforms::is_refetchable_type;
+use relay_transforms::refetchable_type_descriptions;
+use relay_transforms::REFETCHABLE_NAME;
+use schema::SDLSchema;
+use schema::Schema;
+
+use super::find_graphql_source_for_range;
+
+/// Offered when the range is in the header of a fragment, before its selections.
+/// The code action is disabled, with the reason, when the type of the fragment
+/// can't be refetched.
+pub(crate) fn create_make_refetchable_code_action(
+    document_text: &str,
+    extract_options: &ExtractOptions,
+    schema: &SDLSchema,
+    url: &Url,
+    range: Range,
+    used_operation_names: &HashSet<String>,
+) -> Option<CodeActionOrCommand> {
+    let (index, text_source, range_span) =
+        find_graphql_source_for_range(document_text, extract_options, url, range)?;
+
+    let document = parse_executable_with_error_recovery(
+        &text_source.text,
+        SourceLocationKey::embedded(url.as_ref(), index),
+    )
+    .item;
+    let fragment = document
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            ExecutableDefinition::Fragment(fragment) => {
+                let header = Span::new(
+                    fragment.location.span().start,
+                    fragment.selections.span.start,
+                );
+                if header.contains(range_span) {
+                    Some(fragment)
+                } else {
+                    None
+                }
+            }
+            ExecutableDefinition::Operation(_) => None,
+        })?;
+    if fragment
+        .directives
+        .iter()
+        .any(|directive| directive.name.value == REFETCHABLE_NAME.0)
+    {
+        return None;
+    }
+
+    let query_name = create_query_name(fragment.name.value.lookup(), used_operation_names);
+    let title = format!("Make fragment refetchable with query '{}'", query_name);
+
+    let is_eligible = schema
+        .get_type(fragment.type_condition.type_.value)
+        .map_or(false, |type_| is_refetchable_type(schema, type_));
+    if !is_eligible {
+        return Some(CodeActionOrCommand::CodeAction(CodeAction {
+            title,
+            kind: Some(CodeActionKind::REFACTOR),
+            disabled: Some(CodeActionDisabled {
+                reason: format!(
+                    "Only fragments on these types can be refetchable:\n{}",
+                    refetchable_type_descriptions()
+                ),
+            }),
+            ..Default::default()
+        }));
+    }
+
+    let insert_at = fragment
+        .directives
+        .last()
+        .map_or(fragment.type_condition.span.end, |directive| {
+            directive.span.end
+        });
+    let edit = TextEdit {
+        range: text_source.to_span_range(Span::new(insert_at, insert_at)),
+        new_text: format!(" @{}(queryName: \"{}\")", REFETCHABLE_NAME.0, query_name),
+    };
+
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title,
+        kind: Some(CodeActionKind::REFACTOR),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(url.clone(), vec![edit])])),
+            document_changes: None,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }))
+}
+
+/// Suggests `<FragmentName>RefetchQuery`, with an index if the name is already used.
+fn create_query_name(fragment_name: &str, used_operation_names: &HashSet<String>) -> String {
+    let base_name = format!("{}RefetchQuery", fragment_name);
+    if !used_operation_names.contains(&base_name) {
+        return base_name;
+    }
+    (1..)
+        .map(|index| format!("{}Refetch{}Query", fragment_name, index))
+        .find(|name| !used_operation_names.contains(name))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use extract_graphql::ExtractOptions;
+    use lsp_types::CodeActionOrCommand;
+    use lsp_types::Position;
+    use lsp_types::Range;
+    use lsp_types::Url;
+    use relay_test_schema::get_test_schema;
+
+    use super::create_make_refetchable_code_action;
+
+    fn cursor(line: u32, character: u32) -> Range {
+        Range {
+            start: Position { line, character },
+            end: Position { line, character },
+        }
+    }
+
+    #[test]
+    fn adds_refetchable_directive() {
+        let text = r#"const fragment = graphql`
+  fragment Foo_user on User @relay(mask: false) {
+    id
+  }
+`;
+"#;
+        let url = Url::parse("file:///src/Foo.react.js").unwrap();
+        let used_operation_names = HashSet::from(["Foo_userRefetchQuery".to_string()]);
+        let action = create_make_refetchable_code_action(
+            text,
+            &ExtractOptions::default(),
+            &get_test_schema(),
+            &url,
+            cursor(1, 14),
+            &used_operation_names,
+        );
+
+        let action = match action {
+            Some(CodeActionOrCommand::CodeAction(action)) => action,
+            _ => panic!("Expected a code action"),
+        };
+        assert_eq!(
+            action.title,
+            "Make fragment refetchable with query 'Foo_userRefetch1Query'"
+        );
+        assert!(action.disabled.is_none());
+        let edits = &action.edit.unwrap().changes.unwrap()[&url];
+        assert_eq!(edits[0].range, cursor(1, 47));
+        assert_eq!(
+            edits[0].new_text,
+            r#" @refetchable(queryName: "Foo_userRefetch1Query")"#
+        );
+    }
+
+    #[test]
+    fn explains_why_the_fragment_cant_be_refetchable() {
+        let text = r#"const fragment = graphql`
+  fragment Foo_image on Image {
+    uri
+  }
+`;
+"#;
+        let url = Url::parse("file:///src/Foo.react.js").unwrap();
+        let action = create_make_refetchable_code_action(
+            text,
+            &ExtractOptions::default(),
+            &get_test_schema(),
+            &url,
+            cursor(1, 14),
+            &HashSet::new(),
+        );
+
+        let action = match action {
+            Some(CodeActionOrCommand::CodeAction(action)) => action,
+            _ => panic!("Expected a code action"),
+        };
+        assert!(action.edit.is_none());
+        let reason = action.disabled.unwrap().reason;
+        assert!(reason.starts_with("Only fragments on these types can be refetchable:"));
+        assert!(reason.contains(" - the Viewer type"));
+    }
+
+    #[test]
+    fn ignores_selections() {
+        let text = r#"const fragment = graphql`
+  fragment Foo_user on User {
+    id
+  }
+`;
+"#;
+        let url = Url::parse("file:///src/Foo.react.js").unwrap();
+        assert!(create_make_refetchable_code_action(
+            text,
+            &ExtractOptions::default(),
+            &get_test_schema(),
+            &url,
+            cursor(2, 5),
+            &HashSet::new(),
+        )
+        .is_none());
+    }
+}
@@ -6,29 +6,42 @@
  */
 
 mod create_name_suggestion;
+mod declare_variables;
+mod extract_fragment;
+mod make_refetchable;
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
 
+use common::SourceLocationKey;
 use common::Span;
+use common::TextSource;
 use create_name_suggestion::create_default_name;
 use create_name_suggestion::create_default_name_with_index;
 use create_name_suggestion::create_impactful_name;
 use create_name_suggestion::create_name_wrapper;
 use create_name_suggestion::DefinitionNameSuffix;
+use declare_variables::create_declare_variables_code_action;
+use extract_fragment::create_extract_fragment_code_action;
+use extract_graphql::ExtractOptions;
+use extract_graphql::JavaScriptSourceFeature;
 use graphql_syntax::ExecutableDefinition;
 use intern::Lookup;
+use make_refetchable::create_make_refetchable_code_action;
 use lsp_types::request::CodeActionRequest;
 use lsp_types::request::Request;
 use lsp_types::CodeAction;
 use lsp_types::CodeActionOrCommand;
 use lsp_types::Diagnostic;
+use lsp_types::NumberOrString;
 use lsp_types::Position;
 use lsp_types::Range;
 use lsp_types::TextDocumentPositionParams;
 use lsp_types::TextEdit;
 use lsp_types::Url;
 use lsp_types::WorkspaceEdit;
+use relay_compiler::fixes::get_removal_fix;
 use resolution_path::IdentParent;
 use resolution_path::IdentPath;
 use resolution_path::OperationDefinitionPath;
@@ -40,6 +53,7 @@ use crate::lsp_runtime_error::LSPRuntimeError;
 use crate::lsp_runtime_error::LSPRuntimeResult;
 use crate::server::GlobalState;
 use crate::utils::is_file_uri_in_dir;
+use crate::utils::position_to_offset;
 
 pub(crate) fn on_code_action(
     state: &impl GlobalState,
@@ -58,9 +72,21 @@ pub(crate) fn on_code_action(
     }
 
     if let Some(diagnostic) = state.get_diagnostic_for_range(&uri, params.range) {
-        let code_actions = get_code_actions_from_diagnostics(&uri, diagnostic);
-        if code_actions.is_some() {
-            return Ok(code_actions);
+        if let Some(code_action) = state.get_document_text(&uri).ok().and_then(|text| {
+            create_declare_variables_code_action(
+                &text,
+                &state.get_extract_options(),
+                &uri,
+                &diagnostic,
+            )
+        }) {
+            return Ok(Some(vec![code_action]));
+        }
+        let mut code_actions =
+            get_code_actions_from_diagnostics(&uri, diagnostic.clone()).unwrap_or_default();
+        code_actions.extend(get_removal_code_action(state, &uri, &diagnostic));
+        if !code_actions.is_empty() {
+            return Ok(Some(code_actions));
         }
     }
 
@@ -76,9 +102,142 @@ pub(crate) fn on_code_action(
     let path = document.resolve((), position_span);
 
     let used_definition_names = get_definition_names(&definitions);
-    let result = get_code_actions(path, used_definition_names, uri, params.range)
-        .ok_or(LSPRuntimeError::ExpectedError)?;
-    Ok(Some(result))
+    let extract_fragment_code_action = get_extract_fragment_code_action(
+        state,
+        &uri,
+        params.range,
+        &used_definition_names.fragment_names,
+    );
+    let make_refetchable_code_action = get_make_refetchable_code_action(
+        state,
+        &uri,
+        params.range,
+        &used_definition_names.operation_names,
+    );
+
+    let mut result =
+        get_code_actions(path, used_definition_names, uri, params.range).unwrap_or_default();
+    result.extend(extract_fragment_code_action);
+    result.extend(make_refetchable_code_action);
+    if result.is_empty() {
+        Err(LSPRuntimeError::ExpectedError)
+    } else {
+        Ok(Some(result))
+    }
+}
+
+fn get_extract_fragment_code_action(
+    state: &impl GlobalState,
+    uri: &Url,
+    range: Range,
+    used_fragment_names: &HashSet<String>,
+) -> Option<CodeActionOrCommand> {
+    let document_text = state.get_document_text(uri).ok()?;
+    let project_name = state.extract_project_name_from_url(uri).ok()?;
+    let schema = state.get_schema(&project_name).ok()?;
+    create_extract_fragment_code_action(
+        &document_text,
+        &state.get_extract_options(),
+        &schema,
+        uri,
+        range,
+        used_fragment_names,
+    )
+}
+
+fn get_make_refetchable_code_action(
+    state: &impl GlobalState,
+    uri: &Url,
+    range: Range,
+    used_operation_names: &HashSet<String>,
+) -> Option<CodeActionOrCommand> {
+    let document_text = state.get_document_text(uri).ok()?;
+    let project_name = state.extract_project_name_from_url(uri).ok()?;
+    let schema = state.get_schema(&project_name).ok()?;
+    create_make_refetchable_code_action(
+        &document_text,
+        &state.get_extract_options(),
+        &schema,
+        uri,
+        range,
+        used_operation_names,
+    )
+}
+
+/// Removes the code reported by "unused" diagnostics, see `relay_compiler::fixes`.
+fn get_removal_code_action(
+    state: &impl GlobalState,
+    uri: &Url,
+    diagnostic: &Diagnostic,
+) -> Option<CodeActionOrCommand> {
+    let err = match &diagnostic.code {
+        Some(NumberOrString::String(err)) => err,
+        _ => return None,
+    };
+    let document_text = state.get_document_text(uri).ok()?;
+    let (index, text_source, span) = find_graphql_source_for_range(
+        &document_text,
+        &state.get_extract_options(),
+        uri,
+        diagnostic.range,
+    )?;
+    let fix = get_removal_fix(
+        &text_source.text,
+        SourceLocationKey::embedded(uri.as_ref(), index),
+        err,
+        span,
+    )?;
+
+    Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: fix.title.to_string(),
+        kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(
+                uri.clone(),
+                vec![TextEdit {
+                    range: text_source.to_span_range(fix.span),
+                    new_text: String::new(),
+                }],
+            )])),
+            document_changes: None,
+            ..Default::default()
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }))
+}
+
+/// Finds the GraphQL literal of the document containing the range. Returns its
+/// index in the document, its source, and the span of the range in the source.
+fn find_graphql_source_for_range(
+    document_text: &str,
+    extract_options: &ExtractOptions,
+    uri: &Url,
+    range: Range,
+) -> Option<(usize, TextSource, Span)> {
+    let (index, text_source) = extract_graphql::extract_from_path_with_options(
+        Path::new(uri.path()),
+        document_text,
+        extract_options,
+    )
+    .into_iter()
+    .enumerate()
+    .find_map(|(index, feature)| match feature {
+        JavaScriptSourceFeature::GraphQL(source) => {
+            let source_range = source.text_source().to_range();
+            if source_range.start <= range.start && range.end <= source_range.end {
+                Some((index, source.text_source().clone()))
+            } else {
+                None
+            }
+        }
+        JavaScriptSourceFeature::Docblock(_) => None,
+    })?;
+    let text = &text_source.text;
+    let start = position_to_offset(&range.start, 1, text_source.line_index, text)?;
+    let end = position_to_offset(&range.end, 1, text_source.line_index, text)?;
+    Some((index, text_source, Span::new(start, end)))
 }
 
 fn get_code_actions_from_diagnostics(
@@ -110,7 +269,7 @@ fn get_code_actions_from_diagnostics(
 
 struct FragmentAndOperationNames {
     operation_names: HashSet<String>,
-    _fragment_names: HashSet<String>,
+    fragment_names: HashSet<String>,
 }
 
 fn get_definition_names(definitions: &[ExecutableDefinition]) -> FragmentAndOperationNames {
@@ -131,7 +290,7 @@ fn get_definition_names(definitions: &[ExecutableDefinition]) -> FragmentAndOper
 
     FragmentAndOperationNames {
         operation_names,
-        _fragment_names: fragment_names,
+        fragment_names,
     }
 }
 
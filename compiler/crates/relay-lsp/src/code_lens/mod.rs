@@ -0,0 +1,228 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing code lenses: the number of usages of each fragment,
+//! a link to the artifact generated for each operation and fragment, and a
+//! command executing queries and mutations against a development endpoint.
+
+use std::path::Path;
+
+use common::SourceLocationKey;
+use common::Span;
+use common::TextSource;
+use extract_graphql::ExtractOptions;
+use extract_graphql::JavaScriptSourceFeature;
+use graphql_ir::FragmentDefinitionName;
+use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::OperationDefinition;
+use graphql_syntax::OperationKind;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use lsp_types::request::CodeLensRequest;
+use lsp_types::request::Request;
+use lsp_types::CodeLens;
+use lsp_types::Command;
+use lsp_types::Location as LSPLocation;
+use lsp_types::Url;
+use serde_json::json;
+
+use crate::location::transform_relay_location_to_lsp_location;
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+
+/// Client command showing the locations passed as its arguments, along with
+/// the document and position they are references of.
+pub const SHOW_REFERENCES_COMMAND: &str = "relay.showReferences";
+
+/// Client command opening the document passed as its argument.
+pub const OPEN_ARTIFACT_COMMAND: &str = "relay.openArtifact";
+
+/// Client command executing the operation passed as its arguments, after
+/// prompting for the values of its variables.
+pub const EXECUTE_OPERATION_COMMAND: &str = "relay.executeOperation";
+
+pub fn on_code_lens(
+    state: &impl GlobalState,
+    params: <CodeLensRequest as Request>::Params,
+) -> LSPRuntimeResult<<CodeLensRequest as Request>::Result> {
+    let uri = params.text_document.uri;
+    let document_text = state.get_document_text(&uri)?;
+    let project_name = state.extract_project_name_from_url(&uri)?;
+    let root_dir = state.root_dir();
+    let extract_options = state.get_extract_options();
+
+    Ok(Some(get_code_lenses(
+        &document_text,
+        &uri,
+        &extract_options,
+        |fragment_name| {
+            state
+                .get_fragment_spread_locations(&project_name, fragment_name)
+                .ok()?
+                .into_iter()
+                .map(|location| {
+                    transform_relay_location_to_lsp_location(&root_dir, location, &extract_options)
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+        },
+        |definition_name| {
+            // Only link artifacts the compiler has written
+            let artifact_path = state
+                .get_artifact_path(&project_name, &uri, definition_name)
+                .ok()?;
+            if artifact_path.exists() {
+                Url::from_file_path(artifact_path).ok()
+            } else {
+                None
+            }
+        },
+    )))
+}
+
+fn get_code_lenses(
+    document_text: &str,
+    uri: &Url,
+    extract_options: &ExtractOptions,
+    get_fragment_usages: impl Fn(FragmentDefinitionName) -> Option<Vec<LSPLocation>>,
+    get_artifact_url: impl Fn(StringKey) -> Option<Url>,
+) -> Vec<CodeLens> {
+    let mut lenses = vec![];
+    for (index, feature) in extract_graphql::extract_from_path_with_options(
+        Path::new(uri.path()),
+        document_text,
+        extract_options,
+    )
+    .into_iter()
+    .enumerate()
+    {
+        let source = match feature {
+            JavaScriptSourceFeature::GraphQL(source) => source,
+            JavaScriptSourceFeature::Docblock(_) => continue,
+        };
+        let text_source = source.text_source();
+        let document = parse_executable_with_error_recovery(
+            &text_source.text,
+            SourceLocationKey::embedded(uri.as_ref(), index),
+        )
+        .item;
+        for definition in &document.definitions {
+            let (name, span) = match definition {
+                ExecutableDefinition::Operation(operation) => {
+                    // Subscriptions can't be executed with a single request
+                    if operation.operation_kind() != OperationKind::Subscription {
+                        lenses.push(create_execute_lens(text_source, uri, operation));
+                    }
+                    match &operation.name {
+                        Some(name) => (name.value, name.span),
+                        // Anonymous operations have no artifact
+                        None => continue,
+                    }
+                }
+                ExecutableDefinition::Fragment(fragment) => {
+                    let name = fragment.name.value;
+                    if let Some(usages) = get_fragment_usages(FragmentDefinitionName(name)) {
+                        lenses.push(create_usages_lens(
+                            text_source,
+                            uri,
+                            fragment.name.span,
+                            usages,
+                        ));
+                    }
+                    (name, fragment.name.span)
+                }
+            };
+            if let Some(artifact_url) = get_artifact_url(name) {
+                lenses.push(create_lens(
+                    text_source,
+                    span,
+                    "Open generated artifact".to_string(),
+                    OPEN_ARTIFACT_COMMAND,
+                    vec![json!(artifact_url)],
+                ));
+            }
+        }
+    }
+    lenses
+}
+
+fn create_usages_lens(
+    text_source: &TextSource,
+    uri: &Url,
+    span: Span,
+    usages: Vec<LSPLocation>,
+) -> CodeLens {
+    let title = match usages.len() {
+        1 => "1 usage".to_string(),
+        count => format!("{} usages", count),
+    };
+    let position = text_source.to_span_range(span).start;
+    create_lens(
+        text_source,
+        span,
+        title,
+        SHOW_REFERENCES_COMMAND,
+        vec![json!(uri), json!(position), json!(usages)],
+    )
+}
+
+/// The arguments of the execute command are the document, the text of the
+/// template, sent back with the `graphql/executeQuery` request to get the full
+/// query text, and the name and type of each variable.
+fn create_execute_lens(
+    text_source: &TextSource,
+    uri: &Url,
+    operation: &OperationDefinition,
+) -> CodeLens {
+    let span = if let Some(name) = &operation.name {
+        name.span
+    } else if let Some((token, _)) = &operation.operation {
+        token.span
+    } else {
+        operation.selections.start.span
+    };
+    let variables = operation
+        .variable_definitions
+        .iter()
+        .flat_map(|variable_definitions| &variable_definitions.items)
+        .map(|variable_definition| {
+            json!({
+                "name": variable_definition.name.name.lookup(),
+                "type": variable_definition.type_.to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+    create_lens(
+        text_source,
+        span,
+        "Execute".to_string(),
+        EXECUTE_OPERATION_COMMAND,
+        vec![json!(uri), json!(text_source.text), json!(variables)],
+    )
+}
+
+fn create_lens(
+    text_source: &TextSource,
+    span: Span,
+    title: String,
+    command: &str,
+    arguments: Vec<serde_json::Value>,
+) -> CodeLens {
+    CodeLens {
+        range: text_source.to_span_range(span),
+        command: Some(Command {
+            title,
+            command: command.to_string(),
+            arguments: Some(arguments),
+        }),
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod test;
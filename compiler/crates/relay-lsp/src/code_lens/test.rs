@@ -0,0 +1,201 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use extract_graphql::ExtractOptions;
+use intern::Lookup;
+use lsp_types::Location as LSPLocation;
+use lsp_types::Position;
+use lsp_types::Range;
+use lsp_types::Url;
+
+use super::get_code_lenses;
+use super::EXECUTE_OPERATION_COMMAND;
+use super::OPEN_ARTIFACT_COMMAND;
+use super::SHOW_REFERENCES_COMMAND;
+
+const TEXT: &str = r#"const fragment = graphql`
+  fragment Foo_user on User {
+    name
+  }
+`;
+
+const query = graphql`
+  query FooQuery {
+    me {
+      ...Foo_user
+    }
+  }
+`;
+
+const anonymous = graphql`
+  query {
+    me { id }
+  }
+`;
+"#;
+
+fn document_uri() -> Url {
+    Url::parse("file:///src/Foo.js").unwrap()
+}
+
+fn artifact_uri(name: &str) -> Url {
+    Url::parse(&format!("file:///src/__generated__/{}.graphql.js", name)).unwrap()
+}
+
+/// The line, title and command of each lens
+fn get_lenses(usages: Option<Vec<LSPLocation>>, has_artifacts: bool) -> Vec<(u32, String, String)> {
+    get_code_lenses(
+        TEXT,
+        &document_uri(),
+        &ExtractOptions::default(),
+        |_| usages.clone(),
+        |name| {
+            if has_artifacts {
+                Some(artifact_uri(name.lookup()))
+            } else {
+                None
+            }
+        },
+    )
+    .into_iter()
+    .map(|lens| {
+        let command = lens.command.unwrap();
+        (lens.range.start.line, command.title, command.command)
+    })
+    .collect()
+}
+
+#[test]
+fn shows_usages_and_artifacts() {
+    let usage = LSPLocation::new(
+        document_uri(),
+        Range::new(Position::new(9, 6), Position::new(9, 17)),
+    );
+    assert_eq!(
+        get_lenses(Some(vec![usage]), true),
+        vec![
+            (
+                1,
+                "1 usage".to_string(),
+                SHOW_REFERENCES_COMMAND.to_string()
+            ),
+            (
+                1,
+                "Open generated artifact".to_string(),
+                OPEN_ARTIFACT_COMMAND.to_string()
+            ),
+            (
+                7,
+                "Execute".to_string(),
+                EXECUTE_OPERATION_COMMAND.to_string()
+            ),
+            (
+                7,
+                "Open generated artifact".to_string(),
+                OPEN_ARTIFACT_COMMAND.to_string()
+            ),
+            (
+                15,
+                "Execute".to_string(),
+                EXECUTE_OPERATION_COMMAND.to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn counts_missing_usages() {
+    assert_eq!(
+        get_lenses(Some(vec![]), false),
+        vec![
+            (
+                1,
+                "0 usages".to_string(),
+                SHOW_REFERENCES_COMMAND.to_string()
+            ),
+            (
+                7,
+                "Execute".to_string(),
+                EXECUTE_OPERATION_COMMAND.to_string()
+            ),
+            (
+                15,
+                "Execute".to_string(),
+                EXECUTE_OPERATION_COMMAND.to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn passes_locations_to_the_command() {
+    let usage = LSPLocation::new(
+        document_uri(),
+        Range::new(Position::new(9, 6), Position::new(9, 17)),
+    );
+    let lenses = get_code_lenses(
+        TEXT,
+        &document_uri(),
+        &ExtractOptions::default(),
+        |_| Some(vec![usage.clone()]),
+        |_| None,
+    );
+    let arguments = lenses[0]
+        .command
+        .as_ref()
+        .unwrap()
+        .arguments
+        .clone()
+        .unwrap();
+    assert_eq!(
+        arguments,
+        vec![
+            serde_json::json!(document_uri()),
+            serde_json::json!(Position::new(1, 11)),
+            serde_json::json!(vec![usage]),
+        ]
+    );
+}
+
+#[test]
+fn passes_operation_and_variables_to_execute_command() {
+    let text = r#"const mutation = graphql`
+  mutation FooMutation($id: ID!, $names: [String!]) {
+    setNames(id: $id, names: $names) { id }
+  }
+`;
+
+const subscription = graphql`
+  subscription FooSubscription {
+    updates { id }
+  }
+`;
+"#;
+    let lenses = get_code_lenses(
+        text,
+        &document_uri(),
+        &ExtractOptions::default(),
+        |_| None,
+        |_| None,
+    );
+    assert_eq!(lenses.len(), 1);
+    let command = lenses[0].command.as_ref().unwrap();
+    assert_eq!(command.command, EXECUTE_OPERATION_COMMAND);
+    assert_eq!(
+        command.arguments.clone().unwrap(),
+        vec![
+            serde_json::json!(document_uri()),
+            serde_json::json!(
+                "\n  mutation FooMutation($id: ID!, $names: [String!]) {\n    setNames(id: $id, names: $names) { id }\n  }\n"
+            ),
+            serde_json::json!([
+                {"name": "id", "type": "ID!"},
+                {"name": "names", "type": "[String!]"},
+            ]),
+        ]
+    );
+}
@@ -0,0 +1,208 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for previewing the artifacts generated for the operation or
+//! fragment under the cursor, without writing them to disk.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use common::Diagnostic;
+use common::PerfLogger;
+use common::SourceLocationKey;
+use extract_graphql::extract_from_path_with_options;
+use extract_graphql::JavaScriptSourceFeature;
+use graphql_ir::build_ir_with_extra_features;
+use graphql_ir::BuilderOptions;
+use graphql_ir::ExecutableDefinition;
+use graphql_ir::ExecutableDefinitionName;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::FragmentVariablesSemantic;
+use graphql_ir::OperationDefinitionName;
+use graphql_syntax::parse_executable;
+use intern::string_key::StringKey;
+use lsp_types::request::Request;
+use lsp_types::TextDocumentPositionParams;
+use relay_compiler::preview::preview_artifacts;
+use relay_compiler::preview::PreviewArtifact;
+use relay_compiler::SourceHashes;
+use schema_documentation::SchemaDocumentation;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::graphql_tools::get_program_with_referenced_fragments;
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+use crate::server::LSPState;
+use crate::LSPRuntimeError;
+
+pub(crate) enum PreviewCompiledOutput {}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CompiledArtifact {
+    /// Path of the artifact, relative to the root directory
+    path: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PreviewCompiledOutputResult {
+    definition_name: String,
+    artifacts: Vec<CompiledArtifact>,
+}
+
+impl Request for PreviewCompiledOutput {
+    type Params = TextDocumentPositionParams;
+    type Result = PreviewCompiledOutputResult;
+    const METHOD: &'static str = "relay/previewCompiledOutput";
+}
+
+pub(crate) fn on_preview_compiled_output(
+    state: &impl GlobalState,
+    params: TextDocumentPositionParams,
+) -> LSPRuntimeResult<<PreviewCompiledOutput as Request>::Result> {
+    let uri = &params.text_document.uri;
+    let project_name = state.extract_project_name_from_url(uri)?;
+    let (document, position_span) = state.extract_executable_document_from_text(&params, 1)?;
+    let definition = document
+        .definitions
+        .iter()
+        .find(|definition| definition.location().contains(position_span))
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    let name = definition.name().ok_or_else(|| {
+        LSPRuntimeError::UnexpectedError(
+            "Anonymous operations can't be compiled, give the operation a name.".to_string(),
+        )
+    })?;
+    let definition_name: ExecutableDefinitionName = match definition {
+        graphql_syntax::ExecutableDefinition::Operation(_) => OperationDefinitionName(name).into(),
+        graphql_syntax::ExecutableDefinition::Fragment(_) => FragmentDefinitionName(name).into(),
+    };
+
+    // The extracted document is keyed by the URL of the file, but the compiler
+    // derives the paths of the artifacts from the relative path of the source.
+    let index = match definition.location().source_location() {
+        SourceLocationKey::Embedded { index, .. } => index as usize,
+        _ => return Err(LSPRuntimeError::ExpectedError),
+    };
+    let absolute_file_path = uri.to_file_path().map_err(|_| {
+        LSPRuntimeError::UnexpectedError(format!("Unable to convert URL to file path: {:?}", uri))
+    })?;
+    let root_dir = state.root_dir();
+    let file_path = absolute_file_path
+        .strip_prefix(&root_dir)
+        .ok()
+        .and_then(|file_path| file_path.to_str())
+        .ok_or_else(|| {
+            LSPRuntimeError::UnexpectedError(format!(
+                "Failed to strip prefix {:?} from {:?}",
+                root_dir, absolute_file_path
+            ))
+        })?;
+    let text = state.get_document_text(uri)?;
+    let graphql_source = match extract_from_path_with_options(
+        Path::new(uri.path()),
+        &text,
+        &state.get_extract_options(),
+    )
+    .into_iter()
+    .nth(index)
+    {
+        Some(JavaScriptSourceFeature::GraphQL(graphql_source)) => graphql_source,
+        _ => return Err(LSPRuntimeError::ExpectedError),
+    };
+    let document = parse_executable(
+        &graphql_source.text_source().text,
+        SourceLocationKey::embedded(file_path, index),
+    )
+    .map_err(|diagnostics| LSPRuntimeError::UnexpectedError(print_diagnostics(&diagnostics)))?;
+
+    let artifacts =
+        state.get_compiled_output(&project_name, &document.definitions, definition_name)?;
+
+    Ok(PreviewCompiledOutputResult {
+        definition_name: name.to_string(),
+        artifacts: artifacts
+            .into_iter()
+            .map(|artifact| CompiledArtifact {
+                path: artifact.path.to_string_lossy().to_string(),
+                content: artifact.content,
+            })
+            .collect(),
+    })
+}
+
+/// Compiles the `definitions` of a document, with the fragments they reference
+/// from the source program, and returns the artifacts of `definition_name`.
+pub(crate) fn get_compiled_output<
+    TPerfLogger: PerfLogger + 'static,
+    TSchemaDocumentation: SchemaDocumentation,
+>(
+    state: &LSPState<TPerfLogger, TSchemaDocumentation>,
+    project_name: &StringKey,
+    definitions: &[graphql_syntax::ExecutableDefinition],
+    definition_name: ExecutableDefinitionName,
+) -> LSPRuntimeResult<Vec<PreviewArtifact>> {
+    let config = state.config();
+    let project_config = config
+        .enabled_projects()
+        .find(|project_config| &project_config.name == project_name)
+        .ok_or_else(|| {
+            LSPRuntimeError::UnexpectedError(format!(
+                "Unable to get project config for project {}.",
+                project_name
+            ))
+        })?;
+    let schema = state.get_schema(project_name)?;
+
+    let ir = build_ir_with_extra_features(
+        &schema,
+        definitions,
+        &BuilderOptions {
+            allow_undefined_fragment_spreads: true,
+            fragment_variables_semantic: FragmentVariablesSemantic::PassedValue,
+            relay_mode: Some(graphql_ir::RelayMode),
+            default_anonymous_operation_name: None,
+        },
+    )
+    .map_err(|diagnostics| LSPRuntimeError::UnexpectedError(print_diagnostics(&diagnostics)))?;
+
+    let mut operations = vec![];
+    let mut fragments = vec![];
+    for definition in ir {
+        match definition {
+            ExecutableDefinition::Operation(operation) => operations.push(Arc::new(operation)),
+            ExecutableDefinition::Fragment(fragment) => fragments.push(Arc::new(fragment)),
+        }
+    }
+    let source_program = state.get_program(project_name)?;
+    let program = get_program_with_referenced_fragments(operations, fragments, &source_program)
+        .ok_or_else(|| {
+            LSPRuntimeError::UnexpectedError(
+                "Unable to find the fragments referenced by the document.".to_string(),
+            )
+        })?;
+
+    preview_artifacts(
+        &config,
+        project_config,
+        program,
+        SourceHashes::from_definitions(definitions),
+        definition_name,
+        Arc::clone(&state.perf_logger),
+    )
+    .map_err(|diagnostics| LSPRuntimeError::UnexpectedError(print_diagnostics(&diagnostics)))
+}
+
+fn print_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|diagnostic| format!("- {}\n", diagnostic))
+        .collect()
+}
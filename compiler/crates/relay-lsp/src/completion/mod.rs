@@ -35,6 +35,7 @@ use graphql_syntax::ScalarField;
 use graphql_syntax::Selection;
 use graphql_syntax::TokenKind;
 use graphql_syntax::Value;
+use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use intern::Lookup;
 use log::debug;
@@ -43,6 +44,7 @@ use lsp_types::request::Request;
 use lsp_types::request::ResolveCompletionItem;
 use lsp_types::CompletionItem;
 use lsp_types::CompletionItemKind;
+use lsp_types::CompletionItemLabelDetails;
 use lsp_types::CompletionResponse;
 use lsp_types::Documentation;
 use lsp_types::InsertTextFormat;
@@ -56,6 +58,7 @@ use schema::Schema;
 use schema::Type;
 use schema::TypeReference;
 use schema::TypeWithFields;
+use serde::Deserialize;
 
 use crate::lsp_runtime_error::LSPRuntimeResult;
 use crate::node_resolution_info::TypePath;
@@ -64,6 +67,23 @@ use crate::server::GlobalState;
 use crate::LSPRuntimeError;
 use crate::SchemaDocumentation;
 
+/// Which completions to offer, from the `completion` initialization option.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CompletionConfig {
+    /// Offer a variant of linked fields that also selects the `id` and
+    /// `__typename` of the object
+    pub required_selections: bool,
+}
+
+impl Default for CompletionConfig {
+    fn default() -> Self {
+        Self {
+            required_selections: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum CompletionKind {
     FieldName {
@@ -82,6 +102,9 @@ pub enum CompletionKind {
         executable_name: ExecutableName,
         argument_name: StringKey,
         kind: ArgumentKind,
+        /// The input object fields from the argument to the value, if the
+        /// value is nested in an input object
+        input_field_path: Vec<StringKey>,
     },
     InlineFragmentType {
         existing_inline_fragment: bool,
@@ -371,6 +394,7 @@ impl CompletionRequestBuilder {
         mut input_field_path: Vec<StringKey>,
         constant_value: &ConstantValue,
         name: StringKey,
+        kind: ArgumentKind,
     ) -> Option<CompletionRequest> {
         match constant_value {
             ConstantValue::List(list) => list
@@ -384,6 +408,7 @@ impl CompletionRequestBuilder {
                         input_field_path,
                         constant_value,
                         name,
+                        kind,
                     )
                 }),
             ConstantValue::Object(arguments) => {
@@ -399,6 +424,7 @@ impl CompletionRequestBuilder {
                         input_field_path,
                         &constant_argument.value,
                         name,
+                        kind,
                     )
                 } else {
                     Some(self.new_request(
@@ -412,10 +438,36 @@ impl CompletionRequestBuilder {
                     ))
                 }
             }
+            ConstantValue::Enum(_) => {
+                self.build_request_from_input_value_leaf(type_path, input_field_path, name, kind)
+            }
+            ConstantValue::Null(token) if token.kind == TokenKind::Empty => {
+                self.build_request_from_input_value_leaf(type_path, input_field_path, name, kind)
+            }
             _ => None,
         }
     }
 
+    /// Completes the value of an argument, or of a field of an input object
+    /// in the argument.
+    fn build_request_from_input_value_leaf(
+        &self,
+        type_path: Vec<TypePathItem>,
+        input_field_path: Vec<StringKey>,
+        name: StringKey,
+        kind: ArgumentKind,
+    ) -> Option<CompletionRequest> {
+        Some(self.new_request(
+            CompletionKind::ArgumentValue {
+                executable_name: self.current_executable_name?,
+                argument_name: name,
+                kind,
+                input_field_path,
+            },
+            type_path,
+        ))
+    }
+
     fn build_request_from_input_value(
         &self,
         position_span: Span,
@@ -423,6 +475,7 @@ impl CompletionRequestBuilder {
         mut input_field_path: Vec<StringKey>,
         value: &Value,
         name: StringKey,
+        kind: ArgumentKind,
     ) -> Option<CompletionRequest> {
         match value {
             Value::List(list) => list
@@ -436,6 +489,7 @@ impl CompletionRequestBuilder {
                         input_field_path,
                         value,
                         name,
+                        kind,
                     )
                 }),
             Value::Object(arguments) => {
@@ -451,6 +505,7 @@ impl CompletionRequestBuilder {
                         input_field_path,
                         &position_argument.value,
                         name,
+                        kind,
                     )
                 } else {
                     Some(self.new_request(
@@ -470,8 +525,11 @@ impl CompletionRequestBuilder {
                 input_field_path,
                 constant_value,
                 name,
+                kind,
             ),
-            _ => None,
+            Value::Variable(_) => {
+                self.build_request_from_input_value_leaf(type_path, input_field_path, name, kind)
+            }
         }
     }
 
@@ -514,6 +572,7 @@ impl CompletionRequestBuilder {
                                     argument_name: name.value,
                                     executable_name,
                                     kind,
+                                    input_field_path: vec![],
                                 },
                                 type_path,
                             ))
@@ -525,12 +584,14 @@ impl CompletionRequestBuilder {
                                 Default::default(),
                                 constant_value,
                                 name.value,
+                                kind,
                             ),
                         Value::Variable(_) => Some(self.new_request(
                             CompletionKind::ArgumentValue {
                                 argument_name: name.value,
                                 executable_name,
                                 kind,
+                                input_field_path: vec![],
                             },
                             type_path,
                         )),
@@ -540,6 +601,7 @@ impl CompletionRequestBuilder {
                             Default::default(),
                             value,
                             name.value,
+                            kind,
                         ),
                     }
                 } else {
@@ -569,6 +631,7 @@ impl CompletionRequestBuilder {
                                         argument_name: name.value,
                                         executable_name,
                                         kind,
+                                        input_field_path: vec![],
                                     },
                                     type_path,
                                 ))
@@ -669,15 +732,17 @@ fn completion_items_for_request(
     schema: &SDLSchema,
     schema_documentation: impl SchemaDocumentation,
     program: &Program,
+    config: CompletionConfig,
 ) -> Option<Vec<CompletionItem>> {
     let kind = request.kind;
     debug!("completion_items_for_request: {:?}", kind);
     match kind {
         CompletionKind::FragmentSpread => {
             let leaf_type = request.type_path.resolve_leaf_type(schema)?;
-            Some(resolve_completion_items_for_fragment_spread(
-                leaf_type, program, schema, true,
-            ))
+            Some(merge_completion_items_ordered([
+                resolve_completion_items_for_fragment_spread(leaf_type, program, schema, true),
+                resolve_completion_items_for_inline_fragment_after_spread(leaf_type, schema),
+            ]))
         }
         CompletionKind::FieldName {
             existing_linked_field,
@@ -690,6 +755,7 @@ fn completion_items_for_request(
                         schema,
                         schema_documentation,
                         existing_linked_field,
+                        config,
                     ),
                     resolve_completion_items_typename(Type::Interface(interface_id), schema),
                     resolve_completion_items_for_inline_fragment(
@@ -711,6 +777,7 @@ fn completion_items_for_request(
                     schema,
                     schema_documentation,
                     existing_linked_field,
+                    config,
                 ),
                 resolve_completion_items_typename(Type::Object(object_id), schema),
                 resolve_completion_items_for_fragment_spread(
@@ -776,6 +843,7 @@ fn completion_items_for_request(
             executable_name,
             argument_name,
             kind,
+            input_field_path,
         } => {
             let argument_type = match kind {
                 ArgumentKind::Field => {
@@ -798,9 +866,22 @@ fn completion_items_for_request(
                         .type_
                 }
             };
+            let mut value_type = argument_type;
+            for input_field_name in input_field_path {
+                value_type = match value_type.inner() {
+                    Type::InputObject(id) => {
+                        &schema
+                            .input_object(id)
+                            .fields
+                            .named(ArgumentName(input_field_name))?
+                            .type_
+                    }
+                    _ => return None,
+                };
+            }
             Some(resolve_completion_items_for_argument_value(
                 schema,
-                argument_type,
+                value_type,
                 program,
                 executable_name,
             ))
@@ -963,11 +1044,9 @@ fn resolve_completion_items_for_argument_name<T: ArgumentLike>(
         .collect()
 }
 
-fn resolve_completion_items_for_inline_fragment(
-    type_: Type,
-    schema: &SDLSchema,
-    existing_inline_fragment: bool,
-) -> Vec<CompletionItem> {
+/// The types of the inline fragments that can be selected on an abstract type:
+/// the type itself and its possible types.
+fn get_inline_fragment_types(type_: Type, schema: &SDLSchema) -> Vec<Type> {
     match type_ {
         Type::Interface(id) => {
             let interface = schema.interface(id);
@@ -993,37 +1072,70 @@ fn resolve_completion_items_for_inline_fragment(
         }
         Type::Enum(_) | Type::Object(_) | Type::InputObject(_) | Type::Scalar(_) => vec![],
     }
-    .into_iter()
-    .map(|type_| {
-        let type_name = schema.get_type_name(type_).lookup();
-        if existing_inline_fragment {
-            CompletionItem::new_simple(type_name.to_owned(), "".into())
-        } else {
+}
+
+fn resolve_completion_items_for_inline_fragment(
+    type_: Type,
+    schema: &SDLSchema,
+    existing_inline_fragment: bool,
+) -> Vec<CompletionItem> {
+    get_inline_fragment_types(type_, schema)
+        .into_iter()
+        .map(|type_| {
+            let type_name = schema.get_type_name(type_).lookup();
+            if existing_inline_fragment {
+                CompletionItem::new_simple(type_name.to_owned(), "".into())
+            } else {
+                CompletionItem {
+                    label: format!("... on {type_name}"),
+                    kind: None,
+                    detail: None,
+                    documentation: None,
+                    deprecated: None,
+                    preselect: None,
+                    sort_text: None,
+                    filter_text: None,
+                    insert_text: Some(format!("... on {type_name} {{\n\t$1\n}}")),
+                    insert_text_format: Some(lsp_types::InsertTextFormat::SNIPPET),
+                    text_edit: None,
+                    additional_text_edits: None,
+                    command: Some(lsp_types::Command::new(
+                        "Suggest".into(),
+                        "editor.action.triggerSuggest".into(),
+                        None,
+                    )),
+                    data: None,
+                    tags: None,
+                    ..Default::default()
+                }
+            }
+        })
+        .collect()
+}
+
+/// Inline fragments completing a `...` that could also be a fragment spread.
+fn resolve_completion_items_for_inline_fragment_after_spread(
+    type_: Type,
+    schema: &SDLSchema,
+) -> Vec<CompletionItem> {
+    get_inline_fragment_types(type_, schema)
+        .into_iter()
+        .map(|type_| {
+            let type_name = schema.get_type_name(type_).lookup();
             CompletionItem {
-                label: format!("... on {type_name}"),
-                kind: None,
-                detail: None,
-                documentation: None,
-                deprecated: None,
-                preselect: None,
-                sort_text: None,
-                filter_text: None,
-                insert_text: Some(format!("... on {type_name} {{\n\t$1\n}}")),
+                label: format!("on {type_name}"),
+                filter_text: Some(type_name.to_owned()),
+                insert_text: Some(format!("on {type_name} {{\n\t$1\n}}")),
                 insert_text_format: Some(lsp_types::InsertTextFormat::SNIPPET),
-                text_edit: None,
-                additional_text_edits: None,
                 command: Some(lsp_types::Command::new(
                     "Suggest".into(),
                     "editor.action.triggerSuggest".into(),
                     None,
                 )),
-                data: None,
-                tags: None,
                 ..Default::default()
             }
-        }
-    })
-    .collect()
+        })
+        .collect()
 }
 
 fn resolve_completion_items_for_argument_value(
@@ -1032,6 +1144,21 @@ fn resolve_completion_items_for_argument_value(
     program: &Program,
     executable_name: ExecutableName,
 ) -> Vec<CompletionItem> {
+    // Variables can be passed where a supertype of theirs is expected, and
+    // as items of lists
+    let is_assignable = |variable_type: &TypeReference<Type>| {
+        schema.is_type_subtype_of(variable_type, type_)
+            || matches!(
+                type_.nullable_type(),
+                TypeReference::List(item_type) if schema.is_type_subtype_of(variable_type, item_type)
+            )
+    };
+    let variable_completion_item = |variable: &VariableDefinition| {
+        CompletionItem::new_simple(
+            format!("${}", variable.name.item),
+            schema.get_type_string(&variable.type_),
+        )
+    };
     let mut completion_items = match executable_name {
         ExecutableName::Fragment(name) => {
             if let Some(fragment) = program.fragment(name) {
@@ -1039,10 +1166,8 @@ fn resolve_completion_items_for_argument_value(
                     .used_global_variables
                     .iter()
                     .chain(fragment.variable_definitions.iter())
-                    .filter(|variable| variable.type_.eq(type_))
-                    .map(|variable| {
-                        CompletionItem::new_simple(format!("${}", variable.name.item,), "".into())
-                    })
+                    .filter(|variable| is_assignable(&variable.type_))
+                    .map(variable_completion_item)
                     .collect()
             } else {
                 vec![]
@@ -1053,10 +1178,8 @@ fn resolve_completion_items_for_argument_value(
                 operation
                     .variable_definitions
                     .iter()
-                    .filter(|variable| variable.type_.eq(type_))
-                    .map(|variable| {
-                        CompletionItem::new_simple(format!("${}", variable.name.item,), "".into())
-                    })
+                    .filter(|variable| is_assignable(&variable.type_))
+                    .map(variable_completion_item)
                     .collect()
             } else {
                 vec![]
@@ -1064,16 +1187,13 @@ fn resolve_completion_items_for_argument_value(
         }
     };
 
-    if !type_.is_list() {
-        if let Type::Enum(id) = type_.inner() {
-            let enum_ = schema.enum_(id);
-            completion_items.extend(
-                enum_
-                    .values
-                    .iter()
-                    .map(|value| CompletionItem::new_simple(value.value.to_string(), "".into())),
-            )
-        }
+    // A single enum value is also accepted for a list of enums
+    if let Type::Enum(id) = type_.inner() {
+        let enum_ = schema.enum_(id);
+        let enum_type_name = schema.get_type_name(type_.inner());
+        completion_items.extend(enum_.values.iter().map(|value| {
+            CompletionItem::new_simple(value.value.to_string(), enum_type_name.to_string())
+        }))
     }
 
     completion_items
@@ -1084,11 +1204,12 @@ fn resolve_completion_items_for_fields<T: TypeWithFields + Named>(
     schema: &SDLSchema,
     schema_documentation: impl SchemaDocumentation,
     existing_linked_field: bool,
+    config: CompletionConfig,
 ) -> Vec<CompletionItem> {
     type_
         .fields()
         .iter()
-        .map(|field_id| {
+        .flat_map(|field_id| {
             let field = schema.field(*field_id);
             let field_name = field.name.item.to_string();
             let deprecated = field.deprecated();
@@ -1147,7 +1268,7 @@ fn resolve_completion_items_for_fields<T: TypeWithFields + Named>(
                 Type::Scalar(_) => Some(CompletionItemKind::FIELD),
             };
 
-            CompletionItem {
+            let item = CompletionItem {
                 label: field_name,
                 kind,
                 detail: deprecated_reason.or(Some(type_name)),
@@ -1164,11 +1285,64 @@ fn resolve_completion_items_for_fields<T: TypeWithFields + Named>(
                 data: None,
                 tags: None,
                 ..Default::default()
+            };
+
+            let required_selections = if config.required_selections && !existing_linked_field {
+                required_selections(field.type_.inner(), schema)
+            } else {
+                vec![]
+            };
+            if required_selections.is_empty() {
+                return vec![item];
             }
+
+            // A variant of the field that also inserts its required selections
+            let selections = required_selections
+                .iter()
+                .map(|selection| format!("\t{}\n", selection))
+                .collect::<String>();
+            let insert_text = if args.is_empty() {
+                format!("{} {{\n{}\t$1\n}}", item.label, selections)
+            } else {
+                format!(
+                    "{}({}) {{\n{}\t${}\n}}",
+                    item.label,
+                    args.join(", "),
+                    selections,
+                    args.len() + 1
+                )
+            };
+            let item_with_required_selections = CompletionItem {
+                label_details: Some(CompletionItemLabelDetails {
+                    detail: Some(format!(" {{ {} }}", required_selections.join(" "))),
+                    description: None,
+                }),
+                // Right after the field without the selections
+                sort_text: Some(format!("{} ", item.label)),
+                insert_text: Some(insert_text),
+                ..item.clone()
+            };
+            vec![item, item_with_required_selections]
         })
         .collect()
 }
 
+/// The selections that Relay needs in a linked field of `type_`: the `id` to
+/// identify the object in the store, and the `__typename` of abstract types.
+fn required_selections(type_: Type, schema: &SDLSchema) -> Vec<&'static str> {
+    let mut selections = vec![];
+    if matches!(type_, Type::Scalar(_) | Type::Enum(_)) {
+        return selections;
+    }
+    if schema.named_field(type_, "id".intern()).is_some() {
+        selections.push("id");
+    }
+    if type_.is_abstract_type() {
+        selections.push("__typename");
+    }
+    selections
+}
+
 fn resolve_completion_items_for_fragment_spread(
     type_: Type,
     source_program: &Program,
@@ -1184,10 +1358,14 @@ fn resolve_completion_items_for_fragment_spread(
             } else {
                 format!("...{}", fragment.name.item)
             };
-            let detail = schema
-                .get_type_name(fragment.type_condition)
-                .lookup()
-                .to_string();
+            let type_name = schema.get_type_name(fragment.type_condition).lookup();
+            let source_location = fragment.name.location.source_location();
+            // Include the file of the fragment, to tell apart similarly named fragments
+            let detail = if source_location.is_generated() {
+                type_name.to_string()
+            } else {
+                format!("{} ({})", type_name, source_location.path())
+            };
             if fragment.variable_definitions.is_empty() {
                 return CompletionItem::new_simple(label, detail);
             }
@@ -1335,6 +1513,7 @@ pub fn on_completion(
                 schema,
                 state.get_schema_documentation(project_name.lookup()),
                 &state.get_program(&project_name)?,
+                state.get_completion_config(),
             )
             .unwrap_or_else(Vec::new);
             Ok(Some(CompletionResponse::Array(items)))
@@ -1377,11 +1556,18 @@ fn resolve_completion_items(
     schema: &SDLSchema,
     schema_documentation: impl SchemaDocumentation,
     progam: &Program,
+    config: CompletionConfig,
 ) -> Option<Vec<CompletionItem>> {
     let completion_request = CompletionRequestBuilder::new(project_name)
         .create_completion_request(document, position_span);
     completion_request.and_then(|completion_request| {
-        completion_items_for_request(completion_request, schema, schema_documentation, progam)
+        completion_items_for_request(
+            completion_request,
+            schema,
+            schema_documentation,
+            progam,
+            config,
+        )
     })
 }
 
@@ -21,11 +21,20 @@ use lsp_types::Documentation;
 use relay_test_schema::get_test_schema;
 
 use super::make_markdown_table_documentation;
+use super::CompletionConfig;
 use super::resolve_completion_items;
 
 fn parse_and_resolve_completion_items(
     source: &str,
     program: Option<Program>,
+) -> Option<Vec<CompletionItem>> {
+    parse_and_resolve_completion_items_with_config(source, program, Default::default())
+}
+
+fn parse_and_resolve_completion_items_with_config(
+    source: &str,
+    program: Option<Program>,
+    config: CompletionConfig,
 ) -> Option<Vec<CompletionItem>> {
     let pos = source.find('|').unwrap() - 1;
     let next_source = source.replace("|", "");
@@ -48,6 +57,7 @@ fn parse_and_resolve_completion_items(
         &Arc::clone(&test_schema),
         Arc::clone(&test_schema),
         &program.unwrap_or_else(|| Program::new(Arc::clone(&test_schema))),
+        config,
     )
 }
 
@@ -124,6 +134,48 @@ fn linked_field() {
     assert_labels(items.unwrap(), vec!["location", "categories", "__typename"]);
 }
 
+#[test]
+fn linked_field_with_required_selections() {
+    let items = parse_and_resolve_completion_items(
+        r#"
+            query Test {
+                |
+            }
+        "#,
+        None,
+    )
+    .unwrap();
+    let insert_texts = items
+        .into_iter()
+        .filter(|item| item.label_details.is_some())
+        .map(|item| (item.label, item.insert_text.unwrap()))
+        .collect::<HashMap<_, _>>();
+
+    assert_eq!(insert_texts.get("me").unwrap(), "me {\n\tid\n\t$1\n}");
+    assert_eq!(
+        insert_texts.get("node_id_required").unwrap(),
+        "node_id_required(id: $1) {\n\tid\n\t__typename\n\t$2\n}"
+    );
+    assert!(!insert_texts.contains_key("viewer"));
+}
+
+#[test]
+fn linked_field_without_required_selections() {
+    let items = parse_and_resolve_completion_items_with_config(
+        r#"
+            query Test {
+                |
+            }
+        "#,
+        None,
+        CompletionConfig {
+            required_selections: false,
+        },
+    )
+    .unwrap();
+    assert!(items.iter().all(|item| item.label_details.is_none()));
+}
+
 #[test]
 fn whitespace_in_linked_field() {
     let items = parse_and_resolve_completion_items(
@@ -542,7 +594,53 @@ fn fragment_spread_on_interface() {
     "#,
         )),
     );
-    assert_labels(items.unwrap(), vec!["TestFragment", "TestFragment2"]);
+    assert_labels(
+        items.unwrap(),
+        vec![
+            "TestFragment",
+            "TestFragment2",
+            "on Actor",
+            "on Page",
+            "on User",
+        ],
+    );
+}
+
+#[test]
+fn fragment_spread_with_source_file() {
+    let document = parse_executable(
+        r#"
+        fragment TestFragment on Viewer {
+           __typename
+        }
+
+        fragment UnrelatedFragment on Task {
+            __typename
+        }
+      "#,
+        SourceLocationKey::standalone("src/TestFragment.graphql"),
+    )
+    .unwrap();
+    let ir = build(&get_test_schema(), &document.definitions).unwrap();
+    let items = parse_and_resolve_completion_items(
+        r#"
+            fragment Test on Viewer {
+               ...T|
+            }
+        "#,
+        Some(Program::from_definitions(get_test_schema(), ir)),
+    )
+    .unwrap();
+    assert_eq!(
+        items
+            .into_iter()
+            .map(|item| (item.label, item.detail))
+            .collect::<Vec<_>>(),
+        vec![(
+            "TestFragment".to_string(),
+            Some("Viewer (src/TestFragment.graphql)".to_string())
+        )]
+    );
 }
 
 #[test]
@@ -652,6 +750,105 @@ fn argument_value_between_names() {
     assert_labels(items.unwrap(), vec!["$pictureSize", "$pictureSize2"]);
 }
 
+#[test]
+fn argument_value_compatible_variables() {
+    let items = parse_and_resolve_completion_items(
+        r#"
+            fragment Test on User {
+                profilePicture(size: |) {
+                    uri
+                }
+            }
+        "#,
+        Some(build_test_program(
+            r#"
+            fragment Test on User
+                @argumentDefinitions(
+                    sizes: {type: "[Int]!"},
+                    size: {type: "Int!"},
+                    name: {type: "String"}
+                ) {
+                __typename
+            }
+        "#,
+        )),
+    );
+    assert_labels(items.unwrap(), vec!["$sizes", "$size"]);
+}
+
+#[test]
+fn argument_value_enum() {
+    let items = parse_and_resolve_completion_items(
+        r#"
+            fragment Test on User {
+                profilePicture(preset: S|) {
+                    uri
+                }
+            }
+        "#,
+        None,
+    );
+    assert_labels(items.unwrap(), vec!["SMALL", "LARGE"]);
+}
+
+#[test]
+fn argument_value_enum_in_list() {
+    let items = parse_and_resolve_completion_items(
+        r#"
+            fragment Test on User {
+                friends(traits: [HELPFUL, S|]) {
+                    __typename
+                }
+            }
+        "#,
+        None,
+    );
+    assert_labels(
+        items.unwrap(),
+        vec!["CHEERFUL", "DERISIVE", "HELPFUL", "SNARKY"],
+    );
+}
+
+#[test]
+fn argument_value_enum_in_input_object() {
+    let items = parse_and_resolve_completion_items(
+        r#"
+            fragment Test on User {
+                storySearch(query: {text: "relay", type: U|}) {
+                    id
+                }
+            }
+        "#,
+        None,
+    );
+    assert_labels(items.unwrap(), vec!["DIRECTED", "UNDIRECTED"]);
+}
+
+#[test]
+fn arguments_directive_names() {
+    let items = parse_and_resolve_completion_items(
+        r#"
+            fragment Test on User {
+                ...Other_user @arguments(size: 32, |)
+            }
+        "#,
+        Some(build_test_program(
+            r#"
+            fragment Other_user on User
+                @argumentDefinitions(
+                    size: {type: "[Int]"},
+                    preset: {type: "PhotoSize"}
+                ) {
+                profilePicture(size: $size, preset: $preset) {
+                    uri
+                }
+            }
+        "#,
+        )),
+    );
+    assert_labels(items.unwrap(), vec!["preset"]);
+}
+
 #[test]
 fn no_typename_on_query() {
     let items = parse_and_resolve_completion_items(
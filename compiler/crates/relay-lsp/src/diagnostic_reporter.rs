@@ -8,6 +8,7 @@
 //! Utilities for reporting errors to an LSP client
 use std::path::PathBuf;
 
+use common::diagnostic_code_url;
 use common::get_diagnostics_data;
 use common::Diagnostic as CompilerDiagnostic;
 use common::DiagnosticRelatedInformation;
@@ -17,16 +18,19 @@ use crossbeam::channel::Sender;
 use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
 use dunce::canonicalize;
+use extract_graphql::ExtractOptions;
 use extract_graphql::JavaScriptSourceFeature;
 use lsp_server::Message;
 use lsp_server::Notification as ServerNotification;
 use lsp_types::notification::Notification;
 use lsp_types::notification::PublishDiagnostics;
+use lsp_types::CodeDescription;
 use lsp_types::Diagnostic;
 use lsp_types::DiagnosticRelatedInformation as LspDiagnosticRelatedInformation;
 use lsp_types::DiagnosticSeverity;
 use lsp_types::DiagnosticTag;
 use lsp_types::Location as LspLocation;
+use lsp_types::NumberOrString;
 use lsp_types::Position;
 use lsp_types::PublishDiagnosticsParams;
 use lsp_types::Range;
@@ -36,6 +40,8 @@ use relay_compiler::errors::Error;
 use relay_compiler::source_for_location;
 use relay_compiler::FsSourceReader;
 use relay_compiler::SourceReader;
+use relay_transforms::diagnostic_code;
+use serde_json::Value;
 
 use crate::lsp_process_error::LSPProcessResult;
 
@@ -43,6 +49,12 @@ use crate::lsp_process_error::LSPProcessResult;
 /// Returns None if we are unable to do the conversion
 fn url_from_location(location: Location, root_dir: &PathBuf) -> Option<Url> {
     let file_path = location.source_location().path();
+    // Sources validated as they are typed are keyed by the URL of their document
+    if let Ok(url) = Url::parse(file_path) {
+        if url.scheme() == "file" {
+            return Some(url);
+        }
+    }
     let canonical_path = canonicalize(root_dir.join(file_path)).ok()?;
     Url::from_file_path(canonical_path).ok()
 }
@@ -63,12 +75,16 @@ pub struct DiagnosticReporter {
 }
 
 impl DiagnosticReporter {
-    pub fn new(root_dir: PathBuf, sender: Sender<Message>) -> Self {
+    pub fn new(
+        root_dir: PathBuf,
+        sender: Sender<Message>,
+        extract_options: ExtractOptions,
+    ) -> Self {
         Self {
             active_diagnostics: Default::default(),
             sender,
             root_dir,
-            source_reader: Box::new(FsSourceReader),
+            source_reader: Box::new(FsSourceReader::new(extract_options)),
         }
     }
 
@@ -117,6 +133,30 @@ impl DiagnosticReporter {
         }
     }
 
+    /// Returns the number of errors currently published to the client, counting
+    /// the errors reported by both the IDE and the watchman sources once.
+    pub fn error_count(&self) -> usize {
+        let is_error =
+            |diagnostic: &&Diagnostic| diagnostic.severity == Some(DiagnosticSeverity::ERROR);
+        self.active_diagnostics
+            .iter()
+            .map(|r| {
+                let diagnostics = r.value();
+                diagnostics
+                    .quick_diagnostics
+                    .iter()
+                    .filter(is_error)
+                    .count()
+                    + diagnostics
+                        .regular_diagnostics
+                        .iter()
+                        .filter(is_error)
+                        .filter(|diagnostic| !diagnostics.quick_diagnostics.contains(diagnostic))
+                        .count()
+            })
+            .sum()
+    }
+
     fn add_diagnostic(&self, url: Url, diagnostic: Diagnostic) {
         self.active_diagnostics
             .entry(url)
@@ -205,12 +245,42 @@ impl DiagnosticReporter {
         let related_information = diagnostic
             .related_information()
             .iter()
-            .filter_map(|info| self.maybe_convert_related_information(info))
+            .filter_map(|info| {
+                self.maybe_convert_related_information(diagnostic.location(), text_source, info)
+            })
             .collect::<Vec<_>>();
 
+        let machine_readable = diagnostic.machine_readable();
+        // Code actions match on the `err` of the machine readable metadata, so
+        // it takes precedence over the stable code of the message.
+        let stable_code = diagnostic_code(diagnostic);
+        let code = machine_readable
+            .as_ref()
+            .and_then(|metadata| metadata.get("err"))
+            .map(|err| NumberOrString::String(err.clone()))
+            .or_else(|| stable_code.map(|code| NumberOrString::String(code.to_string())));
+        let code_description = stable_code.and_then(|code| {
+            Url::parse(&diagnostic_code_url(code))
+                .ok()
+                .map(|href| CodeDescription { href })
+        });
+        // Suggestions take precedence; otherwise expose the machine readable metadata,
+        // so that code actions can build fixes from it.
+        let data = get_diagnostics_data(diagnostic).or_else(|| {
+            machine_readable.map(|metadata| {
+                Value::Object(
+                    metadata
+                        .into_iter()
+                        .map(|(key, value)| (key, Value::String(value)))
+                        .collect(),
+                )
+            })
+        });
+
         Diagnostic {
-            code: None,
-            data: get_diagnostics_data(diagnostic),
+            code,
+            code_description,
+            data,
             message: diagnostic.message().to_string(),
             range: text_source.to_span_range(diagnostic.location().span()),
             related_information: if related_information.is_empty() {
@@ -221,23 +291,30 @@ impl DiagnosticReporter {
             severity: Some(diagnostic.severity()),
             tags: if tags.is_empty() { None } else { Some(tags) },
             source: None,
-            ..Default::default()
         }
     }
 
+    /// Converts an annotation of a diagnostic to a related location. Annotations
+    /// in the same source as the diagnostic use its `text_source`, which may not
+    /// be saved yet, the others are read from the disk.
     pub fn maybe_convert_related_information(
         &self,
+        diagnostic_location: Location,
+        text_source: &TextSource,
         info: &DiagnosticRelatedInformation,
     ) -> Option<LspDiagnosticRelatedInformation> {
+        let range = if info.location.source_location() == diagnostic_location.source_location() {
+            text_source.to_span_range(info.location.span())
+        } else {
+            self.source_from_location(info.location)?
+                .text_source()
+                .to_span_range(info.location.span())
+        };
         let uri = self.url_from_location(info.location)?;
-        let source = self.source_from_location(info.location)?;
 
         Some(LspDiagnosticRelatedInformation {
             message: info.message.to_string(),
-            location: LspLocation {
-                range: source.text_source().to_span_range(info.location.span()),
-                uri,
-            },
+            location: LspLocation { range, uri },
         })
     }
 
@@ -340,6 +417,8 @@ mod tests {
     use common::Location;
     use common::SourceLocationKey;
     use common::Span;
+    use common::TextSource;
+    use extract_graphql::ExtractOptions;
     use intern::string_key::Intern;
     use lsp_types::Position;
     use lsp_types::Range;
@@ -361,7 +440,7 @@ mod tests {
         let root_dir =
             env::current_dir().expect("expect to be able to get the current working directory");
         let (sender, _) = crossbeam::channel::unbounded();
-        let mut reporter = DiagnosticReporter::new(root_dir, sender);
+        let mut reporter = DiagnosticReporter::new(root_dir, sender, ExtractOptions::default());
         reporter.set_source_reader(Box::new(MockSourceReader("Content".to_string())));
         let source_location = SourceLocationKey::Standalone {
             path: "foo.txt".intern(),
@@ -374,6 +453,62 @@ mod tests {
         assert_eq!(reporter.active_diagnostics.len(), 1);
     }
 
+    #[test]
+    fn error_count_test() {
+        let root_dir =
+            env::current_dir().expect("expect to be able to get the current working directory");
+        let (sender, _) = crossbeam::channel::unbounded();
+        let mut reporter = DiagnosticReporter::new(root_dir, sender, ExtractOptions::default());
+        reporter.set_source_reader(Box::new(MockSourceReader("Content".to_string())));
+        let source_location = SourceLocationKey::Standalone {
+            path: "foo.txt".intern(),
+        };
+        assert_eq!(reporter.error_count(), 0);
+        reporter.report_diagnostic(&Diagnostic::error(
+            "test message",
+            Location::new(source_location, Span { start: 0, end: 1 }),
+        ));
+        reporter.report_diagnostic(&Diagnostic::hint(
+            "test hint",
+            Location::new(source_location, Span { start: 1, end: 2 }),
+            vec![],
+        ));
+        assert_eq!(reporter.error_count(), 1);
+    }
+
+    #[test]
+    fn related_information_in_same_source_test() {
+        let root_dir = PathBuf::from("/tmp");
+        let (sender, _) = crossbeam::channel::unbounded();
+        let mut reporter = DiagnosticReporter::new(root_dir, sender, ExtractOptions::default());
+        reporter.set_source_reader(Box::new(MockSourceReader("".to_string())));
+        let source_location = SourceLocationKey::embedded("file:///tmp/Component.js", 0);
+        let text_source = TextSource::new("query Q {\n  a: foo\n  a: bar\n}", 3, 16);
+        let diagnostic = Diagnostic::error(
+            "conflicting fields",
+            Location::new(source_location, Span { start: 12, end: 18 }),
+        )
+        .annotate(
+            "the other field",
+            Location::new(source_location, Span { start: 21, end: 27 }),
+        );
+
+        let related_information = reporter
+            .convert_diagnostic(&text_source, &diagnostic)
+            .related_information
+            .unwrap();
+        assert_eq!(related_information.len(), 1);
+        assert_eq!(related_information[0].message, "the other field");
+        assert_eq!(
+            related_information[0].location.uri.as_str(),
+            "file:///tmp/Component.js"
+        );
+        assert_eq!(
+            related_information[0].location.range,
+            Range::new(Position::new(5, 2), Position::new(5, 8))
+        );
+    }
+
     /// This test will assert that the message without URL (with generated source) won't be reported by LSPStatusReporter
     /// I'm not sure if this is the right behavior, but lets capture it here.
     #[test]
@@ -381,7 +516,7 @@ mod tests {
         let root_dir = PathBuf::from("/tmp");
         let (sender, _) = crossbeam::channel::unbounded();
 
-        let mut reporter = DiagnosticReporter::new(root_dir, sender);
+        let mut reporter = DiagnosticReporter::new(root_dir, sender, ExtractOptions::default());
         reporter.set_source_reader(Box::new(MockSourceReader("".to_string())));
 
         reporter.report_diagnostic(&Diagnostic::error("-", Location::generated()));
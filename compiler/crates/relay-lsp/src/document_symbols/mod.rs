@@ -0,0 +1,177 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing the document outline: the operations and fragments
+//! of a document, with the linked fields and inline fragments they select.
+
+use std::path::Path;
+
+use common::SourceLocationKey;
+use common::Span;
+use common::TextSource;
+use extract_graphql::ExtractOptions;
+use extract_graphql::JavaScriptSourceFeature;
+use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::Selection;
+use lsp_types::request::DocumentSymbolRequest;
+use lsp_types::request::Request;
+use lsp_types::DocumentSymbol;
+use lsp_types::DocumentSymbolResponse;
+use lsp_types::SymbolKind;
+
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+
+pub fn on_document_symbol(
+    state: &impl GlobalState,
+    params: <DocumentSymbolRequest as Request>::Params,
+) -> LSPRuntimeResult<<DocumentSymbolRequest as Request>::Result> {
+    let uri = params.text_document.uri;
+    let document_text = state.get_document_text(&uri)?;
+
+    Ok(Some(DocumentSymbolResponse::Nested(get_document_symbols(
+        &document_text,
+        uri.as_ref(),
+        &state.get_extract_options(),
+    ))))
+}
+
+fn get_document_symbols(
+    document_text: &str,
+    path: &str,
+    extract_options: &ExtractOptions,
+) -> Vec<DocumentSymbol> {
+    let mut symbols = vec![];
+    for (index, feature) in extract_graphql::extract_from_path_with_options(
+        Path::new(path),
+        document_text,
+        extract_options,
+    )
+    .into_iter()
+    .enumerate()
+    {
+        let source = match feature {
+            JavaScriptSourceFeature::GraphQL(source) => source,
+            JavaScriptSourceFeature::Docblock(_) => continue,
+        };
+        let text_source = source.text_source();
+        let document = parse_executable_with_error_recovery(
+            &text_source.text,
+            SourceLocationKey::embedded(path, index),
+        )
+        .item;
+        for definition in &document.definitions {
+            symbols.push(match definition {
+                ExecutableDefinition::Operation(operation) => {
+                    let operation_kind = operation.operation_kind();
+                    let selection_span = match (&operation.name, &operation.operation) {
+                        (Some(name), _) => name.span,
+                        (None, Some((token, _))) => token.span,
+                        (None, None) => operation.selections.span,
+                    };
+                    create_symbol(
+                        text_source,
+                        operation
+                            .name
+                            .as_ref()
+                            .map_or_else(|| operation_kind.to_string(), |name| name.to_string()),
+                        Some(operation_kind.to_string()),
+                        SymbolKind::FUNCTION,
+                        operation.location.span(),
+                        selection_span,
+                        get_selection_symbols(text_source, &operation.selections.items),
+                    )
+                }
+                ExecutableDefinition::Fragment(fragment) => create_symbol(
+                    text_source,
+                    fragment.name.to_string(),
+                    Some(fragment.type_condition.to_string()),
+                    SymbolKind::CLASS,
+                    fragment.location.span(),
+                    fragment.name.span,
+                    get_selection_symbols(text_source, &fragment.selections.items),
+                ),
+            });
+        }
+    }
+    symbols
+}
+
+/// The symbols of the linked fields and inline fragments of the selections.
+fn get_selection_symbols(
+    text_source: &TextSource,
+    selections: &[Selection],
+) -> Vec<DocumentSymbol> {
+    selections
+        .iter()
+        .filter_map(|selection| match selection {
+            Selection::LinkedField(field) => Some(create_symbol(
+                text_source,
+                field
+                    .alias
+                    .as_ref()
+                    .map_or_else(|| field.name.to_string(), |alias| alias.alias.to_string()),
+                field.alias.as_ref().map(|_| field.name.to_string()),
+                SymbolKind::FIELD,
+                field.span,
+                field
+                    .alias
+                    .as_ref()
+                    .map_or(field.name.span, |alias| alias.alias.span),
+                get_selection_symbols(text_source, &field.selections.items),
+            )),
+            Selection::InlineFragment(fragment) => Some(create_symbol(
+                text_source,
+                match &fragment.type_condition {
+                    Some(type_condition) => format!("... {}", type_condition),
+                    None => "...".to_string(),
+                },
+                None,
+                SymbolKind::STRUCT,
+                fragment.span,
+                fragment
+                    .type_condition
+                    .as_ref()
+                    .map_or(fragment.spread.span, |type_condition| type_condition.span),
+                get_selection_symbols(text_source, &fragment.selections.items),
+            )),
+            Selection::FragmentSpread(_) | Selection::ScalarField(_) => None,
+        })
+        .collect()
+}
+
+fn create_symbol(
+    text_source: &TextSource,
+    name: String,
+    detail: Option<String>,
+    kind: SymbolKind,
+    span: Span,
+    selection_span: Span,
+    children: Vec<DocumentSymbol>,
+) -> DocumentSymbol {
+    // `deprecated` is deprecated in favor of `tags`, but still required to
+    // construct the symbol.
+    #[allow(deprecated)]
+    DocumentSymbol {
+        name,
+        detail,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: text_source.to_span_range(span),
+        selection_range: text_source.to_span_range(selection_span),
+        children: if children.is_empty() {
+            None
+        } else {
+            Some(children)
+        },
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,103 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use extract_graphql::ExtractOptions;
+use lsp_types::DocumentSymbol;
+use lsp_types::Position;
+use lsp_types::Range;
+use lsp_types::SymbolKind;
+
+use super::get_document_symbols;
+
+const TEXT: &str = r#"const query = graphql`
+  query FooQuery {
+    me {
+      name
+      avatar: profilePicture(size: 32) {
+        uri
+      }
+      ... on User {
+        friends {
+          count
+        }
+      }
+    }
+  }
+`;
+
+const fragment = graphql`
+  fragment Foo_user on User {
+    ...Bar_user
+  }
+`;
+"#;
+
+/// The symbols in depth-first order, with their depth.
+fn flatten(
+    symbols: &[DocumentSymbol],
+    depth: usize,
+    result: &mut Vec<(usize, String, Option<String>, SymbolKind)>,
+) {
+    for symbol in symbols {
+        result.push((
+            depth,
+            symbol.name.clone(),
+            symbol.detail.clone(),
+            symbol.kind,
+        ));
+        flatten(
+            symbol.children.as_deref().unwrap_or_default(),
+            depth + 1,
+            result,
+        );
+    }
+}
+
+#[test]
+fn outlines_operations_and_fragments() {
+    let symbols = get_document_symbols(TEXT, "/src/Foo.js", &ExtractOptions::default());
+    let mut result = vec![];
+    flatten(&symbols, 0, &mut result);
+    assert_eq!(
+        result,
+        vec![
+            (
+                0,
+                "FooQuery".to_string(),
+                Some("query".to_string()),
+                SymbolKind::FUNCTION
+            ),
+            (1, "me".to_string(), None, SymbolKind::FIELD),
+            (
+                2,
+                "avatar".to_string(),
+                Some("profilePicture".to_string()),
+                SymbolKind::FIELD
+            ),
+            (2, "... on User".to_string(), None, SymbolKind::STRUCT),
+            (3, "friends".to_string(), None, SymbolKind::FIELD),
+            (
+                0,
+                "Foo_user".to_string(),
+                Some("on User".to_string()),
+                SymbolKind::CLASS
+            ),
+        ]
+    );
+}
+
+#[test]
+fn uses_document_ranges() {
+    let symbols = get_document_symbols(TEXT, "/src/Foo.js", &ExtractOptions::default());
+    let fragment = &symbols[1];
+    assert_eq!(
+        fragment.selection_range,
+        Range::new(Position::new(17, 11), Position::new(17, 19))
+    );
+    assert_eq!(fragment.range.start, Position::new(17, 2));
+    assert_eq!(fragment.range.end, Position::new(19, 3));
+}
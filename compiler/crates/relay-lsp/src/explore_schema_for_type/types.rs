@@ -70,6 +70,8 @@ pub(crate) struct SchemaExplorerInputObject {
 #[derive(Deserialize, Serialize)]
 pub(crate) struct SchemaExplorerEnum {
     values: Vec<String>,
+    // The descriptions of the values, in the same order
+    value_descriptions: Vec<Option<String>>,
     is_extension: bool,
     directives: Vec<SchemaExplorerDirective>,
 }
@@ -145,7 +147,7 @@ pub(crate) fn get_full_schema_explorer_type_reference(
             SchemaExplorerSchemaType::Interface(interface)
         }
         Type::Enum(enum_id) => {
-            let enum_ = get_schema_explorer_enum(enum_id, schema);
+            let enum_ = get_schema_explorer_enum(enum_id, schema, documentation);
             SchemaExplorerSchemaType::Enum(enum_)
         }
         Type::InputObject(input_object_id) => {
@@ -188,15 +190,30 @@ fn get_empty_schema_explorer_type_reference(
     }
 }
 
-fn get_schema_explorer_enum(enum_id: EnumID, schema: &SDLSchema) -> SchemaExplorerEnum {
+fn get_schema_explorer_enum(
+    enum_id: EnumID,
+    schema: &SDLSchema,
+    documentation: &impl SchemaDocumentation,
+) -> SchemaExplorerEnum {
     let enum_ = schema.enum_(enum_id);
+    let enum_name = enum_.name.item.0.lookup();
     let values = enum_
         .values
         .iter()
         .map(|value| value.value.to_string())
         .collect::<Vec<_>>();
+    let value_descriptions = enum_
+        .values
+        .iter()
+        .map(|value| {
+            documentation
+                .get_enum_value_description(enum_name, value.value.lookup())
+                .map(|description| description.to_string())
+        })
+        .collect::<Vec<_>>();
     SchemaExplorerEnum {
         values,
+        value_descriptions,
         is_extension: enum_.is_extension,
         directives: get_schema_explorer_directives(&enum_.directives),
     }
@@ -263,6 +280,7 @@ fn get_schema_explorer_input_object(
     documentation: &impl SchemaDocumentation,
 ) -> SchemaExplorerInputObject {
     let input_object = schema.input_object(input_object_id);
+    let input_object_name = input_object.name.item.0.lookup();
     let arguments = input_object
         .fields
         .iter()
@@ -272,7 +290,9 @@ fn get_schema_explorer_input_object(
 
             SchemaExplorerFieldArgument {
                 argument_name: arg.name.to_string(),
-                argument_description: None,
+                argument_description: documentation
+                    .get_input_field_description(input_object_name, arg.name.0.lookup())
+                    .map(|description| description.to_string()),
                 default_value: arg.default_value.as_ref().map(|value| value.to_string()),
                 rendered_type_name: schema.get_type_string(&arg.type_),
                 type_reference,
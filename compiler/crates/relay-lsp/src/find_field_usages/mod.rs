@@ -11,6 +11,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use common::Location as IRLocation;
+use common::SchemaCoordinate;
 use common::WithLocation;
 pub(crate) use find_field_locations::find_field_locations;
 use graphql_ir::FragmentDefinition;
@@ -24,6 +25,7 @@ use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use itertools::Itertools;
 use lsp_types::request::Request;
+use schema::validate_schema_coordinate;
 use schema::FieldID;
 use schema::SDLSchema;
 use schema::Schema;
@@ -70,23 +72,82 @@ impl Request for FindFieldUsages {
     const METHOD: &'static str = "relay/findFieldUsages";
 }
 
+/// Same as `FindFieldUsages`, with the field given as a schema coordinate,
+/// such as `User.avatar`.
+pub struct FindSchemaCoordinateUsages {}
+
+#[derive(Deserialize, Serialize)]
+pub struct FindSchemaCoordinateUsagesParams {
+    pub schema_name: String,
+    pub coordinate: String,
+}
+
+impl Request for FindSchemaCoordinateUsages {
+    type Params = FindSchemaCoordinateUsagesParams;
+    type Result = FindFieldUsagesResult;
+    const METHOD: &'static str = "relay/findSchemaCoordinateUsages";
+}
+
 pub fn on_find_field_usages(
     state: &impl GlobalState,
     params: <FindFieldUsages as Request>::Params,
 ) -> LSPRuntimeResult<<FindFieldUsages as Request>::Result> {
+    find_field_usages(
+        state,
+        params.schema_name.intern(),
+        params.type_name.intern(),
+        params.field_name.intern(),
+    )
+}
+
+pub fn on_find_schema_coordinate_usages(
+    state: &impl GlobalState,
+    params: <FindSchemaCoordinateUsages as Request>::Params,
+) -> LSPRuntimeResult<<FindSchemaCoordinateUsages as Request>::Result> {
     let schema_name = params.schema_name.intern();
-    let type_name = params.type_name.intern();
-    let field_name = params.field_name.intern();
+    let (type_name, field_name) =
+        parse_field_coordinate(&params.coordinate).map_err(LSPRuntimeError::UnexpectedError)?;
+    let coordinate = SchemaCoordinate::Member {
+        type_name,
+        member_name: field_name,
+    };
+    validate_schema_coordinate(state.get_schema(&schema_name)?.as_ref(), coordinate)
+        .map_err(|error| LSPRuntimeError::UnexpectedError(error.to_string()))?;
+    find_field_usages(state, schema_name, type_name, field_name)
+}
 
+/// Parses a field schema coordinate (`Type.field`) into the type and field
+/// names, rejecting the other kinds of schema coordinates.
+pub fn parse_field_coordinate(coordinate: &str) -> Result<(StringKey, StringKey), String> {
+    match coordinate.parse::<SchemaCoordinate>() {
+        Ok(SchemaCoordinate::Member {
+            type_name,
+            member_name,
+        }) => Ok((type_name, member_name)),
+        Ok(_) | Err(_) => Err(format!(
+            "Expected a field coordinate like `User.name`, got `{}`.",
+            coordinate
+        )),
+    }
+}
+
+fn find_field_usages(
+    state: &impl GlobalState,
+    schema_name: StringKey,
+    type_name: StringKey,
+    field_name: StringKey,
+) -> LSPRuntimeResult<FindFieldUsagesResult> {
     let schema = state.get_schema(&schema_name)?;
     let program = state.get_program(&schema_name)?;
     let root_dir = &state.root_dir();
+    let extract_options = &state.get_extract_options();
 
     let ir_locations = get_usages(&program, &schema, type_name, field_name)?;
     let lsp_locations = ir_locations
         .into_iter()
         .map(|(label, ir_location)| {
-            let lsp_location = transform_relay_location_to_lsp_location(root_dir, ir_location)?;
+            let lsp_location =
+                transform_relay_location_to_lsp_location(root_dir, ir_location, extract_options)?;
             Ok(FindFieldUsageResultItem {
                 location_uri: lsp_location.uri.to_string(),
                 location_range: lsp_location.range,
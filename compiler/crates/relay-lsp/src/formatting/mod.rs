@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for formatting the GraphQL in `graphql` tagged templates, leaving
+//! the surrounding JavaScript untouched.
+
+use std::path::Path;
+
+use common::SourceLocationKey;
+use common::Span;
+use common::TextSource;
+use extract_graphql::ExtractOptions;
+use extract_graphql::JavaScriptSourceFeature;
+use graphql_syntax::parse_executable;
+use graphql_text_printer::format_executable_document_ast;
+use graphql_text_printer::FormatOptions;
+use lsp_types::request::RangeFormatting;
+use lsp_types::request::Request;
+use lsp_types::Range;
+use lsp_types::TextEdit;
+
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+
+pub fn on_range_formatting(
+    state: &impl GlobalState,
+    params: <RangeFormatting as Request>::Params,
+) -> LSPRuntimeResult<<RangeFormatting as Request>::Result> {
+    let uri = params.text_document.uri;
+    let document_text = state.get_document_text(&uri)?;
+
+    Ok(Some(get_formatting_edits(
+        &document_text,
+        uri.as_ref(),
+        &state.get_extract_options(),
+        params.range,
+        FormatOptions {
+            indent_width: params.options.tab_size as usize,
+            ..Default::default()
+        },
+    )))
+}
+
+/// Edits formatting each GraphQL template overlapping `range`. Templates that
+/// don't parse, or have comments the printer can't keep, are left as they are.
+fn get_formatting_edits(
+    document_text: &str,
+    path: &str,
+    extract_options: &ExtractOptions,
+    range: Range,
+    options: FormatOptions,
+) -> Vec<TextEdit> {
+    extract_graphql::extract_from_path_with_options(Path::new(path), document_text, extract_options)
+        .iter()
+        .filter_map(|feature| match feature {
+            JavaScriptSourceFeature::GraphQL(source) => {
+                format_template(document_text, source.text_source(), range, options)
+            }
+            JavaScriptSourceFeature::Docblock(_) => None,
+        })
+        .collect()
+}
+
+fn format_template(
+    document_text: &str,
+    text_source: &TextSource,
+    range: Range,
+    options: FormatOptions,
+) -> Option<TextEdit> {
+    let template_range = text_source.to_range();
+    if template_range.end < range.start || range.end < template_range.start {
+        return None;
+    }
+
+    // Only replace the definitions, keeping the whitespace around them
+    // that lines the template up with the backticks
+    let text = &text_source.text;
+    let start = text.len() - text.trim_start().len();
+    let end = text.trim_end().len();
+    if start >= end {
+        return None;
+    }
+    let edit_range = text_source.to_span_range(Span::new(start as u32, end as u32));
+    let indent = document_text
+        .lines()
+        .nth(edit_range.start.line as usize)?
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect::<String>();
+
+    let document = parse_executable(text, SourceLocationKey::generated()).ok()?;
+    let formatted = format_executable_document_ast(
+        &document,
+        text,
+        FormatOptions {
+            max_line_width: options.max_line_width.saturating_sub(indent.len()),
+            ..options
+        },
+    )?;
+    // Strings are printed without escaping, so make sure the result is still
+    // the same GraphQL
+    parse_executable(&formatted, SourceLocationKey::generated()).ok()?;
+    let new_text = formatted
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 || line.is_empty() {
+                line.to_string()
+            } else {
+                format!("{}{}", indent, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if new_text == text[start..end] {
+        None
+    } else {
+        Some(TextEdit {
+            range: edit_range,
+            new_text,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,153 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use extract_graphql::ExtractOptions;
+use graphql_text_printer::FormatOptions;
+use lsp_types::Position;
+use lsp_types::Range;
+
+use super::get_formatting_edits;
+
+const TEXT: &str = r#"const fragment = graphql`
+  fragment Foo_user on User {
+        # The display name
+      name
+  friends(first: 10,
+    orderby: ["name"]) { count }
+  }
+`;
+
+const query = graphql`
+  query FooQuery {
+    me {
+      ...Foo_user
+    }
+  }
+`;
+"#;
+
+const PATH: &str = "/test/Foo.js";
+
+fn whole_document() -> Range {
+    Range::new(Position::new(0, 0), Position::new(17, 0))
+}
+
+#[test]
+fn formats_templates_in_range() {
+    let edits = get_formatting_edits(
+        TEXT,
+        PATH,
+        &ExtractOptions::default(),
+        whole_document(),
+        FormatOptions::default(),
+    );
+    assert_eq!(edits.len(), 1);
+    assert_eq!(
+        edits[0].range,
+        Range::new(Position::new(1, 2), Position::new(6, 3))
+    );
+    assert_eq!(
+        edits[0].new_text,
+        r#"fragment Foo_user on User {
+    # The display name
+    name
+    friends(first: 10, orderby: ["name"]) {
+      count
+    }
+  }"#
+    );
+}
+
+#[test]
+fn formats_with_the_indent_width_of_the_client() {
+    // Only the fragment, the query would be reindented too.
+    let fragment = Range::new(Position::new(0, 0), Position::new(8, 0));
+    let edits = get_formatting_edits(
+        TEXT,
+        PATH,
+        &ExtractOptions::default(),
+        fragment,
+        FormatOptions {
+            indent_width: 4,
+            ..Default::default()
+        },
+    );
+    assert_eq!(edits.len(), 1);
+    assert_eq!(
+        edits[0].new_text,
+        r#"fragment Foo_user on User {
+      # The display name
+      name
+      friends(first: 10, orderby: ["name"]) {
+          count
+      }
+  }"#
+    );
+}
+
+#[test]
+fn skips_templates_outside_of_range() {
+    let range = Range::new(Position::new(9, 0), Position::new(15, 0));
+    assert_eq!(
+        get_formatting_edits(
+            TEXT,
+            PATH,
+            &ExtractOptions::default(),
+            range,
+            FormatOptions::default(),
+        ),
+        vec![]
+    );
+}
+
+#[test]
+fn skips_templates_with_syntax_errors() {
+    let text = "graphql`\n  fragment Foo_user on User {\n      name\n`;\n";
+    assert_eq!(
+        get_formatting_edits(
+            text,
+            PATH,
+            &ExtractOptions::default(),
+            whole_document(),
+            FormatOptions::default(),
+        ),
+        vec![]
+    );
+}
+
+#[test]
+fn skips_templates_with_comments_it_cannot_keep() {
+    let text = "graphql`\n  fragment Foo_user on User {\n      friends(\n  # first\n  first: 1) { count }\n  }\n`;\n";
+    assert_eq!(
+        get_formatting_edits(
+            text,
+            PATH,
+            &ExtractOptions::default(),
+            whole_document(),
+            FormatOptions::default(),
+        ),
+        vec![]
+    );
+}
+
+#[test]
+fn formats_script_blocks_of_vue_components() {
+    let text = "<template>\n  <p>graphql`fragment   Markup on User { name }`</p>\n</template>\n<script>\nconst fragment = graphql`fragment Foo_user on User {   name }`;\n</script>\n";
+    let edits = get_formatting_edits(
+        text,
+        "/test/Foo.vue",
+        &ExtractOptions::default(),
+        whole_document(),
+        FormatOptions::default(),
+    );
+    assert_eq!(edits.len(), 1);
+    assert_eq!(
+        edits[0].range,
+        Range::new(Position::new(4, 25), Position::new(4, 61))
+    );
+    assert_eq!(edits[0].new_text, "fragment Foo_user on User {\n  name\n}");
+}
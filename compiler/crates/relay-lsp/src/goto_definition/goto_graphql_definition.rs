@@ -7,19 +7,39 @@
 
 use std::sync::Arc;
 
+use common::ArgumentName;
+use common::DirectiveName;
 use common::Span;
 use graphql_ir::FragmentDefinitionName;
 use graphql_syntax::ExecutableDocument;
 use intern::string_key::StringKey;
+use resolution_path::ArgumentParent;
+use resolution_path::ArgumentPath;
+use resolution_path::ConstantArgPath;
+use resolution_path::ConstantEnumPath;
+use resolution_path::ConstantListPath;
+use resolution_path::ConstantObjPath;
+use resolution_path::ConstantObjectPath;
+use resolution_path::ConstantValueParent;
+use resolution_path::ConstantValuePath;
+use resolution_path::DefaultValuePath;
+use resolution_path::DirectivePath;
 use resolution_path::IdentParent;
 use resolution_path::IdentPath;
 use resolution_path::LinkedFieldPath;
+use resolution_path::NamedTypeAnnotationPath;
 use resolution_path::ResolutionPath;
 use resolution_path::ResolvePosition;
 use resolution_path::ScalarFieldPath;
 use resolution_path::SelectionParent;
 use resolution_path::TypeConditionPath;
+use resolution_path::ValueListPath;
+use resolution_path::ValueParent;
+use resolution_path::ValuePath;
+use resolution_path::VariableDefinitionPath;
 use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
 
 use super::DefinitionDescription;
 use crate::lsp_runtime_error::LSPRuntimeError;
@@ -64,6 +84,40 @@ pub fn get_graphql_definition_description(
         }) => Ok(DefinitionDescription::Type {
             type_name: type_condition.type_.value,
         }),
+        ResolutionPath::Ident(IdentPath {
+            inner: _,
+            parent:
+                IdentParent::NamedTypeAnnotation(NamedTypeAnnotationPath {
+                    inner: type_annotation,
+                    parent: _,
+                }),
+        }) => Ok(DefinitionDescription::Type {
+            type_name: type_annotation.name.value,
+        }),
+        ResolutionPath::Ident(IdentPath {
+            inner: _,
+            parent:
+                IdentParent::DirectiveName(DirectivePath {
+                    inner: directive,
+                    parent: _,
+                }),
+        }) => Ok(DefinitionDescription::Directive {
+            directive_name: DirectiveName(directive.name.value),
+        }),
+        ResolutionPath::ConstantEnum(ConstantEnumPath {
+            inner: enum_value,
+            parent:
+                ConstantValuePath {
+                    inner: _,
+                    parent: constant_value_parent,
+                },
+        }) => match find_constant_value_type(&constant_value_parent, schema) {
+            Some(Type::Enum(enum_id)) => Ok(DefinitionDescription::EnumValue {
+                enum_id,
+                value: enum_value.value,
+            }),
+            _ => Err(LSPRuntimeError::ExpectedError),
+        },
         _ => Err(LSPRuntimeError::ExpectedError),
     }
 }
@@ -82,3 +136,116 @@ fn resolve_field(
         field_name,
     })
 }
+
+/// The named type of a constant value, from the definition of the argument,
+/// input field or variable it is the value of.
+fn find_constant_value_type(
+    constant_value_parent: &ConstantValueParent<'_>,
+    schema: &SDLSchema,
+) -> Option<Type> {
+    match constant_value_parent {
+        ConstantValueParent::Value(ValuePath {
+            inner: _,
+            parent: value_parent,
+        }) => find_value_type(value_parent, schema),
+        ConstantValueParent::DefaultValue(DefaultValuePath {
+            inner: _,
+            parent:
+                VariableDefinitionPath {
+                    inner: variable_definition,
+                    parent: _,
+                },
+        }) => schema.get_type(variable_definition.type_.inner().name.value),
+        ConstantValueParent::ConstantList(ConstantListPath {
+            inner: _,
+            parent: constant_value_path,
+        }) => find_constant_value_type(&constant_value_path.parent, schema),
+        ConstantValueParent::ConstantObj(_) => None,
+        ConstantValueParent::ConstantArgValue(ConstantArgPath {
+            inner: constant_argument,
+            parent:
+                ConstantObjPath {
+                    inner: _,
+                    parent: constant_value_path,
+                },
+        }) => find_input_field_type(
+            find_constant_value_type(&constant_value_path.parent, schema)?,
+            ArgumentName(constant_argument.name.value),
+            schema,
+        ),
+    }
+}
+
+fn find_value_type(value_parent: &ValueParent<'_>, schema: &SDLSchema) -> Option<Type> {
+    match value_parent {
+        ValueParent::ArgumentValue(argument_path) => find_argument_type(argument_path, schema),
+        ValueParent::ValueList(ValueListPath {
+            inner: _,
+            parent: value_path,
+        }) => find_value_type(&value_path.parent, schema),
+    }
+}
+
+fn find_argument_type(argument_path: &ArgumentPath<'_>, schema: &SDLSchema) -> Option<Type> {
+    let ArgumentPath {
+        inner: argument,
+        parent,
+    } = argument_path;
+    let argument_name = ArgumentName(argument.name.value);
+    let arguments = match parent {
+        ArgumentParent::LinkedField(LinkedFieldPath {
+            inner: field,
+            parent: selection_path,
+        }) => {
+            let parent_type = selection_path.parent.find_parent_type(schema)?;
+            &schema
+                .field(schema.named_field(parent_type, field.name.value)?)
+                .arguments
+        }
+        ArgumentParent::ScalarField(ScalarFieldPath {
+            inner: field,
+            parent: selection_path,
+        }) => {
+            let parent_type = selection_path.parent.find_parent_type(schema)?;
+            &schema
+                .field(schema.named_field(parent_type, field.name.value)?)
+                .arguments
+        }
+        ArgumentParent::Directive(DirectivePath {
+            inner: directive,
+            parent: _,
+        }) => {
+            &schema
+                .get_directive(DirectiveName(directive.name.value))?
+                .arguments
+        }
+        ArgumentParent::ConstantObject(ConstantObjectPath {
+            inner: _,
+            parent: value_path,
+        }) => {
+            return find_input_field_type(
+                find_value_type(&value_path.parent, schema)?,
+                argument_name,
+                schema,
+            );
+        }
+    };
+    arguments
+        .named(argument_name)
+        .map(|argument| argument.type_.inner())
+}
+
+fn find_input_field_type(
+    type_: Type,
+    field_name: ArgumentName,
+    schema: &SDLSchema,
+) -> Option<Type> {
+    match type_ {
+        Type::InputObject(input_object_id) => schema
+            .input_object(input_object_id)
+            .fields
+            .named(field_name)
+            .map(|field| field.type_.inner()),
+        _ => None,
+    }
+}
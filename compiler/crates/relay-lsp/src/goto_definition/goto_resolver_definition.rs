@@ -0,0 +1,116 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::path::Path;
+
+use common::NamedItem;
+use common::Span;
+use common::TextSource;
+use extract_graphql::ExtractOptions;
+use docblock_shared::IMPORT_NAME_ARGUMENT_NAME;
+use docblock_shared::IMPORT_PATH_ARGUMENT_NAME;
+use docblock_shared::RELAY_RESOLVER_DIRECTIVE_NAME;
+use intern::Lookup;
+use lsp_types::GotoDefinitionResponse;
+use lsp_types::Url;
+use schema::Field;
+
+use crate::location::transform_relay_location_to_lsp_location;
+
+/// Locates the definitions of a field backed by a Relay Resolver: the docblock
+/// that defines the field, and the function exported to resolve it. Returns
+/// `None` if the field is not backed by a resolver.
+pub(super) fn locate_resolver_definition(
+    field: &Field,
+    root_dir: &Path,
+    extract_options: &ExtractOptions,
+) -> Option<GotoDefinitionResponse> {
+    let resolver_directive = field.directives.named(*RELAY_RESOLVER_DIRECTIVE_NAME)?;
+    let import_path = resolver_directive
+        .arguments
+        .named(*IMPORT_PATH_ARGUMENT_NAME)?
+        .get_string_literal()?;
+    let import_name = resolver_directive
+        .arguments
+        .named(*IMPORT_NAME_ARGUMENT_NAME)
+        .and_then(|argument| argument.get_string_literal());
+
+    let mut locations = vec![];
+    if let Ok(location) =
+        transform_relay_location_to_lsp_location(root_dir, field.name.location, extract_options)
+    {
+        locations.push(location);
+    }
+    let path = root_dir.join(import_path.lookup());
+    if let Ok(source) = std::fs::read_to_string(&path) {
+        let export_span = find_resolver_export(&source, import_name.map(|name| name.lookup()));
+        if let (Some(span), Ok(uri)) = (export_span, Url::from_file_path(&path)) {
+            let range = TextSource::from_whole_document(source).to_span_range(span);
+            locations.push(lsp_types::Location { uri, range });
+        }
+    }
+
+    match locations.len() {
+        0 => None,
+        1 => locations.pop().map(GotoDefinitionResponse::Scalar),
+        _ => Some(GotoDefinitionResponse::Array(locations)),
+    }
+}
+
+/// The span of the export of a resolver function in its module: the name of
+/// the named export `import_name`, or else the default export.
+pub(super) fn find_resolver_export(source: &str, import_name: Option<&str>) -> Option<Span> {
+    let mut line_start = 0;
+    for line in source.split_inclusive('\n') {
+        let statement = line.trim_start();
+        let statement_start = line_start + line.len() - statement.len();
+        line_start += line.len();
+
+        let (start, len) = match import_name {
+            Some(import_name) => {
+                let declaration = match statement.strip_prefix("export ") {
+                    Some(declaration) => declaration,
+                    None => continue,
+                };
+                match find_declared_name(declaration, import_name) {
+                    Some(offset) => (
+                        statement.len() - declaration.len() + offset,
+                        import_name.len(),
+                    ),
+                    None => continue,
+                }
+            }
+            None => match ["export default", "module.exports"]
+                .iter()
+                .find(|prefix| statement.starts_with(*prefix))
+            {
+                Some(prefix) => (0, prefix.len()),
+                None => continue,
+            },
+        };
+        let start = statement_start + start;
+        return Some(Span::new(start as u32, (start + len) as u32));
+    }
+    None
+}
+
+/// The offset of `name` in a function or variable declaration of it.
+fn find_declared_name(declaration: &str, name: &str) -> Option<usize> {
+    let declaration_without_async = declaration.strip_prefix("async ").unwrap_or(declaration);
+    ["function ", "function* ", "const ", "let ", "var "]
+        .iter()
+        .find_map(|keyword| {
+            let rest = declaration_without_async
+                .strip_prefix(keyword)?
+                .trim_start();
+            let after_name = rest.strip_prefix(name)?;
+            if after_name.starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == '$') {
+                return None;
+            }
+            Some(declaration.len() - rest.len())
+        })
+}
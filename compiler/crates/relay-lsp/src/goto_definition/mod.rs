@@ -9,16 +9,24 @@
 
 mod goto_docblock_definition;
 mod goto_graphql_definition;
+mod goto_resolver_definition;
 use std::str;
 use std::sync::Arc;
 
+use common::DirectiveName;
+use common::Location;
+use extract_graphql::extract_imports;
+use extract_graphql::is_standalone_document;
+use extract_graphql::ExtractOptions;
 use graphql_ir::FragmentDefinitionName;
 use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use lsp_types::request::GotoDefinition;
 use lsp_types::request::Request;
 use lsp_types::GotoDefinitionResponse;
+use lsp_types::TextDocumentPositionParams;
 use lsp_types::Url;
+use schema::EnumID;
 use schema::SDLSchema;
 use schema::Schema;
 use schema::Type;
@@ -27,10 +35,12 @@ use serde::Serialize;
 
 use self::goto_docblock_definition::get_docblock_definition_description;
 use self::goto_graphql_definition::get_graphql_definition_description;
+use self::goto_resolver_definition::locate_resolver_definition;
 use crate::location::transform_relay_location_to_lsp_location;
 use crate::lsp_runtime_error::LSPRuntimeError;
 use crate::lsp_runtime_error::LSPRuntimeResult;
 use crate::server::GlobalState;
+use crate::utils::position_to_offset;
 use crate::FieldDefinitionSourceInfo;
 use crate::FieldSchemaInfo;
 use crate::LSPExtraDataProvider;
@@ -47,6 +57,13 @@ pub enum DefinitionDescription {
     Type {
         type_name: StringKey,
     },
+    EnumValue {
+        enum_id: EnumID,
+        value: StringKey,
+    },
+    Directive {
+        directive_name: DirectiveName,
+    },
 }
 
 /// Resolve a GotoDefinitionRequest to a GotoDefinitionResponse
@@ -54,6 +71,10 @@ pub fn on_goto_definition(
     state: &impl GlobalState,
     params: <GotoDefinition as Request>::Params,
 ) -> LSPRuntimeResult<<GotoDefinition as Request>::Result> {
+    if let Some(location) = locate_import(state, &params.text_document_position_params) {
+        return Ok(Some(GotoDefinitionResponse::Array(vec![location])));
+    }
+
     let (feature, position_span) =
         state.extract_feature_from_text(&params.text_document_position_params, 1)?;
 
@@ -73,6 +94,7 @@ pub fn on_goto_definition(
 
     let extra_data_provider = &*state.get_extra_data_provider();
     let root_dir = state.root_dir();
+    let extract_options = state.get_extract_options();
 
     let goto_definition_response: GotoDefinitionResponse = match definition_description {
         DefinitionDescription::Field {
@@ -85,9 +107,10 @@ pub fn on_goto_definition(
             extra_data_provider,
             project_name,
             &root_dir,
+            &extract_options,
         )?,
         DefinitionDescription::Fragment { fragment_name } => {
-            locate_fragment_definition(program, fragment_name, &root_dir)?
+            locate_fragment_definition(program, fragment_name, &root_dir, &extract_options)?
         }
         DefinitionDescription::Type { type_name } => locate_type_definition(
             extra_data_provider,
@@ -95,7 +118,25 @@ pub fn on_goto_definition(
             type_name,
             &schema,
             &root_dir,
+            &extract_options,
         )?,
+        DefinitionDescription::EnumValue { enum_id, value } => {
+            let location = schema
+                .enum_(enum_id)
+                .values
+                .iter()
+                .find(|enum_value| enum_value.value == value)
+                .map(|enum_value| enum_value.location)
+                .ok_or(LSPRuntimeError::ExpectedError)?;
+            locate_schema_definition(location, &root_dir, &extract_options)?
+        }
+        DefinitionDescription::Directive { directive_name } => {
+            let location = schema
+                .get_directive(directive_name)
+                .map(|directive| directive.location)
+                .ok_or(LSPRuntimeError::ExpectedError)?;
+            locate_schema_definition(location, &root_dir, &extract_options)?
+        }
     };
 
     // For some lsp-clients, such as clients relying on org.eclipse.lsp4j,
@@ -109,10 +150,31 @@ pub fn on_goto_definition(
     Ok(Some(goto_definition_response))
 }
 
+/// The imported document, when the position is on the path of an `#import`
+/// of a standalone document.
+fn locate_import(
+    state: &impl GlobalState,
+    text_document_position: &TextDocumentPositionParams,
+) -> Option<lsp_types::Location> {
+    let uri = &text_document_position.text_document.uri;
+    let path = uri.to_file_path().ok()?;
+    if !is_standalone_document(&path) {
+        return None;
+    }
+    let text = state.get_document_text(uri).ok()?;
+    let offset = position_to_offset(&text_document_position.position, 1, 0, &text)?;
+    let import = extract_imports(&text)
+        .into_iter()
+        .find(|import| import.span.start <= offset && offset <= import.span.end)?;
+    let imported_uri = Url::from_file_path(import.resolve(&path)).ok()?;
+    Some(lsp_types::Location::new(imported_uri, Default::default()))
+}
+
 fn locate_fragment_definition(
     program: graphql_ir::Program,
     fragment_name: FragmentDefinitionName,
     root_dir: &std::path::Path,
+    extract_options: &ExtractOptions,
 ) -> Result<GotoDefinitionResponse, LSPRuntimeError> {
     let fragment = program.fragment(fragment_name).ok_or_else(|| {
         LSPRuntimeError::UnexpectedError(format!(
@@ -121,7 +183,11 @@ fn locate_fragment_definition(
         ))
     })?;
     Ok(GotoDefinitionResponse::Scalar(
-        transform_relay_location_to_lsp_location(root_dir, fragment.name.location)?,
+        transform_relay_location_to_lsp_location(
+            root_dir,
+            fragment.name.location,
+            extract_options,
+        )?,
     ))
 }
 
@@ -131,6 +197,7 @@ fn locate_type_definition(
     type_name: StringKey,
     schema: &Arc<SDLSchema>,
     root_dir: &std::path::Path,
+    extract_options: &ExtractOptions,
 ) -> Result<GotoDefinitionResponse, LSPRuntimeError> {
     let provider_response = extra_data_provider.resolve_field_definition(
         project_name.to_string(),
@@ -166,8 +233,12 @@ fn locate_type_definition(
                     Type::Object(object_id) => schema.object(object_id).name.location,
                 })
                 .map(|schema_location| {
-                    transform_relay_location_to_lsp_location(root_dir, schema_location)
-                        .map(GotoDefinitionResponse::Scalar)
+                    transform_relay_location_to_lsp_location(
+                        root_dir,
+                        schema_location,
+                        extract_options,
+                    )
+                    .map(GotoDefinitionResponse::Scalar)
                 })
                 .ok_or(LSPRuntimeError::ExpectedError)?
         }
@@ -181,10 +252,17 @@ fn locate_field_definition(
     extra_data_provider: &dyn LSPExtraDataProvider,
     project_name: StringKey,
     root_dir: &std::path::Path,
+    extract_options: &ExtractOptions,
 ) -> Result<GotoDefinitionResponse, LSPRuntimeError> {
     let field = schema.field(schema.named_field(parent_type, field_name).ok_or_else(|| {
         LSPRuntimeError::UnexpectedError(format!("Could not find field with name {}", field_name,))
     })?);
+    if let Some(resolver_definition) = locate_resolver_definition(field, root_dir, extract_options)
+    {
+        // Fields backed by Relay Resolvers are defined by their docblock and
+        // resolver function
+        return Ok(resolver_definition);
+    }
     let parent_type = schema.get_type_name(parent_type);
     let provider_response = extra_data_provider.resolve_field_definition(
         project_name.to_string(),
@@ -205,7 +283,7 @@ fn locate_field_definition(
             return Err(LSPRuntimeError::ExpectedError);
         }
     } else if let Ok(location) =
-        transform_relay_location_to_lsp_location(root_dir, field.name.location)
+        transform_relay_location_to_lsp_location(root_dir, field.name.location, extract_options)
     {
         // Step 2: is field a standalone graphql file?
         GotoDefinitionResponse::Scalar(location)
@@ -215,6 +293,20 @@ fn locate_field_definition(
     })
 }
 
+/// Locates a definition in the schema SDL files. The definitions of schemas
+/// that are not read from local files have no location to go to.
+fn locate_schema_definition(
+    location: Location,
+    root_dir: &std::path::Path,
+    extract_options: &ExtractOptions,
+) -> Result<GotoDefinitionResponse, LSPRuntimeError> {
+    if location.source_location().is_generated() {
+        return Err(LSPRuntimeError::ExpectedError);
+    }
+    transform_relay_location_to_lsp_location(root_dir, location, extract_options)
+        .map(GotoDefinitionResponse::Scalar)
+}
+
 fn get_location(path: &str, line: u64) -> Result<lsp_types::Location, LSPRuntimeError> {
     let start = lsp_types::Position {
         line: line as u32,
@@ -311,3 +403,6 @@ fn get_field_definition_source_info_result(
             )
         })
 }
+
+#[cfg(test)]
+mod test;
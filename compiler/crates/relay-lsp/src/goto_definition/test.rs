@@ -0,0 +1,133 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use common::Span;
+use graphql_syntax::parse_executable;
+use relay_test_schema::get_test_schema;
+use schema::Schema;
+
+use super::goto_graphql_definition::get_graphql_definition_description;
+use super::goto_resolver_definition::find_resolver_export;
+use super::DefinitionDescription;
+
+/// The definition to go to from the `|` of the text.
+fn definition(text: &str) -> Option<String> {
+    let position = text.find('|').unwrap() as u32;
+    let source = text.replace('|', "");
+    let document =
+        parse_executable(&source, SourceLocationKey::standalone("/src/Foo.graphql")).unwrap();
+    let schema = get_test_schema();
+    let description =
+        get_graphql_definition_description(document, Span::new(position, position), &schema)
+            .ok()?;
+    Some(match description {
+        DefinitionDescription::Field {
+            parent_type,
+            field_name,
+        } => format!("field {}.{}", schema.get_type_name(parent_type), field_name),
+        DefinitionDescription::Fragment { fragment_name } => format!("fragment {}", fragment_name),
+        DefinitionDescription::Type { type_name } => format!("type {}", type_name),
+        DefinitionDescription::EnumValue { enum_id, value } => {
+            format!("enum value {}.{}", schema.enum_(enum_id).name.item, value)
+        }
+        DefinitionDescription::Directive { directive_name } => {
+            format!("directive @{}", directive_name)
+        }
+    })
+}
+
+#[test]
+fn field_and_type_condition() {
+    assert_eq!(
+        definition("query Foo { me { na|me } }"),
+        Some("field User.name".to_string())
+    );
+    assert_eq!(
+        definition("query Foo { node(id: 4) { ... on Us|er { id } } }"),
+        Some("type User".to_string())
+    );
+}
+
+#[test]
+fn directive() {
+    assert_eq!(
+        definition("query Foo { me { name @customDir|ective(level: 1) } }"),
+        Some("directive @customDirective".to_string())
+    );
+}
+
+#[test]
+fn variable_type() {
+    assert_eq!(
+        definition("query Foo($environment: Environ|ment) { settings(environment: $environment) { cache_id } }"),
+        Some("type Environment".to_string())
+    );
+}
+
+#[test]
+fn enum_values() {
+    assert_eq!(
+        definition("query Foo { settings(environment: MOB|ILE) { cache_id } }"),
+        Some("enum value Environment.MOBILE".to_string())
+    );
+    assert_eq!(
+        definition("query Foo { me { checkins(environments: [W|EB]) { __typename } } }"),
+        Some("enum value Environment.WEB".to_string())
+    );
+    assert_eq!(
+        definition("query Foo { me { storySearch(query: {type: DIRE|CTED}) { id } } }"),
+        Some("enum value StoryType.DIRECTED".to_string())
+    );
+    assert_eq!(
+        definition("query Foo($environment: Environment = W|EB) { settings(environment: $environment) { cache_id } }"),
+        Some("enum value Environment.WEB".to_string())
+    );
+}
+
+#[test]
+fn enum_value_of_unknown_argument() {
+    assert_eq!(
+        definition("query Foo { settings(unknown: MOB|ILE) { cache_id } }"),
+        None
+    );
+}
+
+const RESOLVER_SOURCE: &str = r#"/**
+ * @RelayResolver User.greeting: String
+ */
+export function greeting(): string {
+  return 'Hello';
+}
+
+export const greetingCount = 1;
+
+export default function defaultGreeting(): string {
+  return 'Hi';
+}
+"#;
+
+/// The text of the span of the resolver export.
+fn resolver_export(import_name: Option<&str>) -> Option<&'static str> {
+    find_resolver_export(RESOLVER_SOURCE, import_name)
+        .map(|span| &RESOLVER_SOURCE[span.start as usize..span.end as usize])
+}
+
+#[test]
+fn resolver_named_export() {
+    assert_eq!(resolver_export(Some("greeting")), Some("greeting"));
+    assert_eq!(
+        resolver_export(Some("greetingCount")),
+        Some("greetingCount")
+    );
+    assert_eq!(resolver_export(Some("greet")), None);
+}
+
+#[test]
+fn resolver_default_export() {
+    assert_eq!(resolver_export(None), Some("export default"));
+}
@@ -22,6 +22,7 @@ use graphql_ir::Program;
 use graphql_ir::Selection;
 use graphql_syntax::parse_executable_with_error_recovery;
 use graphql_text_printer::print_full_operation;
+use graphql_text_printer::print_operation_with_inlined_fragments;
 use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use lsp_types::request::Request;
@@ -66,6 +67,16 @@ impl GraphQLExecuteQueryParams {
             "facebook".intern()
         }
     }
+
+    fn get_project_name(&self, state: &impl GlobalState) -> StringKey {
+        if let Some(url) = &self.get_url() {
+            state
+                .extract_project_name_from_url(url)
+                .unwrap_or_else(|_| self.get_schema_name())
+        } else {
+            self.get_schema_name()
+        }
+    }
 }
 
 impl Request for GraphQLExecuteQuery {
@@ -74,17 +85,30 @@ impl Request for GraphQLExecuteQuery {
     const METHOD: &'static str = "graphql/executeQuery";
 }
 
-/// This function will return the program that contains only operation
-/// and all referenced fragments.
+/// Prints the operation in `text` with the fragments it spreads inlined, as
+/// the server receives it, for debugging and review.
+pub(crate) enum PrintInlinedOperation {}
+
+impl Request for PrintInlinedOperation {
+    type Params = GraphQLExecuteQueryParams;
+    type Result = String;
+    const METHOD: &'static str = "relay/printInlinedOperation";
+}
+
+/// This function will return the program that contains only the operations,
+/// the fragments, and all fragments they reference from the `program`.
 /// We can use it to print the full query text
-fn get_operation_only_program(
-    operation: Arc<OperationDefinition>,
+pub(crate) fn get_program_with_referenced_fragments(
+    operations: Vec<Arc<OperationDefinition>>,
     fragments: Vec<Arc<FragmentDefinition>>,
     program: &Program,
 ) -> Option<Program> {
-    let mut selections_to_visit: Vec<_> = vec![&operation.selections];
+    let mut selections_to_visit: Vec<_> = vec![];
     let mut next_program = Program::new(program.schema.clone());
-    next_program.insert_operation(Arc::clone(&operation));
+    for operation in operations.iter() {
+        selections_to_visit.push(&operation.selections);
+        next_program.insert_operation(Arc::clone(operation));
+    }
     for fragment in fragments.iter() {
         selections_to_visit.push(&fragment.selections);
         next_program.insert_fragment(Arc::clone(fragment));
@@ -147,20 +171,33 @@ fn transform_program<TPerfLogger: PerfLogger + 'static>(
         perf_logger,
         None,
         custom_transforms_config,
+        None,
     )
     .map_err(|errors| format!("{:?}", errors))
 }
 
-fn print_full_operation_text(programs: Programs, operation_name: StringKey) -> Option<String> {
+fn print_full_operation_text(
+    programs: Programs,
+    operation_name: StringKey,
+    inline_fragments: bool,
+) -> Option<String> {
     let print_operation_node = programs
         .operation_text
         .operation(OperationDefinitionName(operation_name))?;
 
-    Some(print_full_operation(
-        &programs.operation_text,
-        print_operation_node,
-        Default::default(),
-    ))
+    Some(if inline_fragments {
+        print_operation_with_inlined_fragments(
+            &programs.operation_text,
+            print_operation_node,
+            Default::default(),
+        )
+    } else {
+        print_full_operation(
+            &programs.operation_text,
+            print_operation_node,
+            Default::default(),
+        )
+    })
 }
 
 /// From the list of AST nodes we're trying to extract the operation and possible
@@ -213,11 +250,12 @@ pub(crate) fn get_query_text<
     state: &LSPState<TPerfLogger, TSchemaDocumentation>,
     original_text: String,
     project_name: &StringKey,
+    inline_fragments: bool,
 ) -> LSPRuntimeResult<String> {
     let schema = state.get_schema(project_name)?;
 
-    let project_config = state
-        .config
+    let config = state.config();
+    let project_config = config
         .enabled_projects()
         .find(|project_config| &project_config.name == project_name)
         .ok_or_else(|| {
@@ -246,20 +284,22 @@ pub(crate) fn get_query_text<
     let operation_name = operation.name.item.0;
     let program = state.get_program(project_name)?;
 
-    let query_text =
-        if let Some(program) = get_operation_only_program(operation, fragments, &program) {
-            let programs = transform_program(
-                project_config,
-                Arc::new(program),
-                Arc::clone(&state.perf_logger),
-                state.config.custom_transforms.as_ref(),
-            )
-            .map_err(LSPRuntimeError::UnexpectedError)?;
+    let query_text = if let Some(program) =
+        get_program_with_referenced_fragments(vec![operation], fragments, &program)
+    {
+        let programs = transform_program(
+            project_config,
+            Arc::new(program),
+            Arc::clone(&state.perf_logger),
+            config.custom_transforms.as_ref(),
+        )
+        .map_err(LSPRuntimeError::UnexpectedError)?;
 
-            print_full_operation_text(programs, operation_name).unwrap_or(original_text)
-        } else {
-            original_text
-        };
+        print_full_operation_text(programs, operation_name, inline_fragments)
+            .unwrap_or(original_text)
+    } else {
+        original_text
+    };
 
     Ok(query_text)
 }
@@ -268,13 +308,14 @@ pub(crate) fn on_graphql_execute_query(
     state: &impl GlobalState,
     params: GraphQLExecuteQueryParams,
 ) -> LSPRuntimeResult<<GraphQLExecuteQuery as Request>::Result> {
-    let project_name = if let Some(url) = &params.get_url() {
-        state
-            .extract_project_name_from_url(url)
-            .unwrap_or_else(|_| params.get_schema_name())
-    } else {
-        params.get_schema_name()
-    };
-
+    let project_name = params.get_project_name(state);
     state.get_full_query_text(params.text, &project_name)
 }
+
+pub(crate) fn on_print_inlined_operation(
+    state: &impl GlobalState,
+    params: GraphQLExecuteQueryParams,
+) -> LSPRuntimeResult<<PrintInlinedOperation as Request>::Result> {
+    let project_name = params.get_project_name(state);
+    state.get_inlined_query_text(params.text, &project_name)
+}
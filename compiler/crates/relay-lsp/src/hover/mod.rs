@@ -86,6 +86,7 @@ pub fn on_hover(
         &*state.get_extra_data_provider(),
         &schema_documentation,
         &state.get_program(&project_name)?,
+        &state.root_dir(),
         state.get_content_consumer_type(),
     ))
 }
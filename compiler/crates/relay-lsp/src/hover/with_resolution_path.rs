@@ -5,12 +5,17 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::path::Path;
+
+use common::ArgumentName;
 use common::DirectiveName;
 use common::NamedItem;
+use docblock_shared::IMPORT_PATH_ARGUMENT_NAME;
 use docblock_shared::RELAY_RESOLVER_DIRECTIVE_NAME;
 use graphql_ir::FragmentDefinitionName;
 use graphql_ir::Program;
 use graphql_ir::Value;
+use graphql_syntax::ConstantValue;
 use graphql_syntax::FragmentDefinition;
 use graphql_syntax::Identifier;
 use graphql_syntax::OperationDefinition;
@@ -22,6 +27,7 @@ use intern::Lookup;
 use lsp_types::Hover;
 use lsp_types::HoverContents;
 use lsp_types::MarkedString;
+use lsp_types::Url;
 use resolution_path::ArgumentPath;
 use resolution_path::ArgumentRoot;
 use resolution_path::ConstantArgPath;
@@ -63,10 +69,12 @@ use resolution_path::VariableIdentifierParent;
 use resolution_path::VariableIdentifierPath;
 use schema::SDLSchema;
 use schema::Schema;
+use schema::Type;
 use schema_documentation::SchemaDocumentation;
 use schema_print::print_directive;
 
 use crate::hover::get_open_schema_explorer_command_link;
+use crate::hover::graphql_marked_string;
 use crate::hover::GraphQLSchemaExplorerParams;
 use crate::LSPExtraDataProvider;
 
@@ -92,6 +100,7 @@ impl ContentConsumerType {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn get_hover<'a>(
     path: &'a ResolutionPath<'a>,
     schema: &SDLSchema,
@@ -99,6 +108,7 @@ pub fn get_hover<'a>(
     extra_data_provider: &dyn LSPExtraDataProvider,
     schema_documentation: &impl SchemaDocumentation,
     program: &Program,
+    root_dir: &Path,
     content_consumer_type: ContentConsumerType,
 ) -> Option<Hover> {
     let hover_behavior = get_hover_behavior_from_resolution_path(path);
@@ -109,6 +119,7 @@ pub fn get_hover<'a>(
         extra_data_provider,
         schema_documentation,
         program,
+        root_dir,
         content_consumer_type,
     );
 
@@ -431,6 +442,7 @@ fn get_hover_behavior_from_resolution_path<'a>(path: &'a ResolutionPath<'a>) ->
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn get_hover_contents<'a>(
     hover_behavior: HoverBehavior<'a>,
     schema: &SDLSchema,
@@ -438,6 +450,7 @@ fn get_hover_contents<'a>(
     extra_data_provider: &dyn LSPExtraDataProvider,
     schema_documentation: &impl SchemaDocumentation,
     program: &Program,
+    root_dir: &Path,
     content_consumer_type: ContentConsumerType,
 ) -> Option<HoverContents> {
     match hover_behavior {
@@ -454,6 +467,7 @@ fn get_hover_contents<'a>(
             schema,
             schema_name,
             schema_documentation,
+            root_dir,
             content_consumer_type,
         ),
         HoverBehavior::ScalarOrLinkedField(field_name, selection_path) => {
@@ -463,6 +477,7 @@ fn get_hover_contents<'a>(
                 schema,
                 schema_name,
                 schema_documentation,
+                root_dir,
                 content_consumer_type,
             )
         }
@@ -471,6 +486,7 @@ fn get_hover_contents<'a>(
             schema,
             schema_name,
             schema_documentation,
+            root_dir,
             content_consumer_type,
         ),
         HoverBehavior::InlineFragment(inline_fragment_path) => on_hover_inline_fragment(
@@ -553,6 +569,7 @@ fn on_hover_constant_value<'a>(
     schema: &SDLSchema,
     schema_name: StringKey,
     schema_documentation: &impl SchemaDocumentation,
+    root_dir: &Path,
     content_consumer_type: ContentConsumerType,
 ) -> Option<HoverContents> {
     match constant_value_parent.find_constant_value_root() {
@@ -568,6 +585,7 @@ fn on_hover_constant_value<'a>(
             schema,
             schema_name,
             schema_documentation,
+            root_dir,
             content_consumer_type,
         ),
     }
@@ -578,6 +596,7 @@ fn on_hover_argument_path<'a>(
     schema: &SDLSchema,
     schema_name: StringKey,
     schema_documentation: &impl SchemaDocumentation,
+    root_dir: &Path,
     content_consumer_type: ContentConsumerType,
 ) -> Option<HoverContents> {
     let ArgumentPath {
@@ -597,6 +616,7 @@ fn on_hover_argument_path<'a>(
             schema,
             schema_name,
             schema_documentation,
+            root_dir,
             content_consumer_type,
         ),
         ArgumentRoot::Directive(directive_path) => {
@@ -608,22 +628,83 @@ fn on_hover_argument_path<'a>(
             schema,
             schema_name,
             schema_documentation,
+            root_dir,
             content_consumer_type,
         ),
     }?;
 
     let mut contents = vec![argument_info];
+    if let Some(description) =
+        get_enum_argument_value_description(argument_path, schema, schema_documentation)
+    {
+        contents.push(MarkedString::String(description.to_string()));
+    }
     contents.extend(field_hover_info.into_iter());
 
     Some(HoverContents::Array(contents))
 }
 
+/// The description of the enum value an argument is set to, if the argument
+/// has an enum type.
+fn get_enum_argument_value_description<'a>(
+    argument_path: &ArgumentPath<'_>,
+    schema: &SDLSchema,
+    schema_documentation: &'a impl SchemaDocumentation,
+) -> Option<&'a str> {
+    let ArgumentPath {
+        inner: argument,
+        parent,
+    } = argument_path;
+    let enum_value = match &argument.value {
+        graphql_syntax::Value::Constant(ConstantValue::Enum(enum_node)) => enum_node.value,
+        _ => return None,
+    };
+    let argument_name = ArgumentName(argument.name.value);
+    let argument_type = match parent.find_argument_root() {
+        ArgumentRoot::LinkedField(LinkedFieldPath {
+            inner: field,
+            parent,
+        }) => field_argument_type(&field.name, parent, argument_name, schema),
+        ArgumentRoot::ScalarField(ScalarFieldPath {
+            inner: field,
+            parent,
+        }) => field_argument_type(&field.name, parent, argument_name, schema),
+        ArgumentRoot::Directive(directive_path) => schema
+            .get_directive(DirectiveName(directive_path.inner.name.value))?
+            .arguments
+            .named(argument_name)
+            .map(|argument| argument.type_.inner()),
+    }?;
+    match argument_type {
+        Type::Enum(_) => schema_documentation.get_enum_value_description(
+            schema.get_type_name(argument_type).lookup(),
+            enum_value.lookup(),
+        ),
+        _ => None,
+    }
+}
+
+fn field_argument_type(
+    field_name: &Identifier,
+    field_selection_path: &SelectionPath<'_>,
+    argument_name: ArgumentName,
+    schema: &SDLSchema,
+) -> Option<Type> {
+    let parent_type = *field_selection_path.parent.find_type_path(schema).last()?;
+    let field = schema.field(schema.named_field(parent_type, field_name.value)?);
+    field
+        .arguments
+        .named(argument_name)
+        .map(|argument| argument.type_.inner())
+}
+
 fn on_hover_scalar_or_linked_field(
     field_name: &Identifier,
     field_selection_path: &SelectionPath<'_>,
     schema: &SDLSchema,
     schema_name: StringKey,
     schema_documentation: &impl SchemaDocumentation,
+    root_dir: &Path,
     content_consumer_type: ContentConsumerType,
 ) -> Option<HoverContents> {
     let content = get_scalar_or_linked_field_hover_content(
@@ -632,6 +713,7 @@ fn on_hover_scalar_or_linked_field(
         schema,
         schema_name,
         schema_documentation,
+        root_dir,
         content_consumer_type,
     )?;
     Some(HoverContents::Array(content))
@@ -643,6 +725,7 @@ fn get_scalar_or_linked_field_hover_content(
     schema: &SDLSchema,
     schema_name: StringKey,
     schema_documentation: &impl SchemaDocumentation,
+    root_dir: &Path,
     content_consumer_type: ContentConsumerType,
 ) -> Option<Vec<MarkedString>> {
     let parent_types = field_selection_path.parent.find_type_path(schema);
@@ -670,6 +753,12 @@ fn get_scalar_or_linked_field_hover_content(
         field.name.item
     ))];
 
+    if let Some(deprecation) = field.deprecated() {
+        hover_contents.push(MarkedString::String(deprecation_message(
+            deprecation.reason,
+        )));
+    }
+
     if let Some(field_description) =
         schema_documentation.get_field_description(parent_type_name, field.name.item.lookup())
     {
@@ -703,15 +792,38 @@ fn get_scalar_or_linked_field_hover_content(
         hover_contents.push(MarkedString::String(type_description.to_string()));
     }
 
+    if let Type::Scalar(scalar_id) = field.type_.inner() {
+        if let Some(url) = schema.scalar(scalar_id).specified_by_url() {
+            hover_contents.push(MarkedString::String(format!("Specified by: {}", url)));
+        }
+    }
+
     if !field.arguments.is_empty() {
-        hover_contents.push(MarkedString::String(
-            "This field accepts these arguments".to_string(),
-        ));
+        hover_contents.push(graphql_marked_string(format!(
+            "{}({}): {}",
+            field.name.item,
+            field
+                .arguments
+                .iter()
+                .map(|arg| format!(
+                    "{}: {}{}",
+                    arg.name,
+                    schema.get_type_string(&arg.type_),
+                    if let Some(default_value) = &arg.default_value {
+                        format!(" = {}", default_value)
+                    } else {
+                        "".to_string()
+                    }
+                ))
+                .collect::<Vec<_>>()
+                .join(", "),
+            schema.get_type_string(&field.type_),
+        )));
 
         for arg in field.arguments.iter() {
             let arg_type_name = schema.get_type_name(arg.type_.inner()).lookup();
-            hover_contents.push(MarkedString::from_markdown(format!(
-                "{}: **{}**{}\n\n{}",
+            let mut arg_content = format!(
+                "`{}`: **{}**",
                 arg.name,
                 content_consumer_type.render_text_with_params(
                     &schema.get_type_string(&arg.type_),
@@ -721,27 +833,37 @@ fn get_scalar_or_linked_field_hover_content(
                         filter: None,
                     }
                 ),
-                if let Some(default_value) = &arg.default_value {
-                    format!(" = {}", default_value)
-                } else {
-                    "".to_string()
-                },
-                if let Some(description) = schema_documentation.get_field_argument_description(
-                    parent_type_name,
-                    field.name.item.lookup(),
-                    arg.name.0.lookup(),
-                ) {
-                    description.to_string()
-                } else {
-                    "".to_string()
-                }
-            )));
+            );
+            if let Some(deprecation) = arg.deprecated() {
+                arg_content.push_str(&format!("\n\n{}", deprecation_message(deprecation.reason)));
+            }
+            if let Some(description) = schema_documentation.get_field_argument_description(
+                parent_type_name,
+                field.name.item.lookup(),
+                arg.name.0.lookup(),
+            ) {
+                arg_content.push_str(&format!("\n\n{}", description));
+            }
+            hover_contents.push(MarkedString::String(arg_content));
         }
     }
 
     if is_resolver {
         let msg = "**Relay Resolver**: This field is backed by a Relay Resolver, and is therefore only avaliable in Relay code. [Learn More](https://relay.dev/docs/next/guides/relay-resolvers/).";
-        hover_contents.push(MarkedString::String(msg.to_string()))
+        hover_contents.push(MarkedString::String(msg.to_string()));
+        if let Some(import_path) = field
+            .directives
+            .named(*RELAY_RESOLVER_DIRECTIVE_NAME)
+            .and_then(|directive| directive.arguments.named(*IMPORT_PATH_ARGUMENT_NAME))
+            .and_then(|import_path| import_path.get_string_literal())
+        {
+            hover_contents.push(MarkedString::String(
+                match Url::from_file_path(root_dir.join(import_path.lookup())) {
+                    Ok(url) => format!("Resolver source: [`{}`]({})", import_path, url),
+                    Err(_) => format!("Resolver source: `{}`", import_path),
+                },
+            ));
+        }
     } else if field.is_extension {
         let msg = match content_consumer_type {
             ContentConsumerType::Relay => {
@@ -767,6 +889,13 @@ fn get_scalar_or_linked_field_hover_content(
     Some(hover_contents)
 }
 
+fn deprecation_message(reason: Option<StringKey>) -> String {
+    match reason {
+        Some(reason) => format!("**Deprecated**: {}", reason),
+        None => "**Deprecated**".to_string(),
+    }
+}
+
 fn on_hover_inline_fragment(
     inline_fragment: &InlineFragmentPath<'_>,
     schema: &SDLSchema,
@@ -0,0 +1,230 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing inlay hints: the type of each selected field, and
+//! the type that inline fragments without a type condition apply to.
+
+use std::path::Path;
+
+use common::SourceLocationKey;
+use common::Span;
+use common::TextSource;
+use extract_graphql::ExtractOptions;
+use extract_graphql::JavaScriptSourceFeature;
+use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::OperationKind;
+use graphql_syntax::Selection;
+use intern::string_key::StringKey;
+use lsp_types::request::InlayHintRequest;
+use lsp_types::request::Request;
+use lsp_types::InlayHint;
+use lsp_types::InlayHintKind;
+use lsp_types::InlayHintLabel;
+use lsp_types::Range;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+use serde::Deserialize;
+
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+
+/// Which inlay hints to show, from the `inlayHints` initialization option.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InlayHintsConfig {
+    /// Show the type of fields after their name and arguments
+    pub field_types: bool,
+    /// Show the parent type of inline fragments without a type condition
+    pub type_conditions: bool,
+}
+
+impl Default for InlayHintsConfig {
+    fn default() -> Self {
+        Self {
+            field_types: true,
+            type_conditions: true,
+        }
+    }
+}
+
+pub fn on_inlay_hint(
+    state: &impl GlobalState,
+    params: <InlayHintRequest as Request>::Params,
+) -> LSPRuntimeResult<<InlayHintRequest as Request>::Result> {
+    let uri = params.text_document.uri;
+    let config = state.get_inlay_hints_config();
+    if !config.field_types && !config.type_conditions {
+        return Ok(None);
+    }
+    let document_text = state.get_document_text(&uri)?;
+    let project_name = state.extract_project_name_from_url(&uri)?;
+    let schema = state.get_schema(&project_name)?;
+
+    Ok(Some(get_inlay_hints(
+        &document_text,
+        &schema,
+        uri.as_ref(),
+        &state.get_extract_options(),
+        params.range,
+        config,
+    )))
+}
+
+fn get_inlay_hints(
+    document_text: &str,
+    schema: &SDLSchema,
+    path: &str,
+    extract_options: &ExtractOptions,
+    range: Range,
+    config: InlayHintsConfig,
+) -> Vec<InlayHint> {
+    let mut hints = vec![];
+    for (index, feature) in extract_graphql::extract_from_path_with_options(
+        Path::new(path),
+        document_text,
+        extract_options,
+    )
+    .into_iter()
+    .enumerate()
+    {
+        let source = match feature {
+            JavaScriptSourceFeature::GraphQL(source) => source,
+            JavaScriptSourceFeature::Docblock(_) => continue,
+        };
+        let text_source = source.text_source();
+        let source_range = text_source.to_range();
+        if source_range.end < range.start || range.end < source_range.start {
+            continue;
+        }
+        let document = parse_executable_with_error_recovery(
+            &text_source.text,
+            SourceLocationKey::embedded(path, index),
+        )
+        .item;
+        let mut collector = InlayHintCollector {
+            schema,
+            text_source,
+            config,
+            hints: vec![],
+        };
+        for definition in &document.definitions {
+            let (parent_type, selections) = match definition {
+                ExecutableDefinition::Operation(operation) => (
+                    match operation.operation_kind() {
+                        OperationKind::Query => schema.query_type(),
+                        OperationKind::Mutation => schema.mutation_type(),
+                        OperationKind::Subscription => schema.subscription_type(),
+                    },
+                    &operation.selections,
+                ),
+                ExecutableDefinition::Fragment(fragment) => (
+                    schema.get_type(fragment.type_condition.type_.value),
+                    &fragment.selections,
+                ),
+            };
+            if let Some(parent_type) = parent_type {
+                collector.collect_selections(parent_type, &selections.items);
+            }
+        }
+        hints.extend(
+            collector
+                .hints
+                .into_iter()
+                .filter(|hint| range.start <= hint.position && hint.position <= range.end),
+        );
+    }
+    hints
+}
+
+struct InlayHintCollector<'a> {
+    schema: &'a SDLSchema,
+    text_source: &'a TextSource,
+    config: InlayHintsConfig,
+    hints: Vec<InlayHint>,
+}
+
+impl InlayHintCollector<'_> {
+    fn collect_selections(&mut self, parent_type: Type, selections: &[Selection]) {
+        for selection in selections {
+            match selection {
+                Selection::ScalarField(field) => {
+                    let end = field
+                        .arguments
+                        .as_ref()
+                        .map_or(field.name.span.end, |arguments| arguments.span.end);
+                    self.add_field_type_hint(parent_type, field.name.value, end);
+                }
+                Selection::LinkedField(field) => {
+                    let end = field
+                        .arguments
+                        .as_ref()
+                        .map_or(field.name.span.end, |arguments| arguments.span.end);
+                    if let Some(field_type) =
+                        self.add_field_type_hint(parent_type, field.name.value, end)
+                    {
+                        self.collect_selections(field_type, &field.selections.items);
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    let type_ = match &fragment.type_condition {
+                        Some(type_condition) => self.schema.get_type(type_condition.type_.value),
+                        None => {
+                            if self.config.type_conditions {
+                                let label =
+                                    format!(" on {}", self.schema.get_type_name(parent_type));
+                                self.add_hint(fragment.spread.span.end, label);
+                            }
+                            Some(parent_type)
+                        }
+                    };
+                    if let Some(type_) = type_ {
+                        self.collect_selections(type_, &fragment.selections.items);
+                    }
+                }
+                Selection::FragmentSpread(_) => {}
+            }
+        }
+    }
+
+    /// Adds the type of the field at `offset`, and returns the type of the
+    /// selections of the field.
+    fn add_field_type_hint(
+        &mut self,
+        parent_type: Type,
+        field_name: StringKey,
+        offset: u32,
+    ) -> Option<Type> {
+        let field_id = self.schema.named_field(parent_type, field_name)?;
+        let field = self.schema.field(field_id);
+        if self.config.field_types {
+            let label = format!(": {}", self.schema.get_type_string(&field.type_));
+            self.add_hint(offset, label);
+        }
+        Some(field.type_.inner())
+    }
+
+    fn add_hint(&mut self, offset: u32, label: String) {
+        self.hints.push(InlayHint {
+            position: self
+                .text_source
+                .to_span_range(Span::new(offset, offset))
+                .start,
+            label: InlayHintLabel::String(label),
+            kind: Some(InlayHintKind::TYPE),
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: None,
+            data: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,80 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use extract_graphql::ExtractOptions;
+use lsp_types::InlayHintLabel;
+use lsp_types::Position;
+use lsp_types::Range;
+use relay_test_schema::get_test_schema;
+
+use super::get_inlay_hints;
+use super::InlayHintsConfig;
+
+const TEXT: &str = r#"const query = graphql`
+  query FooQuery {
+    me {
+      name
+      profilePicture(size: 32) {
+        uri
+      }
+      ... @include(if: true) {
+        id
+      }
+    }
+  }
+`;
+"#;
+
+fn get_hints(range: Range, config: InlayHintsConfig) -> Vec<(u32, u32, String)> {
+    get_inlay_hints(
+        TEXT,
+        &get_test_schema(),
+        "/src/Foo.js",
+        &ExtractOptions::default(),
+        range,
+        config,
+    )
+    .into_iter()
+    .map(|hint| match hint.label {
+        InlayHintLabel::String(label) => (hint.position.line, hint.position.character, label),
+        InlayHintLabel::LabelParts(_) => panic!("Expected a string label"),
+    })
+    .collect()
+}
+
+fn whole_document() -> Range {
+    Range::new(Position::new(0, 0), Position::new(13, 0))
+}
+
+#[test]
+fn shows_field_types_and_type_conditions() {
+    assert_eq!(
+        get_hints(whole_document(), InlayHintsConfig::default()),
+        vec![
+            (2, 6, ": User".to_string()),
+            (3, 10, ": String".to_string()),
+            (4, 30, ": Image".to_string()),
+            (5, 11, ": String".to_string()),
+            (7, 9, " on User".to_string()),
+            (8, 10, ": ID!".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn respects_range_and_config() {
+    assert_eq!(
+        get_hints(
+            Range::new(Position::new(6, 0), Position::new(9, 0)),
+            InlayHintsConfig {
+                field_types: false,
+                type_conditions: true,
+            }
+        ),
+        vec![(7, 9, " on User".to_string())]
+    );
+}
@@ -9,14 +9,19 @@
 
 mod client;
 mod code_action;
+pub mod code_lens;
+mod compiled_output;
 pub mod completion;
 pub mod diagnostic_reporter;
 mod docblock_resolution_info;
+pub mod document_symbols;
 mod explore_schema_for_type;
 pub mod find_field_usages;
+pub mod formatting;
 pub mod goto_definition;
 mod graphql_tools;
 pub mod hover;
+pub mod inlay_hints;
 pub mod js_language_server;
 pub mod location;
 mod lsp_extra_data_provider;
@@ -24,10 +29,14 @@ pub mod lsp_process_error;
 pub mod lsp_runtime_error;
 pub mod node_resolution_info;
 pub mod references;
+mod rename;
 mod resolved_types_at_location;
+mod schema_tree;
 mod search_schema_items;
+pub mod semantic_tokens;
 mod server;
 mod shutdown;
+pub mod signature_help;
 mod status_reporter;
 pub mod status_updater;
 pub mod text_documents;
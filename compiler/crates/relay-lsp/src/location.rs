@@ -11,6 +11,7 @@ use std::path::PathBuf;
 use common::Location;
 use common::SourceLocationKey;
 use common::TextSource;
+use extract_graphql::ExtractOptions;
 use intern::Lookup;
 use lsp_types::Url;
 
@@ -22,6 +23,7 @@ use crate::lsp_runtime_error::LSPRuntimeResult;
 pub fn transform_relay_location_to_lsp_location(
     root_dir: &Path,
     location: Location,
+    extract_options: &ExtractOptions,
 ) -> LSPRuntimeResult<lsp_types::Location> {
     match location.source_location() {
         SourceLocationKey::Standalone { path } => {
@@ -41,7 +43,11 @@ pub fn transform_relay_location_to_lsp_location(
 
             let file_contents = get_file_contents(&path_to_fragment)?;
 
-            let response = extract_graphql::extract(&file_contents);
+            let response = extract_graphql::extract_from_path_with_options(
+                &path_to_fragment,
+                &file_contents,
+                extract_options,
+            );
             let response_length = response.len();
             let embedded_source = response
                 .into_iter()
@@ -0,0 +1,48 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::Location as IRLocation;
+use fnv::FnvHashMap;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::FragmentSpread;
+use graphql_ir::Program;
+use graphql_ir::Visitor;
+
+/// Locations of every fragment spread in a project's source program, keyed by
+/// the name of the spread fragment. The LSP rebuilds it whenever the source
+/// program of the project changes, so requests don't need to walk the program.
+#[derive(Debug, Default)]
+pub struct FragmentSpreadIndex {
+    spreads: FnvHashMap<FragmentDefinitionName, Vec<IRLocation>>,
+}
+
+impl FragmentSpreadIndex {
+    pub fn from_program(program: &Program) -> Self {
+        let mut index = Self::default();
+        index.visit_program(program);
+        index
+    }
+
+    pub fn get(&self, fragment_name: FragmentDefinitionName) -> &[IRLocation] {
+        self.spreads
+            .get(&fragment_name)
+            .map_or(&[], |locations| locations.as_slice())
+    }
+}
+
+impl Visitor for FragmentSpreadIndex {
+    const NAME: &'static str = "FragmentSpreadIndex";
+    const VISIT_ARGUMENTS: bool = false;
+    const VISIT_DIRECTIVES: bool = false;
+
+    fn visit_fragment_spread(&mut self, spread: &FragmentSpread) {
+        self.spreads
+            .entry(spread.fragment.item)
+            .or_default()
+            .push(spread.fragment.location);
+    }
+}
@@ -7,13 +7,11 @@
 
 //! Utilities for providing the goto definition feature
 
-use std::path::Path;
+mod fragment_spread_index;
 
-use common::Location as IRLocation;
+pub use fragment_spread_index::FragmentSpreadIndex;
 use graphql_ir::FragmentDefinitionName;
-use graphql_ir::FragmentSpread;
 use graphql_ir::Program;
-use graphql_ir::Visitor;
 use intern::string_key::StringKey;
 use lsp_types::request::References;
 use lsp_types::request::Request;
@@ -34,23 +32,30 @@ use crate::FeatureResolutionInfo;
 
 fn get_references_response(
     feature_resolution_info: FeatureResolutionInfo,
-    program: &Program,
-    root_dir: &Path,
+    state: &impl GlobalState,
+    project_name: StringKey,
+    include_declaration: bool,
 ) -> LSPRuntimeResult<Vec<LSPLocation>> {
+    let program = &state.get_program(&project_name)?;
+    let root_dir = &state.root_dir();
+    let extract_options = &state.get_extract_options();
     match feature_resolution_info {
         FeatureResolutionInfo::GraphqlNode(node_resolution_info) => {
             match node_resolution_info.kind {
-                NodeKind::FragmentDefinition(fragment) => {
-                    let references =
-                        ReferenceFinder::get_references_to_fragment(program, fragment.name.value)
-                            .into_iter()
-                            .map(|location| {
-                                transform_relay_location_to_lsp_location(root_dir, location)
-                            })
-                            .collect::<Result<Vec<_>, LSPRuntimeError>>()?;
-
-                    Ok(references)
-                }
+                NodeKind::FragmentDefinition(fragment) => get_fragment_references(
+                    state,
+                    program,
+                    project_name,
+                    FragmentDefinitionName(fragment.name.value),
+                    include_declaration,
+                ),
+                NodeKind::FragmentSpread(fragment_name) => get_fragment_references(
+                    state,
+                    program,
+                    project_name,
+                    FragmentDefinitionName(fragment_name),
+                    include_declaration,
+                ),
                 NodeKind::FieldName => {
                     let (type_, field) = node_resolution_info
                         .type_path
@@ -65,7 +70,11 @@ fn get_references_response(
                         get_usages(program, &program.schema, type_name, field_name)?
                             .into_iter()
                             .map(|(_, ir_location)| {
-                                transform_relay_location_to_lsp_location(root_dir, ir_location)
+                                transform_relay_location_to_lsp_location(
+                                    root_dir,
+                                    ir_location,
+                                    extract_options,
+                                )
                             })
                             .collect::<Result<Vec<_>, LSPRuntimeError>>()?;
                     Ok(lsp_locations)
@@ -97,7 +106,9 @@ fn get_references_response(
                 let references = find_field_locations(program, field_name, type_name)
                     .ok_or(LSPRuntimeError::ExpectedError)?
                     .into_iter()
-                    .map(|location| transform_relay_location_to_lsp_location(root_dir, location))
+                    .map(|location| {
+                        transform_relay_location_to_lsp_location(root_dir, location, extract_options)
+                    })
                     .collect::<Result<Vec<_>, LSPRuntimeError>>()?;
 
                 Ok(references)
@@ -109,33 +120,31 @@ fn get_references_response(
     }
 }
 
-#[derive(Debug, Clone)]
-struct ReferenceFinder {
-    references: Vec<IRLocation>,
-    name: StringKey,
-}
-
-impl ReferenceFinder {
-    fn get_references_to_fragment(program: &Program, name: StringKey) -> Vec<IRLocation> {
-        let mut reference_finder = ReferenceFinder {
-            references: vec![],
-            name,
-        };
-        reference_finder.visit_program(program);
-        reference_finder.references
-    }
-}
-
-impl Visitor for ReferenceFinder {
-    const NAME: &'static str = "ReferenceFinder";
-    const VISIT_ARGUMENTS: bool = false;
-    const VISIT_DIRECTIVES: bool = false;
-
-    fn visit_fragment_spread(&mut self, spread: &FragmentSpread) {
-        if spread.fragment.item == FragmentDefinitionName(self.name) {
-            self.references.push(spread.fragment.location);
+/// Every spread of the fragment across the project, read from the fragment
+/// spread index of the project instead of walking the program.
+fn get_fragment_references(
+    state: &impl GlobalState,
+    program: &Program,
+    project_name: StringKey,
+    fragment_name: FragmentDefinitionName,
+    include_declaration: bool,
+) -> LSPRuntimeResult<Vec<LSPLocation>> {
+    let mut locations = vec![];
+    if include_declaration {
+        if let Some(fragment) = program.fragment(fragment_name) {
+            locations.push(fragment.name.location);
         }
     }
+    locations.extend(state.get_fragment_spread_locations(&project_name, fragment_name)?);
+
+    let root_dir = state.root_dir();
+    let extract_options = state.get_extract_options();
+    locations
+        .into_iter()
+        .map(|location| {
+            transform_relay_location_to_lsp_location(&root_dir, location, &extract_options)
+        })
+        .collect::<Result<Vec<_>, LSPRuntimeError>>()
 }
 
 pub fn on_references(
@@ -143,13 +152,13 @@ pub fn on_references(
     params: <References as Request>::Params,
 ) -> LSPRuntimeResult<<References as Request>::Result> {
     let node_resolution_info = state.resolve_node(&params.text_document_position)?;
+    let project_name =
+        state.extract_project_name_from_url(&params.text_document_position.text_document.uri)?;
     let references_response = get_references_response(
         node_resolution_info,
-        &state
-            .get_program(&state.extract_project_name_from_url(
-                &params.text_document_position.text_document.uri,
-            )?)?,
-        &state.root_dir(),
+        state,
+        project_name,
+        params.context.include_declaration,
     )?;
     Ok(Some(references_response))
 }
@@ -0,0 +1,265 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing the rename feature for fragments and operations
+
+use std::collections::HashMap;
+
+use common::Span;
+use common::TextSource;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::OperationDefinitionName;
+use intern::string_key::Intern;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use lsp_types::request::Rename;
+use lsp_types::request::Request;
+use lsp_types::AnnotatedTextEdit;
+use lsp_types::ChangeAnnotation;
+use lsp_types::DocumentChanges;
+use lsp_types::OneOf;
+use lsp_types::OptionalVersionedTextDocumentIdentifier;
+use lsp_types::TextDocumentEdit;
+use lsp_types::TextEdit;
+use lsp_types::Url;
+use lsp_types::WorkspaceEdit;
+use relay_transforms::extract_module_name;
+
+use crate::location::transform_relay_location_to_lsp_location;
+use crate::lsp_runtime_error::LSPRuntimeError;
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::node_resolution_info::NodeKind;
+use crate::server::GlobalState;
+use crate::FeatureResolutionInfo;
+
+/// Change annotation attached to edits of generated artifact imports. These
+/// edits are found by scanning the source text, so clients should ask the
+/// user to confirm them.
+const GENERATED_IMPORT_ANNOTATION: &str = "relay-generated-import";
+
+/// The definition being renamed.
+enum RenameTarget {
+    Fragment(FragmentDefinitionName),
+    Operation(OperationDefinitionName),
+}
+
+pub fn on_rename(
+    state: &impl GlobalState,
+    params: <Rename as Request>::Params,
+) -> LSPRuntimeResult<<Rename as Request>::Result> {
+    let text_document_position = &params.text_document_position;
+    let target = match state.resolve_node(text_document_position)? {
+        FeatureResolutionInfo::GraphqlNode(node_resolution_info) => {
+            match node_resolution_info.kind {
+                NodeKind::FragmentDefinition(fragment) => {
+                    RenameTarget::Fragment(FragmentDefinitionName(fragment.name.value))
+                }
+                NodeKind::FragmentSpread(fragment_name) => {
+                    RenameTarget::Fragment(FragmentDefinitionName(fragment_name))
+                }
+                NodeKind::OperationDefinition(operation) => {
+                    let name = operation.name.ok_or(LSPRuntimeError::ExpectedError)?;
+                    RenameTarget::Operation(OperationDefinitionName(name.value))
+                }
+                _ => return Err(LSPRuntimeError::ExpectedError),
+            }
+        }
+        FeatureResolutionInfo::DocblockNode(_) => return Err(LSPRuntimeError::ExpectedError),
+    };
+
+    if !is_valid_graphql_name(&params.new_name) {
+        return Err(LSPRuntimeError::UnexpectedError(format!(
+            "`{}` is not a valid GraphQL name.",
+            params.new_name
+        )));
+    }
+
+    let project_name =
+        state.extract_project_name_from_url(&text_document_position.text_document.uri)?;
+
+    Ok(Some(get_rename_edits(
+        state,
+        project_name,
+        target,
+        &params.new_name,
+    )?))
+}
+
+fn get_rename_edits(
+    state: &impl GlobalState,
+    project_name: StringKey,
+    target: RenameTarget,
+    new_name: &str,
+) -> LSPRuntimeResult<WorkspaceEdit> {
+    let program = state.get_program(&project_name)?;
+    let root_dir = state.root_dir();
+    let extract_options = state.get_extract_options();
+    let (old_name, locations) = match target {
+        RenameTarget::Fragment(fragment_name) => {
+            let fragment = program.fragment(fragment_name).ok_or_else(|| {
+                LSPRuntimeError::UnexpectedError(format!(
+                    "Could not find fragment with name {}",
+                    fragment_name
+                ))
+            })?;
+            if program
+                .fragment(FragmentDefinitionName(new_name.intern()))
+                .is_some()
+            {
+                return Err(LSPRuntimeError::UnexpectedError(format!(
+                    "A fragment named `{}` already exists.",
+                    new_name
+                )));
+            }
+            let path = fragment.name.location.source_location().path();
+            if let Some(module_name) = extract_module_name(path) {
+                if !is_valid_fragment_name(new_name, &module_name) {
+                    return Err(LSPRuntimeError::UnexpectedError(format!(
+                        "Fragments in `{}` must be named `{}_<name>`. Got `{}` instead.",
+                        path, module_name, new_name
+                    )));
+                }
+            }
+            let mut locations = vec![fragment.name.location];
+            locations.extend(state.get_fragment_spread_locations(&project_name, fragment_name)?);
+            (fragment_name.0, locations)
+        }
+        RenameTarget::Operation(operation_name) => {
+            let operation = program.operation(operation_name).ok_or_else(|| {
+                LSPRuntimeError::UnexpectedError(format!(
+                    "Could not find operation with name {}",
+                    operation_name
+                ))
+            })?;
+            if program
+                .operation(OperationDefinitionName(new_name.intern()))
+                .is_some()
+            {
+                return Err(LSPRuntimeError::UnexpectedError(format!(
+                    "An operation named `{}` already exists.",
+                    new_name
+                )));
+            }
+            (operation_name.0, vec![operation.name.location])
+        }
+    };
+
+    let mut edits_by_uri: HashMap<Url, Vec<OneOf<TextEdit, AnnotatedTextEdit>>> = HashMap::new();
+    for location in locations {
+        let lsp_location =
+            transform_relay_location_to_lsp_location(&root_dir, location, &extract_options)?;
+        edits_by_uri
+            .entry(lsp_location.uri)
+            .or_default()
+            .push(OneOf::Left(TextEdit {
+                range: lsp_location.range,
+                new_text: new_name.to_string(),
+            }));
+    }
+
+    // Imports of the generated artifacts (`Name.graphql`, `Name$key`, ...) are not part
+    // of the program, so we flag the ones we can find in the affected files. Only the
+    // synced documents are searched, as the edits must match the text of the client.
+    let mut has_generated_import_edits = false;
+    for (uri, edits) in edits_by_uri.iter_mut() {
+        let text = match state.get_document_text(uri) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+        let text_source = TextSource::from_whole_document(&text);
+        for span in find_generated_name_references(&text, old_name) {
+            has_generated_import_edits = true;
+            edits.push(OneOf::Right(AnnotatedTextEdit {
+                text_edit: TextEdit {
+                    range: text_source.to_span_range(span),
+                    new_text: new_name.to_string(),
+                },
+                annotation_id: GENERATED_IMPORT_ANNOTATION.to_string(),
+            }));
+        }
+    }
+
+    let change_annotations = if has_generated_import_edits {
+        Some(HashMap::from([(
+            GENERATED_IMPORT_ANNOTATION.to_string(),
+            ChangeAnnotation {
+                label: "Update generated artifact imports".to_string(),
+                needs_confirmation: Some(true),
+                description: Some(format!(
+                    "References to the generated artifact of `{}`. Re-run the compiler to regenerate it as `{}`.",
+                    old_name, new_name
+                )),
+            },
+        )]))
+    } else {
+        None
+    };
+
+    let mut document_edits = edits_by_uri
+        .into_iter()
+        .map(|(uri, edits)| TextDocumentEdit {
+            text_document: OptionalVersionedTextDocumentIdentifier { uri, version: None },
+            edits,
+        })
+        .collect::<Vec<_>>();
+    document_edits.sort_by(|a, b| a.text_document.uri.cmp(&b.text_document.uri));
+
+    Ok(WorkspaceEdit {
+        changes: None,
+        document_changes: Some(DocumentChanges::Edits(document_edits)),
+        change_annotations,
+    })
+}
+
+/// Find the spans of `name` where it is used as the name of a generated artifact,
+/// e.g. `'Name.graphql'` or `Name$key`, outside of GraphQL literals.
+fn find_generated_name_references(text: &str, name: StringKey) -> Vec<Span> {
+    let name = name.lookup();
+    let bytes = text.as_bytes();
+    let mut spans = vec![];
+    let mut start = 0;
+    while let Some(offset) = text[start..].find(name) {
+        let begin = start + offset;
+        let end = begin + name.len();
+        start = end;
+        if begin > 0 && is_identifier_byte(bytes[begin - 1]) {
+            continue;
+        }
+        let rest = &text[end..];
+        if rest.starts_with(".graphql") || (rest.starts_with('$') && rest.len() > 1) {
+            spans.push(Span::new(begin as u32, end as u32));
+        }
+    }
+    spans
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$'
+}
+
+/// Whether `name` follows the `<Module>_<name>` naming convention of the
+/// fragments of the module named `module_name`.
+fn is_valid_fragment_name(name: &str, module_name: &str) -> bool {
+    matches!(
+        name.strip_prefix(module_name)
+            .and_then(|rest| rest.strip_prefix('_')),
+        Some(rest) if !rest.is_empty()
+    )
+}
+
+fn is_valid_graphql_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test;
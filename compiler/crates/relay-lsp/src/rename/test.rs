@@ -0,0 +1,48 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::Span;
+use intern::string_key::Intern;
+
+use super::find_generated_name_references;
+use super::is_valid_fragment_name;
+use super::is_valid_graphql_name;
+
+#[test]
+fn finds_generated_artifact_references() {
+    let text = r#"
+import type {Foo_user$key} from './__generated__/Foo_user.graphql';
+import type {Bar_Foo_user$key} from 'Bar_Foo_user.graphql';
+const fragment = graphql`fragment Foo_user on User { ...Foo_user_other }`;
+"#;
+    let spans = find_generated_name_references(text, "Foo_user".intern());
+    let matched = spans
+        .iter()
+        .map(|span| &text[span.start as usize..span.end as usize])
+        .collect::<Vec<_>>();
+    assert_eq!(matched, vec!["Foo_user", "Foo_user"]);
+    assert_eq!(spans[0], Span::new(14, 22));
+}
+
+#[test]
+fn validates_fragment_names() {
+    assert!(is_valid_fragment_name("Foo_user", "Foo"));
+    assert!(is_valid_fragment_name("Foo_user_viewer", "Foo"));
+    assert!(!is_valid_fragment_name("Foo", "Foo"));
+    assert!(!is_valid_fragment_name("Foo_", "Foo"));
+    assert!(!is_valid_fragment_name("FooUser", "Foo"));
+    assert!(!is_valid_fragment_name("Bar_user", "Foo"));
+}
+
+#[test]
+fn validates_graphql_names() {
+    assert!(is_valid_graphql_name("Foo_user"));
+    assert!(is_valid_graphql_name("_private2"));
+    assert!(!is_valid_graphql_name(""));
+    assert!(!is_valid_graphql_name("2Fast"));
+    assert!(!is_valid_graphql_name("Foo-user"));
+}
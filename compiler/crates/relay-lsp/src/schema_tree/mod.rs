@@ -0,0 +1,354 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The whole schema of a project, as a tree of types, fields and arguments,
+//! for editors rendering a schema explorer.
+
+use intern::string_key::Intern;
+use intern::Lookup;
+use lsp_types::request::Request;
+use schema::Argument;
+use schema::ArgumentDefinitions;
+use schema::FieldID;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+use schema_documentation::SchemaDocumentation;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+
+pub(crate) enum GetSchemaTree {}
+
+#[derive(Deserialize, Serialize)]
+pub(crate) struct GetSchemaTreeParams {
+    pub schema_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SchemaTree {
+    query_type: Option<String>,
+    mutation_type: Option<String>,
+    subscription_type: Option<String>,
+    /// Sorted by name
+    types: Vec<SchemaTreeType>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SchemaTreeType {
+    name: String,
+    kind: SchemaTreeTypeKind,
+    description: Option<String>,
+    /// Defined or extended by the client schema extensions
+    is_extension: bool,
+    /// The fields of objects and interfaces, and of input objects
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<SchemaTreeField>,
+    /// The interfaces implemented by objects and interfaces
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    interfaces: Vec<String>,
+    /// The members of unions, and the objects implementing interfaces
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    possible_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    enum_values: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum SchemaTreeTypeKind {
+    Object,
+    Interface,
+    Union,
+    Enum,
+    Scalar,
+    InputObject,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SchemaTreeField {
+    name: String,
+    #[serde(rename = "type")]
+    type_: String,
+    description: Option<String>,
+    is_extension: bool,
+    deprecation_reason: Option<String>,
+    is_deprecated: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    arguments: Vec<SchemaTreeArgument>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SchemaTreeArgument {
+    name: String,
+    #[serde(rename = "type")]
+    type_: String,
+    description: Option<String>,
+    default_value: Option<String>,
+}
+
+impl Request for GetSchemaTree {
+    type Params = GetSchemaTreeParams;
+    type Result = SchemaTree;
+    const METHOD: &'static str = "relay/getSchemaTree";
+}
+
+pub(crate) fn on_get_schema_tree(
+    state: &impl GlobalState,
+    params: <GetSchemaTree as Request>::Params,
+) -> LSPRuntimeResult<<GetSchemaTree as Request>::Result> {
+    let schema_name: &str = &params.schema_name;
+    let schema = state.get_schema(&schema_name.intern())?;
+    let schema_documentation = state.get_schema_documentation(schema_name);
+
+    Ok(build_schema_tree(&schema, &schema_documentation))
+}
+
+fn build_schema_tree(
+    schema: &SDLSchema,
+    schema_documentation: &impl SchemaDocumentation,
+) -> SchemaTree {
+    let root_type_name =
+        |type_: Option<Type>| type_.map(|type_| schema.get_type_name(type_).to_string());
+
+    let mut types = schema
+        .get_type_map()
+        .map(|(_, type_)| build_type(schema, schema_documentation, *type_))
+        .collect::<Vec<_>>();
+    types.sort_by(|a, b| a.name.cmp(&b.name));
+
+    SchemaTree {
+        query_type: root_type_name(schema.query_type()),
+        mutation_type: root_type_name(schema.mutation_type()),
+        subscription_type: root_type_name(schema.subscription_type()),
+        types,
+    }
+}
+
+fn build_type(
+    schema: &SDLSchema,
+    schema_documentation: &impl SchemaDocumentation,
+    type_: Type,
+) -> SchemaTreeType {
+    let type_name = schema.get_type_name(type_);
+    let mut tree_type = SchemaTreeType {
+        name: type_name.to_string(),
+        kind: SchemaTreeTypeKind::Scalar,
+        description: schema_documentation
+            .get_type_description(type_name.lookup())
+            .map(|description| description.to_string()),
+        is_extension: schema.is_extension_type(type_),
+        fields: vec![],
+        interfaces: vec![],
+        possible_types: vec![],
+        enum_values: vec![],
+    };
+    let type_names = |types: &mut dyn Iterator<Item = Type>| {
+        types
+            .map(|type_| schema.get_type_name(type_).to_string())
+            .collect::<Vec<_>>()
+    };
+    match type_ {
+        Type::Object(id) => {
+            let object = schema.object(id);
+            tree_type.kind = SchemaTreeTypeKind::Object;
+            tree_type.fields = build_fields(schema, schema_documentation, type_, &object.fields);
+            tree_type.interfaces =
+                type_names(&mut object.interfaces.iter().map(|id| Type::Interface(*id)));
+        }
+        Type::Interface(id) => {
+            let interface = schema.interface(id);
+            tree_type.kind = SchemaTreeTypeKind::Interface;
+            tree_type.fields = build_fields(schema, schema_documentation, type_, &interface.fields);
+            tree_type.interfaces =
+                type_names(&mut interface.interfaces.iter().map(|id| Type::Interface(*id)));
+            tree_type.possible_types = type_names(
+                &mut interface
+                    .implementing_objects
+                    .iter()
+                    .map(|id| Type::Object(*id)),
+            );
+        }
+        Type::Union(id) => {
+            tree_type.kind = SchemaTreeTypeKind::Union;
+            tree_type.possible_types =
+                type_names(&mut schema.union(id).members.iter().map(|id| Type::Object(*id)));
+        }
+        Type::Enum(id) => {
+            tree_type.kind = SchemaTreeTypeKind::Enum;
+            tree_type.enum_values = schema
+                .enum_(id)
+                .values
+                .iter()
+                .map(|value| value.value.to_string())
+                .collect();
+        }
+        Type::InputObject(id) => {
+            tree_type.kind = SchemaTreeTypeKind::InputObject;
+            tree_type.fields = build_input_fields(schema, &schema.input_object(id).fields);
+        }
+        Type::Scalar(_) => {}
+    }
+    tree_type
+}
+
+fn build_fields(
+    schema: &SDLSchema,
+    schema_documentation: &impl SchemaDocumentation,
+    parent_type: Type,
+    field_ids: &[FieldID],
+) -> Vec<SchemaTreeField> {
+    let parent_type_name = schema.get_type_name(parent_type);
+    field_ids
+        .iter()
+        .map(|field_id| {
+            let field = schema.field(*field_id);
+            let field_name = field.name.item;
+            let deprecated = field.deprecated();
+            SchemaTreeField {
+                name: field_name.to_string(),
+                type_: schema.get_type_string(&field.type_),
+                description: schema_documentation
+                    .get_field_description(parent_type_name.lookup(), field_name.lookup())
+                    .map(|description| description.to_string()),
+                is_extension: field.is_extension,
+                is_deprecated: deprecated.is_some(),
+                deprecation_reason: deprecated
+                    .and_then(|deprecated| deprecated.reason)
+                    .map(|reason| reason.to_string()),
+                arguments: field
+                    .arguments
+                    .iter()
+                    .map(|argument| {
+                        let mut tree_argument = build_argument(schema, argument);
+                        if let Some(description) = schema_documentation
+                            .get_field_argument_description(
+                                parent_type_name.lookup(),
+                                field_name.lookup(),
+                                argument.name.0.lookup(),
+                            )
+                        {
+                            tree_argument.description = Some(description.to_string());
+                        }
+                        tree_argument
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// The fields of input objects are arguments in the schema, without arguments
+/// of their own.
+fn build_input_fields(schema: &SDLSchema, fields: &ArgumentDefinitions) -> Vec<SchemaTreeField> {
+    fields
+        .iter()
+        .map(|field| {
+            let argument = build_argument(schema, field);
+            let deprecated = field.deprecated();
+            SchemaTreeField {
+                name: argument.name,
+                type_: argument.type_,
+                description: argument.description,
+                is_extension: false,
+                is_deprecated: deprecated.is_some(),
+                deprecation_reason: deprecated
+                    .and_then(|deprecated| deprecated.reason)
+                    .map(|reason| reason.to_string()),
+                arguments: vec![],
+            }
+        })
+        .collect()
+}
+
+fn build_argument(schema: &SDLSchema, argument: &Argument) -> SchemaTreeArgument {
+    SchemaTreeArgument {
+        name: argument.name.0.to_string(),
+        type_: schema.get_type_string(&argument.type_),
+        description: argument
+            .description
+            .map(|description| description.to_string()),
+        default_value: argument
+            .default_value
+            .as_ref()
+            .map(|default_value| default_value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use relay_test_schema::get_test_schema_with_extensions;
+
+    use super::build_schema_tree;
+    use super::SchemaTreeTypeKind;
+
+    #[test]
+    fn builds_tree_of_the_schema_with_extensions() {
+        let schema = get_test_schema_with_extensions(
+            r#"
+            type ClientTodo {
+              "The text of the todo"
+              text(uppercase: Boolean = false): String
+            }
+
+            extend type User {
+              clientTodos: [ClientTodo!]
+            }
+            "#,
+        );
+        let tree = build_schema_tree(&schema, &schema);
+
+        assert_eq!(tree.query_type.as_deref(), Some("Query"));
+        assert!(
+            tree.types
+                .windows(2)
+                .all(|types| types[0].name <= types[1].name)
+        );
+
+        let client_todo = tree
+            .types
+            .iter()
+            .find(|type_| type_.name == "ClientTodo")
+            .unwrap();
+        assert_eq!(client_todo.kind, SchemaTreeTypeKind::Object);
+        assert!(client_todo.is_extension);
+        let text = &client_todo.fields[0];
+        assert_eq!(text.type_, "String");
+        assert_eq!(text.description.as_deref(), Some("The text of the todo"));
+        assert_eq!(text.arguments[0].name, "uppercase");
+        assert_eq!(text.arguments[0].type_, "Boolean");
+        assert_eq!(text.arguments[0].default_value.as_deref(), Some("false"));
+
+        let user = tree
+            .types
+            .iter()
+            .find(|type_| type_.name == "User")
+            .unwrap();
+        assert!(!user.is_extension);
+        assert!(user.interfaces.contains(&"Node".to_string()));
+        let client_todos = user
+            .fields
+            .iter()
+            .find(|field| field.name == "clientTodos")
+            .unwrap();
+        assert!(client_todos.is_extension);
+        assert_eq!(client_todos.type_, "[ClientTodo!]");
+        assert!(
+            !user
+                .fields
+                .iter()
+                .find(|field| field.name == "name")
+                .unwrap()
+                .is_extension
+        );
+    }
+}
@@ -0,0 +1,333 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing semantic tokens: the fields, aliases, arguments,
+//! variables and directives of the GraphQL in a document, with the fields and
+//! arguments deprecated in the schema marked as such.
+
+use std::path::Path;
+
+use common::ArgumentName;
+use common::DirectiveName;
+use common::SourceLocationKey;
+use common::Span;
+use extract_graphql::ExtractOptions;
+use extract_graphql::JavaScriptSourceFeature;
+use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_syntax::Alias;
+use graphql_syntax::Argument;
+use graphql_syntax::Directive;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::Identifier;
+use graphql_syntax::List;
+use graphql_syntax::OperationKind;
+use graphql_syntax::Selection;
+use graphql_syntax::Value;
+use graphql_syntax::VariableDefinition;
+use lsp_types::request::Request;
+use lsp_types::request::SemanticTokensFullRequest;
+use lsp_types::Position;
+use lsp_types::SemanticToken;
+use lsp_types::SemanticTokenModifier;
+use lsp_types::SemanticTokenType;
+use lsp_types::SemanticTokens;
+use lsp_types::SemanticTokensLegend;
+use lsp_types::SemanticTokensResult;
+use schema::ArgumentDefinitions;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+
+/// The token types, in the order of their index in the legend.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TokenType {
+    Field,
+    Alias,
+    Argument,
+    Variable,
+    Directive,
+    Type,
+}
+
+const TOKEN_TYPES: [SemanticTokenType; 6] = [
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::new("label"),
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::new("decorator"),
+    SemanticTokenType::TYPE,
+];
+
+/// Bit of the `deprecated` modifier, the only modifier in the legend.
+const DEPRECATED_MODIFIER: u32 = 1;
+
+pub fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: vec![SemanticTokenModifier::DEPRECATED],
+    }
+}
+
+pub fn on_semantic_tokens_full(
+    state: &impl GlobalState,
+    params: <SemanticTokensFullRequest as Request>::Params,
+) -> LSPRuntimeResult<<SemanticTokensFullRequest as Request>::Result> {
+    let uri = params.text_document.uri;
+    let document_text = state.get_document_text(&uri)?;
+    let project_name = state.extract_project_name_from_url(&uri)?;
+    let schema = state.get_schema(&project_name)?;
+
+    Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+        result_id: None,
+        data: get_semantic_tokens(
+            &document_text,
+            &schema,
+            uri.as_ref(),
+            &state.get_extract_options(),
+        ),
+    })))
+}
+
+fn get_semantic_tokens(
+    document_text: &str,
+    schema: &SDLSchema,
+    path: &str,
+    extract_options: &ExtractOptions,
+) -> Vec<SemanticToken> {
+    let mut tokens: Vec<(Position, u32, TokenType, bool)> = vec![];
+    for (index, feature) in extract_graphql::extract_from_path_with_options(
+        Path::new(path),
+        document_text,
+        extract_options,
+    )
+    .into_iter()
+    .enumerate()
+    {
+        let source = match feature {
+            JavaScriptSourceFeature::GraphQL(source) => source,
+            JavaScriptSourceFeature::Docblock(_) => continue,
+        };
+        let text_source = source.text_source();
+        let document = parse_executable_with_error_recovery(
+            &text_source.text,
+            SourceLocationKey::embedded(path, index),
+        )
+        .item;
+        let mut collector = SemanticTokenCollector {
+            schema,
+            tokens: vec![],
+        };
+        for definition in &document.definitions {
+            collector.collect_definition(definition);
+        }
+        for (span, token_type, is_deprecated) in collector.tokens {
+            if span.start == span.end {
+                continue;
+            }
+            let range = text_source.to_span_range(span);
+            // Names never span multiple lines
+            if range.start.line != range.end.line {
+                continue;
+            }
+            let length = range.end.character - range.start.character;
+            tokens.push((range.start, length, token_type, is_deprecated));
+        }
+    }
+    tokens.sort_by_key(|(position, ..)| (position.line, position.character));
+
+    let mut previous = Position::new(0, 0);
+    tokens
+        .into_iter()
+        .map(|(position, length, token_type, is_deprecated)| {
+            let delta_line = position.line - previous.line;
+            let delta_start = if delta_line == 0 {
+                position.character - previous.character
+            } else {
+                position.character
+            };
+            previous = position;
+            SemanticToken {
+                delta_line,
+                delta_start,
+                length,
+                token_type: token_type as u32,
+                token_modifiers_bitset: if is_deprecated {
+                    DEPRECATED_MODIFIER
+                } else {
+                    0
+                },
+            }
+        })
+        .collect()
+}
+
+struct SemanticTokenCollector<'a> {
+    schema: &'a SDLSchema,
+    tokens: Vec<(Span, TokenType, bool)>,
+}
+
+impl SemanticTokenCollector<'_> {
+    fn collect_definition(&mut self, definition: &ExecutableDefinition) {
+        match definition {
+            ExecutableDefinition::Operation(operation) => {
+                if let Some(variable_definitions) = &operation.variable_definitions {
+                    self.collect_variable_definitions(&variable_definitions.items);
+                }
+                self.collect_directives(&operation.directives);
+                let parent_type = match operation.operation_kind() {
+                    OperationKind::Query => self.schema.query_type(),
+                    OperationKind::Mutation => self.schema.mutation_type(),
+                    OperationKind::Subscription => self.schema.subscription_type(),
+                };
+                self.collect_selections(parent_type, &operation.selections.items);
+            }
+            ExecutableDefinition::Fragment(fragment) => {
+                if let Some(variable_definitions) = &fragment.variable_definitions {
+                    self.collect_variable_definitions(&variable_definitions.items);
+                }
+                self.add(fragment.type_condition.type_.span, TokenType::Type, false);
+                self.collect_directives(&fragment.directives);
+                let parent_type = self.schema.get_type(fragment.type_condition.type_.value);
+                self.collect_selections(parent_type, &fragment.selections.items);
+            }
+        }
+    }
+
+    fn collect_variable_definitions(&mut self, variable_definitions: &[VariableDefinition]) {
+        for variable_definition in variable_definitions {
+            self.add(variable_definition.name.span, TokenType::Variable, false);
+            self.collect_directives(&variable_definition.directives);
+        }
+    }
+
+    fn collect_selections(&mut self, parent_type: Option<Type>, selections: &[Selection]) {
+        for selection in selections {
+            match selection {
+                Selection::ScalarField(field) => {
+                    self.collect_field(
+                        parent_type,
+                        field.alias.as_ref(),
+                        &field.name,
+                        field.arguments.as_ref(),
+                        &field.directives,
+                    );
+                }
+                Selection::LinkedField(field) => {
+                    let field_type = self.collect_field(
+                        parent_type,
+                        field.alias.as_ref(),
+                        &field.name,
+                        field.arguments.as_ref(),
+                        &field.directives,
+                    );
+                    self.collect_selections(field_type, &field.selections.items);
+                }
+                Selection::InlineFragment(fragment) => {
+                    let type_ = match &fragment.type_condition {
+                        Some(type_condition) => {
+                            self.add(type_condition.type_.span, TokenType::Type, false);
+                            self.schema.get_type(type_condition.type_.value)
+                        }
+                        None => parent_type,
+                    };
+                    self.collect_directives(&fragment.directives);
+                    self.collect_selections(type_, &fragment.selections.items);
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.collect_directives(&spread.directives);
+                }
+            }
+        }
+    }
+
+    /// Adds the tokens of a field, and returns the type of the selections of
+    /// the field if it is defined in the schema.
+    fn collect_field(
+        &mut self,
+        parent_type: Option<Type>,
+        alias: Option<&Alias>,
+        name: &Identifier,
+        arguments: Option<&List<Argument>>,
+        directives: &[Directive],
+    ) -> Option<Type> {
+        let schema = self.schema;
+        let field = parent_type
+            .and_then(|parent_type| schema.named_field(parent_type, name.value))
+            .map(|field_id| schema.field(field_id));
+        if let Some(alias) = alias {
+            self.add(alias.alias.span, TokenType::Alias, false);
+        }
+        self.add(
+            name.span,
+            TokenType::Field,
+            field.map_or(false, |field| field.deprecated().is_some()),
+        );
+        if let Some(arguments) = arguments {
+            self.collect_arguments(&arguments.items, field.map(|field| &field.arguments));
+        }
+        self.collect_directives(directives);
+        field.map(|field| field.type_.inner())
+    }
+
+    fn collect_directives(&mut self, directives: &[Directive]) {
+        let schema = self.schema;
+        for directive in directives {
+            self.add(
+                Span::new(directive.at.span.start, directive.name.span.end),
+                TokenType::Directive,
+                false,
+            );
+            if let Some(arguments) = &directive.arguments {
+                let definitions = schema
+                    .get_directive(DirectiveName(directive.name.value))
+                    .map(|definition| &definition.arguments);
+                self.collect_arguments(&arguments.items, definitions);
+            }
+        }
+    }
+
+    fn collect_arguments(
+        &mut self,
+        arguments: &[Argument],
+        definitions: Option<&ArgumentDefinitions>,
+    ) {
+        for argument in arguments {
+            let is_deprecated = definitions
+                .and_then(|definitions| definitions.named(ArgumentName(argument.name.value)))
+                .map_or(false, |definition| definition.deprecated().is_some());
+            self.add(argument.name.span, TokenType::Argument, is_deprecated);
+            self.collect_value(&argument.value);
+        }
+    }
+
+    fn collect_value(&mut self, value: &Value) {
+        match value {
+            Value::Variable(variable) => self.add(variable.span, TokenType::Variable, false),
+            Value::List(list) => {
+                for item in &list.items {
+                    self.collect_value(item);
+                }
+            }
+            Value::Object(object) => {
+                for field in &object.items {
+                    self.collect_value(&field.value);
+                }
+            }
+            Value::Constant(_) => {}
+        }
+    }
+
+    fn add(&mut self, span: Span, token_type: TokenType, is_deprecated: bool) {
+        self.tokens.push((span, token_type, is_deprecated));
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,109 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use extract_graphql::ExtractOptions;
+use relay_test_schema::get_test_schema_with_extensions;
+
+use super::get_semantic_tokens;
+use super::TokenType;
+use super::DEPRECATED_MODIFIER;
+use super::TOKEN_TYPES;
+
+/// Decodes the tokens of `text` to absolute positions.
+fn get_tokens(text: &str) -> Vec<(u32, u32, u32, TokenType, bool)> {
+    let schema = get_test_schema_with_extensions(
+        r#"
+extend type User {
+  legacy_name(locale: String @deprecated): String @deprecated(reason: "Use name")
+}
+"#,
+    );
+    let token_types = [
+        TokenType::Field,
+        TokenType::Alias,
+        TokenType::Argument,
+        TokenType::Variable,
+        TokenType::Directive,
+        TokenType::Type,
+    ];
+    assert_eq!(token_types.len(), TOKEN_TYPES.len());
+    let mut line = 0;
+    let mut character = 0;
+    get_semantic_tokens(text, &schema, "/src/Foo.js", &ExtractOptions::default())
+        .into_iter()
+        .map(|token| {
+            if token.delta_line == 0 {
+                character += token.delta_start;
+            } else {
+                line += token.delta_line;
+                character = token.delta_start;
+            }
+            (
+                line,
+                character,
+                token.length,
+                token_types[token.token_type as usize],
+                token.token_modifiers_bitset & DEPRECATED_MODIFIER != 0,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn highlights_operations() {
+    let text = r#"const query = graphql`
+  query FooQuery($locale: String) @preloadable {
+    me {
+      userName: name
+      legacy_name(locale: $locale)
+    }
+  }
+`;
+"#;
+    assert_eq!(
+        get_tokens(text),
+        vec![
+            (1, 17, 7, TokenType::Variable, false),
+            (1, 34, 12, TokenType::Directive, false),
+            (2, 4, 2, TokenType::Field, false),
+            (3, 6, 8, TokenType::Alias, false),
+            (3, 16, 4, TokenType::Field, false),
+            (4, 6, 11, TokenType::Field, true),
+            (4, 18, 6, TokenType::Argument, true),
+            (4, 26, 7, TokenType::Variable, false),
+        ]
+    );
+}
+
+#[test]
+fn highlights_fragments() {
+    let text = r#"const fragment = graphql`
+  fragment Foo_user on User @argumentDefinitions(size: {type: "[Int]"}) {
+    ... on User @include(if: true) {
+      profilePicture(size: $size) {
+        uri
+      }
+    }
+  }
+`;
+"#;
+    assert_eq!(
+        get_tokens(text),
+        vec![
+            (1, 23, 4, TokenType::Type, false),
+            (1, 28, 20, TokenType::Directive, false),
+            (1, 49, 4, TokenType::Argument, false),
+            (2, 11, 4, TokenType::Type, false),
+            (2, 16, 8, TokenType::Directive, false),
+            (2, 25, 2, TokenType::Argument, false),
+            (3, 6, 14, TokenType::Field, false),
+            (3, 21, 4, TokenType::Argument, false),
+            (3, 27, 5, TokenType::Variable, false),
+            (4, 8, 3, TokenType::Field, false),
+        ]
+    );
+}
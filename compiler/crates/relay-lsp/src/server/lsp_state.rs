@@ -5,27 +5,36 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::RwLock;
 
+use common::Diagnostic as CompilerDiagnostic;
+use common::Location;
 use common::PerfLogger;
 use common::SourceLocationKey;
 use common::Span;
+use common::TextSource;
 use crossbeam::channel::SendError;
 use crossbeam::channel::Sender;
 use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
 use docblock_syntax::parse_docblock;
+use extract_graphql::ExtractOptions;
 use extract_graphql::JavaScriptSourceFeature;
 use fnv::FnvBuildHasher;
 use graphql_ir::build_ir_with_extra_features;
 use graphql_ir::BuilderOptions;
+use graphql_ir::ExecutableDefinitionName;
+use graphql_ir::FragmentDefinitionName;
 use graphql_ir::FragmentVariablesSemantic;
 use graphql_ir::Program;
 use graphql_ir::RelayMode;
-use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_syntax::parse_executable_with_error_recovery_and_parser_features;
 use graphql_syntax::ExecutableDefinition;
 use graphql_syntax::ExecutableDocument;
+use graphql_syntax::ParserFeatures;
 use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use log::debug;
@@ -35,7 +44,10 @@ use lsp_types::Range;
 use lsp_types::TextDocumentPositionParams;
 use lsp_types::Url;
 use relay_compiler::config::Config;
+use relay_compiler::parser_features;
+use relay_compiler::preview::PreviewArtifact;
 use relay_compiler::FileCategorizer;
+use relay_compiler::NoopArtifactWriter;
 use relay_docblock::parse_docblock_ast;
 use relay_docblock::ParseOptions;
 use relay_transforms::deprecated_fields_for_executable_definition;
@@ -46,12 +58,18 @@ use schema_documentation::SchemaDocumentationLoader;
 use tokio::sync::Notify;
 
 use super::task_queue::TaskScheduler;
+use crate::compiled_output::get_compiled_output;
+use crate::completion::CompletionConfig;
 use crate::diagnostic_reporter::DiagnosticReporter;
 use crate::docblock_resolution_info::create_docblock_resolution_info;
 use crate::graphql_tools::get_query_text;
+use crate::inlay_hints::InlayHintsConfig;
 use crate::js_language_server::JSLanguageServer;
 use crate::lsp_runtime_error::LSPRuntimeResult;
 use crate::node_resolution_info::create_node_resolution_info;
+use crate::references::FragmentSpreadIndex;
+use crate::status_reporter::LSPStatusReporter;
+use crate::status_updater::WorkDoneProgressReporter;
 use crate::utils::extract_executable_definitions_from_text_document;
 use crate::utils::extract_feature_from_text;
 use crate::utils::extract_project_name_from_url;
@@ -64,6 +82,7 @@ use crate::LSPRuntimeError;
 
 pub type Schemas = Arc<DashMap<StringKey, Arc<SDLSchema>, FnvBuildHasher>>;
 pub type SourcePrograms = Arc<DashMap<StringKey, Program, FnvBuildHasher>>;
+pub type FragmentSpreadIndexes = Arc<DashMap<StringKey, Arc<FragmentSpreadIndex>, FnvBuildHasher>>;
 pub type ProjectStatusMap = Arc<DashMap<StringKey, ProjectStatus, FnvBuildHasher>>;
 
 #[derive(Eq, PartialEq)]
@@ -72,6 +91,74 @@ pub enum ProjectStatus {
     Completed,
 }
 
+/// The GraphQL templates of a synced document as of its last validation.
+/// Edits usually change a single template, so the templates on the lines
+/// the edits didn't change are reused, as long as they keep their index in
+/// the document and the schema stays the same.
+struct ValidatedDocument {
+    text: String,
+    sources: Vec<ValidatedGraphQLSource>,
+}
+
+/// A GraphQL template of a synced document, parsed and validated.
+struct ValidatedGraphQLSource {
+    /// The index of the template among the sources of the document, which
+    /// its definitions and diagnostics are located in.
+    index: usize,
+    line_index: usize,
+    schema: Arc<SDLSchema>,
+    definitions: Vec<ExecutableDefinition>,
+    diagnostics: Vec<CompilerDiagnostic>,
+}
+
+/// The lines at the start and the end of a document that an edit didn't
+/// change.
+struct UnchangedLines {
+    /// The number of unchanged lines at the start.
+    prefix: usize,
+    /// The first of the unchanged lines at the end, in the new text.
+    suffix_start: usize,
+    /// The first of the unchanged lines at the end, in the previous text.
+    previous_suffix_start: usize,
+}
+
+impl UnchangedLines {
+    fn new(previous_text: &str, text: &str) -> Self {
+        let previous_lines = previous_text.split('\n').collect::<Vec<_>>();
+        let lines = text.split('\n').collect::<Vec<_>>();
+        let prefix = previous_lines
+            .iter()
+            .zip(&lines)
+            .take_while(|(previous_line, line)| previous_line == line)
+            .count();
+        let suffix = previous_lines
+            .iter()
+            .rev()
+            .zip(lines.iter().rev())
+            .take(previous_lines.len().min(lines.len()) - prefix)
+            .take_while(|(previous_line, line)| previous_line == line)
+            .count();
+        Self {
+            prefix,
+            suffix_start: lines.len() - suffix,
+            previous_suffix_start: previous_lines.len() - suffix,
+        }
+    }
+
+    /// The line in the previous text of a template that starts at
+    /// `line_index` in the new text, if none of its lines changed.
+    fn previous_line_index(&self, line_index: usize, text: &str) -> Option<usize> {
+        let line_count = text.matches('\n').count() + 1;
+        if line_index + line_count <= self.prefix {
+            Some(line_index)
+        } else if line_index >= self.suffix_start {
+            Some(line_index - self.suffix_start + self.previous_suffix_start)
+        } else {
+            None
+        }
+    }
+}
+
 pub trait GlobalState {
     type TSchemaDocumentation: SchemaDocumentation;
 
@@ -79,6 +166,22 @@ pub trait GlobalState {
 
     fn get_program(&self, project_name: &StringKey) -> LSPRuntimeResult<Program>;
 
+    /// Locations of every spread of `fragment_name` in the source program of the project
+    fn get_fragment_spread_locations(
+        &self,
+        project_name: &StringKey,
+        fragment_name: FragmentDefinitionName,
+    ) -> LSPRuntimeResult<Vec<Location>>;
+
+    /// Absolute path of the artifact generated for the operation or fragment
+    /// named `definition_name` in the document at `url`
+    fn get_artifact_path(
+        &self,
+        project_name: &StringKey,
+        url: &Url,
+        definition_name: StringKey,
+    ) -> LSPRuntimeResult<PathBuf>;
+
     fn resolve_node(
         &self,
         text_document_position: &TextDocumentPositionParams,
@@ -125,12 +228,41 @@ pub trait GlobalState {
         project_name: &StringKey,
     ) -> LSPRuntimeResult<String>;
 
+    /// Full text of the operation in `query_text`, with the fragments it
+    /// spreads inlined
+    fn get_inlined_query_text(
+        &self,
+        query_text: String,
+        project_name: &StringKey,
+    ) -> LSPRuntimeResult<String>;
+
+    /// Artifacts the compiler would generate for `definition_name`, compiling
+    /// the `definitions` of a document in memory
+    fn get_compiled_output(
+        &self,
+        project_name: &StringKey,
+        definitions: &[ExecutableDefinition],
+        definition_name: ExecutableDefinitionName,
+    ) -> LSPRuntimeResult<Vec<PreviewArtifact>>;
+
+    /// Full text of a synced document that contains GraphQL, as last sent by the client
+    fn get_document_text(&self, url: &Url) -> LSPRuntimeResult<String>;
+
     fn document_opened(&self, url: &Url, text: &str) -> LSPRuntimeResult<()>;
 
     fn document_changed(&self, url: &Url, full_text: &str) -> LSPRuntimeResult<()>;
 
     fn document_closed(&self, url: &Url) -> LSPRuntimeResult<()>;
 
+    /// Which inlay hints the client asked for
+    fn get_inlay_hints_config(&self) -> InlayHintsConfig;
+
+    /// Which completions the client asked for
+    fn get_completion_config(&self) -> CompletionConfig;
+
+    /// Which template literals of the documents contain GraphQL
+    fn get_extract_options(&self) -> ExtractOptions;
+
     /// To distinguish content, that we show to consumers
     /// we may need to know who's our current consumer.
     /// This is mostly for hover handler (where we render markup)
@@ -143,27 +275,35 @@ pub struct LSPState<
     TPerfLogger: PerfLogger + 'static,
     TSchemaDocumentation: SchemaDocumentation + 'static,
 > {
-    pub(crate) config: Arc<Config>,
+    config: RwLock<Arc<Config>>,
     pub(crate) sender: Sender<Message>,
     task_scheduler: Arc<TaskScheduler<super::Task>>,
     root_dir: PathBuf,
     extra_data_provider: Box<dyn LSPExtraDataProvider>,
-    file_categorizer: FileCategorizer,
+    file_categorizer: RwLock<FileCategorizer>,
     pub(crate) schemas: Schemas,
     schema_documentation_loader: Option<Box<dyn SchemaDocumentationLoader<TSchemaDocumentation>>>,
     pub(crate) source_programs: SourcePrograms,
+    pub(crate) fragment_spread_indexes: FragmentSpreadIndexes,
     synced_javascript_features: DashMap<Url, Vec<JavaScriptSourceFeature>>,
+    synced_document_texts: DashMap<Url, String>,
+    validated_documents: DashMap<Url, ValidatedDocument>,
     pub(crate) perf_logger: Arc<TPerfLogger>,
     pub(crate) diagnostic_reporter: Arc<DiagnosticReporter>,
     pub(crate) notify_lsp_state_resources: Arc<Notify>,
     pub(crate) project_status: ProjectStatusMap,
     js_resource: Option<Box<dyn JSLanguageServer<TState = Self>>>,
+    inlay_hints_config: InlayHintsConfig,
+    completion_config: CompletionConfig,
+    /// Whether the client supports server initiated `$/progress` reporting
+    work_done_progress: bool,
 }
 
 impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentation>
     LSPState<TPerfLogger, TSchemaDocumentation>
 {
     /// Private constructor
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         config: Arc<Config>,
         sender: Sender<Message>,
@@ -174,6 +314,9 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             Box<dyn SchemaDocumentationLoader<TSchemaDocumentation>>,
         >,
         js_resource: Option<Box<dyn JSLanguageServer<TState = Self>>>,
+        inlay_hints_config: InlayHintsConfig,
+        completion_config: CompletionConfig,
+        work_done_progress: bool,
     ) -> Self {
         debug!("Creating lsp_state...");
         let file_categorizer = FileCategorizer::from_config(&config);
@@ -181,15 +324,16 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         let diagnostic_reporter = Arc::new(DiagnosticReporter::new(
             config.root_dir.clone(),
             sender.clone(),
+            config.extract_options(),
         ));
 
         let lsp_state = Self {
-            config,
+            config: RwLock::new(config),
             sender,
             task_scheduler,
             diagnostic_reporter,
             extra_data_provider,
-            file_categorizer,
+            file_categorizer: RwLock::new(file_categorizer),
             notify_lsp_state_resources: Arc::new(Notify::new()),
             perf_logger,
             project_status: Arc::new(DashMap::with_hasher(FnvBuildHasher::default())),
@@ -197,8 +341,14 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             schemas: Arc::new(DashMap::with_hasher(FnvBuildHasher::default())),
             schema_documentation_loader,
             source_programs: Arc::new(DashMap::with_hasher(FnvBuildHasher::default())),
+            fragment_spread_indexes: Arc::new(DashMap::with_hasher(FnvBuildHasher::default())),
             synced_javascript_features: Default::default(),
+            synced_document_texts: Default::default(),
+            validated_documents: Default::default(),
             js_resource,
+            inlay_hints_config,
+            completion_config,
+            work_done_progress,
         };
 
         // Preload schema documentation - this will warm-up schema documentation cache in the LSP Extra Data providers
@@ -209,8 +359,40 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         lsp_state
     }
 
-    fn insert_synced_sources(&self, url: &Url, sources: Vec<JavaScriptSourceFeature>) {
+    pub(crate) fn config(&self) -> Arc<Config> {
+        Arc::clone(&self.config.read().unwrap())
+    }
+
+    /// Replaces the config with one reloaded from the config file, and drops
+    /// the schemas and programs built with the previous one, so that they are
+    /// built again. The root directory stays the one the server started with.
+    pub(crate) fn reload_config(&self, mut config: Config) {
+        config.artifact_writer = Box::new(NoopArtifactWriter);
+        config.status_reporter = Box::new(LSPStatusReporter::new(
+            self.root_dir.clone(),
+            self.sender.clone(),
+            config.extract_options(),
+        ));
+
+        // Projects removed from the config can't be built anymore
+        self.project_status
+            .retain(|project_name, _| config.projects.contains_key(project_name));
+        for mut project_status in self.project_status.iter_mut() {
+            *project_status = ProjectStatus::Activated;
+        }
+        self.schemas.clear();
+        self.source_programs.clear();
+        self.fragment_spread_indexes.clear();
+        self.validated_documents.clear();
+
+        *self.file_categorizer.write().unwrap() = FileCategorizer::from_config(&config);
+        *self.config.write().unwrap() = Arc::new(config);
+    }
+
+    fn insert_synced_sources(&self, url: &Url, text: &str, sources: Vec<JavaScriptSourceFeature>) {
         self.synced_javascript_features.insert(url.clone(), sources);
+        self.synced_document_texts
+            .insert(url.clone(), text.to_string());
     }
 
     fn validate_synced_sources(&self, url: &Url) -> LSPRuntimeResult<()> {
@@ -223,56 +405,68 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             .schemas
             .get(&project_name)
             .ok_or(LSPRuntimeError::ExpectedError)?;
+        let features = self
+            .config()
+            .projects
+            .get(&project_name)
+            .map_or_else(ParserFeatures::default, |project_config| {
+                parser_features(&project_config.feature_flags)
+            });
 
         let mut executable_definitions = vec![];
         let mut docblock_sources = vec![];
+        let text = self
+            .synced_document_texts
+            .get(url)
+            .map_or_else(String::new, |text| text.clone());
+        let (unchanged_lines, mut previous_graphql_sources) =
+            match self.validated_documents.remove(url) {
+                Some((_, previous_document)) => (
+                    Some(UnchangedLines::new(&previous_document.text, &text)),
+                    previous_document.sources,
+                ),
+                None => (None, vec![]),
+            };
+        let mut graphql_sources = vec![];
 
         for (index, feature) in javascript_features.iter().enumerate() {
             let source_location_key = SourceLocationKey::embedded(&url.to_string(), index);
 
             match feature {
                 JavaScriptSourceFeature::GraphQL(graphql_source) => {
-                    let result = parse_executable_with_error_recovery(
-                        &graphql_source.text_source().text,
-                        source_location_key,
-                    );
-                    diagnostics.extend(result.diagnostics.iter().map(|diagnostic| {
-                        self.diagnostic_reporter
-                            .convert_diagnostic(graphql_source.text_source(), diagnostic)
-                    }));
-
-                    let compiler_diagnostics = match build_ir_with_extra_features(
-                        &schema,
-                        &result.item.definitions,
-                        &BuilderOptions {
-                            allow_undefined_fragment_spreads: true,
-                            fragment_variables_semantic: FragmentVariablesSemantic::PassedValue,
-                            relay_mode: Some(RelayMode),
-                            default_anonymous_operation_name: None,
+                    let text_source = graphql_source.text_source();
+                    let previous_position = unchanged_lines
+                        .as_ref()
+                        .and_then(|unchanged_lines| {
+                            unchanged_lines
+                                .previous_line_index(text_source.line_index, &text_source.text)
+                        })
+                        .and_then(|previous_line_index| {
+                            previous_graphql_sources.iter().position(|previous_source| {
+                                previous_source.index == index
+                                    && previous_source.line_index == previous_line_index
+                                    && Arc::ptr_eq(&previous_source.schema, &schema)
+                            })
+                        });
+                    let validated_source = match previous_position {
+                        Some(position) => ValidatedGraphQLSource {
+                            line_index: text_source.line_index,
+                            ..previous_graphql_sources.swap_remove(position)
                         },
-                    )
-                    .and_then(|documents| {
-                        let mut warnings = vec![];
-                        for document in documents {
-                            // Today the only warning we check for is deprecated
-                            // fields, but in the future we could check for more
-                            // things here by making this more generic.
-                            warnings.extend(deprecated_fields_for_executable_definition(
-                                &schema, &document,
-                            )?)
-                        }
-                        Ok(warnings)
-                    }) {
-                        Ok(warnings) => warnings,
-                        Err(errors) => errors,
+                        None => validate_graphql_source(
+                            index,
+                            text_source,
+                            source_location_key,
+                            features,
+                            &schema,
+                        ),
                     };
-
-                    diagnostics.extend(compiler_diagnostics.iter().map(|diagnostic| {
+                    diagnostics.extend(validated_source.diagnostics.iter().map(|diagnostic| {
                         self.diagnostic_reporter
-                            .convert_diagnostic(graphql_source.text_source(), diagnostic)
+                            .convert_diagnostic(text_source, diagnostic)
                     }));
-
-                    executable_definitions.extend(result.item.definitions);
+                    executable_definitions.extend(validated_source.definitions.iter().cloned());
+                    graphql_sources.push(validated_source);
                 }
                 JavaScriptSourceFeature::Docblock(docblock_source) => {
                     docblock_sources.push(docblock_source);
@@ -280,7 +474,8 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             }
         }
 
-        let project_config = self.config.projects.get(&project_name).unwrap();
+        let config = self.config();
+        let project_config = config.projects.get(&project_name).unwrap();
         for (index, docblock_source) in docblock_sources.iter().enumerate() {
             let source_location_key = SourceLocationKey::embedded(url.as_ref(), index);
             let text_source = docblock_source.text_source();
@@ -304,6 +499,13 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                 }));
             }
         }
+        self.validated_documents.insert(
+            url.clone(),
+            ValidatedDocument {
+                text,
+                sources: graphql_sources,
+            },
+        );
         self.diagnostic_reporter
             .update_quick_diagnostics_for_url(url, diagnostics);
 
@@ -311,7 +513,7 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
     }
 
     fn preload_documentation(&self) {
-        for project_config in self.config.enabled_projects() {
+        for project_config in self.config().enabled_projects() {
             self.get_schema_documentation(&project_config.name.to_string());
         }
     }
@@ -320,6 +522,23 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         self.sender.send(message)
     }
 
+    /// Starts reporting the progress of a long running task, if the client supports it.
+    pub(crate) fn begin_work_done_progress(
+        &self,
+        title: impl Into<String>,
+        message: Option<String>,
+    ) -> Option<WorkDoneProgressReporter> {
+        if self.work_done_progress {
+            Some(WorkDoneProgressReporter::begin(
+                &self.sender,
+                title,
+                message,
+            ))
+        } else {
+            None
+        }
+    }
+
     pub fn schedule_task(&self, task: Task) {
         self.task_scheduler.schedule(super::Task::LSPState(task));
     }
@@ -327,6 +546,7 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
     fn process_synced_sources(
         &self,
         uri: &Url,
+        text: &str,
         sources: Vec<JavaScriptSourceFeature>,
     ) -> LSPRuntimeResult<()> {
         let project_name = self.extract_project_name_from_url(uri)?;
@@ -336,14 +556,29 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             self.notify_lsp_state_resources.notify_one();
         }
 
-        self.insert_synced_sources(uri, sources);
+        self.insert_synced_sources(uri, text, sources);
         self.schedule_task(Task::ValidateSyncedSource(uri.clone()));
 
         Ok(())
     }
 
+    /// The GraphQL and docblocks of a document. Standalone GraphQL documents
+    /// only have sources in projects with standalone documents, elsewhere
+    /// `.graphql` files are schemas.
+    fn extract_sources(&self, uri: &Url, text: &str) -> Vec<JavaScriptSourceFeature> {
+        let path = Path::new(uri.path());
+        if extract_graphql::is_standalone_document(path)
+            && self.extract_project_name_from_url(uri).is_err()
+        {
+            return vec![];
+        }
+        extract_graphql::extract_from_path_with_options(path, text, &self.get_extract_options())
+    }
+
     fn remove_synced_sources(&self, url: &Url) {
         self.synced_javascript_features.remove(url);
+        self.synced_document_texts.remove(url);
+        self.validated_documents.remove(url);
         self.diagnostic_reporter
             .clear_quick_diagnostics_for_url(url);
     }
@@ -384,6 +619,56 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             })
     }
 
+    fn get_fragment_spread_locations(
+        &self,
+        project_name: &StringKey,
+        fragment_name: FragmentDefinitionName,
+    ) -> LSPRuntimeResult<Vec<Location>> {
+        self.fragment_spread_indexes
+            .get(project_name)
+            .map(|index| index.get(fragment_name).to_vec())
+            .ok_or_else(|| {
+                LSPRuntimeError::UnexpectedError(format!(
+                    "get_fragment_spread_locations: index is missing (or not ready, yet) for the `{}` project.",
+                    project_name
+                ))
+            })
+    }
+
+    fn get_artifact_path(
+        &self,
+        project_name: &StringKey,
+        url: &Url,
+        definition_name: StringKey,
+    ) -> LSPRuntimeResult<PathBuf> {
+        let config = self.config();
+        let project_config = config.projects.get(project_name).ok_or_else(|| {
+            LSPRuntimeError::UnexpectedError(format!(
+                "get_artifact_path: config is missing for the `{}` project.",
+                project_name
+            ))
+        })?;
+        let absolute_file_path = url.to_file_path().map_err(|_| {
+            LSPRuntimeError::UnexpectedError(format!(
+                "Unable to convert URL to file path: {:?}",
+                url
+            ))
+        })?;
+        let file_path = absolute_file_path
+            .strip_prefix(&self.root_dir)
+            .ok()
+            .and_then(|file_path| file_path.to_str())
+            .ok_or_else(|| {
+                LSPRuntimeError::UnexpectedError(format!(
+                    "Failed to strip prefix {:?} from {:?}",
+                    self.root_dir, absolute_file_path
+                ))
+            })?;
+        let artifact_path = project_config
+            .path_for_artifact(SourceLocationKey::standalone(file_path), definition_name);
+        Ok(self.root_dir.join(artifact_path))
+    }
+
     fn resolve_node(
         &self,
         text_document_position: &TextDocumentPositionParams,
@@ -435,7 +720,8 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         index_offset: usize,
     ) -> LSPRuntimeResult<(Feature, Span)> {
         let project_name = self.extract_project_name_from_url(&position.text_document.uri)?;
-        let project_config = self.config.projects.get(&project_name).unwrap();
+        let config = self.config();
+        let project_config = config.projects.get(&project_name).unwrap();
 
         extract_feature_from_text(
             project_config,
@@ -459,7 +745,7 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
     }
 
     fn extract_project_name_from_url(&self, url: &Url) -> LSPRuntimeResult<StringKey> {
-        extract_project_name_from_url(&self.file_categorizer, url, &self.root_dir)
+        extract_project_name_from_url(&self.file_categorizer.read().unwrap(), url, &self.root_dir)
     }
 
     fn get_extra_data_provider(&self) -> &dyn LSPExtraDataProvider {
@@ -490,7 +776,31 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         query_text: String,
         project_name: &StringKey,
     ) -> LSPRuntimeResult<String> {
-        get_query_text(self, query_text, project_name)
+        get_query_text(self, query_text, project_name, false)
+    }
+
+    fn get_inlined_query_text(
+        &self,
+        query_text: String,
+        project_name: &StringKey,
+    ) -> LSPRuntimeResult<String> {
+        get_query_text(self, query_text, project_name, true)
+    }
+
+    fn get_compiled_output(
+        &self,
+        project_name: &StringKey,
+        definitions: &[ExecutableDefinition],
+        definition_name: ExecutableDefinitionName,
+    ) -> LSPRuntimeResult<Vec<PreviewArtifact>> {
+        get_compiled_output(self, project_name, definitions, definition_name)
+    }
+
+    fn get_document_text(&self, uri: &Url) -> LSPRuntimeResult<String> {
+        self.synced_document_texts
+            .get(uri)
+            .map(|text| text.value().clone())
+            .ok_or(LSPRuntimeError::ExpectedError)
     }
 
     fn document_opened(&self, uri: &Url, text: &str) -> LSPRuntimeResult<()> {
@@ -499,11 +809,11 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         }
 
         // First we check to see if this document has any GraphQL documents.
-        let embedded_sources = extract_graphql::extract(text);
+        let embedded_sources = self.extract_sources(uri, text);
         if embedded_sources.is_empty() {
             Ok(())
         } else {
-            self.process_synced_sources(uri, embedded_sources)
+            self.process_synced_sources(uri, text, embedded_sources)
         }
     }
 
@@ -513,12 +823,12 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         }
 
         // First we check to see if this document has any GraphQL documents.
-        let embedded_sources = extract_graphql::extract(full_text);
+        let embedded_sources = self.extract_sources(uri, full_text);
         if embedded_sources.is_empty() {
             self.remove_synced_sources(uri);
             Ok(())
         } else {
-            self.process_synced_sources(uri, embedded_sources)
+            self.process_synced_sources(uri, full_text, embedded_sources)
         }
     }
 
@@ -530,11 +840,76 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         Ok(())
     }
 
+    fn get_inlay_hints_config(&self) -> InlayHintsConfig {
+        self.inlay_hints_config
+    }
+
+    fn get_completion_config(&self) -> CompletionConfig {
+        self.completion_config
+    }
+
+    fn get_extract_options(&self) -> ExtractOptions {
+        self.config().extract_options()
+    }
+
     fn get_content_consumer_type(&self) -> ContentConsumerType {
         ContentConsumerType::Relay
     }
 }
 
+fn validate_graphql_source(
+    index: usize,
+    text_source: &TextSource,
+    source_location_key: SourceLocationKey,
+    features: ParserFeatures,
+    schema: &Arc<SDLSchema>,
+) -> ValidatedGraphQLSource {
+    let result = parse_executable_with_error_recovery_and_parser_features(
+        &text_source.text,
+        source_location_key,
+        features,
+    );
+    let mut diagnostics = result.diagnostics;
+
+    let compiler_diagnostics = match build_ir_with_extra_features(
+        schema,
+        &result.item.definitions,
+        &BuilderOptions {
+            allow_undefined_fragment_spreads: true,
+            fragment_variables_semantic: FragmentVariablesSemantic::PassedValue,
+            relay_mode: Some(RelayMode),
+            default_anonymous_operation_name: None,
+        },
+    )
+    .and_then(|documents| {
+        let mut warnings = vec![];
+        for document in documents {
+            // Today the only warning we check for is deprecated
+            // fields, but in the future we could check for more
+            // things here by making this more generic.
+            warnings.extend(deprecated_fields_for_executable_definition(
+                schema, &document,
+            )?)
+        }
+        Ok(warnings)
+    }) {
+        Ok(warnings) => warnings,
+        Err(errors) => errors,
+    };
+    diagnostics.extend(compiler_diagnostics);
+
+    ValidatedGraphQLSource {
+        index,
+        line_index: text_source.line_index,
+        schema: Arc::clone(schema),
+        definitions: result.item.definitions,
+        diagnostics,
+    }
+}
+
+/// Re-validating at least this many synced documents reports its progress to the client
+const LARGE_VALIDATION_DOCUMENT_COUNT: usize = 20;
+
 #[derive(Debug)]
 pub enum Task {
     ValidateSyncedSource(Url),
@@ -553,9 +928,129 @@ pub(crate) fn handle_lsp_state_tasks<
             state.validate_synced_sources(&url).ok();
         }
         Task::ValidateSyncedSources => {
-            for item in &state.synced_javascript_features {
-                state.schedule_task(Task::ValidateSyncedSource(item.key().clone()));
+            let urls: Vec<Url> = state
+                .synced_javascript_features
+                .iter()
+                .map(|item| item.key().clone())
+                .collect();
+            if urls.len() < LARGE_VALIDATION_DOCUMENT_COUNT {
+                for url in urls {
+                    state.schedule_task(Task::ValidateSyncedSource(url));
+                }
+                return;
+            }
+            // Validate the documents here, so that we can report the progress of it
+            let progress = state.begin_work_done_progress("Relay: validating documents", None);
+            for (index, url) in urls.iter().enumerate() {
+                state.validate_synced_sources(url).ok();
+                if let Some(progress) = &progress {
+                    progress.report(
+                        format!("{}/{} documents", index + 1, urls.len()),
+                        Some(((index + 1) * 100 / urls.len()) as u32),
+                    );
+                }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use common::NoopPerfLogger;
+    use crossbeam::channel::unbounded;
+    use intern::Lookup;
+
+    use super::super::task_queue::TaskQueue;
+    use super::super::LSPTaskProcessor;
+    use super::*;
+    use crate::DummyExtraDataProvider;
+
+    const SCHEMA: &str = "type Query { me: User } type User { id: ID name: String }";
+
+    #[test]
+    fn keeps_the_locations_of_templates_moved_by_an_edit() {
+        let root = std::env::temp_dir().join(format!("relay-lsp-state-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("schema.graphql"), SCHEMA).unwrap();
+        fs::write(
+            root.join("relay.config.json"),
+            r#"{
+  "sources": { "src": "app" },
+  "projects": {
+    "app": { "schema": "schema.graphql", "language": "javascript" }
+  }
+}"#,
+        )
+        .unwrap();
+        let config = Config::load(root.join("relay.config.json")).unwrap();
+        let (sender, _receiver) = unbounded();
+        let task_queue: TaskQueue<LSPState<NoopPerfLogger, SDLSchema>, _> =
+            TaskQueue::new(Arc::new(LSPTaskProcessor));
+        let lsp_state = LSPState::<NoopPerfLogger, SDLSchema>::new(
+            Arc::new(config),
+            sender,
+            task_queue.get_scheduler(),
+            Arc::new(NoopPerfLogger),
+            Box::new(DummyExtraDataProvider::new()),
+            None,
+            None,
+            InlayHintsConfig::default(),
+            CompletionConfig::default(),
+            false,
+        );
+        lsp_state.schemas.insert(
+            "app".intern(),
+            Arc::new(schema::build_schema(SCHEMA).unwrap()),
+        );
+        let url = Url::from_file_path(root.join("src/App.js")).unwrap();
+
+        let user_template = "graphql`fragment App_user on User { unknown }`;\n";
+        lsp_state.document_opened(&url, user_template).unwrap();
+        lsp_state.validate_synced_sources(&url).unwrap();
+        let viewer_template = "graphql`fragment App_viewer on User { id }`;\n";
+        lsp_state
+            .document_changed(&url, &format!("{}{}", viewer_template, user_template))
+            .unwrap();
+        lsp_state.validate_synced_sources(&url).unwrap();
+
+        let document = lsp_state.validated_documents.get(&url).unwrap();
+        let locations = document
+            .sources
+            .iter()
+            .map(|source| {
+                (
+                    source.definitions[0].name().unwrap().lookup(),
+                    source.definitions[0].location().source_location(),
+                    source
+                        .diagnostics
+                        .iter()
+                        .map(|diagnostic| diagnostic.location().source_location())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let embedded = |index| SourceLocationKey::embedded(url.as_str(), index);
+        assert_eq!(
+            locations,
+            vec![
+                ("App_viewer", embedded(0), vec![]),
+                ("App_user", embedded(1), vec![embedded(1)]),
+            ]
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn finds_the_previous_lines_of_unchanged_templates() {
+        let unchanged_lines = UnchangedLines::new("a\nb\nc\nd", "a\nB\nx\nc\nd");
+        // Before the edit
+        assert_eq!(unchanged_lines.previous_line_index(0, "a"), Some(0));
+        // Ends on a changed line
+        assert_eq!(unchanged_lines.previous_line_index(0, "a\nB"), None);
+        assert_eq!(unchanged_lines.previous_line_index(2, "x"), None);
+        // Moved down by the inserted line
+        assert_eq!(unchanged_lines.previous_line_index(3, "c\nd"), Some(2));
+    }
+}
@@ -5,8 +5,15 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::RwLock;
+use std::time::Duration;
+use std::time::SystemTime;
 
 use common::PerfLogEvent;
 use common::PerfLogger;
@@ -16,6 +23,7 @@ use graphql_ir::FragmentDefinitionNameSet;
 use graphql_watchman::WatchmanFileSourceSubscriptionNextChange;
 use intern::string_key::StringKey;
 use log::debug;
+use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
 use relay_compiler::build_project::get_project_asts;
 use relay_compiler::build_project::ProjectAstData;
@@ -24,6 +32,7 @@ use relay_compiler::build_raw_program;
 use relay_compiler::build_schema;
 use relay_compiler::compiler_state::CompilerState;
 use relay_compiler::compiler_state::ProjectName;
+use relay_compiler::config::Config;
 use relay_compiler::config::ProjectConfig;
 use relay_compiler::errors::BuildProjectError;
 use relay_compiler::errors::Error;
@@ -40,11 +49,16 @@ use schema::SDLSchema;
 use schema_documentation::SchemaDocumentation;
 use tokio::task;
 use tokio::task::JoinHandle;
+use tokio::time::sleep;
 
 use super::lsp_state::ProjectStatus;
 use super::lsp_state::Task;
+use crate::references::FragmentSpreadIndex;
+use crate::status_updater::send_relay_status;
 use crate::status_updater::set_ready_status;
 use crate::status_updater::update_in_progress_status;
+use crate::status_updater::RelayServerState;
+use crate::status_updater::WorkDoneProgressReporter;
 use crate::LSPState;
 
 /// This structure is responsible for keeping schemas/programs in sync with the current state of the world
@@ -57,6 +71,9 @@ pub(crate) struct LSPStateResources<
 
 const MAX_ERROR_COUNT: usize = 3;
 
+/// How often the config file is checked for changes
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentation + 'static>
     LSPStateResources<TPerfLogger, TSchemaDocumentation>
 {
@@ -80,6 +97,7 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         'outer: loop {
             debug!("Initializing resources for LSP server");
 
+            self.send_relay_status(RelayServerState::Compiling);
             update_in_progress_status(
                 "Relay: watchman...",
                 Some("Sending watchman query to get source files and possible saved state"),
@@ -91,11 +109,12 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                 .create_event("lsp_state_initialize_resources");
             let timer = setup_event.start("lsp_state_initialize_resources_time");
 
-            let file_source = match FileSource::connect(&self.lsp_state.config, &setup_event).await
-            {
+            let config = self.lsp_state.config();
+            let file_source = match FileSource::connect(&config, &setup_event).await {
                 Ok(f) => f,
                 Err(error) => {
                     self.log_errors("watch_build_error", &error);
+                    self.send_relay_status(RelayServerState::Error);
                     error_count += 1;
                     if error_count == MAX_ERROR_COUNT {
                         panic!("{}", error);
@@ -110,6 +129,7 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                 Ok(f) => f,
                 Err(error) => {
                     self.log_errors("watch_build_error", &error);
+                    self.send_relay_status(RelayServerState::Error);
                     error_count += 1;
                     if error_count == MAX_ERROR_COUNT {
                         panic!("{}", error);
@@ -131,6 +151,8 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                 pending_file_source_changes,
                 Arc::clone(&compiler_state.source_control_update_status),
             );
+            let config_changed = Arc::new(AtomicBool::new(false));
+            let config_watcher_handle = self.config_file_watcher(Arc::clone(&config_changed));
             update_in_progress_status(
                 "Relay: creating state...",
                 Some("Building schemas and source programs for LSP"),
@@ -142,10 +164,16 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                 .clear_regular_diagnostics();
 
             // Run initial build, before entering the watch changes loop
-            if let Err(error) = self.build_projects(&mut compiler_state, &setup_event) {
-                self.publish_errors(&error, "lsp_state_error");
+            let progress = self
+                .lsp_state
+                .begin_work_done_progress("Relay: building projects", None);
+            let result = self.build_projects(&mut compiler_state, &setup_event, progress.as_ref());
+            drop(progress);
+            if let Err(error) = &result {
+                self.publish_errors(error, "lsp_state_error");
             }
             set_ready_status(&self.lsp_state.sender);
+            self.send_build_status(&result);
 
             setup_event.stop(timer);
             setup_event.complete();
@@ -155,10 +183,20 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
 
             // Here we will wait for changes from watchman
             'inner: loop {
-                // Wait for a notify from watchman updates, or when a Relay file
-                // from an unactivated project is opened in VSCode
+                // Wait for a notify from watchman updates, config file changes, or
+                // when a Relay file from an unactivated project is opened in VSCode
                 self.lsp_state.notify_lsp_state_resources.notified().await;
 
+                // The config changed, we need to re-initialize resources for LSP with the new
+                // config. If it can't be loaded, we keep using the previous one.
+                if config_changed.swap(false, Ordering::SeqCst) && self.reload_config() {
+                    subscription_handle.abort();
+                    if let Some(config_watcher_handle) = config_watcher_handle {
+                        config_watcher_handle.abort();
+                    }
+                    continue 'outer;
+                }
+
                 // Source control update started, we can ignore all pending changes, and wait for it to complete,
                 // we may change the status bar to `Source Control Update...`
                 if compiler_state.source_control_update_status.is_started() {
@@ -174,6 +212,9 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                 if compiler_state.source_control_update_status.is_completed() {
                     debug!("Watchman indicated the the source control update has completed!");
                     subscription_handle.abort();
+                    if let Some(config_watcher_handle) = config_watcher_handle {
+                        config_watcher_handle.abort();
+                    }
                     continue 'outer;
                 }
 
@@ -183,10 +224,12 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                     .create_event("lsp_state_watchman_event");
                 let log_time = log_event.start("lsp_state_watchman_event_time");
 
-                if let Err(error) = self.incremental_build(&mut compiler_state, &log_event) {
-                    self.publish_errors(&error, "lsp_state_user_error");
+                let result = self.incremental_build(&mut compiler_state, &log_event);
+                if let Err(error) = &result {
+                    self.publish_errors(error, "lsp_state_user_error");
                 }
                 set_ready_status(&self.lsp_state.sender);
+                self.send_build_status(&result);
 
                 log_event.stop(log_time);
                 log_event.complete();
@@ -199,8 +242,9 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         compiler_state: &mut CompilerState,
         log_event: &impl PerfLogEvent,
     ) -> Result<(), Error> {
+        let config = self.lsp_state.config();
         let has_new_changes = compiler_state.merge_file_source_changes(
-            &self.lsp_state.config,
+            &config,
             self.lsp_state.perf_logger.as_ref(),
             false,
         )?;
@@ -215,17 +259,31 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         {
             debug!("LSP server detected changes...");
 
+            self.send_relay_status(RelayServerState::Compiling);
             self.lsp_state
                 .diagnostic_reporter
                 .clear_regular_diagnostics();
 
-            update_in_progress_status(
-                "Relay: checking...",
-                Some("Validating changes, and updating source programs with the latest changes."),
-                &self.lsp_state.sender,
-            );
+            let progress = if config.enabled_projects().any(|project_config| {
+                compiler_state.project_has_pending_schema_changes(project_config.name)
+            }) {
+                update_in_progress_status(
+                    "Relay: reloading schema...",
+                    Some("Rebuilding schemas and source programs with the changed schema files."),
+                    &self.lsp_state.sender,
+                );
+                self.lsp_state
+                    .begin_work_done_progress("Relay: reloading schema", None)
+            } else {
+                update_in_progress_status(
+                    "Relay: checking...",
+                    Some("Validating changes, and updating source programs with the latest changes."),
+                    &self.lsp_state.sender,
+                );
+                None
+            };
 
-            self.build_projects(compiler_state, log_event)?;
+            self.build_projects(compiler_state, log_event, progress.as_ref())?;
         }
 
         Ok(())
@@ -235,11 +293,14 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         &self,
         compiler_state: &mut CompilerState,
         log_event: &impl PerfLogEvent,
+        progress: Option<&WorkDoneProgressReporter>,
     ) -> Result<(), Error> {
+        let config = self.lsp_state.config();
         let graphql_asts = log_event.time("parse_sources_time", || {
             GraphQLAsts::from_graphql_sources_map(
+                &config,
                 &compiler_state.graphql_sources,
-                &compiler_state.get_dirty_definitions(&self.lsp_state.config),
+                &compiler_state.get_dirty_definitions(&config),
             )
         })?;
 
@@ -249,9 +310,7 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         }
 
         let timer = log_event.start("build_lsp_projects");
-        let build_results: Vec<_> = self
-            .lsp_state
-            .config
+        let project_configs: Vec<&ProjectConfig> = config
             .par_enabled_projects()
             .filter(|project_config| {
                 // Filter inactive projects
@@ -277,7 +336,21 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                 }
                 compiler_state.project_has_pending_changes(project_config.name)
             })
-            .map(|project_config| self.build_project(project_config, compiler_state, &graphql_asts))
+            .collect();
+        let built_project_count = AtomicUsize::new(0);
+        let build_results: Vec<_> = project_configs
+            .par_iter()
+            .map(|project_config| {
+                let result = self.build_project(project_config, compiler_state, &graphql_asts);
+                if let Some(progress) = progress {
+                    let built = built_project_count.fetch_add(1, Ordering::SeqCst) + 1;
+                    progress.report(
+                        format!("{}/{} projects", built, project_configs.len()),
+                        Some((built * 100 / project_configs.len()) as u32),
+                    );
+                }
+                result
+            })
             .collect();
         log_event.stop(timer);
 
@@ -344,15 +417,17 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         project_config: &ProjectConfig,
         graphql_asts_map: &FnvHashMap<ProjectName, GraphQLAsts>,
     ) -> Result<Arc<SDLSchema>, BuildProjectFailure> {
+        let config = self.lsp_state.config();
         match self.lsp_state.schemas.entry(project_config.name) {
             Entry::Vacant(e) => {
-                let schema = build_schema(compiler_state, project_config, graphql_asts_map)
-                    .map_err(|errors| {
-                        BuildProjectFailure::Error(BuildProjectError::ValidationErrors {
-                            errors,
-                            project_name: project_config.name,
-                        })
-                    })?;
+                let schema =
+                    build_schema(&config, compiler_state, project_config, graphql_asts_map)
+                        .map_err(|errors| {
+                            BuildProjectFailure::Error(BuildProjectError::ValidationErrors {
+                                errors,
+                                project_name: project_config.name,
+                            })
+                        })?;
                 e.insert(Arc::clone(&schema));
                 Ok(schema)
             }
@@ -360,14 +435,15 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                 if !compiler_state.project_has_pending_schema_changes(project_config.name) {
                     Ok(Arc::clone(e.get()))
                 } else {
-                    let schema = build_schema(compiler_state, project_config, graphql_asts_map)
-                        .map_err(|errors| {
-                            debug!("build error");
-                            BuildProjectFailure::Error(BuildProjectError::ValidationErrors {
-                                errors,
-                                project_name: project_config.name,
-                            })
-                        })?;
+                    let schema =
+                        build_schema(&config, compiler_state, project_config, graphql_asts_map)
+                            .map_err(|errors| {
+                                debug!("build error");
+                                BuildProjectFailure::Error(BuildProjectError::ValidationErrors {
+                                    errors,
+                                    project_name: project_config.name,
+                                })
+                            })?;
                     e.insert(Arc::clone(&schema));
                     Ok(schema)
                 }
@@ -385,25 +461,28 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         schema: Arc<SDLSchema>,
         log_event: &impl PerfLogEvent,
     ) -> Result<(), BuildProjectFailure> {
-        let is_incremental_build = self
+        // The source program keeps the IR of the definitions that are not
+        // rebuilt, which refers to the previous schema, so schema changes that
+        // affect any definition rebuild all of them.
+        let affected_schema_members = if self
             .lsp_state
             .source_programs
             .contains_key(&project_config.name)
             && compiler_state.has_processed_changes()
-            && !compiler_state
-                .has_breaking_schema_change(project_config.name, &project_config.schema_config)
-            && if let Some(base) = project_config.base {
-                !compiler_state.has_breaking_schema_change(base, &project_config.schema_config)
-            } else {
-                true
-            };
+        {
+            compiler_state
+                .get_affected_schema_members(project_config)
+                .filter(|members| members.is_empty())
+        } else {
+            None
+        };
 
         let (base_program, _) = build_raw_program(
             project_config,
             project_asts,
             schema,
             log_event,
-            is_incremental_build,
+            affected_schema_members.as_ref(),
         )?;
 
         if compiler_state.should_cancel_current_build() {
@@ -411,22 +490,26 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             return Err(BuildProjectFailure::Cancelled);
         }
 
-        match self.lsp_state.source_programs.entry(project_config.name) {
-            Entry::Vacant(e) => {
-                e.insert(base_program.clone());
-            }
+        let fragment_spread_index = match self.lsp_state.source_programs.entry(project_config.name)
+        {
+            Entry::Vacant(e) => FragmentSpreadIndex::from_program(&e.insert(base_program.clone())),
             Entry::Occupied(mut e) => {
                 let program = e.get_mut();
                 let removed_definition_names = graphql_asts
                     .get(&project_config.name)
                     .map(|ast| ast.removed_definition_names.as_ref());
                 program.merge_program(&base_program, removed_definition_names);
+                FragmentSpreadIndex::from_program(program)
             }
-        }
+        };
+        self.lsp_state
+            .fragment_spread_indexes
+            .insert(project_config.name, Arc::new(fragment_spread_index));
 
         // Call validation rules that go beyond type checking.
+        let config = self.lsp_state.config();
         validate_program(
-            &self.lsp_state.config,
+            &config,
             project_config,
             &base_program,
             log_event,
@@ -438,11 +521,60 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             Arc::new(base_fragment_names),
             Arc::clone(&self.lsp_state.perf_logger),
             log_event,
-            self.lsp_state.config.custom_transforms.as_ref(),
+            config.custom_transforms.as_ref(),
+            None,
         )?;
         Ok(())
     }
 
+    /// Loads the changed config file, and replaces the config of the LSP state
+    /// with it. Returns `false` if the config couldn't be loaded.
+    fn reload_config(&self) -> bool {
+        let config_path = match &self.lsp_state.config().config_path {
+            Some(config_path) => config_path.clone(),
+            None => return false,
+        };
+        update_in_progress_status(
+            "Relay: reloading config...",
+            Some("Rebuilding schemas and source programs with the changed Relay config"),
+            &self.lsp_state.sender,
+        );
+
+        match Config::load(config_path) {
+            Ok(config) => {
+                debug!("Reloaded the Relay config");
+                self.lsp_state.reload_config(config);
+                true
+            }
+            Err(error) => {
+                self.publish_errors(&error, "lsp_config_reload_error");
+                set_ready_status(&self.lsp_state.sender);
+                self.send_relay_status(RelayServerState::Error);
+                false
+            }
+        }
+    }
+
+    /// Start a separate task checking the config file for changes, which
+    /// notifies when it's modified. There's nothing to watch if the config
+    /// wasn't loaded from a file.
+    fn config_file_watcher(&self, config_changed: Arc<AtomicBool>) -> Option<JoinHandle<()>> {
+        let config_path = self.lsp_state.config().config_path.clone()?;
+        let notify_sender = self.lsp_state.notify_lsp_state_resources.clone();
+        Some(task::spawn(async move {
+            let mut last_modified = modified_time(&config_path);
+            loop {
+                sleep(CONFIG_POLL_INTERVAL).await;
+                let modified = modified_time(&config_path);
+                if modified != last_modified {
+                    last_modified = modified;
+                    config_changed.store(true, Ordering::SeqCst);
+                    notify_sender.notify_one();
+                }
+            }
+        }))
+    }
+
     fn watchman_subscription_handler(
         &self,
         mut file_source_subscription: FileSourceSubscription,
@@ -493,6 +625,24 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         error_event.complete();
     }
 
+    /// Notify the client of the state of the server, along with the current error count
+    fn send_relay_status(&self, state: RelayServerState) {
+        send_relay_status(
+            &self.lsp_state.sender,
+            state,
+            self.lsp_state.diagnostic_reporter.error_count(),
+        );
+    }
+
+    fn send_build_status(&self, result: &Result<(), Error>) {
+        match result {
+            Ok(()) => self.send_relay_status(RelayServerState::Idle),
+            // A newer build is about to start
+            Err(Error::Cancelled) => {}
+            Err(_) => self.send_relay_status(RelayServerState::Error),
+        }
+    }
+
     /// Log errors and report the diagnostics to IDE
     fn publish_errors(&self, error: &Error, log_event_name: &'static str) {
         self.lsp_state.diagnostic_reporter.report_error(error);
@@ -500,3 +650,9 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         self.log_errors(log_event_name, error)
     }
 }
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -41,16 +41,28 @@ use lsp_types::notification::DidOpenTextDocument;
 use lsp_types::notification::DidSaveTextDocument;
 use lsp_types::notification::Exit;
 use lsp_types::request::CodeActionRequest;
+use lsp_types::request::CodeLensRequest;
 use lsp_types::request::Completion;
+use lsp_types::request::DocumentSymbolRequest;
 use lsp_types::request::GotoDefinition;
 use lsp_types::request::HoverRequest;
+use lsp_types::request::InlayHintRequest;
+use lsp_types::request::RangeFormatting;
 use lsp_types::request::References;
+use lsp_types::request::Rename;
 use lsp_types::request::ResolveCompletionItem;
+use lsp_types::request::SemanticTokensFullRequest;
 use lsp_types::request::Shutdown;
+use lsp_types::request::SignatureHelpRequest;
 use lsp_types::CodeActionProviderCapability;
+use lsp_types::CodeLensOptions;
 use lsp_types::CompletionOptions;
 use lsp_types::InitializeParams;
+use lsp_types::SemanticTokensFullOptions;
+use lsp_types::SemanticTokensOptions;
+use lsp_types::SemanticTokensServerCapabilities;
 use lsp_types::ServerCapabilities;
+use lsp_types::SignatureHelpOptions;
 use lsp_types::TextDocumentSyncCapability;
 use lsp_types::TextDocumentSyncKind;
 use lsp_types::WorkDoneProgressOptions;
@@ -61,31 +73,47 @@ use schema_documentation::SchemaDocumentationLoader;
 
 use self::task_queue::TaskProcessor;
 use crate::code_action::on_code_action;
+use crate::code_lens::on_code_lens;
+use crate::compiled_output::on_preview_compiled_output;
+use crate::compiled_output::PreviewCompiledOutput;
 use crate::completion::on_completion;
 use crate::completion::on_resolve_completion_item;
+use crate::document_symbols::on_document_symbol;
 use crate::explore_schema_for_type::on_explore_schema_for_type;
 use crate::explore_schema_for_type::ExploreSchemaForType;
 use crate::find_field_usages::on_find_field_usages;
+use crate::find_field_usages::on_find_schema_coordinate_usages;
 use crate::find_field_usages::FindFieldUsages;
+use crate::find_field_usages::FindSchemaCoordinateUsages;
+use crate::formatting::on_range_formatting;
 use crate::goto_definition::on_get_source_location_of_type_definition;
 use crate::goto_definition::on_goto_definition;
 use crate::goto_definition::GetSourceLocationOfTypeDefinition;
 use crate::graphql_tools::on_graphql_execute_query;
+use crate::graphql_tools::on_print_inlined_operation;
 use crate::graphql_tools::GraphQLExecuteQuery;
+use crate::graphql_tools::PrintInlinedOperation;
 use crate::hover::on_hover;
+use crate::inlay_hints::on_inlay_hint;
 use crate::js_language_server::JSLanguageServer;
 use crate::lsp_process_error::LSPProcessResult;
 use crate::lsp_runtime_error::LSPRuntimeError;
 use crate::references::on_references;
+use crate::rename::on_rename;
 use crate::resolved_types_at_location::on_get_resolved_types_at_location;
 use crate::resolved_types_at_location::ResolvedTypesAtLocation;
+use crate::schema_tree::on_get_schema_tree;
+use crate::schema_tree::GetSchemaTree;
 use crate::search_schema_items::on_search_schema_items;
 use crate::search_schema_items::SearchSchemaItems;
+use crate::semantic_tokens::on_semantic_tokens_full;
+use crate::semantic_tokens::semantic_tokens_legend;
 use crate::server::lsp_state::handle_lsp_state_tasks;
 use crate::server::lsp_state_resources::LSPStateResources;
 use crate::server::task_queue::TaskQueue;
 use crate::shutdown::on_exit;
 use crate::shutdown::on_shutdown;
+use crate::signature_help::on_signature_help;
 use crate::status_reporter::LSPStatusReporter;
 use crate::text_documents::on_cancel;
 use crate::text_documents::on_did_change_text_document;
@@ -110,10 +138,32 @@ pub fn initialize(connection: &Connection) -> LSPProcessResult<InitializeParams>
             ..Default::default()
         }),
 
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".into(), ",".into()]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+
         hover_provider: Some(lsp_types::HoverProviderCapability::Simple(true)),
         definition_provider: Some(lsp_types::OneOf::Left(true)),
         references_provider: Some(lsp_types::OneOf::Left(true)),
+        rename_provider: Some(lsp_types::OneOf::Left(true)),
         code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        code_lens_provider: Some(CodeLensOptions {
+            resolve_provider: Some(false),
+        }),
+        document_range_formatting_provider: Some(lsp_types::OneOf::Left(true)),
+        inlay_hint_provider: Some(lsp_types::OneOf::Left(true)),
+        document_symbol_provider: Some(lsp_types::OneOf::Left(true)),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                legend: semantic_tokens_legend(),
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+                ..Default::default()
+            },
+        )),
         ..Default::default()
     };
 
@@ -136,7 +186,7 @@ pub async fn run<
 >(
     connection: Connection,
     mut config: Config,
-    _params: InitializeParams,
+    params: InitializeParams,
     perf_logger: Arc<TPerfLogger>,
     extra_data_provider: Box<dyn LSPExtraDataProvider + Send + Sync>,
     schema_documentation_loader: Option<Box<dyn SchemaDocumentationLoader<TSchemaDocumentation>>>,
@@ -160,8 +210,32 @@ where
     config.status_reporter = Box::new(LSPStatusReporter::new(
         config.root_dir.clone(),
         connection.sender.clone(),
+        config.extract_options(),
     ));
 
+    // Clients can turn off some of the inlay hints with the `inlayHints` initialization option.
+    let inlay_hints_config = params
+        .initialization_options
+        .as_ref()
+        .and_then(|options| options.get("inlayHints"))
+        .and_then(|options| serde_json::from_value(options.clone()).ok())
+        .unwrap_or_default();
+
+    // And the snippets with the required selections of linked fields with the `completion` option.
+    let completion_config = params
+        .initialization_options
+        .as_ref()
+        .and_then(|options| options.get("completion"))
+        .and_then(|options| serde_json::from_value(options.clone()).ok())
+        .unwrap_or_default();
+
+    let work_done_progress = params
+        .capabilities
+        .window
+        .as_ref()
+        .and_then(|window| window.work_done_progress)
+        .unwrap_or(false);
+
     let lsp_state = Arc::new(LSPState::new(
         Arc::new(config),
         connection.sender.clone(),
@@ -170,6 +244,9 @@ where
         extra_data_provider,
         schema_documentation_loader,
         js_resource,
+        inlay_hints_config,
+        completion_config,
+        work_done_progress,
     ));
 
     LSPStateResources::new(Arc::clone(&lsp_state)).watch();
@@ -233,19 +310,30 @@ fn dispatch_request(request: lsp_server::Request, lsp_state: &impl GlobalState)
             .on_request_sync::<ResolvedTypesAtLocation>(on_get_resolved_types_at_location)?
             .on_request_sync::<SearchSchemaItems>(on_search_schema_items)?
             .on_request_sync::<ExploreSchemaForType>(on_explore_schema_for_type)?
+            .on_request_sync::<GetSchemaTree>(on_get_schema_tree)?
             .on_request_sync::<GetSourceLocationOfTypeDefinition>(
                 on_get_source_location_of_type_definition,
             )?
             .on_request_sync::<HoverRequest>(on_hover)?
             .on_request_sync::<GotoDefinition>(on_goto_definition)?
             .on_request_sync::<References>(on_references)?
+            .on_request_sync::<Rename>(on_rename)?
             .on_request_sync::<Completion>(on_completion)?
             .on_request_sync::<ResolveCompletionItem>(on_resolve_completion_item)?
             .on_request_sync::<CodeActionRequest>(on_code_action)?
+            .on_request_sync::<CodeLensRequest>(on_code_lens)?
+            .on_request_sync::<RangeFormatting>(on_range_formatting)?
+            .on_request_sync::<InlayHintRequest>(on_inlay_hint)?
+            .on_request_sync::<SemanticTokensFullRequest>(on_semantic_tokens_full)?
+            .on_request_sync::<DocumentSymbolRequest>(on_document_symbol)?
+            .on_request_sync::<SignatureHelpRequest>(on_signature_help)?
             .on_request_sync::<Shutdown>(on_shutdown)?
             .on_request_sync::<GraphQLExecuteQuery>(on_graphql_execute_query)?
+            .on_request_sync::<PrintInlinedOperation>(on_print_inlined_operation)?
+            .on_request_sync::<PreviewCompiledOutput>(on_preview_compiled_output)?
             .on_request_sync::<HeartbeatRequest>(on_heartbeat)?
             .on_request_sync::<FindFieldUsages>(on_find_field_usages)?
+            .on_request_sync::<FindSchemaCoordinateUsages>(on_find_schema_coordinate_usages)?
             .request();
 
         // If we have gotten here, we have not handled the request
@@ -0,0 +1,252 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing signature help: the arguments of the field or
+//! directive whose argument list contains the cursor.
+
+use common::DirectiveName;
+use graphql_syntax::Argument;
+use graphql_syntax::Directive;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::ExecutableDocument;
+use graphql_syntax::Identifier;
+use graphql_syntax::List;
+use graphql_syntax::OperationKind;
+use graphql_syntax::Selection;
+use intern::string_key::StringKey;
+use lsp_types::request::Request;
+use lsp_types::request::SignatureHelpRequest;
+use lsp_types::Documentation;
+use lsp_types::MarkupContent;
+use lsp_types::MarkupKind;
+use lsp_types::ParameterInformation;
+use lsp_types::ParameterLabel;
+use lsp_types::SignatureHelp;
+use lsp_types::SignatureInformation;
+use schema::ArgumentDefinitions;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+use schema::TypeReference;
+
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+
+pub fn on_signature_help(
+    state: &impl GlobalState,
+    params: <SignatureHelpRequest as Request>::Params,
+) -> LSPRuntimeResult<<SignatureHelpRequest as Request>::Result> {
+    let (document, position_span) =
+        state.extract_executable_document_from_text(&params.text_document_position_params, 0)?;
+    let project_name = state
+        .extract_project_name_from_url(&params.text_document_position_params.text_document.uri)?;
+    let schema = state.get_schema(&project_name)?;
+
+    Ok(get_signature_help(&document, &schema, position_span.start))
+}
+
+fn get_signature_help(
+    document: &ExecutableDocument,
+    schema: &SDLSchema,
+    position: u32,
+) -> Option<SignatureHelp> {
+    let finder = InvocationFinder { schema, position };
+    let invocation = document
+        .definitions
+        .iter()
+        .find_map(|definition| finder.find_in_definition(definition))?;
+    Some(create_signature_help(invocation, schema, position))
+}
+
+/// A field or directive, with the argument list containing the position.
+struct Invocation<'a> {
+    name: String,
+    arguments: &'a List<Argument>,
+    definitions: &'a ArgumentDefinitions,
+    type_: Option<&'a TypeReference<Type>>,
+    description: Option<StringKey>,
+}
+
+struct InvocationFinder<'a> {
+    schema: &'a SDLSchema,
+    position: u32,
+}
+
+impl<'a> InvocationFinder<'a> {
+    fn find_in_definition(&self, definition: &'a ExecutableDefinition) -> Option<Invocation<'a>> {
+        match definition {
+            ExecutableDefinition::Operation(operation) => {
+                self.find_in_directives(&operation.directives).or_else(|| {
+                    let parent_type = match operation.operation_kind() {
+                        OperationKind::Query => self.schema.query_type(),
+                        OperationKind::Mutation => self.schema.mutation_type(),
+                        OperationKind::Subscription => self.schema.subscription_type(),
+                    }?;
+                    self.find_in_selections(parent_type, &operation.selections.items)
+                })
+            }
+            ExecutableDefinition::Fragment(fragment) => {
+                self.find_in_directives(&fragment.directives).or_else(|| {
+                    let parent_type = self.schema.get_type(fragment.type_condition.type_.value)?;
+                    self.find_in_selections(parent_type, &fragment.selections.items)
+                })
+            }
+        }
+    }
+
+    fn find_in_selections(
+        &self,
+        parent_type: Type,
+        selections: &'a [Selection],
+    ) -> Option<Invocation<'a>> {
+        selections.iter().find_map(|selection| match selection {
+            Selection::ScalarField(field) => self.find_in_field(
+                parent_type,
+                &field.name,
+                field.arguments.as_ref(),
+                &field.directives,
+                &[],
+            ),
+            Selection::LinkedField(field) => self.find_in_field(
+                parent_type,
+                &field.name,
+                field.arguments.as_ref(),
+                &field.directives,
+                &field.selections.items,
+            ),
+            Selection::InlineFragment(fragment) => {
+                self.find_in_directives(&fragment.directives).or_else(|| {
+                    let type_ = match &fragment.type_condition {
+                        Some(type_condition) => self.schema.get_type(type_condition.type_.value)?,
+                        None => parent_type,
+                    };
+                    self.find_in_selections(type_, &fragment.selections.items)
+                })
+            }
+            Selection::FragmentSpread(spread) => self.find_in_directives(&spread.directives),
+        })
+    }
+
+    fn find_in_field(
+        &self,
+        parent_type: Type,
+        name: &Identifier,
+        arguments: Option<&'a List<Argument>>,
+        directives: &'a [Directive],
+        selections: &'a [Selection],
+    ) -> Option<Invocation<'a>> {
+        let field = self
+            .schema
+            .field(self.schema.named_field(parent_type, name.value)?);
+        if let Some(arguments) = arguments {
+            if self.is_within(arguments) {
+                return Some(Invocation {
+                    name: field.name.item.to_string(),
+                    arguments,
+                    definitions: &field.arguments,
+                    type_: Some(&field.type_),
+                    description: field.description,
+                });
+            }
+        }
+        self.find_in_directives(directives)
+            .or_else(|| self.find_in_selections(field.type_.inner(), selections))
+    }
+
+    fn find_in_directives(&self, directives: &'a [Directive]) -> Option<Invocation<'a>> {
+        directives.iter().find_map(|directive| {
+            let arguments = directive.arguments.as_ref()?;
+            if !self.is_within(arguments) {
+                return None;
+            }
+            let definition = self
+                .schema
+                .get_directive(DirectiveName(directive.name.value))?;
+            Some(Invocation {
+                name: format!("@{}", directive.name.value),
+                arguments,
+                definitions: &definition.arguments,
+                type_: None,
+                description: definition.description,
+            })
+        })
+    }
+
+    /// Whether the position is between the parentheses of the argument list.
+    fn is_within(&self, arguments: &List<Argument>) -> bool {
+        arguments.start.span.end <= self.position && self.position <= arguments.end.span.start
+    }
+}
+
+fn create_signature_help(
+    invocation: Invocation<'_>,
+    schema: &SDLSchema,
+    position: u32,
+) -> SignatureHelp {
+    let provided_arguments = &invocation.arguments.items;
+    let current_argument = provided_arguments
+        .iter()
+        .find(|argument| argument.span.start <= position && position <= argument.span.end);
+
+    let mut label = format!("{}(", invocation.name);
+    let mut parameters = vec![];
+    let mut active_parameter = None;
+    for (index, definition) in invocation.definitions.iter().enumerate() {
+        if index > 0 {
+            label.push_str(", ");
+        }
+        let start = label.len();
+        label.push_str(&format!(
+            "{}: {}",
+            definition.name,
+            schema.get_type_string(&definition.type_)
+        ));
+        if let Some(default_value) = &definition.default_value {
+            label.push_str(&format!(" = {}", default_value));
+        }
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start as u32, label.len() as u32]),
+            documentation: definition.description.map(markdown_documentation),
+        });
+
+        // The argument at the cursor, or else the first one left to provide
+        let is_active = match current_argument {
+            Some(argument) => argument.name.value == definition.name.0,
+            None => !provided_arguments
+                .iter()
+                .any(|argument| argument.name.value == definition.name.0),
+        };
+        if is_active && active_parameter.is_none() {
+            active_parameter = Some(index as u32);
+        }
+    }
+    label.push(')');
+    if let Some(type_) = invocation.type_ {
+        label.push_str(&format!(": {}", schema.get_type_string(type_)));
+    }
+
+    SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation: invocation.description.map(markdown_documentation),
+            parameters: Some(parameters),
+            active_parameter,
+        }],
+        active_signature: Some(0),
+        active_parameter,
+    }
+}
+
+fn markdown_documentation(value: StringKey) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod test;
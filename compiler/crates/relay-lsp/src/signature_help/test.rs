@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use graphql_syntax::parse_executable_with_error_recovery;
+use lsp_types::ParameterLabel;
+use lsp_types::SignatureHelp;
+use relay_test_schema::get_test_schema;
+
+use super::get_signature_help;
+
+/// The signature help at the `|` of the text.
+fn signature_help(text: &str) -> Option<SignatureHelp> {
+    let position = text.find('|').unwrap();
+    let source = text.replace('|', "");
+    let document = parse_executable_with_error_recovery(
+        &source,
+        SourceLocationKey::standalone("/src/Foo.graphql"),
+    )
+    .item;
+    get_signature_help(&document, &get_test_schema(), position as u32)
+}
+
+/// The label, the parameter labels and the active parameter of the signature.
+fn describe(signature_help: SignatureHelp) -> (String, Vec<String>, Option<u32>) {
+    let signature = &signature_help.signatures[0];
+    let parameters = signature
+        .parameters
+        .iter()
+        .flatten()
+        .map(|parameter| match parameter.label {
+            ParameterLabel::LabelOffsets([start, end]) => {
+                signature.label[start as usize..end as usize].to_string()
+            }
+            ParameterLabel::Simple(_) => panic!("Expected label offsets"),
+        })
+        .collect();
+    (
+        signature.label.clone(),
+        parameters,
+        signature_help.active_parameter,
+    )
+}
+
+#[test]
+fn shows_field_arguments() {
+    let help = signature_help("query Foo { me { profilePicture(size: 32, |) { uri } } }").unwrap();
+    assert_eq!(
+        describe(help),
+        (
+            "profilePicture(size: [Int], preset: PhotoSize): Image".to_string(),
+            vec!["size: [Int]".to_string(), "preset: PhotoSize".to_string()],
+            Some(1)
+        )
+    );
+
+    let help = signature_help("query Foo { me { profilePicture(size: 3|2) { uri } } }").unwrap();
+    assert_eq!(describe(help).2, Some(0));
+}
+
+#[test]
+fn shows_directive_arguments() {
+    let help = signature_help("query Foo { me { name @include(|) } }").unwrap();
+    assert_eq!(
+        describe(help),
+        (
+            "@include(if: Boolean!)".to_string(),
+            vec!["if: Boolean!".to_string()],
+            Some(0)
+        )
+    );
+}
+
+#[test]
+fn ignores_positions_outside_of_arguments() {
+    assert_eq!(
+        signature_help("query Foo { me { profilePicture(size: 32) { u|ri } } }"),
+        None
+    );
+}
@@ -9,6 +9,7 @@
 use std::path::PathBuf;
 
 use crossbeam::channel::Sender;
+use extract_graphql::ExtractOptions;
 use log::info;
 use lsp_server::Message;
 use relay_compiler::errors::Error;
@@ -25,11 +26,15 @@ pub struct LSPStatusReporter {
 }
 
 impl LSPStatusReporter {
-    pub fn new(root_dir: PathBuf, sender: Sender<Message>) -> Self {
+    pub fn new(
+        root_dir: PathBuf,
+        sender: Sender<Message>,
+        extract_options: ExtractOptions,
+    ) -> Self {
         let sender_clone = sender.clone();
         Self {
             sender,
-            diagnostic_reporter: DiagnosticReporter::new(root_dir, sender_clone),
+            diagnostic_reporter: DiagnosticReporter::new(root_dir, sender_clone, extract_options),
         }
     }
 }
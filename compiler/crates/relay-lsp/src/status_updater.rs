@@ -9,17 +9,30 @@
 // We use two crates, lsp_types and lsp_server, for interacting with LSP. This module re-exports
 // types from both so that we have a central source-of-truth for all LSP-related utilities.
 
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
 use crossbeam::channel::SendError;
 use crossbeam::channel::Sender;
 use lsp_server::Message;
 use lsp_server::Notification as ServerNotification;
 use lsp_server::Request as ServerRequest;
 use lsp_types::notification::Notification;
+use lsp_types::notification::Progress as ProgressNotification;
 use lsp_types::notification::ShowMessage;
 use lsp_types::request::Request;
+use lsp_types::request::WorkDoneProgressCreate;
 use lsp_types::MessageActionItem;
 use lsp_types::MessageType;
+use lsp_types::NumberOrString;
+use lsp_types::ProgressParams;
+use lsp_types::ProgressParamsValue;
 use lsp_types::ShowMessageParams;
+use lsp_types::WorkDoneProgress;
+use lsp_types::WorkDoneProgressBegin;
+use lsp_types::WorkDoneProgressCreateParams;
+use lsp_types::WorkDoneProgressEnd;
+use lsp_types::WorkDoneProgressReport;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -111,6 +124,119 @@ pub fn update_status(
         .expect("update_status: failed to send");
 }
 
+#[derive(Debug)]
+pub enum RelayStatus {}
+
+/// The state of the LSP server, rendered by the clients in their status bar
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RelayServerState {
+    Idle,
+    Compiling,
+    Error,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayStatusParams {
+    pub state: RelayServerState,
+    /// The number of errors currently reported to the client
+    pub error_count: usize,
+}
+
+impl Notification for RelayStatus {
+    type Params = RelayStatusParams;
+    const METHOD: &'static str = "relay/status";
+}
+
+/// Sends the `relay/status` notification, so that the clients can show
+/// whether the server is compiling, and how many errors there are.
+pub(crate) fn send_relay_status(
+    sender: &Sender<Message>,
+    state: RelayServerState,
+    error_count: usize,
+) {
+    let notification = ServerNotification::new(
+        RelayStatus::METHOD.into(),
+        RelayStatusParams { state, error_count },
+    );
+    sender
+        .send(Message::Notification(notification))
+        .expect("send_relay_status: failed to send");
+}
+
+static NEXT_WORK_DONE_PROGRESS_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Reports the progress of a long running task with `$/progress` notifications.
+/// The progress is ended when this is dropped.
+pub(crate) struct WorkDoneProgressReporter {
+    token: NumberOrString,
+    sender: Sender<Message>,
+}
+
+impl WorkDoneProgressReporter {
+    /// Asks the client to create a progress with the `window/workDoneProgress/create`
+    /// request, and starts it. The client must support work done progress.
+    pub(crate) fn begin(
+        sender: &Sender<Message>,
+        title: impl Into<String>,
+        message: Option<String>,
+    ) -> Self {
+        let id = format!(
+            "relay/progress/{}",
+            NEXT_WORK_DONE_PROGRESS_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        let request = ServerRequest::new(
+            format!("{}/{}", WorkDoneProgressCreate::METHOD, id).into(),
+            WorkDoneProgressCreate::METHOD.into(),
+            WorkDoneProgressCreateParams {
+                token: NumberOrString::String(id.clone()),
+            },
+        );
+        sender
+            .send(Message::Request(request))
+            .expect("WorkDoneProgressReporter: failed to send");
+
+        let reporter = Self {
+            token: NumberOrString::String(id),
+            sender: sender.clone(),
+        };
+        reporter.send(WorkDoneProgress::Begin(WorkDoneProgressBegin {
+            title: title.into(),
+            cancellable: Some(false),
+            message,
+            percentage: Some(0),
+        }));
+        reporter
+    }
+
+    pub(crate) fn report(&self, message: impl Into<String>, percentage: Option<u32>) {
+        self.send(WorkDoneProgress::Report(WorkDoneProgressReport {
+            cancellable: Some(false),
+            message: Some(message.into()),
+            percentage,
+        }));
+    }
+
+    fn send(&self, progress: WorkDoneProgress) {
+        let notification = ServerNotification::new(
+            ProgressNotification::METHOD.into(),
+            ProgressParams {
+                token: self.token.clone(),
+                value: ProgressParamsValue::WorkDone(progress),
+            },
+        );
+        // The client may have gone away, in which case there is nobody to report to.
+        self.sender.send(Message::Notification(notification)).ok();
+    }
+}
+
+impl Drop for WorkDoneProgressReporter {
+    fn drop(&mut self) {
+        self.send(WorkDoneProgress::End(WorkDoneProgressEnd { message: None }));
+    }
+}
+
 /// Show a notification in the client
 #[allow(dead_code)]
 pub(crate) fn show_info_message(
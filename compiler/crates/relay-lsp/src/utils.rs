@@ -14,6 +14,7 @@ use dashmap::DashMap;
 use docblock_syntax::parse_docblock;
 use extract_graphql::JavaScriptSourceFeature;
 use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_syntax::parse_executable_with_error_recovery_and_parser_features;
 use graphql_syntax::ExecutableDefinition;
 use intern::string_key::StringKey;
 use log::debug;
@@ -21,6 +22,7 @@ use lsp_types::Position;
 use lsp_types::TextDocumentPositionParams;
 use lsp_types::Url;
 use relay_compiler::FileCategorizer;
+use relay_compiler::parser_features;
 use relay_compiler::FileGroup;
 use relay_compiler::ProjectConfig;
 use relay_docblock::parse_docblock_ast;
@@ -136,9 +138,10 @@ pub fn extract_feature_from_text(
 
     match javascript_feature {
         JavaScriptSourceFeature::GraphQL(graphql_source) => {
-            let document = parse_executable_with_error_recovery(
+            let document = parse_executable_with_error_recovery_and_parser_features(
                 &graphql_source.text_source().text,
                 source_location_key,
+                parser_features(&project_config.feature_flags),
             )
             .item;
 
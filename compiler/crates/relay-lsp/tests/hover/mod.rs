@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::path::Path;
 use std::sync::Arc;
 
 use common::SourceLocationKey;
@@ -53,6 +54,7 @@ pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
         &extra_data_provider,
         &schema,
         &program,
+        Path::new("/repo"),
         ContentConsumerType::Relay,
     )
     .ok_or("<NO HOVER RESPONSE>")?
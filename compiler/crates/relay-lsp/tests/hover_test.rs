@@ -12,6 +12,13 @@ mod hover;
 use hover::transform_fixture;
 use fixture_tests::test_fixture;
 
+#[test]
+fn deprecated_field_with_arguments() {
+    let input = include_str!("hover/fixtures/deprecated_field_with_arguments.graphql");
+    let expected = include_str!("hover/fixtures/deprecated_field_with_arguments.expected");
+    test_fixture(transform_fixture, "deprecated_field_with_arguments.graphql", "hover/fixtures/deprecated_field_with_arguments.expected", input, expected);
+}
+
 #[test]
 fn double_underscore_id_field() {
     let input = include_str!("hover/fixtures/double_underscore_id_field.graphql");
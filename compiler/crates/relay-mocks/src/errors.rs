@@ -0,0 +1,18 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use intern::string_key::StringKey;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MockError {
+    #[error("No operation or fragment named `{0}`.")]
+    UnknownDefinition(StringKey),
+
+    #[error("Invalid mock options: {0}")]
+    InvalidOptions(String),
+}
@@ -0,0 +1,439 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use fnv::FnvHashMap;
+use graphql_ir::ConditionValue;
+use graphql_ir::Directive;
+use graphql_ir::Field as _;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::OperationDefinitionName;
+use graphql_ir::Program;
+use graphql_ir::Selection;
+use intern::string_key::Intern;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use schema::FieldID;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+use schema::TypeReference;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::errors::MockError;
+use crate::options::MockOptions;
+use crate::options::ScalarContext;
+use crate::random::Random;
+
+/// Generates the response of the operation: `{"data": ...}`.
+pub fn mock_operation(
+    program: &Program,
+    name: OperationDefinitionName,
+    options: &MockOptions,
+) -> Result<Value, MockError> {
+    let operation = program
+        .operation(name)
+        .ok_or(MockError::UnknownDefinition(name.0))?;
+    let selections = operation.selections.iter().collect::<Vec<_>>();
+    let data =
+        Generator::new(program, options).mock_object(operation.type_, &selections, Role::Plain)?;
+    let mut response = Map::new();
+    response.insert("data".to_string(), data);
+    Ok(Value::Object(response))
+}
+
+/// Generates the data of the fragment, with the data of the fragments it
+/// spreads, like it is in the response of an operation.
+pub fn mock_fragment(
+    program: &Program,
+    name: FragmentDefinitionName,
+    options: &MockOptions,
+) -> Result<Value, MockError> {
+    let fragment = program
+        .fragment(name)
+        .ok_or(MockError::UnknownDefinition(name.0))?;
+    let selections = fragment.selections.iter().collect::<Vec<_>>();
+    Generator::new(program, options).mock_object(fragment.type_condition, &selections, Role::Plain)
+}
+
+/// Generates the response of the operation, or else the data of the
+/// fragment, with this name.
+pub fn mock_definition(
+    program: &Program,
+    name: StringKey,
+    options: &MockOptions,
+) -> Result<Value, MockError> {
+    if program.operation(OperationDefinitionName(name)).is_some() {
+        mock_operation(program, OperationDefinitionName(name), options)
+    } else {
+        mock_fragment(program, FragmentDefinitionName(name), options)
+    }
+}
+
+/// Where an object is in a connection, to generate edges, cursors and page
+/// info that match.
+#[derive(Clone, Copy)]
+enum Role {
+    Plain,
+    Connection { edges: usize },
+    Edges,
+    Edge { index: usize },
+    PageInfo { edges: usize },
+}
+
+/// The selections of a response key of an object, merged from the fragments
+/// and inline fragments that select it.
+struct FieldGroup<'a> {
+    key: StringKey,
+    definition: FieldID,
+    is_connection: bool,
+    selections: Vec<&'a Selection>,
+}
+
+struct Generator<'a> {
+    program: &'a Program,
+    schema: &'a SDLSchema,
+    options: &'a MockOptions,
+    random: Random,
+    /// The last number of the values of each scalar, or of the ids of each
+    /// type, or of the strings of each field.
+    numbers: FnvHashMap<StringKey, usize>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(program: &'a Program, options: &'a MockOptions) -> Self {
+        Self {
+            program,
+            schema: &program.schema,
+            options,
+            random: Random::new(options.seed),
+            numbers: Default::default(),
+        }
+    }
+
+    fn next_number(&mut self, key: StringKey) -> usize {
+        let number = self.numbers.entry(key).or_default();
+        *number += 1;
+        *number
+    }
+
+    fn mock_object(
+        &mut self,
+        type_: Type,
+        selections: &[&'a Selection],
+        role: Role,
+    ) -> Result<Value, MockError> {
+        let concrete_type = self.concrete_type(type_, selections)?;
+        let mut groups = Vec::new();
+        self.collect_fields(concrete_type, selections, &mut groups)?;
+
+        let mut object = Map::new();
+        for group in groups {
+            let field = self.schema.field(group.definition);
+            let name = field.name.item;
+            let value = if field.type_.inner().is_scalar() || field.type_.inner().is_enum() {
+                self.mock_scalar_field(concrete_type, group.definition, role)
+            } else {
+                let (child_role, length) = match role {
+                    Role::Connection { edges } if name.lookup() == "edges" => (Role::Edges, edges),
+                    Role::Connection { edges } if name.lookup() == "pageInfo" => {
+                        (Role::PageInfo { edges }, self.options.list_length)
+                    }
+                    _ if group.is_connection => (
+                        Role::Connection {
+                            edges: self.options.connection_length,
+                        },
+                        self.options.list_length,
+                    ),
+                    _ => (Role::Plain, self.options.list_length),
+                };
+                self.mock_linked_type(&field.type_, &group.selections, child_role, length)?
+            };
+            object.insert(group.key.lookup().to_string(), value);
+        }
+        Ok(Value::Object(object))
+    }
+
+    fn mock_linked_type(
+        &mut self,
+        type_: &TypeReference<Type>,
+        selections: &[&'a Selection],
+        role: Role,
+        length: usize,
+    ) -> Result<Value, MockError> {
+        match type_.nullable_type() {
+            TypeReference::List(item_type) => {
+                let mut items = Vec::with_capacity(length);
+                for index in 0..length {
+                    let item_role = match role {
+                        Role::Edges => Role::Edge { index },
+                        role => role,
+                    };
+                    items.push(self.mock_linked_type(
+                        item_type,
+                        selections,
+                        item_role,
+                        self.options.list_length,
+                    )?);
+                }
+                Ok(Value::Array(items))
+            }
+            _ => self.mock_object(type_.inner(), selections, role),
+        }
+    }
+
+    /// The object type of the value of an abstract type: one of the types
+    /// that the fragments and inline fragments select, if any.
+    fn concrete_type(&mut self, type_: Type, selections: &[&Selection]) -> Result<Type, MockError> {
+        let mut candidates = match type_ {
+            Type::Interface(id) => self
+                .schema
+                .interface(id)
+                .recursively_implementing_objects(self.schema)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            Type::Union(id) => self.schema.union(id).members.clone(),
+            _ => return Ok(type_),
+        };
+        candidates.retain(|id| !self.schema.object(*id).is_extension);
+        candidates.sort_by_key(|id| self.schema.object(*id).name.item.0.lookup());
+
+        let mut conditions = Vec::new();
+        for selection in selections {
+            match selection {
+                Selection::InlineFragment(fragment) => {
+                    conditions.extend(fragment.type_condition);
+                }
+                Selection::FragmentSpread(spread) => {
+                    conditions.push(self.fragment_type(spread.fragment.item)?);
+                }
+                _ => {}
+            }
+        }
+        conditions.retain(|condition| *condition != type_);
+        let preferred = candidates
+            .iter()
+            .copied()
+            .filter(|id| {
+                conditions.iter().any(|condition| {
+                    self.schema
+                        .is_named_type_subtype_of(Type::Object(*id), *condition)
+                })
+            })
+            .collect::<Vec<_>>();
+        let candidates = if preferred.is_empty() {
+            candidates
+        } else {
+            preferred
+        };
+        if candidates.is_empty() {
+            return Ok(type_);
+        }
+        Ok(Type::Object(
+            candidates[self.random.below(candidates.len())],
+        ))
+    }
+
+    fn fragment_type(&self, name: FragmentDefinitionName) -> Result<Type, MockError> {
+        self.program
+            .fragment(name)
+            .map(|fragment| fragment.type_condition)
+            .ok_or(MockError::UnknownDefinition(name.0))
+    }
+
+    /// Collects the fields that are selected on the concrete type, from the
+    /// matching fragments and inline fragments. Client fields are not part
+    /// of server responses, and are skipped.
+    fn collect_fields(
+        &self,
+        concrete_type: Type,
+        selections: &[&'a Selection],
+        groups: &mut Vec<FieldGroup<'a>>,
+    ) -> Result<(), MockError> {
+        for selection in selections {
+            match selection {
+                Selection::ScalarField(field) => {
+                    self.add_field(
+                        groups,
+                        field.alias_or_name(self.schema),
+                        field.definition.item,
+                        false,
+                        &[],
+                    );
+                }
+                Selection::LinkedField(field) => {
+                    self.add_field(
+                        groups,
+                        field.alias_or_name(self.schema),
+                        field.definition.item,
+                        is_connection(self.schema, field.definition.item, &field.directives),
+                        &field.selections,
+                    );
+                }
+                Selection::InlineFragment(fragment) => {
+                    let matches = fragment.type_condition.map_or(true, |condition| {
+                        self.schema
+                            .is_named_type_subtype_of(concrete_type, condition)
+                    });
+                    if matches {
+                        let selections = fragment.selections.iter().collect::<Vec<_>>();
+                        self.collect_fields(concrete_type, &selections, groups)?;
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let fragment = self
+                        .program
+                        .fragment(spread.fragment.item)
+                        .ok_or(MockError::UnknownDefinition(spread.fragment.item.0))?;
+                    if self
+                        .schema
+                        .is_named_type_subtype_of(concrete_type, fragment.type_condition)
+                    {
+                        let selections = fragment.selections.iter().collect::<Vec<_>>();
+                        self.collect_fields(concrete_type, &selections, groups)?;
+                    }
+                }
+                Selection::Condition(condition) => {
+                    // Conditions on variables are included.
+                    let skipped = matches!(
+                        condition.value,
+                        ConditionValue::Constant(value) if value != condition.passing_value
+                    );
+                    if !skipped {
+                        let selections = condition.selections.iter().collect::<Vec<_>>();
+                        self.collect_fields(concrete_type, &selections, groups)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_field(
+        &self,
+        groups: &mut Vec<FieldGroup<'a>>,
+        key: StringKey,
+        definition: FieldID,
+        is_connection: bool,
+        selections: &'a [Selection],
+    ) {
+        let field = self.schema.field(definition);
+        if field.is_extension
+            || definition == self.schema.clientid_field()
+            || definition == self.schema.fetch_token_field()
+            || definition == self.schema.is_fulfilled_field()
+        {
+            return;
+        }
+        match groups.iter_mut().find(|group| group.key == key) {
+            Some(group) => {
+                group.is_connection |= is_connection;
+                group.selections.extend(selections);
+            }
+            None => groups.push(FieldGroup {
+                key,
+                definition,
+                is_connection,
+                selections: selections.iter().collect(),
+            }),
+        }
+    }
+
+    fn mock_scalar_field(&mut self, parent_type: Type, definition: FieldID, role: Role) -> Value {
+        let field = self.schema.field(definition);
+        let name = field.name.item;
+        match (role, name.lookup()) {
+            (Role::Edge { index }, "cursor") => return cursor(index),
+            (Role::PageInfo { .. }, "hasNextPage" | "hasPreviousPage") => {
+                return Value::Bool(false);
+            }
+            (Role::PageInfo { edges }, "startCursor") if edges > 0 => return cursor(0),
+            (Role::PageInfo { edges }, "endCursor") if edges > 0 => return cursor(edges - 1),
+            (Role::PageInfo { .. }, "startCursor" | "endCursor") => return Value::Null,
+            _ => {}
+        }
+        if definition == self.schema.typename_field() {
+            return Value::String(self.schema.get_type_name(parent_type).to_string());
+        }
+        self.mock_scalar_type(&field.type_, parent_type, name)
+    }
+
+    fn mock_scalar_type(
+        &mut self,
+        type_: &TypeReference<Type>,
+        parent_type: Type,
+        field: StringKey,
+    ) -> Value {
+        if let TypeReference::List(item_type) = type_.nullable_type() {
+            return Value::Array(
+                (0..self.options.list_length)
+                    .map(|_| self.mock_scalar_type(item_type, parent_type, field))
+                    .collect(),
+            );
+        }
+        let type_ = type_.inner();
+        let type_name = self.schema.get_type_name(type_);
+        let parent_type_name = self.schema.get_type_name(parent_type);
+        if let Some(generator) = self.options.scalars.get(&type_name) {
+            let context = ScalarContext {
+                scalar: type_name,
+                parent_type: parent_type_name,
+                field,
+                number: self.next_number(type_name),
+                random: self.random.next_u64(),
+            };
+            return generator.generate(&context);
+        }
+        if let Type::Enum(id) = type_ {
+            let values = self
+                .schema
+                .enum_(id)
+                .values
+                .iter()
+                .filter(|value| !value.is_extension)
+                .collect::<Vec<_>>();
+            if values.is_empty() {
+                return Value::Null;
+            }
+            let index = self.random.below(values.len());
+            return Value::String(values[index].value.to_string());
+        }
+        match type_name.lookup() {
+            "ID" => Value::String(format!(
+                "{}:{}",
+                parent_type_name,
+                self.next_number(parent_type_name)
+            )),
+            "String" => Value::String(format!("{} {}", field, self.next_number(field))),
+            "Int" => Value::from(self.random.below(100)),
+            "Float" => Value::from(self.random.below(10_000) as f64 / 100.0),
+            "Boolean" => Value::Bool(self.random.next_u64() % 2 == 1),
+            _ => {
+                let key = format!("{}:{}", type_name, field).intern();
+                Value::String(format!("{} {}", type_name, self.next_number(key)))
+            }
+        }
+    }
+}
+
+fn cursor(index: usize) -> Value {
+    Value::String(format!("cursor:{}", index))
+}
+
+/// Fields with `@connection` or `@stream_connection`, or whose type is named
+/// like a connection.
+fn is_connection(schema: &SDLSchema, definition: FieldID, directives: &[Directive]) -> bool {
+    directives.iter().any(|directive| {
+        matches!(
+            directive.name.item.0.lookup(),
+            "connection" | "stream_connection"
+        )
+    }) || schema
+        .get_type_name(schema.field(definition).type_.inner())
+        .lookup()
+        .ends_with("Connection")
+}
@@ -0,0 +1,137 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+#![deny(warnings)]
+#![deny(rust_2018_idioms)]
+#![deny(clippy::all)]
+
+//! Generates mock responses of operations, and mock data of fragments, that
+//! are valid for the schema: for tests, stories and prototypes.
+//!
+//! - The same options generate the same payload, and a different `seed`
+//!   generates different numbers, booleans, enum values and concrete types
+//!   of abstract fields.
+//! - Fields with `@connection`, or whose type is named `...Connection`, get
+//!   `connection_length` edges, with cursors and a `pageInfo` that match.
+//!   Other lists get `list_length` items.
+//! - Custom scalars get the value of their generator in
+//!   [`MockOptions::scalars`], or else a string like `"DateTime 1"`.
+
+mod errors;
+mod generator;
+mod options;
+mod random;
+
+pub use errors::MockError;
+pub use generator::mock_definition;
+pub use generator::mock_fragment;
+pub use generator::mock_operation;
+pub use options::MockOptions;
+pub use options::ScalarContext;
+pub use options::ScalarGenerator;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common::SourceLocationKey;
+    use graphql_ir::Program;
+    use graphql_syntax::parse_executable;
+    use intern::string_key::Intern;
+    use relay_test_schema::get_test_schema;
+    use serde_json::json;
+
+    use super::*;
+
+    const DOCUMENT: &str = r#"
+        query MockTestQuery($id: ID!) {
+          node(id: $id) {
+            __typename
+            ...MockTestFragment
+            ... on User {
+              name
+              friends(first: 10) @connection(key: "MockTestQuery_friends") {
+                edges {
+                  cursor
+                  node {
+                    id
+                  }
+                }
+                pageInfo {
+                  hasNextPage
+                  endCursor
+                }
+              }
+            }
+          }
+        }
+
+        fragment MockTestFragment on User {
+          id
+          birthdate {
+            day
+          }
+        }
+    "#;
+
+    fn program() -> Program {
+        let schema = get_test_schema();
+        let ast = parse_executable(DOCUMENT, SourceLocationKey::generated()).unwrap();
+        let ir = graphql_ir::build(&schema, &ast.definitions).unwrap();
+        Program::from_definitions(Arc::clone(&schema), ir)
+    }
+
+    #[test]
+    fn same_seed_same_payload() {
+        let program = program();
+        let options = MockOptions {
+            seed: 7,
+            ..Default::default()
+        };
+        let name = "MockTestQuery".intern();
+        assert_eq!(
+            mock_definition(&program, name, &options).unwrap(),
+            mock_definition(&program, name, &options).unwrap()
+        );
+    }
+
+    #[test]
+    fn connections_and_spreads() {
+        let program = program();
+        let response =
+            mock_definition(&program, "MockTestQuery".intern(), &Default::default()).unwrap();
+        let node = &response["data"]["node"];
+        // The spread of a fragment on `User` makes the node a `User`.
+        assert_eq!(node["__typename"], json!("User"));
+        assert_eq!(node["id"], json!("User:1"));
+        assert_eq!(node["name"], json!("name 1"));
+        let friends = &node["friends"];
+        assert_eq!(friends["edges"].as_array().unwrap().len(), 2);
+        assert_eq!(friends["edges"][1]["cursor"], json!("cursor:1"));
+        assert_eq!(friends["edges"][1]["node"]["id"], json!("User:3"));
+        assert_eq!(
+            friends["pageInfo"],
+            json!({"hasNextPage": false, "endCursor": "cursor:1"})
+        );
+    }
+
+    #[test]
+    fn fragments_and_options() {
+        let program = program();
+        let options =
+            MockOptions::from_json(r#"{"seed": 1, "scalars": {"ID": "id-{n}"}}"#).unwrap();
+        let data = mock_definition(&program, "MockTestFragment".intern(), &options).unwrap();
+        assert_eq!(data["id"], json!("id-1"));
+        assert!(data["birthdate"]["day"].is_i64());
+
+        assert!(MockOptions::from_json(r#"{"seeds": 1}"#).is_err());
+        assert!(matches!(
+            mock_definition(&program, "Unknown".intern(), &options),
+            Err(MockError::UnknownDefinition(_))
+        ));
+    }
+}
@@ -0,0 +1,146 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fmt;
+use std::sync::Arc;
+
+use fnv::FnvHashMap;
+use intern::string_key::Intern;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use serde_json::Value;
+
+use crate::errors::MockError;
+
+/// What a custom scalar generator is called with, for each value.
+#[derive(Clone, Copy, Debug)]
+pub struct ScalarContext {
+    /// The name of the scalar, like `DateTime`.
+    pub scalar: StringKey,
+    /// The concrete type of the object the field is selected on.
+    pub parent_type: StringKey,
+    pub field: StringKey,
+    /// The number of the value among the values of this scalar, from 1.
+    pub number: usize,
+    /// A random number drawn from the seed of the options.
+    pub random: u64,
+}
+
+#[derive(Clone)]
+pub enum ScalarGenerator {
+    /// This value for every field of the scalar. In strings, `{n}` is
+    /// replaced with the number of the value and `{field}` with the name of
+    /// the field.
+    Value(Value),
+    Function(Arc<dyn Fn(&ScalarContext) -> Value + Send + Sync>),
+}
+
+impl fmt::Debug for ScalarGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScalarGenerator::Value(value) => f.debug_tuple("Value").field(value).finish(),
+            ScalarGenerator::Function(_) => f.write_str("Function"),
+        }
+    }
+}
+
+impl ScalarGenerator {
+    pub(crate) fn generate(&self, context: &ScalarContext) -> Value {
+        match self {
+            ScalarGenerator::Value(Value::String(template)) => Value::String(
+                template
+                    .replace("{n}", &context.number.to_string())
+                    .replace("{field}", context.field.lookup()),
+            ),
+            ScalarGenerator::Value(value) => value.clone(),
+            ScalarGenerator::Function(generate) => generate(context),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MockOptions {
+    /// The same seed generates the same payload for the same definition.
+    pub seed: u64,
+    /// The number of items of list fields.
+    pub list_length: usize,
+    /// The number of edges of connections: fields with `@connection`, or
+    /// whose type is named `...Connection`. Their cursors and `pageInfo`
+    /// match the edges.
+    pub connection_length: usize,
+    /// Generators of custom scalars, by scalar name. They can also replace
+    /// the values of built-in scalars, like `ID`.
+    pub scalars: FnvHashMap<StringKey, ScalarGenerator>,
+}
+
+impl Default for MockOptions {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            list_length: 1,
+            connection_length: 2,
+            scalars: Default::default(),
+        }
+    }
+}
+
+impl MockOptions {
+    /// Reads the options from JSON, for the CLI and the JS bindings, like:
+    ///
+    /// ```json
+    /// {"seed": 1, "listLength": 2, "connectionLength": 3, "scalars": {"DateTime": "2024-01-0{n}"}}
+    /// ```
+    ///
+    /// All keys are optional, and the scalars are `ScalarGenerator::Value`s.
+    pub fn from_json(json: &str) -> Result<Self, MockError> {
+        let invalid = |message: &str| MockError::InvalidOptions(message.to_string());
+        let value: Value =
+            serde_json::from_str(json).map_err(|err| MockError::InvalidOptions(err.to_string()))?;
+        let object = value
+            .as_object()
+            .ok_or_else(|| invalid("expected an object"))?;
+        let mut options = Self::default();
+        for (key, value) in object {
+            match key.as_str() {
+                "seed" => {
+                    options.seed = value
+                        .as_u64()
+                        .ok_or_else(|| invalid("`seed` should be a positive integer"))?
+                }
+                "listLength" => {
+                    options.list_length = value
+                        .as_u64()
+                        .ok_or_else(|| invalid("`listLength` should be a positive integer"))?
+                        as usize
+                }
+                "connectionLength" => {
+                    options.connection_length = value
+                        .as_u64()
+                        .ok_or_else(|| invalid("`connectionLength` should be a positive integer"))?
+                        as usize
+                }
+                "scalars" => {
+                    let scalars = value
+                        .as_object()
+                        .ok_or_else(|| invalid("`scalars` should be an object"))?;
+                    for (scalar, value) in scalars {
+                        options
+                            .scalars
+                            .insert(scalar.intern(), ScalarGenerator::Value(value.clone()));
+                    }
+                }
+                key => {
+                    return Err(MockError::InvalidOptions(format!(
+                        "unknown option `{}`",
+                        key
+                    )));
+                }
+            }
+        }
+        Ok(options)
+    }
+}
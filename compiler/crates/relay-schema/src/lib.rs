@@ -39,17 +39,39 @@ pub fn build_schema_with_extensions<T: AsRef<str>, U: AsRef<str>>(
     server_sdls: &[(T, SourceLocationKey)],
     extension_sdls: &[(U, SourceLocationKey)],
 ) -> DiagnosticsResult<SDLSchema> {
-    let extensions: Vec<(&str, SourceLocationKey)> =
-        once((RELAY_EXTENSIONS, SourceLocationKey::generated()))
-            .chain(
-                extension_sdls
-                    .iter()
-                    .map(|(source, location_key)| (source.as_ref(), *location_key)),
-            )
-            .collect();
+    let mut schema =
+        schema::build_schema_with_extensions(server_sdls, &with_relay_extensions(extension_sdls))?;
+    update_relay_directives(&mut schema);
+    Ok(schema)
+}
+
+/// Same as `build_schema_with_extensions`, but each of the server SDLs is an
+/// Apollo Federation subgraph. See `schema::compose_subgraphs`.
+pub fn build_schema_from_subgraphs_with_extensions<T: AsRef<str>, U: AsRef<str>>(
+    subgraph_sdls: &[(T, SourceLocationKey)],
+    extension_sdls: &[(U, SourceLocationKey)],
+) -> DiagnosticsResult<SDLSchema> {
+    let mut schema = schema::build_schema_from_subgraphs_with_extensions(
+        subgraph_sdls,
+        &with_relay_extensions(extension_sdls),
+    )?;
+    update_relay_directives(&mut schema);
+    Ok(schema)
+}
 
-    let mut schema = schema::build_schema_with_extensions(server_sdls, &extensions)?;
+fn with_relay_extensions<U: AsRef<str>>(
+    extension_sdls: &[(U, SourceLocationKey)],
+) -> Vec<(&str, SourceLocationKey)> {
+    once((RELAY_EXTENSIONS, SourceLocationKey::generated()))
+        .chain(
+            extension_sdls
+                .iter()
+                .map(|(source, location_key)| (source.as_ref(), *location_key)),
+        )
+        .collect()
+}
 
+fn update_relay_directives(schema: &mut SDLSchema) {
     // Remove label arg from @defer and @stream directives since the compiler
     // adds these arguments.
     for directive_name in &[*DEFER, *STREAM] {
@@ -65,5 +87,4 @@ pub fn build_schema_with_extensions<T: AsRef<str>, U: AsRef<str>>(
             directive.arguments = ArgumentDefinitions::new(next_args);
         }
     }
-    Ok(schema)
 }
@@ -9,10 +9,14 @@ use std::sync::Arc;
 
 use common::sync::try_join;
 use common::DiagnosticsResult;
+use common::FeatureFlags;
 use common::PerfLogEvent;
 use common::PerfLogger;
 use graphql_ir::FragmentDefinitionNameSet;
 use graphql_ir::Program;
+use graphql_ir_validations::validate_selection_conflict;
+use graphql_ir_validations::validate_selection_conflict_with_cache;
+use graphql_ir_validations::SelectionConflictCache;
 use relay_config::ProjectConfig;
 
 use super::*;
@@ -35,6 +39,8 @@ pub struct Programs {
     pub normalization: Arc<Program>,
     pub operation_text: Arc<Program>,
     pub typegen: Arc<Program>,
+    /// The fragments of the base project that are referenced by this project.
+    pub base_fragment_names: Arc<FragmentDefinitionNameSet>,
 }
 
 pub fn apply_transforms<TPerfLogger>(
@@ -44,10 +50,28 @@ pub fn apply_transforms<TPerfLogger>(
     perf_logger: Arc<TPerfLogger>,
     print_stats: Option<fn(extra_info: &str, program: &Program) -> ()>,
     custom_transforms_config: Option<&CustomTransformsConfig>,
+    selection_conflict_cache: Option<&SelectionConflictCache>,
 ) -> DiagnosticsResult<Programs>
 where
     TPerfLogger: PerfLogger + 'static,
 {
+    let documents: Vec<_> = program
+        .fragments()
+        .map(|fragment| {
+            (
+                fragment.name.item.0,
+                fragment.name.location.source_location(),
+            )
+        })
+        .chain(program.operations().map(|operation| {
+            (
+                operation.name.item.0,
+                operation.name.location.source_location(),
+            )
+        }))
+        .collect();
+    let feature_flags = project_config.feature_flags_for_documents(&documents);
+
     // The execution pipeline is as follows, where items at the same indentation
     // can be computed independently and therefore in parallel:
     // |- common
@@ -64,6 +88,7 @@ where
         || {
             let common_program = apply_common_transforms(
                 project_config,
+                &feature_flags,
                 Arc::clone(&program),
                 Arc::clone(&base_fragment_names),
                 Arc::clone(&perf_logger),
@@ -74,6 +99,7 @@ where
                 || {
                     let operation_program = apply_operation_transforms(
                         project_config,
+                        &feature_flags,
                         Arc::clone(&common_program),
                         Arc::clone(&base_fragment_names),
                         Arc::clone(&perf_logger),
@@ -84,6 +110,7 @@ where
                         || {
                             apply_normalization_transforms(
                                 project_config,
+                                &feature_flags,
                                 Arc::clone(&operation_program),
                                 Arc::clone(&base_fragment_names),
                                 Arc::clone(&perf_logger),
@@ -94,10 +121,12 @@ where
                         || {
                             apply_operation_text_transforms(
                                 project_config,
+                                &feature_flags,
                                 Arc::clone(&operation_program),
                                 Arc::clone(&base_fragment_names),
                                 Arc::clone(&perf_logger),
                                 custom_transforms_config,
+                                selection_conflict_cache,
                             )
                         },
                     )
@@ -105,6 +134,7 @@ where
                 || {
                     apply_reader_transforms(
                         project_config,
+                        &feature_flags,
                         Arc::clone(&common_program),
                         Arc::clone(&base_fragment_names),
                         Arc::clone(&perf_logger),
@@ -116,6 +146,7 @@ where
         || {
             apply_typegen_transforms(
                 project_config,
+                &feature_flags,
                 Arc::clone(&program),
                 Arc::clone(&base_fragment_names),
                 Arc::clone(&perf_logger),
@@ -130,12 +161,14 @@ where
         normalization: normalization_program,
         operation_text: text_program,
         typegen: typegen_program,
+        base_fragment_names,
     })
 }
 
 /// Applies transforms that apply to every output.
 fn apply_common_transforms(
     project_config: &ProjectConfig,
+    feature_flags: &FeatureFlags,
     program: Arc<Program>,
     base_fragment_names: Arc<FragmentDefinitionNameSet>,
     perf_logger: Arc<impl PerfLogger>,
@@ -162,11 +195,7 @@ fn apply_common_transforms(
         transform_defer_stream(&program)
     })?;
     program = log_event.time("transform_match", || {
-        transform_match(
-            &program,
-            &project_config.feature_flags,
-            project_config.module_import_config,
-        )
+        transform_match(&program, feature_flags, project_config.module_import_config)
     })?;
     program = log_event.time("transform_subscriptions", || {
         transform_subscriptions(&program)
@@ -181,7 +210,7 @@ fn apply_common_transforms(
     })?;
 
     program = log_event.time("relay_actor_change_transform", || {
-        relay_actor_change_transform(&program, &project_config.feature_flags.actor_change_support)
+        relay_actor_change_transform(&program, &feature_flags.actor_change_support)
     })?;
 
     program = log_event.time("provided_variable_fragment_transform", || {
@@ -220,6 +249,7 @@ fn apply_common_transforms(
 /// Corresponds to the "fragment transforms" in the JS compiler.
 fn apply_reader_transforms(
     project_config: &ProjectConfig,
+    feature_flags: &FeatureFlags,
     program: Arc<Program>,
     base_fragment_names: Arc<FragmentDefinitionNameSet>,
     perf_logger: Arc<impl PerfLogger>,
@@ -239,10 +269,7 @@ fn apply_reader_transforms(
     )?;
 
     program = log_event.time("fragment_alias_directive", || {
-        fragment_alias_directive(
-            &program,
-            &project_config.feature_flags.enable_fragment_aliases,
-        )
+        fragment_alias_directive(&program, &feature_flags.enable_fragment_aliases)
     })?;
 
     program = log_event.time("required_directive", || required_directive(&program))?;
@@ -251,10 +278,7 @@ fn apply_reader_transforms(
     })?;
 
     program = log_event.time("relay_resolvers", || {
-        relay_resolvers(
-            &program,
-            project_config.feature_flags.enable_relay_resolver_transform,
-        )
+        relay_resolvers(&program, feature_flags.enable_relay_resolver_transform)
     })?;
 
     program = log_event.time("client_extensions", || client_extensions(&program));
@@ -302,6 +326,7 @@ fn apply_reader_transforms(
 /// Corresponds to the "query transforms" in the JS compiler.
 fn apply_operation_transforms(
     project_config: &ProjectConfig,
+    feature_flags: &FeatureFlags,
     program: Arc<Program>,
     base_fragment_names: Arc<FragmentDefinitionNameSet>,
     perf_logger: Arc<impl PerfLogger>,
@@ -328,10 +353,7 @@ fn apply_operation_transforms(
         client_edges(&program, &project_config.schema_config)
     })?;
     program = log_event.time("relay_resolvers", || {
-        relay_resolvers(
-            &program,
-            project_config.feature_flags.enable_relay_resolver_transform,
-        )
+        relay_resolvers(&program, feature_flags.enable_relay_resolver_transform)
     })?;
 
     program = log_event.time("split_module_import", || {
@@ -377,6 +399,7 @@ fn apply_operation_transforms(
 /// Corresponds to the "codegen transforms" in the JS compiler
 fn apply_normalization_transforms(
     project_config: &ProjectConfig,
+    feature_flags: &FeatureFlags,
     program: Arc<Program>,
     base_fragment_names: Arc<FragmentDefinitionNameSet>,
     perf_logger: Arc<impl PerfLogger>,
@@ -404,7 +427,7 @@ fn apply_normalization_transforms(
         apply_fragment_arguments(
             &program,
             true,
-            &project_config.feature_flags.no_inline,
+            &feature_flags.no_inline,
             &base_fragment_names,
         )
     })?;
@@ -490,10 +513,12 @@ fn apply_normalization_transforms(
 /// Corresponds to the "print transforms" in the JS compiler
 fn apply_operation_text_transforms(
     project_config: &ProjectConfig,
+    feature_flags: &FeatureFlags,
     program: Arc<Program>,
     base_fragment_names: Arc<FragmentDefinitionNameSet>,
     perf_logger: Arc<impl PerfLogger>,
     custom_transforms_config: Option<&CustomTransformsConfig>,
+    selection_conflict_cache: Option<&SelectionConflictCache>,
 ) -> DiagnosticsResult<Arc<Program>> {
     let log_event = perf_logger.create_event("apply_operation_text_transforms");
     log_event.string("project", project_config.name.to_string());
@@ -513,7 +538,7 @@ fn apply_operation_text_transforms(
         apply_fragment_arguments(
             &program,
             false,
-            &project_config.feature_flags.no_inline,
+            &feature_flags.no_inline,
             &base_fragment_names,
         )
     })?;
@@ -538,9 +563,13 @@ fn apply_operation_text_transforms(
         skip_null_arguments_transform(&program)
     });
     log_event.time("validate_selection_conflict", || {
-        graphql_ir_validations::validate_selection_conflict::<RelayLocationAgnosticBehavior>(
-            &program, true,
-        )
+        if let Some(cache) = selection_conflict_cache {
+            validate_selection_conflict_with_cache::<RelayLocationAgnosticBehavior>(
+                &program, true, cache,
+            )
+        } else {
+            validate_selection_conflict::<RelayLocationAgnosticBehavior>(&program, true)
+        }
     })?;
     program = log_event.time("skip_client_extensions", || {
         skip_client_extensions(&program)
@@ -580,6 +609,7 @@ fn apply_operation_text_transforms(
 
 fn apply_typegen_transforms(
     project_config: &ProjectConfig,
+    feature_flags: &FeatureFlags,
     program: Arc<Program>,
     base_fragment_names: Arc<FragmentDefinitionNameSet>,
     perf_logger: Arc<impl PerfLogger>,
@@ -599,19 +629,12 @@ fn apply_typegen_transforms(
     )?;
 
     program = log_event.time("fragment_alias_directive", || {
-        fragment_alias_directive(
-            &program,
-            &project_config.feature_flags.enable_fragment_aliases,
-        )
+        fragment_alias_directive(&program, &feature_flags.enable_fragment_aliases)
     })?;
 
     program = log_event.time("mask", || mask(&program));
     program = log_event.time("transform_match", || {
-        transform_match(
-            &program,
-            &project_config.feature_flags,
-            project_config.module_import_config,
-        )
+        transform_match(&program, feature_flags, project_config.module_import_config)
     })?;
     program = log_event.time("transform_subscriptions", || {
         transform_subscriptions(&program)
@@ -647,10 +670,7 @@ fn apply_typegen_transforms(
     });
 
     program = log_event.time("relay_resolvers", || {
-        relay_resolvers(
-            &program,
-            project_config.feature_flags.enable_relay_resolver_transform,
-        )
+        relay_resolvers(&program, feature_flags.enable_relay_resolver_transform)
     })?;
     log_event.time("flatten", || flatten(&mut program, false, false))?;
     program = log_event.time("transform_refetchable_fragment", || {
@@ -666,7 +686,7 @@ fn apply_typegen_transforms(
     });
 
     program = log_event.time("relay_actor_change_transform", || {
-        relay_actor_change_transform(&program, &project_config.feature_flags.actor_change_support)
+        relay_actor_change_transform(&program, &feature_flags.actor_change_support)
     })?;
 
     program = apply_after_custom_transforms(
@@ -0,0 +1,167 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The codes of the validation messages, see `common::DiagnosticCode`.
+
+use common::diagnostic_codes;
+use common::Diagnostic;
+use common::DiagnosticCode;
+
+use crate::errors::ValidationMessage;
+use crate::errors::ValidationMessageWithData;
+
+diagnostic_codes! {
+    VALIDATION_MESSAGE_CODES for ValidationMessage {
+        DuplicateRelayClientComponentSplitOperation => "RELAY-2001":
+            "The fragment spread has `@relay_client_component` more than once, which would generate the same split operation twice.",
+        InvalidRelayResolverKeyArg => "RELAY-2002":
+            "The arguments of `@relay_resolver` in the schema must be string literals. Regenerate the resolver schema or fix the docblock of the resolver.",
+        MissingRelayResolverKeyArg => "RELAY-2003":
+            "A required argument of `@relay_resolver` is missing from the schema of the resolver. Check the docblock of the resolver.",
+        RelayResolverUnexpectedDirective => "RELAY-2004":
+            "Fields backed by Relay Resolvers don't support directives yet. Remove the directive.",
+        InvalidRelayResolverFragmentName => "RELAY-2005":
+            "The root fragment of the resolver doesn't exist. Check the `@rootFragment` of the resolver docblock.",
+        UnsupportedGlobalVariablesInResolverFragment => "RELAY-2006":
+            "The root fragments of resolvers can't use global variables. Define the variable with `@argumentDefinitions` of the fragment.",
+        ActorChangeInvalidSelection => "RELAY-2007":
+            "A field with `@as_actor` must select exactly one fragment spread.",
+        ActorChangeCannotUseOnScalarFields => "RELAY-2008":
+            "`@as_actor` can only be used on fields of object types.",
+        ActorChangeIsExperimental => "RELAY-2009":
+            "Actor change is experimental and only enabled for some fragments with the `actor_change_support` feature flag.",
+        ActorChangePluralFieldsNotSupported => "RELAY-2010":
+            "`@as_actor` can't be used on list fields yet.",
+        RelayResolversDisabled => "RELAY-2011":
+            "The field is backed by a Relay Resolver, but the project doesn't enable Relay Resolvers.",
+        ActorChangeExpectViewerFieldOnType => "RELAY-2012":
+            "`@as_actor` selects the actor field of the type, which the schema doesn't define. Check that the type supports actor change.",
+        ActorChangeViewerShouldBeScalar => "RELAY-2013":
+            "The actor field that `@as_actor` selects must be a scalar in the schema.",
+        RequiredExplicitNoInlineDirective => "RELAY-2014":
+            "A fragment used with `@module` is also spread normally, so it needs an explicit `@no_inline` on its definition.",
+        TestOperationOutsideTestDirectory => "RELAY-2015":
+            "`@relay_test_operation` generates larger files for tests, so it's only allowed in files that match the `testPathRegex` of the config.",
+        UndefinedFragment => "RELAY-2016":
+            "The fragment is not defined in the project. Check the spelling, or whether its file is in the sources of the project.",
+        ConflictingModuleSelections => "RELAY-2017":
+            "A field can only have one `@module` selection per type. Put each `@module` selection in its own aliased copy of the field.",
+        ClientEdgeToClientInterface => "RELAY-2018":
+            "Client edges to interfaces defined in client schema extensions are not supported yet.",
+        ClientEdgeToClientUnion => "RELAY-2019":
+            "Client edges to unions defined in client schema extensions are not supported yet.",
+        FragmentAliasIncompatibleDirective => "RELAY-2020":
+            "`@alias` can't be combined with other directives on the same fragment.",
+        FragmentAliasDirectiveDisabled => "RELAY-2021":
+            "`@alias` is not enabled for this location by the feature flags of the project.",
+        FragmentAliasDirectiveDynamicNameArg => "RELAY-2022":
+            "The `as` argument of `@alias` must be a string literal.",
+        FragmentAliasDirectiveMissingAs => "RELAY-2023":
+            "Inline fragments without a type condition have no name to alias them by, so `@alias` needs an `as` argument.",
+        InvalidStaticArgument => "RELAY-2024":
+            "The argument is marked `@static`, so the compiler reads it and it must be a constant rather than a variable.",
+        ClientEdgeUnsupportedDirective => "RELAY-2025":
+            "The directive is not supported on fields backed by client edges. Remove it.",
+        RelayResolverOutputTypeInvalidInputObjectType => "RELAY-2026":
+            "Resolvers can't return input objects. Use an object type as the `@outputType`.",
+        RelayResolverOutputTypeUnsupported => "RELAY-2027":
+            "Resolvers can't return this kind of type with `@outputType` yet.",
+        RelayResolverClientInterfaceMustBeImplemented => "RELAY-2028":
+            "A client interface returned by a resolver must be implemented by at least one client object type.",
+        RelayResolverClientInterfaceImplementingTypeMustBeClientTypes => "RELAY-2029":
+            "All the implementations of a client interface returned by a resolver must be client types.",
+        RelayResolverTypeRecursionDetected => "RELAY-2030":
+            "An `@outputType` can't contain itself. To connect entities of the same type, use a resolver with `@edgeTo`.",
+        RelayResolverServerTypeNotSupported => "RELAY-2031":
+            "`@outputType` can't return server types. Expose edges to server types with `@edgeTo` and `@waterfall`.",
+        RelayResolverIDFieldNotSupported => "RELAY-2032":
+            "`id` is reserved for objects that implement `Node`, so `@outputType` objects can't define it.",
+        RelayResolverArgumentsNotSupported => "RELAY-2033":
+            "Fields of an `@outputType` can't have arguments. Expose them as separate resolvers.",
+        UnselectableField => "RELAY-2034":
+            "The field is marked with the unselectable directive of the schema config, so documents can't select it. Use the replacement that the reason names.",
+        BannedField => "RELAY-2035":
+            "The field or argument is listed in the `bannedFields` of the schema config. Use another field.",
+        InvalidBannedField => "RELAY-2036":
+            "An entry of `bannedFields` in the schema config is not a field or field argument of the schema. Fix or remove the entry.",
+        ClientEnumValueOnServerField => "RELAY-2037":
+            "The enum value is defined in a client schema extension, so the server doesn't know it. Pass a value that the server schema defines.",
+    }
+}
+
+diagnostic_codes! {
+    VALIDATION_MESSAGE_WITH_DATA_CODES for ValidationMessageWithData {
+        RelayResolversMissingWaterfall => "RELAY-2501":
+            "Reading a client edge to a server object fetches it from the server. Mark the field with `@waterfall` to make the network roundtrip explicit.",
+        RelayResolversUnexpectedWaterfall => "RELAY-2502":
+            "`@waterfall` is only for client edges that point to server objects. Remove it.",
+    }
+}
+
+/// The code of the message of the diagnostic, if it is a validation message
+/// of the IR or of the transforms.
+pub fn diagnostic_code(diagnostic: &Diagnostic) -> Option<&'static str> {
+    graphql_ir::diagnostic_code(diagnostic).or_else(|| {
+        let message = diagnostic.message_as_any();
+        if let Some(message) = message.downcast_ref::<ValidationMessage>() {
+            Some(message.code())
+        } else {
+            message
+                .downcast_ref::<ValidationMessageWithData>()
+                .map(|message| message.code())
+        }
+    })
+}
+
+/// All the codes, in the order of their numbers.
+pub fn all_diagnostic_codes() -> impl Iterator<Item = &'static DiagnosticCode> {
+    graphql_ir::diagnostic_codes()
+        .chain(VALIDATION_MESSAGE_CODES)
+        .chain(VALIDATION_MESSAGE_WITH_DATA_CODES)
+}
+
+/// Finds a code like `RELAY-1007`, ignoring the case.
+pub fn find_diagnostic_code(code: &str) -> Option<&'static DiagnosticCode> {
+    all_diagnostic_codes().find(|diagnostic_code| diagnostic_code.code.eq_ignore_ascii_case(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use common::Location;
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    #[test]
+    fn codes_are_unique() {
+        let mut codes = HashSet::new();
+        for diagnostic_code in all_diagnostic_codes() {
+            assert!(
+                codes.insert(diagnostic_code.code),
+                "Duplicate code {}",
+                diagnostic_code.code
+            );
+        }
+    }
+
+    #[test]
+    fn finds_the_code_of_a_diagnostic() {
+        let diagnostic = Diagnostic::error(
+            graphql_ir::ValidationMessage::DuplicateDefinition("Foo".intern()),
+            Location::generated(),
+        );
+        assert_eq!(diagnostic_code(&diagnostic), Some("RELAY-1001"));
+        assert_eq!(
+            find_diagnostic_code("relay-1001").map(|code| code.name),
+            Some("DuplicateDefinition")
+        );
+        let diagnostic = Diagnostic::error("Not a validation message", Location::generated());
+        assert_eq!(diagnostic_code(&diagnostic), None);
+    }
+}
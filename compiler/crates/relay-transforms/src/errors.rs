@@ -10,6 +10,7 @@ use common::DiagnosticDisplay;
 use common::DirectiveName;
 use common::InterfaceName;
 use common::ObjectName;
+use common::SchemaCoordinate;
 use common::WithDiagnosticData;
 use graphql_ir::FragmentDefinitionName;
 use graphql_ir::VariableName;
@@ -213,6 +214,24 @@ pub enum ValidationMessage {
         parent_name: Option<StringKey>,
         reason: Option<StringKey>,
     },
+
+    #[error("Disallowed use of `{coordinate}`, it is listed in the `bannedFields` schema config.")]
+    BannedField { coordinate: SchemaCoordinate },
+
+    #[error("Invalid `bannedFields` schema config entry `{coordinate}`: {reason}")]
+    InvalidBannedField {
+        coordinate: SchemaCoordinate,
+        reason: String,
+    },
+
+    #[error(
+        "Enum value `{enum_name}.{value}` is defined in a client schema extension, and can't be passed to the server field `{field_name}`."
+    )]
+    ClientEnumValueOnServerField {
+        enum_name: StringKey,
+        value: StringKey,
+        field_name: StringKey,
+    },
 }
 
 #[derive(Clone, Debug, Error, Eq, PartialEq, Ord, PartialOrd, Hash)]
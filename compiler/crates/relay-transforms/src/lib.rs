@@ -22,6 +22,7 @@ mod client_extensions_abstract_types;
 mod connections;
 mod declarative_connection;
 mod defer_stream;
+mod diagnostic_codes;
 mod directive_finder;
 mod errors;
 mod flatten;
@@ -120,6 +121,9 @@ pub use defer_stream::transform_defer_stream;
 pub use defer_stream::DeferDirective;
 pub use defer_stream::StreamDirective;
 pub use defer_stream::DEFER_STREAM_CONSTANTS;
+pub use diagnostic_codes::all_diagnostic_codes;
+pub use diagnostic_codes::diagnostic_code;
+pub use diagnostic_codes::find_diagnostic_code;
 pub use directive_finder::DirectiveFinder;
 pub use flatten::flatten;
 pub use fragment_alias_directive::fragment_alias_directive;
@@ -152,6 +156,8 @@ pub use no_inline::NO_INLINE_DIRECTIVE_NAME;
 pub use preloadable_directive::is_operation_preloadable;
 pub use preloadable_directive::should_generate_hack_preloader;
 pub use provided_variable_fragment_transform::provided_variable_fragment_transform;
+pub use refetchable_fragment::is_refetchable_type;
+pub use refetchable_fragment::refetchable_type_descriptions;
 pub use refetchable_fragment::transform_refetchable_fragment;
 pub use refetchable_fragment::RefetchableDerivedFromMetadata;
 pub use refetchable_fragment::RefetchableMetadata;
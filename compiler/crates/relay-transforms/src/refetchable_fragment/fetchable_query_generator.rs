@@ -25,6 +25,7 @@ use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use relay_config::SchemaConfig;
 use schema::Argument as ArgumentDef;
+use schema::DirectiveValue;
 use schema::FieldID;
 use schema::SDLSchema;
 use schema::Schema;
@@ -131,19 +132,7 @@ fn get_fetchable_field_name(
     fragment: &FragmentDefinition,
     schema: &SDLSchema,
 ) -> DiagnosticsResult<Option<StringKey>> {
-    let fetchable_directive = match fragment.type_condition {
-        Type::Interface(interface_id) => {
-            let interface = schema.interface(interface_id);
-            interface.directives.named(CONSTANTS.fetchable)
-        }
-        Type::Object(object_id) => {
-            let object = schema.object(object_id);
-            object.directives.named(CONSTANTS.fetchable)
-        }
-        _ => None,
-    };
-
-    if let Some(fetchable) = fetchable_directive {
+    if let Some(fetchable) = get_fetchable_directive(schema, fragment.type_condition) {
         let field_name_arg = fetchable.arguments.named(CONSTANTS.field_name);
         if let Some(field_name_arg) = field_name_arg {
             if let Some(value) = field_name_arg.value.get_string_literal() {
@@ -161,6 +150,20 @@ fn get_fetchable_field_name(
     Ok(None)
 }
 
+fn get_fetchable_directive(schema: &SDLSchema, type_: Type) -> Option<&DirectiveValue> {
+    match type_ {
+        Type::Interface(interface_id) => {
+            let interface = schema.interface(interface_id);
+            interface.directives.named(CONSTANTS.fetchable)
+        }
+        Type::Object(object_id) => {
+            let object = schema.object(object_id);
+            object.directives.named(CONSTANTS.fetchable)
+        }
+        _ => None,
+    }
+}
+
 fn get_identifier_field_id(
     fragment: &FragmentDefinition,
     schema: &SDLSchema,
@@ -245,8 +248,13 @@ fn enforce_selections_with_id_field(
     next_selections
 }
 
+fn is_eligible(schema: &SDLSchema, type_: Type) -> bool {
+    get_fetchable_directive(schema, type_).is_some()
+}
+
 pub const FETCHABLE_QUERY_GENERATOR: QueryGenerator = QueryGenerator {
     // T138625502 we should support interfaces and maybe unions
     description: "server objects and interfaces with the @fetchable directive",
     build_refetch_operation,
+    is_eligible,
 };
@@ -19,10 +19,12 @@ use std::sync::Arc;
 use ::errors::validate_map;
 use common::Diagnostic;
 use common::DiagnosticsResult;
+use common::Location;
 use common::NamedItem;
 use common::WithLocation;
 use fetchable_query_generator::FETCHABLE_QUERY_GENERATOR;
 use graphql_ir::Directive;
+use graphql_ir::Field;
 use graphql_ir::FragmentDefinition;
 use graphql_ir::FragmentDefinitionName;
 use graphql_ir::FragmentDefinitionNameSet;
@@ -38,6 +40,7 @@ use query_query_generator::QUERY_QUERY_GENERATOR;
 use relay_config::SchemaConfig;
 use schema::SDLSchema;
 use schema::Schema;
+use schema::Type;
 pub use utils::RefetchableDerivedFromMetadata;
 pub use utils::RefetchableMetadata;
 pub use utils::CONSTANTS;
@@ -49,6 +52,8 @@ pub use self::refetchable_directive::REFETCHABLE_NAME;
 use self::validation_message::ValidationMessage;
 use crate::connections::extract_connection_metadata_from_directive;
 use crate::connections::ConnectionConstants;
+use crate::handle_fields::extract_handle_field_directives;
+use crate::handle_fields::extract_values_from_handle_field_directive;
 use crate::relay_directive::PLURAL_ARG_NAME;
 use crate::relay_directive::RELAY_DIRECTIVE_NAME;
 use crate::root_variables::InferVariablesVisitor;
@@ -184,15 +189,10 @@ impl<'program, 'sc> RefetchableFragment<'program, 'sc> {
                 return Ok((refetchable_directive, refetch_root));
             }
         }
-        let mut descriptions = String::new();
-        for generator in GENERATORS.iter() {
-            writeln!(descriptions, " - {}", generator.description).unwrap();
-        }
-        descriptions.pop();
         Err(vec![Diagnostic::error(
             ValidationMessage::UnsupportedRefetchableFragment {
                 fragment_name: fragment.name.item,
-                descriptions,
+                descriptions: refetchable_type_descriptions(),
             },
             fragment.name.location,
         )])
@@ -278,14 +278,26 @@ impl<'program, 'sc> RefetchableFragment<'program, 'sc> {
     /// Connection metadata is extracted in `transform_connection`
     fn validate_connection_metadata(&self, fragment: &FragmentDefinition) -> DiagnosticsResult<()> {
         if let Some(metadatas) = extract_connection_metadata_from_directive(&fragment.directives) {
-            // TODO: path or connection field locations in the error messages
+            let mut connection_field_locations = vec![];
+            self.collect_connection_field_locations(
+                &fragment.selections,
+                &mut connection_field_locations,
+            );
+            let location = connection_field_locations
+                .first()
+                .copied()
+                .unwrap_or(fragment.name.location);
             if metadatas.len() > 1 {
-                return Err(vec![Diagnostic::error(
+                let mut diagnostic = Diagnostic::error(
                     ValidationMessage::RefetchableWithMultipleConnections {
                         fragment_name: fragment.name.item,
                     },
-                    fragment.name.location,
-                )]);
+                    location,
+                );
+                for other_location in connection_field_locations.iter().skip(1) {
+                    diagnostic = diagnostic.annotate("another connection", *other_location);
+                }
+                return Err(vec![diagnostic]);
             } else if metadatas.len() == 1 {
                 let metadata = &metadatas[0];
                 if metadata.path.is_none() {
@@ -293,7 +305,7 @@ impl<'program, 'sc> RefetchableFragment<'program, 'sc> {
                         ValidationMessage::RefetchableWithConnectionInPlural {
                             fragment_name: fragment.name.item,
                         },
-                        fragment.name.location,
+                        location,
                     )]);
                 }
                 if (metadata.after.is_none() || metadata.first.is_none())
@@ -304,7 +316,7 @@ impl<'program, 'sc> RefetchableFragment<'program, 'sc> {
                             fragment_name: fragment.name.item,
                             arguments: "after and first",
                         },
-                        fragment.name.location,
+                        location,
                     )]);
                 } else if (metadata.before.is_none() || metadata.last.is_none())
                     && metadata.direction != self.connection_constants.direction_forward
@@ -314,13 +326,44 @@ impl<'program, 'sc> RefetchableFragment<'program, 'sc> {
                             fragment_name: fragment.name.item,
                             arguments: "before and last",
                         },
-                        fragment.name.location,
+                        location,
                     )]);
                 }
             }
         }
         Ok(())
     }
+
+    /// The locations of the fields with a connection handle, which replaced
+    /// their `@connection` directive, in document order.
+    fn collect_connection_field_locations(
+        &self,
+        selections: &[Selection],
+        locations: &mut Vec<Location>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::LinkedField(field) => {
+                    let is_connection =
+                        extract_handle_field_directives(&field.directives).any(|directive| {
+                            extract_values_from_handle_field_directive(directive).handle
+                                == self.connection_constants.connection_directive_name.0
+                        });
+                    if is_connection {
+                        locations.push(field.alias_or_name_location());
+                    }
+                    self.collect_connection_field_locations(&field.selections, locations);
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.collect_connection_field_locations(&fragment.selections, locations);
+                }
+                Selection::Condition(condition) => {
+                    self.collect_connection_field_locations(&condition.selections, locations);
+                }
+                Selection::FragmentSpread(_) | Selection::ScalarField(_) => {}
+            }
+        }
+    }
 }
 
 type BuildRefetchOperationFn = fn(
@@ -341,6 +384,9 @@ pub struct QueryGenerator {
     /// Returns RefetchRoot or null if not applicable. Might throw a user error
     /// for an invalid schema or other problems.
     pub build_refetch_operation: BuildRefetchOperationFn,
+
+    /// Whether fragments on the type can be refetched with this generator.
+    pub is_eligible: fn(schema: &SDLSchema, type_: Type) -> bool,
 }
 
 const GENERATORS: [QueryGenerator; 4] = [
@@ -350,6 +396,23 @@ const GENERATORS: [QueryGenerator; 4] = [
     FETCHABLE_QUERY_GENERATOR,
 ];
 
+/// Whether a refetch query can be generated for fragments on the type.
+pub fn is_refetchable_type(schema: &SDLSchema, type_: Type) -> bool {
+    GENERATORS
+        .iter()
+        .any(|generator| (generator.is_eligible)(schema, type_))
+}
+
+/// Describes the types of the fragments that can be refetchable, one per line.
+pub fn refetchable_type_descriptions() -> String {
+    let mut descriptions = String::new();
+    for generator in GENERATORS.iter() {
+        writeln!(descriptions, " - {}", generator.description).unwrap();
+    }
+    descriptions.pop();
+    descriptions
+}
+
 pub struct RefetchRoot {
     pub fragment: Arc<FragmentDefinition>,
     pub selections: Vec<Selection>,
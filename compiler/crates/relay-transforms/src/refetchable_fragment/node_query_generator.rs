@@ -54,47 +54,10 @@ fn build_refetch_operation(
 ) -> DiagnosticsResult<Option<RefetchRoot>> {
     let id_name = schema_config.node_interface_id_field;
 
-    let node_interface_id = schema.get_type(CONSTANTS.node_type_name).and_then(|type_| {
-        if let Type::Interface(id) = type_ {
-            Some(id)
-        } else {
-            None
-        }
-    });
-
-    match node_interface_id {
+    match get_node_interface_id(schema) {
         None => Ok(None),
         Some(node_interface_id) => {
-            let eligible = match fragment.type_condition {
-                Type::Interface(id) => {
-                    id == node_interface_id
-                        || schema
-                            .interface(id)
-                            .implementing_objects
-                            .iter()
-                            .all(|&object_id| {
-                                schema
-                                    .object(object_id)
-                                    .interfaces
-                                    .iter()
-                                    .any(|interface_id| *interface_id == node_interface_id)
-                            })
-                }
-                Type::Object(id) => schema
-                    .object(id)
-                    .interfaces
-                    .iter()
-                    .any(|interface_id| *interface_id == node_interface_id),
-                Type::Union(id) => schema.union(id).members.iter().all(|&object_id| {
-                    schema
-                        .object(object_id)
-                        .interfaces
-                        .iter()
-                        .any(|interface_id| *interface_id == node_interface_id)
-                }),
-                _ => false,
-            };
-            if !eligible {
+            if !implements_node(schema, node_interface_id, fragment.type_condition) {
                 return Ok(None);
             }
 
@@ -257,7 +220,57 @@ fn enforce_selections_with_id_field(
     }
 }
 
+fn get_node_interface_id(schema: &SDLSchema) -> Option<InterfaceID> {
+    schema.get_type(CONSTANTS.node_type_name).and_then(|type_| {
+        if let Type::Interface(id) = type_ {
+            Some(id)
+        } else {
+            None
+        }
+    })
+}
+
+/// The Node interface, and the types whose objects all implement it
+fn implements_node(schema: &SDLSchema, node_interface_id: InterfaceID, type_: Type) -> bool {
+    match type_ {
+        Type::Interface(id) => {
+            id == node_interface_id
+                || schema
+                    .interface(id)
+                    .implementing_objects
+                    .iter()
+                    .all(|&object_id| {
+                        schema
+                            .object(object_id)
+                            .interfaces
+                            .iter()
+                            .any(|interface_id| *interface_id == node_interface_id)
+                    })
+        }
+        Type::Object(id) => schema
+            .object(id)
+            .interfaces
+            .iter()
+            .any(|interface_id| *interface_id == node_interface_id),
+        Type::Union(id) => schema.union(id).members.iter().all(|&object_id| {
+            schema
+                .object(object_id)
+                .interfaces
+                .iter()
+                .any(|interface_id| *interface_id == node_interface_id)
+        }),
+        _ => false,
+    }
+}
+
+fn is_eligible(schema: &SDLSchema, type_: Type) -> bool {
+    get_node_interface_id(schema).map_or(false, |node_interface_id| {
+        implements_node(schema, node_interface_id, type_)
+    })
+}
+
 pub const NODE_QUERY_GENERATOR: QueryGenerator = QueryGenerator {
     description: "the Node interface, object types that implement the Node interface, interfaces whose implementing objects all implement Node, and unions whose members all implement Node",
     build_refetch_operation,
+    is_eligible,
 };
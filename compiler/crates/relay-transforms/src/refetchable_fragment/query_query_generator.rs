@@ -13,6 +13,7 @@ use graphql_ir::OperationDefinitionName;
 use relay_config::SchemaConfig;
 use schema::SDLSchema;
 use schema::Schema;
+use schema::Type;
 
 use super::build_fragment_metadata_as_directive;
 use super::build_fragment_spread;
@@ -30,8 +31,7 @@ fn build_refetch_operation(
     query_name: OperationDefinitionName,
     variables_map: &VariableMap,
 ) -> DiagnosticsResult<Option<RefetchRoot>> {
-    let query_type = schema.query_type().unwrap();
-    if fragment.type_condition != query_type {
+    if !is_eligible(schema, fragment.type_condition) {
         return Ok(None);
     }
 
@@ -58,7 +58,12 @@ fn build_refetch_operation(
     }))
 }
 
+fn is_eligible(schema: &SDLSchema, type_: Type) -> bool {
+    schema.query_type() == Some(type_)
+}
+
 pub const QUERY_QUERY_GENERATOR: QueryGenerator = QueryGenerator {
     description: "the Query type",
     build_refetch_operation,
+    is_eligible,
 };
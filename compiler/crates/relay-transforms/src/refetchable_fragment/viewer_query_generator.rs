@@ -38,7 +38,7 @@ fn build_refetch_operation(
     query_name: OperationDefinitionName,
     variables_map: &VariableMap,
 ) -> DiagnosticsResult<Option<RefetchRoot>> {
-    if schema.get_type_name(fragment.type_condition) != CONSTANTS.viewer_type_name {
+    if !is_eligible(schema, fragment.type_condition) {
         return Ok(None);
     }
     let query_type = schema.query_type().unwrap();
@@ -100,7 +100,12 @@ fn get_viewer_field_id(
     )])
 }
 
+fn is_eligible(schema: &SDLSchema, type_: Type) -> bool {
+    schema.get_type_name(type_) == CONSTANTS.viewer_type_name
+}
+
 pub const VIEWER_QUERY_GENERATOR: QueryGenerator = QueryGenerator {
     description: "the Viewer type",
     build_refetch_operation,
+    is_eligible,
 };
@@ -7,11 +7,13 @@
 
 use std::sync::Arc;
 
+use common::ArgumentName;
 use common::Diagnostic;
 use common::DiagnosticTag;
 use common::DiagnosticsResult;
 use common::WithLocation;
 use graphql_ir::Argument;
+use graphql_ir::ConstantValue;
 use graphql_ir::Directive;
 use graphql_ir::ExecutableDefinition;
 use graphql_ir::LinkedField;
@@ -23,6 +25,7 @@ use graphql_ir::Value;
 use schema::FieldID;
 use schema::SDLSchema;
 use schema::Schema;
+use schema::Type;
 
 pub fn deprecated_fields(
     schema: &Arc<SDLSchema>,
@@ -60,7 +63,7 @@ impl<'a> DeprecatedFields<'a> {
     }
 
     fn validate_field(&mut self, field_id: &WithLocation<FieldID>, arguments: &[Argument]) {
-        let schema = &self.schema;
+        let schema = self.schema;
         let field_definition = schema.field(field_id.item);
         if let Some(deprecation) = field_definition.deprecated() {
             let parent_type = field_definition.parent_type.unwrap();
@@ -79,6 +82,7 @@ impl<'a> DeprecatedFields<'a> {
 
         for arg in arguments {
             if let Some(arg_definition) = field_definition.arguments.named(arg.name.item) {
+                self.validate_input_fields(&arg.value.item, arg_definition.type_.inner());
                 if let Some(directive) = arg_definition.deprecated() {
                     let parent_type = field_definition.parent_type.unwrap();
                     let parent_name = schema.get_type_name(parent_type);
@@ -97,6 +101,78 @@ impl<'a> DeprecatedFields<'a> {
             }
         }
     }
+
+    /// Warns about the deprecated input object fields a value sets, in
+    /// nested objects and lists too.
+    fn validate_input_fields(&mut self, value: &Value, type_: Type) {
+        match value {
+            Value::Constant(value) => self.validate_constant_input_fields(value, type_),
+            Value::List(items) => {
+                for item in items {
+                    self.validate_input_fields(item, type_);
+                }
+            }
+            Value::Object(fields) => {
+                for field in fields {
+                    if let Some(field_definition) = self.input_field(type_, &field.name) {
+                        self.validate_input_fields(
+                            &field.value.item,
+                            field_definition.type_.inner(),
+                        );
+                    }
+                }
+            }
+            Value::Variable(_) => {}
+        }
+    }
+
+    fn validate_constant_input_fields(&mut self, value: &ConstantValue, type_: Type) {
+        match value {
+            ConstantValue::List(items) => {
+                for item in items {
+                    self.validate_constant_input_fields(item, type_);
+                }
+            }
+            ConstantValue::Object(fields) => {
+                for field in fields {
+                    if let Some(field_definition) = self.input_field(type_, &field.name) {
+                        self.validate_constant_input_fields(
+                            &field.value.item,
+                            field_definition.type_.inner(),
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the definition of a field of an input object, and warns if
+    /// it is deprecated.
+    fn input_field(
+        &mut self,
+        type_: Type,
+        field_name: &WithLocation<ArgumentName>,
+    ) -> Option<&'a schema::Argument> {
+        let schema: &'a SDLSchema = self.schema;
+        let input_object = match type_ {
+            Type::InputObject(id) => schema.input_object(id),
+            _ => return None,
+        };
+        let field_definition = input_object.fields.named(field_name.item)?;
+        if let Some(deprecation) = field_definition.deprecated() {
+            self.warnings.push(Diagnostic::hint(
+                ValidationMessage::DeprecatedInputField {
+                    field_name: field_name.item,
+                    input_object_name: input_object.name.item.0,
+                    deprecation_reason: deprecation.reason,
+                },
+                field_name.location,
+                vec![DiagnosticTag::DEPRECATED],
+            ));
+        }
+        Some(field_definition)
+    }
 }
 
 // While the individual methods return a diagnostic, since using deprecated fields are not errors per-se, we reserve
@@ -130,6 +206,7 @@ impl<'a> Validator for DeprecatedFields<'a> {
         if let Some(directive_definition) = self.schema.get_directive(directive.name.item) {
             for arg in &directive.arguments {
                 if let Some(arg_definition) = directive_definition.arguments.named(arg.name.item) {
+                    self.validate_input_fields(&arg.value.item, arg_definition.type_.inner());
                     if let Some(deprecation) = arg_definition.deprecated() {
                         self.warnings.push(Diagnostic::hint(
                             ValidationMessage::DeprecatedDirectiveArgument {
@@ -0,0 +1,135 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::HashSet;
+
+use common::Diagnostic;
+use common::DiagnosticsResult;
+use common::Location;
+use common::SchemaCoordinate;
+use common::WithLocation;
+use graphql_ir::Argument;
+use graphql_ir::LinkedField;
+use graphql_ir::Program;
+use graphql_ir::ScalarField;
+use graphql_ir::Validator;
+use relay_config::SchemaConfig;
+use schema::validate_schema_coordinate;
+use schema::FieldID;
+use schema::SDLSchema;
+use schema::Schema;
+
+use crate::ValidationMessage;
+
+/// Reports selections of the fields, and uses of the field arguments, listed
+/// in `schemaConfig.bannedFields`. A field is matched on the type it is
+/// selected on, so banning `Node.id` doesn't ban `User.id`.
+pub fn disallow_banned_fields(
+    program: &Program,
+    schema_config: &SchemaConfig,
+) -> DiagnosticsResult<()> {
+    if schema_config.banned_fields.is_empty() {
+        return Ok(());
+    }
+    let mut errors = vec![];
+    for coordinate in &schema_config.banned_fields {
+        let reason = match coordinate {
+            SchemaCoordinate::Member { .. } | SchemaCoordinate::Argument { .. } => {
+                validate_schema_coordinate(program.schema.as_ref(), *coordinate)
+                    .err()
+                    .map(|error| error.to_string())
+            }
+            SchemaCoordinate::Type(_)
+            | SchemaCoordinate::Directive(_)
+            | SchemaCoordinate::DirectiveArgument { .. } => {
+                Some("Only fields and field arguments can be banned.".to_string())
+            }
+        };
+        if let Some(reason) = reason {
+            errors.push(Diagnostic::error(
+                ValidationMessage::InvalidBannedField {
+                    coordinate: *coordinate,
+                    reason,
+                },
+                Location::generated(),
+            ));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut validator = BannedFields {
+        schema: &program.schema,
+        banned_fields: schema_config.banned_fields.iter().copied().collect(),
+        errors: vec![],
+    };
+    validator.validate_program(program)?;
+
+    if validator.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(validator.errors)
+    }
+}
+
+struct BannedFields<'a> {
+    schema: &'a SDLSchema,
+    banned_fields: HashSet<SchemaCoordinate>,
+    errors: Vec<Diagnostic>,
+}
+
+impl<'a> BannedFields<'a> {
+    fn validate_field(&mut self, field_id: &WithLocation<FieldID>, arguments: &[Argument]) {
+        let field = self.schema.field(field_id.item);
+        let type_name = match field.parent_type {
+            Some(parent_type) => self.schema.get_type_name(parent_type),
+            None => return,
+        };
+        let field_name = field.name.item;
+
+        let coordinate = SchemaCoordinate::Member {
+            type_name,
+            member_name: field_name,
+        };
+        if self.banned_fields.contains(&coordinate) {
+            self.errors.push(Diagnostic::error(
+                ValidationMessage::BannedField { coordinate },
+                field_id.location,
+            ));
+        }
+        for argument in arguments {
+            let coordinate = SchemaCoordinate::Argument {
+                type_name,
+                field_name,
+                argument_name: argument.name.item,
+            };
+            if self.banned_fields.contains(&coordinate) {
+                self.errors.push(Diagnostic::error(
+                    ValidationMessage::BannedField { coordinate },
+                    argument.name.location,
+                ));
+            }
+        }
+    }
+}
+
+impl<'a> Validator for BannedFields<'a> {
+    const NAME: &'static str = "BannedFields";
+    const VALIDATE_ARGUMENTS: bool = false;
+    const VALIDATE_DIRECTIVES: bool = false;
+
+    fn validate_linked_field(&mut self, field: &LinkedField) -> DiagnosticsResult<()> {
+        self.validate_field(&field.definition, &field.arguments);
+        self.default_validate_linked_field(field)
+    }
+
+    fn validate_scalar_field(&mut self, field: &ScalarField) -> DiagnosticsResult<()> {
+        self.validate_field(&field.definition, &field.arguments);
+        self.default_validate_scalar_field(field)
+    }
+}
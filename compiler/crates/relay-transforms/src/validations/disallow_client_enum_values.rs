@@ -0,0 +1,172 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::Diagnostic;
+use common::DiagnosticsResult;
+use common::Location;
+use common::WithLocation;
+use graphql_ir::Argument;
+use graphql_ir::ConstantValue;
+use graphql_ir::LinkedField;
+use graphql_ir::Program;
+use graphql_ir::ScalarField;
+use graphql_ir::Validator;
+use graphql_ir::Value;
+use intern::string_key::StringKey;
+use schema::FieldID;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+use schema::TypeReference;
+
+use crate::ValidationMessage;
+
+/// Reports enum values that were added by `extend enum` in client schema
+/// extensions, in the arguments of server fields: the server doesn't know
+/// these values. Values of variables can't be checked.
+pub fn disallow_client_enum_values(program: &Program) -> DiagnosticsResult<()> {
+    let mut validator = ClientEnumValues {
+        schema: &program.schema,
+        errors: vec![],
+    };
+    validator.validate_program(program)?;
+
+    if validator.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(validator.errors)
+    }
+}
+
+struct ClientEnumValues<'a> {
+    schema: &'a SDLSchema,
+    errors: Vec<Diagnostic>,
+}
+
+impl<'a> ClientEnumValues<'a> {
+    fn validate_field(&mut self, field_id: &WithLocation<FieldID>, arguments: &[Argument]) {
+        let field = self.schema.field(field_id.item);
+        if field.is_extension {
+            return;
+        }
+        for argument in arguments {
+            if let Some(definition) = field.arguments.named(argument.name.item) {
+                self.validate_value(
+                    field.name.item,
+                    &argument.value.item,
+                    &definition.type_,
+                    argument.value.location,
+                );
+            }
+        }
+    }
+
+    fn validate_value(
+        &mut self,
+        field_name: StringKey,
+        value: &Value,
+        type_: &TypeReference<Type>,
+        location: Location,
+    ) {
+        match value {
+            Value::Constant(value) => self.validate_constant(field_name, value, type_, location),
+            // Items have the type of the list, since only its named type is used
+            Value::List(items) => {
+                for item in items {
+                    self.validate_value(field_name, item, type_, location);
+                }
+            }
+            Value::Object(fields) => {
+                if let Type::InputObject(id) = type_.inner() {
+                    let input_object = self.schema.input_object(id);
+                    for field in fields {
+                        if let Some(definition) = input_object.fields.named(field.name.item) {
+                            self.validate_value(
+                                field_name,
+                                &field.value.item,
+                                &definition.type_,
+                                field.value.location,
+                            );
+                        }
+                    }
+                }
+            }
+            Value::Variable(_) => {}
+        }
+    }
+
+    fn validate_constant(
+        &mut self,
+        field_name: StringKey,
+        value: &ConstantValue,
+        type_: &TypeReference<Type>,
+        location: Location,
+    ) {
+        match value {
+            ConstantValue::Enum(value) => {
+                if let Type::Enum(id) = type_.inner() {
+                    let enum_ = self.schema.enum_(id);
+                    if enum_
+                        .values
+                        .iter()
+                        .any(|enum_value| enum_value.value == *value && enum_value.is_extension)
+                    {
+                        self.errors.push(Diagnostic::error(
+                            ValidationMessage::ClientEnumValueOnServerField {
+                                enum_name: enum_.name.item.0,
+                                value: *value,
+                                field_name,
+                            },
+                            location,
+                        ));
+                    }
+                }
+            }
+            ConstantValue::List(items) => {
+                for item in items {
+                    self.validate_constant(field_name, item, type_, location);
+                }
+            }
+            ConstantValue::Object(fields) => {
+                if let Type::InputObject(id) = type_.inner() {
+                    let input_object = self.schema.input_object(id);
+                    for field in fields {
+                        if let Some(definition) = input_object.fields.named(field.name.item) {
+                            self.validate_constant(
+                                field_name,
+                                &field.value.item,
+                                &definition.type_,
+                                field.value.location,
+                            );
+                        }
+                    }
+                }
+            }
+            ConstantValue::Int(_)
+            | ConstantValue::Float(_)
+            | ConstantValue::String(_)
+            | ConstantValue::Boolean(_)
+            | ConstantValue::Null() => {}
+        }
+    }
+}
+
+impl<'a> Validator for ClientEnumValues<'a> {
+    const NAME: &'static str = "ClientEnumValues";
+    const VALIDATE_ARGUMENTS: bool = false;
+    const VALIDATE_DIRECTIVES: bool = false;
+
+    fn validate_linked_field(&mut self, field: &LinkedField) -> DiagnosticsResult<()> {
+        self.validate_field(&field.definition, &field.arguments);
+        self.default_validate_linked_field(field)
+    }
+
+    fn validate_scalar_field(&mut self, field: &ScalarField) -> DiagnosticsResult<()> {
+        self.validate_field(&field.definition, &field.arguments);
+        self.default_validate_scalar_field(field)
+    }
+}
@@ -14,6 +14,7 @@ use graphql_ir::Program;
 use graphql_ir::ValidationMessage;
 use graphql_ir::Validator;
 use intern::Lookup;
+use schema::Schema;
 
 use crate::root_variables::InferVariablesVisitor;
 use crate::DIRECTIVE_SPLIT_OPERATION;
@@ -23,12 +24,14 @@ pub fn validate_global_variables(program: &Program) -> DiagnosticsResult<()> {
 }
 
 pub struct ValidateGlobalVariables<'program> {
+    program: &'program Program,
     visitor: InferVariablesVisitor<'program>,
 }
 
 impl<'program> ValidateGlobalVariables<'program> {
     fn new(program: &'program Program) -> Self {
         Self {
+            program,
             visitor: InferVariablesVisitor::new(program),
         }
     }
@@ -80,7 +83,16 @@ impl Validator for ValidateGlobalVariables<'_> {
                     ),
                 },
                 locations.next().unwrap(),
-            );
+            )
+            .metadata_for_machine("err", "GlobalVariables");
+            // The types inferred from the argument positions, so that IDEs can offer
+            // to declare the variables.
+            for var in &undefined_variables {
+                error = error.metadata_for_machine(
+                    format!("variable.{}", var.name.item.0),
+                    self.program.schema.get_type_string(&var.type_),
+                );
+            }
             for related_location in locations {
                 error = error.annotate("related location", related_location);
             }
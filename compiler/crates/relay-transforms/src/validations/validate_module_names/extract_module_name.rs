@@ -174,6 +174,14 @@ mod tests {
             extract_module_name("/path/button/indexButton.js"),
             Some("indexButton".to_string())
         );
+        assert_eq!(
+            extract_module_name("/path/ButtonQuery.graphql"),
+            Some("ButtonQuery".to_string())
+        );
+        assert_eq!(
+            extract_module_name("/path/button/index.gql"),
+            Some("button".to_string())
+        );
     }
 
     #[cfg(windows)]
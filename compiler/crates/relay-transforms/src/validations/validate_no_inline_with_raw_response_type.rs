@@ -78,7 +78,7 @@ impl<'a> Validator for NoInlineRawResponseTypeValidator<'a> {
                         ValidationMessage::RequiredRawResponseTypeOnNoInline {
                             fragment_name: fragment.name.item,
                         },
-                        fragment.name.location,
+                        directive.name.location,
                     )
                     .annotate(
                         "The query with @raw_response_type",
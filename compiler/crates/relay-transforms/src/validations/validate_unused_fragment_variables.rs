@@ -65,13 +65,16 @@ impl Validator for ValidateUnusedFragmentVariables {
                         expect_unused_directive.name.location,
                     ))
                 } else {
-                    Some(Diagnostic::error(
-                        ValidationMessage::UnusedFragmentVariable {
-                            fragment_name: fragment.name.item,
-                            variable_name: variable_definition.name.item,
-                        },
-                        variable_definition.name.location,
-                    ))
+                    Some(
+                        Diagnostic::error(
+                            ValidationMessage::UnusedFragmentVariable {
+                                fragment_name: fragment.name.item,
+                                variable_name: variable_definition.name.item,
+                            },
+                            variable_definition.name.location,
+                        )
+                        .metadata_for_machine("err", "UnusedFragmentVariable"),
+                    )
                 }
             })
             .collect();
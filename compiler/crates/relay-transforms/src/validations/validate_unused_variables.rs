@@ -65,6 +65,7 @@ impl Validator for ValidateUnusedVariables<'_> {
                         },
                         unused_variable.name.location,
                     )
+                    .metadata_for_machine("err", "UnusedVariable")
                 })
                 .collect());
         }
@@ -75,7 +76,8 @@ impl Validator for ValidateUnusedVariables<'_> {
                         operation_name: operation.name.item.0,
                     },
                     directive.name.location,
-                )]);
+                )
+                .metadata_for_machine("err", "UnusedIgnoreUnusedVariablesDirective")]);
             }
         }
         Ok(())
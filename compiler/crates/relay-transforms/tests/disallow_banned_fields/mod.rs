@@ -0,0 +1,48 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use relay_config::SchemaConfig;
+use relay_test_schema::get_test_schema;
+use relay_transforms::disallow_banned_fields;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let parts: Vec<_> = fixture.content.split("%banned_fields%").collect();
+
+    if let [_, banned_fields] = parts.as_slice() {
+        let source_location = SourceLocationKey::standalone(fixture.file_name);
+        let ast = parse_executable(fixture.content, source_location).unwrap();
+        let schema = get_test_schema();
+
+        let ir = build(&schema, &ast.definitions).unwrap();
+        let program = Program::from_definitions(Arc::clone(&schema), ir);
+
+        let schema_config = SchemaConfig {
+            banned_fields: banned_fields
+                .lines()
+                .map(|line| line.trim_start_matches('#').trim())
+                .filter(|line| !line.is_empty())
+                .map(|line| line.parse().unwrap())
+                .collect(),
+            ..Default::default()
+        };
+
+        disallow_banned_fields(&program, &schema_config)
+            .map_err(|diagnostics| diagnostics_to_sorted_string(fixture.content, &diagnostics))?;
+
+        Ok("OK".to_owned())
+    } else {
+        panic!("Expected exactly one %banned_fields% section marker.")
+    }
+}
@@ -0,0 +1,41 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<4aa45f4499dc9e8d0c6f5303658f5737>>
+ */
+
+mod disallow_banned_fields;
+
+use disallow_banned_fields::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn banned_argument_invalid() {
+    let input = include_str!("disallow_banned_fields/fixtures/banned-argument.invalid.graphql");
+    let expected = include_str!("disallow_banned_fields/fixtures/banned-argument.invalid.expected");
+    test_fixture(transform_fixture, "banned-argument.invalid.graphql", "disallow_banned_fields/fixtures/banned-argument.invalid.expected", input, expected);
+}
+
+#[test]
+fn banned_field_invalid() {
+    let input = include_str!("disallow_banned_fields/fixtures/banned-field.invalid.graphql");
+    let expected = include_str!("disallow_banned_fields/fixtures/banned-field.invalid.expected");
+    test_fixture(transform_fixture, "banned-field.invalid.graphql", "disallow_banned_fields/fixtures/banned-field.invalid.expected", input, expected);
+}
+
+#[test]
+fn unbanned_selections() {
+    let input = include_str!("disallow_banned_fields/fixtures/unbanned-selections.graphql");
+    let expected = include_str!("disallow_banned_fields/fixtures/unbanned-selections.expected");
+    test_fixture(transform_fixture, "unbanned-selections.graphql", "disallow_banned_fields/fixtures/unbanned-selections.expected", input, expected);
+}
+
+#[test]
+fn undefined_coordinates_invalid() {
+    let input = include_str!("disallow_banned_fields/fixtures/undefined-coordinates.invalid.graphql");
+    let expected = include_str!("disallow_banned_fields/fixtures/undefined-coordinates.invalid.expected");
+    test_fixture(transform_fixture, "undefined-coordinates.invalid.graphql", "disallow_banned_fields/fixtures/undefined-coordinates.invalid.expected", input, expected);
+}
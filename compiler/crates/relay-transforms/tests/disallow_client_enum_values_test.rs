@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<7e3ed48dcf6adf91aa6d34510eff103a>>
+ */
+
+mod disallow_client_enum_values;
+
+use disallow_client_enum_values::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn client_enum_value_on_client_field() {
+    let input = include_str!("disallow_client_enum_values/fixtures/client-enum-value-on-client-field.graphql");
+    let expected = include_str!("disallow_client_enum_values/fixtures/client-enum-value-on-client-field.expected");
+    test_fixture(transform_fixture, "client-enum-value-on-client-field.graphql", "disallow_client_enum_values/fixtures/client-enum-value-on-client-field.expected", input, expected);
+}
+
+#[test]
+fn client_enum_value_on_server_field_invalid() {
+    let input = include_str!("disallow_client_enum_values/fixtures/client-enum-value-on-server-field.invalid.graphql");
+    let expected = include_str!("disallow_client_enum_values/fixtures/client-enum-value-on-server-field.invalid.expected");
+    test_fixture(transform_fixture, "client-enum-value-on-server-field.invalid.graphql", "disallow_client_enum_values/fixtures/client-enum-value-on-server-field.invalid.expected", input, expected);
+}
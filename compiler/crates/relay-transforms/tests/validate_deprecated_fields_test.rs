@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<fe6689a7953b227a33d0f86a2ab54818>>
+ * @generated SignedSource<<058e81cc698533a18ca473caa8540aed>>
  */
 
 mod validate_deprecated_fields;
@@ -54,6 +54,13 @@ fn deprecated_field_with_reason() {
     test_fixture(transform_fixture, "deprecated_field_with_reason.graphql", "validate_deprecated_fields/fixtures/deprecated_field_with_reason.expected", input, expected);
 }
 
+#[test]
+fn deprecated_input_field() {
+    let input = include_str!("validate_deprecated_fields/fixtures/deprecated_input_field.graphql");
+    let expected = include_str!("validate_deprecated_fields/fixtures/deprecated_input_field.expected");
+    test_fixture(transform_fixture, "deprecated_input_field.graphql", "validate_deprecated_fields/fixtures/deprecated_input_field.expected", input, expected);
+}
+
 #[test]
 fn deprecated_linked_field() {
     let input = include_str!("validate_deprecated_fields/fixtures/deprecated_linked_field.graphql");
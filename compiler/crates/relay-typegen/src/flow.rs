@@ -78,6 +78,7 @@ impl Writer for FlowPrinter {
                 self.write(type_)?;
                 write!(&mut self.result, "['{}']", property_name)
             }
+            AST::Deprecated { type_, .. } => self.write(type_),
         }
     }
 
@@ -261,6 +262,15 @@ impl FlowPrinter {
                         )?;
                         self.write_indentation()?;
                     }
+                    if let AST::Deprecated { reason, .. } = &key_value_pair.value {
+                        match reason {
+                            Some(reason) => {
+                                writeln!(&mut self.result, "/** @deprecated {} */", reason)?
+                            }
+                            None => writeln!(&mut self.result, "/** @deprecated */")?,
+                        }
+                        self.write_indentation()?;
+                    }
                     if key_value_pair.read_only {
                         write!(&mut self.result, "+")?;
                     }
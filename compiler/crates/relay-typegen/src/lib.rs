@@ -238,6 +238,9 @@ struct TypegenContext<'a> {
     project_config: &'a ProjectConfig,
     fragment_locations: &'a FragmentLocations,
     has_unified_output: bool,
+    // Fields marked with @semanticNonNull are typed as non-null, which is
+    // only enabled for schemas with semantic nullability support
+    emit_semantic_nullability_types: bool,
     generating_updatable_types: bool,
     definition_source_location: WithLocation<StringKey>,
     // All keys in raw response should be required
@@ -258,6 +261,10 @@ impl<'a> TypegenContext<'a> {
             project_config,
             fragment_locations,
             has_unified_output: project_config.output.is_some(),
+            emit_semantic_nullability_types: project_config.schema_config.semantic_non_null_support
+                && project_config
+                    .typegen_config
+                    .experimental_emit_semantic_nullability_types,
             generating_updatable_types,
             definition_source_location,
             no_optional_fields_in_raw_response_type,
@@ -6,6 +6,8 @@
  */
 
 use std::fmt::Result as FmtResult;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use common::InputObjectName;
@@ -17,11 +19,11 @@ use graphql_ir::FragmentDefinitionName;
 use indexmap::IndexMap;
 use intern::string_key::StringKey;
 use intern::Lookup;
+use relay_config::ProjectConfig;
 use schema::EnumID;
 use schema::SDLSchema;
 use schema::Schema;
 
-use crate::writer::ExactObject;
 use crate::writer::Writer;
 use crate::writer::AST;
 use crate::KEY_DATA_ID;
@@ -70,14 +72,14 @@ impl RuntimeImports {
 /// An enum used to prevent redundantly processing input objects.
 pub(crate) enum GeneratedInputObject {
     Pending,
-    Resolved(ExactObject),
+    Resolved(AST),
 }
 
 impl GeneratedInputObject {
-    pub(crate) fn unwrap_resolved_type(self) -> ExactObject {
+    pub(crate) fn unwrap_resolved_type(self) -> AST {
         match self {
             GeneratedInputObject::Pending => panic!("Unexpected pending type"),
-            GeneratedInputObject::Resolved(exact_object) => exact_object,
+            GeneratedInputObject::Resolved(ast) => ast,
         }
     }
 }
@@ -113,19 +115,52 @@ pub(crate) enum EncounteredFragment {
 /// reference it in another generated artifact.
 /// This is used in non-haste setups that do not have a single
 /// directory for generated artifacts.
-pub struct FragmentLocations(pub FnvHashMap<FragmentDefinitionName, Location>);
+pub struct FragmentLocations {
+    locations: FnvHashMap<FragmentDefinitionName, Location>,
+    /// Fragments of a base project are generated by that project, so their
+    /// artifacts may not be in the output directory of the current project.
+    base_artifact_paths: FnvHashMap<FragmentDefinitionName, PathBuf>,
+}
 
 impl FragmentLocations {
     pub fn new<'a>(fragments: impl Iterator<Item = &'a Arc<FragmentDefinition>>) -> Self {
-        Self(
-            fragments
+        Self {
+            locations: fragments
                 .map(|fragment| (fragment.name.item, fragment.name.location))
                 .collect::<_>(),
-        )
+            base_artifact_paths: Default::default(),
+        }
+    }
+
+    /// Adds the fragments of the base project with the given config.
+    pub fn add_base_fragments<'a>(
+        &mut self,
+        base_project_config: &ProjectConfig,
+        fragments: impl Iterator<Item = &'a Arc<FragmentDefinition>>,
+    ) {
+        for fragment in fragments {
+            self.locations
+                .insert(fragment.name.item, fragment.name.location);
+            self.base_artifact_paths.insert(
+                fragment.name.item,
+                base_project_config.create_path_for_artifact(
+                    fragment.name.location.source_location(),
+                    fragment.name.item.to_string(),
+                ),
+            );
+        }
     }
 
     pub fn location(&self, fragment_name: &FragmentDefinitionName) -> Option<Location> {
-        self.0.get(fragment_name).copied()
+        self.locations.get(fragment_name).copied()
+    }
+
+    /// The artifact path (without extension) of a fragment defined in a base
+    /// project.
+    pub fn base_artifact_path(&self, fragment_name: &FragmentDefinitionName) -> Option<&Path> {
+        self.base_artifact_paths
+            .get(fragment_name)
+            .map(|path| path.as_path())
     }
 }
 
@@ -88,6 +88,7 @@ impl Writer for TypeScriptPrinter {
                 self.write(type_)?;
                 write!(&mut self.result, "['{}']", property_name)
             }
+            AST::Deprecated { type_, .. } => self.write(type_),
         }
     }
 
@@ -258,6 +259,15 @@ impl TypeScriptPrinter {
                         )?;
                         self.write_indentation()?;
                     }
+                    if let AST::Deprecated { reason, .. } = &key_value_pair.value {
+                        match reason {
+                            Some(reason) => {
+                                writeln!(&mut self.result, "/** @deprecated {} */", reason)?
+                            }
+                            None => writeln!(&mut self.result, "/** @deprecated */")?,
+                        }
+                        self.write_indentation()?;
+                    }
                     if key_value_pair.read_only {
                         write!(&mut self.result, "readonly ")?;
                     }
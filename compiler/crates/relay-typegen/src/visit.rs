@@ -50,6 +50,7 @@ use relay_transforms::CHILDREN_CAN_BUBBLE_METADATA_KEY;
 use relay_transforms::CLIENT_EXTENSION_DIRECTIVE_NAME;
 use relay_transforms::RELAY_ACTOR_CHANGE_DIRECTIVE_FOR_CODEGEN;
 use relay_transforms::UPDATABLE_DIRECTIVE_FOR_TYPEGEN;
+use schema::Argument;
 use schema::EnumID;
 use schema::Field;
 use schema::SDLSchema;
@@ -169,6 +170,7 @@ pub(crate) fn visit_selections(
                     typegen_context.schema,
                     &mut type_selections,
                     linked_field,
+                    typegen_context.emit_semantic_nullability_types,
                     |selections| {
                         visit_selections(
                             typegen_context,
@@ -212,6 +214,7 @@ pub(crate) fn visit_selections(
                         encountered_enums,
                         custom_scalars,
                         enclosing_linked_field_concrete_type,
+                        typegen_context.emit_semantic_nullability_types,
                     )
                 }
             }
@@ -957,6 +960,7 @@ fn gen_visit_linked_field(
     schema: &SDLSchema,
     type_selections: &mut Vec<TypeSelection>,
     linked_field: &LinkedField,
+    semantic_nullability: bool,
     mut visit_selections_fn: impl FnMut(&[Selection]) -> Vec<TypeSelection>,
 ) {
     let field = schema.field(linked_field.definition.item);
@@ -968,7 +972,10 @@ fn gen_visit_linked_field(
     };
     let selections = visit_selections_fn(&linked_field.selections);
 
-    let node_type = apply_required_directive_nullability(&field.type_, &linked_field.directives);
+    let node_type = apply_required_directive_nullability(
+        &semantic_field_type(field, semantic_nullability),
+        &linked_field.directives,
+    );
 
     type_selections.push(TypeSelection::LinkedField(TypeSelectionLinkedField {
         field_name_or_alias: key,
@@ -986,6 +993,7 @@ fn visit_scalar_field(
     encountered_enums: &mut EncounteredEnums,
     custom_scalars: &mut CustomScalarsImports,
     enclosing_linked_field_concrete_type: Option<Type>,
+    semantic_nullability: bool,
 ) {
     let field = typegen_context.schema.field(scalar_field.definition.item);
     let schema_name = field.name.item;
@@ -994,7 +1002,10 @@ fn visit_scalar_field(
     } else {
         schema_name
     };
-    let field_type = apply_required_directive_nullability(&field.type_, &scalar_field.directives);
+    let field_type = apply_required_directive_nullability(
+        &semantic_field_type(field, semantic_nullability),
+        &scalar_field.directives,
+    );
     let special_field = ScalarFieldSpecialSchemaField::from_schema_name(
         schema_name,
         &typegen_context.project_config.schema_config,
@@ -1814,12 +1825,13 @@ fn transform_graphql_scalar_type(
     // TODO: We could implement custom variables that are provided via the
     // config by inserting them into the schema with directives, thus avoiding
     // having two different ways to express typed custom scalars internally.
-    if let Some(custom_scalar) = typegen_context
-        .project_config
-        .typegen_config
-        .custom_scalar_types
-        .get(&scalar_name.item)
-    {
+    if let Some(custom_scalar) = typegen_context.project_config.custom_scalar_type(
+        scalar_name.item,
+        typegen_context
+            .definition_source_location
+            .location
+            .source_location(),
+    ) {
         match custom_scalar {
             CustomScalarType::Name(custom_scalar) => AST::RawType(*custom_scalar),
             CustomScalarType::Path(CustomScalarTypeImport { name, path }) => {
@@ -1920,6 +1932,7 @@ pub(crate) fn raw_response_visit_selections(
                     typegen_context.schema,
                     &mut type_selections,
                     linked_field,
+                    false,
                     |selections| {
                         raw_response_visit_selections(
                             typegen_context,
@@ -1935,6 +1948,7 @@ pub(crate) fn raw_response_visit_selections(
                     },
                 )
             }
+            // The raw response has the nulls of fields with errors
             Selection::ScalarField(scalar_field) => visit_scalar_field(
                 typegen_context,
                 &mut type_selections,
@@ -1942,6 +1956,7 @@ pub(crate) fn raw_response_visit_selections(
                 encountered_enums,
                 custom_scalars,
                 enclosing_linked_field_concrete_type,
+                false,
             ),
             Selection::Condition(condition) => {
                 type_selections.extend(raw_response_visit_selections(
@@ -1989,34 +2004,66 @@ fn transform_non_nullable_input_type(
                     input_object_types
                         .insert(input_object.name.item, GeneratedInputObject::Pending);
 
-                    let props = ExactObject::new(
-                        input_object
-                            .fields
-                            .iter()
-                            .map(|field| {
-                                Prop::KeyValuePair(KeyValuePairProp {
-                                    key: field.name.0,
-                                    read_only: false,
-                                    optional: !field.type_.is_non_null()
-                                        || typegen_context
-                                            .project_config
-                                            .typegen_config
-                                            .optional_input_fields
-                                            .contains(&field.name.0),
-                                    value: transform_input_type(
-                                        typegen_context,
-                                        &field.type_,
-                                        input_object_types,
-                                        encountered_enums,
-                                        custom_scalars,
-                                    ),
+                    let input_object_type = if input_object.is_one_of() {
+                        // One object type per field, with just that field set
+                        AST::Union(SortedASTList::new(
+                            input_object
+                                .fields
+                                .iter()
+                                .map(|field| {
+                                    AST::ExactObject(ExactObject::new(vec![Prop::KeyValuePair(
+                                        KeyValuePairProp {
+                                            key: field.name.0,
+                                            read_only: false,
+                                            optional: false,
+                                            value: mark_deprecated_input_field(
+                                                field,
+                                                transform_input_type(
+                                                    typegen_context,
+                                                    &field.type_.non_null(),
+                                                    input_object_types,
+                                                    encountered_enums,
+                                                    custom_scalars,
+                                                ),
+                                            ),
+                                        },
+                                    )]))
                                 })
-                            })
-                            .collect(),
-                    );
+                                .collect(),
+                        ))
+                    } else {
+                        AST::ExactObject(ExactObject::new(
+                            input_object
+                                .fields
+                                .iter()
+                                .map(|field| {
+                                    Prop::KeyValuePair(KeyValuePairProp {
+                                        key: field.name.0,
+                                        read_only: false,
+                                        optional: !field.type_.is_non_null()
+                                            || typegen_context
+                                                .project_config
+                                                .typegen_config
+                                                .optional_input_fields
+                                                .contains(&field.name.0),
+                                        value: mark_deprecated_input_field(
+                                            field,
+                                            transform_input_type(
+                                                typegen_context,
+                                                &field.type_,
+                                                input_object_types,
+                                                encountered_enums,
+                                                custom_scalars,
+                                            ),
+                                        ),
+                                    })
+                                })
+                                .collect(),
+                        ))
+                    };
                     input_object_types.insert(
                         input_object.name.item,
-                        GeneratedInputObject::Resolved(props),
+                        GeneratedInputObject::Resolved(input_object_type),
                     );
                 }
                 AST::Identifier(input_object.name.item.0)
@@ -2029,6 +2076,18 @@ fn transform_non_nullable_input_type(
     }
 }
 
+/// Deprecated input fields are marked with a doc comment in the generated
+/// input object type.
+fn mark_deprecated_input_field(field: &Argument, value: AST) -> AST {
+    match field.deprecated() {
+        Some(deprecation) => AST::Deprecated {
+            type_: Box::new(value),
+            reason: deprecation.reason,
+        },
+        None => value,
+    }
+}
+
 pub(crate) fn transform_input_type(
     typegen_context: &'_ TypegenContext<'_>,
     type_ref: &TypeReference<Type>,
@@ -2243,6 +2302,15 @@ fn group_refs(props: impl Iterator<Item = TypeSelection>) -> impl Iterator<Item
     })
 }
 
+/// The type of the field, non-null at the levels of its `@semanticNonNull`
+/// directive when typing semantic nullability.
+fn semantic_field_type(field: &Field, semantic_nullability: bool) -> TypeReference<Type> {
+    match field.semantic_non_null_levels() {
+        Some(levels) if semantic_nullability => field.type_.with_non_null_levels(&levels),
+        _ => field.type_.clone(),
+    }
+}
+
 fn apply_required_directive_nullability(
     field_type: &TypeReference<Type>,
     directives: &[Directive],
@@ -482,7 +482,21 @@ fn write_fragment_imports(
 
         match typegen_context.project_config.js_module_format {
             JsModuleFormat::CommonJS => {
-                if typegen_context.has_unified_output {
+                if let Some(base_artifact_path) = typegen_context
+                    .fragment_locations
+                    .base_artifact_path(&current_referenced_fragment)
+                {
+                    let fragment_import_path =
+                        typegen_context.project_config.js_module_import_path(
+                            typegen_context.definition_source_location,
+                            base_artifact_path.to_str().unwrap().intern(),
+                        );
+
+                    writer.write_import_fragment_type(
+                        &[&fragment_type_name],
+                        &format!("./{}.graphql", fragment_import_path),
+                    )?;
+                } else if typegen_context.has_unified_output {
                     writer.write_import_fragment_type(
                         &[&fragment_type_name],
                         &format!("./{}.graphql", current_referenced_fragment),
@@ -711,11 +725,11 @@ fn generate_provided_variables_type(
 }
 
 fn write_input_object_types(
-    input_object_types: impl Iterator<Item = (InputObjectName, ExactObject)>,
+    input_object_types: impl Iterator<Item = (InputObjectName, AST)>,
     writer: &mut Box<dyn Writer>,
 ) -> FmtResult {
     for (type_identifier, input_object_type) in input_object_types {
-        writer.write_export_type(type_identifier.lookup(), &input_object_type.into())?;
+        writer.write_export_type(type_identifier.lookup(), &input_object_type)?;
     }
     Ok(())
 }
@@ -58,6 +58,12 @@ pub enum AST {
         type_: Box<AST>,
         property_name: StringKey,
     },
+    /// Prints as `type_`, with a `@deprecated` doc comment on the property
+    /// it is the value of.
+    Deprecated {
+        type_: Box<AST>,
+        reason: Option<StringKey>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -26,6 +26,7 @@ use relay_codegen::JsModuleFormat;
 use relay_config::CustomScalarType;
 use relay_config::CustomScalarTypeImport;
 use relay_config::ProjectConfig;
+use relay_config::SchemaConfig;
 use relay_test_schema::get_test_schema;
 use relay_test_schema::get_test_schema_with_extensions;
 use relay_transforms::apply_transforms;
@@ -83,13 +84,19 @@ pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
             path: "TypeDefsFile".into(),
         }),
     );
+    let semantic_non_null_support = fixture.content.contains("# semantic-non-null-support");
     let project_config = ProjectConfig {
         name: "test".intern(),
         js_module_format: JsModuleFormat::Haste,
         feature_flags: Arc::new(feature_flags),
+        schema_config: SchemaConfig {
+            semantic_non_null_support,
+            ..Default::default()
+        },
         typegen_config: TypegenConfig {
             language: TypegenLanguage::Flow,
             custom_scalar_types,
+            experimental_emit_semantic_nullability_types: semantic_non_null_support,
             ..Default::default()
         },
         ..Default::default()
@@ -102,6 +109,7 @@ pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
         Arc::new(ConsoleLogger),
         None,
         None,
+        None,
     )
     .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
 
@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<83c7978511f69151b68e1476d398d6e3>>
+ * @generated SignedSource<<60592ef7d04cd028deb5fbddfe0444c5>>
  */
 
 mod generate_flow;
@@ -201,6 +201,13 @@ fn query_provided_variables_custom_scalar() {
     test_fixture(transform_fixture, "query-provided-variables-custom-scalar.graphql", "generate_flow/fixtures/query-provided-variables-custom-scalar.expected", input, expected);
 }
 
+#[test]
+fn query_with_deprecated_input_field() {
+    let input = include_str!("generate_flow/fixtures/query-with-deprecated-input-field.graphql");
+    let expected = include_str!("generate_flow/fixtures/query-with-deprecated-input-field.expected");
+    test_fixture(transform_fixture, "query-with-deprecated-input-field.graphql", "generate_flow/fixtures/query-with-deprecated-input-field.expected", input, expected);
+}
+
 #[test]
 fn query_with_handles() {
     let input = include_str!("generate_flow/fixtures/query-with-handles.graphql");
@@ -229,6 +236,13 @@ fn query_with_multiple_match_fields() {
     test_fixture(transform_fixture, "query-with-multiple-match-fields.graphql", "generate_flow/fixtures/query-with-multiple-match-fields.expected", input, expected);
 }
 
+#[test]
+fn query_with_one_of_input() {
+    let input = include_str!("generate_flow/fixtures/query-with-one-of-input.graphql");
+    let expected = include_str!("generate_flow/fixtures/query-with-one-of-input.expected");
+    test_fixture(transform_fixture, "query-with-one-of-input.graphql", "generate_flow/fixtures/query-with-one-of-input.expected", input, expected);
+}
+
 #[test]
 fn query_with_raw_response_on_conditional() {
     let input = include_str!("generate_flow/fixtures/query-with-raw-response-on-conditional.graphql");
@@ -684,6 +698,13 @@ fn scalar_field() {
     test_fixture(transform_fixture, "scalar-field.graphql", "generate_flow/fixtures/scalar-field.expected", input, expected);
 }
 
+#[test]
+fn semantic_non_null() {
+    let input = include_str!("generate_flow/fixtures/semantic-non-null.graphql");
+    let expected = include_str!("generate_flow/fixtures/semantic-non-null.expected");
+    test_fixture(transform_fixture, "semantic-non-null.graphql", "generate_flow/fixtures/semantic-non-null.expected", input, expected);
+}
+
 #[test]
 fn simple() {
     let input = include_str!("generate_flow/fixtures/simple.graphql");
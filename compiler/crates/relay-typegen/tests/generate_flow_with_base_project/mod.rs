@@ -0,0 +1,132 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::ConsoleLogger;
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build_ir_in_relay_mode;
+use graphql_ir::ExecutableDefinition;
+use graphql_ir::FragmentDefinitionNameSet;
+use graphql_ir::OperationDefinitionName;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use intern::string_key::Intern;
+use relay_codegen::JsModuleFormat;
+use relay_config::ProjectConfig;
+use relay_test_schema::get_test_schema;
+use relay_transforms::apply_transforms;
+use relay_typegen::FragmentLocations;
+use relay_typegen::TypegenConfig;
+use relay_typegen::TypegenLanguage;
+
+/// The fixture contains the documents of the base project and of the
+/// project, separated by `%project%`. The artifacts of both projects are
+/// generated into different output directories.
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let parts = fixture.content.split("%project%").collect::<Vec<_>>();
+    let (base_source, source) = match parts.as_slice() {
+        [base_source, source] => (base_source, source),
+        _ => panic!("Expected the base project and project documents separated by %project%"),
+    };
+    let schema = get_test_schema();
+
+    let base_ast = parse_executable(
+        base_source,
+        SourceLocationKey::standalone("base/components/Base.js"),
+    )
+    .map_err(|diagnostics| diagnostics_to_sorted_string(base_source, &diagnostics))?;
+    let ast = parse_executable(source, SourceLocationKey::standalone("app/pages/App.js"))
+        .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+
+    let base_ir = build_ir_in_relay_mode(&schema, &base_ast.definitions)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(base_source, &diagnostics))?;
+    let base_fragment_names = base_ir
+        .iter()
+        .filter_map(|definition| match definition {
+            ExecutableDefinition::Fragment(fragment) => Some(fragment.name.item),
+            ExecutableDefinition::Operation(_) => None,
+        })
+        .collect::<FragmentDefinitionNameSet>();
+    let mut ir = build_ir_in_relay_mode(
+        &schema,
+        &ast.definitions
+            .iter()
+            .chain(&base_ast.definitions)
+            .cloned()
+            .collect::<Vec<_>>(),
+    )
+    .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+    ir.sort_by_key(|definition| definition.name_with_location().item);
+    let program = Program::from_definitions(Arc::clone(&schema), ir);
+
+    let base_project_config = ProjectConfig {
+        name: "base".intern(),
+        output: Some("base/__generated__".into()),
+        js_module_format: JsModuleFormat::CommonJS,
+        ..Default::default()
+    };
+    let project_config = ProjectConfig {
+        name: "app".intern(),
+        base: Some(base_project_config.name),
+        output: Some("app/__generated__".into()),
+        js_module_format: JsModuleFormat::CommonJS,
+        typegen_config: TypegenConfig {
+            language: TypegenLanguage::Flow,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let programs = apply_transforms(
+        &project_config,
+        Arc::new(program),
+        Arc::new(base_fragment_names),
+        Arc::new(ConsoleLogger),
+        None,
+        None,
+        None,
+    )
+    .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+
+    let mut fragment_locations = FragmentLocations::new(programs.typegen.fragments());
+    fragment_locations.add_base_fragments(
+        &base_project_config,
+        programs
+            .source
+            .fragments()
+            .filter(|fragment| programs.base_fragment_names.contains(&fragment.name.item)),
+    );
+
+    let operation_strings = programs.typegen.operations().map(|typegen_operation| {
+        let normalization_operation = programs
+            .normalization
+            .operation(OperationDefinitionName(typegen_operation.name.item.0))
+            .unwrap();
+        relay_typegen::generate_operation_type_exports_section(
+            typegen_operation,
+            normalization_operation,
+            &schema,
+            &project_config,
+            &fragment_locations,
+        )
+    });
+    let fragment_strings = programs.typegen.fragments().map(|fragment| {
+        relay_typegen::generate_fragment_type_exports_section(
+            fragment,
+            &schema,
+            &project_config,
+            &fragment_locations,
+        )
+    });
+
+    let mut result: Vec<String> = operation_strings.collect();
+    result.extend(fragment_strings);
+    Ok(result
+        .join("-------------------------------------------------------------------------------\n"))
+}
@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<252f2cf548897fbb6388f011c268eff4>>
+ */
+
+mod generate_flow_with_base_project;
+
+use generate_flow_with_base_project::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn base_fragment_spread() {
+    let input = include_str!("generate_flow_with_base_project/fixtures/base-fragment-spread.graphql");
+    let expected = include_str!("generate_flow_with_base_project/fixtures/base-fragment-spread.expected");
+    test_fixture(transform_fixture, "base-fragment-spread.graphql", "generate_flow_with_base_project/fixtures/base-fragment-spread.expected", input, expected);
+}
+
+#[test]
+fn unreferenced_base_fragment() {
+    let input = include_str!("generate_flow_with_base_project/fixtures/unreferenced-base-fragment.graphql");
+    let expected = include_str!("generate_flow_with_base_project/fixtures/unreferenced-base-fragment.expected");
+    test_fixture(transform_fixture, "unreferenced-base-fragment.graphql", "generate_flow_with_base_project/fixtures/unreferenced-base-fragment.expected", input, expected);
+}
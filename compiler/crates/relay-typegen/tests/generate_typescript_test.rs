@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<c12cbcb2699b05580be1a62dde8a3bcf>>
+ * @generated SignedSource<<54a29450790a94f2193248fd3b4dca94>>
  */
 
 mod generate_typescript;
@@ -159,6 +159,13 @@ fn plural_fragment() {
     test_fixture(transform_fixture, "plural-fragment.graphql", "generate_typescript/fixtures/plural-fragment.expected", input, expected);
 }
 
+#[test]
+fn query_with_deprecated_input_field() {
+    let input = include_str!("generate_typescript/fixtures/query-with-deprecated-input-field.graphql");
+    let expected = include_str!("generate_typescript/fixtures/query-with-deprecated-input-field.expected");
+    test_fixture(transform_fixture, "query-with-deprecated-input-field.graphql", "generate_typescript/fixtures/query-with-deprecated-input-field.expected", input, expected);
+}
+
 #[test]
 fn query_with_handles() {
     let input = include_str!("generate_typescript/fixtures/query-with-handles.graphql");
@@ -187,6 +194,13 @@ fn query_with_multiple_match_fields() {
     test_fixture(transform_fixture, "query-with-multiple-match-fields.graphql", "generate_typescript/fixtures/query-with-multiple-match-fields.expected", input, expected);
 }
 
+#[test]
+fn query_with_one_of_input() {
+    let input = include_str!("generate_typescript/fixtures/query-with-one-of-input.graphql");
+    let expected = include_str!("generate_typescript/fixtures/query-with-one-of-input.expected");
+    test_fixture(transform_fixture, "query-with-one-of-input.graphql", "generate_typescript/fixtures/query-with-one-of-input.expected", input, expected);
+}
+
 #[test]
 fn query_with_raw_response_on_conditional() {
     let input = include_str!("generate_typescript/fixtures/query-with-raw-response-on-conditional.graphql");
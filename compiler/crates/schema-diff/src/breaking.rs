@@ -0,0 +1,698 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Classification of the changes between two schemas by their effect on
+//! clients, similar to `findBreakingChanges` of graphql-js.
+
+use std::fmt;
+
+use common::ArgumentName;
+use common::DirectiveName;
+use common::SchemaCoordinate;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use schema::SDLSchema;
+use schema::diff::AddedInputValue;
+use schema::diff::FieldDiff;
+use schema::diff::FieldsDiff;
+use schema::diff::InputValueDiff;
+use schema::diff::InputValuesDiff;
+use schema::diff::NamesDiff;
+use schema::diff::TypeDiffKind;
+
+use crate::definitions::Type;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeSeverity {
+    /// Documents that use the changed member are invalid, or fail at runtime.
+    Breaking,
+    /// Documents stay valid, but clients may get data they don't handle,
+    /// e.g. a new enum value.
+    Dangerous,
+    Safe,
+}
+
+impl ChangeSeverity {
+    pub fn name(self) -> &'static str {
+        match self {
+            ChangeSeverity::Breaking => "breaking",
+            ChangeSeverity::Dangerous => "dangerous",
+            ChangeSeverity::Safe => "safe",
+        }
+    }
+}
+
+/// A change from the old to the new schema. `type_name`s of fields and
+/// arguments are the types that define them.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    TypeAdded(StringKey),
+    TypeRemoved(StringKey),
+    TypeKindChanged {
+        type_name: StringKey,
+        old_kind: &'static str,
+        new_kind: &'static str,
+    },
+    FieldAdded {
+        type_name: StringKey,
+        field_name: StringKey,
+    },
+    FieldRemoved {
+        type_name: StringKey,
+        field_name: StringKey,
+    },
+    FieldTypeChanged {
+        type_name: StringKey,
+        field_name: StringKey,
+        old_type: Type,
+        new_type: Type,
+    },
+    FieldDeprecated {
+        type_name: StringKey,
+        field_name: StringKey,
+    },
+    ArgumentAdded {
+        type_name: StringKey,
+        field_name: StringKey,
+        argument_name: StringKey,
+        required: bool,
+    },
+    ArgumentRemoved {
+        type_name: StringKey,
+        field_name: StringKey,
+        argument_name: StringKey,
+    },
+    ArgumentTypeChanged {
+        type_name: StringKey,
+        field_name: StringKey,
+        argument_name: StringKey,
+        old_type: Type,
+        new_type: Type,
+    },
+    ArgumentDefaultChanged {
+        type_name: StringKey,
+        field_name: StringKey,
+        argument_name: StringKey,
+    },
+    InputFieldAdded {
+        type_name: StringKey,
+        field_name: StringKey,
+        required: bool,
+    },
+    InputFieldRemoved {
+        type_name: StringKey,
+        field_name: StringKey,
+    },
+    InputFieldTypeChanged {
+        type_name: StringKey,
+        field_name: StringKey,
+        old_type: Type,
+        new_type: Type,
+    },
+    EnumValueAdded {
+        type_name: StringKey,
+        value: StringKey,
+    },
+    EnumValueRemoved {
+        type_name: StringKey,
+        value: StringKey,
+    },
+    UnionMemberAdded {
+        type_name: StringKey,
+        member: StringKey,
+    },
+    UnionMemberRemoved {
+        type_name: StringKey,
+        member: StringKey,
+    },
+    InterfaceAdded {
+        type_name: StringKey,
+        interface: StringKey,
+    },
+    InterfaceRemoved {
+        type_name: StringKey,
+        interface: StringKey,
+    },
+    DirectiveAdded(StringKey),
+    DirectiveRemoved(StringKey),
+}
+
+impl ChangeKind {
+    pub fn severity(&self) -> ChangeSeverity {
+        match self {
+            ChangeKind::TypeRemoved(_)
+            | ChangeKind::TypeKindChanged { .. }
+            | ChangeKind::FieldRemoved { .. }
+            | ChangeKind::ArgumentRemoved { .. }
+            | ChangeKind::InputFieldRemoved { .. }
+            | ChangeKind::EnumValueRemoved { .. }
+            | ChangeKind::UnionMemberRemoved { .. }
+            | ChangeKind::InterfaceRemoved { .. }
+            | ChangeKind::DirectiveRemoved(_) => ChangeSeverity::Breaking,
+            ChangeKind::ArgumentAdded { required, .. }
+            | ChangeKind::InputFieldAdded { required, .. } => {
+                if *required {
+                    ChangeSeverity::Breaking
+                } else {
+                    ChangeSeverity::Dangerous
+                }
+            }
+            ChangeKind::FieldTypeChanged {
+                old_type, new_type, ..
+            } => {
+                if is_safe_output_type_change(old_type, new_type) {
+                    ChangeSeverity::Safe
+                } else {
+                    ChangeSeverity::Breaking
+                }
+            }
+            ChangeKind::ArgumentTypeChanged {
+                old_type, new_type, ..
+            }
+            | ChangeKind::InputFieldTypeChanged {
+                old_type, new_type, ..
+            } => {
+                if is_safe_input_type_change(old_type, new_type) {
+                    ChangeSeverity::Safe
+                } else {
+                    ChangeSeverity::Breaking
+                }
+            }
+            ChangeKind::ArgumentDefaultChanged { .. }
+            | ChangeKind::EnumValueAdded { .. }
+            | ChangeKind::UnionMemberAdded { .. }
+            | ChangeKind::InterfaceAdded { .. } => ChangeSeverity::Dangerous,
+            ChangeKind::TypeAdded(_)
+            | ChangeKind::FieldAdded { .. }
+            | ChangeKind::FieldDeprecated { .. }
+            | ChangeKind::DirectiveAdded(_) => ChangeSeverity::Safe,
+        }
+    }
+
+    /// The schema element that changed. Elements that were removed are in the
+    /// old schema, all others are in the new schema.
+    pub fn coordinate(&self) -> SchemaCoordinate {
+        match *self {
+            ChangeKind::TypeAdded(type_name)
+            | ChangeKind::TypeRemoved(type_name)
+            | ChangeKind::TypeKindChanged { type_name, .. }
+            | ChangeKind::UnionMemberAdded { type_name, .. }
+            | ChangeKind::UnionMemberRemoved { type_name, .. }
+            | ChangeKind::InterfaceAdded { type_name, .. }
+            | ChangeKind::InterfaceRemoved { type_name, .. } => SchemaCoordinate::Type(type_name),
+            ChangeKind::FieldAdded {
+                type_name,
+                field_name,
+            }
+            | ChangeKind::FieldRemoved {
+                type_name,
+                field_name,
+            }
+            | ChangeKind::FieldTypeChanged {
+                type_name,
+                field_name,
+                ..
+            }
+            | ChangeKind::FieldDeprecated {
+                type_name,
+                field_name,
+            }
+            | ChangeKind::InputFieldAdded {
+                type_name,
+                field_name,
+                ..
+            }
+            | ChangeKind::InputFieldRemoved {
+                type_name,
+                field_name,
+            }
+            | ChangeKind::InputFieldTypeChanged {
+                type_name,
+                field_name,
+                ..
+            } => SchemaCoordinate::Member {
+                type_name,
+                member_name: field_name,
+            },
+            ChangeKind::EnumValueAdded { type_name, value }
+            | ChangeKind::EnumValueRemoved { type_name, value } => SchemaCoordinate::Member {
+                type_name,
+                member_name: value,
+            },
+            ChangeKind::ArgumentAdded {
+                type_name,
+                field_name,
+                argument_name,
+                ..
+            }
+            | ChangeKind::ArgumentRemoved {
+                type_name,
+                field_name,
+                argument_name,
+            }
+            | ChangeKind::ArgumentTypeChanged {
+                type_name,
+                field_name,
+                argument_name,
+                ..
+            }
+            | ChangeKind::ArgumentDefaultChanged {
+                type_name,
+                field_name,
+                argument_name,
+            } => SchemaCoordinate::Argument {
+                type_name,
+                field_name,
+                argument_name: ArgumentName(argument_name),
+            },
+            ChangeKind::DirectiveAdded(name) | ChangeKind::DirectiveRemoved(name) => {
+                SchemaCoordinate::Directive(DirectiveName(name))
+            }
+        }
+    }
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeKind::TypeAdded(name) => write!(f, "Type `{}` was added.", name),
+            ChangeKind::TypeRemoved(name) => write!(f, "Type `{}` was removed.", name),
+            ChangeKind::TypeKindChanged {
+                type_name,
+                old_kind,
+                new_kind,
+            } => write!(
+                f,
+                "Type `{}` changed from {} to {}.",
+                type_name, old_kind, new_kind
+            ),
+            ChangeKind::FieldAdded {
+                type_name,
+                field_name,
+            } => write!(f, "Field `{}.{}` was added.", type_name, field_name),
+            ChangeKind::FieldRemoved {
+                type_name,
+                field_name,
+            } => write!(f, "Field `{}.{}` was removed.", type_name, field_name),
+            ChangeKind::FieldTypeChanged {
+                type_name,
+                field_name,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "Field `{}.{}` changed type from `{}` to `{}`.",
+                type_name, field_name, old_type, new_type
+            ),
+            ChangeKind::FieldDeprecated {
+                type_name,
+                field_name,
+            } => write!(f, "Field `{}.{}` was deprecated.", type_name, field_name),
+            ChangeKind::ArgumentAdded {
+                type_name,
+                field_name,
+                argument_name,
+                required,
+            } => write!(
+                f,
+                "{} argument `{}` was added to `{}.{}`.",
+                if *required { "Required" } else { "Optional" },
+                argument_name,
+                type_name,
+                field_name
+            ),
+            ChangeKind::ArgumentRemoved {
+                type_name,
+                field_name,
+                argument_name,
+            } => write!(
+                f,
+                "Argument `{}` was removed from `{}.{}`.",
+                argument_name, type_name, field_name
+            ),
+            ChangeKind::ArgumentTypeChanged {
+                type_name,
+                field_name,
+                argument_name,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "Argument `{}` of `{}.{}` changed type from `{}` to `{}`.",
+                argument_name, type_name, field_name, old_type, new_type
+            ),
+            ChangeKind::ArgumentDefaultChanged {
+                type_name,
+                field_name,
+                argument_name,
+            } => write!(
+                f,
+                "The default value of argument `{}` of `{}.{}` changed.",
+                argument_name, type_name, field_name
+            ),
+            ChangeKind::InputFieldAdded {
+                type_name,
+                field_name,
+                required,
+            } => write!(
+                f,
+                "{} input field `{}.{}` was added.",
+                if *required { "Required" } else { "Optional" },
+                type_name,
+                field_name
+            ),
+            ChangeKind::InputFieldRemoved {
+                type_name,
+                field_name,
+            } => write!(f, "Input field `{}.{}` was removed.", type_name, field_name),
+            ChangeKind::InputFieldTypeChanged {
+                type_name,
+                field_name,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "Input field `{}.{}` changed type from `{}` to `{}`.",
+                type_name, field_name, old_type, new_type
+            ),
+            ChangeKind::EnumValueAdded { type_name, value } => {
+                write!(f, "Value `{}` was added to enum `{}`.", value, type_name)
+            }
+            ChangeKind::EnumValueRemoved { type_name, value } => {
+                write!(
+                    f,
+                    "Value `{}` was removed from enum `{}`.",
+                    value, type_name
+                )
+            }
+            ChangeKind::UnionMemberAdded { type_name, member } => {
+                write!(f, "`{}` was added to union `{}`.", member, type_name)
+            }
+            ChangeKind::UnionMemberRemoved { type_name, member } => {
+                write!(f, "`{}` was removed from union `{}`.", member, type_name)
+            }
+            ChangeKind::InterfaceAdded {
+                type_name,
+                interface,
+            } => write!(f, "`{}` now implements `{}`.", type_name, interface),
+            ChangeKind::InterfaceRemoved {
+                type_name,
+                interface,
+            } => write!(f, "`{}` no longer implements `{}`.", type_name, interface),
+            ChangeKind::DirectiveAdded(name) => write!(f, "Directive `@{}` was added.", name),
+            ChangeKind::DirectiveRemoved(name) => {
+                write!(f, "Directive `@{}` was removed.", name)
+            }
+        }
+    }
+}
+
+/// Output types may only become non-null: a client that handles `null`
+/// also handles values that are never `null`.
+fn is_safe_output_type_change(old_type: &Type, new_type: &Type) -> bool {
+    match (old_type, new_type) {
+        (Type::Named(old), Type::Named(new)) => old == new,
+        (Type::List(old), Type::List(new)) | (Type::NonNull(old), Type::NonNull(new)) => {
+            is_safe_output_type_change(old, new)
+        }
+        (old, Type::NonNull(new)) => is_safe_output_type_change(old, new),
+        _ => false,
+    }
+}
+
+/// Input types may only become nullable: every value that clients send for
+/// the non-null type is also valid for the nullable type.
+fn is_safe_input_type_change(old_type: &Type, new_type: &Type) -> bool {
+    match (old_type, new_type) {
+        (Type::Named(old), Type::Named(new)) => old == new,
+        (Type::List(old), Type::List(new)) | (Type::NonNull(old), Type::NonNull(new)) => {
+            is_safe_input_type_change(old, new)
+        }
+        (Type::NonNull(old), new) => is_safe_input_type_change(old, new),
+        _ => false,
+    }
+}
+
+/// Returns the changes from `old` to `new`, sorted by type and member name.
+pub fn find_schema_changes(old: &SDLSchema, new: &SDLSchema) -> Vec<ChangeKind> {
+    let diff = schema::diff(old, new);
+    let mut changes = vec![];
+
+    let mut type_names: Vec<StringKey> = diff
+        .added_types
+        .iter()
+        .chain(&diff.removed_types)
+        .chain(diff.changed_types.iter().map(|type_| &type_.name))
+        .copied()
+        .collect();
+    type_names.sort_by_key(|name| name.lookup());
+    for type_name in type_names {
+        if diff.added_types.contains(&type_name) {
+            changes.push(ChangeKind::TypeAdded(type_name));
+        } else if diff.removed_types.contains(&type_name) {
+            changes.push(ChangeKind::TypeRemoved(type_name));
+        } else if let Some(type_diff) = diff
+            .changed_types
+            .iter()
+            .find(|type_| type_.name == type_name)
+        {
+            push_type_changes(&mut changes, type_name, &type_diff.kind);
+        }
+    }
+
+    changes.extend(
+        diff.removed_directives
+            .into_iter()
+            .map(|name| ChangeKind::DirectiveRemoved(name.0)),
+    );
+    changes.extend(
+        diff.added_directives
+            .into_iter()
+            .map(|name| ChangeKind::DirectiveAdded(name.0)),
+    );
+    changes
+}
+
+fn push_type_changes(changes: &mut Vec<ChangeKind>, type_name: StringKey, kind: &TypeDiffKind) {
+    match kind {
+        TypeDiffKind::KindChanged { old_kind, new_kind } => {
+            changes.push(ChangeKind::TypeKindChanged {
+                type_name,
+                old_kind,
+                new_kind,
+            })
+        }
+        TypeDiffKind::Object { interfaces, fields }
+        | TypeDiffKind::Interface { interfaces, fields } => {
+            push_name_changes(
+                changes,
+                interfaces,
+                |interface| ChangeKind::InterfaceAdded {
+                    type_name,
+                    interface,
+                },
+                |interface| ChangeKind::InterfaceRemoved {
+                    type_name,
+                    interface,
+                },
+            );
+            push_field_changes(changes, type_name, fields);
+        }
+        TypeDiffKind::Union { members } => push_name_changes(
+            changes,
+            members,
+            |member| ChangeKind::UnionMemberAdded { type_name, member },
+            |member| ChangeKind::UnionMemberRemoved { type_name, member },
+        ),
+        TypeDiffKind::Enum { values } => push_name_changes(
+            changes,
+            values,
+            |value| ChangeKind::EnumValueAdded { type_name, value },
+            |value| ChangeKind::EnumValueRemoved { type_name, value },
+        ),
+        TypeDiffKind::InputObject { fields } => {
+            for (field_name, field) in merge_by_name(&fields.added, &fields.changed) {
+                match field {
+                    Member::Added(field) => changes.push(ChangeKind::InputFieldAdded {
+                        type_name,
+                        field_name,
+                        required: field.is_required(),
+                    }),
+                    Member::Changed(field) => {
+                        if let Some((old_type, new_type)) = &field.type_changed {
+                            changes.push(ChangeKind::InputFieldTypeChanged {
+                                type_name,
+                                field_name,
+                                old_type: Type::from(old_type.clone()),
+                                new_type: Type::from(new_type.clone()),
+                            });
+                        }
+                    }
+                }
+            }
+            changes.extend(
+                fields
+                    .removed
+                    .iter()
+                    .map(|field_name| ChangeKind::InputFieldRemoved {
+                        type_name,
+                        field_name: *field_name,
+                    }),
+            );
+        }
+    }
+}
+
+/// Pushes the names that were added, and then the names that were removed.
+fn push_name_changes(
+    changes: &mut Vec<ChangeKind>,
+    names: &NamesDiff,
+    added: impl Fn(StringKey) -> ChangeKind,
+    removed: impl Fn(StringKey) -> ChangeKind,
+) {
+    changes.extend(names.added.iter().map(|name| added(*name)));
+    changes.extend(names.removed.iter().map(|name| removed(*name)));
+}
+
+fn push_field_changes(changes: &mut Vec<ChangeKind>, type_name: StringKey, fields: &FieldsDiff) {
+    for (field_name, field) in merge_by_name(&fields.added, &fields.changed) {
+        let field = match field {
+            Member::Added(_) => {
+                changes.push(ChangeKind::FieldAdded {
+                    type_name,
+                    field_name,
+                });
+                continue;
+            }
+            Member::Changed(field) => field,
+        };
+        if let Some((old_type, new_type)) = &field.type_changed {
+            changes.push(ChangeKind::FieldTypeChanged {
+                type_name,
+                field_name,
+                old_type: Type::from(old_type.clone()),
+                new_type: Type::from(new_type.clone()),
+            });
+        }
+        if field.deprecated {
+            changes.push(ChangeKind::FieldDeprecated {
+                type_name,
+                field_name,
+            });
+        }
+        push_argument_changes(changes, type_name, field_name, &field.arguments);
+    }
+    changes.extend(
+        fields
+            .removed
+            .iter()
+            .map(|field_name| ChangeKind::FieldRemoved {
+                type_name,
+                field_name: *field_name,
+            }),
+    );
+}
+
+fn push_argument_changes(
+    changes: &mut Vec<ChangeKind>,
+    type_name: StringKey,
+    field_name: StringKey,
+    arguments: &InputValuesDiff,
+) {
+    for (argument_name, argument) in merge_by_name(&arguments.added, &arguments.changed) {
+        let argument = match argument {
+            Member::Added(argument) => {
+                changes.push(ChangeKind::ArgumentAdded {
+                    type_name,
+                    field_name,
+                    argument_name,
+                    required: argument.is_required(),
+                });
+                continue;
+            }
+            Member::Changed(argument) => argument,
+        };
+        if let Some((old_type, new_type)) = &argument.type_changed {
+            changes.push(ChangeKind::ArgumentTypeChanged {
+                type_name,
+                field_name,
+                argument_name,
+                old_type: Type::from(old_type.clone()),
+                new_type: Type::from(new_type.clone()),
+            });
+        }
+        if argument.default_value_changed {
+            changes.push(ChangeKind::ArgumentDefaultChanged {
+                type_name,
+                field_name,
+                argument_name,
+            });
+        }
+    }
+    changes.extend(
+        arguments
+            .removed
+            .iter()
+            .map(|argument_name| ChangeKind::ArgumentRemoved {
+                type_name,
+                field_name,
+                argument_name: *argument_name,
+            }),
+    );
+}
+
+trait MemberName {
+    fn member_name(&self) -> StringKey;
+}
+
+impl MemberName for StringKey {
+    fn member_name(&self) -> StringKey {
+        *self
+    }
+}
+
+impl MemberName for FieldDiff {
+    fn member_name(&self) -> StringKey {
+        self.name
+    }
+}
+
+impl MemberName for AddedInputValue {
+    fn member_name(&self) -> StringKey {
+        self.name
+    }
+}
+
+impl MemberName for InputValueDiff {
+    fn member_name(&self) -> StringKey {
+        self.name
+    }
+}
+
+enum Member<'a, A, C> {
+    Added(&'a A),
+    Changed(&'a C),
+}
+
+/// The added and changed members of a type, together sorted by name, so
+/// that the changes to each member are reported in the order of the members.
+fn merge_by_name<'a, A: MemberName, C: MemberName>(
+    added: &'a [A],
+    changed: &'a [C],
+) -> Vec<(StringKey, Member<'a, A, C>)> {
+    let mut members: Vec<(StringKey, Member<'a, A, C>)> = added
+        .iter()
+        .map(|member| (member.member_name(), Member::Added(member)))
+        .chain(
+            changed
+                .iter()
+                .map(|member| (member.member_name(), Member::Changed(member))),
+        )
+        .collect();
+    members.sort_by_key(|(name, _)| name.lookup());
+    members
+}
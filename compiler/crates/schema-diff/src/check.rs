@@ -13,6 +13,7 @@ use relay_config::SchemaConfig;
 use schema::SDLSchema;
 use schema::Schema;
 
+use crate::definitions::AffectedSchemaMembers;
 use crate::definitions::ArgumentChange;
 use crate::definitions::DefinitionChange;
 use crate::definitions::SchemaChange;
@@ -92,6 +93,98 @@ impl SchemaChange {
             }
         }
     }
+
+    /// The types and fields of the schema that the change affects, so that
+    /// only the documents that use them are rebuilt. Returns `None` if they
+    /// can't be determined and every document has to be rebuilt, e.g. for
+    /// changes of directive definitions, or of the query type and the `Node`
+    /// interface that generated `@refetchable` queries select.
+    pub fn get_affected_members(
+        self: &SchemaChange,
+        schema: &SDLSchema,
+        schema_config: &SchemaConfig,
+    ) -> Option<AffectedSchemaMembers> {
+        let changes = match self {
+            SchemaChange::None => return Some(Default::default()),
+            SchemaChange::GenericChange | SchemaChange::InvalidSchema => return None,
+            SchemaChange::DefinitionChanges(changes) => changes,
+        };
+        let id_name = schema_config.node_interface_id_field;
+        let query_type_name = schema
+            .query_type()
+            .map(|query_type| schema.get_type_name(query_type));
+
+        let mut members = AffectedSchemaMembers::default();
+        for change in changes {
+            match change {
+                DefinitionChange::ObjectChanged {
+                    name,
+                    added,
+                    removed,
+                    changed,
+                    interfaces_added,
+                    interfaces_removed,
+                } => {
+                    if Some(*name) == query_type_name && !(removed.is_empty() && changed.is_empty())
+                    {
+                        return None;
+                    }
+                    // Selections on the interfaces may include the object
+                    if !interfaces_added.is_empty() || !interfaces_removed.is_empty() {
+                        members.types.insert(*name);
+                        members
+                            .types
+                            .extend(interfaces_added.iter().chain(interfaces_removed));
+                    }
+                    add_affected_fields(&mut members, *name, added, removed, changed, id_name);
+                }
+                DefinitionChange::InterfaceChanged {
+                    name,
+                    added,
+                    removed,
+                    changed,
+                } => {
+                    if *name == NODE_INTERFACE_KEY.0 && !(removed.is_empty() && changed.is_empty())
+                    {
+                        return None;
+                    }
+                    add_affected_fields(&mut members, *name, added, removed, changed, id_name);
+                }
+                DefinitionChange::ObjectAdded(name) => {
+                    // Selections on the interfaces of the object may have to
+                    // select its `id`
+                    if !is_object_add_safe(*name, schema, schema_config) {
+                        if let Some(schema::Type::Object(id)) = schema.get_type(*name) {
+                            members.types.extend(
+                                schema
+                                    .object(id)
+                                    .interfaces
+                                    .iter()
+                                    .map(|id| schema.interface(*id).name.item.0),
+                            );
+                        }
+                    }
+                }
+                DefinitionChange::InterfaceAdded(_)
+                | DefinitionChange::EnumAdded(_)
+                | DefinitionChange::ScalarAdded(_)
+                | DefinitionChange::UnionAdded(_)
+                | DefinitionChange::InputObjectAdded(_) => {}
+                DefinitionChange::EnumChanged { name }
+                | DefinitionChange::UnionChanged { name, .. }
+                | DefinitionChange::InputObjectChanged { name, .. }
+                | DefinitionChange::EnumRemoved(name)
+                | DefinitionChange::UnionRemoved(name)
+                | DefinitionChange::ScalarRemoved(name)
+                | DefinitionChange::InputObjectRemoved(name)
+                | DefinitionChange::InterfaceRemoved(name)
+                | DefinitionChange::ObjectRemoved(name) => {
+                    members.types.insert(*name);
+                }
+            }
+        }
+        Some(members)
+    }
 }
 
 lazy_static! {
@@ -166,3 +259,29 @@ fn is_field_changes_safe(
     }
     true
 }
+
+/// Removed fields, fields with a new type and fields with changed arguments
+/// affect the documents that select them. The special `id` and `js` fields
+/// affect all documents that use the type, see `is_field_changes_safe`.
+fn add_affected_fields(
+    members: &mut AffectedSchemaMembers,
+    type_name: StringKey,
+    added: &[TypeChange],
+    removed: &[TypeChange],
+    changed: &[ArgumentChange],
+    id_name: StringKey,
+) {
+    if added
+        .iter()
+        .chain(removed)
+        .any(|change| change.name == id_name || change.name == *JS_FIELD_KEY)
+    {
+        members.types.insert(type_name);
+    }
+    members.fields.extend(
+        removed
+            .iter()
+            .map(|field| (type_name, field.name))
+            .chain(changed.iter().map(|field| (type_name, field.name))),
+    );
+}
@@ -7,8 +7,10 @@
 
 use std::fmt;
 
+use fnv::FnvHashSet;
 pub use graphql_syntax::TypeAnnotation;
 use intern::string_key::StringKey;
+use schema::TypeReference;
 
 #[derive(Eq, PartialEq, PartialOrd, Ord)]
 pub enum DefinitionChange {
@@ -151,6 +153,26 @@ impl From<TypeAnnotation> for Type {
     }
 }
 
+impl From<TypeReference<StringKey>> for Type {
+    fn from(type_: TypeReference<StringKey>) -> Self {
+        match type_ {
+            TypeReference::Named(name) => Type::Named(name),
+            TypeReference::List(of) => Type::List(Box::new(Type::from(*of))),
+            TypeReference::NonNull(of) => Type::NonNull(Box::new(Type::from(*of))),
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Named(name) => write!(f, "{}", name),
+            Type::List(of) => write!(f, "[{}]", of),
+            Type::NonNull(of) => write!(f, "{}!", of),
+        }
+    }
+}
+
 #[derive(Eq, PartialEq, PartialOrd, Ord)]
 pub struct TypeChange {
     pub name: StringKey,
@@ -181,3 +203,25 @@ impl fmt::Debug for SchemaChange {
         }
     }
 }
+
+/// The types and fields of the schema that a change affects: the documents
+/// that use them may compile to different artifacts.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AffectedSchemaMembers {
+    /// Types that affect every document that references them.
+    pub types: FnvHashSet<StringKey>,
+    /// Fields, as their type and name, that affect the documents that select
+    /// them.
+    pub fields: FnvHashSet<(StringKey, StringKey)>,
+}
+
+impl AffectedSchemaMembers {
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.fields.is_empty()
+    }
+
+    pub fn extend(&mut self, other: AffectedSchemaMembers) {
+        self.types.extend(other.types);
+        self.fields.extend(other.fields);
+    }
+}
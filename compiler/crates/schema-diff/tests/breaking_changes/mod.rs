@@ -0,0 +1,30 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use fixture_tests::Fixture;
+use schema::build_schema;
+use schema_diff::breaking::find_schema_changes;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let parts: Vec<_> = fixture.content.split("%new_schema%").collect();
+    let (old, new) = match parts.as_slice() {
+        [old, new] => (build_schema(old).unwrap(), build_schema(new).unwrap()),
+        _ => panic!("Expected the old and the new schema to be separated by %new_schema%."),
+    };
+    let changes = find_schema_changes(&old, &new);
+    Ok(changes
+        .iter()
+        .map(|change| {
+            format!(
+                "[{}] {}: {}\n",
+                change.severity().name(),
+                change.coordinate(),
+                change
+            )
+        })
+        .collect())
+}
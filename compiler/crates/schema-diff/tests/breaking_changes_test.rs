@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<1d0aa625d549b7c25b280c0780895309>>
+ */
+
+mod breaking_changes;
+
+use breaking_changes::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn input_types() {
+    let input = include_str!("breaking_changes/fixtures/input-types.graphql");
+    let expected = include_str!("breaking_changes/fixtures/input-types.expected");
+    test_fixture(transform_fixture, "input-types.graphql", "breaking_changes/fixtures/input-types.expected", input, expected);
+}
+
+#[test]
+fn output_types() {
+    let input = include_str!("breaking_changes/fixtures/output-types.graphql");
+    let expected = include_str!("breaking_changes/fixtures/output-types.expected");
+    test_fixture(transform_fixture, "output-types.graphql", "breaking_changes/fixtures/output-types.expected", input, expected);
+}
@@ -22,6 +22,28 @@ fn is_safe(current: &str, previous: &str) -> bool {
     change.is_safe(&schema, &Default::default())
 }
 
+fn affected_members(current: &str, previous: &str) -> Option<(Vec<String>, Vec<String>)> {
+    let schema = build_schema(current).unwrap();
+    let change = detect_changes(&[current], &[previous]);
+    change
+        .get_affected_members(&schema, &Default::default())
+        .map(|members| {
+            let mut types = members
+                .types
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>();
+            types.sort();
+            let mut fields = members
+                .fields
+                .iter()
+                .map(|(type_name, field_name)| format!("{}.{}", type_name, field_name))
+                .collect::<Vec<_>>();
+            fields.sort();
+            (types, fields)
+        })
+}
+
 #[test]
 fn test_same_text() {
     assert_eq!(
@@ -962,6 +984,145 @@ fn test_unimplemented_changes() {
     ))
 }
 
+#[test]
+fn test_affected_members_of_safe_changes() {
+    assert_eq!(
+        affected_members(
+            r"
+            type Query {
+                a: A
+            }
+            type A {
+                key: String
+                name: String
+            }
+            enum B {
+                OK
+            }
+            #",
+            r"
+            type Query {
+                a: A
+            }
+            type A {
+                key: String
+            }
+            #"
+        ),
+        Some((vec![], vec![]))
+    );
+}
+
+#[test]
+fn test_affected_members_of_changed_fields() {
+    assert_eq!(
+        affected_members(
+            r"
+            type Query {
+                a: A
+            }
+            type A {
+                key: Int
+                name(first: Int!): String
+            }
+            enum B {
+                OK
+            }
+            #",
+            r"
+            type Query {
+                a: A
+            }
+            type A {
+                key: String
+                name: String
+                removed: String
+            }
+            enum B {
+                OK
+                NOT_OK
+            }
+            #"
+        ),
+        Some((
+            vec!["B".to_string()],
+            vec![
+                "A.key".to_string(),
+                "A.name".to_string(),
+                "A.removed".to_string()
+            ]
+        ))
+    );
+}
+
+#[test]
+fn test_affected_members_of_id_and_interfaces() {
+    assert_eq!(
+        affected_members(
+            r"
+            type Query {
+                a: A
+            }
+            interface Actor {
+                name: String
+            }
+            type A implements Actor {
+                id: ID
+                name: String
+            }
+            #",
+            r"
+            type Query {
+                a: A
+            }
+            interface Actor {
+                name: String
+            }
+            type A {
+                name: String
+            }
+            #"
+        ),
+        Some((vec!["A".to_string(), "Actor".to_string()], vec![]))
+    );
+}
+
+#[test]
+fn test_affected_members_of_query_and_directive_changes() {
+    assert_eq!(
+        affected_members(
+            r"
+            type Query {
+                a: String
+            }
+            #",
+            r"
+            type Query {
+                a: String
+                b: String
+            }
+            #"
+        ),
+        None
+    );
+    assert_eq!(
+        affected_members(
+            r"
+            directive @a on FIELD
+            type Query {
+                a: String
+            }
+            #",
+            r"
+            type Query {
+                a: String
+            }
+            #"
+        ),
+        None
+    );
+}
+
 fn sort_change(change: &mut SchemaChange) {
     if let SchemaChange::DefinitionChanges(changes) = change {
         changes.sort();
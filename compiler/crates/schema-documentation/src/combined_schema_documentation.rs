@@ -43,6 +43,19 @@ impl<TPrimary: SchemaDocumentation, TSecondary: SchemaDocumentation> SchemaDocum
                     .get_field_argument_description(type_name, field_name, argument_name)
             })
     }
+    fn get_input_field_description(&self, type_name: &str, field_name: &str) -> Option<&str> {
+        self.primary
+            .get_input_field_description(type_name, field_name)
+            .or_else(|| {
+                self.secondary
+                    .get_input_field_description(type_name, field_name)
+            })
+    }
+    fn get_enum_value_description(&self, type_name: &str, value: &str) -> Option<&str> {
+        self.primary
+            .get_enum_value_description(type_name, value)
+            .or_else(|| self.secondary.get_enum_value_description(type_name, value))
+    }
     fn get_hack_source(&self, type_name: &str) -> Option<&str> {
         self.primary
             .get_hack_source(type_name)
@@ -31,6 +31,12 @@ pub trait SchemaDocumentation: Send + Sync {
     ) -> Option<&str> {
         None
     }
+    fn get_input_field_description(&self, _type_name: &str, _field_name: &str) -> Option<&str> {
+        None
+    }
+    fn get_enum_value_description(&self, _type_name: &str, _value: &str) -> Option<&str> {
+        None
+    }
     fn get_hack_source(&self, _type_name: &str) -> Option<&str> {
         None
     }
@@ -58,6 +64,13 @@ impl<TSchemaDocumentation: SchemaDocumentation> SchemaDocumentation for Arc<TSch
         self.as_ref()
             .get_field_argument_description(type_name, field_name, argument_name)
     }
+    fn get_input_field_description(&self, type_name: &str, field_name: &str) -> Option<&str> {
+        self.as_ref()
+            .get_input_field_description(type_name, field_name)
+    }
+    fn get_enum_value_description(&self, type_name: &str, value: &str) -> Option<&str> {
+        self.as_ref().get_enum_value_description(type_name, value)
+    }
     fn get_hack_source(&self, type_name: &str) -> Option<&str> {
         self.as_ref().get_hack_source(type_name)
     }
@@ -88,6 +101,14 @@ impl<TSchemaDocumentation: SchemaDocumentation> SchemaDocumentation
         self.as_ref()
             .and_then(|s| s.get_field_argument_description(type_name, field_name, argument_name))
     }
+    fn get_input_field_description(&self, type_name: &str, field_name: &str) -> Option<&str> {
+        self.as_ref()
+            .and_then(|s| s.get_input_field_description(type_name, field_name))
+    }
+    fn get_enum_value_description(&self, type_name: &str, value: &str) -> Option<&str> {
+        self.as_ref()
+            .and_then(|s| s.get_enum_value_description(type_name, value))
+    }
     fn get_hack_source(&self, type_name: &str) -> Option<&str> {
         self.as_ref().and_then(|s| s.get_hack_source(type_name))
     }
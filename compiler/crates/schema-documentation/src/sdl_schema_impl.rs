@@ -50,6 +50,34 @@ impl SchemaDocumentation for SDLSchema {
             .map(|string_key| string_key.lookup())
     }
 
+    fn get_input_field_description(&self, type_name: &str, field_name: &str) -> Option<&str> {
+        let input_object = match self.get_type(type_name.intern())? {
+            Type::InputObject(id) => self.input_object(id),
+            _ => return None,
+        };
+        let field_name_string_key = field_name.intern();
+        input_object
+            .fields
+            .iter()
+            .find(|field| field.name.0 == field_name_string_key)
+            .and_then(|field| field.description)
+            .map(|string_key| string_key.lookup())
+    }
+
+    fn get_enum_value_description(&self, type_name: &str, value: &str) -> Option<&str> {
+        let enum_ = match self.get_type(type_name.intern())? {
+            Type::Enum(id) => self.enum_(id),
+            _ => return None,
+        };
+        let value_string_key = value.intern();
+        enum_
+            .values
+            .iter()
+            .find(|enum_value| enum_value.value == value_string_key)
+            .and_then(|enum_value| enum_value.description)
+            .map(|string_key| string_key.lookup())
+    }
+
     fn get_hack_source(&self, type_name: &str) -> Option<&str> {
         self.get_type(type_name.intern())
             .and_then(|type_| get_hack_source_from_type(type_, self))
@@ -79,7 +107,7 @@ fn get_description_from_type(type_: Type, schema: &SDLSchema) -> Option<StringKe
 fn get_field_from_type(type_: Type, schema: &SDLSchema, field_name: StringKey) -> Option<&Field> {
     let fields = match type_ {
         Type::Enum(_id) => None,
-        // Note: InputObjects have Arguments (akin to fields). These Arguments cannot have descriptions.
+        // Note: The fields of InputObjects are Arguments, see `get_input_field_description`.
         Type::InputObject(_id) => None,
         Type::Interface(id) => Some(&schema.interface(id).fields),
         Type::Object(id) => Some(&schema.object(id).fields),
@@ -1516,6 +1516,8 @@ impl<'a> Argument<'a> {
   pub const VT_NAME: flatbuffers::VOffsetT = 4;
   pub const VT_TYPE_: flatbuffers::VOffsetT = 6;
   pub const VT_VALUE: flatbuffers::VOffsetT = 8;
+  pub const VT_DIRECTIVES: flatbuffers::VOffsetT = 10;
+  pub const VT_DESCRIPTION: flatbuffers::VOffsetT = 12;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1527,6 +1529,8 @@ impl<'a> Argument<'a> {
     args: &'args ArgumentArgs<'args>
   ) -> flatbuffers::WIPOffset<Argument<'bldr>> {
     let mut builder = ArgumentBuilder::new(_fbb);
+    if let Some(x) = args.description { builder.add_description(x); }
+    if let Some(x) = args.directives { builder.add_directives(x); }
     if let Some(x) = args.value { builder.add_value(x); }
     if let Some(x) = args.type_ { builder.add_type_(x); }
     if let Some(x) = args.name { builder.add_name(x); }
@@ -1545,6 +1549,14 @@ impl<'a> Argument<'a> {
   pub fn value(&self) -> Option<ConstValue<'a>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<ConstValue>>(Argument::VT_VALUE, None)
   }
+  #[inline]
+  pub fn directives(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue>>>>(Argument::VT_DIRECTIVES, None)
+  }
+  #[inline]
+  pub fn description(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Argument::VT_DESCRIPTION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for Argument<'_> {
@@ -1557,6 +1569,8 @@ impl flatbuffers::Verifiable for Argument<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<TypeReference>>("type_", Self::VT_TYPE_, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<ConstValue>>("value", Self::VT_VALUE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<DirectiveValue>>>>("directives", Self::VT_DIRECTIVES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("description", Self::VT_DESCRIPTION, false)?
      .finish();
     Ok(())
   }
@@ -1565,6 +1579,8 @@ pub struct ArgumentArgs<'a> {
     pub name: Option<flatbuffers::WIPOffset<&'a str>>,
     pub type_: Option<flatbuffers::WIPOffset<TypeReference<'a>>>,
     pub value: Option<flatbuffers::WIPOffset<ConstValue<'a>>>,
+    pub directives: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>>>,
+    pub description: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for ArgumentArgs<'a> {
   #[inline]
@@ -1573,6 +1589,8 @@ impl<'a> Default for ArgumentArgs<'a> {
       name: None,
       type_: None,
       value: None,
+      directives: None,
+      description: None,
     }
   }
 }
@@ -1595,6 +1613,14 @@ impl<'a: 'b, 'b> ArgumentBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<ConstValue>>(Argument::VT_VALUE, value);
   }
   #[inline]
+  pub fn add_directives(&mut self, directives: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<DirectiveValue<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Argument::VT_DIRECTIVES, directives);
+  }
+  #[inline]
+  pub fn add_description(&mut self, description: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Argument::VT_DESCRIPTION, description);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ArgumentBuilder<'a, 'b> {
     let start = _fbb.start_table();
     ArgumentBuilder {
@@ -1615,6 +1641,8 @@ impl core::fmt::Debug for Argument<'_> {
       ds.field("name", &self.name());
       ds.field("type_", &self.type_());
       ds.field("value", &self.value());
+      ds.field("directives", &self.directives());
+      ds.field("description", &self.description());
       ds.finish()
   }
 }
@@ -1639,6 +1667,7 @@ impl<'a> Directive<'a> {
   pub const VT_ARGUMENTS: flatbuffers::VOffsetT = 8;
   pub const VT_LOCATIONS: flatbuffers::VOffsetT = 10;
   pub const VT_REPEATABLE: flatbuffers::VOffsetT = 12;
+  pub const VT_DESCRIPTION: flatbuffers::VOffsetT = 14;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1650,6 +1679,7 @@ impl<'a> Directive<'a> {
     args: &'args DirectiveArgs<'args>
   ) -> flatbuffers::WIPOffset<Directive<'bldr>> {
     let mut builder = DirectiveBuilder::new(_fbb);
+    if let Some(x) = args.description { builder.add_description(x); }
     if let Some(x) = args.locations { builder.add_locations(x); }
     if let Some(x) = args.arguments { builder.add_arguments(x); }
     if let Some(x) = args.name { builder.add_name(x); }
@@ -1678,6 +1708,10 @@ impl<'a> Directive<'a> {
   pub fn repeatable(&self) -> bool {
     self._tab.get::<bool>(Directive::VT_REPEATABLE, Some(false)).unwrap()
   }
+  #[inline]
+  pub fn description(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Directive::VT_DESCRIPTION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for Directive<'_> {
@@ -1692,6 +1726,7 @@ impl flatbuffers::Verifiable for Directive<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Argument>>>>("arguments", Self::VT_ARGUMENTS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, DirectiveLocation>>>("locations", Self::VT_LOCATIONS, false)?
      .visit_field::<bool>("repeatable", Self::VT_REPEATABLE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("description", Self::VT_DESCRIPTION, false)?
      .finish();
     Ok(())
   }
@@ -1702,6 +1737,7 @@ pub struct DirectiveArgs<'a> {
     pub arguments: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Argument<'a>>>>>,
     pub locations: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, DirectiveLocation>>>,
     pub repeatable: bool,
+    pub description: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for DirectiveArgs<'a> {
   #[inline]
@@ -1712,6 +1748,7 @@ impl<'a> Default for DirectiveArgs<'a> {
       arguments: None,
       locations: None,
       repeatable: false,
+      description: None,
     }
   }
 }
@@ -1742,6 +1779,10 @@ impl<'a: 'b, 'b> DirectiveBuilder<'a, 'b> {
     self.fbb_.push_slot::<bool>(Directive::VT_REPEATABLE, repeatable, false);
   }
   #[inline]
+  pub fn add_description(&mut self, description: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Directive::VT_DESCRIPTION, description);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> DirectiveBuilder<'a, 'b> {
     let start = _fbb.start_table();
     DirectiveBuilder {
@@ -1764,6 +1805,7 @@ impl core::fmt::Debug for Directive<'_> {
       ds.field("arguments", &self.arguments());
       ds.field("locations", &self.locations());
       ds.field("repeatable", &self.repeatable());
+      ds.field("description", &self.description());
       ds.finish()
   }
 }
@@ -1785,6 +1827,8 @@ impl<'a> flatbuffers::Follow<'a> for EnumValue<'a> {
 impl<'a> EnumValue<'a> {
   pub const VT_VALUE: flatbuffers::VOffsetT = 4;
   pub const VT_DIRECTIVES: flatbuffers::VOffsetT = 6;
+  pub const VT_IS_EXTENSION: flatbuffers::VOffsetT = 8;
+  pub const VT_DESCRIPTION: flatbuffers::VOffsetT = 10;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1796,8 +1840,10 @@ impl<'a> EnumValue<'a> {
     args: &'args EnumValueArgs<'args>
   ) -> flatbuffers::WIPOffset<EnumValue<'bldr>> {
     let mut builder = EnumValueBuilder::new(_fbb);
+    if let Some(x) = args.description { builder.add_description(x); }
     if let Some(x) = args.directives { builder.add_directives(x); }
     if let Some(x) = args.value { builder.add_value(x); }
+    builder.add_is_extension(args.is_extension);
     builder.finish()
   }
 
@@ -1809,6 +1855,14 @@ impl<'a> EnumValue<'a> {
   pub fn directives(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue>>>>(EnumValue::VT_DIRECTIVES, None)
   }
+  #[inline]
+  pub fn is_extension(&self) -> bool {
+    self._tab.get::<bool>(EnumValue::VT_IS_EXTENSION, Some(false)).unwrap()
+  }
+  #[inline]
+  pub fn description(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(EnumValue::VT_DESCRIPTION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for EnumValue<'_> {
@@ -1820,6 +1874,8 @@ impl flatbuffers::Verifiable for EnumValue<'_> {
     v.visit_table(pos)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("value", Self::VT_VALUE, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<DirectiveValue>>>>("directives", Self::VT_DIRECTIVES, false)?
+     .visit_field::<bool>("is_extension", Self::VT_IS_EXTENSION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("description", Self::VT_DESCRIPTION, false)?
      .finish();
     Ok(())
   }
@@ -1827,6 +1883,8 @@ impl flatbuffers::Verifiable for EnumValue<'_> {
 pub struct EnumValueArgs<'a> {
     pub value: Option<flatbuffers::WIPOffset<&'a str>>,
     pub directives: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>>>,
+    pub is_extension: bool,
+    pub description: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for EnumValueArgs<'a> {
   #[inline]
@@ -1834,6 +1892,8 @@ impl<'a> Default for EnumValueArgs<'a> {
     EnumValueArgs {
       value: None,
       directives: None,
+      is_extension: false,
+      description: None,
     }
   }
 }
@@ -1852,6 +1912,14 @@ impl<'a: 'b, 'b> EnumValueBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(EnumValue::VT_DIRECTIVES, directives);
   }
   #[inline]
+  pub fn add_is_extension(&mut self, is_extension: bool) {
+    self.fbb_.push_slot::<bool>(EnumValue::VT_IS_EXTENSION, is_extension, false);
+  }
+  #[inline]
+  pub fn add_description(&mut self, description: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(EnumValue::VT_DESCRIPTION, description);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> EnumValueBuilder<'a, 'b> {
     let start = _fbb.start_table();
     EnumValueBuilder {
@@ -1871,6 +1939,8 @@ impl core::fmt::Debug for EnumValue<'_> {
     let mut ds = f.debug_struct("EnumValue");
       ds.field("value", &self.value());
       ds.field("directives", &self.directives());
+      ds.field("is_extension", &self.is_extension());
+      ds.field("description", &self.description());
       ds.finish()
   }
 }
@@ -1893,6 +1963,7 @@ impl<'a> Scalar<'a> {
   pub const VT_NAME: flatbuffers::VOffsetT = 4;
   pub const VT_IS_EXTENSION: flatbuffers::VOffsetT = 6;
   pub const VT_DIRECTIVES: flatbuffers::VOffsetT = 8;
+  pub const VT_DESCRIPTION: flatbuffers::VOffsetT = 10;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1904,6 +1975,7 @@ impl<'a> Scalar<'a> {
     args: &'args ScalarArgs<'args>
   ) -> flatbuffers::WIPOffset<Scalar<'bldr>> {
     let mut builder = ScalarBuilder::new(_fbb);
+    if let Some(x) = args.description { builder.add_description(x); }
     if let Some(x) = args.directives { builder.add_directives(x); }
     if let Some(x) = args.name { builder.add_name(x); }
     builder.add_is_extension(args.is_extension);
@@ -1922,6 +1994,10 @@ impl<'a> Scalar<'a> {
   pub fn directives(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue>>>>(Scalar::VT_DIRECTIVES, None)
   }
+  #[inline]
+  pub fn description(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Scalar::VT_DESCRIPTION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for Scalar<'_> {
@@ -1934,6 +2010,7 @@ impl flatbuffers::Verifiable for Scalar<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, false)?
      .visit_field::<bool>("is_extension", Self::VT_IS_EXTENSION, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<DirectiveValue>>>>("directives", Self::VT_DIRECTIVES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("description", Self::VT_DESCRIPTION, false)?
      .finish();
     Ok(())
   }
@@ -1942,6 +2019,7 @@ pub struct ScalarArgs<'a> {
     pub name: Option<flatbuffers::WIPOffset<&'a str>>,
     pub is_extension: bool,
     pub directives: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>>>,
+    pub description: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for ScalarArgs<'a> {
   #[inline]
@@ -1950,6 +2028,7 @@ impl<'a> Default for ScalarArgs<'a> {
       name: None,
       is_extension: false,
       directives: None,
+      description: None,
     }
   }
 }
@@ -1972,6 +2051,10 @@ impl<'a: 'b, 'b> ScalarBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Scalar::VT_DIRECTIVES, directives);
   }
   #[inline]
+  pub fn add_description(&mut self, description: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Scalar::VT_DESCRIPTION, description);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ScalarBuilder<'a, 'b> {
     let start = _fbb.start_table();
     ScalarBuilder {
@@ -1992,6 +2075,7 @@ impl core::fmt::Debug for Scalar<'_> {
       ds.field("name", &self.name());
       ds.field("is_extension", &self.is_extension());
       ds.field("directives", &self.directives());
+      ds.field("description", &self.description());
       ds.finish()
   }
 }
@@ -2014,6 +2098,7 @@ impl<'a> InputObject<'a> {
   pub const VT_NAME: flatbuffers::VOffsetT = 4;
   pub const VT_FIELDS: flatbuffers::VOffsetT = 6;
   pub const VT_DIRECTIVES: flatbuffers::VOffsetT = 8;
+  pub const VT_DESCRIPTION: flatbuffers::VOffsetT = 10;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -2025,6 +2110,7 @@ impl<'a> InputObject<'a> {
     args: &'args InputObjectArgs<'args>
   ) -> flatbuffers::WIPOffset<InputObject<'bldr>> {
     let mut builder = InputObjectBuilder::new(_fbb);
+    if let Some(x) = args.description { builder.add_description(x); }
     if let Some(x) = args.directives { builder.add_directives(x); }
     if let Some(x) = args.fields { builder.add_fields(x); }
     if let Some(x) = args.name { builder.add_name(x); }
@@ -2043,6 +2129,10 @@ impl<'a> InputObject<'a> {
   pub fn directives(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue>>>>(InputObject::VT_DIRECTIVES, None)
   }
+  #[inline]
+  pub fn description(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(InputObject::VT_DESCRIPTION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for InputObject<'_> {
@@ -2055,6 +2145,7 @@ impl flatbuffers::Verifiable for InputObject<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Argument>>>>("fields", Self::VT_FIELDS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<DirectiveValue>>>>("directives", Self::VT_DIRECTIVES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("description", Self::VT_DESCRIPTION, false)?
      .finish();
     Ok(())
   }
@@ -2063,6 +2154,7 @@ pub struct InputObjectArgs<'a> {
     pub name: Option<flatbuffers::WIPOffset<&'a str>>,
     pub fields: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Argument<'a>>>>>,
     pub directives: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>>>,
+    pub description: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for InputObjectArgs<'a> {
   #[inline]
@@ -2071,6 +2163,7 @@ impl<'a> Default for InputObjectArgs<'a> {
       name: None,
       fields: None,
       directives: None,
+      description: None,
     }
   }
 }
@@ -2093,6 +2186,10 @@ impl<'a: 'b, 'b> InputObjectBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(InputObject::VT_DIRECTIVES, directives);
   }
   #[inline]
+  pub fn add_description(&mut self, description: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(InputObject::VT_DESCRIPTION, description);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> InputObjectBuilder<'a, 'b> {
     let start = _fbb.start_table();
     InputObjectBuilder {
@@ -2113,6 +2210,7 @@ impl core::fmt::Debug for InputObject<'_> {
       ds.field("name", &self.name());
       ds.field("fields", &self.fields());
       ds.field("directives", &self.directives());
+      ds.field("description", &self.description());
       ds.finish()
   }
 }
@@ -2136,6 +2234,7 @@ impl<'a> Enum<'a> {
   pub const VT_IS_EXTENSION: flatbuffers::VOffsetT = 6;
   pub const VT_VALUES: flatbuffers::VOffsetT = 8;
   pub const VT_DIRECTIVES: flatbuffers::VOffsetT = 10;
+  pub const VT_DESCRIPTION: flatbuffers::VOffsetT = 12;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -2147,6 +2246,7 @@ impl<'a> Enum<'a> {
     args: &'args EnumArgs<'args>
   ) -> flatbuffers::WIPOffset<Enum<'bldr>> {
     let mut builder = EnumBuilder::new(_fbb);
+    if let Some(x) = args.description { builder.add_description(x); }
     if let Some(x) = args.directives { builder.add_directives(x); }
     if let Some(x) = args.values { builder.add_values(x); }
     if let Some(x) = args.name { builder.add_name(x); }
@@ -2170,6 +2270,10 @@ impl<'a> Enum<'a> {
   pub fn directives(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue>>>>(Enum::VT_DIRECTIVES, None)
   }
+  #[inline]
+  pub fn description(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Enum::VT_DESCRIPTION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for Enum<'_> {
@@ -2183,6 +2287,7 @@ impl flatbuffers::Verifiable for Enum<'_> {
      .visit_field::<bool>("is_extension", Self::VT_IS_EXTENSION, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<EnumValue>>>>("values", Self::VT_VALUES, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<DirectiveValue>>>>("directives", Self::VT_DIRECTIVES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("description", Self::VT_DESCRIPTION, false)?
      .finish();
     Ok(())
   }
@@ -2192,6 +2297,7 @@ pub struct EnumArgs<'a> {
     pub is_extension: bool,
     pub values: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<EnumValue<'a>>>>>,
     pub directives: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>>>,
+    pub description: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for EnumArgs<'a> {
   #[inline]
@@ -2201,6 +2307,7 @@ impl<'a> Default for EnumArgs<'a> {
       is_extension: false,
       values: None,
       directives: None,
+      description: None,
     }
   }
 }
@@ -2227,6 +2334,10 @@ impl<'a: 'b, 'b> EnumBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Enum::VT_DIRECTIVES, directives);
   }
   #[inline]
+  pub fn add_description(&mut self, description: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Enum::VT_DESCRIPTION, description);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> EnumBuilder<'a, 'b> {
     let start = _fbb.start_table();
     EnumBuilder {
@@ -2248,6 +2359,7 @@ impl core::fmt::Debug for Enum<'_> {
       ds.field("is_extension", &self.is_extension());
       ds.field("values", &self.values());
       ds.field("directives", &self.directives());
+      ds.field("description", &self.description());
       ds.finish()
   }
 }
@@ -2272,6 +2384,7 @@ impl<'a> Object<'a> {
   pub const VT_FIELDS: flatbuffers::VOffsetT = 8;
   pub const VT_INTERFACES: flatbuffers::VOffsetT = 10;
   pub const VT_DIRECTIVES: flatbuffers::VOffsetT = 12;
+  pub const VT_DESCRIPTION: flatbuffers::VOffsetT = 14;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -2283,6 +2396,7 @@ impl<'a> Object<'a> {
     args: &'args ObjectArgs<'args>
   ) -> flatbuffers::WIPOffset<Object<'bldr>> {
     let mut builder = ObjectBuilder::new(_fbb);
+    if let Some(x) = args.description { builder.add_description(x); }
     if let Some(x) = args.directives { builder.add_directives(x); }
     if let Some(x) = args.interfaces { builder.add_interfaces(x); }
     if let Some(x) = args.fields { builder.add_fields(x); }
@@ -2311,6 +2425,10 @@ impl<'a> Object<'a> {
   pub fn directives(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue>>>>(Object::VT_DIRECTIVES, None)
   }
+  #[inline]
+  pub fn description(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Object::VT_DESCRIPTION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for Object<'_> {
@@ -2325,6 +2443,7 @@ impl flatbuffers::Verifiable for Object<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u32>>>("fields", Self::VT_FIELDS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u32>>>("interfaces", Self::VT_INTERFACES, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<DirectiveValue>>>>("directives", Self::VT_DIRECTIVES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("description", Self::VT_DESCRIPTION, false)?
      .finish();
     Ok(())
   }
@@ -2335,6 +2454,7 @@ pub struct ObjectArgs<'a> {
     pub fields: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u32>>>,
     pub interfaces: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u32>>>,
     pub directives: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>>>,
+    pub description: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for ObjectArgs<'a> {
   #[inline]
@@ -2345,6 +2465,7 @@ impl<'a> Default for ObjectArgs<'a> {
       fields: None,
       interfaces: None,
       directives: None,
+      description: None,
     }
   }
 }
@@ -2375,6 +2496,10 @@ impl<'a: 'b, 'b> ObjectBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Object::VT_DIRECTIVES, directives);
   }
   #[inline]
+  pub fn add_description(&mut self, description: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Object::VT_DESCRIPTION, description);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ObjectBuilder<'a, 'b> {
     let start = _fbb.start_table();
     ObjectBuilder {
@@ -2397,6 +2522,7 @@ impl core::fmt::Debug for Object<'_> {
       ds.field("fields", &self.fields());
       ds.field("interfaces", &self.interfaces());
       ds.field("directives", &self.directives());
+      ds.field("description", &self.description());
       ds.finish()
   }
 }
@@ -2423,6 +2549,7 @@ impl<'a> Interface<'a> {
   pub const VT_IMPLEMENTING_INTERFACES: flatbuffers::VOffsetT = 12;
   pub const VT_IMPLEMENTING_OBJECTS: flatbuffers::VOffsetT = 14;
   pub const VT_DIRECTIVES: flatbuffers::VOffsetT = 16;
+  pub const VT_DESCRIPTION: flatbuffers::VOffsetT = 18;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -2434,6 +2561,7 @@ impl<'a> Interface<'a> {
     args: &'args InterfaceArgs<'args>
   ) -> flatbuffers::WIPOffset<Interface<'bldr>> {
     let mut builder = InterfaceBuilder::new(_fbb);
+    if let Some(x) = args.description { builder.add_description(x); }
     if let Some(x) = args.directives { builder.add_directives(x); }
     if let Some(x) = args.implementing_objects { builder.add_implementing_objects(x); }
     if let Some(x) = args.implementing_interfaces { builder.add_implementing_interfaces(x); }
@@ -2472,6 +2600,10 @@ impl<'a> Interface<'a> {
   pub fn directives(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue>>>>(Interface::VT_DIRECTIVES, None)
   }
+  #[inline]
+  pub fn description(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Interface::VT_DESCRIPTION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for Interface<'_> {
@@ -2488,6 +2620,7 @@ impl flatbuffers::Verifiable for Interface<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u32>>>("implementing_interfaces", Self::VT_IMPLEMENTING_INTERFACES, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u32>>>("implementing_objects", Self::VT_IMPLEMENTING_OBJECTS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<DirectiveValue>>>>("directives", Self::VT_DIRECTIVES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("description", Self::VT_DESCRIPTION, false)?
      .finish();
     Ok(())
   }
@@ -2500,6 +2633,7 @@ pub struct InterfaceArgs<'a> {
     pub implementing_interfaces: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u32>>>,
     pub implementing_objects: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u32>>>,
     pub directives: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>>>,
+    pub description: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for InterfaceArgs<'a> {
   #[inline]
@@ -2512,6 +2646,7 @@ impl<'a> Default for InterfaceArgs<'a> {
       implementing_interfaces: None,
       implementing_objects: None,
       directives: None,
+      description: None,
     }
   }
 }
@@ -2550,6 +2685,10 @@ impl<'a: 'b, 'b> InterfaceBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Interface::VT_DIRECTIVES, directives);
   }
   #[inline]
+  pub fn add_description(&mut self, description: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Interface::VT_DESCRIPTION, description);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> InterfaceBuilder<'a, 'b> {
     let start = _fbb.start_table();
     InterfaceBuilder {
@@ -2574,6 +2713,7 @@ impl core::fmt::Debug for Interface<'_> {
       ds.field("implementing_interfaces", &self.implementing_interfaces());
       ds.field("implementing_objects", &self.implementing_objects());
       ds.field("directives", &self.directives());
+      ds.field("description", &self.description());
       ds.finish()
   }
 }
@@ -2597,6 +2737,7 @@ impl<'a> Union<'a> {
   pub const VT_IS_EXTENSION: flatbuffers::VOffsetT = 6;
   pub const VT_MEMBERS: flatbuffers::VOffsetT = 8;
   pub const VT_DIRECTIVES: flatbuffers::VOffsetT = 10;
+  pub const VT_DESCRIPTION: flatbuffers::VOffsetT = 12;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -2608,6 +2749,7 @@ impl<'a> Union<'a> {
     args: &'args UnionArgs<'args>
   ) -> flatbuffers::WIPOffset<Union<'bldr>> {
     let mut builder = UnionBuilder::new(_fbb);
+    if let Some(x) = args.description { builder.add_description(x); }
     if let Some(x) = args.directives { builder.add_directives(x); }
     if let Some(x) = args.members { builder.add_members(x); }
     if let Some(x) = args.name { builder.add_name(x); }
@@ -2631,6 +2773,10 @@ impl<'a> Union<'a> {
   pub fn directives(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue>>>>(Union::VT_DIRECTIVES, None)
   }
+  #[inline]
+  pub fn description(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Union::VT_DESCRIPTION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for Union<'_> {
@@ -2644,6 +2790,7 @@ impl flatbuffers::Verifiable for Union<'_> {
      .visit_field::<bool>("is_extension", Self::VT_IS_EXTENSION, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u32>>>("members", Self::VT_MEMBERS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<DirectiveValue>>>>("directives", Self::VT_DIRECTIVES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("description", Self::VT_DESCRIPTION, false)?
      .finish();
     Ok(())
   }
@@ -2653,6 +2800,7 @@ pub struct UnionArgs<'a> {
     pub is_extension: bool,
     pub members: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u32>>>,
     pub directives: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>>>,
+    pub description: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for UnionArgs<'a> {
   #[inline]
@@ -2662,6 +2810,7 @@ impl<'a> Default for UnionArgs<'a> {
       is_extension: false,
       members: None,
       directives: None,
+      description: None,
     }
   }
 }
@@ -2688,6 +2837,10 @@ impl<'a: 'b, 'b> UnionBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Union::VT_DIRECTIVES, directives);
   }
   #[inline]
+  pub fn add_description(&mut self, description: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Union::VT_DESCRIPTION, description);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> UnionBuilder<'a, 'b> {
     let start = _fbb.start_table();
     UnionBuilder {
@@ -2709,6 +2862,7 @@ impl core::fmt::Debug for Union<'_> {
       ds.field("is_extension", &self.is_extension());
       ds.field("members", &self.members());
       ds.field("directives", &self.directives());
+      ds.field("description", &self.description());
       ds.finish()
   }
 }
@@ -2734,6 +2888,7 @@ impl<'a> Field<'a> {
   pub const VT_TYPE_: flatbuffers::VOffsetT = 10;
   pub const VT_DIRECTIVES: flatbuffers::VOffsetT = 12;
   pub const VT_PARENT_TYPE: flatbuffers::VOffsetT = 14;
+  pub const VT_DESCRIPTION: flatbuffers::VOffsetT = 16;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -2745,6 +2900,7 @@ impl<'a> Field<'a> {
     args: &'args FieldArgs<'args>
   ) -> flatbuffers::WIPOffset<Field<'bldr>> {
     let mut builder = FieldBuilder::new(_fbb);
+    if let Some(x) = args.description { builder.add_description(x); }
     if let Some(x) = args.parent_type { builder.add_parent_type(x); }
     if let Some(x) = args.directives { builder.add_directives(x); }
     if let Some(x) = args.type_ { builder.add_type_(x); }
@@ -2778,6 +2934,10 @@ impl<'a> Field<'a> {
   pub fn parent_type(&self) -> Option<Type<'a>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<Type>>(Field::VT_PARENT_TYPE, None)
   }
+  #[inline]
+  pub fn description(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Field::VT_DESCRIPTION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for Field<'_> {
@@ -2793,6 +2953,7 @@ impl flatbuffers::Verifiable for Field<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<TypeReference>>("type_", Self::VT_TYPE_, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<DirectiveValue>>>>("directives", Self::VT_DIRECTIVES, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<Type>>("parent_type", Self::VT_PARENT_TYPE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("description", Self::VT_DESCRIPTION, false)?
      .finish();
     Ok(())
   }
@@ -2804,6 +2965,7 @@ pub struct FieldArgs<'a> {
     pub type_: Option<flatbuffers::WIPOffset<TypeReference<'a>>>,
     pub directives: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<DirectiveValue<'a>>>>>,
     pub parent_type: Option<flatbuffers::WIPOffset<Type<'a>>>,
+    pub description: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for FieldArgs<'a> {
   #[inline]
@@ -2815,6 +2977,7 @@ impl<'a> Default for FieldArgs<'a> {
       type_: None,
       directives: None,
       parent_type: None,
+      description: None,
     }
   }
 }
@@ -2849,6 +3012,10 @@ impl<'a: 'b, 'b> FieldBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<Type>>(Field::VT_PARENT_TYPE, parent_type);
   }
   #[inline]
+  pub fn add_description(&mut self, description: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Field::VT_DESCRIPTION, description);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> FieldBuilder<'a, 'b> {
     let start = _fbb.start_table();
     FieldBuilder {
@@ -2872,6 +3039,7 @@ impl core::fmt::Debug for Field<'_> {
       ds.field("type_", &self.type_());
       ds.field("directives", &self.directives());
       ds.field("parent_type", &self.parent_type());
+      ds.field("description", &self.description());
       ds.finish()
   }
 }
@@ -0,0 +1,37 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IntrospectionError {
+    #[error("Network create error: {error}")]
+    NetworkCreateError {
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("Network error: {source}")]
+    NetworkError {
+        #[from]
+        source: hyper::Error,
+    },
+
+    #[error("The server responded with status {status}: {body}")]
+    ErrorStatus { status: u16, body: String },
+
+    #[error("The introspection query failed: {}", messages.join(", "))]
+    GraphQLErrors { messages: Vec<String> },
+
+    #[error("Failed parsing introspection result: {source}")]
+    ParseError {
+        #[from]
+        source: serde_json::Error,
+    },
+
+    #[error("The introspection result doesn't contain a `__schema` field.")]
+    MissingSchema,
+}
@@ -0,0 +1,83 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use hyper::header;
+use hyper::Body;
+use hyper::Client;
+use hyper::Method;
+use hyper::Request;
+use hyper::StatusCode;
+use hyper_tls::HttpsConnector;
+
+use crate::errors::IntrospectionError;
+use crate::introspection::INTROSPECTION_QUERY;
+
+pub enum FetchResult {
+    /// The server confirmed that the schema for the given `etag` is still
+    /// current.
+    NotModified,
+    Fetched {
+        /// The raw JSON response of the introspection query.
+        response: String,
+        etag: Option<String>,
+    },
+}
+
+/// Sends the introspection query to `url`. If `etag` is provided, it is sent
+/// as `If-None-Match`, so servers that support it can skip sending an
+/// unchanged schema.
+pub async fn fetch_introspection(
+    url: &str,
+    headers: impl IntoIterator<Item = (&String, &String)>,
+    etag: Option<&str>,
+) -> Result<FetchResult, IntrospectionError> {
+    let request_body = serde_json::json!({
+        "query": INTROSPECTION_QUERY,
+        "operationName": "IntrospectionQuery",
+    })
+    .to_string();
+
+    let mut builder = Request::builder()
+        .method(Method::POST)
+        .uri(url)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, "application/json");
+    if let Some(etag) = etag {
+        builder = builder.header(header::IF_NONE_MATCH, etag);
+    }
+    for (k, v) in headers {
+        builder = builder.header(k, v);
+    }
+    let req = builder.body(Body::from(request_body)).map_err(|err| {
+        IntrospectionError::NetworkCreateError {
+            error: Box::new(err),
+        }
+    })?;
+    let https = HttpsConnector::new();
+    let client = Client::builder().build(https);
+    let res = client.request(req).await?;
+
+    let status = res.status();
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(FetchResult::NotModified);
+    }
+    let etag = res
+        .headers()
+        .get(header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+    let bytes = hyper::body::to_bytes(res.into_body()).await?;
+    let response = String::from_utf8_lossy(&bytes).into_owned();
+    if !status.is_success() {
+        return Err(IntrospectionError::ErrorStatus {
+            status: status.as_u16(),
+            body: response,
+        });
+    }
+
+    Ok(FetchResult::Fetched { response, etag })
+}
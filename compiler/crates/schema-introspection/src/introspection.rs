@@ -0,0 +1,222 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::errors::IntrospectionError;
+
+/// The standard introspection query, as sent by graphql-js'
+/// `getIntrospectionQuery` with default options. `specifiedByURL` and
+/// `isRepeatable` are omitted because older servers reject them, but they are
+/// read from introspection results that include them.
+pub const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives {
+      name
+      description
+      locations
+      args { ...InputValue }
+    }
+  }
+}
+
+fragment FullType on __Type {
+  kind
+  name
+  description
+  fields(includeDeprecated: true) {
+    name
+    description
+    args { ...InputValue }
+    type { ...TypeRef }
+    isDeprecated
+    deprecationReason
+  }
+  inputFields { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) {
+    name
+    description
+    isDeprecated
+    deprecationReason
+  }
+  possibleTypes { ...TypeRef }
+}
+
+fragment InputValue on __InputValue {
+  name
+  description
+  type { ...TypeRef }
+  defaultValue
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntrospectionSchema {
+    pub query_type: Option<NamedTypeRef>,
+    pub mutation_type: Option<NamedTypeRef>,
+    pub subscription_type: Option<NamedTypeRef>,
+    pub types: Vec<FullType>,
+    #[serde(default)]
+    pub directives: Vec<Directive>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NamedTypeRef {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullType {
+    pub kind: TypeKind,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default, rename = "specifiedByURL", alias = "specifiedByUrl")]
+    pub specified_by_url: Option<String>,
+    pub fields: Option<Vec<Field>>,
+    pub input_fields: Option<Vec<InputValue>>,
+    pub interfaces: Option<Vec<TypeRef>>,
+    pub enum_values: Option<Vec<EnumValue>>,
+    pub possible_types: Option<Vec<TypeRef>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Field {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    pub type_: TypeRef,
+    #[serde(default)]
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputValue {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: TypeRef,
+    pub default_value: Option<String>,
+    #[serde(default)]
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnumValue {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeRef {
+    pub kind: TypeKind,
+    pub name: Option<String>,
+    pub of_type: Option<Box<TypeRef>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Directive {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_repeatable: bool,
+    pub locations: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<InputValue>,
+}
+
+#[derive(Deserialize)]
+struct GraphQLError {
+    message: String,
+}
+
+/// Parses the JSON result of an introspection query. Accepts the full
+/// response (`{"data": {"__schema": ...}}`) as well as just the data
+/// (`{"__schema": ...}`).
+pub fn parse_introspection(json: &str) -> Result<IntrospectionSchema, IntrospectionError> {
+    let mut value: Value = serde_json::from_str(json)?;
+    if let Some(errors) = value.get("errors") {
+        let errors: Vec<GraphQLError> = serde_json::from_value(errors.clone())?;
+        if !errors.is_empty() {
+            return Err(IntrospectionError::GraphQLErrors {
+                messages: errors.into_iter().map(|error| error.message).collect(),
+            });
+        }
+    }
+    if let Some(data) = value.get_mut("data") {
+        value = data.take();
+    }
+    match value.get_mut("__schema") {
+        Some(schema) => Ok(serde_json::from_value(schema.take())?),
+        None => Err(IntrospectionError::MissingSchema),
+    }
+}
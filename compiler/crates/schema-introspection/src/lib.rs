@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Support for schemas that are only available as the result of a GraphQL
+//! introspection query: fetching them from an endpoint, and converting the
+//! result to SDL that can be parsed by the `schema` crate.
+
+#![deny(warnings)]
+#![deny(rust_2018_idioms)]
+#![deny(clippy::all)]
+
+mod errors;
+mod fetch;
+mod introspection;
+mod sdl;
+
+pub use errors::IntrospectionError;
+pub use fetch::fetch_introspection;
+pub use fetch::FetchResult;
+pub use introspection::parse_introspection;
+pub use introspection::IntrospectionSchema;
+pub use introspection::INTROSPECTION_QUERY;
+pub use sdl::introspection_to_sdl;
@@ -0,0 +1,250 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fmt::Result as FmtResult;
+use std::fmt::Write;
+
+use crate::introspection::Directive;
+use crate::introspection::FullType;
+use crate::introspection::InputValue;
+use crate::introspection::IntrospectionSchema;
+use crate::introspection::TypeKind;
+use crate::introspection::TypeRef;
+
+const BUILTIN_SCALARS: [&str; 5] = ["Boolean", "Float", "ID", "Int", "String"];
+const BUILTIN_DIRECTIVES: [&str; 4] = ["deprecated", "include", "skip", "specifiedBy"];
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// Prints the schema of an introspection result as SDL. Built-in scalars,
+/// built-in directives and introspection types are omitted.
+pub fn introspection_to_sdl(schema: &IntrospectionSchema) -> String {
+    let mut printer = SDLPrinter {
+        output: String::new(),
+    };
+    printer.print_schema(schema).unwrap();
+    printer.output
+}
+
+struct SDLPrinter {
+    output: String,
+}
+
+impl SDLPrinter {
+    fn print_schema(&mut self, schema: &IntrospectionSchema) -> FmtResult {
+        writeln!(self.output, "schema {{")?;
+        for (operation, type_) in [
+            ("query", &schema.query_type),
+            ("mutation", &schema.mutation_type),
+            ("subscription", &schema.subscription_type),
+        ] {
+            if let Some(type_) = type_ {
+                writeln!(self.output, "  {}: {}", operation, type_.name)?;
+            }
+        }
+        writeln!(self.output, "}}")?;
+
+        for directive in &schema.directives {
+            if !BUILTIN_DIRECTIVES.contains(&directive.name.as_str()) {
+                writeln!(self.output)?;
+                self.print_directive(directive)?;
+            }
+        }
+        for type_ in &schema.types {
+            if !is_builtin_type(type_) {
+                writeln!(self.output)?;
+                self.print_type(type_)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn print_directive(&mut self, directive: &Directive) -> FmtResult {
+        self.print_description(&directive.description, "")?;
+        write!(self.output, "directive @{}", directive.name)?;
+        self.print_arguments(&directive.args, "")?;
+        if directive.is_repeatable {
+            write!(self.output, " repeatable")?;
+        }
+        writeln!(self.output, " on {}", directive.locations.join(" | "))
+    }
+
+    fn print_type(&mut self, type_: &FullType) -> FmtResult {
+        self.print_description(&type_.description, "")?;
+        match type_.kind {
+            TypeKind::Scalar => {
+                write!(self.output, "scalar {}", type_.name)?;
+                if let Some(url) = &type_.specified_by_url {
+                    write!(self.output, " @specifiedBy(url: {})", print_string(url))?;
+                }
+                writeln!(self.output)
+            }
+            TypeKind::Object | TypeKind::Interface => {
+                let keyword = if type_.kind == TypeKind::Object {
+                    "type"
+                } else {
+                    "interface"
+                };
+                write!(self.output, "{} {}", keyword, type_.name)?;
+                let interfaces = type_.interfaces.as_deref().unwrap_or_default();
+                if !interfaces.is_empty() {
+                    write!(
+                        self.output,
+                        " implements {}",
+                        interfaces
+                            .iter()
+                            .map(named_type)
+                            .collect::<Vec<_>>()
+                            .join(" & ")
+                    )?;
+                }
+                writeln!(self.output, " {{")?;
+                for field in type_.fields.as_deref().unwrap_or_default() {
+                    self.print_description(&field.description, "  ")?;
+                    write!(self.output, "  {}", field.name)?;
+                    self.print_arguments(&field.args, "  ")?;
+                    write!(self.output, ": {}", print_type_ref(&field.type_))?;
+                    self.print_deprecated(field.is_deprecated, &field.deprecation_reason)?;
+                    writeln!(self.output)?;
+                }
+                writeln!(self.output, "}}")
+            }
+            TypeKind::Union => writeln!(
+                self.output,
+                "union {} = {}",
+                type_.name,
+                type_
+                    .possible_types
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(named_type)
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            ),
+            TypeKind::Enum => {
+                writeln!(self.output, "enum {} {{", type_.name)?;
+                for value in type_.enum_values.as_deref().unwrap_or_default() {
+                    self.print_description(&value.description, "  ")?;
+                    write!(self.output, "  {}", value.name)?;
+                    self.print_deprecated(value.is_deprecated, &value.deprecation_reason)?;
+                    writeln!(self.output)?;
+                }
+                writeln!(self.output, "}}")
+            }
+            TypeKind::InputObject => {
+                writeln!(self.output, "input {} {{", type_.name)?;
+                for field in type_.input_fields.as_deref().unwrap_or_default() {
+                    self.print_description(&field.description, "  ")?;
+                    write!(self.output, "  ")?;
+                    self.print_input_value(field)?;
+                    writeln!(self.output)?;
+                }
+                writeln!(self.output, "}}")
+            }
+            TypeKind::List | TypeKind::NonNull => {
+                // Wrapping types only occur in type references
+                Ok(())
+            }
+        }
+    }
+
+    fn print_arguments(&mut self, arguments: &[InputValue], indent: &str) -> FmtResult {
+        if arguments.is_empty() {
+            return Ok(());
+        }
+        if arguments
+            .iter()
+            .all(|argument| argument.description.is_none())
+        {
+            write!(self.output, "(")?;
+            for (index, argument) in arguments.iter().enumerate() {
+                if index > 0 {
+                    write!(self.output, ", ")?;
+                }
+                self.print_input_value(argument)?;
+            }
+            write!(self.output, ")")
+        } else {
+            let argument_indent = format!("{}  ", indent);
+            writeln!(self.output, "(")?;
+            for argument in arguments {
+                self.print_description(&argument.description, &argument_indent)?;
+                write!(self.output, "{}", argument_indent)?;
+                self.print_input_value(argument)?;
+                writeln!(self.output)?;
+            }
+            write!(self.output, "{})", indent)
+        }
+    }
+
+    fn print_input_value(&mut self, input_value: &InputValue) -> FmtResult {
+        write!(
+            self.output,
+            "{}: {}",
+            input_value.name,
+            print_type_ref(&input_value.type_)
+        )?;
+        if let Some(default_value) = &input_value.default_value {
+            write!(self.output, " = {}", default_value)?;
+        }
+        self.print_deprecated(input_value.is_deprecated, &input_value.deprecation_reason)
+    }
+
+    fn print_deprecated(&mut self, is_deprecated: bool, reason: &Option<String>) -> FmtResult {
+        if !is_deprecated {
+            return Ok(());
+        }
+        match reason.as_deref() {
+            None | Some(DEFAULT_DEPRECATION_REASON) => write!(self.output, " @deprecated"),
+            Some(reason) => write!(
+                self.output,
+                " @deprecated(reason: {})",
+                print_string(reason)
+            ),
+        }
+    }
+
+    fn print_description(&mut self, description: &Option<String>, indent: &str) -> FmtResult {
+        if let Some(description) = description {
+            if description.is_empty() {
+                return Ok(());
+            }
+            writeln!(self.output, "{}\"\"\"", indent)?;
+            for line in description.replace("\"\"\"", "\\\"\"\"").lines() {
+                if line.is_empty() {
+                    writeln!(self.output)?;
+                } else {
+                    writeln!(self.output, "{}{}", indent, line)?;
+                }
+            }
+            writeln!(self.output, "{}\"\"\"", indent)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_builtin_type(type_: &FullType) -> bool {
+    type_.name.starts_with("__")
+        || (type_.kind == TypeKind::Scalar && BUILTIN_SCALARS.contains(&type_.name.as_str()))
+}
+
+fn named_type(type_ref: &TypeRef) -> &str {
+    type_ref.name.as_deref().unwrap_or_default()
+}
+
+fn print_type_ref(type_ref: &TypeRef) -> String {
+    match (type_ref.kind, &type_ref.of_type) {
+        (TypeKind::NonNull, Some(of_type)) => format!("{}!", print_type_ref(of_type)),
+        (TypeKind::List, Some(of_type)) => format!("[{}]", print_type_ref(of_type)),
+        _ => named_type(type_ref).to_string(),
+    }
+}
+
+/// GraphQL string literals use the same escape sequences as JSON.
+fn print_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+}
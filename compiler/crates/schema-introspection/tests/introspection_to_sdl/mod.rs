@@ -0,0 +1,19 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use fixture_tests::Fixture;
+use schema::build_schema;
+use schema_introspection::introspection_to_sdl;
+use schema_introspection::parse_introspection;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let introspection = parse_introspection(fixture.content).map_err(|err| err.to_string())?;
+    let sdl = introspection_to_sdl(&introspection);
+    // The printed SDL should be a valid schema.
+    build_schema(&sdl).map_err(|diagnostics| format!("{:?}", diagnostics))?;
+    Ok(sdl)
+}
@@ -0,0 +1,34 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<7194abbf619d846b30fd76289d7e3772>>
+ */
+
+mod introspection_to_sdl;
+
+use introspection_to_sdl::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn errors() {
+    let input = include_str!("introspection_to_sdl/fixtures/errors.json");
+    let expected = include_str!("introspection_to_sdl/fixtures/errors.expected");
+    test_fixture(transform_fixture, "errors.json", "introspection_to_sdl/fixtures/errors.expected", input, expected);
+}
+
+#[test]
+fn kitchen_sink() {
+    let input = include_str!("introspection_to_sdl/fixtures/kitchen-sink.json");
+    let expected = include_str!("introspection_to_sdl/fixtures/kitchen-sink.expected");
+    test_fixture(transform_fixture, "kitchen-sink.json", "introspection_to_sdl/fixtures/kitchen-sink.expected", input, expected);
+}
+
+#[test]
+fn schema_without_data() {
+    let input = include_str!("introspection_to_sdl/fixtures/schema-without-data.json");
+    let expected = include_str!("introspection_to_sdl/fixtures/schema-without-data.expected");
+    test_fixture(transform_fixture, "schema-without-data.json", "introspection_to_sdl/fixtures/schema-without-data.expected", input, expected);
+}
@@ -34,6 +34,21 @@ pub fn print(schema: &SDLSchema) -> String {
     builder
 }
 
+/// Same as `print`, but with the types, directives, fields, enum values,
+/// union members and implemented interfaces sorted by name, so that the
+/// output doesn't depend on the order of the schema sources.
+pub fn print_sorted(schema: &SDLSchema) -> String {
+    let mut result = vec![String::new(); DEAULT_SHARD_COUNT];
+    let mut printer = Printer::new(schema, &mut result);
+    printer.sorted = true;
+    if !is_schema_of_common_name(schema) && has_schema_definition_types(schema) {
+        printer.print_schema_definition().unwrap();
+    }
+    printer.print_directives().unwrap();
+    printer.print_types().unwrap();
+    result.into_iter().next().unwrap()
+}
+
 pub fn print_schema_definition(schema: &SDLSchema) -> String {
     if is_schema_of_common_name(schema) || !has_schema_definition_types(schema) {
         return String::new();
@@ -168,6 +183,9 @@ struct Printer<'schema, 'writer> {
     // When sharding individual types, this represents index to
     // specify which shard to wite for a given type and field
     type_writers_index: Option<(StringKey, usize)>,
+    // Print the members of the schema sorted by name instead of in the
+    // order of the schema.
+    sorted: bool,
 }
 
 impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
@@ -179,6 +197,7 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
             shard_count: DEAULT_SHARD_COUNT,
             type_writers: None,
             type_writers_index: None,
+            sorted: false,
         }
     }
 
@@ -195,6 +214,7 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
             shard_count,
             type_writers,
             type_writers_index: None,
+            sorted: false,
         }
     }
 
@@ -234,7 +254,11 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
             return Ok(());
         }
         let mut ordered_directives = self.schema.get_directives().collect::<Vec<_>>();
-        ordered_directives.sort_by_key(|dir| dir.name);
+        if self.sorted {
+            ordered_directives.sort_by_key(|dir| dir.name.0.lookup());
+        } else {
+            ordered_directives.sort_by_key(|dir| dir.name);
+        }
         for directive in ordered_directives {
             self.print_directive(directive)?;
         }
@@ -244,6 +268,9 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
     fn print_directive(&mut self, directive: &Directive) -> FmtResult {
         write!(self.writer(), "directive @{}", directive.name)?;
         self.print_args(&directive.arguments)?;
+        if directive.repeatable {
+            write!(self.writer(), " repeatable")?;
+        }
         write!(
             self.writer(),
             " on {}",
@@ -253,9 +280,17 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
     }
 
     fn print_types(&mut self) -> FmtResult {
-        let ordered_type_map = self.schema.get_type_map().collect::<BTreeMap<_, _>>();
-        for (_key, value) in ordered_type_map.iter() {
-            self.print_type(**value)?;
+        let mut ordered_types = self
+            .schema
+            .get_type_map()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        if self.sorted {
+            ordered_types.sort_by_key(|(key, _)| key.lookup());
+        }
+        for (_key, value) in ordered_types {
+            self.print_type(*value)?;
         }
         Ok(())
     }
@@ -306,13 +341,15 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
         write!(self.writer(), "union {}", union_.name.item)?;
         self.print_directive_values(&union_.directives)?;
         if !union_.members.is_empty() {
-            let union_members = union_
+            let mut union_members = union_
                 .members
                 .iter()
                 .map(|id| self.schema.object(*id).name.item)
-                .collect::<Vec<_>>()
-                .iter()
-                .join(" | ");
+                .collect::<Vec<_>>();
+            if self.sorted {
+                union_members.sort_by_key(|name| name.lookup());
+            }
+            let union_members = union_members.iter().join(" | ");
             write!(self.writer(), " = {}", union_members)?;
         }
         self.print_definition_end()
@@ -343,7 +380,11 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
         write!(self.writer(), "{{")?;
         self.print_new_line()?;
         let typename = type_name.lookup().intern();
-        for field_id in fields {
+        let mut fields = fields.to_vec();
+        if self.sorted {
+            fields.sort_by_key(|field_id| self.schema.field(*field_id).name.item.lookup());
+        }
+        for field_id in &fields {
             let field = &self.schema.field(*field_id);
             self.update_writer_index_for_field_start(field.name.item, typename);
             self.print_space()?;
@@ -376,6 +417,7 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
             if let Some(default) = &arg.default_value {
                 write!(self.writer(), " = {}", default,)?;
             }
+            self.print_directive_values(&arg.directives)?;
         }
         write!(self.writer(), ")")
     }
@@ -386,6 +428,10 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
         }
         write!(self.writer(), "{{")?;
         self.print_new_line()?;
+        let mut values = values.iter().collect::<Vec<_>>();
+        if self.sorted {
+            values.sort_by_key(|value| value.value.lookup());
+        }
         for value in values {
             write!(self.writer(), "  {}", value.value,)?;
             self.print_directive_values(&value.directives)?;
@@ -400,12 +446,17 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
         }
         write!(self.writer(), "{{")?;
         self.print_new_line()?;
-        for arg in args.iter() {
+        let mut args = args.iter().collect::<Vec<_>>();
+        if self.sorted {
+            args.sort_by_key(|arg| arg.name.0.lookup());
+        }
+        for arg in args {
             let type_string = self.schema.get_type_string(&arg.type_);
             write!(self.writer(), "  {}: {}", arg.name, type_string,)?;
             if let Some(default) = &arg.default_value {
                 write!(self.writer(), " = {}", default,)?;
             }
+            self.print_directive_values(&arg.directives)?;
             self.print_new_line()?;
         }
         write!(self.writer(), "}}")
@@ -441,10 +492,14 @@ impl<'schema, 'writer, 'curent_writer> Printer<'schema, 'writer> {
 
     fn print_implementing_interfaces(&mut self, interfaces: &[InterfaceID]) -> FmtResult {
         if !interfaces.is_empty() {
-            let interface_names = interfaces
+            let mut interface_names = interfaces
                 .iter()
                 .map(|id| self.schema.interface(*id).name.item)
-                .join(" & ");
+                .collect::<Vec<_>>();
+            if self.sorted {
+                interface_names.sort_by_key(|name| name.lookup());
+            }
+            let interface_names = interface_names.iter().join(" & ");
             write!(self.writer(), " implements {}", interface_names,)?;
         }
         Ok(())
@@ -0,0 +1,15 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use fixture_tests::Fixture;
+use schema::build_schema;
+use schema_print::print_sorted;
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let schema = build_schema(fixture.content).unwrap();
+    Ok(print_sorted(&schema))
+}
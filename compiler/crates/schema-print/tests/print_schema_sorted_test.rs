@@ -0,0 +1,34 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<1bc9844034ac5332678cae31545016dc>>
+ */
+
+mod print_schema_sorted;
+
+use print_schema_sorted::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn argument_directives() {
+    let input = include_str!("print_schema_sorted/fixtures/argument-directives.graphql");
+    let expected = include_str!("print_schema_sorted/fixtures/argument-directives.expected");
+    test_fixture(transform_fixture, "argument-directives.graphql", "print_schema_sorted/fixtures/argument-directives.expected", input, expected);
+}
+
+#[test]
+fn repeatable_directive() {
+    let input = include_str!("print_schema_sorted/fixtures/repeatable-directive.graphql");
+    let expected = include_str!("print_schema_sorted/fixtures/repeatable-directive.expected");
+    test_fixture(transform_fixture, "repeatable-directive.graphql", "print_schema_sorted/fixtures/repeatable-directive.expected", input, expected);
+}
+
+#[test]
+fn unordered() {
+    let input = include_str!("print_schema_sorted/fixtures/unordered.graphql");
+    let expected = include_str!("print_schema_sorted/fixtures/unordered.expected");
+    test_fixture(transform_fixture, "unordered.graphql", "print_schema_sorted/fixtures/unordered.expected", input, expected);
+}
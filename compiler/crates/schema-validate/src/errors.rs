@@ -87,4 +87,10 @@ pub enum SchemaValidationError {
 
     #[error("Cyclic reference found for interface inheritance: {0}.")]
     CyclicInterfaceInheritance(String),
+
+    #[error("OneOf input field '{0}.{1}' must be nullable.")]
+    NonNullOneOfInputField(StringKey, ArgumentName),
+
+    #[error("OneOf input field '{0}.{1}' cannot have a default value.")]
+    OneOfInputFieldWithDefaultValue(StringKey, ArgumentName),
 }
@@ -315,6 +315,28 @@ impl<'schema> ValidationContext<'schema> {
                     context,
                 );
             }
+
+            // Ensure the fields of @oneOf input objects are optional.
+            if input_object.is_one_of() {
+                if field.type_.is_non_null() {
+                    self.report_error(
+                        SchemaValidationError::NonNullOneOfInputField(
+                            input_object.name.item.0,
+                            field.name,
+                        ),
+                        context,
+                    );
+                }
+                if field.default_value.is_some() {
+                    self.report_error(
+                        SchemaValidationError::OneOfInputFieldWithDefaultValue(
+                            input_object.name.item.0,
+                            field.name,
+                        ),
+                        context,
+                    );
+                }
+            }
         }
     }
 
@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<e0f69125973077ab82c37b910a577a54>>
+ * @generated SignedSource<<fefda17b135e67725622991b9270832a>>
  */
 
 mod validate_schema;
@@ -40,6 +40,13 @@ fn validate_object() {
     test_fixture(transform_fixture, "validate_object.graphql", "validate_schema/fixtures/validate_object.expected", input, expected);
 }
 
+#[test]
+fn validate_one_of() {
+    let input = include_str!("validate_schema/fixtures/validate_one_of.graphql");
+    let expected = include_str!("validate_schema/fixtures/validate_one_of.expected");
+    test_fixture(transform_fixture, "validate_one_of.graphql", "validate_schema/fixtures/validate_one_of.expected", input, expected);
+}
+
 #[test]
 fn validate_root_types() {
     let input = include_str!("validate_schema/fixtures/validate_root_types.graphql");
@@ -10,6 +10,8 @@ use std::collections::HashSet;
 use common::InterfaceName;
 use common::WithLocation;
 use intern::string_key::StringKey;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::DirectiveValue;
 use crate::FieldID;
@@ -17,7 +19,7 @@ use crate::InterfaceID;
 use crate::ObjectID;
 use crate::Schema;
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Interface {
     pub name: WithLocation<InterfaceName>,
     pub is_extension: bool,
@@ -11,12 +11,14 @@ use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
 use std::slice::Iter;
+use std::sync::Arc;
 
 use common::ArgumentName;
 use common::DirectiveName;
 use common::EnumName;
 use common::InputObjectName;
 use common::InterfaceName;
+use common::Location;
 use common::Named;
 use common::NamedItem;
 use common::ObjectName;
@@ -28,19 +30,37 @@ pub use interface::*;
 use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use lazy_static::lazy_static;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::Schema;
 
 lazy_static! {
     static ref DIRECTIVE_DEPRECATED: DirectiveName = DirectiveName("deprecated".intern());
+    static ref DIRECTIVE_ONE_OF: DirectiveName = DirectiveName("oneOf".intern());
+    static ref DIRECTIVE_SPECIFIED_BY: DirectiveName = DirectiveName("specifiedBy".intern());
+    static ref DIRECTIVE_SEMANTIC_NON_NULL: DirectiveName =
+        DirectiveName("semanticNonNull".intern());
+    static ref ARGUMENT_LEVELS: ArgumentName = ArgumentName("levels".intern());
     static ref ARGUMENT_REASON: ArgumentName = ArgumentName("reason".intern());
+    static ref ARGUMENT_URL: ArgumentName = ArgumentName("url".intern());
 }
 
 pub(crate) type TypeMap = HashMap<StringKey, Type>;
 
 macro_rules! type_id {
     ($name:ident, $type:ident) => {
-        #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+        #[derive(
+            Copy,
+            Clone,
+            Eq,
+            PartialEq,
+            Ord,
+            PartialOrd,
+            Hash,
+            Serialize,
+            Deserialize
+        )]
         pub struct $name(pub $type);
         impl $name {
             pub(crate) fn as_usize(&self) -> usize {
@@ -70,7 +90,17 @@ type_id!(ScalarID, u32);
 type_id!(UnionID, u32);
 type_id!(FieldID, u32);
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub enum Type {
     Enum(EnumID),
     InputObject(InputObjectID),
@@ -196,7 +226,17 @@ impl Type {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub enum TypeReference<T> {
     Named(T),
     NonNull(Box<TypeReference<T>>),
@@ -241,6 +281,26 @@ impl<T: Copy> TypeReference<T> {
         }
     }
 
+    /// Makes the type non-null at the given levels, where level 0 is the
+    /// type itself, level 1 the items of a list and so on.
+    pub fn with_non_null_levels(&self, levels: &[i64]) -> TypeReference<T> {
+        self.with_non_null_levels_from(levels, 0)
+    }
+
+    fn with_non_null_levels_from(&self, levels: &[i64], level: i64) -> TypeReference<T> {
+        let nullable_type = match self.nullable_type() {
+            TypeReference::List(of) => {
+                TypeReference::List(Box::new(of.with_non_null_levels_from(levels, level + 1)))
+            }
+            named => named.clone(),
+        };
+        if self.is_non_null() || levels.contains(&level) {
+            nullable_type.non_null()
+        } else {
+            nullable_type
+        }
+    }
+
     // Return None if the type is a List, otherwise return the inner type
     pub fn non_list_type(&self) -> Option<T> {
         match self {
@@ -296,9 +356,12 @@ impl<T> TypeReference<T> {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Directive {
     pub name: DirectiveName,
+    /// Where the directive is defined, as opposed to the `locations` it may
+    /// be used in.
+    pub location: Location,
     pub arguments: ArgumentDefinitions,
     pub locations: Vec<DirectiveLocation>,
     pub repeatable: bool,
@@ -314,7 +377,7 @@ impl Named for Directive {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Scalar {
     pub name: WithLocation<ScalarName>,
     pub is_extension: bool,
@@ -323,7 +386,18 @@ pub struct Scalar {
     pub hack_source: Option<StringKey>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+impl Scalar {
+    /// The URL of the specification of a custom scalar, from its
+    /// `@specifiedBy(url: ...)` directive.
+    pub fn specified_by_url(&self) -> Option<StringKey> {
+        self.directives
+            .named(*DIRECTIVE_SPECIFIED_BY)
+            .and_then(|directive| directive.arguments.named(*ARGUMENT_URL))
+            .and_then(|url| url.value.get_string_literal())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Object {
     pub name: WithLocation<ObjectName>,
     pub is_extension: bool,
@@ -334,7 +408,7 @@ pub struct Object {
     pub hack_source: Option<StringKey>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct InputObject {
     pub name: WithLocation<InputObjectName>,
     pub fields: ArgumentDefinitions,
@@ -343,7 +417,15 @@ pub struct InputObject {
     pub hack_source: Option<StringKey>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+impl InputObject {
+    /// Exactly one field of a `@oneOf` input object must be set, to a
+    /// non-null value.
+    pub fn is_one_of(&self) -> bool {
+        self.directives.named(*DIRECTIVE_ONE_OF).is_some()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Enum {
     pub name: WithLocation<EnumName>,
     pub is_extension: bool,
@@ -353,7 +435,7 @@ pub struct Enum {
     pub hack_source: Option<StringKey>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Union {
     pub name: WithLocation<StringKey>,
     pub is_extension: bool,
@@ -363,7 +445,7 @@ pub struct Union {
     pub hack_source: Option<StringKey>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Field {
     pub name: WithLocation<StringKey>,
     pub is_extension: bool,
@@ -394,13 +476,48 @@ impl Field {
                     .and_then(|reason| reason.value.get_string_literal()),
             })
     }
+
+    /// The levels of the type that are only null if the field has an error,
+    /// from its `@semanticNonNull(levels: [Int] = [0])` directive. Level 0
+    /// is the field itself, level 1 the items of a list and so on. Only
+    /// schemas with semantic nullability support are read for it.
+    pub fn semantic_non_null_levels(&self) -> Option<Vec<i64>> {
+        let directive = self.directives.named(*DIRECTIVE_SEMANTIC_NON_NULL)?;
+        Some(match directive.arguments.named(*ARGUMENT_LEVELS) {
+            Some(levels) => match &levels.value {
+                ConstantValue::List(list) => list
+                    .items
+                    .iter()
+                    .filter_map(|level| match level {
+                        ConstantValue::Int(level) => Some(level.value),
+                        _ => None,
+                    })
+                    .collect(),
+                ConstantValue::Int(level) => vec![level.value],
+                _ => Vec::new(),
+            },
+            None => vec![0],
+        })
+    }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
 pub struct Argument {
     pub name: ArgumentName,
     pub type_: TypeReference<Type>,
-    pub default_value: Option<ConstantValue>,
+    /// Shared by the arguments with the same default value, see
+    /// `InMemorySchema::share_default_values`.
+    pub default_value: Option<Arc<ConstantValue>>,
     pub description: Option<StringKey>,
     pub directives: Vec<DirectiveValue>,
 }
@@ -425,7 +542,17 @@ impl Named for Argument {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Hash,
+    Ord,
+    PartialOrd,
+    Serialize,
+    Deserialize
+)]
 pub struct ArgumentValue {
     pub name: ArgumentName,
     pub value: ConstantValue,
@@ -449,19 +576,47 @@ impl ArgumentValue {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Hash,
+    Ord,
+    PartialOrd,
+    Serialize,
+    Deserialize
+)]
 pub struct DirectiveValue {
     pub name: DirectiveName,
     pub arguments: Vec<ArgumentValue>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct EnumValue {
     pub value: StringKey,
+    pub location: Location,
     pub directives: Vec<DirectiveValue>,
+    /// Whether the value is only known to the client, added to the enum by
+    /// a client schema extension.
+    pub is_extension: bool,
+    pub description: Option<StringKey>,
+}
+
+impl EnumValue {
+    pub fn deprecated(&self) -> Option<Deprecation> {
+        self.directives
+            .named(*DIRECTIVE_DEPRECATED)
+            .map(|directive| Deprecation {
+                reason: directive
+                    .arguments
+                    .named(*ARGUMENT_REASON)
+                    .and_then(|reason| reason.value.get_string_literal()),
+            })
+    }
 }
 
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub struct ArgumentDefinitions(pub(crate) Vec<Argument>);
 
 impl ArgumentDefinitions {
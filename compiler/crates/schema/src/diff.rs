@@ -0,0 +1,551 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! The structural changes between two schemas: the types, fields, arguments,
+//! input fields, enum values, union members, interfaces and directives that
+//! were added, removed or changed. Descriptions, locations and the order of
+//! definitions are not compared.
+//!
+//! All lists are sorted by name.
+
+use common::DirectiveName;
+use fnv::FnvHashMap;
+use intern::string_key::StringKey;
+use intern::Lookup;
+
+use crate::SDLSchema;
+use crate::definitions::Argument;
+use crate::definitions::ArgumentDefinitions;
+use crate::definitions::Field;
+use crate::definitions::FieldID;
+use crate::definitions::Type;
+use crate::definitions::TypeReference;
+use crate::graphql_schema::Schema;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    pub added_types: Vec<StringKey>,
+    pub removed_types: Vec<StringKey>,
+    pub changed_types: Vec<TypeDiff>,
+    pub added_directives: Vec<DirectiveName>,
+    pub removed_directives: Vec<DirectiveName>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_types.is_empty()
+            && self.removed_types.is_empty()
+            && self.changed_types.is_empty()
+            && self.added_directives.is_empty()
+            && self.removed_directives.is_empty()
+    }
+}
+
+/// A type that is defined in both schemas, and changed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TypeDiff {
+    pub name: StringKey,
+    pub kind: TypeDiffKind,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TypeDiffKind {
+    /// The type is a different kind of type in the new schema, e.g. an
+    /// object that became an interface. Its members are not compared.
+    KindChanged {
+        old_kind: &'static str,
+        new_kind: &'static str,
+    },
+    Object {
+        interfaces: NamesDiff,
+        fields: FieldsDiff,
+    },
+    Interface {
+        interfaces: NamesDiff,
+        fields: FieldsDiff,
+    },
+    Union {
+        members: NamesDiff,
+    },
+    Enum {
+        values: NamesDiff,
+    },
+    InputObject {
+        fields: InputValuesDiff,
+    },
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct NamesDiff {
+    pub added: Vec<StringKey>,
+    pub removed: Vec<StringKey>,
+}
+
+impl NamesDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FieldsDiff {
+    pub added: Vec<StringKey>,
+    pub removed: Vec<StringKey>,
+    pub changed: Vec<FieldDiff>,
+}
+
+impl FieldsDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A field that is defined in both schemas, and changed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub name: StringKey,
+    /// The old and new type of the field, if it changed.
+    pub type_changed: Option<(TypeReference<StringKey>, TypeReference<StringKey>)>,
+    /// Whether the field is deprecated in the new schema only.
+    pub deprecated: bool,
+    pub arguments: InputValuesDiff,
+}
+
+/// The changes to the arguments of a field, or the fields of an input object.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct InputValuesDiff {
+    pub added: Vec<AddedInputValue>,
+    pub removed: Vec<StringKey>,
+    pub changed: Vec<InputValueDiff>,
+}
+
+impl InputValuesDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AddedInputValue {
+    pub name: StringKey,
+    pub type_: TypeReference<StringKey>,
+    pub has_default_value: bool,
+}
+
+impl AddedInputValue {
+    /// Whether documents have to pass a value for it.
+    pub fn is_required(&self) -> bool {
+        self.type_.is_non_null() && !self.has_default_value
+    }
+}
+
+/// An argument or input field that is defined in both schemas, and changed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InputValueDiff {
+    pub name: StringKey,
+    /// The old and new type of the value, if it changed.
+    pub type_changed: Option<(TypeReference<StringKey>, TypeReference<StringKey>)>,
+    pub default_value_changed: bool,
+}
+
+/// Returns the changes from `old` to `new`.
+pub fn diff(old: &SDLSchema, new: &SDLSchema) -> SchemaDiff {
+    let mut result = SchemaDiff::default();
+
+    let mut type_names: Vec<StringKey> = old
+        .get_type_map()
+        .chain(new.get_type_map())
+        .map(|(name, _)| *name)
+        .collect();
+    sort_names(&mut type_names);
+    type_names.dedup();
+    for name in type_names {
+        match (old.get_type(name), new.get_type(name)) {
+            (Some(_), None) => result.removed_types.push(name),
+            (None, Some(_)) => result.added_types.push(name),
+            (Some(old_type), Some(new_type)) => {
+                if let Some(kind) = diff_type(old, old_type, new, new_type) {
+                    result.changed_types.push(TypeDiff { name, kind });
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    let directives = diff_names(
+        old.get_directives().map(|directive| directive.name.0),
+        new.get_directives().map(|directive| directive.name.0),
+    );
+    result.added_directives = directives.added.into_iter().map(DirectiveName).collect();
+    result.removed_directives = directives.removed.into_iter().map(DirectiveName).collect();
+
+    result
+}
+
+fn diff_type(
+    old: &SDLSchema,
+    old_type: Type,
+    new: &SDLSchema,
+    new_type: Type,
+) -> Option<TypeDiffKind> {
+    let kind = match (old_type, new_type) {
+        (Type::Object(old_id), Type::Object(new_id)) => {
+            let old_object = old.object(old_id);
+            let new_object = new.object(new_id);
+            let interfaces = diff_names(
+                old_object
+                    .interfaces
+                    .iter()
+                    .map(|id| old.interface(*id).name.item.0),
+                new_object
+                    .interfaces
+                    .iter()
+                    .map(|id| new.interface(*id).name.item.0),
+            );
+            let fields = diff_fields(old, &old_object.fields, new, &new_object.fields);
+            if interfaces.is_empty() && fields.is_empty() {
+                return None;
+            }
+            TypeDiffKind::Object { interfaces, fields }
+        }
+        (Type::Interface(old_id), Type::Interface(new_id)) => {
+            let old_interface = old.interface(old_id);
+            let new_interface = new.interface(new_id);
+            let interfaces = diff_names(
+                old_interface
+                    .interfaces
+                    .iter()
+                    .map(|id| old.interface(*id).name.item.0),
+                new_interface
+                    .interfaces
+                    .iter()
+                    .map(|id| new.interface(*id).name.item.0),
+            );
+            let fields = diff_fields(old, &old_interface.fields, new, &new_interface.fields);
+            if interfaces.is_empty() && fields.is_empty() {
+                return None;
+            }
+            TypeDiffKind::Interface { interfaces, fields }
+        }
+        (Type::Union(old_id), Type::Union(new_id)) => {
+            let members = diff_names(
+                old.union(old_id)
+                    .members
+                    .iter()
+                    .map(|id| old.object(*id).name.item.0),
+                new.union(new_id)
+                    .members
+                    .iter()
+                    .map(|id| new.object(*id).name.item.0),
+            );
+            if members.is_empty() {
+                return None;
+            }
+            TypeDiffKind::Union { members }
+        }
+        (Type::Enum(old_id), Type::Enum(new_id)) => {
+            let values = diff_names(
+                old.enum_(old_id).values.iter().map(|value| value.value),
+                new.enum_(new_id).values.iter().map(|value| value.value),
+            );
+            if values.is_empty() {
+                return None;
+            }
+            TypeDiffKind::Enum { values }
+        }
+        (Type::InputObject(old_id), Type::InputObject(new_id)) => {
+            let fields = diff_input_values(
+                old,
+                &old.input_object(old_id).fields,
+                new,
+                &new.input_object(new_id).fields,
+            );
+            if fields.is_empty() {
+                return None;
+            }
+            TypeDiffKind::InputObject { fields }
+        }
+        (Type::Scalar(_), Type::Scalar(_)) => return None,
+        _ => TypeDiffKind::KindChanged {
+            old_kind: old_type.get_variant_name(),
+            new_kind: new_type.get_variant_name(),
+        },
+    };
+    Some(kind)
+}
+
+fn diff_names(
+    old_names: impl Iterator<Item = StringKey>,
+    new_names: impl Iterator<Item = StringKey>,
+) -> NamesDiff {
+    let mut old_names: Vec<StringKey> = old_names.collect();
+    let mut new_names: Vec<StringKey> = new_names.collect();
+    sort_names(&mut old_names);
+    sort_names(&mut new_names);
+    NamesDiff {
+        added: new_names
+            .iter()
+            .filter(|name| !old_names.contains(name))
+            .copied()
+            .collect(),
+        removed: old_names
+            .iter()
+            .filter(|name| !new_names.contains(name))
+            .copied()
+            .collect(),
+    }
+}
+
+fn diff_fields(
+    old: &SDLSchema,
+    old_fields: &[FieldID],
+    new: &SDLSchema,
+    new_fields: &[FieldID],
+) -> FieldsDiff {
+    let old_fields: FnvHashMap<StringKey, &Field> = old_fields
+        .iter()
+        .map(|id| {
+            let field = old.field(*id);
+            (field.name.item, field)
+        })
+        .collect();
+    let mut new_fields: Vec<&Field> = new_fields.iter().map(|id| new.field(*id)).collect();
+    new_fields.sort_by_key(|field| field.name.item.lookup());
+
+    let mut result = FieldsDiff::default();
+    for new_field in &new_fields {
+        let name = new_field.name.item;
+        let old_field = match old_fields.get(&name) {
+            Some(old_field) => old_field,
+            None => {
+                result.added.push(name);
+                continue;
+            }
+        };
+        let old_type = type_name_reference(old, &old_field.type_);
+        let new_type = type_name_reference(new, &new_field.type_);
+        let field = FieldDiff {
+            name,
+            type_changed: if old_type != new_type {
+                Some((old_type, new_type))
+            } else {
+                None
+            },
+            deprecated: old_field.deprecated().is_none() && new_field.deprecated().is_some(),
+            arguments: diff_input_values(old, &old_field.arguments, new, &new_field.arguments),
+        };
+        if field.type_changed.is_some() || field.deprecated || !field.arguments.is_empty() {
+            result.changed.push(field);
+        }
+    }
+
+    result.removed = old_fields
+        .keys()
+        .filter(|name| !new_fields.iter().any(|field| field.name.item == **name))
+        .copied()
+        .collect();
+    sort_names(&mut result.removed);
+    result
+}
+
+fn diff_input_values(
+    old: &SDLSchema,
+    old_values: &ArgumentDefinitions,
+    new: &SDLSchema,
+    new_values: &ArgumentDefinitions,
+) -> InputValuesDiff {
+    let mut new_list: Vec<&Argument> = new_values.iter().collect();
+    new_list.sort_by_key(|value| value.name.0.lookup());
+
+    let mut result = InputValuesDiff::default();
+    for new_value in new_list {
+        let name = new_value.name.0;
+        let old_value = match old_values.named(new_value.name) {
+            Some(old_value) => old_value,
+            None => {
+                result.added.push(AddedInputValue {
+                    name,
+                    type_: type_name_reference(new, &new_value.type_),
+                    has_default_value: new_value.default_value.is_some(),
+                });
+                continue;
+            }
+        };
+        let old_type = type_name_reference(old, &old_value.type_);
+        let new_type = type_name_reference(new, &new_value.type_);
+        let value = InputValueDiff {
+            name,
+            type_changed: if old_type != new_type {
+                Some((old_type, new_type))
+            } else {
+                None
+            },
+            default_value_changed: old_value.default_value != new_value.default_value,
+        };
+        if value.type_changed.is_some() || value.default_value_changed {
+            result.changed.push(value);
+        }
+    }
+
+    result.removed = old_values
+        .iter()
+        .filter(|value| !new_values.contains(value.name.0))
+        .map(|value| value.name.0)
+        .collect();
+    sort_names(&mut result.removed);
+    result
+}
+
+/// The type reference with the names of the types, which can be compared
+/// across schemas.
+fn type_name_reference(
+    schema: &SDLSchema,
+    type_: &TypeReference<Type>,
+) -> TypeReference<StringKey> {
+    type_.clone().map(|type_| schema.get_type_name(type_))
+}
+
+fn sort_names(names: &mut [StringKey]) {
+    names.sort_by_key(|name| name.lookup());
+}
+
+#[cfg(test)]
+mod tests {
+    use intern::string_key::Intern;
+
+    use super::*;
+    use crate::build_schema;
+
+    fn diff_sdl(old: &str, new: &str) -> SchemaDiff {
+        diff(&build_schema(old).unwrap(), &build_schema(new).unwrap())
+    }
+
+    #[test]
+    fn same_schema_has_no_changes() {
+        let sdl = "type Query { user(id: ID!): User } type User { name: String }";
+        assert!(diff_sdl(sdl, sdl).is_empty());
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed_types() {
+        let diff = diff_sdl(
+            "type Query { a: A } type A { id: ID } scalar Removed union U = A",
+            "type Query { a: A } type A { id: ID } enum Added { X } interface U { id: ID }",
+        );
+        assert_eq!(diff.added_types, vec!["Added".intern()]);
+        assert_eq!(diff.removed_types, vec!["Removed".intern()]);
+        assert_eq!(
+            diff.changed_types,
+            vec![TypeDiff {
+                name: "U".intern(),
+                kind: TypeDiffKind::KindChanged {
+                    old_kind: "a union",
+                    new_kind: "an interface",
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_field_and_argument_changes() {
+        let diff = diff_sdl(
+            "type Query { a(x: Int, y: Int = 1, gone: Int): String, b: Int, c: Int }",
+            "type Query { a(x: Int!, y: Int = 2, z: ID!): String!, b: Int @deprecated, d: Int }",
+        );
+        assert_eq!(
+            diff.changed_types,
+            vec![TypeDiff {
+                name: "Query".intern(),
+                kind: TypeDiffKind::Object {
+                    interfaces: NamesDiff::default(),
+                    fields: FieldsDiff {
+                        added: vec!["d".intern()],
+                        removed: vec!["c".intern()],
+                        changed: vec![
+                            FieldDiff {
+                                name: "a".intern(),
+                                type_changed: Some((
+                                    TypeReference::Named("String".intern()),
+                                    TypeReference::NonNull(Box::new(TypeReference::Named(
+                                        "String".intern()
+                                    ))),
+                                )),
+                                deprecated: false,
+                                arguments: InputValuesDiff {
+                                    added: vec![AddedInputValue {
+                                        name: "z".intern(),
+                                        type_: TypeReference::NonNull(Box::new(
+                                            TypeReference::Named("ID".intern())
+                                        )),
+                                        has_default_value: false,
+                                    }],
+                                    removed: vec!["gone".intern()],
+                                    changed: vec![
+                                        InputValueDiff {
+                                            name: "x".intern(),
+                                            type_changed: Some((
+                                                TypeReference::Named("Int".intern()),
+                                                TypeReference::NonNull(Box::new(
+                                                    TypeReference::Named("Int".intern())
+                                                )),
+                                            )),
+                                            default_value_changed: false,
+                                        },
+                                        InputValueDiff {
+                                            name: "y".intern(),
+                                            type_changed: None,
+                                            default_value_changed: true,
+                                        },
+                                    ],
+                                },
+                            },
+                            FieldDiff {
+                                name: "b".intern(),
+                                type_changed: None,
+                                deprecated: true,
+                                arguments: InputValuesDiff::default(),
+                            },
+                        ],
+                    },
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_members_values_and_directives() {
+        let diff = diff_sdl(
+            "directive @old on FIELD type Query { a: Int } type A { id: ID } type B { id: ID } union U = A enum E { X Y }",
+            "directive @new on FIELD type Query { a: Int } type A { id: ID } type B { id: ID } union U = A | B enum E { Y Z }",
+        );
+        assert_eq!(diff.added_directives, vec![DirectiveName("new".intern())]);
+        assert_eq!(diff.removed_directives, vec![DirectiveName("old".intern())]);
+        assert_eq!(
+            diff.changed_types,
+            vec![
+                TypeDiff {
+                    name: "E".intern(),
+                    kind: TypeDiffKind::Enum {
+                        values: NamesDiff {
+                            added: vec!["Z".intern()],
+                            removed: vec!["X".intern()],
+                        },
+                    },
+                },
+                TypeDiff {
+                    name: "U".intern(),
+                    kind: TypeDiffKind::Union {
+                        members: NamesDiff {
+                            added: vec!["B".intern()],
+                            removed: vec![],
+                        },
+                    },
+                },
+            ]
+        );
+    }
+}
@@ -5,6 +5,7 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use common::SchemaCoordinate;
 use graphql_syntax::OperationType;
 use intern::string_key::StringKey;
 use thiserror::Error;
@@ -24,6 +25,11 @@ pub enum SchemaError {
     #[error("Cannot extend type '{0}', the type is not defined on the server schema.")]
     ExtendUndefinedType(StringKey),
 
+    #[error(
+        "Cannot add server type '{1}' to union '{0}' in a client schema extension, only client types can be added to server unions."
+    )]
+    ExtendUnionWithServerType(StringKey, StringKey),
+
     #[error("Expected an object type for name '{0}', got '{1:?}'.")]
     ExpectedObjectReference(StringKey, Type),
 
@@ -39,6 +45,15 @@ pub enum SchemaError {
     #[error("Duplicate definition for type '{0}'.")]
     DuplicateType(StringKey),
 
+    #[error("Conflicting definitions for type '{0}': {1}.")]
+    ConflictingTypeDefinitions(StringKey, String),
+
+    #[error("Conflicting definitions for field '{0}.{1}': {2}.")]
+    ConflictingFieldDefinitions(StringKey, StringKey, String),
+
+    #[error("Duplicate value '{1}' in enum '{0}'.")]
+    DuplicateEnumValue(StringKey, StringKey),
+
     #[error("Invalid ID '{0}' provided for type '{1}'")]
     UnknownTypeID(usize, String),
 
@@ -51,6 +66,25 @@ pub enum SchemaError {
     )]
     ExpectedInputType(StringKey),
 
+    #[error("Invalid type '{0}', expected a type like 'User', '[User]' or 'User!'.")]
+    InvalidTypeNotation(String),
+
+    #[error(
+        "Field '{0}.{1}' has no level {2} to mark with @semanticNonNull, its type has {3} levels."
+    )]
+    InvalidSemanticNonNullLevel(StringKey, StringKey, i64, usize),
+
+    #[error(
+        "Field '{0}.{1}' is already non-null at level {2}, only nullable levels can be marked with @semanticNonNull."
+    )]
+    SemanticNonNullOnNonNullType(StringKey, StringKey, i64),
+
     #[error("Reference to undefined directive '{0}'.")]
     UndefinedDirective(StringKey),
+
+    #[error("Type '{0}' has no field or value named '{1}'.")]
+    UndefinedMember(StringKey, StringKey),
+
+    #[error("'{0}' has no argument named '{1}'.")]
+    UndefinedArgument(SchemaCoordinate, StringKey),
 }
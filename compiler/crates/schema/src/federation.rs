@@ -0,0 +1,540 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Support for Apollo Federation subgraph schemas.
+//!
+//! Subgraph SDL uses federation directives (`@key`, `@external`, ...) and
+//! types (`_Any`, `_Entity`, `_FieldSet`, ...) that are meaningless for
+//! clients, and a type may be defined (or extended) by several subgraphs.
+//! `compose_subgraphs` turns the subgraph documents into a single client
+//! schema document:
+//!
+//! - federation directives and types are removed,
+//! - definitions and extensions of the same type are merged into a single
+//!   definition (input objects only keep the fields that all definitions
+//!   share),
+//! - types, fields, enum values and input fields marked `@inaccessible` are
+//!   removed.
+
+use common::DiagnosticsResult;
+use common::Location;
+use common::SourceLocationKey;
+use fnv::FnvHashMap;
+use fnv::FnvHashSet;
+use graphql_syntax::*;
+use intern::string_key::StringKey;
+use intern::Lookup;
+
+const FEDERATION_DIRECTIVES: [&str; 19] = [
+    "authenticated",
+    "composeDirective",
+    "context",
+    "cost",
+    "extends",
+    "external",
+    "fromContext",
+    "inaccessible",
+    "interfaceObject",
+    "key",
+    "link",
+    "listSize",
+    "override",
+    "policy",
+    "provides",
+    "requires",
+    "requiresScopes",
+    "shareable",
+    "tag",
+];
+
+const FEDERATION_TYPES: [&str; 5] = ["_Any", "_Entity", "_FieldSet", "_Service", "FieldSet"];
+
+/// Namespaces of the definitions imported with `@link`, and those that
+/// appear in supergraph SDL.
+const FEDERATION_PREFIXES: [&str; 3] = ["federation__", "join__", "link__"];
+
+fn has_federation_prefix(name: &str) -> bool {
+    FEDERATION_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+fn is_federation_directive(name: StringKey) -> bool {
+    let name = name.lookup();
+    FEDERATION_DIRECTIVES.contains(&name) || has_federation_prefix(name)
+}
+
+fn is_federation_type(name: StringKey) -> bool {
+    let name = name.lookup();
+    FEDERATION_TYPES.contains(&name) || has_federation_prefix(name)
+}
+
+fn is_inaccessible(directives: &[ConstantDirective]) -> bool {
+    directives.iter().any(|directive| {
+        let name = directive.name.value.lookup();
+        name == "inaccessible" || name == "federation__inaccessible"
+    })
+}
+
+fn strip_federation_directives(directives: &mut Vec<ConstantDirective>) {
+    directives.retain(|directive| !is_federation_directive(directive.name.value));
+}
+
+/// Merges the documents of one or more subgraphs into a single client schema
+/// document. See the module documentation for details.
+pub fn compose_subgraphs(documents: Vec<SchemaDocument>) -> SchemaDocument {
+    let location = match documents.as_slice() {
+        [document] => document.location,
+        _ => Location::generated(),
+    };
+    let mut composer = SubgraphComposer::default();
+    for document in documents {
+        for definition in document.definitions {
+            composer.add_definition(definition);
+        }
+    }
+    SchemaDocument {
+        location,
+        definitions: composer.finish(),
+    }
+}
+
+/// Parses each subgraph SDL separately and composes them with
+/// `compose_subgraphs`.
+pub(crate) fn parse_subgraphs<T: AsRef<str>>(
+    subgraph_sdls: &[(T, SourceLocationKey)],
+) -> DiagnosticsResult<SchemaDocument> {
+    let documents = subgraph_sdls
+        .iter()
+        .map(|(sdl, location_key)| {
+            graphql_syntax::parse_schema_document(sdl.as_ref(), *location_key)
+        })
+        .collect::<DiagnosticsResult<Vec<_>>>()?;
+    Ok(compose_subgraphs(documents))
+}
+
+enum TypeEntry {
+    Removed,
+    Vacant,
+    Occupied(usize),
+}
+
+#[derive(Default)]
+struct SubgraphComposer {
+    operation_types: Vec<OperationTypeDefinition>,
+    directives: Vec<DirectiveDefinition>,
+    directive_names: FnvHashSet<StringKey>,
+    types: Vec<TypeSystemDefinition>,
+    type_indices: FnvHashMap<StringKey, usize>,
+    /// Types that don't end up in the client schema. References to them are
+    /// removed as well.
+    removed_types: FnvHashSet<StringKey>,
+}
+
+impl SubgraphComposer {
+    fn add_definition(&mut self, definition: TypeSystemDefinition) {
+        match definition {
+            TypeSystemDefinition::SchemaDefinition(SchemaDefinition {
+                operation_types, ..
+            }) => self.add_operation_types(operation_types.items),
+            TypeSystemDefinition::SchemaExtension(SchemaExtension {
+                operation_types, ..
+            }) => {
+                if let Some(operation_types) = operation_types {
+                    self.add_operation_types(operation_types.items)
+                }
+            }
+            TypeSystemDefinition::DirectiveDefinition(directive) => {
+                if !is_federation_directive(directive.name.value)
+                    && self.directive_names.insert(directive.name.value)
+                {
+                    self.directives.push(directive);
+                }
+            }
+            TypeSystemDefinition::ObjectTypeDefinition(definition) => {
+                self.add_object(definition);
+            }
+            TypeSystemDefinition::ObjectTypeExtension(ObjectTypeExtension {
+                name,
+                interfaces,
+                directives,
+                fields,
+            }) => self.add_object(ObjectTypeDefinition {
+                name,
+                interfaces,
+                directives,
+                fields,
+                description: None,
+            }),
+            TypeSystemDefinition::InterfaceTypeDefinition(definition) => {
+                self.add_interface(definition);
+            }
+            TypeSystemDefinition::InterfaceTypeExtension(InterfaceTypeExtension {
+                name,
+                interfaces,
+                directives,
+                fields,
+            }) => self.add_interface(InterfaceTypeDefinition {
+                name,
+                interfaces,
+                directives,
+                fields,
+                description: None,
+            }),
+            TypeSystemDefinition::UnionTypeDefinition(definition) => self.add_union(definition),
+            TypeSystemDefinition::UnionTypeExtension(UnionTypeExtension {
+                name,
+                directives,
+                members,
+            }) => self.add_union(UnionTypeDefinition {
+                name,
+                directives,
+                members,
+                description: None,
+            }),
+            TypeSystemDefinition::EnumTypeDefinition(definition) => self.add_enum(definition),
+            TypeSystemDefinition::EnumTypeExtension(EnumTypeExtension {
+                name,
+                directives,
+                values,
+            }) => self.add_enum(EnumTypeDefinition {
+                name,
+                directives,
+                values,
+                description: None,
+            }),
+            TypeSystemDefinition::InputObjectTypeDefinition(definition) => {
+                self.add_input_object(definition, false)
+            }
+            TypeSystemDefinition::InputObjectTypeExtension(InputObjectTypeExtension {
+                name,
+                directives,
+                fields,
+            }) => self.add_input_object(
+                InputObjectTypeDefinition {
+                    name,
+                    directives,
+                    fields,
+                    description: None,
+                },
+                true,
+            ),
+            TypeSystemDefinition::ScalarTypeDefinition(definition) => self.add_scalar(definition),
+            TypeSystemDefinition::ScalarTypeExtension(ScalarTypeExtension { name, directives }) => {
+                self.add_scalar(ScalarTypeDefinition {
+                    name,
+                    directives,
+                    description: None,
+                })
+            }
+        }
+    }
+
+    fn add_operation_types(&mut self, operation_types: Vec<OperationTypeDefinition>) {
+        for operation_type in operation_types {
+            if !self
+                .operation_types
+                .iter()
+                .any(|existing| existing.operation == operation_type.operation)
+            {
+                self.operation_types.push(operation_type);
+            }
+        }
+    }
+
+    /// Federation and `@inaccessible` types are dropped, all other
+    /// definitions and extensions of a type are merged into the first one.
+    fn type_entry(&mut self, name: StringKey, directives: &[ConstantDirective]) -> TypeEntry {
+        if is_federation_type(name) || is_inaccessible(directives) {
+            self.removed_types.insert(name);
+            return TypeEntry::Removed;
+        }
+        match self.type_indices.get(&name) {
+            Some(index) => TypeEntry::Occupied(*index),
+            None => {
+                self.type_indices.insert(name, self.types.len());
+                TypeEntry::Vacant
+            }
+        }
+    }
+
+    fn add_object(&mut self, definition: ObjectTypeDefinition) {
+        match self.type_entry(definition.name.value, &definition.directives) {
+            TypeEntry::Removed => {}
+            TypeEntry::Vacant => self
+                .types
+                .push(TypeSystemDefinition::ObjectTypeDefinition(definition)),
+            TypeEntry::Occupied(index) => {
+                if let TypeSystemDefinition::ObjectTypeDefinition(existing) = &mut self.types[index]
+                {
+                    merge_identifiers(&mut existing.interfaces, definition.interfaces);
+                    merge_directives(&mut existing.directives, definition.directives);
+                    merge_list(&mut existing.fields, definition.fields, |field| {
+                        field.name.value
+                    });
+                }
+            }
+        }
+    }
+
+    fn add_interface(&mut self, definition: InterfaceTypeDefinition) {
+        match self.type_entry(definition.name.value, &definition.directives) {
+            TypeEntry::Removed => {}
+            TypeEntry::Vacant => self
+                .types
+                .push(TypeSystemDefinition::InterfaceTypeDefinition(definition)),
+            TypeEntry::Occupied(index) => {
+                if let TypeSystemDefinition::InterfaceTypeDefinition(existing) =
+                    &mut self.types[index]
+                {
+                    merge_identifiers(&mut existing.interfaces, definition.interfaces);
+                    merge_directives(&mut existing.directives, definition.directives);
+                    merge_list(&mut existing.fields, definition.fields, |field| {
+                        field.name.value
+                    });
+                }
+            }
+        }
+    }
+
+    fn add_union(&mut self, definition: UnionTypeDefinition) {
+        match self.type_entry(definition.name.value, &definition.directives) {
+            TypeEntry::Removed => {}
+            TypeEntry::Vacant => self
+                .types
+                .push(TypeSystemDefinition::UnionTypeDefinition(definition)),
+            TypeEntry::Occupied(index) => {
+                if let TypeSystemDefinition::UnionTypeDefinition(existing) = &mut self.types[index]
+                {
+                    merge_directives(&mut existing.directives, definition.directives);
+                    merge_identifiers(&mut existing.members, definition.members);
+                }
+            }
+        }
+    }
+
+    fn add_enum(&mut self, definition: EnumTypeDefinition) {
+        match self.type_entry(definition.name.value, &definition.directives) {
+            TypeEntry::Removed => {}
+            TypeEntry::Vacant => self
+                .types
+                .push(TypeSystemDefinition::EnumTypeDefinition(definition)),
+            TypeEntry::Occupied(index) => {
+                if let TypeSystemDefinition::EnumTypeDefinition(existing) = &mut self.types[index] {
+                    merge_directives(&mut existing.directives, definition.directives);
+                    merge_list(&mut existing.values, definition.values, |value| {
+                        value.name.value
+                    });
+                }
+            }
+        }
+    }
+
+    fn add_input_object(&mut self, definition: InputObjectTypeDefinition, is_extension: bool) {
+        match self.type_entry(definition.name.value, &definition.directives) {
+            TypeEntry::Removed => {}
+            TypeEntry::Vacant => self
+                .types
+                .push(TypeSystemDefinition::InputObjectTypeDefinition(definition)),
+            TypeEntry::Occupied(index) => {
+                if let TypeSystemDefinition::InputObjectTypeDefinition(existing) =
+                    &mut self.types[index]
+                {
+                    merge_directives(&mut existing.directives, definition.directives);
+                    if is_extension {
+                        merge_list(&mut existing.fields, definition.fields, |field| {
+                            field.name.value
+                        });
+                    } else if let Some(existing_fields) = &mut existing.fields {
+                        // Every subgraph must accept the fields of an input
+                        // object, so only the fields that all subgraphs
+                        // define are kept.
+                        let field_names = definition
+                            .fields
+                            .iter()
+                            .flat_map(|fields| &fields.items)
+                            .map(|field| field.name.value)
+                            .collect::<FnvHashSet<_>>();
+                        existing_fields
+                            .items
+                            .retain(|field| field_names.contains(&field.name.value));
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_scalar(&mut self, definition: ScalarTypeDefinition) {
+        match self.type_entry(definition.name.value, &definition.directives) {
+            TypeEntry::Removed => {}
+            TypeEntry::Vacant => self
+                .types
+                .push(TypeSystemDefinition::ScalarTypeDefinition(definition)),
+            TypeEntry::Occupied(index) => {
+                if let TypeSystemDefinition::ScalarTypeDefinition(existing) = &mut self.types[index]
+                {
+                    merge_directives(&mut existing.directives, definition.directives);
+                }
+            }
+        }
+    }
+
+    fn finish(self) -> Vec<TypeSystemDefinition> {
+        let removed_types = self.removed_types;
+        // Federation types are removed even if a subgraph only references
+        // them, e.g. `_FieldSet` in arguments of `@key`.
+        let is_removed_type =
+            |name: &StringKey| removed_types.contains(name) || is_federation_type(*name);
+        let is_removed = |type_: &TypeAnnotation| is_removed_type(&type_.inner().name.value);
+        let is_field_removed = |field: &FieldDefinition| {
+            is_inaccessible(&field.directives)
+                || is_removed(&field.type_)
+                || field
+                    .arguments
+                    .iter()
+                    .flat_map(|arguments| &arguments.items)
+                    .any(|argument| {
+                        is_removed(&argument.type_)
+                            && matches!(argument.type_, TypeAnnotation::NonNull(_))
+                    })
+        };
+        let clean_fields = |fields: &mut Option<List<FieldDefinition>>| {
+            if let Some(fields) = fields {
+                fields.items.retain(|field| !is_field_removed(field));
+                for field in &mut fields.items {
+                    strip_federation_directives(&mut field.directives);
+                    clean_input_values(&mut field.arguments, &is_removed);
+                }
+            }
+        };
+
+        let mut definitions = Vec::with_capacity(self.types.len() + self.directives.len() + 1);
+        if !self.operation_types.is_empty() {
+            definitions.push(TypeSystemDefinition::SchemaDefinition(SchemaDefinition {
+                directives: vec![],
+                operation_types: List::generated(self.operation_types),
+            }));
+        }
+        for mut directive in self.directives {
+            clean_input_values(&mut directive.arguments, &is_removed);
+            definitions.push(TypeSystemDefinition::DirectiveDefinition(directive));
+        }
+        for mut definition in self.types {
+            match &mut definition {
+                TypeSystemDefinition::ObjectTypeDefinition(ObjectTypeDefinition {
+                    interfaces,
+                    directives,
+                    fields,
+                    ..
+                })
+                | TypeSystemDefinition::InterfaceTypeDefinition(InterfaceTypeDefinition {
+                    interfaces,
+                    directives,
+                    fields,
+                    ..
+                }) => {
+                    interfaces.retain(|interface| !is_removed_type(&interface.value));
+                    strip_federation_directives(directives);
+                    clean_fields(fields);
+                }
+                TypeSystemDefinition::UnionTypeDefinition(UnionTypeDefinition {
+                    directives,
+                    members,
+                    ..
+                }) => {
+                    members.retain(|member| !is_removed_type(&member.value));
+                    strip_federation_directives(directives);
+                }
+                TypeSystemDefinition::EnumTypeDefinition(EnumTypeDefinition {
+                    directives,
+                    values,
+                    ..
+                }) => {
+                    strip_federation_directives(directives);
+                    if let Some(values) = values {
+                        values
+                            .items
+                            .retain(|value| !is_inaccessible(&value.directives));
+                        for value in &mut values.items {
+                            strip_federation_directives(&mut value.directives);
+                        }
+                    }
+                }
+                TypeSystemDefinition::InputObjectTypeDefinition(InputObjectTypeDefinition {
+                    directives,
+                    fields,
+                    ..
+                }) => {
+                    strip_federation_directives(directives);
+                    clean_input_values(fields, &is_removed);
+                }
+                TypeSystemDefinition::ScalarTypeDefinition(ScalarTypeDefinition {
+                    directives,
+                    ..
+                }) => strip_federation_directives(directives),
+                _ => {}
+            }
+            definitions.push(definition);
+        }
+        definitions
+    }
+}
+
+fn clean_input_values(
+    input_values: &mut Option<List<InputValueDefinition>>,
+    is_removed: &impl Fn(&TypeAnnotation) -> bool,
+) {
+    if let Some(input_values) = input_values {
+        input_values.items.retain(|input_value| {
+            !is_inaccessible(&input_value.directives) && !is_removed(&input_value.type_)
+        });
+        for input_value in &mut input_values.items {
+            strip_federation_directives(&mut input_value.directives);
+        }
+    }
+}
+
+fn merge_identifiers(existing: &mut Vec<Identifier>, new: Vec<Identifier>) {
+    for identifier in new {
+        if !existing.iter().any(|item| item.value == identifier.value) {
+            existing.push(identifier);
+        }
+    }
+}
+
+fn merge_directives(existing: &mut Vec<ConstantDirective>, new: Vec<ConstantDirective>) {
+    for directive in new {
+        if !existing
+            .iter()
+            .any(|item| item.name.value == directive.name.value)
+        {
+            existing.push(directive);
+        }
+    }
+}
+
+fn merge_list<T>(
+    existing: &mut Option<List<T>>,
+    new: Option<List<T>>,
+    key: impl Fn(&T) -> StringKey,
+) {
+    match (existing, new) {
+        (Some(existing), Some(new)) => {
+            for item in new.items {
+                if !existing
+                    .items
+                    .iter()
+                    .any(|existing_item| key(existing_item) == key(&item))
+                {
+                    existing.items.push(item);
+                }
+            }
+        }
+        (existing @ None, new) => *existing = new,
+        (Some(_), None) => {}
+    }
+}
@@ -9,12 +9,14 @@ mod serialize;
 mod wrapper;
 
 use std::cmp::Ordering;
+use std::sync::Arc;
 
 use common::ArgumentName;
 use common::DirectiveName;
 use common::EnumName;
 use common::InputObjectName;
 use common::InterfaceName;
+use common::Location;
 use common::ObjectName;
 use common::ScalarName;
 use common::Span;
@@ -170,11 +172,12 @@ impl<'fb> FlatBufferSchema<'fb> {
             .collect::<Vec<_>>();
         let parsed_directive = Directive {
             name: DirectiveName(directive.name()?.intern()),
+            location: Location::generated(),
             is_extension: directive.is_extension(),
             arguments: self.parse_arguments(directive.arguments()?)?,
             locations,
             repeatable: directive.repeatable(),
-            description: None,
+            description: parse_description(directive.description()),
             hack_source: None,
         };
         Some(parsed_directive)
@@ -223,7 +226,7 @@ impl<'fb> FlatBufferSchema<'fb> {
             name: WithLocation::generated(name),
             is_extension: scalar.is_extension(),
             directives: self.parse_directive_values(scalar.directives()?)?,
-            description: None,
+            description: parse_description(scalar.description()),
             hack_source: None,
         };
         Some(parsed_scalar)
@@ -236,7 +239,7 @@ impl<'fb> FlatBufferSchema<'fb> {
             name: WithLocation::generated(name),
             fields: self.parse_arguments(input_object.fields()?)?,
             directives: self.parse_directive_values(input_object.directives()?)?,
-            description: None,
+            description: parse_description(input_object.description()),
             hack_source: None,
         };
         Some(parsed_input_object)
@@ -250,7 +253,7 @@ impl<'fb> FlatBufferSchema<'fb> {
             is_extension: enum_.is_extension(),
             values: self.parse_enum_values(enum_.values()?)?,
             directives: self.parse_directive_values(enum_.directives()?)?,
-            description: None,
+            description: parse_description(enum_.description()),
             hack_source: None,
         };
         Some(parsed_enum)
@@ -265,7 +268,7 @@ impl<'fb> FlatBufferSchema<'fb> {
             fields: object.fields()?.iter().map(FieldID).collect(),
             interfaces: object.interfaces()?.iter().map(InterfaceID).collect(),
             directives: self.parse_directive_values(object.directives()?)?,
-            description: None,
+            description: parse_description(object.description()),
             hack_source: None,
         };
         Some(parsed_object)
@@ -283,7 +286,7 @@ impl<'fb> FlatBufferSchema<'fb> {
             fields: wrap_ids(interface.fields(), FieldID),
             directives: self.parse_directive_values(interface.directives()?)?,
             interfaces: wrap_ids(interface.interfaces(), InterfaceID),
-            description: None,
+            description: parse_description(interface.description()),
             hack_source: None,
         };
         Some(parsed_interface)
@@ -297,7 +300,7 @@ impl<'fb> FlatBufferSchema<'fb> {
             is_extension: union.is_extension(),
             members: wrap_ids(union.members(), ObjectID),
             directives: self.parse_directive_values(union.directives()?)?,
-            description: None,
+            description: parse_description(union.description()),
             hack_source: None,
         };
         Some(parsed_union)
@@ -312,7 +315,7 @@ impl<'fb> FlatBufferSchema<'fb> {
             type_: self.parse_type_reference(field.type_()?)?,
             directives: self.parse_directive_values(field.directives()?)?,
             parent_type: self.get_type(self.get_fbtype_name(&field.parent_type()?)),
-            description: None,
+            description: parse_description(field.description()),
             hack_source: None,
         };
         Some(parsed_field)
@@ -332,7 +335,10 @@ impl<'fb> FlatBufferSchema<'fb> {
         let directives = self.parse_directive_values(value.directives()?)?;
         Some(EnumValue {
             value: value.value()?.intern(),
+            location: Location::generated(),
             directives,
+            is_extension: value.is_extension(),
+            description: parse_description(value.description()),
         })
     }
 
@@ -351,12 +357,12 @@ impl<'fb> FlatBufferSchema<'fb> {
         Some(Argument {
             name: ArgumentName(argument.name().unwrap().intern()),
             default_value: match argument.value() {
-                Some(value) => Some(self.parse_const_value(value)?),
+                Some(value) => Some(Arc::new(self.parse_const_value(value)?)),
                 _ => None,
             },
             type_: self.parse_type_reference(argument.type_()?)?,
-            description: None,
-            directives: Default::default(),
+            description: parse_description(argument.description()),
+            directives: self.parse_directive_values(argument.directives()?)?,
         })
     }
 
@@ -565,6 +571,10 @@ fn get_empty_span() -> Span {
     Span { start: 0, end: 0 }
 }
 
+fn parse_description(description: Option<&str>) -> Option<StringKey> {
+    description.map(|description| description.intern())
+}
+
 fn wrap_ids<T>(ids: Option<Vector<'_, u32>>, f: impl Fn(u32) -> T) -> Vec<T> {
     ids.map_or_else(Vec::new, |ids| ids.into_iter().map(f).collect())
 }
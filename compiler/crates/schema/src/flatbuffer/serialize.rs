@@ -157,6 +157,7 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
             arguments: Some(self.bldr.create_vector(arguments)),
             locations: Some(self.bldr.create_vector(locations)),
             repeatable: directive.repeatable,
+            description: self.serialize_description(directive.description),
         };
         let fb_directive = schema_flatbuffer::Directive::create(&mut self.bldr, &args);
 
@@ -201,6 +202,7 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
             name: Some(self.bldr.create_string(name.item.lookup())),
             is_extension: scalar.is_extension,
             directives: Some(self.bldr.create_vector(directives)),
+            description: self.serialize_description(scalar.description),
         };
         self.add_to_type_map(
             self.scalars.len(),
@@ -228,6 +230,7 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
             name: Some(self.bldr.create_string(name.item.0.lookup())),
             directives: Some(self.bldr.create_vector(items)),
             fields: Some(self.bldr.create_vector(fields)),
+            description: self.serialize_description(input_object.description),
         };
         self.input_objects[idx] = schema_flatbuffer::InputObject::create(&mut self.bldr, &args);
     }
@@ -242,6 +245,7 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
             is_extension: enum_.is_extension,
             directives: Some(self.bldr.create_vector(directives)),
             values: Some(self.bldr.create_vector(values)),
+            description: self.serialize_description(enum_.description),
         };
         self.add_to_type_map(
             self.enums.len(),
@@ -282,6 +286,7 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
             directives: Some(self.bldr.create_vector(directives)),
             fields: Some(self.bldr.create_vector(fields)),
             interfaces: Some(self.bldr.create_vector(interfaces)),
+            description: self.serialize_description(object.description),
         };
         self.objects[idx] = schema_flatbuffer::Object::create(&mut self.bldr, &args);
     }
@@ -331,6 +336,7 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
             interfaces: Some(self.bldr.create_vector(interfaces)),
             implementing_interfaces: Some(self.bldr.create_vector(implementing_interfaces)),
             implementing_objects: Some(self.bldr.create_vector(implementing_objects)),
+            description: self.serialize_description(interface.description),
         };
         self.interfaces[idx] = schema_flatbuffer::Interface::create(&mut self.bldr, &args);
     }
@@ -356,6 +362,7 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
             is_extension: union.is_extension,
             members: Some(self.bldr.create_vector(members)),
             directives: Some(self.bldr.create_vector(directives)),
+            description: self.serialize_description(union.description),
         };
         self.unions[idx] = schema_flatbuffer::Union::create(&mut self.bldr, &args);
     }
@@ -378,6 +385,7 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
                 }
                 _ => None,
             },
+            description: self.serialize_description(field.description),
         };
         self.fields
             .push(schema_flatbuffer::Field::create(&mut self.bldr, &args));
@@ -402,6 +410,8 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
         let args = schema_flatbuffer::EnumValueArgs {
             value: Some(self.bldr.create_string(value.value.lookup())),
             directives: Some(self.bldr.create_vector(directives)),
+            is_extension: value.is_extension,
+            description: self.serialize_description(value.description),
         };
         schema_flatbuffer::EnumValue::create(&mut self.bldr, &args)
     }
@@ -420,6 +430,7 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
         &mut self,
         value: &Argument,
     ) -> WIPOffset<schema_flatbuffer::Argument<'fb>> {
+        let directives = &self.serialize_directive_values(&value.directives);
         let args = schema_flatbuffer::ArgumentArgs {
             name: Some(self.bldr.create_string(value.name.0.lookup())),
             value: value
@@ -427,10 +438,19 @@ impl<'fb, 'schema> Serializer<'fb, 'schema> {
                 .as_ref()
                 .map(|default_value| self.serialize_const_value(default_value)),
             type_: Some(self.serialize_type_reference(&value.type_)),
+            directives: Some(self.bldr.create_vector(directives)),
+            description: self.serialize_description(value.description),
         };
         schema_flatbuffer::Argument::create(&mut self.bldr, &args)
     }
 
+    fn serialize_description(
+        &mut self,
+        description: Option<StringKey>,
+    ) -> Option<WIPOffset<&'fb str>> {
+        description.map(|description| self.bldr.create_string(description.lookup()))
+    }
+
     fn serialize_type_reference(
         &mut self,
         type_: &TypeReference<Type>,
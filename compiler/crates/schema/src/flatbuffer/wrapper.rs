@@ -7,6 +7,7 @@
 
 use std::fmt;
 use std::hash::Hash;
+use std::mem::size_of;
 
 use common::ArgumentName;
 use common::DirectiveName;
@@ -21,6 +22,7 @@ use ouroboros::self_referencing;
 use super::FlatBufferSchema;
 use crate::field_descriptions::CLIENT_ID_DESCRIPTION;
 use crate::field_descriptions::TYPENAME_DESCRIPTION;
+use crate::memory_stats::SchemaMemoryStats;
 use crate::Argument;
 use crate::ArgumentDefinitions;
 use crate::Directive;
@@ -42,10 +44,19 @@ use crate::TypeReference;
 use crate::Union;
 use crate::UnionID;
 
+/// The owner of the flatbuffer bytes, like a `Vec<u8>` or a memory mapped file.
+struct FlatBufferBytes(Box<dyn AsRef<[u8]> + Send + Sync>);
+
+impl AsRef<[u8]> for FlatBufferBytes {
+    fn as_ref(&self) -> &[u8] {
+        (*self.0).as_ref()
+    }
+}
+
 #[self_referencing]
 struct OwnedFlatBufferSchema {
     #[allow(dead_code)]
-    data: Vec<u8>,
+    data: Box<FlatBufferBytes>,
 
     #[borrows(data)]
     #[covariant]
@@ -75,6 +86,7 @@ pub struct SchemaWrapper {
     fields: Cache<FieldID, Field>,
     objects: Cache<ObjectID, Object>,
     fb: OwnedFlatBufferSchema,
+    fb_bytes: usize,
 }
 impl fmt::Debug for SchemaWrapper {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -89,9 +101,17 @@ fn _assert_send_sync2(s: &SchemaWrapper) {
 
 impl SchemaWrapper {
     pub fn from_vec(data: Vec<u8>) -> Self {
+        Self::from_bytes(data)
+    }
+
+    /// Reads the schema from the bytes of a flatbuffer, e.g. of a memory
+    /// mapped file. Types, fields and directives are read when they are
+    /// first looked up.
+    pub fn from_bytes(data: impl AsRef<[u8]> + Send + Sync + 'static) -> Self {
+        let fb_bytes = data.as_ref().len();
         let fb = OwnedFlatBufferSchemaBuilder {
-            data,
-            schema_builder: |data| FlatBufferSchema::build(data),
+            data: Box::new(FlatBufferBytes(Box::new(data))),
+            schema_builder: |data| FlatBufferSchema::build((*data).as_ref()),
         }
         .build();
 
@@ -111,6 +131,7 @@ impl SchemaWrapper {
             fields: Cache::new(),
             objects: Cache::new(),
             fb,
+            fb_bytes,
         };
 
         // prepopulate special fields
@@ -190,6 +211,35 @@ impl SchemaWrapper {
         result
     }
 
+    /// Estimates the memory the schema holds: the flatbuffer, and the
+    /// definitions that were read from it so far.
+    pub fn memory_stats(&self) -> SchemaMemoryStats {
+        let mut stats = SchemaMemoryStats::default();
+        stats.add("flatbuffer", 1, self.fb_bytes);
+        stats.add(
+            "loaded types",
+            self.objects.len()
+                + self.interfaces.len()
+                + self.unions.len()
+                + self.enums.len()
+                + self.input_objects.len()
+                + self.scalars.len(),
+            self.objects.bytes()
+                + self.interfaces.bytes()
+                + self.unions.bytes()
+                + self.enums.bytes()
+                + self.input_objects.bytes()
+                + self.scalars.bytes(),
+        );
+        stats.add("loaded fields", self.fields.len(), self.fields.bytes());
+        stats.add(
+            "loaded directives",
+            self.directives.len(),
+            self.directives.bytes(),
+        );
+        stats
+    }
+
     pub fn has_type(&self, type_name: StringKey) -> bool {
         self.flatbuffer_schema().has_type(type_name)
     }
@@ -497,6 +547,16 @@ impl<K: Hash + Eq, V> Cache<K, V> {
             .entry(key)
             .or_insert_with(|| Box::leak(Box::new(f())))
     }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// The entries and the leaked values, without the heap allocations of
+    /// the values.
+    fn bytes(&self) -> usize {
+        self.map.len() * (size_of::<K>() + size_of::<&V>() + size_of::<V>())
+    }
 }
 
 #[cfg(test)]
@@ -608,4 +668,63 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn descriptions() -> DiagnosticsResult<()> {
+        let sdl = r#"
+        """
+        A person.
+        """
+        type Query {
+          "The name."
+          name(
+            "The format of the name."
+            format: Format
+          ): String
+        }
+
+        "A format."
+        enum Format {
+          "Only the first name."
+          SHORT
+          LONG
+        }
+        "#;
+        let sdl_schema = build_schema(sdl)?.unwrap_in_memory_impl();
+        let bytes = serialize_as_flatbuffer(&sdl_schema);
+        let fb_schema = SchemaWrapper::from_vec(bytes);
+
+        assert_descriptions(&sdl_schema);
+        assert_descriptions(&fb_schema);
+
+        Ok(())
+    }
+
+    fn assert_descriptions(schema: &impl Schema) {
+        let query_type = schema.get_type("Query".intern()).unwrap();
+        let query = match query_type {
+            Type::Object(id) => schema.object(id),
+            _ => panic!("Expected `Query` to be an object."),
+        };
+        assert_eq!(query.description, Some("A person.".intern()));
+        let name = schema.field(schema.named_field(query_type, "name".intern()).unwrap());
+        assert_eq!(name.description, Some("The name.".intern()));
+        assert_eq!(
+            name.arguments.iter().next().unwrap().description,
+            Some("The format of the name.".intern())
+        );
+        let format = match schema.get_type("Format".intern()) {
+            Some(Type::Enum(id)) => schema.enum_(id),
+            _ => panic!("Expected `Format` to be an enum."),
+        };
+        assert_eq!(format.description, Some("A format.".intern()));
+        assert_eq!(
+            format
+                .values
+                .iter()
+                .map(|value| value.description)
+                .collect::<Vec<_>>(),
+            vec![Some("Only the first name.".intern()), None]
+        );
+    }
 }
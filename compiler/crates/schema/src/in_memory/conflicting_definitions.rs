@@ -0,0 +1,406 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::HashMap;
+
+use common::Diagnostic;
+use common::DiagnosticsResult;
+use common::Location;
+use graphql_syntax::*;
+use intern::Lookup;
+use intern::string_key::StringKey;
+
+use crate::errors::SchemaError;
+
+/// A type may be defined in several files of the server schema, as long as
+/// the definitions agree. Reports each definition that differs from an
+/// earlier one, at both definitions and with the first difference between
+/// them, and each field of a type extension that differs from an earlier
+/// definition of the field. Definitions without a location, like the
+/// builtins, are not checked.
+pub(super) fn find_conflicting_definitions(
+    schema_documents: &[SchemaDocument],
+) -> DiagnosticsResult<()> {
+    let mut types: HashMap<StringKey, (&TypeSystemDefinition, Location)> = HashMap::new();
+    let mut field_definitions: HashMap<(StringKey, StringKey), (&FieldDefinition, Location)> =
+        HashMap::new();
+    let mut errors = Vec::new();
+
+    for document in schema_documents {
+        let location_key = document.location.source_location();
+        if location_key.is_generated() {
+            continue;
+        }
+        for definition in &document.definitions {
+            let location = Location::new(location_key, definition.location());
+            let (type_name, type_fields) = match definition {
+                TypeSystemDefinition::ObjectTypeDefinition(ObjectTypeDefinition {
+                    name,
+                    fields,
+                    ..
+                })
+                | TypeSystemDefinition::InterfaceTypeDefinition(InterfaceTypeDefinition {
+                    name,
+                    fields,
+                    ..
+                }) => (name.value, Some(fields)),
+                TypeSystemDefinition::ObjectTypeExtension(ObjectTypeExtension {
+                    name,
+                    fields,
+                    ..
+                })
+                | TypeSystemDefinition::InterfaceTypeExtension(InterfaceTypeExtension {
+                    name,
+                    fields,
+                    ..
+                }) => {
+                    add_fields(
+                        name.value,
+                        fields,
+                        location,
+                        &mut field_definitions,
+                        &mut errors,
+                    );
+                    continue;
+                }
+                TypeSystemDefinition::UnionTypeDefinition(UnionTypeDefinition { name, .. })
+                | TypeSystemDefinition::InputObjectTypeDefinition(InputObjectTypeDefinition {
+                    name,
+                    ..
+                })
+                | TypeSystemDefinition::EnumTypeDefinition(EnumTypeDefinition { name, .. })
+                | TypeSystemDefinition::ScalarTypeDefinition(ScalarTypeDefinition {
+                    name, ..
+                }) => (name.value, None),
+                _ => continue,
+            };
+            if let Some((previous, previous_location)) = types.get(&type_name) {
+                if let Some(difference) = type_difference(type_name, previous, definition) {
+                    errors.push(
+                        Diagnostic::error(
+                            SchemaError::ConflictingTypeDefinitions(type_name, difference),
+                            location,
+                        )
+                        .annotate("previously defined here", *previous_location),
+                    );
+                }
+                // The fields of an identical definition were added already
+                continue;
+            }
+            types.insert(type_name, (definition, location));
+            if let Some(type_fields) = type_fields {
+                add_fields(
+                    type_name,
+                    type_fields,
+                    location,
+                    &mut field_definitions,
+                    &mut errors,
+                );
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn add_fields<'a>(
+    type_name: StringKey,
+    field_defs: &'a Option<List<FieldDefinition>>,
+    type_location: Location,
+    field_definitions: &mut HashMap<(StringKey, StringKey), (&'a FieldDefinition, Location)>,
+    errors: &mut Vec<Diagnostic>,
+) {
+    for field in field_defs.iter().flat_map(|list| &list.items) {
+        let location = type_location.with_span(field.name.span);
+        match field_definitions.get(&(type_name, field.name.value)) {
+            Some((previous, previous_location)) => {
+                let coordinate = format!("{}.{}", type_name, field.name.value);
+                if let Some(difference) = field_difference(&coordinate, previous, field) {
+                    errors.push(
+                        Diagnostic::error(
+                            SchemaError::ConflictingFieldDefinitions(
+                                type_name,
+                                field.name.value,
+                                difference,
+                            ),
+                            location,
+                        )
+                        .annotate("previously defined here", *previous_location),
+                    );
+                }
+            }
+            None => {
+                field_definitions.insert((type_name, field.name.value), (field, location));
+            }
+        }
+    }
+}
+
+/// Describes the first difference between two definitions of a type, or
+/// returns `None` if they agree. Descriptions are not compared.
+fn type_difference(
+    type_name: StringKey,
+    first: &TypeSystemDefinition,
+    second: &TypeSystemDefinition,
+) -> Option<String> {
+    let member_coordinate = |name: StringKey| format!("{}.{}", type_name, name);
+    match (first, second) {
+        (
+            TypeSystemDefinition::ObjectTypeDefinition(ObjectTypeDefinition {
+                interfaces: first_interfaces,
+                directives: first_directives,
+                fields: first_fields,
+                ..
+            }),
+            TypeSystemDefinition::ObjectTypeDefinition(ObjectTypeDefinition {
+                interfaces: second_interfaces,
+                directives: second_directives,
+                fields: second_fields,
+                ..
+            }),
+        )
+        | (
+            TypeSystemDefinition::InterfaceTypeDefinition(InterfaceTypeDefinition {
+                interfaces: first_interfaces,
+                directives: first_directives,
+                fields: first_fields,
+                ..
+            }),
+            TypeSystemDefinition::InterfaceTypeDefinition(InterfaceTypeDefinition {
+                interfaces: second_interfaces,
+                directives: second_directives,
+                fields: second_fields,
+                ..
+            }),
+        ) => list_difference(
+            type_name.lookup(),
+            "interfaces",
+            first_interfaces,
+            second_interfaces,
+            " & ",
+        )
+        .or_else(|| directives_difference(type_name.lookup(), first_directives, second_directives))
+        .or_else(|| {
+            members_difference(
+                items(first_fields),
+                items(second_fields),
+                |field| field.name.value,
+                member_coordinate,
+                field_difference,
+            )
+        }),
+        (
+            TypeSystemDefinition::UnionTypeDefinition(first),
+            TypeSystemDefinition::UnionTypeDefinition(second),
+        ) => list_difference(
+            type_name.lookup(),
+            "members",
+            &first.members,
+            &second.members,
+            " | ",
+        )
+        .or_else(|| {
+            directives_difference(type_name.lookup(), &first.directives, &second.directives)
+        }),
+        (
+            TypeSystemDefinition::InputObjectTypeDefinition(first),
+            TypeSystemDefinition::InputObjectTypeDefinition(second),
+        ) => directives_difference(type_name.lookup(), &first.directives, &second.directives)
+            .or_else(|| {
+                members_difference(
+                    items(&first.fields),
+                    items(&second.fields),
+                    |field| field.name.value,
+                    member_coordinate,
+                    input_value_difference,
+                )
+            }),
+        (
+            TypeSystemDefinition::EnumTypeDefinition(first),
+            TypeSystemDefinition::EnumTypeDefinition(second),
+        ) => directives_difference(type_name.lookup(), &first.directives, &second.directives)
+            .or_else(|| {
+                members_difference(
+                    items(&first.values),
+                    items(&second.values),
+                    |value| value.name.value,
+                    member_coordinate,
+                    |coordinate, first, second| {
+                        directives_difference(coordinate, &first.directives, &second.directives)
+                    },
+                )
+            }),
+        (
+            TypeSystemDefinition::ScalarTypeDefinition(first),
+            TypeSystemDefinition::ScalarTypeDefinition(second),
+        ) => directives_difference(type_name.lookup(), &first.directives, &second.directives),
+        _ => Some(format!(
+            "`{}` is {} in one and {} in the other",
+            type_name,
+            kind_name(first),
+            kind_name(second)
+        )),
+    }
+}
+
+fn field_difference(
+    coordinate: &str,
+    first: &FieldDefinition,
+    second: &FieldDefinition,
+) -> Option<String> {
+    if first.type_.to_string() != second.type_.to_string() {
+        return Some(format!(
+            "`{}` has the type `{}` in one and `{}` in the other",
+            coordinate, first.type_, second.type_
+        ));
+    }
+    members_difference(
+        items(&first.arguments),
+        items(&second.arguments),
+        |argument| argument.name.value,
+        |name| format!("{}({}:)", coordinate, name),
+        input_value_difference,
+    )
+    .or_else(|| directives_difference(coordinate, &first.directives, &second.directives))
+}
+
+fn input_value_difference(
+    coordinate: &str,
+    first: &InputValueDefinition,
+    second: &InputValueDefinition,
+) -> Option<String> {
+    let (first, second) = (first.to_string(), second.to_string());
+    if first == second {
+        None
+    } else {
+        Some(format!(
+            "`{}` is `{}` in one and `{}` in the other",
+            coordinate, first, second
+        ))
+    }
+}
+
+/// Compares the members of two definitions by name, in the order of the
+/// first definition, and then the members only the second one has.
+fn members_difference<T>(
+    first: &[T],
+    second: &[T],
+    name: impl Fn(&T) -> StringKey,
+    coordinate: impl Fn(StringKey) -> String,
+    difference: impl Fn(&str, &T, &T) -> Option<String>,
+) -> Option<String> {
+    for first_member in first {
+        let member_name = name(first_member);
+        let member_coordinate = coordinate(member_name);
+        match second.iter().find(|member| name(member) == member_name) {
+            Some(second_member) => {
+                if let Some(difference) =
+                    difference(&member_coordinate, first_member, second_member)
+                {
+                    return Some(difference);
+                }
+            }
+            None => {
+                return Some(format!(
+                    "`{}` is only defined in one of them",
+                    member_coordinate
+                ));
+            }
+        }
+    }
+    second
+        .iter()
+        .map(&name)
+        .find(|member_name| !first.iter().any(|member| name(member) == *member_name))
+        .map(|member_name| {
+            format!(
+                "`{}` is only defined in one of them",
+                coordinate(member_name)
+            )
+        })
+}
+
+fn list_difference(
+    coordinate: &str,
+    list_kind: &str,
+    first: &[Identifier],
+    second: &[Identifier],
+    separator: &str,
+) -> Option<String> {
+    let print = |list: &[Identifier]| {
+        if list.is_empty() {
+            "none".to_string()
+        } else {
+            format!(
+                "`{}`",
+                list.iter()
+                    .map(|identifier| identifier.value.lookup())
+                    .collect::<Vec<_>>()
+                    .join(separator)
+            )
+        }
+    };
+    let (first, second) = (print(first), print(second));
+    if first == second {
+        None
+    } else {
+        Some(format!(
+            "`{}` has the {} {} in one and {} in the other",
+            coordinate, list_kind, first, second
+        ))
+    }
+}
+
+fn directives_difference(
+    coordinate: &str,
+    first: &[ConstantDirective],
+    second: &[ConstantDirective],
+) -> Option<String> {
+    let print = |directives: &[ConstantDirective]| {
+        if directives.is_empty() {
+            "none".to_string()
+        } else {
+            format!(
+                "`{}`",
+                directives
+                    .iter()
+                    .map(|directive| directive.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+        }
+    };
+    let (first, second) = (print(first), print(second));
+    if first == second {
+        None
+    } else {
+        Some(format!(
+            "`{}` has the directives {} in one and {} in the other",
+            coordinate, first, second
+        ))
+    }
+}
+
+fn items<T>(list: &Option<List<T>>) -> &[T] {
+    list.as_ref().map_or(&[], |list| &list.items)
+}
+
+fn kind_name(definition: &TypeSystemDefinition) -> &'static str {
+    match definition {
+        TypeSystemDefinition::ObjectTypeDefinition(_) => "an object",
+        TypeSystemDefinition::InterfaceTypeDefinition(_) => "an interface",
+        TypeSystemDefinition::UnionTypeDefinition(_) => "a union",
+        TypeSystemDefinition::InputObjectTypeDefinition(_) => "an input object",
+        TypeSystemDefinition::EnumTypeDefinition(_) => "an enum",
+        TypeSystemDefinition::ScalarTypeDefinition(_) => "a scalar",
+        _ => "an extension",
+    }
+}
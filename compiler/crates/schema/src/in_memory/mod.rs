@@ -5,8 +5,13 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+mod conflicting_definitions;
+mod schema_builder;
+
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::mem::size_of;
+use std::sync::Arc;
 
 use common::ArgumentName;
 use common::Diagnostic;
@@ -20,10 +25,16 @@ use common::ObjectName;
 use common::ScalarName;
 use common::SourceLocationKey;
 use common::WithLocation;
+use conflicting_definitions::find_conflicting_definitions;
 use graphql_syntax::*;
 use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use intern::Lookup;
+pub use schema_builder::ArgumentsBuilder;
+pub use schema_builder::SchemaBuilder;
+pub use schema_builder::TypeBuilder;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::definitions::Argument;
 use crate::definitions::Directive;
@@ -32,12 +43,18 @@ use crate::errors::SchemaError;
 use crate::field_descriptions::CLIENT_ID_DESCRIPTION;
 use crate::field_descriptions::TYPENAME_DESCRIPTION;
 use crate::graphql_schema::Schema;
+use crate::memory_stats::arguments_bytes;
+use crate::memory_stats::directive_values_bytes;
+use crate::memory_stats::shared_constant_value_bytes;
+use crate::memory_stats::type_reference_bytes;
+use crate::memory_stats::vec_bytes;
+use crate::memory_stats::SchemaMemoryStats;
 
 fn todo_add_location<T>(error: SchemaError) -> DiagnosticsResult<T> {
     Err(vec![Diagnostic::error(error, Location::generated())])
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InMemorySchema {
     query_type: Option<ObjectID>,
     mutation_type: Option<ObjectID>,
@@ -366,6 +383,182 @@ impl InMemorySchema {
         self.directives.contains_key(&directive_name)
     }
 
+    /// Estimates the memory the schema holds, see `SchemaMemoryStats`.
+    pub fn memory_stats(&self) -> SchemaMemoryStats {
+        let mut stats = SchemaMemoryStats::default();
+        let type_count = self.objects.len()
+            + self.interfaces.len()
+            + self.unions.len()
+            + self.enums.len()
+            + self.input_objects.len()
+            + self.scalars.len();
+        let type_bytes = vec_bytes(&self.objects)
+            + self
+                .objects
+                .iter()
+                .map(|object| {
+                    vec_bytes(&object.fields)
+                        + vec_bytes(&object.interfaces)
+                        + directive_values_bytes(&object.directives)
+                })
+                .sum::<usize>()
+            + vec_bytes(&self.interfaces)
+            + self
+                .interfaces
+                .iter()
+                .map(|interface| {
+                    vec_bytes(&interface.fields)
+                        + vec_bytes(&interface.interfaces)
+                        + vec_bytes(&interface.implementing_interfaces)
+                        + vec_bytes(&interface.implementing_objects)
+                        + directive_values_bytes(&interface.directives)
+                })
+                .sum::<usize>()
+            + vec_bytes(&self.unions)
+            + self
+                .unions
+                .iter()
+                .map(|union| vec_bytes(&union.members) + directive_values_bytes(&union.directives))
+                .sum::<usize>()
+            + vec_bytes(&self.enums)
+            + self
+                .enums
+                .iter()
+                .map(|enum_| {
+                    vec_bytes(&enum_.values)
+                        + enum_
+                            .values
+                            .iter()
+                            .map(|value| directive_values_bytes(&value.directives))
+                            .sum::<usize>()
+                        + directive_values_bytes(&enum_.directives)
+                })
+                .sum::<usize>()
+            + vec_bytes(&self.input_objects)
+            + self
+                .input_objects
+                .iter()
+                .map(|input_object| directive_values_bytes(&input_object.directives))
+                .sum::<usize>()
+            + vec_bytes(&self.scalars)
+            + self
+                .scalars
+                .iter()
+                .map(|scalar| directive_values_bytes(&scalar.directives))
+                .sum::<usize>();
+        stats.add("types", type_count, type_bytes);
+
+        stats.add(
+            "fields",
+            self.fields.len(),
+            vec_bytes(&self.fields)
+                + self
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        type_reference_bytes(&field.type_)
+                            + directive_values_bytes(&field.directives)
+                    })
+                    .sum::<usize>(),
+        );
+
+        let arguments = self
+            .fields
+            .iter()
+            .map(|field| &field.arguments)
+            .chain(
+                self.input_objects
+                    .iter()
+                    .map(|input_object| &input_object.fields),
+            )
+            .chain(
+                self.directives
+                    .values()
+                    .map(|directive| &directive.arguments),
+            );
+        // The shared default values, by address
+        let mut default_values: HashMap<*const ConstantValue, &ConstantValue> = HashMap::new();
+        let (argument_count, argument_bytes) =
+            arguments.fold((0, 0), |(count, bytes), arguments| {
+                default_values.extend(arguments.iter().filter_map(|argument| {
+                    let default_value = argument.default_value.as_ref()?;
+                    Some((Arc::as_ptr(default_value), &**default_value))
+                }));
+                (
+                    count + arguments.0.len(),
+                    bytes + arguments_bytes(arguments),
+                )
+            });
+        stats.add("arguments and input fields", argument_count, argument_bytes);
+        stats.add(
+            "argument default values",
+            default_values.len(),
+            default_values
+                .values()
+                .map(|default_value| shared_constant_value_bytes(default_value))
+                .sum::<usize>(),
+        );
+
+        stats.add(
+            "directives",
+            self.directives.len(),
+            self.directives.capacity() * size_of::<(DirectiveName, Directive)>(),
+        );
+        stats.add(
+            "type map entries",
+            self.type_map.len(),
+            self.type_map.capacity() * size_of::<(StringKey, Type)>(),
+        );
+        stats
+    }
+
+    /// Releases the memory that was reserved for definitions that were not
+    /// added, e.g. when the number of definitions was overestimated, and
+    /// shares the default values of arguments.
+    pub fn shrink_to_fit(&mut self) {
+        self.type_map.shrink_to_fit();
+        self.directives.shrink_to_fit();
+        self.enums.shrink_to_fit();
+        self.fields.shrink_to_fit();
+        self.input_objects.shrink_to_fit();
+        self.interfaces.shrink_to_fit();
+        self.objects.shrink_to_fit();
+        self.scalars.shrink_to_fit();
+        self.unions.shrink_to_fit();
+        self.share_default_values();
+    }
+
+    /// Makes the arguments with the same default value share it, e.g. the
+    /// `first: Int = 10` of the connection fields of a large schema. Values
+    /// are compared as printed, ignoring their span in the schema file.
+    fn share_default_values(&mut self) {
+        let mut default_values: HashMap<String, Arc<ConstantValue>> = HashMap::new();
+        let arguments = self
+            .fields
+            .iter_mut()
+            .map(|field| &mut field.arguments)
+            .chain(
+                self.input_objects
+                    .iter_mut()
+                    .map(|input_object| &mut input_object.fields),
+            )
+            .chain(
+                self.directives
+                    .values_mut()
+                    .map(|directive| &mut directive.arguments),
+            );
+        for arguments in arguments {
+            for argument in arguments.0.iter_mut() {
+                if let Some(default_value) = &mut argument.default_value {
+                    let shared_value = default_values
+                        .entry(default_value.to_string())
+                        .or_insert_with(|| Arc::clone(default_value));
+                    *default_value = Arc::clone(shared_value);
+                }
+            }
+        }
+    }
+
     pub fn has_type(&self, type_name: StringKey) -> bool {
         self.type_map.contains_key(&type_name)
     }
@@ -701,6 +894,8 @@ impl InMemorySchema {
             .flat_map(|document| &document.definitions)
             .collect();
 
+        find_conflicting_definitions(schema_documents)?;
+
         // Step 1: build the type_map from type names to type keys
         let mut type_map =
             HashMap::with_capacity(schema_definitions.len() + client_definitions.len());
@@ -763,9 +958,9 @@ impl InMemorySchema {
                 }
                 TypeSystemDefinition::ObjectTypeExtension { .. } => {}
                 TypeSystemDefinition::InterfaceTypeExtension { .. } => {}
-                TypeSystemDefinition::SchemaExtension { .. } => todo!("SchemaExtension"),
-                TypeSystemDefinition::EnumTypeExtension { .. } => todo!("EnumTypeExtension"),
-                TypeSystemDefinition::UnionTypeExtension { .. } => todo!("UnionTypeExtension"),
+                TypeSystemDefinition::SchemaExtension { .. } => {}
+                TypeSystemDefinition::EnumTypeExtension { .. } => {}
+                TypeSystemDefinition::UnionTypeExtension { .. } => {}
                 TypeSystemDefinition::InputObjectTypeExtension { .. } => {
                     todo!("InputObjectTypeExtension")
                 }
@@ -879,6 +1074,7 @@ impl InMemorySchema {
             }
         }
         schema.load_defaults();
+        schema.shrink_to_fit();
 
         Ok(schema)
     }
@@ -1111,6 +1307,61 @@ impl InMemorySchema {
         Ok(())
     }
 
+    fn add_operation_types(
+        &mut self,
+        operation_types: &[OperationTypeDefinition],
+        location_key: &SourceLocationKey,
+    ) -> DiagnosticsResult<()> {
+        for OperationTypeDefinition { operation, type_ } in operation_types {
+            let operation_id = self.build_object_id(type_.value)?;
+            match operation {
+                OperationType::Query => {
+                    if let Some(prev_query_type) = self.query_type {
+                        return Err(vec![Diagnostic::error(
+                            SchemaError::DuplicateOperationDefinition(
+                                *operation,
+                                type_.value,
+                                expect_object_type_name(&self.type_map, prev_query_type),
+                            ),
+                            Location::new(*location_key, type_.span),
+                        )]);
+                    } else {
+                        self.query_type = Some(operation_id);
+                    }
+                }
+                OperationType::Mutation => {
+                    if let Some(prev_mutation_type) = self.mutation_type {
+                        return Err(vec![Diagnostic::error(
+                            SchemaError::DuplicateOperationDefinition(
+                                *operation,
+                                type_.value,
+                                expect_object_type_name(&self.type_map, prev_mutation_type),
+                            ),
+                            Location::new(*location_key, type_.span),
+                        )]);
+                    } else {
+                        self.mutation_type = Some(operation_id);
+                    }
+                }
+                OperationType::Subscription => {
+                    if let Some(prev_subscription_type) = self.subscription_type {
+                        return Err(vec![Diagnostic::error(
+                            SchemaError::DuplicateOperationDefinition(
+                                *operation,
+                                type_.value,
+                                expect_object_type_name(&self.type_map, prev_subscription_type),
+                            ),
+                            Location::new(*location_key, type_.span),
+                        )]);
+                    } else {
+                        self.subscription_type = Some(operation_id);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn add_definition(
         &mut self,
         definition: &TypeSystemDefinition,
@@ -1122,55 +1373,14 @@ impl InMemorySchema {
                 operation_types,
                 directives: _directives,
             }) => {
-                for OperationTypeDefinition { operation, type_ } in &operation_types.items {
-                    let operation_id = self.build_object_id(type_.value)?;
-                    match operation {
-                        OperationType::Query => {
-                            if let Some(prev_query_type) = self.query_type {
-                                return Err(vec![Diagnostic::error(
-                                    SchemaError::DuplicateOperationDefinition(
-                                        *operation,
-                                        type_.value,
-                                        expect_object_type_name(&self.type_map, prev_query_type),
-                                    ),
-                                    Location::new(*location_key, type_.span),
-                                )]);
-                            } else {
-                                self.query_type = Some(operation_id);
-                            }
-                        }
-                        OperationType::Mutation => {
-                            if let Some(prev_mutation_type) = self.mutation_type {
-                                return Err(vec![Diagnostic::error(
-                                    SchemaError::DuplicateOperationDefinition(
-                                        *operation,
-                                        type_.value,
-                                        expect_object_type_name(&self.type_map, prev_mutation_type),
-                                    ),
-                                    Location::new(*location_key, type_.span),
-                                )]);
-                            } else {
-                                self.mutation_type = Some(operation_id);
-                            }
-                        }
-                        OperationType::Subscription => {
-                            if let Some(prev_subscription_type) = self.subscription_type {
-                                return Err(vec![Diagnostic::error(
-                                    SchemaError::DuplicateOperationDefinition(
-                                        *operation,
-                                        type_.value,
-                                        expect_object_type_name(
-                                            &self.type_map,
-                                            prev_subscription_type,
-                                        ),
-                                    ),
-                                    Location::new(*location_key, type_.span),
-                                )]);
-                            } else {
-                                self.subscription_type = Some(operation_id);
-                            }
-                        }
-                    }
+                self.add_operation_types(&operation_types.items, location_key)?;
+            }
+            TypeSystemDefinition::SchemaExtension(SchemaExtension {
+                operation_types,
+                directives: _directives,
+            }) => {
+                if let Some(operation_types) = operation_types {
+                    self.add_operation_types(&operation_types.items, location_key)?;
                 }
             }
             TypeSystemDefinition::DirectiveDefinition(DirectiveDefinition {
@@ -1196,6 +1406,7 @@ impl InMemorySchema {
                     DirectiveName(name.value),
                     Directive {
                         name: DirectiveName(name.value),
+                        location: Location::new(*location_key, name.span),
                         arguments,
                         locations: locations.clone(),
                         repeatable: *repeatable,
@@ -1210,6 +1421,7 @@ impl InMemorySchema {
                 interfaces,
                 fields,
                 directives,
+                description,
             }) => {
                 let parent_id = Type::Object(ObjectID(self.objects.len() as u32));
                 let fields = if is_extension {
@@ -1236,7 +1448,7 @@ impl InMemorySchema {
                     is_extension,
                     interfaces,
                     directives,
-                    description: None,
+                    description: description.as_ref().map(|node| node.value),
                     hack_source: None,
                 });
             }
@@ -1245,6 +1457,7 @@ impl InMemorySchema {
                 interfaces,
                 directives,
                 fields,
+                description,
             }) => {
                 let parent_id = Type::Interface(InterfaceID(self.interfaces.len() as u32));
                 let fields = if is_extension {
@@ -1273,7 +1486,7 @@ impl InMemorySchema {
                     fields,
                     directives,
                     interfaces,
-                    description: None,
+                    description: description.as_ref().map(|node| node.value),
                     hack_source: None,
                 });
             }
@@ -1281,6 +1494,7 @@ impl InMemorySchema {
                 name,
                 directives,
                 members,
+                description,
             }) => {
                 let members = members
                     .iter()
@@ -1292,7 +1506,7 @@ impl InMemorySchema {
                     is_extension,
                     members,
                     directives,
-                    description: None,
+                    description: description.as_ref().map(|node| node.value),
                     hack_source: None,
                 });
             }
@@ -1300,6 +1514,7 @@ impl InMemorySchema {
                 name,
                 fields,
                 directives,
+                description,
             }) => {
                 let fields = self.build_arguments(fields)?;
                 let directives = self.build_directive_values(directives);
@@ -1311,7 +1526,7 @@ impl InMemorySchema {
 
                     fields,
                     directives,
-                    description: None,
+                    description: description.as_ref().map(|node| node.value),
                     hack_source: None,
                 });
             }
@@ -1319,6 +1534,7 @@ impl InMemorySchema {
                 name,
                 directives,
                 values,
+                description,
             }) => {
                 let directives = self.build_directive_values(directives);
                 let values = if let Some(values) = values {
@@ -1327,7 +1543,10 @@ impl InMemorySchema {
                         .iter()
                         .map(|enum_def| EnumValue {
                             value: enum_def.name.value,
+                            location: Location::new(*location_key, enum_def.name.span),
                             directives: self.build_directive_values(&enum_def.directives),
+                            is_extension,
+                            description: enum_def.description.as_ref().map(|node| node.value),
                         })
                         .collect()
                 } else {
@@ -1341,13 +1560,14 @@ impl InMemorySchema {
                     is_extension,
                     values,
                     directives,
-                    description: None,
+                    description: description.as_ref().map(|node| node.value),
                     hack_source: None,
                 });
             }
             TypeSystemDefinition::ScalarTypeDefinition(ScalarTypeDefinition {
                 name,
                 directives,
+                description,
             }) => {
                 let directives = self.build_directive_values(directives);
                 self.scalars.push(Scalar {
@@ -1357,7 +1577,7 @@ impl InMemorySchema {
                     ),
                     is_extension,
                     directives,
-                    description: None,
+                    description: description.as_ref().map(|node| node.value),
                     hack_source: None,
                 })
             }
@@ -1456,9 +1676,102 @@ impl InMemorySchema {
                     )]);
                 }
             },
-            TypeSystemDefinition::SchemaExtension { .. } => todo!("SchemaExtension"),
-            TypeSystemDefinition::EnumTypeExtension { .. } => todo!("EnumTypeExtension"),
-            TypeSystemDefinition::UnionTypeExtension { .. } => todo!("UnionTypeExtension"),
+            TypeSystemDefinition::EnumTypeExtension(EnumTypeExtension {
+                name,
+                directives,
+                values,
+            }) => match self.type_map.get(&name.value).cloned() {
+                Some(Type::Enum(id)) if id.as_usize() < self.enums.len() => {
+                    let index = id.as_usize();
+                    let mut built_values = Vec::with_capacity(len_of_option_list(values));
+                    for value in values.iter().flat_map(|values| &values.items) {
+                        if self.enums[index]
+                            .values
+                            .iter()
+                            .chain(&built_values)
+                            .any(|existing: &EnumValue| existing.value == value.name.value)
+                        {
+                            return Err(vec![Diagnostic::error(
+                                SchemaError::DuplicateEnumValue(name.value, value.name.value),
+                                Location::new(*location_key, value.name.span),
+                            )]);
+                        }
+                        built_values.push(EnumValue {
+                            value: value.name.value,
+                            location: Location::new(*location_key, value.name.span),
+                            directives: self.build_directive_values(&value.directives),
+                            is_extension,
+                            description: value.description.as_ref().map(|node| node.value),
+                        });
+                    }
+                    self.enums[index].values.extend(built_values);
+
+                    let built_directives = self.build_directive_values(directives);
+                    extend_without_duplicates(&mut self.enums[index].directives, built_directives);
+                }
+                _ => {
+                    return Err(vec![Diagnostic::error(
+                        SchemaError::ExtendUndefinedType(name.value),
+                        Location::new(*location_key, name.span),
+                    )]);
+                }
+            },
+            TypeSystemDefinition::UnionTypeExtension(UnionTypeExtension {
+                name,
+                directives,
+                members,
+            }) => match self.type_map.get(&name.value).cloned() {
+                Some(Type::Union(id)) if id.as_usize() < self.unions.len() => {
+                    let index = id.as_usize();
+                    let mut built_members = Vec::with_capacity(members.len());
+                    for member in members {
+                        let location = Location::new(*location_key, member.span);
+                        let member_id = match self.type_map.get(&member.value) {
+                            Some(Type::Object(id)) => *id,
+                            Some(non_object_type) => {
+                                return Err(vec![Diagnostic::error(
+                                    SchemaError::ExpectedObjectReference(
+                                        member.value,
+                                        *non_object_type,
+                                    ),
+                                    location,
+                                )]);
+                            }
+                            None => {
+                                return Err(vec![Diagnostic::error(
+                                    SchemaError::UndefinedType(member.value),
+                                    location,
+                                )]);
+                            }
+                        };
+                        // The server doesn't know that its types are members
+                        // of the union, so only client types can be added to
+                        // server unions. Server types are all defined before
+                        // the client extensions are added.
+                        let is_server_member = self
+                            .objects
+                            .get(member_id.as_usize())
+                            .map_or(false, |object| !object.is_extension);
+                        if is_extension && !self.unions[index].is_extension && is_server_member {
+                            return Err(vec![Diagnostic::error(
+                                SchemaError::ExtendUnionWithServerType(name.value, member.value),
+                                location,
+                            )]);
+                        }
+                        built_members.push(member_id);
+                    }
+                    extend_without_duplicates(&mut self.unions[index].members, built_members);
+
+                    let built_directives = self.build_directive_values(directives);
+                    extend_without_duplicates(&mut self.unions[index].directives, built_directives);
+                }
+                _ => {
+                    return Err(vec![Diagnostic::error(
+                        SchemaError::ExtendUndefinedType(name.value),
+                        Location::new(*location_key, name.span),
+                    )]);
+                }
+            },
             TypeSystemDefinition::InputObjectTypeExtension { .. } => {
                 todo!("InputObjectTypeExtension")
             }
@@ -1595,8 +1908,8 @@ impl InMemorySchema {
                     Ok(Argument {
                         name: ArgumentName(arg_def.name.value),
                         type_: self.build_input_object_reference(&arg_def.type_)?,
-                        default_value: arg_def.default_value.clone(),
-                        description: None,
+                        default_value: arg_def.default_value.clone().map(Arc::new),
+                        description: arg_def.description.as_ref().map(|node| node.value),
                         directives: self.build_directive_values(&arg_def.directives),
                     })
                 })
@@ -1762,6 +2075,7 @@ mod tests {
                     interfaces: vec![identifier_from_value("ITunes".intern())],
                     directives: vec![],
                     fields: None,
+                    description: None,
                 },
                 SourceLocationKey::Generated,
             )
@@ -1774,4 +2088,66 @@ mod tests {
             "ITunes should have an implementing object"
         );
     }
+
+    #[test]
+    fn test_memory_stats() {
+        let schema = crate::build_schema(
+            "type Query { users(first: Int = 10, orderBy: [String!]): [User] } type User { id: ID! }",
+        )
+        .unwrap()
+        .unwrap_in_memory_impl();
+        assert_eq!(schema.fields.capacity(), schema.fields.len());
+
+        let stats = schema.memory_stats();
+        let fields = stats
+            .kinds
+            .iter()
+            .find(|kind| kind.name == "fields")
+            .unwrap();
+        assert_eq!(fields.count, schema.fields.len());
+        assert!(fields.bytes >= schema.fields.len() * size_of::<Field>());
+        assert_eq!(
+            stats.total_bytes(),
+            stats.kinds.iter().map(|kind| kind.bytes).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_share_default_values() {
+        let schema = crate::build_schema(
+            "type Query { users(first: Int = 10): [User] friends(first: Int = 10, last: Int = 5): [User] }
+             type User { id: ID! }",
+        )
+        .unwrap()
+        .unwrap_in_memory_impl();
+        let query = schema.get_type("Query".intern()).unwrap();
+        let default_value = |field_name: &str, argument_name: &str| {
+            let field = schema.field(schema.named_field(query, field_name.intern()).unwrap());
+            Arc::clone(
+                field
+                    .arguments
+                    .named(ArgumentName(argument_name.intern()))
+                    .unwrap()
+                    .default_value
+                    .as_ref()
+                    .unwrap(),
+            )
+        };
+        assert!(Arc::ptr_eq(
+            &default_value("users", "first"),
+            &default_value("friends", "first")
+        ));
+        assert!(!Arc::ptr_eq(
+            &default_value("friends", "first"),
+            &default_value("friends", "last")
+        ));
+
+        let stats = schema.memory_stats();
+        let default_values = stats
+            .kinds
+            .iter()
+            .find(|kind| kind.name == "argument default values")
+            .unwrap();
+        assert_eq!(default_values.count, 2);
+    }
 }
@@ -0,0 +1,588 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::HashSet;
+
+use common::ArgumentName;
+use common::Diagnostic;
+use common::DiagnosticsResult;
+use common::DirectiveName;
+use common::EnumName;
+use common::InputObjectName;
+use common::InterfaceName;
+use common::Location;
+use common::ObjectName;
+use common::ScalarName;
+use common::WithLocation;
+use graphql_syntax::DirectiveLocation;
+use intern::string_key::Intern;
+use intern::string_key::StringKey;
+
+use super::InMemorySchema;
+use crate::builtins;
+use crate::definitions::*;
+use crate::errors::SchemaError;
+use crate::graphql_schema::Schema;
+use crate::schema::SDLSchema;
+
+/// Builds a schema in code, for tests and embedders that would otherwise
+/// write SDL only for it to be parsed again. Types may reference types that
+/// are added later, the references are resolved by `build`. Types are given
+/// in the SDL notation, like `User`, `[User!]` or `ID!`.
+///
+/// ```ignore
+/// let schema = SchemaBuilder::new()
+///     .object("Query", |query| query.field("node", "Node"))
+///     .interface("Node", |node| node.field("id", "ID!"))
+///     .object("User", |user| {
+///         user.implements("Node")
+///             .field("id", "ID!")
+///             .field_with_arguments("friends", "[User]", |arguments| {
+///                 arguments.argument("first", "Int")
+///             })
+///     })
+///     .build()?;
+/// ```
+///
+/// As when building a schema from SDL, the objects named `Query`, `Mutation`
+/// and `Subscription` are the root types, unless the schema that is extended
+/// defines them already.
+#[derive(Default)]
+pub struct SchemaBuilder {
+    schema: Option<InMemorySchema>,
+    scalars: Vec<StringKey>,
+    enums: Vec<(StringKey, Vec<StringKey>)>,
+    input_objects: Vec<(StringKey, ArgumentsBuilder)>,
+    objects: Vec<TypeBuilder>,
+    interfaces: Vec<TypeBuilder>,
+    unions: Vec<(StringKey, Vec<StringKey>)>,
+    type_extensions: Vec<TypeBuilder>,
+    directives: Vec<(StringKey, Vec<DirectiveLocation>, ArgumentsBuilder)>,
+}
+
+impl SchemaBuilder {
+    /// Starts from a schema with only the builtin types and directives.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds to an existing schema, e.g. one built from SDL.
+    pub fn extend(schema: InMemorySchema) -> Self {
+        Self {
+            schema: Some(schema),
+            ..Default::default()
+        }
+    }
+
+    pub fn scalar(mut self, name: &str) -> Self {
+        self.scalars.push(name.intern());
+        self
+    }
+
+    pub fn enum_<'a>(mut self, name: &str, values: impl IntoIterator<Item = &'a str>) -> Self {
+        self.enums.push((
+            name.intern(),
+            values.into_iter().map(|value| value.intern()).collect(),
+        ));
+        self
+    }
+
+    pub fn input_object(
+        mut self,
+        name: &str,
+        fields: impl FnOnce(ArgumentsBuilder) -> ArgumentsBuilder,
+    ) -> Self {
+        self.input_objects
+            .push((name.intern(), fields(ArgumentsBuilder::default())));
+        self
+    }
+
+    pub fn object(mut self, name: &str, object: impl FnOnce(TypeBuilder) -> TypeBuilder) -> Self {
+        self.objects.push(object(TypeBuilder::new(name)));
+        self
+    }
+
+    pub fn interface(
+        mut self,
+        name: &str,
+        interface: impl FnOnce(TypeBuilder) -> TypeBuilder,
+    ) -> Self {
+        self.interfaces.push(interface(TypeBuilder::new(name)));
+        self
+    }
+
+    pub fn union<'a>(mut self, name: &str, members: impl IntoIterator<Item = &'a str>) -> Self {
+        self.unions.push((
+            name.intern(),
+            members.into_iter().map(|member| member.intern()).collect(),
+        ));
+        self
+    }
+
+    /// Adds fields and interfaces to an object or interface, of the schema
+    /// that is extended or added by this builder.
+    pub fn extend_type(
+        mut self,
+        name: &str,
+        extension: impl FnOnce(TypeBuilder) -> TypeBuilder,
+    ) -> Self {
+        self.type_extensions.push(extension(TypeBuilder::new(name)));
+        self
+    }
+
+    pub fn directive(
+        mut self,
+        name: &str,
+        locations: impl IntoIterator<Item = DirectiveLocation>,
+        arguments: impl FnOnce(ArgumentsBuilder) -> ArgumentsBuilder,
+    ) -> Self {
+        self.directives.push((
+            name.intern(),
+            locations.into_iter().collect(),
+            arguments(ArgumentsBuilder::default()),
+        ));
+        self
+    }
+
+    pub fn build(self) -> DiagnosticsResult<SDLSchema> {
+        let mut schema = match self.schema {
+            Some(schema) => schema,
+            None => InMemorySchema::build(&[builtins()?], &[])?,
+        };
+
+        // Add all types first, so that they can reference each other
+        for name in &self.scalars {
+            schema.add_scalar(Scalar {
+                name: WithLocation::generated(ScalarName(*name)),
+                is_extension: false,
+                directives: Vec::new(),
+                description: None,
+                hack_source: None,
+            })?;
+        }
+        for (name, values) in &self.enums {
+            let mut seen_values = HashSet::new();
+            for value in values {
+                if !seen_values.insert(value) {
+                    return error(SchemaError::DuplicateEnumValue(*name, *value));
+                }
+            }
+            schema.add_enum(Enum {
+                name: WithLocation::generated(EnumName(*name)),
+                is_extension: false,
+                values: values
+                    .iter()
+                    .map(|value| EnumValue {
+                        value: *value,
+                        location: Location::generated(),
+                        directives: Vec::new(),
+                        is_extension: false,
+                        description: None,
+                    })
+                    .collect(),
+                directives: Vec::new(),
+                description: None,
+                hack_source: None,
+            })?;
+        }
+        for (name, _) in &self.input_objects {
+            schema.add_input_object(InputObject {
+                name: WithLocation::generated(InputObjectName(*name)),
+                fields: ArgumentDefinitions::new(Vec::new()),
+                directives: Vec::new(),
+                description: None,
+                hack_source: None,
+            })?;
+        }
+        for object in &self.objects {
+            schema.add_object(Object {
+                name: WithLocation::generated(ObjectName(object.name)),
+                is_extension: false,
+                fields: Vec::new(),
+                interfaces: Vec::new(),
+                directives: Vec::new(),
+                description: object.description,
+                hack_source: None,
+            })?;
+        }
+        for interface in &self.interfaces {
+            schema.add_interface(Interface {
+                name: WithLocation::generated(InterfaceName(interface.name)),
+                is_extension: false,
+                implementing_interfaces: Vec::new(),
+                implementing_objects: Vec::new(),
+                fields: Vec::new(),
+                directives: Vec::new(),
+                interfaces: Vec::new(),
+                description: interface.description,
+                hack_source: None,
+            })?;
+        }
+        for (name, _) in &self.unions {
+            schema.add_union(Union {
+                name: WithLocation::generated(*name),
+                is_extension: false,
+                members: Vec::new(),
+                directives: Vec::new(),
+                description: None,
+                hack_source: None,
+            })?;
+        }
+
+        // Then resolve the references between them
+        for (name, fields) in &self.input_objects {
+            let id = schema
+                .get_type(*name)
+                .unwrap()
+                .get_input_object_id()
+                .unwrap();
+            let fields = fields.build(&schema)?;
+            schema.set_input_object_args(id, fields)?;
+        }
+        for (name, members) in &self.unions {
+            let id = schema.get_type(*name).unwrap().get_union_id().unwrap();
+            for member in members {
+                let member_id = schema.build_object_id(*member)?;
+                schema.add_member_to_union(id, member_id)?;
+            }
+        }
+        for type_ in self
+            .objects
+            .iter()
+            .chain(&self.interfaces)
+            .chain(&self.type_extensions)
+        {
+            type_.add_to(&mut schema)?;
+        }
+        for (name, locations, arguments) in &self.directives {
+            schema.add_directive(Directive {
+                name: DirectiveName(*name),
+                location: Location::generated(),
+                arguments: arguments.build(&schema)?,
+                locations: locations.clone(),
+                repeatable: false,
+                is_extension: false,
+                description: None,
+                hack_source: None,
+            })?;
+        }
+
+        schema.load_default_root_types();
+        Ok(SDLSchema::InMemory(schema))
+    }
+}
+
+/// The fields and interfaces of an object or interface.
+pub struct TypeBuilder {
+    name: StringKey,
+    description: Option<StringKey>,
+    interfaces: Vec<StringKey>,
+    fields: Vec<FieldBuilder>,
+}
+
+struct FieldBuilder {
+    name: StringKey,
+    type_: String,
+    description: Option<StringKey>,
+    arguments: ArgumentsBuilder,
+}
+
+impl TypeBuilder {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.intern(),
+            description: None,
+            interfaces: Vec::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.intern());
+        self
+    }
+
+    pub fn implements(mut self, interface_name: &str) -> Self {
+        self.interfaces.push(interface_name.intern());
+        self
+    }
+
+    pub fn field(self, name: &str, type_: &str) -> Self {
+        self.field_with_arguments(name, type_, |arguments| arguments)
+    }
+
+    pub fn field_with_arguments(
+        mut self,
+        name: &str,
+        type_: &str,
+        arguments: impl FnOnce(ArgumentsBuilder) -> ArgumentsBuilder,
+    ) -> Self {
+        self.fields.push(FieldBuilder {
+            name: name.intern(),
+            type_: type_.to_string(),
+            description: None,
+            arguments: arguments(ArgumentsBuilder::default()),
+        });
+        self
+    }
+
+    /// Sets the description of the field that was added last.
+    pub fn field_description(mut self, description: &str) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.description = Some(description.intern());
+        }
+        self
+    }
+
+    fn add_to(&self, schema: &mut InMemorySchema) -> DiagnosticsResult<()> {
+        let parent_type = match schema.get_type(self.name) {
+            Some(type_ @ (Type::Object(_) | Type::Interface(_))) => type_,
+            Some(type_) => return error(SchemaError::ExpectedObjectReference(self.name, type_)),
+            None => return error(SchemaError::UndefinedType(self.name)),
+        };
+        let existing_fields = match parent_type {
+            Type::Object(id) => &schema.object(id).fields,
+            Type::Interface(id) => &schema.interface(id).fields,
+            _ => unreachable!(),
+        };
+        let mut field_names = existing_fields
+            .iter()
+            .map(|id| schema.field(*id).name.item)
+            .collect::<HashSet<_>>();
+
+        for field in &self.fields {
+            if !field_names.insert(field.name) {
+                return error(SchemaError::DuplicateField(field.name));
+            }
+            let field_id = schema.add_field(Field {
+                name: WithLocation::generated(field.name),
+                is_extension: false,
+                arguments: field.arguments.build(schema)?,
+                type_: type_reference(schema, &field.type_, false)?,
+                directives: Vec::new(),
+                parent_type: Some(parent_type),
+                description: field.description,
+                hack_source: None,
+            })?;
+            match parent_type {
+                Type::Object(id) => {
+                    schema.add_field_to_object(id, field_id)?;
+                }
+                Type::Interface(id) => {
+                    schema.add_field_to_interface(id, field_id)?;
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        for interface_name in &self.interfaces {
+            let interface_id = match schema.get_type(*interface_name) {
+                Some(Type::Interface(id)) => id,
+                Some(type_) => {
+                    return error(SchemaError::ExpectedInterfaceReference(
+                        *interface_name,
+                        type_,
+                    ));
+                }
+                None => return error(SchemaError::UndefinedType(*interface_name)),
+            };
+            match parent_type {
+                Type::Object(id) => {
+                    schema.add_interface_to_object(id, interface_id)?;
+                    schema.add_implementing_object_to_interface(interface_id, id)?;
+                }
+                Type::Interface(id) => {
+                    schema.add_parent_interface_to_interface(id, interface_id)?;
+                    schema.interfaces[interface_id.as_usize()]
+                        .implementing_interfaces
+                        .push(id);
+                }
+                _ => unreachable!(),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The arguments of a field or directive, or the fields of an input object.
+#[derive(Default)]
+pub struct ArgumentsBuilder {
+    arguments: Vec<(StringKey, String)>,
+}
+
+impl ArgumentsBuilder {
+    pub fn argument(mut self, name: &str, type_: &str) -> Self {
+        self.arguments.push((name.intern(), type_.to_string()));
+        self
+    }
+
+    fn build(&self, schema: &InMemorySchema) -> DiagnosticsResult<ArgumentDefinitions> {
+        let mut names = HashSet::new();
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|(name, type_)| {
+                if !names.insert(*name) {
+                    return error(SchemaError::DuplicateField(*name));
+                }
+                Ok(Argument {
+                    name: ArgumentName(*name),
+                    type_: type_reference(schema, type_, true)?,
+                    default_value: None,
+                    description: None,
+                    directives: Vec::new(),
+                })
+            })
+            .collect::<DiagnosticsResult<Vec<_>>>()?;
+        Ok(ArgumentDefinitions::new(arguments))
+    }
+}
+
+/// Resolves a type in the SDL notation. The types of arguments and input
+/// fields must be input types.
+fn type_reference(
+    schema: &InMemorySchema,
+    notation: &str,
+    is_input: bool,
+) -> DiagnosticsResult<TypeReference<Type>> {
+    let type_name_reference = match parse_type_notation(notation) {
+        Some(type_name_reference) => type_name_reference,
+        None => return error(SchemaError::InvalidTypeNotation(notation.to_string())),
+    };
+    let type_name = type_name_reference.inner();
+    let type_ = match schema.get_type(type_name) {
+        Some(type_) => type_,
+        None => return error(SchemaError::UndefinedType(type_name)),
+    };
+    if is_input && !(type_.is_enum() || type_.is_scalar() || type_.is_input_object()) {
+        return error(SchemaError::ExpectedInputType(type_name));
+    }
+    Ok(type_name_reference.map(|_| type_))
+}
+
+fn parse_type_notation(notation: &str) -> Option<TypeReference<StringKey>> {
+    if let Some(of_type) = notation.strip_suffix('!') {
+        match parse_type_notation(of_type)? {
+            TypeReference::NonNull(_) => None,
+            of_type => Some(TypeReference::NonNull(Box::new(of_type))),
+        }
+    } else if let Some(of_type) = notation
+        .strip_prefix('[')
+        .and_then(|notation| notation.strip_suffix(']'))
+    {
+        Some(TypeReference::List(Box::new(parse_type_notation(of_type)?)))
+    } else {
+        let mut chars = notation.chars();
+        let is_name = chars
+            .next()
+            .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        is_name.then(|| TypeReference::Named(notation.intern()))
+    }
+}
+
+/// The definitions of the builder have no location.
+fn error<T>(error: SchemaError) -> DiagnosticsResult<T> {
+    Err(vec![Diagnostic::error(error, Location::generated())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_schema() {
+        let schema = SchemaBuilder::new()
+            .object("Query", |query| query.field("node", "Node"))
+            .interface("Node", |node| node.field("id", "ID!"))
+            .object("User", |user| {
+                user.implements("Node")
+                    .field("id", "ID!")
+                    .field_with_arguments("friends", "[User!]", |arguments| {
+                        arguments.argument("orderBy", "[Order!]")
+                    })
+            })
+            .enum_("Order", ["NAME", "AGE"])
+            .directive("cached", [DirectiveLocation::Query], |arguments| {
+                arguments.argument("ttl", "Int")
+            })
+            .build()
+            .unwrap();
+
+        let query = schema.query_type().unwrap();
+        assert_eq!(schema.get_type_name(query), "Query".intern());
+        let node = schema.get_type("Node".intern()).unwrap();
+        let user = schema.get_type("User".intern()).unwrap();
+        assert!(schema.is_named_type_subtype_of(user, node));
+
+        let friends = schema.field(schema.named_field(user, "friends".intern()).unwrap());
+        assert_eq!(friends.parent_type, Some(user));
+        assert_eq!(
+            schema.get_type_string(&friends.type_),
+            "[User!]".to_string()
+        );
+        let order_by = friends
+            .arguments
+            .named(ArgumentName("orderBy".intern()))
+            .unwrap();
+        assert_eq!(
+            schema.get_type_string(&order_by.type_),
+            "[Order!]".to_string()
+        );
+        assert!(
+            schema
+                .get_directive(DirectiveName("cached".intern()))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_extend_schema() {
+        let schema = SchemaBuilder::new()
+            .object("Query", |query| query)
+            .build()
+            .unwrap()
+            .unwrap_in_memory_impl();
+        let schema = SchemaBuilder::extend(schema)
+            .scalar("Date")
+            .extend_type("Query", |query| query.field("today", "Date"))
+            .build()
+            .unwrap();
+
+        let query = schema.query_type().unwrap();
+        let today = schema.field(schema.named_field(query, "today".intern()).unwrap());
+        assert_eq!(schema.get_type_string(&today.type_), "Date".to_string());
+    }
+
+    #[test]
+    fn test_invalid_references() {
+        let undefined_type = SchemaBuilder::new()
+            .object("Query", |query| query.field("user", "User"))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            undefined_type[0].message().to_string(),
+            "Reference to undefined type 'User'."
+        );
+
+        let output_argument = SchemaBuilder::new()
+            .object("Query", |query| {
+                query.field_with_arguments("node", "Query", |arguments| {
+                    arguments.argument("query", "Query")
+                })
+            })
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            output_argument[0].message().to_string(),
+            "Expected a field of the input object to be a valid input object, scalar, or enum. Got 'Query'."
+        );
+
+        assert_eq!(parse_type_notation("[[ID]!"), None);
+        assert_eq!(parse_type_notation("ID!!"), None);
+    }
+}
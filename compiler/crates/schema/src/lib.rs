@@ -12,12 +12,17 @@
 #![deny(clippy::all)]
 
 pub mod definitions;
+pub mod diff;
 mod errors;
+mod federation;
 mod field_descriptions;
 mod flatbuffer;
 mod graphql_schema;
 mod in_memory;
+mod memory_stats;
 mod schema;
+mod schema_coordinate;
+mod semantic_non_null;
 pub mod suggestion_list;
 
 use common::DiagnosticsResult;
@@ -45,15 +50,25 @@ pub use definitions::TypeReference;
 pub use definitions::TypeWithFields;
 pub use definitions::Union;
 pub use definitions::UnionID;
+pub use diff::diff;
+pub use diff::SchemaDiff;
 pub use errors::Result;
 pub use errors::SchemaError;
+pub use federation::compose_subgraphs;
 use flatbuffer::FlatBufferSchema;
 pub use flatbuffer::SchemaWrapper;
 pub use graphql_schema::Schema;
 pub use graphql_syntax::DirectiveLocation;
 use graphql_syntax::SchemaDocument;
 pub use graphql_syntax::TypeSystemDefinition;
+pub use in_memory::ArgumentsBuilder;
 pub use in_memory::InMemorySchema;
+pub use in_memory::SchemaBuilder;
+pub use in_memory::TypeBuilder;
+pub use memory_stats::MemoryStatsKind;
+pub use memory_stats::SchemaMemoryStats;
+pub use schema_coordinate::validate_schema_coordinate;
+pub use semantic_non_null::validate_semantic_non_null;
 
 pub use crate::schema::SDLSchema;
 
@@ -71,28 +86,60 @@ pub fn build_schema_with_extensions<T: AsRef<str>, U: AsRef<str>>(
 ) -> DiagnosticsResult<SDLSchema> {
     let mut server_documents = vec![builtins()?];
 
-    let server_schema_document = match server_sdls {
-        [(sdl, source_location)] => {
-            graphql_syntax::parse_schema_document(sdl.as_ref(), *source_location)?
-        }
+    match server_sdls {
+        [(sdl, source_location)] => server_documents.push(graphql_syntax::parse_schema_document(
+            sdl.as_ref(),
+            *source_location,
+        )?),
         _ => {
-            // When the schema is split across multiple files, the individual
-            // files may not be syntactically complete, so we join them together
-            // before parsing.
-
-            // Note that this requires us to use a generates source location key which
-            // means click to definition for schema files will not work.
-            let mut combined_sdl: String = String::new();
-            for (sdl, _) in server_sdls {
-                combined_sdl.push_str(sdl.as_ref());
-                combined_sdl.push('\n');
+            // When the schema is split across multiple files, each file is
+            // parsed on its own so that definitions keep their location in
+            // the file, e.g. for click to definition.
+            let documents = server_sdls
+                .iter()
+                .map(|(sdl, source_location)| {
+                    graphql_syntax::parse_schema_document(sdl.as_ref(), *source_location)
+                })
+                .collect::<DiagnosticsResult<Vec<_>>>();
+            match documents {
+                Ok(documents) => server_documents.extend(documents),
+                Err(_) => {
+                    // The individual files may not be syntactically complete,
+                    // so we join them together before parsing.
+
+                    // Note that this requires us to use a generates source location key which
+                    // means click to definition for schema files will not work.
+                    let mut combined_sdl: String = String::new();
+                    for (sdl, _) in server_sdls {
+                        combined_sdl.push_str(sdl.as_ref());
+                        combined_sdl.push('\n');
+                    }
+                    server_documents.push(graphql_syntax::parse_schema_document(
+                        &combined_sdl,
+                        SourceLocationKey::Generated,
+                    )?);
+                }
             }
-            graphql_syntax::parse_schema_document(&combined_sdl, SourceLocationKey::Generated)?
         }
-    };
+    }
 
-    server_documents.push(server_schema_document);
+    build_schema_from_documents(server_documents, extension_sdls)
+}
 
+/// Builds the client schema of one or more Apollo Federation subgraphs, see
+/// `compose_subgraphs`.
+pub fn build_schema_from_subgraphs_with_extensions<T: AsRef<str>, U: AsRef<str>>(
+    subgraph_sdls: &[(T, SourceLocationKey)],
+    extension_sdls: &[(U, SourceLocationKey)],
+) -> DiagnosticsResult<SDLSchema> {
+    let server_documents = vec![builtins()?, federation::parse_subgraphs(subgraph_sdls)?];
+    build_schema_from_documents(server_documents, extension_sdls)
+}
+
+fn build_schema_from_documents<U: AsRef<str>>(
+    server_documents: Vec<SchemaDocument>,
+    extension_sdls: &[(U, SourceLocationKey)],
+) -> DiagnosticsResult<SDLSchema> {
     let mut client_schema_documents = Vec::new();
     for (extension_sdl, location_key) in extension_sdls {
         client_schema_documents.push(graphql_syntax::parse_schema_document(
@@ -104,8 +151,8 @@ pub fn build_schema_with_extensions<T: AsRef<str>, U: AsRef<str>>(
     SDLSchema::build(&server_documents, &client_schema_documents)
 }
 
-pub fn build_schema_with_flat_buffer(bytes: Vec<u8>) -> SDLSchema {
-    SDLSchema::FlatBuffer(SchemaWrapper::from_vec(bytes))
+pub fn build_schema_with_flat_buffer(bytes: impl AsRef<[u8]> + Send + Sync + 'static) -> SDLSchema {
+    SDLSchema::FlatBuffer(SchemaWrapper::from_bytes(bytes))
 }
 
 pub fn build_schema_from_flat_buffer(bytes: &[u8]) -> DiagnosticsResult<FlatBufferSchema<'_>> {
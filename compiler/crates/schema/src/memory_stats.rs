@@ -0,0 +1,110 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fmt;
+use std::mem::size_of;
+
+use graphql_syntax::ConstantValue;
+
+use crate::definitions::*;
+
+/// An estimate of the memory a schema holds, by kind of definition. The
+/// sizes count the definitions and the heap allocations they own, but not
+/// the interned strings, which are shared with the rest of the compiler.
+#[derive(Debug, Default)]
+pub struct SchemaMemoryStats {
+    pub kinds: Vec<MemoryStatsKind>,
+}
+
+#[derive(Debug)]
+pub struct MemoryStatsKind {
+    pub name: &'static str,
+    pub count: usize,
+    pub bytes: usize,
+}
+
+impl SchemaMemoryStats {
+    pub(crate) fn add(&mut self, name: &'static str, count: usize, bytes: usize) {
+        self.kinds.push(MemoryStatsKind { name, count, bytes });
+    }
+
+    pub fn total_bytes(&self) -> usize {
+        self.kinds.iter().map(|kind| kind.bytes).sum()
+    }
+}
+
+impl fmt::Display for SchemaMemoryStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for kind in &self.kinds {
+            writeln!(f, "  {} {}, {} bytes", kind.count, kind.name, kind.bytes)?;
+        }
+        write!(f, "  {} bytes in total", self.total_bytes())
+    }
+}
+
+pub(crate) fn vec_bytes<T>(vec: &Vec<T>) -> usize {
+    vec.capacity() * size_of::<T>()
+}
+
+pub(crate) fn type_reference_bytes(type_: &TypeReference<Type>) -> usize {
+    match type_ {
+        TypeReference::Named(_) => 0,
+        TypeReference::NonNull(of) | TypeReference::List(of) => {
+            size_of::<TypeReference<Type>>() + type_reference_bytes(of)
+        }
+    }
+}
+
+pub(crate) fn directive_values_bytes(directives: &Vec<DirectiveValue>) -> usize {
+    vec_bytes(directives)
+        + directives
+            .iter()
+            .map(|directive| {
+                vec_bytes(&directive.arguments)
+                    + directive
+                        .arguments
+                        .iter()
+                        .map(|argument| constant_value_bytes(&argument.value))
+                        .sum::<usize>()
+            })
+            .sum::<usize>()
+}
+
+/// The heap allocations of the arguments, which are stored inline in their
+/// field, directive or input object. Their default values are shared, and
+/// counted with `shared_constant_value_bytes`.
+pub(crate) fn arguments_bytes(arguments: &ArgumentDefinitions) -> usize {
+    vec_bytes(&arguments.0)
+        + arguments
+            .iter()
+            .map(|argument| {
+                type_reference_bytes(&argument.type_) + directive_values_bytes(&argument.directives)
+            })
+            .sum::<usize>()
+}
+
+/// The allocation of a value in an `Arc`, with its reference counts.
+pub(crate) fn shared_constant_value_bytes(value: &ConstantValue) -> usize {
+    2 * size_of::<usize>() + size_of::<ConstantValue>() + constant_value_bytes(value)
+}
+
+fn constant_value_bytes(value: &ConstantValue) -> usize {
+    match value {
+        ConstantValue::List(list) => {
+            vec_bytes(&list.items) + list.items.iter().map(constant_value_bytes).sum::<usize>()
+        }
+        ConstantValue::Object(object) => {
+            vec_bytes(&object.items)
+                + object
+                    .items
+                    .iter()
+                    .map(|field| constant_value_bytes(&field.value))
+                    .sum::<usize>()
+        }
+        _ => 0,
+    }
+}
@@ -16,6 +16,7 @@ use crate::definitions::*;
 use crate::flatbuffer::SchemaWrapper;
 use crate::graphql_schema::Schema;
 use crate::in_memory::InMemorySchema;
+use crate::memory_stats::SchemaMemoryStats;
 
 #[derive(Debug)]
 pub enum SDLSchema {
@@ -324,6 +325,21 @@ impl SDLSchema {
         }
     }
 
+    pub fn memory_stats(&self) -> SchemaMemoryStats {
+        match self {
+            SDLSchema::FlatBuffer(schema) => schema.memory_stats(),
+            SDLSchema::InMemory(schema) => schema.memory_stats(),
+        }
+    }
+
+    /// Releases the memory reserved for definitions that were not added. A
+    /// flatbuffer schema reserves none.
+    pub fn shrink_to_fit(&mut self) {
+        if let SDLSchema::InMemory(schema) = self {
+            schema.shrink_to_fit();
+        }
+    }
+
     pub fn unwrap_in_memory_impl(self) -> InMemorySchema {
         match self {
             SDLSchema::FlatBuffer(_schema) => panic!("expected an underlying InMemorySchema"),
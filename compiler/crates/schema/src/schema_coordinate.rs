@@ -0,0 +1,170 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SchemaCoordinate;
+use intern::string_key::StringKey;
+
+use crate::definitions::FieldID;
+use crate::definitions::Type;
+use crate::errors::Result;
+use crate::errors::SchemaError;
+use crate::graphql_schema::Schema;
+
+/// Checks that the element a schema coordinate refers to is defined in the
+/// schema: `Type.member` may be a field, an input field or an enum value.
+pub fn validate_schema_coordinate(
+    schema: &impl Schema,
+    coordinate: SchemaCoordinate,
+) -> Result<()> {
+    match coordinate {
+        SchemaCoordinate::Type(type_name) => get_type(schema, type_name).map(|_| ()),
+        SchemaCoordinate::Member {
+            type_name,
+            member_name,
+        } => {
+            let type_ = get_type(schema, type_name)?;
+            let is_defined = match type_ {
+                Type::Object(_) | Type::Interface(_) | Type::Union(_) => {
+                    schema.named_field(type_, member_name).is_some()
+                }
+                Type::InputObject(id) => schema.input_object(id).fields.contains(member_name),
+                Type::Enum(id) => schema
+                    .enum_(id)
+                    .values
+                    .iter()
+                    .any(|value| value.value == member_name),
+                Type::Scalar(_) => false,
+            };
+            if is_defined {
+                Ok(())
+            } else {
+                Err(SchemaError::UndefinedMember(type_name, member_name))
+            }
+        }
+        SchemaCoordinate::Argument {
+            type_name,
+            field_name,
+            argument_name,
+        } => {
+            let field_id = get_field(schema, type_name, field_name)?;
+            if schema
+                .field(field_id)
+                .arguments
+                .named(argument_name)
+                .is_some()
+            {
+                Ok(())
+            } else {
+                Err(SchemaError::UndefinedArgument(
+                    SchemaCoordinate::Member {
+                        type_name,
+                        member_name: field_name,
+                    },
+                    argument_name.0,
+                ))
+            }
+        }
+        SchemaCoordinate::Directive(directive_name) => schema
+            .get_directive(directive_name)
+            .map(|_| ())
+            .ok_or(SchemaError::UndefinedDirective(directive_name.0)),
+        SchemaCoordinate::DirectiveArgument {
+            directive_name,
+            argument_name,
+        } => {
+            let directive = schema
+                .get_directive(directive_name)
+                .ok_or(SchemaError::UndefinedDirective(directive_name.0))?;
+            if directive.arguments.named(argument_name).is_some() {
+                Ok(())
+            } else {
+                Err(SchemaError::UndefinedArgument(
+                    SchemaCoordinate::Directive(directive_name),
+                    argument_name.0,
+                ))
+            }
+        }
+    }
+}
+
+fn get_type(schema: &impl Schema, type_name: StringKey) -> Result<Type> {
+    schema
+        .get_type(type_name)
+        .ok_or(SchemaError::UndefinedType(type_name))
+}
+
+fn get_field(schema: &impl Schema, type_name: StringKey, field_name: StringKey) -> Result<FieldID> {
+    let type_ = get_type(schema, type_name)?;
+    schema
+        .named_field(type_, field_name)
+        .ok_or(SchemaError::UndefinedMember(type_name, field_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_schema;
+
+    const SCHEMA: &str = r#"
+        directive @cached(ttl: Int) on FIELD
+        type Query { user(id: ID!): User }
+        type User { name(format: String): String }
+        input UserInput { name: String }
+        enum Role { ADMIN }
+        scalar Url
+    "#;
+
+    fn validate(coordinate: &str) -> std::result::Result<(), String> {
+        let schema = build_schema(SCHEMA).unwrap();
+        validate_schema_coordinate(&schema, coordinate.parse().unwrap())
+            .map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn resolves_defined_coordinates() {
+        for coordinate in [
+            "User",
+            "User.name",
+            "User.name(format:)",
+            "UserInput.name",
+            "Role.ADMIN",
+            "@cached",
+            "@cached(ttl:)",
+            "@include(if:)",
+        ] {
+            assert_eq!(validate(coordinate), Ok(()), "{}", coordinate);
+        }
+    }
+
+    #[test]
+    fn reports_undefined_coordinates() {
+        assert_eq!(
+            validate("Viewer.name"),
+            Err("Reference to undefined type 'Viewer'.".to_string())
+        );
+        assert_eq!(
+            validate("Role.USER"),
+            Err("Type 'Role' has no field or value named 'USER'.".to_string())
+        );
+        assert_eq!(
+            validate("Url.host"),
+            Err("Type 'Url' has no field or value named 'host'.".to_string())
+        );
+        assert_eq!(
+            validate("Query.user(name:)"),
+            Err("'Query.user' has no argument named 'name'.".to_string())
+        );
+        assert_eq!(
+            validate("@cached(scope:)"),
+            Err("'@cached' has no argument named 'scope'.".to_string())
+        );
+        assert_eq!(
+            validate("@live"),
+            Err("Reference to undefined directive 'live'.".to_string())
+        );
+    }
+}
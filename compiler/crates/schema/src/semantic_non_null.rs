@@ -0,0 +1,122 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::Diagnostic;
+use common::DiagnosticsResult;
+use intern::string_key::Intern;
+
+use crate::definitions::Type;
+use crate::definitions::TypeReference;
+use crate::errors::SchemaError;
+use crate::graphql_schema::Schema;
+use crate::SDLSchema;
+
+/// Checks the levels of the `@semanticNonNull` directives of a schema with
+/// semantic nullability support: each level must exist in the type of its
+/// field and be nullable.
+pub fn validate_semantic_non_null(schema: &SDLSchema) -> DiagnosticsResult<()> {
+    let mut errors = Vec::new();
+    for field in schema.fields() {
+        let levels = match field.semantic_non_null_levels() {
+            Some(levels) => levels,
+            None => continue,
+        };
+        let type_name = field
+            .parent_type
+            .map_or_else(|| "(unknown)".intern(), |type_| schema.get_type_name(type_));
+        let nullable_levels = nullable_levels(&field.type_);
+        for level in levels {
+            let error = match usize::try_from(level)
+                .ok()
+                .and_then(|index| nullable_levels.get(index))
+            {
+                None => SchemaError::InvalidSemanticNonNullLevel(
+                    type_name,
+                    field.name.item,
+                    level,
+                    nullable_levels.len(),
+                ),
+                Some(false) => {
+                    SchemaError::SemanticNonNullOnNonNullType(type_name, field.name.item, level)
+                }
+                Some(true) => continue,
+            };
+            errors.push(Diagnostic::error(error, field.name.location));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Whether each level of the type is nullable, from the type itself to its
+/// innermost list items.
+fn nullable_levels(type_: &TypeReference<Type>) -> Vec<bool> {
+    let mut levels = vec![!type_.is_non_null()];
+    let mut current = type_.nullable_type();
+    while let TypeReference::List(of) = current {
+        levels.push(!of.is_non_null());
+        current = of.nullable_type();
+    }
+    levels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_schema;
+
+    fn validate(sdl: &str) -> Vec<String> {
+        let schema = build_schema(&format!(
+            "directive @semanticNonNull(levels: [Int] = [0]) on FIELD_DEFINITION\n{}",
+            sdl
+        ))
+        .unwrap();
+        validate_semantic_non_null(&schema)
+            .err()
+            .unwrap_or_default()
+            .iter()
+            .map(|error| error.message().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn accepts_nullable_levels() {
+        assert!(
+            validate(
+                r#"
+                type Query {
+                    name: String @semanticNonNull
+                    names: [String] @semanticNonNull(levels: [0, 1])
+                    ids: [ID]! @semanticNonNull(levels: [1])
+                }
+                "#
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_levels() {
+        assert_eq!(
+            validate(
+                r#"
+                type Query {
+                    name: String! @semanticNonNull
+                    names: [String] @semanticNonNull(levels: [2])
+                }
+                "#
+            ),
+            vec![
+                "Field 'Query.name' is already non-null at level 0, only nullable levels can be marked with @semanticNonNull.",
+                "Field 'Query.names' has no level 2 to mark with @semanticNonNull, its type has 2 levels.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,44 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use common::TextSource;
+use fixture_tests::Fixture;
+use graphql_cli::DiagnosticPrinter;
+use schema::build_schema_with_extensions;
+
+const FILE_SEPARATOR: &str = "%file%";
+
+pub fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let files = fixture
+        .content
+        .split(FILE_SEPARATOR)
+        .enumerate()
+        .map(|(index, sdl)| {
+            (
+                sdl,
+                SourceLocationKey::standalone(&format!("{}:{}", fixture.file_name, index)),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    build_schema_with_extensions::<_, &str>(&files, &[])
+        .map(|_| "OK".to_string())
+        .map_err(|diagnostics| {
+            let printer = DiagnosticPrinter::new(|location_key| {
+                files
+                    .iter()
+                    .find(|(_, key)| *key == location_key)
+                    .map(|(sdl, _)| TextSource::from_whole_document(sdl.to_string()))
+            });
+            diagnostics
+                .iter()
+                .map(|diagnostic| printer.diagnostic_to_string(diagnostic))
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        })
+}
@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<d868844bc96e1c453c69299655c14e00>>
+ */
+
+mod build_schema_from_files;
+
+use build_schema_from_files::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn conflicting_definitions() {
+    let input = include_str!("build_schema_from_files/fixtures/conflicting-definitions.graphql");
+    let expected = include_str!("build_schema_from_files/fixtures/conflicting-definitions.expected");
+    test_fixture(transform_fixture, "conflicting-definitions.graphql", "build_schema_from_files/fixtures/conflicting-definitions.expected", input, expected);
+}
+
+#[test]
+fn identical_definitions() {
+    let input = include_str!("build_schema_from_files/fixtures/identical-definitions.graphql");
+    let expected = include_str!("build_schema_from_files/fixtures/identical-definitions.expected");
+    test_fixture(transform_fixture, "identical-definitions.graphql", "build_schema_from_files/fixtures/identical-definitions.expected", input, expected);
+}
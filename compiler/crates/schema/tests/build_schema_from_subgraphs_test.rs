@@ -0,0 +1,41 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<2ae23b3f243e85a702696a999d4f870c>>
+ */
+
+mod build_schema_from_subgraphs;
+
+use build_schema_from_subgraphs::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[test]
+fn federation_v1_subgraphs() {
+    let input = include_str!("build_schema_from_subgraphs/fixtures/federation-v1-subgraphs.graphql");
+    let expected = include_str!("build_schema_from_subgraphs/fixtures/federation-v1-subgraphs.expected");
+    test_fixture(transform_fixture, "federation-v1-subgraphs.graphql", "build_schema_from_subgraphs/fixtures/federation-v1-subgraphs.expected", input, expected);
+}
+
+#[test]
+fn federation_v2_subgraphs() {
+    let input = include_str!("build_schema_from_subgraphs/fixtures/federation-v2-subgraphs.graphql");
+    let expected = include_str!("build_schema_from_subgraphs/fixtures/federation-v2-subgraphs.expected");
+    test_fixture(transform_fixture, "federation-v2-subgraphs.graphql", "build_schema_from_subgraphs/fixtures/federation-v2-subgraphs.expected", input, expected);
+}
+
+#[test]
+fn invalid_subgraph_syntax() {
+    let input = include_str!("build_schema_from_subgraphs/fixtures/invalid-subgraph-syntax.graphql");
+    let expected = include_str!("build_schema_from_subgraphs/fixtures/invalid-subgraph-syntax.expected");
+    test_fixture(transform_fixture, "invalid-subgraph-syntax.graphql", "build_schema_from_subgraphs/fixtures/invalid-subgraph-syntax.expected", input, expected);
+}
+
+#[test]
+fn single_subgraph_with_schema_definition() {
+    let input = include_str!("build_schema_from_subgraphs/fixtures/single-subgraph-with-schema-definition.graphql");
+    let expected = include_str!("build_schema_from_subgraphs/fixtures/single-subgraph-with-schema-definition.expected");
+    test_fixture(transform_fixture, "single-subgraph-with-schema-definition.graphql", "build_schema_from_subgraphs/fixtures/single-subgraph-with-schema-definition.expected", input, expected);
+}
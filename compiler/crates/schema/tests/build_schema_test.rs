@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<8634e6fbc71207f6f4bb9079d4bea17a>>
+ * @generated SignedSource<<38a20521e34198b039ee662f45e1eb5e>>
  */
 
 mod build_schema;
@@ -40,6 +40,13 @@ fn extend_object_before_define() {
     test_fixture(transform_fixture, "extend-object-before-define.graphql", "build_schema/fixtures/extend-object-before-define.expected", input, expected);
 }
 
+#[test]
+fn extend_schema() {
+    let input = include_str!("build_schema/fixtures/extend-schema.graphql");
+    let expected = include_str!("build_schema/fixtures/extend-schema.expected");
+    test_fixture(transform_fixture, "extend-schema.graphql", "build_schema/fixtures/extend-schema.expected", input, expected);
+}
+
 #[test]
 fn field_descriptions() {
     let input = include_str!("build_schema/fixtures/field-descriptions.graphql");
@@ -68,6 +75,13 @@ fn invalid_duplicate_query_operation() {
     test_fixture(transform_fixture, "invalid-duplicate-query-operation.graphql", "build_schema/fixtures/invalid-duplicate-query-operation.expected", input, expected);
 }
 
+#[test]
+fn invalid_enum_extension_duplicated_value() {
+    let input = include_str!("build_schema/fixtures/invalid-enum-extension-duplicated-value.graphql");
+    let expected = include_str!("build_schema/fixtures/invalid-enum-extension-duplicated-value.expected");
+    test_fixture(transform_fixture, "invalid-enum-extension-duplicated-value.graphql", "build_schema/fixtures/invalid-enum-extension-duplicated-value.expected", input, expected);
+}
+
 #[test]
 fn invalid_extension_implements_noninterface() {
     let input = include_str!("build_schema/fixtures/invalid-extension-implements-noninterface.graphql");
@@ -138,6 +152,13 @@ fn invalid_type_reference() {
     test_fixture(transform_fixture, "invalid-type-reference.graphql", "build_schema/fixtures/invalid-type-reference.expected", input, expected);
 }
 
+#[test]
+fn invalid_union_extension_server_type() {
+    let input = include_str!("build_schema/fixtures/invalid-union-extension-server-type.graphql");
+    let expected = include_str!("build_schema/fixtures/invalid-union-extension-server-type.expected");
+    test_fixture(transform_fixture, "invalid-union-extension-server-type.graphql", "build_schema/fixtures/invalid-union-extension-server-type.expected", input, expected);
+}
+
 #[test]
 fn kitchen_sink() {
     let input = include_str!("build_schema/fixtures/kitchen-sink.graphql");
@@ -13,3 +13,5 @@ GITHUB_ROOT=$(dirname "$SCRIPT_DIR")
 
 cd "$GITHUB_ROOT"/compiler || exit
 cargo run --bin fixture_tests_bin --release --  $(cat "$GITHUB_ROOT"/compiler/fixture_dirs.txt)
+# The tests of the harness use the harness, like the tests of its users.
+cargo run --bin relay_fixture_harness_bin --release -- crates/relay-fixture-harness/tests/required_directive
@@ -0,0 +1,185 @@
+/**
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+import * as http from 'http';
+import * as https from 'https';
+import {
+  EventEmitter,
+  TextDocumentContentProvider,
+  Uri,
+  ViewColumn,
+  window,
+} from 'vscode';
+import {RequestType} from 'vscode-languageclient';
+import {getConfig} from '../config';
+import {RelayExtensionContext} from '../context';
+
+type Variable = {
+  name: string;
+  type: string;
+};
+
+type GraphQLExecuteQueryParams = {
+  text: string;
+  documentPath: string;
+};
+
+// Resolves the full text of an operation, with the fragments it spreads
+const GraphQLExecuteQuery = new RequestType<
+  GraphQLExecuteQueryParams,
+  string,
+  void
+>('graphql/executeQuery');
+
+const RESPONSE_URI = Uri.parse('relay-response:Response.json');
+
+/**
+ * Read-only documents showing the response of the last executed operation.
+ */
+class ResponseContentProvider implements TextDocumentContentProvider {
+  private response = '';
+
+  private onDidChangeEmitter = new EventEmitter<Uri>();
+
+  onDidChange = this.onDidChangeEmitter.event;
+
+  provideTextDocumentContent(): string {
+    return this.response;
+  }
+
+  setResponse(response: string): void {
+    this.response = response;
+    this.onDidChangeEmitter.fire(RESPONSE_URI);
+  }
+}
+
+export const responseContentProvider = new ResponseContentProvider();
+
+export const RESPONSE_SCHEME = RESPONSE_URI.scheme;
+
+/**
+ * Executes the operation sent with the execute code lens of an operation
+ * against the `relay.executeOperation.endpoint`, and shows the response.
+ */
+export async function handleExecuteOperationCommand(
+  context: RelayExtensionContext,
+  uri: string,
+  text: string,
+  variables: Variable[],
+): Promise<void> {
+  const {client} = context;
+  if (client == null) {
+    return;
+  }
+
+  const {endpoint, headers} = getConfig().executeOperation;
+  if (endpoint == null) {
+    window.showErrorMessage(
+      'Set `relay.executeOperation.endpoint` to the URL of a GraphQL endpoint to execute operations.',
+    );
+    return;
+  }
+
+  const variableValues = await promptForVariables(variables);
+  if (variableValues == null) {
+    return;
+  }
+
+  try {
+    const query = await client.sendRequest(GraphQLExecuteQuery, {
+      text,
+      documentPath: Uri.parse(uri).fsPath,
+    });
+    const response = await postJSON(endpoint, headers, {
+      query,
+      variables: variableValues,
+    });
+    responseContentProvider.setResponse(formatResponse(response));
+    await window.showTextDocument(RESPONSE_URI, {
+      viewColumn: ViewColumn.Beside,
+      preserveFocus: true,
+      preview: true,
+    });
+  } catch (error) {
+    window.showErrorMessage(`Failed to execute the operation: ${error}`);
+  }
+}
+
+/**
+ * Asks for the value of each variable, as JSON. Returns `null` if the user
+ * cancels any of the prompts.
+ */
+async function promptForVariables(
+  variables: Variable[],
+): Promise<Record<string, unknown> | null> {
+  const values: Record<string, unknown> = {};
+  for (const {name, type} of variables) {
+    const input = await window.showInputBox({
+      title: `$${name}: ${type}`,
+      prompt: 'Value as JSON. Strings can be entered without quotes.',
+      ignoreFocusOut: true,
+    });
+    if (input == null) {
+      return null;
+    }
+    // Leave out nullable variables without a value
+    if (input !== '' || type.endsWith('!')) {
+      values[name] = parseVariableValue(input);
+    }
+  }
+  return values;
+}
+
+function parseVariableValue(input: string): unknown {
+  try {
+    return JSON.parse(input);
+  } catch {
+    return input;
+  }
+}
+
+function formatResponse(response: string): string {
+  try {
+    return JSON.stringify(JSON.parse(response), null, 2);
+  } catch {
+    return response;
+  }
+}
+
+function postJSON(
+  endpoint: string,
+  headers: Record<string, string>,
+  body: unknown,
+): Promise<string> {
+  const url = new URL(endpoint);
+  const request = url.protocol === 'https:' ? https.request : http.request;
+  const data = JSON.stringify(body);
+
+  return new Promise((resolve, reject) => {
+    const clientRequest = request(
+      url,
+      {
+        method: 'POST',
+        headers: {
+          'Content-Type': 'application/json',
+          'Content-Length': Buffer.byteLength(data),
+          ...headers,
+        },
+      },
+      response => {
+        let responseBody = '';
+        response.setEncoding('utf8');
+        response.on('data', chunk => {
+          responseBody += chunk;
+        });
+        response.on('end', () => resolve(responseBody));
+      },
+    );
+    clientRequest.on('error', reject);
+    clientRequest.end(data);
+  });
+}
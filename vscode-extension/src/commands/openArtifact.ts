@@ -0,0 +1,20 @@
+/**
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+import {Uri, window} from 'vscode';
+import {RelayExtensionContext} from '../context';
+
+/**
+ * Opens the generated artifact sent with the artifact code lens of an
+ * operation or fragment.
+ */
+export function handleOpenArtifactCommand(
+  _context: RelayExtensionContext,
+  uri: string,
+): void {
+  window.showTextDocument(Uri.parse(uri));
+}
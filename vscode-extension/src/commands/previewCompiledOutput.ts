@@ -0,0 +1,110 @@
+/**
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+import * as path from 'path';
+import {
+  EventEmitter,
+  TextDocumentContentProvider,
+  Uri,
+  ViewColumn,
+  window,
+} from 'vscode';
+import {RequestType, TextDocumentPositionParams} from 'vscode-languageclient';
+import {RelayExtensionContext} from '../context';
+
+type CompiledArtifact = {
+  path: string;
+  content: string;
+};
+
+type PreviewCompiledOutputResult = {
+  definitionName: string;
+  artifacts: CompiledArtifact[];
+};
+
+// Compiles the operation or fragment at the position, without writing its artifacts
+const PreviewCompiledOutput = new RequestType<
+  TextDocumentPositionParams,
+  PreviewCompiledOutputResult,
+  void
+>('relay/previewCompiledOutput');
+
+export const COMPILED_OUTPUT_SCHEME = 'relay-compiled-output';
+
+/**
+ * Read-only documents showing the artifacts that would be generated for a
+ * definition, keyed by the path of the document.
+ */
+class CompiledOutputContentProvider implements TextDocumentContentProvider {
+  private outputs = new Map<string, string>();
+
+  private onDidChangeEmitter = new EventEmitter<Uri>();
+
+  onDidChange = this.onDidChangeEmitter.event;
+
+  provideTextDocumentContent(uri: Uri): string {
+    return this.outputs.get(uri.path) ?? '';
+  }
+
+  setOutput(uri: Uri, output: string): void {
+    this.outputs.set(uri.path, output);
+    this.onDidChangeEmitter.fire(uri);
+  }
+}
+
+export const compiledOutputContentProvider =
+  new CompiledOutputContentProvider();
+
+/**
+ * Shows, beside the editor, the artifacts the compiler would generate for the
+ * operation or fragment under the cursor, including the unsaved changes.
+ */
+export async function handlePreviewCompiledOutputCommand(
+  context: RelayExtensionContext,
+): Promise<void> {
+  const {client} = context;
+  const editor = window.activeTextEditor;
+  if (client == null || editor == null) {
+    return;
+  }
+
+  try {
+    const {definitionName, artifacts} = await client.sendRequest(
+      PreviewCompiledOutput,
+      client.code2ProtocolConverter.asTextDocumentPositionParams(
+        editor.document,
+        editor.selection.active,
+      ),
+    );
+    if (artifacts.length === 0) {
+      window.showInformationMessage(
+        `No artifacts are generated for ${definitionName}.`,
+      );
+      return;
+    }
+
+    // Name the document like the artifacts, so that it gets their language
+    const extension = path.extname(artifacts[0].path);
+    const uri = Uri.from({
+      scheme: COMPILED_OUTPUT_SCHEME,
+      path: `${definitionName}.compiled${extension}`,
+    });
+    compiledOutputContentProvider.setOutput(
+      uri,
+      artifacts
+        .map(artifact => `// ${artifact.path}\n\n${artifact.content}`)
+        .join('\n'),
+    );
+    await window.showTextDocument(uri, {
+      viewColumn: ViewColumn.Beside,
+      preserveFocus: true,
+      preview: true,
+    });
+  } catch (error) {
+    window.showErrorMessage(`Failed to compile the definition: ${error}`);
+  }
+}
@@ -5,10 +5,22 @@
  * LICENSE file in the root directory of this source tree.
  */
 
-import {commands} from 'vscode';
+import {commands, workspace} from 'vscode';
 import {RelayExtensionContext} from '../context';
+import {
+  handleExecuteOperationCommand,
+  responseContentProvider,
+  RESPONSE_SCHEME,
+} from './executeOperation';
+import {handleOpenArtifactCommand} from './openArtifact';
+import {
+  compiledOutputContentProvider,
+  COMPILED_OUTPUT_SCHEME,
+  handlePreviewCompiledOutputCommand,
+} from './previewCompiledOutput';
 import {handleRestartLanguageServerCommand} from './restart';
 import {handleShowOutputCommand} from './showOutput';
+import {handleShowReferencesCommand} from './showReferences';
 import {handleStartCompilerCommand} from './startCompiler';
 import {handleStopCompilerCommand} from './stopCompiler';
 
@@ -30,5 +42,29 @@ export function registerCommands(context: RelayExtensionContext) {
       'relay.showOutput',
       handleShowOutputCommand.bind(null, context),
     ),
+    commands.registerCommand(
+      'relay.showReferences',
+      handleShowReferencesCommand.bind(null, context),
+    ),
+    commands.registerCommand(
+      'relay.openArtifact',
+      handleOpenArtifactCommand.bind(null, context),
+    ),
+    commands.registerCommand(
+      'relay.executeOperation',
+      handleExecuteOperationCommand.bind(null, context),
+    ),
+    commands.registerCommand(
+      'relay.previewCompiledOutput',
+      handlePreviewCompiledOutputCommand.bind(null, context),
+    ),
+    workspace.registerTextDocumentContentProvider(
+      RESPONSE_SCHEME,
+      responseContentProvider,
+    ),
+    workspace.registerTextDocumentContentProvider(
+      COMPILED_OUTPUT_SCHEME,
+      compiledOutputContentProvider,
+    ),
   );
 }
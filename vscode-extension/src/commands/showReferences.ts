@@ -0,0 +1,34 @@
+/**
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+import {commands} from 'vscode';
+import {Location, Position} from 'vscode-languageclient';
+import {RelayExtensionContext} from '../context';
+
+/**
+ * Shows the locations sent with the usages code lens of a fragment in the
+ * references peek view.
+ */
+export function handleShowReferencesCommand(
+  context: RelayExtensionContext,
+  uri: string,
+  position: Position,
+  locations: Location[],
+): void {
+  const {client} = context;
+  if (client == null) {
+    return;
+  }
+
+  const converter = client.protocol2CodeConverter;
+  commands.executeCommand(
+    'editor.action.showReferences',
+    converter.asUri(uri),
+    converter.asPosition(position),
+    locations.map(location => converter.asLocation(location)),
+  );
+}
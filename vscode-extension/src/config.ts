@@ -14,6 +14,17 @@ export type Config = {
   lspOutputLevel: string;
   compilerOutpuLevel: string;
   autoStartCompiler: boolean;
+  inlayHints: {
+    fieldTypes: boolean;
+    typeConditions: boolean;
+  };
+  completion: {
+    requiredSelections: boolean;
+  };
+  executeOperation: {
+    endpoint: string | null;
+    headers: Record<string, string>;
+  };
 };
 
 export function getConfig(scope?: ConfigurationScope): Config {
@@ -26,5 +37,17 @@ export function getConfig(scope?: ConfigurationScope): Config {
     compilerOutpuLevel: configuration.get('compilerOutputLevel') ?? 'info',
     rootDirectory: configuration.get('rootDirectory') ?? null,
     autoStartCompiler: configuration.get('autoStartCompiler') ?? false,
+    inlayHints: {
+      fieldTypes: configuration.get('inlayHints.fieldTypes') ?? true,
+      typeConditions: configuration.get('inlayHints.typeConditions') ?? true,
+    },
+    completion: {
+      requiredSelections:
+        configuration.get('completion.requiredSelections') ?? true,
+    },
+    executeOperation: {
+      endpoint: configuration.get('executeOperation.endpoint') ?? null,
+      headers: configuration.get('executeOperation.headers') ?? {},
+    },
   };
 }
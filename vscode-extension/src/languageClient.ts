@@ -51,10 +51,17 @@ export function createAndStartLanguageClient(context: RelayExtensionContext) {
       {scheme: 'file', language: 'typescript'},
       {scheme: 'file', language: 'typescriptreact'},
       {scheme: 'file', language: 'javascriptreact'},
+      {scheme: 'file', language: 'vue'},
+      {scheme: 'file', language: 'svelte'},
     ],
 
     outputChannel: context.lspOutputChannel,
 
+    initializationOptions: {
+      inlayHints: config.inlayHints,
+      completion: config.completion,
+    },
+
     // Since we use stderr for debug logs, the "Something went wrong" popup
     // in VSCode shows up a lot. This tells vscode not to show it in any case.
     revealOutputChannelOn: RevealOutputChannelOn.Never,
@@ -9,6 +9,7 @@ import {Disposable} from 'vscode';
 import {
   StaticFeature,
   InitializeParams,
+  NotificationType,
   RequestType,
 } from 'vscode-languageclient';
 import {RelayExtensionContext} from './context';
@@ -49,6 +50,50 @@ export type ShowStatusParams = {
   actions?: ShowStatusMessageActionItem[];
 };
 
+// Sent by the LSP Server when it starts or finishes compiling.
+// The source of truth is the `RelayStatusParams` in
+// https://github.com/facebook/relay/blob/main/compiler/crates/relay-lsp/src/status_updater.rs
+export type RelayStatusParams = {
+  state: 'idle' | 'compiling' | 'error';
+  errorCount: number;
+};
+
+// The last status message, and error count, so that the status bar can be
+// rendered again when either of them changes.
+let lastShowStatusParams: ShowStatusParams | undefined;
+let lastErrorCount = 0;
+
+function getErrorCountText(errorCount: number): string {
+  if (errorCount === 0) {
+    return '';
+  }
+  return ` $(error) ${errorCount}`;
+}
+
+function renderStatusBar(context: RelayExtensionContext): void {
+  if (lastShowStatusParams == null) {
+    return;
+  }
+
+  const icon = getStatusBarIcon(lastShowStatusParams);
+  const text = getStatusBarText(lastShowStatusParams);
+  const tooltipText = getStatusBarTooltip(lastShowStatusParams);
+
+  if (text) {
+    const textWithIcon = `$(${icon}) ${text}${getErrorCountText(
+      lastErrorCount,
+    )}`;
+
+    context.statusBar.text = textWithIcon;
+    context.statusBar.tooltip =
+      lastErrorCount > 0
+        ? `${tooltipText ?? ''}\n${lastErrorCount} Relay error(s)`.trim()
+        : tooltipText;
+
+    context.statusBar.show();
+  }
+}
+
 function getStatusBarText(params: ShowStatusParams): string | undefined {
   if (params.shortMessage) {
     return params.shortMessage;
@@ -102,18 +147,18 @@ export function handleShowStatusMethod(
   context: RelayExtensionContext,
   params: ShowStatusParams,
 ): void {
-  const icon = getStatusBarIcon(params);
-  const text = getStatusBarText(params);
-  const tooltipText = getStatusBarTooltip(params);
-
-  if (text) {
-    const textWithIcon = `$(${icon}) ${text}`;
-
-    context.statusBar.text = textWithIcon;
-    context.statusBar.tooltip = tooltipText;
+  lastShowStatusParams = params;
+  renderStatusBar(context);
+}
 
-    context.statusBar.show();
-  }
+// The relay/status notification carries the number of errors reported by
+// the LSP Server, which is shown next to the status message.
+export function handleRelayStatusNotification(
+  context: RelayExtensionContext,
+  params: RelayStatusParams,
+): void {
+  lastErrorCount = params.errorCount;
+  renderStatusBar(context);
 }
 
 // This StaticFeature is solely responsible for intercepting
@@ -129,6 +174,8 @@ export class LSPStatusBarFeature implements StaticFeature {
 
   private disposable: Disposable | undefined;
 
+  private relayStatusDisposable: Disposable | undefined;
+
   constructor(context: RelayExtensionContext) {
     this.context = context;
   }
@@ -145,9 +192,16 @@ export class LSPStatusBarFeature implements StaticFeature {
         handleShowStatusMethod(this.context, params);
       },
     );
+    this.relayStatusDisposable = this.context.client?.onNotification(
+      new NotificationType<RelayStatusParams>('relay/status'),
+      params => {
+        handleRelayStatusNotification(this.context, params);
+      },
+    );
   }
 
   dispose(): void {
     this.disposable?.dispose();
+    this.relayStatusDisposable?.dispose();
   }
 }
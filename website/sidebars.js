@@ -225,7 +225,10 @@ module.exports = {
         'principles-and-architecture/runtime-architecture',
         'principles-and-architecture/videos',
       ],
-      'Error reference': ['error-reference/unknown-field'],
+      'Error reference': [
+        'error-reference/unknown-field',
+        'error-reference/error-codes',
+      ],
     },
     'community/learning-resources',
     'glossary/glossary',